@@ -0,0 +1,46 @@
+-- Migration: Season Price Calendar
+-- Date: 2026-10-17
+-- Description: Named seasons with date ranges per year and per-room nightly prices.
+--              Replaces the single Hauptsaison window of pricing_settings
+--              (which stays as fallback for nights outside every season).
+
+-- ============================================================================
+-- 1. SEASONS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS seasons (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    year INTEGER NOT NULL,
+    start_date DATE NOT NULL,           -- first night of the season
+    end_date DATE NOT NULL,             -- last night of the season (inclusive)
+    priority INTEGER NOT NULL DEFAULT 0, -- higher wins when seasons overlap
+    color VARCHAR(20),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT uq_season_name_year UNIQUE (name, year),
+    CONSTRAINT chk_season_dates CHECK (end_date >= start_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_seasons_year ON seasons(year);
+CREATE INDEX IF NOT EXISTS idx_seasons_dates ON seasons(start_date, end_date);
+
+-- ============================================================================
+-- 2. ROOM PRICES PER SEASON
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS room_season_prices (
+    id SERIAL PRIMARY KEY,
+    room_id INTEGER NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    season_id INTEGER NOT NULL REFERENCES seasons(id) ON DELETE CASCADE,
+    price_per_night NUMERIC(10, 2) NOT NULL CHECK (price_per_night >= 0),
+
+    CONSTRAINT uq_room_season UNIQUE (room_id, season_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_room_season_prices_room ON room_season_prices(room_id);
+
+COMMENT ON TABLE seasons IS 'Season price calendar: named date ranges per year (e.g. Ostern 2026)';
+COMMENT ON TABLE room_season_prices IS 'Nightly room price for a season; rooms without entry use nebensaison_preis/hauptsaison_preis';
@@ -0,0 +1,39 @@
+-- Migration: Occupancy-based Pricing
+-- Date: 2026-10-17
+-- Description: Per-room base occupancy, surcharge per extra adult and age-banded
+--              child prices. The occupancy share of a booking is stored as snapshot.
+
+-- ============================================================================
+-- 1. ROOM OCCUPANCY RULES
+-- ============================================================================
+
+-- basis_belegung: persons included in the room price (NULL = no occupancy pricing)
+-- aufpreis_erwachsener: surcharge per night for every adult above basis_belegung
+ALTER TABLE rooms ADD COLUMN IF NOT EXISTS basis_belegung INTEGER;
+ALTER TABLE rooms ADD COLUMN IF NOT EXISTS aufpreis_erwachsener NUMERIC(10, 2);
+
+-- ============================================================================
+-- 2. CHILD PRICES PER AGE BAND
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS room_child_prices (
+    id SERIAL PRIMARY KEY,
+    room_id INTEGER NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    alter_von INTEGER NOT NULL,          -- age on check-in date, inclusive
+    alter_bis INTEGER NOT NULL,          -- inclusive
+    preis_pro_nacht NUMERIC(10, 2) NOT NULL,
+
+    CONSTRAINT chk_child_age_band CHECK (alter_von >= 0 AND alter_bis >= alter_von),
+    CONSTRAINT chk_child_price CHECK (preis_pro_nacht >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_room_child_prices_room ON room_child_prices(room_id);
+
+-- ============================================================================
+-- 3. BOOKING SNAPSHOT
+-- ============================================================================
+
+-- Occupancy surcharges (extra adults + children), already contained in grundpreis
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS belegung_preis NUMERIC(10, 2);
+
+COMMENT ON TABLE room_child_prices IS 'Nightly child price per age band for persons above the base occupancy of a room';
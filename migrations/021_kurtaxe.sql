@@ -0,0 +1,37 @@
+-- Migration: Kurtaxe (tourist tax)
+-- Date: 2026-10-17
+-- Description: Kurtaxe rate per municipality (rooms.ort), charged per person and night.
+--              Children below an age limit and business travellers can be exempt.
+--              The Kurtaxe of a booking is stored as snapshot for the monthly report.
+
+-- ============================================================================
+-- 1. RATES PER MUNICIPALITY
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS kurtaxe_rates (
+    id SERIAL PRIMARY KEY,
+    ort VARCHAR(100) NOT NULL UNIQUE,        -- matches rooms.ort
+    betrag_pro_nacht NUMERIC(10, 2) NOT NULL, -- per liable person and night
+    befreit_unter_alter INTEGER NOT NULL DEFAULT 0, -- persons younger than this (on check-in) are exempt
+    geschaeftsreise_befreit BOOLEAN NOT NULL DEFAULT TRUE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_kurtaxe_betrag CHECK (betrag_pro_nacht >= 0),
+    CONSTRAINT chk_kurtaxe_alter CHECK (befreit_unter_alter >= 0)
+);
+
+-- ============================================================================
+-- 2. BOOKING SNAPSHOT
+-- ============================================================================
+
+-- Business trip: exempt from Kurtaxe if the municipality allows it
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS geschaeftsreise BOOLEAN DEFAULT FALSE;
+
+-- Kurtaxe is a pass-through levy: NOT contained in gesamtpreis, no VAT, no discounts
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS kurtaxe NUMERIC(10, 2);
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS kurtaxe_personen INTEGER;  -- liable persons
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS kurtaxe_befreit INTEGER;   -- exempt persons
+
+COMMENT ON TABLE kurtaxe_rates IS 'Kurtaxe per person and night for each municipality (rooms.ort)';
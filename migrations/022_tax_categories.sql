@@ -0,0 +1,38 @@
+-- Migration: Tax Categories
+-- Date: 2026-10-17
+-- Description: VAT per tax category instead of one blanket rate.
+--              beherbergung = accommodation (7%), standard = other services (19%),
+--              steuerfrei = not taxable (e.g. Kurtaxe). Rates are configured in pricing_settings.
+
+-- ============================================================================
+-- 1. RATES
+-- ============================================================================
+
+ALTER TABLE pricing_settings ADD COLUMN IF NOT EXISTS mwst_beherbergung DOUBLE PRECISION DEFAULT 7.0;
+ALTER TABLE pricing_settings ADD COLUMN IF NOT EXISTS mwst_standard DOUBLE PRECISION DEFAULT 19.0;
+
+-- ============================================================================
+-- 2. CATEGORY PER ROOM / SERVICE
+-- ============================================================================
+
+ALTER TABLE rooms ADD COLUMN IF NOT EXISTS steuerkategorie VARCHAR(20) DEFAULT 'beherbergung';
+ALTER TABLE service_templates ADD COLUMN IF NOT EXISTS steuerkategorie VARCHAR(20) DEFAULT 'standard';
+
+-- NULL = category of the linked template (or standard)
+ALTER TABLE additional_services ADD COLUMN IF NOT EXISTS steuerkategorie VARCHAR(20);
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'chk_rooms_steuerkategorie') THEN
+        ALTER TABLE rooms ADD CONSTRAINT chk_rooms_steuerkategorie
+            CHECK (steuerkategorie IN ('beherbergung', 'standard', 'steuerfrei'));
+    END IF;
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'chk_service_templates_steuerkategorie') THEN
+        ALTER TABLE service_templates ADD CONSTRAINT chk_service_templates_steuerkategorie
+            CHECK (steuerkategorie IN ('beherbergung', 'standard', 'steuerfrei'));
+    END IF;
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'chk_additional_services_steuerkategorie') THEN
+        ALTER TABLE additional_services ADD CONSTRAINT chk_additional_services_steuerkategorie
+            CHECK (steuerkategorie IS NULL OR steuerkategorie IN ('beherbergung', 'standard', 'steuerfrei'));
+    END IF;
+END $$;
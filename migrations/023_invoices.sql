@@ -0,0 +1,70 @@
+-- Migration: Invoices (GoBD)
+-- Date: 2026-10-17
+-- Description: Issued invoices with gap-free yearly numbers. Every invoice stores an
+--              immutable snapshot of its line items and totals and is linked to its booking.
+
+-- ============================================================================
+-- 1. NUMBER SEQUENCE PER YEAR
+-- ============================================================================
+
+-- Incremented in the same transaction as the invoice insert:
+-- a rolled back insert releases the number again, so there are no gaps.
+CREATE TABLE IF NOT EXISTS invoice_number_sequences (
+    year INTEGER PRIMARY KEY,
+    last_number INTEGER NOT NULL DEFAULT 0
+);
+
+-- ============================================================================
+-- 2. INVOICES
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS invoices (
+    id SERIAL PRIMARY KEY,
+    invoice_number VARCHAR(32) NOT NULL UNIQUE,  -- RE-<year>-<sequence>
+    year INTEGER NOT NULL,
+    sequence_number INTEGER NOT NULL,
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE RESTRICT,
+    issue_date DATE NOT NULL,
+    total NUMERIC(10, 2) NOT NULL,               -- after discounts, incl. Kurtaxe
+    amount_due NUMERIC(10, 2) NOT NULL,          -- total - guest credit
+    document JSONB NOT NULL,                     -- invoice::Invoice (lines, discounts, tax lines, totals)
+    pdf_path TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255),
+
+    CONSTRAINT uq_invoice_year_sequence UNIQUE (year, sequence_number)
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoices_booking ON invoices(booking_id);
+
+-- ============================================================================
+-- 3. IMMUTABILITY
+-- ============================================================================
+
+-- Issued invoices can neither be changed nor deleted, only the PDF path may be set
+CREATE OR REPLACE FUNCTION prevent_invoice_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        RAISE EXCEPTION 'Rechnung % ist festgeschrieben und kann nicht gelöscht werden', OLD.invoice_number;
+    END IF;
+
+    IF (NEW.invoice_number, NEW.year, NEW.sequence_number, NEW.booking_id, NEW.issue_date,
+        NEW.total, NEW.amount_due, NEW.document, NEW.created_at, NEW.created_by)
+       IS DISTINCT FROM
+       (OLD.invoice_number, OLD.year, OLD.sequence_number, OLD.booking_id, OLD.issue_date,
+        OLD.total, OLD.amount_due, OLD.document, OLD.created_at, OLD.created_by) THEN
+        RAISE EXCEPTION 'Rechnung % ist festgeschrieben und kann nicht geändert werden', OLD.invoice_number;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_invoices_immutable ON invoices;
+CREATE TRIGGER trg_invoices_immutable
+    BEFORE UPDATE OR DELETE ON invoices
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_invoice_changes();
+
+COMMENT ON TABLE invoices IS 'Issued invoices (GoBD): gap-free yearly numbers, immutable snapshot of line items and totals';
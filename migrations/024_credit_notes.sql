@@ -0,0 +1,86 @@
+-- Migration: Credit Notes (Gutschrift / Stornorechnung)
+-- Date: 2026-10-17
+-- Description: Credit notes are stored in the invoices table with negative amounts and
+--              reference the corrected invoice. They have their own gap-free yearly sequence.
+
+-- ============================================================================
+-- 1. DOCUMENT TYPE + REFERENCE
+-- ============================================================================
+
+ALTER TABLE invoices ADD COLUMN IF NOT EXISTS document_type VARCHAR(20) NOT NULL DEFAULT 'rechnung';
+ALTER TABLE invoices ADD COLUMN IF NOT EXISTS corrects_invoice_id INTEGER REFERENCES invoices(id) ON DELETE RESTRICT;
+-- TRUE = cancels the whole invoice (Stornorechnung), FALSE = partial correction
+ALTER TABLE invoices ADD COLUMN IF NOT EXISTS is_cancellation BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE invoices ADD COLUMN IF NOT EXISTS reason TEXT;
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'chk_invoice_document_type') THEN
+        ALTER TABLE invoices ADD CONSTRAINT chk_invoice_document_type
+            CHECK (document_type IN ('rechnung', 'gutschrift'));
+    END IF;
+
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'chk_credit_note_reference') THEN
+        ALTER TABLE invoices ADD CONSTRAINT chk_credit_note_reference
+            CHECK ((document_type = 'gutschrift') = (corrects_invoice_id IS NOT NULL)
+                   AND (NOT is_cancellation OR document_type = 'gutschrift'));
+    END IF;
+END $$;
+
+-- At most one cancellation per invoice
+CREATE UNIQUE INDEX IF NOT EXISTS idx_invoices_one_cancellation
+    ON invoices(corrects_invoice_id) WHERE is_cancellation;
+CREATE INDEX IF NOT EXISTS idx_invoices_corrects ON invoices(corrects_invoice_id);
+
+-- ============================================================================
+-- 2. ONE SEQUENCE PER DOCUMENT TYPE AND YEAR
+-- ============================================================================
+
+ALTER TABLE invoice_number_sequences ADD COLUMN IF NOT EXISTS document_type VARCHAR(20) NOT NULL DEFAULT 'rechnung';
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.key_column_usage
+        WHERE table_name = 'invoice_number_sequences'
+          AND constraint_name = 'invoice_number_sequences_pkey'
+          AND column_name = 'document_type'
+    ) THEN
+        ALTER TABLE invoice_number_sequences DROP CONSTRAINT invoice_number_sequences_pkey;
+        ALTER TABLE invoice_number_sequences ADD PRIMARY KEY (document_type, year);
+    END IF;
+
+    IF EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'uq_invoice_year_sequence') THEN
+        ALTER TABLE invoices DROP CONSTRAINT uq_invoice_year_sequence;
+    END IF;
+
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'uq_invoice_type_year_sequence') THEN
+        ALTER TABLE invoices ADD CONSTRAINT uq_invoice_type_year_sequence
+            UNIQUE (document_type, year, sequence_number);
+    END IF;
+END $$;
+
+-- ============================================================================
+-- 3. IMMUTABILITY (now including the credit note columns)
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION prevent_invoice_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        RAISE EXCEPTION 'Rechnung % ist festgeschrieben und kann nicht gelöscht werden', OLD.invoice_number;
+    END IF;
+
+    IF (NEW.invoice_number, NEW.year, NEW.sequence_number, NEW.booking_id, NEW.issue_date,
+        NEW.total, NEW.amount_due, NEW.document, NEW.created_at, NEW.created_by,
+        NEW.document_type, NEW.corrects_invoice_id, NEW.is_cancellation, NEW.reason)
+       IS DISTINCT FROM
+       (OLD.invoice_number, OLD.year, OLD.sequence_number, OLD.booking_id, OLD.issue_date,
+        OLD.total, OLD.amount_due, OLD.document, OLD.created_at, OLD.created_by,
+        OLD.document_type, OLD.corrects_invoice_id, OLD.is_cancellation, OLD.reason) THEN
+        RAISE EXCEPTION 'Rechnung % ist festgeschrieben und kann nicht geändert werden', OLD.invoice_number;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
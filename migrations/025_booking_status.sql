@@ -0,0 +1,91 @@
+-- Migration 025: Booking status state machine
+-- Date: 2026-10-17
+-- Description: Adds the status 'anfrage' (booking request) and adapts the cleaning task
+--              trigger to the state machine in booking_status.rs. Transitions are validated
+--              in BookingRepository, the CHECK constraint only guards the value set.
+
+-- ============================================================================
+-- 1. STATUS VALUES
+-- ============================================================================
+
+-- This is the only place that defines bookings_status_check. It runs on every startup,
+-- so it lists the complete value set of BookingStatus::ALL, including 'optioniert'
+-- (migration 029) - a shorter list would fail as soon as such bookings exist.
+ALTER TABLE bookings DROP CONSTRAINT IF EXISTS bookings_status_check;
+
+ALTER TABLE bookings
+ADD CONSTRAINT bookings_status_check
+CHECK (status IN ('anfrage', 'optioniert', 'reserviert', 'bestaetigt', 'eingecheckt', 'ausgecheckt', 'storniert'));
+
+-- ============================================================================
+-- 2. CLEANING TASK TRIGGER
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION generate_cleaning_task_for_booking()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_priority VARCHAR(20);
+    v_has_dog BOOLEAN;
+    v_guest_name VARCHAR(200);
+    v_guest_count INTEGER;
+BEGIN
+    -- Only generate for booked stays: no task for open requests, and after check-out
+    -- the task is placed on the real departure day by the application
+    IF NEW.status IN ('cancelled', 'storniert', 'anfrage', 'ausgecheckt') THEN
+        RETURN NEW;
+    END IF;
+
+    -- Determine priority (HIGH if same-day checkout/checkin)
+    v_priority := 'NORMAL';
+
+    -- Check for dog (via additional_services)
+    SELECT EXISTS(
+        SELECT 1 FROM additional_services
+        WHERE booking_id = NEW.id AND service_name ILIKE '%hund%'
+    ) INTO v_has_dog;
+
+    -- Get guest name
+    SELECT CONCAT(vorname, ' ', nachname) INTO v_guest_name
+    FROM guests WHERE id = NEW.guest_id;
+
+    -- Get guest count (main guest + accompanying guests)
+    SELECT 1 + COUNT(*) INTO v_guest_count
+    FROM accompanying_guests WHERE booking_id = NEW.id;
+
+    -- UPSERT: Insert or update existing cleaning task
+    INSERT INTO cleaning_tasks (
+        booking_id,
+        room_id,
+        task_date,
+        checkout_time,
+        priority,
+        has_dog,
+        change_bedding,
+        guest_count,
+        guest_name,
+        status
+    ) VALUES (
+        NEW.id,
+        NEW.room_id,
+        NEW.checkout_date::date,
+        NULL, -- checkout_time (not stored in bookings)
+        v_priority,
+        v_has_dog,
+        TRUE, -- Always change bedding on checkout
+        v_guest_count,
+        v_guest_name,
+        'pending'
+    )
+    ON CONFLICT (booking_id, room_id, task_date)
+    DO UPDATE SET
+        checkout_time = EXCLUDED.checkout_time,
+        priority = EXCLUDED.priority,
+        has_dog = EXCLUDED.has_dog,
+        change_bedding = EXCLUDED.change_bedding,
+        guest_count = EXCLUDED.guest_count,
+        guest_name = EXCLUDED.guest_name,
+        updated_at = CURRENT_TIMESTAMP;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,125 @@
+-- Migration 026: Booking groups (multi-room group bookings)
+-- Date: 2026-10-17
+-- Description: A booking group owns several room bookings of one organiser, e.g. a police
+--              seminar with 8-15 rooms. The rooms are booked in one transaction, the group
+--              gets one consolidated invoice and one set of emails (master booking = first
+--              room booking of the group). Every room can carry its own list of guest names.
+
+-- ============================================================================
+-- 1. BOOKING GROUPS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS booking_groups (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,                  -- e.g. "Seminar Einsatztaktik Mai 2026"
+    reservierungsnummer VARCHAR(64) NOT NULL UNIQUE,
+    organiser_guest_id INTEGER NOT NULL REFERENCES guests(id) ON DELETE RESTRICT,
+    payment_recipient_id INTEGER REFERENCES payment_recipients(id) ON DELETE SET NULL,
+    bemerkungen TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255)
+);
+
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS booking_group_id INTEGER REFERENCES booking_groups(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_bookings_booking_group ON bookings(booking_group_id);
+
+-- ============================================================================
+-- 2. GUEST NAMES PER ROOM
+-- ============================================================================
+
+-- Plain name list of the people sleeping in a room of a group (no guest records needed)
+CREATE TABLE IF NOT EXISTS booking_guest_names (
+    id SERIAL PRIMARY KEY,
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name VARCHAR(255) NOT NULL,
+
+    CONSTRAINT uq_booking_guest_name_position UNIQUE (booking_id, position)
+);
+
+-- ============================================================================
+-- 3. GROUP INVOICES
+-- ============================================================================
+
+-- Consolidated invoice of a group: booking_id is the master booking, booking_group_id is set
+ALTER TABLE invoices ADD COLUMN IF NOT EXISTS booking_group_id INTEGER REFERENCES booking_groups(id) ON DELETE RESTRICT;
+CREATE INDEX IF NOT EXISTS idx_invoices_booking_group ON invoices(booking_group_id);
+
+-- ============================================================================
+-- 4. REMINDER EMAILS ONLY FOR THE MASTER BOOKING
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION schedule_reminder_emails_for_booking()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_reminder_days INTEGER;
+    v_guest_email VARCHAR;
+    v_reminder_enabled BOOLEAN;
+    reminder_date TIMESTAMP;
+BEGIN
+    -- Group bookings: the organiser gets one reminder (master booking = first room of the group)
+    IF NEW.booking_group_id IS NOT NULL AND EXISTS (
+        SELECT 1 FROM bookings WHERE booking_group_id = NEW.booking_group_id AND id < NEW.id
+    ) THEN
+        RETURN NEW;
+    END IF;
+
+    -- Lade Notification Settings
+    SELECT
+        checkin_reminders_enabled,
+        checkin_reminder_before_days,
+        g.email
+    INTO
+        v_reminder_enabled,
+        v_reminder_days,
+        v_guest_email
+    FROM notification_settings ns
+    CROSS JOIN guests g
+    WHERE g.id = NEW.guest_id
+    LIMIT 1;
+
+    -- Prüfe ob Check-in Erinnerungen aktiviert sind
+    IF COALESCE(v_reminder_enabled, false) = false THEN
+        RETURN NEW;
+    END IF;
+
+    -- Prüfe ob Gast E-Mail hat
+    IF v_guest_email IS NULL OR v_guest_email = '' THEN
+        RETURN NEW;
+    END IF;
+
+    -- Berechne Erinnerungs-Datum (N Tage vor Check-in)
+    reminder_date := (NEW.checkin_date::date - INTERVAL '1 day' * COALESCE(v_reminder_days, 3))::TIMESTAMP;
+
+    -- Nur einfügen wenn in der Zukunft
+    IF reminder_date > NOW() THEN
+        INSERT INTO scheduled_emails (
+            booking_id,
+            guest_id,
+            template_name,
+            recipient_email,
+            subject,
+            scheduled_for,
+            status
+        ) VALUES (
+            NEW.id,
+            NEW.guest_id,
+            'reminder',
+            v_guest_email,
+            format('Erinnerung: Ihre Buchung #%s', NEW.id),
+            reminder_date,
+            'pending'
+        )
+        ON CONFLICT (booking_id, template_name, status)
+        DO UPDATE SET
+            scheduled_for = EXCLUDED.scheduled_for,
+            recipient_email = EXCLUDED.recipient_email,
+            subject = EXCLUDED.subject;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+COMMENT ON TABLE booking_groups IS 'Group bookings: several room bookings of one organiser with one consolidated invoice';
@@ -0,0 +1,73 @@
+-- Migration 027: Room allotments (contingents) for partner organisations
+-- Date: 2026-10-17
+-- Description: Police unions and academies reserve blocks of rooms months ahead. An allotment
+--              holds a quantity of rooms per night - out of a room type (gebaeude_typ) or an
+--              explicit room set - for a date range. Held units reduce the availability for
+--              everybody else, bookings drawn down from the allotment use them up, and units
+--              not drawn down by the release deadline are released by a background job.
+
+-- ============================================================================
+-- 1. ALLOTMENTS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS allotments (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,                  -- e.g. "GdP Landesbezirk Bayern - Sommer 2026"
+    guest_id INTEGER REFERENCES guests(id) ON DELETE SET NULL,  -- partner organisation (contact)
+    gebaeude_typ VARCHAR(100),                   -- room type; NULL = explicit room set (allotment_rooms)
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,                      -- exclusive, like checkout_date
+    quantity INTEGER NOT NULL,                   -- rooms held per night
+    release_date DATE NOT NULL,                  -- undrawn units are released on this day
+    status VARCHAR(20) NOT NULL DEFAULT 'aktiv', -- 'aktiv' | 'freigegeben'
+    released_at TIMESTAMP,
+    bemerkungen TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255),
+
+    CONSTRAINT chk_allotment_dates CHECK (end_date > start_date),
+    CONSTRAINT chk_allotment_quantity CHECK (quantity > 0),
+    CONSTRAINT chk_allotment_status CHECK (status IN ('aktiv', 'freigegeben'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_allotments_active_dates ON allotments(start_date, end_date) WHERE status = 'aktiv';
+
+-- Explicit room set of an allotment (only used when gebaeude_typ is NULL)
+CREATE TABLE IF NOT EXISTS allotment_rooms (
+    allotment_id INTEGER NOT NULL REFERENCES allotments(id) ON DELETE CASCADE,
+    room_id INTEGER NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+
+    PRIMARY KEY (allotment_id, room_id)
+);
+
+-- ============================================================================
+-- 2. BOOKINGS DRAWN DOWN FROM AN ALLOTMENT
+-- ============================================================================
+
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS allotment_id INTEGER REFERENCES allotments(id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_bookings_allotment ON bookings(allotment_id);
+
+-- ============================================================================
+-- 3. REAL-TIME NOTIFY (TapeChart refresh)
+-- ============================================================================
+
+DROP TRIGGER IF EXISTS trg_notify_allotment_insert ON allotments;
+CREATE TRIGGER trg_notify_allotment_insert
+    AFTER INSERT ON allotments
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_table_change();
+
+DROP TRIGGER IF EXISTS trg_notify_allotment_update ON allotments;
+CREATE TRIGGER trg_notify_allotment_update
+    AFTER UPDATE ON allotments
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_table_change();
+
+DROP TRIGGER IF EXISTS trg_notify_allotment_delete ON allotments;
+CREATE TRIGGER trg_notify_allotment_delete
+    AFTER DELETE ON allotments
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_table_change();
+
+COMMENT ON TABLE allotments IS 'Room contingents held for partner organisations until their release deadline';
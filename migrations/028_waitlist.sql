@@ -0,0 +1,56 @@
+-- Migration 028: Waitlist with automatic offers
+-- Date: 2026-10-17
+-- Description: Guests whose request could not be booked go on a waitlist (date range, party size,
+--              acceptable rooms and/or locations). When a booking is cancelled or moved, the freed
+--              room is offered to the first matching entry: the entry is marked 'angeboten' and an
+--              offer email ('warteliste_angebot') is queued in scheduled_emails.
+
+-- ============================================================================
+-- 1. WAITLIST ENTRIES
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS waitlist_entries (
+    id SERIAL PRIMARY KEY,
+    guest_id INTEGER NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    checkin_date DATE NOT NULL,
+    checkout_date DATE NOT NULL,
+    anzahl_gaeste INTEGER NOT NULL DEFAULT 1,
+    room_ids INTEGER[] NOT NULL DEFAULT '{}',   -- acceptable rooms (empty = any room)
+    orte VARCHAR(100)[] NOT NULL DEFAULT '{}',  -- acceptable locations (empty = any location)
+    status VARCHAR(20) NOT NULL DEFAULT 'wartend',
+    offered_room_id INTEGER REFERENCES rooms(id) ON DELETE SET NULL,
+    offered_at TIMESTAMP,
+    offer_email_id INTEGER REFERENCES scheduled_emails(id) ON DELETE SET NULL,
+    bemerkungen TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255),
+
+    CONSTRAINT chk_waitlist_dates CHECK (checkout_date > checkin_date),
+    CONSTRAINT chk_waitlist_guests CHECK (anzahl_gaeste > 0),
+    CONSTRAINT chk_waitlist_status CHECK (status IN ('wartend', 'angeboten', 'gebucht', 'storniert'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_waitlist_waiting_dates ON waitlist_entries(checkin_date, checkout_date) WHERE status = 'wartend';
+CREATE INDEX IF NOT EXISTS idx_waitlist_guest ON waitlist_entries(guest_id);
+
+-- ============================================================================
+-- 2. OFFER EMAIL TEMPLATE
+-- ============================================================================
+
+INSERT INTO email_templates (template_name, subject, body, is_active)
+SELECT
+    'warteliste_angebot',
+    'Ein Zimmer ist frei geworden: {{zimmer}} vom {{checkin}} bis {{checkout}}',
+    E'Sehr geehrte/r {{gast_name}},\n\n'
+    'für Ihren Wunschzeitraum ist ein Zimmer frei geworden:\n\n'
+    'Zimmer: {{zimmer}}\n'
+    'Zeitraum: {{checkin}} - {{checkout}}\n\n'
+    'Bitte melden Sie sich zeitnah bei uns, wenn Sie das Zimmer buchen möchten. '
+    'Das Angebot gilt, solange das Zimmer nicht anderweitig vergeben ist.\n\n'
+    'Mit freundlichen Grüßen,\n'
+    'Ihr DPolG Buchungsteam',
+    true
+WHERE NOT EXISTS (SELECT 1 FROM email_templates WHERE template_name = 'warteliste_angebot');
+
+COMMENT ON TABLE waitlist_entries IS 'Waitlist: freed rooms are offered automatically to the first matching entry';
@@ -0,0 +1,200 @@
+-- Migration 029: Option bookings (provisional holds) with expiry
+-- Date: 2026-10-17
+-- Description: Adds the status 'optioniert': the room is held for the guest (counted as occupied
+--              by the availability check) until bookings.option_expires_at. A reminder is due the
+--              day before expiry; expired options are cancelled by the hold-release job, which
+--              queues the 'option_abgelaufen' email to the guest.
+
+-- ============================================================================
+-- 1. EXPIRY
+-- ============================================================================
+
+-- The status value 'optioniert' itself is part of bookings_status_check (migration 025)
+
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS option_expires_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_bookings_option_expires ON bookings(option_expires_at) WHERE status = 'optioniert';
+
+-- Default hold period for options without an explicit expiry
+ALTER TABLE notification_settings ADD COLUMN IF NOT EXISTS option_hold_days INTEGER DEFAULT 3;
+
+UPDATE notification_settings SET option_hold_days = 3 WHERE option_hold_days IS NULL;
+
+COMMENT ON COLUMN bookings.option_expires_at IS 'End of the hold for status optioniert (released automatically afterwards)';
+COMMENT ON COLUMN notification_settings.option_hold_days IS 'Default hold period of option bookings in days';
+
+-- ============================================================================
+-- 2. DEFAULT EXPIRY
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION set_option_expiry_default()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status = 'optioniert' AND NEW.option_expires_at IS NULL THEN
+        NEW.option_expires_at := CURRENT_TIMESTAMP + make_interval(days => COALESCE(
+            (SELECT option_hold_days FROM notification_settings LIMIT 1), 3
+        ));
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_set_option_expiry_default ON bookings;
+CREATE TRIGGER trg_set_option_expiry_default
+    BEFORE INSERT OR UPDATE OF status, option_expires_at ON bookings
+    FOR EACH ROW
+    EXECUTE FUNCTION set_option_expiry_default();
+
+-- ============================================================================
+-- 3. REMINDER BEFORE EXPIRY
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION sync_option_expiry_reminder()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_guest_name VARCHAR(200);
+BEGIN
+    IF NEW.status = 'optioniert' THEN
+        SELECT CONCAT(vorname, ' ', nachname) INTO v_guest_name
+        FROM guests WHERE id = NEW.guest_id;
+
+        INSERT INTO reminders (
+            booking_id, reminder_type, title, description, due_date, priority,
+            is_completed, is_snoozed, created_at, updated_at
+        )
+        VALUES (
+            NEW.id,
+            'auto_option_expiry',
+            format('Option läuft ab - Buchung #%s', NEW.id),
+            format('Option für %s läuft am %s ab - bestätigen oder freigeben',
+                v_guest_name,
+                TO_CHAR(NEW.option_expires_at, 'DD.MM.YYYY HH24:MI')
+            ),
+            GREATEST((NEW.option_expires_at - INTERVAL '1 day')::date, CURRENT_DATE),
+            'high',
+            false,
+            false,
+            CURRENT_TIMESTAMP,
+            CURRENT_TIMESTAMP
+        )
+        ON CONFLICT (booking_id, reminder_type) WHERE is_completed = FALSE
+        DO UPDATE SET
+            due_date = EXCLUDED.due_date,
+            description = EXCLUDED.description,
+            title = EXCLUDED.title,
+            updated_at = CURRENT_TIMESTAMP;
+    ELSIF TG_OP = 'UPDATE' AND OLD.status = 'optioniert' THEN
+        -- Option confirmed or cancelled: the reminder is done
+        UPDATE reminders
+        SET is_completed = true,
+            completed_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE booking_id = NEW.id
+          AND reminder_type = 'auto_option_expiry'
+          AND is_completed = false;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_sync_option_expiry_reminder ON bookings;
+CREATE TRIGGER trg_sync_option_expiry_reminder
+    AFTER INSERT OR UPDATE OF status, option_expires_at ON bookings
+    FOR EACH ROW
+    EXECUTE FUNCTION sync_option_expiry_reminder();
+
+-- ============================================================================
+-- 4. CLEANING TASK TRIGGER
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION generate_cleaning_task_for_booking()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_priority VARCHAR(20);
+    v_has_dog BOOLEAN;
+    v_guest_name VARCHAR(200);
+    v_guest_count INTEGER;
+BEGIN
+    -- Only generate for booked stays: no task for open requests and unconfirmed options,
+    -- and after check-out the task is placed on the real departure day by the application
+    IF NEW.status IN ('cancelled', 'storniert', 'anfrage', 'optioniert', 'ausgecheckt') THEN
+        RETURN NEW;
+    END IF;
+
+    -- Determine priority (HIGH if same-day checkout/checkin)
+    v_priority := 'NORMAL';
+
+    -- Check for dog (via additional_services)
+    SELECT EXISTS(
+        SELECT 1 FROM additional_services
+        WHERE booking_id = NEW.id AND service_name ILIKE '%hund%'
+    ) INTO v_has_dog;
+
+    -- Get guest name
+    SELECT CONCAT(vorname, ' ', nachname) INTO v_guest_name
+    FROM guests WHERE id = NEW.guest_id;
+
+    -- Get guest count (main guest + accompanying guests)
+    SELECT 1 + COUNT(*) INTO v_guest_count
+    FROM accompanying_guests WHERE booking_id = NEW.id;
+
+    -- UPSERT: Insert or update existing cleaning task
+    INSERT INTO cleaning_tasks (
+        booking_id,
+        room_id,
+        task_date,
+        checkout_time,
+        priority,
+        has_dog,
+        change_bedding,
+        guest_count,
+        guest_name,
+        status
+    ) VALUES (
+        NEW.id,
+        NEW.room_id,
+        NEW.checkout_date::date,
+        NULL, -- checkout_time (not stored in bookings)
+        v_priority,
+        v_has_dog,
+        TRUE, -- Always change bedding on checkout
+        v_guest_count,
+        v_guest_name,
+        'pending'
+    )
+    ON CONFLICT (booking_id, room_id, task_date)
+    DO UPDATE SET
+        checkout_time = EXCLUDED.checkout_time,
+        priority = EXCLUDED.priority,
+        has_dog = EXCLUDED.has_dog,
+        change_bedding = EXCLUDED.change_bedding,
+        guest_count = EXCLUDED.guest_count,
+        guest_name = EXCLUDED.guest_name,
+        updated_at = CURRENT_TIMESTAMP;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================================================================
+-- 5. RELEASE EMAIL TEMPLATE
+-- ============================================================================
+
+INSERT INTO email_templates (template_name, subject, body, is_active)
+SELECT
+    'option_abgelaufen',
+    'Ihre Option für {{zimmer}} vom {{checkin}} bis {{checkout}} ist abgelaufen',
+    E'Sehr geehrte/r {{gast_name}},\n\n'
+    'die für Sie vorgemerkte Option ist abgelaufen, das Zimmer wurde wieder freigegeben:\n\n'
+    'Zimmer: {{zimmer}}\n'
+    'Zeitraum: {{checkin}} - {{checkout}}\n\n'
+    'Wenn Sie weiterhin Interesse haben, melden Sie sich gerne bei uns - '
+    'wir prüfen dann, ob das Zimmer noch verfügbar ist.\n\n'
+    'Mit freundlichen Grüßen,\n'
+    'Ihr DPolG Buchungsteam',
+    true
+WHERE NOT EXISTS (SELECT 1 FROM email_templates WHERE template_name = 'option_abgelaufen');
+
+COMMENT ON FUNCTION set_option_expiry_default() IS 'Option bookings without expiry are held for notification_settings.option_hold_days';
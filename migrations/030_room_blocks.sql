@@ -0,0 +1,51 @@
+-- Migration 030: Room blocks (maintenance, renovation, out of order)
+-- Date: 2026-10-17
+-- Description: Takes a room out of service for a date range without a fake guest booking.
+--              Blocked rooms are not available for bookings (availability check), do not count
+--              as free rooms of an allotment pool, and their nights are removed from the
+--              available nights in the occupancy reports.
+
+-- ============================================================================
+-- 1. ROOM BLOCKS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS room_blocks (
+    id SERIAL PRIMARY KEY,
+    room_id INTEGER NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,                      -- exclusive, like checkout_date
+    reason VARCHAR(255) NOT NULL,                -- e.g. "Renovierung Bad", "Wasserschaden"
+    bemerkungen TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255),
+
+    CONSTRAINT chk_room_block_dates CHECK (end_date > start_date),
+    CONSTRAINT chk_room_block_reason CHECK (length(trim(reason)) > 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_room_blocks_room_dates ON room_blocks(room_id, start_date, end_date);
+
+-- ============================================================================
+-- 2. REAL-TIME NOTIFY (TapeChart refresh)
+-- ============================================================================
+
+DROP TRIGGER IF EXISTS trg_notify_room_block_insert ON room_blocks;
+CREATE TRIGGER trg_notify_room_block_insert
+    AFTER INSERT ON room_blocks
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_table_change();
+
+DROP TRIGGER IF EXISTS trg_notify_room_block_update ON room_blocks;
+CREATE TRIGGER trg_notify_room_block_update
+    AFTER UPDATE ON room_blocks
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_table_change();
+
+DROP TRIGGER IF EXISTS trg_notify_room_block_delete ON room_blocks;
+CREATE TRIGGER trg_notify_room_block_delete
+    AFTER DELETE ON room_blocks
+    FOR EACH ROW
+    EXECUTE FUNCTION notify_table_change();
+
+COMMENT ON TABLE room_blocks IS 'Rooms out of service (maintenance, renovation) - not bookable, not counted as available nights';
@@ -0,0 +1,23 @@
+-- Migration 031: Room features and room assignment
+-- Date: 2026-10-17
+-- Description: Room features used by the room search (dogs allowed, ground floor) and a flag
+--              for bookings whose room was promised to the guest. The room optimiser only moves
+--              future bookings that are not fixed to their room.
+
+-- ============================================================================
+-- 1. ROOM FEATURES
+-- ============================================================================
+
+ALTER TABLE rooms ADD COLUMN IF NOT EXISTS hunde_erlaubt BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE rooms ADD COLUMN IF NOT EXISTS erdgeschoss BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN rooms.hunde_erlaubt IS 'Dogs allowed in this room (hard filter of the room search)';
+COMMENT ON COLUMN rooms.erdgeschoss IS 'Room is on the ground floor / barrier-free access';
+
+-- ============================================================================
+-- 2. FIXED ROOM OF A BOOKING
+-- ============================================================================
+
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS zimmer_fixiert BOOLEAN NOT NULL DEFAULT FALSE;
+
+COMMENT ON COLUMN bookings.zimmer_fixiert IS 'Room was promised to the guest - never moved by the room optimiser';
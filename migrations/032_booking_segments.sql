@@ -0,0 +1,97 @@
+-- Migration 032: Split stays (room change within a stay)
+-- Date: 2026-10-17
+-- Description: A stay can be split into consecutive segments in different rooms. Every segment
+--              is a booking with the reservation number of the stay; later segments point to the
+--              first one (master). Prices are calculated per segment, the invoice combines all
+--              segments, emails and check-in reminders go out for the master only.
+
+-- ============================================================================
+-- 1. SEGMENT LINK
+-- ============================================================================
+
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS split_parent_id INTEGER REFERENCES bookings(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_bookings_split_parent ON bookings(split_parent_id) WHERE split_parent_id IS NOT NULL;
+
+COMMENT ON COLUMN bookings.split_parent_id IS 'Later segment of a split stay (room change) - first segment of the stay';
+
+-- ============================================================================
+-- 2. CHECK-IN REMINDER ONLY FOR THE MASTER
+-- ============================================================================
+
+CREATE OR REPLACE FUNCTION schedule_reminder_emails_for_booking()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_reminder_days INTEGER;
+    v_guest_email VARCHAR;
+    v_reminder_enabled BOOLEAN;
+    reminder_date TIMESTAMP;
+BEGIN
+    -- Group bookings: the organiser gets one reminder (master booking = first room of the group)
+    IF NEW.booking_group_id IS NOT NULL AND EXISTS (
+        SELECT 1 FROM bookings WHERE booking_group_id = NEW.booking_group_id AND id < NEW.id
+    ) THEN
+        RETURN NEW;
+    END IF;
+
+    -- Split stays: the guest arrives once, the later segments get no reminder
+    IF NEW.split_parent_id IS NOT NULL THEN
+        RETURN NEW;
+    END IF;
+
+    -- Lade Notification Settings
+    SELECT
+        checkin_reminders_enabled,
+        checkin_reminder_before_days,
+        g.email
+    INTO
+        v_reminder_enabled,
+        v_reminder_days,
+        v_guest_email
+    FROM notification_settings ns
+    CROSS JOIN guests g
+    WHERE g.id = NEW.guest_id
+    LIMIT 1;
+
+    -- Prüfe ob Check-in Erinnerungen aktiviert sind
+    IF COALESCE(v_reminder_enabled, false) = false THEN
+        RETURN NEW;
+    END IF;
+
+    -- Prüfe ob Gast E-Mail hat
+    IF v_guest_email IS NULL OR v_guest_email = '' THEN
+        RETURN NEW;
+    END IF;
+
+    -- Berechne Erinnerungs-Datum (N Tage vor Check-in)
+    reminder_date := (NEW.checkin_date::date - INTERVAL '1 day' * COALESCE(v_reminder_days, 3))::TIMESTAMP;
+
+    -- Nur einfügen wenn in der Zukunft
+    IF reminder_date > NOW() THEN
+        INSERT INTO scheduled_emails (
+            booking_id,
+            guest_id,
+            template_name,
+            recipient_email,
+            subject,
+            scheduled_for,
+            status
+        ) VALUES (
+            NEW.id,
+            NEW.guest_id,
+            'reminder',
+            v_guest_email,
+            format('Erinnerung: Ihre Buchung #%s', NEW.id),
+            reminder_date,
+            'pending'
+        )
+        ON CONFLICT (booking_id, template_name, status)
+        DO UPDATE SET
+            scheduled_for = EXCLUDED.scheduled_for,
+            recipient_email = EXCLUDED.recipient_email,
+            subject = EXCLUDED.subject;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,53 @@
+-- Migration 033: Booking series (recurring bookings)
+-- Date: 2026-10-17
+-- Description: Regular seminar groups book the same rooms every week or month. A series stores
+--              the recurrence rule and its rooms; every occurrence and room is an ordinary
+--              booking (bookings.series_id) created with the availability check of a single
+--              booking. Occurrences that are not free are reported and not booked.
+
+-- ============================================================================
+-- 1. SERIES
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS booking_series (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,                  -- e.g. "Schießtraining Bereitschaftspolizei"
+    reservierungsnummer VARCHAR(64) NOT NULL UNIQUE,
+    guest_id INTEGER NOT NULL REFERENCES guests(id),
+    payment_recipient_id INTEGER REFERENCES payment_recipients(id) ON DELETE SET NULL,
+    frequency VARCHAR(20) NOT NULL,              -- 'woechentlich' | 'monatlich'
+    interval_count INTEGER NOT NULL DEFAULT 1,   -- every n-th week / month
+    first_checkin DATE NOT NULL,
+    nights INTEGER NOT NULL,
+    occurrences INTEGER,                         -- number of occurrences (or until_date)
+    until_date DATE,                             -- last possible arrival
+    anzahl_gaeste INTEGER NOT NULL DEFAULT 1,    -- guests per room
+    bemerkungen TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255),
+
+    CONSTRAINT chk_series_frequency CHECK (frequency IN ('woechentlich', 'monatlich')),
+    CONSTRAINT chk_series_interval CHECK (interval_count > 0),
+    CONSTRAINT chk_series_nights CHECK (nights > 0),
+    CONSTRAINT chk_series_end CHECK (occurrences IS NOT NULL OR until_date IS NOT NULL)
+);
+
+-- Rooms booked for every occurrence
+CREATE TABLE IF NOT EXISTS booking_series_rooms (
+    series_id INTEGER NOT NULL REFERENCES booking_series(id) ON DELETE CASCADE,
+    room_id INTEGER NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+
+    PRIMARY KEY (series_id, room_id)
+);
+
+-- ============================================================================
+-- 2. BOOKINGS OF A SERIES
+-- ============================================================================
+
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS series_id INTEGER REFERENCES booking_series(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_bookings_series ON bookings(series_id) WHERE series_id IS NOT NULL;
+
+COMMENT ON TABLE booking_series IS 'Recurring bookings: recurrence rule and rooms, one booking per occurrence and room';
+COMMENT ON COLUMN bookings.series_id IS 'Occurrence of a booking series (booking_series)';
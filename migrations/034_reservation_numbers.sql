@@ -0,0 +1,40 @@
+-- Migration 034: Server-side reservation numbers
+-- Date: 2026-10-17
+-- Description: Reservation numbers are allocated in the database from a yearly sequence
+--              instead of being built by the frontend (duplicates when two users booked at the
+--              same time). The pattern is configurable in the company settings, default
+--              '{YYYY}-{seq}' matches the numbers created so far.
+
+-- ============================================================================
+-- 1. PATTERN
+-- ============================================================================
+
+ALTER TABLE company_settings ADD COLUMN IF NOT EXISTS reservierungsnummer_format VARCHAR(64) NOT NULL DEFAULT '{YYYY}-{seq}';
+
+-- ============================================================================
+-- 2. YEARLY SEQUENCE
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS reservation_number_sequences (
+    year INTEGER PRIMARY KEY,
+    last_number INTEGER NOT NULL DEFAULT 0
+);
+
+-- Continue after the numbers already issued ("<year>-<number>"), so new numbers of the
+-- current year cannot repeat an existing one
+INSERT INTO reservation_number_sequences (year, last_number)
+SELECT substring(nr FROM '^(\d{4})-')::int, MAX(substring(nr FROM '^\d{4}-(\d{1,9})$')::int)
+FROM (
+    SELECT reservierungsnummer AS nr FROM bookings
+    UNION ALL
+    SELECT reservierungsnummer FROM booking_groups
+    UNION ALL
+    SELECT reservierungsnummer FROM booking_series
+) numbers
+WHERE nr ~ '^\d{4}-\d{1,9}$'
+GROUP BY 1
+ON CONFLICT (year) DO UPDATE
+SET last_number = GREATEST(reservation_number_sequences.last_number, EXCLUDED.last_number);
+
+COMMENT ON TABLE reservation_number_sequences IS 'Last allocated reservation number per year';
+COMMENT ON COLUMN company_settings.reservierungsnummer_format IS 'Pattern of new reservation numbers: {YYYY}, {YY}, {seq}, {seq:N}';
@@ -0,0 +1,106 @@
+-- Migration 035: Payments ledger and deposit requests (Anzahlungen)
+-- Date: 2026-10-17
+-- Description: Every incoming payment (or refund) of a booking is a row in booking_payments.
+--              Balance and payment status are derived from the ledger (view booking_balances);
+--              bookings.bezahlt / bezahlt_am / zahlungsmethode are kept in sync for the existing
+--              screens. Deposit requests (e.g. 30 % before arrival) have an amount and a due
+--              date, payments are applied to them in order of their due dates.
+
+-- ============================================================================
+-- 1. PAYMENTS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS booking_payments (
+    id SERIAL PRIMARY KEY,
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    amount NUMERIC(10, 2) NOT NULL,              -- negative = refund
+    payment_date DATE NOT NULL DEFAULT CURRENT_DATE,
+    method VARCHAR(50),                          -- 'Überweisung', 'Barzahlung', 'EC-Karte', ...
+    reference VARCHAR(255),                      -- bank reference, receipt number
+    notes TEXT,
+    recorded_by VARCHAR(255),
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_payment_amount CHECK (amount <> 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_booking_payments_booking ON booking_payments(booking_id);
+
+-- Reversal (Storno) of a payment: negative counter-entry, the original row stays in the ledger
+ALTER TABLE booking_payments
+    ADD COLUMN IF NOT EXISTS reversal_of INTEGER REFERENCES booking_payments(id) ON DELETE CASCADE;
+
+-- ============================================================================
+-- 2. DEPOSIT REQUESTS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS deposit_requests (
+    id SERIAL PRIMARY KEY,
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    description VARCHAR(255) NOT NULL DEFAULT 'Anzahlung',
+    amount NUMERIC(10, 2) NOT NULL,
+    percentage NUMERIC(5, 2),                    -- share of the booking total, if requested as percentage
+    due_date DATE NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255),
+
+    CONSTRAINT chk_deposit_amount CHECK (amount > 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_deposit_requests_booking ON deposit_requests(booking_id, due_date);
+
+-- ============================================================================
+-- 3. BALANCE PER BOOKING
+-- ============================================================================
+
+-- Amount due = booking total + Kurtaxe - guest credit used (cancelled bookings: nothing due)
+CREATE OR REPLACE VIEW booking_balances AS
+SELECT booking_id,
+       total_due,
+       paid,
+       total_due - paid AS outstanding,
+       CASE
+           WHEN paid >= total_due THEN 'bezahlt'
+           WHEN paid > 0 THEN 'teilweise_bezahlt'
+           ELSE 'ausstehend'
+       END AS payment_status
+FROM (
+    SELECT b.id AS booking_id,
+           CASE
+               WHEN b.status = 'storniert' THEN 0::numeric
+               ELSE ROUND((COALESCE(b.gesamtpreis, 0) + COALESCE(b.kurtaxe, 0))::numeric, 2) - COALESCE(c.credit, 0)
+           END AS total_due,
+           COALESCE(p.paid, 0) AS paid
+    FROM bookings b
+    LEFT JOIN (
+        SELECT booking_id, SUM(amount) AS paid FROM booking_payments GROUP BY booking_id
+    ) p ON p.booking_id = b.id
+    LEFT JOIN (
+        SELECT booking_id, SUM(amount)::numeric AS credit
+        FROM guest_credit_transactions
+        WHERE transaction_type = 'debit' AND booking_id IS NOT NULL
+        GROUP BY booking_id
+    ) c ON c.booking_id = b.id
+) amounts;
+
+-- ============================================================================
+-- 4. EXISTING PAID BOOKINGS
+-- ============================================================================
+
+-- Bookings marked as paid before the ledger existed get one payment over the amount due
+INSERT INTO booking_payments (booking_id, amount, payment_date, method, reference, recorded_by)
+SELECT b.id,
+       bb.total_due,
+       COALESCE(substring(b.bezahlt_am FROM '^\d{4}-\d{2}-\d{2}')::date, b.updated_at::date, CURRENT_DATE),
+       b.zahlungsmethode,
+       'Übernahme Zahlungsstatus',
+       'Migration 035'
+FROM bookings b
+JOIN booking_balances bb ON bb.booking_id = b.id
+WHERE b.bezahlt = TRUE
+  AND bb.total_due > 0
+  AND NOT EXISTS (SELECT 1 FROM booking_payments p WHERE p.booking_id = b.id);
+
+COMMENT ON TABLE booking_payments IS 'Payments and refunds per booking (ledger)';
+COMMENT ON TABLE deposit_requests IS 'Requested deposits (Anzahlungen) with due date';
+COMMENT ON VIEW booking_balances IS 'Amount due, paid amount, outstanding balance and payment status per booking';
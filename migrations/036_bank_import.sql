@@ -0,0 +1,32 @@
+-- Migration 036: Bank statement import (CAMT.053 / MT940)
+-- Date: 2026-10-17
+-- Description: Incoming transfers from imported bank statements. Each transaction is matched
+--              against the bookings with an open amount (bank_import.rs); a confirmed match
+--              is recorded as payment in booking_payments. Re-importing the same statement
+--              does not create duplicates (dedupe_key).
+
+CREATE TABLE IF NOT EXISTS bank_transactions (
+    id SERIAL PRIMARY KEY,
+    booking_date DATE NOT NULL,
+    amount NUMERIC(10, 2) NOT NULL,
+    payer_name VARCHAR(255),
+    payer_iban VARCHAR(34),
+    remittance TEXT NOT NULL DEFAULT '',
+    bank_reference VARCHAR(255),
+    file_name VARCHAR(255),
+    dedupe_key CHAR(32) NOT NULL UNIQUE,          -- md5 over date, amount, references, remittance
+    status VARCHAR(20) NOT NULL DEFAULT 'offen',
+    booking_id INTEGER REFERENCES bookings(id) ON DELETE SET NULL,
+    payment_id INTEGER REFERENCES booking_payments(id) ON DELETE SET NULL,
+    confidence SMALLINT,                          -- score of the confirmed proposal
+    imported_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    imported_by VARCHAR(255),
+    matched_at TIMESTAMP,
+    matched_by VARCHAR(255),
+
+    CONSTRAINT chk_bank_transaction_status CHECK (status IN ('offen', 'zugeordnet', 'ignoriert'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_bank_transactions_status ON bank_transactions(status, booking_date);
+
+COMMENT ON TABLE bank_transactions IS 'Incoming transfers from imported bank statements and their booking match';
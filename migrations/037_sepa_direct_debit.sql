@@ -0,0 +1,79 @@
+-- Migration 037: SEPA direct debit (Lastschrift) for members
+-- Date: 2026-10-17
+-- Description: SEPA mandates are stored on the guest. A batch export collects the due amount of
+--              every booking whose guest has a mandate and writes a pain.008 file for the bank
+--              (sepa.rs). The collections stay 'ausstehend' until the collection date, then they
+--              are recorded as payments. Returned debits (Rücklastschriften) reverse the payment.
+
+-- ============================================================================
+-- 1. MANDATE ON THE GUEST
+-- ============================================================================
+
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS sepa_mandatsreferenz VARCHAR(35);
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS sepa_mandat_datum DATE;           -- date of signature
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS sepa_iban VARCHAR(34);
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS sepa_bic VARCHAR(11);
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS sepa_kontoinhaber VARCHAR(70);     -- NULL = name of the guest
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS sepa_mandat_typ VARCHAR(10);      -- 'CORE' (Basislastschrift) or 'B2B'
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS sepa_einmalig BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE guests ADD COLUMN IF NOT EXISTS sepa_letzte_lastschrift DATE;     -- NULL = next debit is FRST
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'chk_guests_sepa_mandat_typ') THEN
+        ALTER TABLE guests ADD CONSTRAINT chk_guests_sepa_mandat_typ
+            CHECK (sepa_mandat_typ IS NULL OR sepa_mandat_typ IN ('CORE', 'B2B'));
+    END IF;
+END $$;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_guests_sepa_mandatsreferenz
+    ON guests(sepa_mandatsreferenz) WHERE sepa_mandatsreferenz IS NOT NULL;
+
+-- ============================================================================
+-- 2. CREDITOR
+-- ============================================================================
+
+ALTER TABLE payment_settings ADD COLUMN IF NOT EXISTS glaeubiger_id VARCHAR(35);
+ALTER TABLE payment_settings ADD COLUMN IF NOT EXISTS sepa_vorlauf_tage INTEGER NOT NULL DEFAULT 2;
+
+-- ============================================================================
+-- 3. BATCHES AND COLLECTIONS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS sepa_batches (
+    id SERIAL PRIMARY KEY,
+    message_id VARCHAR(35) NOT NULL UNIQUE,
+    collection_date DATE NOT NULL,
+    transaction_count INTEGER NOT NULL,
+    total NUMERIC(10, 2) NOT NULL,
+    xml TEXT NOT NULL,                            -- the pain.008 file, can be saved again
+    file_path TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255)
+);
+
+CREATE TABLE IF NOT EXISTS sepa_collections (
+    id SERIAL PRIMARY KEY,
+    batch_id INTEGER NOT NULL REFERENCES sepa_batches(id) ON DELETE CASCADE,
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    guest_id INTEGER NOT NULL REFERENCES guests(id) ON DELETE CASCADE,
+    amount NUMERIC(10, 2) NOT NULL CHECK (amount > 0),
+    mandate_reference VARCHAR(35) NOT NULL,
+    sequence_type VARCHAR(4) NOT NULL,            -- FRST, RCUR, OOFF
+    end_to_end_id VARCHAR(35) NOT NULL UNIQUE,
+    collection_date DATE NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'ausstehend',
+    payment_id INTEGER REFERENCES booking_payments(id) ON DELETE SET NULL,
+    return_reason VARCHAR(4),                     -- ISO 20022 reason code, e.g. AC04, MD06
+    returned_at DATE,
+
+    CONSTRAINT chk_sepa_collection_status CHECK (status IN ('ausstehend', 'eingezogen', 'zurueckgegeben')),
+    CONSTRAINT chk_sepa_sequence_type CHECK (sequence_type IN ('FRST', 'RCUR', 'OOFF'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_sepa_collections_status ON sepa_collections(status, collection_date);
+CREATE INDEX IF NOT EXISTS idx_sepa_collections_booking ON sepa_collections(booking_id);
+
+COMMENT ON TABLE sepa_batches IS 'Exported pain.008 direct debit files';
+COMMENT ON TABLE sepa_collections IS 'Direct debits of bookings: pending, collected (payment recorded) or returned';
+COMMENT ON COLUMN guests.sepa_letzte_lastschrift IS 'Collection date of the last debit on the mandate; NULL = first debit (FRST)';
@@ -0,0 +1,131 @@
+-- Migration 038: Dunning levels (Mahnstufen) with escalating templates and fees
+-- Date: 2026-10-17
+-- Description: Unpaid bookings are no longer reminded with the same 'payment_reminder' email
+--              over and over. The dunning procedure escalates through configurable stages
+--              (Zahlungserinnerung, 1. Mahnung, 2. Mahnung), each with its own email template,
+--              payment deadline and optional late fee (added to the booking as a service).
+--              After the deadline of the last stage the booking is handed over to manual
+--              follow-up (reminder 'auto_dunning'). Every step is kept in booking_dunning_history.
+
+-- ============================================================================
+-- 1. STAGES
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS dunning_stages (
+    id SERIAL PRIMARY KEY,
+    stage INTEGER NOT NULL UNIQUE,               -- 1, 2, 3 ... (order of escalation)
+    name VARCHAR(100) NOT NULL,                  -- 'Zahlungserinnerung', '1. Mahnung', ...
+    template_name VARCHAR(100) NOT NULL UNIQUE,  -- email template (one per stage)
+    frist_tage INTEGER NOT NULL DEFAULT 14,      -- payment deadline after sending, then next stage
+    gebuehr NUMERIC(10, 2) NOT NULL DEFAULT 0,   -- late fee, 0 = none
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_dunning_stage CHECK (stage > 0),
+    CONSTRAINT chk_dunning_frist CHECK (frist_tage > 0),
+    CONSTRAINT chk_dunning_gebuehr CHECK (gebuehr >= 0)
+);
+
+INSERT INTO dunning_stages (stage, name, template_name, frist_tage, gebuehr)
+SELECT s.stage, s.name, s.template_name, s.frist_tage, s.gebuehr
+FROM (VALUES
+    (1, 'Zahlungserinnerung', 'payment_reminder', 14, 0.00),
+    (2, '1. Mahnung', 'mahnung_1', 14, 5.00),
+    (3, '2. Mahnung', 'mahnung_2', 10, 10.00)
+) AS s(stage, name, template_name, frist_tage, gebuehr)
+WHERE NOT EXISTS (SELECT 1 FROM dunning_stages);
+
+COMMENT ON TABLE dunning_stages IS 'Dunning levels: template, payment deadline and late fee per stage';
+COMMENT ON COLUMN dunning_stages.frist_tage IS 'Days after sending until the next stage (or the hand-over after the last stage)';
+
+-- ============================================================================
+-- 2. HISTORY PER BOOKING
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS booking_dunning_history (
+    id SERIAL PRIMARY KEY,
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    event VARCHAR(20) NOT NULL DEFAULT 'mahnung',   -- 'mahnung' = stage sent, 'uebergabe' = manual follow-up
+    stage INTEGER,                                  -- NULL for 'uebergabe'
+    stage_name VARCHAR(100),                        -- copy, stages can be renamed later
+    template_name VARCHAR(100),
+    outstanding NUMERIC(10, 2) NOT NULL,            -- open amount incl. the fee of this stage
+    gebuehr NUMERIC(10, 2) NOT NULL DEFAULT 0,
+    zahlungsfrist DATE,                             -- NULL for 'uebergabe'
+    service_id INTEGER REFERENCES additional_services(id) ON DELETE SET NULL,
+    scheduled_email_id INTEGER REFERENCES scheduled_emails(id) ON DELETE SET NULL,
+    reminder_id INTEGER REFERENCES reminders(id) ON DELETE SET NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT chk_dunning_event CHECK (event IN ('mahnung', 'uebergabe')),
+    CONSTRAINT chk_dunning_event_stage CHECK ((event = 'mahnung') = (stage IS NOT NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_dunning_history_booking ON booking_dunning_history(booking_id, created_at);
+
+-- Reminders sent by the old scheduler count as the first stage
+INSERT INTO booking_dunning_history (
+    booking_id, event, stage, stage_name, template_name, outstanding, zahlungsfrist,
+    scheduled_email_id, created_at
+)
+SELECT se.booking_id, 'mahnung', 1, 'Zahlungserinnerung', 'payment_reminder',
+       GREATEST(COALESCE(bb.outstanding, 0), 0),
+       (COALESCE(se.sent_at, se.scheduled_for) + INTERVAL '14 days')::date,
+       se.id, COALESCE(se.sent_at, se.scheduled_for)
+FROM scheduled_emails se
+JOIN bookings b ON b.id = se.booking_id
+LEFT JOIN booking_balances bb ON bb.booking_id = se.booking_id
+WHERE se.template_name = 'payment_reminder'
+  AND se.status = 'sent'
+  AND NOT EXISTS (SELECT 1 FROM booking_dunning_history h WHERE h.booking_id = se.booking_id);
+
+COMMENT ON TABLE booking_dunning_history IS 'Dunning steps per booking (stages sent, hand-over to manual follow-up)';
+
+-- ============================================================================
+-- 3. EMAIL TEMPLATES
+-- ============================================================================
+-- Additional placeholders for dunning emails: {{mahnstufe}}, {{offener_betrag}},
+-- {{mahngebuehr}}, {{zahlungsfrist}}
+
+INSERT INTO email_templates (template_name, subject, body, is_active)
+SELECT
+    'payment_reminder',
+    'Zahlungserinnerung – Ihr Aufenthalt vom {{checkin}} bis {{checkout}}',
+    E'Sehr geehrte/r {{gast_name}},\n\n'
+    'für Ihren Aufenthalt vom {{checkin}} bis {{checkout}} ({{zimmer}}) konnten wir noch keinen '
+    'vollständigen Zahlungseingang feststellen.\n\n'
+    'Offener Betrag: {{offener_betrag}}\n\n'
+    'Bitte überweisen Sie den Betrag bis zum {{zahlungsfrist}}. Sollten Sie die Zahlung bereits '
+    'veranlasst haben, betrachten Sie dieses Schreiben bitte als gegenstandslos.\n\n'
+    'Mit freundlichen Grüßen,\n'
+    'Ihr DPolG Buchungsteam',
+    true
+WHERE NOT EXISTS (SELECT 1 FROM email_templates WHERE template_name = 'payment_reminder');
+
+INSERT INTO email_templates (template_name, subject, body, is_active)
+SELECT
+    'mahnung_1',
+    '1. Mahnung – Ihr Aufenthalt vom {{checkin}} bis {{checkout}}',
+    E'Sehr geehrte/r {{gast_name}},\n\n'
+    'leider ist der offene Betrag für Ihren Aufenthalt vom {{checkin}} bis {{checkout}} trotz '
+    'unserer Zahlungserinnerung noch nicht bei uns eingegangen.\n\n'
+    'Offener Betrag: {{offener_betrag}} (inkl. Mahngebühr {{mahngebuehr}})\n\n'
+    'Bitte überweisen Sie den Betrag bis spätestens {{zahlungsfrist}}.\n\n'
+    'Mit freundlichen Grüßen,\n'
+    'Ihr DPolG Buchungsteam',
+    true
+WHERE NOT EXISTS (SELECT 1 FROM email_templates WHERE template_name = 'mahnung_1');
+
+INSERT INTO email_templates (template_name, subject, body, is_active)
+SELECT
+    'mahnung_2',
+    '2. Mahnung – Ihr Aufenthalt vom {{checkin}} bis {{checkout}}',
+    E'Sehr geehrte/r {{gast_name}},\n\n'
+    'trotz Zahlungserinnerung und 1. Mahnung ist der offene Betrag für Ihren Aufenthalt vom '
+    '{{checkin}} bis {{checkout}} weiterhin nicht beglichen.\n\n'
+    'Offener Betrag: {{offener_betrag}} (inkl. Mahngebühr {{mahngebuehr}})\n\n'
+    'Wir bitten Sie dringend, den Betrag bis zum {{zahlungsfrist}} zu überweisen. Andernfalls '
+    'müssen wir die Forderung weiterverfolgen.\n\n'
+    'Mit freundlichen Grüßen,\n'
+    'Ihr DPolG Buchungsteam',
+    true
+WHERE NOT EXISTS (SELECT 1 FROM email_templates WHERE template_name = 'mahnung_2');
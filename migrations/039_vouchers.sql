@@ -0,0 +1,114 @@
+-- Migration 039: Vouchers (Gutscheine) and expiry of guest credit
+-- Date: 2026-10-17
+-- Description: Vouchers are sold with a code, a value and an expiry date (by default the end
+--              of the regular limitation period, 31.12. of the third year after the sale). They
+--              are transferable and can be redeemed - also partially - against any booking;
+--              every redemption is a payment in booking_payments (method 'Gutschein'), deleting
+--              the payment gives the amount back to the voucher.
+--              Guest credit can now have an expiry date. The hourly job books the amount that
+--              was not used until then as 'expiry' transaction.
+
+-- ============================================================================
+-- 1. GUEST CREDIT EXPIRY
+-- ============================================================================
+
+ALTER TABLE guest_credit_transactions ADD COLUMN IF NOT EXISTS expires_at DATE;
+
+COMMENT ON COLUMN guest_credit_transactions.expires_at IS 'Credit only: valid until (incl.), NULL = does not expire';
+
+-- The type check of migration 001 is unnamed - replace it once by a named one with 'expiry'
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint WHERE conname = 'chk_credit_transaction_type'
+    ) THEN
+        ALTER TABLE guest_credit_transactions
+            DROP CONSTRAINT IF EXISTS guest_credit_transactions_transaction_type_check;
+        ALTER TABLE guest_credit_transactions DROP CONSTRAINT IF EXISTS chk_amount_positive;
+
+        ALTER TABLE guest_credit_transactions
+            ADD CONSTRAINT chk_credit_transaction_type
+                CHECK (transaction_type IN ('credit', 'debit', 'refund', 'expiry')),
+            ADD CONSTRAINT chk_amount_positive CHECK (amount > 0),
+            ADD CONSTRAINT chk_credit_expires_at CHECK (expires_at IS NULL OR transaction_type = 'credit');
+    END IF;
+END $$;
+
+COMMENT ON COLUMN guest_credit_transactions.transaction_type IS
+    'credit=add funds, debit=use funds, refund=return funds, expiry=unused credit expired';
+
+CREATE INDEX IF NOT EXISTS idx_guest_credit_expires_at
+    ON guest_credit_transactions(expires_at) WHERE expires_at IS NOT NULL;
+
+CREATE OR REPLACE FUNCTION get_guest_credit_balance(p_guest_id INTEGER)
+RETURNS NUMERIC(10, 2) AS $$
+DECLARE
+    v_balance NUMERIC(10, 2);
+BEGIN
+    SELECT
+        COALESCE(
+            SUM(CASE
+                WHEN transaction_type = 'credit' THEN amount
+                WHEN transaction_type = 'debit' THEN -amount
+                WHEN transaction_type = 'refund' THEN amount
+                WHEN transaction_type = 'expiry' THEN -amount
+                ELSE 0
+            END),
+            0.0
+        )
+    INTO v_balance
+    FROM guest_credit_transactions
+    WHERE guest_id = p_guest_id;
+
+    RETURN v_balance;
+END;
+$$ LANGUAGE plpgsql;
+
+-- ============================================================================
+-- 2. VOUCHERS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS vouchers (
+    id SERIAL PRIMARY KEY,
+    code VARCHAR(20) NOT NULL UNIQUE,            -- 'GS-XXXX-XXXX', last character is a check character
+    value NUMERIC(10, 2) NOT NULL,
+    issued_at DATE NOT NULL DEFAULT CURRENT_DATE,
+    expires_at DATE NOT NULL,                    -- redeemable until (incl.)
+    purchaser_guest_id INTEGER REFERENCES guests(id) ON DELETE SET NULL,
+    purchaser_name VARCHAR(255),                 -- copy of the guest name or a buyer who is no guest
+    recipient_name VARCHAR(255),                 -- printed on the voucher ("für ...")
+    message TEXT,                                -- personal message printed on the voucher
+    notes TEXT,                                  -- internal (e.g. how the voucher was paid)
+    cancelled_at TIMESTAMP,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255),
+
+    CONSTRAINT chk_voucher_value CHECK (value > 0),
+    CONSTRAINT chk_voucher_expiry CHECK (expires_at >= issued_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_vouchers_purchaser ON vouchers(purchaser_guest_id);
+
+COMMENT ON TABLE vouchers IS 'Sold vouchers (Gutscheine), transferable, redeemable against any booking';
+
+-- ============================================================================
+-- 3. REDEMPTIONS
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS voucher_redemptions (
+    id SERIAL PRIMARY KEY,
+    voucher_id INTEGER NOT NULL REFERENCES vouchers(id),
+    booking_id INTEGER NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    amount NUMERIC(10, 2) NOT NULL,
+    -- Deleting the payment in the ledger cancels the redemption
+    payment_id INTEGER NOT NULL UNIQUE REFERENCES booking_payments(id) ON DELETE CASCADE,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255),
+
+    CONSTRAINT chk_voucher_redemption_amount CHECK (amount > 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_voucher_redemptions_voucher ON voucher_redemptions(voucher_id);
+CREATE INDEX IF NOT EXISTS idx_voucher_redemptions_booking ON voucher_redemptions(booking_id);
+
+COMMENT ON TABLE voucher_redemptions IS 'Redemptions of vouchers, each one is a payment of the booking';
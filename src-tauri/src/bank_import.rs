@@ -0,0 +1,685 @@
+// Bank Import - bank statements (CAMT.053, MT940) and matching of incoming transfers
+// Pure functions only: no State, no DB access. BankTransactionRepository stores the parsed
+// transactions and records confirmed matches as payments (PaymentRepository).
+//
+// Matching of a transfer against the bookings with an open amount:
+// - Reservation number or invoice number in the remittance text: 60 (both: 70). The EPC QR
+//   code of the invoice puts "<Rechnungsnummer> / <Reservierungsnummer>" into the reference.
+// - Amount equals the outstanding amount, the invoice amount or a deposit request: 25
+// - Payer name contains the guest's or the payment recipient's name: 15
+// Proposals below MIN_CONFIDENCE are dropped, at most MAX_PROPOSALS per transaction.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+
+pub const MIN_CONFIDENCE: u8 = 30;
+pub const MAX_PROPOSALS: usize = 5;
+
+/// One booked transaction of a statement (negative amount = debit)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankTransaction {
+    pub booking_date: NaiveDate,
+    pub amount: Money,
+    pub payer_name: Option<String>,
+    pub payer_iban: Option<String>,
+    pub remittance: String,
+    pub bank_reference: Option<String>,
+}
+
+/// Booking with an open amount that a transfer may pay
+#[derive(Debug, Clone)]
+pub struct MatchCandidate {
+    pub booking_id: i32,
+    pub reservierungsnummer: String,
+    pub invoice_numbers: Vec<String>,
+    /// Guest and payment recipient (organisations pay for seminar groups)
+    pub names: Vec<String>,
+    /// Outstanding amount, invoice amount, deposit requests
+    pub expected_amounts: Vec<Money>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchProposal {
+    pub booking_id: i32,
+    pub reservierungsnummer: String,
+    /// 0-100
+    pub confidence: u8,
+    pub reasons: Vec<String>,
+}
+
+/// Parse a statement file, CAMT.053 (XML) or MT940 by content
+pub fn parse_statement(content: &str) -> Result<Vec<BankTransaction>, String> {
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+    if content.starts_with('<') {
+        parse_camt053(content)
+    } else if content.contains(":61:") {
+        parse_mt940(content)
+    } else {
+        Err("Unbekanntes Dateiformat: erwartet wird ein Kontoauszug im Format CAMT.053 (XML) oder MT940".to_string())
+    }
+}
+
+// ============================================================================
+// CAMT.053
+// ============================================================================
+
+/// Entries (Ntry) of all statements in the file. Entries with several transaction details
+/// (batch bookings) become one transaction per detail. Pending entries are skipped.
+pub fn parse_camt053(xml: &str) -> Result<Vec<BankTransaction>, String> {
+    let root = xml::parse(xml)?;
+    if root.find("BkToCstmrStmt").is_none() {
+        return Err("Die Datei ist kein CAMT.053-Kontoauszug (BkToCstmrStmt fehlt)".to_string());
+    }
+
+    let mut transactions = Vec::new();
+    for entry in root.descendants("Ntry") {
+        let status = entry
+            .path(&["Sts", "Cd"])
+            .or_else(|| entry.child("Sts"))
+            .map(|s| s.text.trim().to_string())
+            .unwrap_or_default();
+        if status == "PDNG" || status == "INFO" {
+            continue;
+        }
+
+        let booking_date = entry
+            .path(&["BookgDt", "Dt"])
+            .or_else(|| entry.path(&["BookgDt", "DtTm"]))
+            .or_else(|| entry.path(&["ValDt", "Dt"]))
+            .and_then(|d| d.text.get(..10).and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()))
+            .ok_or("Buchung ohne gültiges Buchungsdatum im CAMT-Kontoauszug")?;
+        let debit = entry.child("CdtDbtInd").map(|c| c.text.trim() == "DBIT").unwrap_or(false);
+        let entry_amount = camt_amount(entry.child("Amt"))?;
+        let entry_reference = entry.child("AcctSvcrRef").map(|r| r.text.trim().to_string());
+
+        let details: Vec<&xml::Element> = entry.descendants("TxDtls");
+        if details.is_empty() {
+            transactions.push(BankTransaction {
+                booking_date,
+                amount: signed(entry_amount, debit),
+                payer_name: None,
+                payer_iban: None,
+                remittance: entry
+                    .child("AddtlNtryInf")
+                    .map(|i| normalize_space(&i.text))
+                    .unwrap_or_default(),
+                bank_reference: entry_reference,
+            });
+            continue;
+        }
+
+        for detail in &details {
+            let amount = match detail.child("Amt").or_else(|| detail.path(&["AmtDtls", "TxAmt", "Amt"])) {
+                Some(amount) => camt_amount(Some(amount))?,
+                None if details.len() == 1 => entry_amount,
+                None => return Err("Sammelbuchung ohne Einzelbetrag im CAMT-Kontoauszug".to_string()),
+            };
+            // Payer of a credit is the debtor, of a debit (refund) the creditor
+            let party = if debit { "Cdtr" } else { "Dbtr" };
+            let parties = detail.child("RltdPties");
+            let payer_name = parties
+                .and_then(|p| p.path(&[party, "Nm"]).or_else(|| p.path(&[party, "Pty", "Nm"])))
+                .map(|n| normalize_space(&n.text));
+            let payer_iban = parties
+                .and_then(|p| p.path(&[&format!("{}Acct", party), "Id", "IBAN"]))
+                .map(|i| i.text.trim().to_string());
+
+            let remittance_info = detail.child("RmtInf");
+            let mut remittance: Vec<String> = remittance_info
+                .map(|r| r.children_named("Ustrd").map(|u| u.text.trim().to_string()).collect())
+                .unwrap_or_default();
+            if let Some(reference) = remittance_info.and_then(|r| r.path(&["Strd", "CdtrRefInf", "Ref"])) {
+                remittance.push(reference.text.trim().to_string());
+            }
+
+            let bank_reference = detail
+                .path(&["Refs", "AcctSvcrRef"])
+                .or_else(|| detail.path(&["Refs", "EndToEndId"]).filter(|r| r.text.trim() != "NOTPROVIDED"))
+                .map(|r| r.text.trim().to_string())
+                .or_else(|| entry_reference.clone());
+
+            transactions.push(BankTransaction {
+                booking_date,
+                amount: signed(amount, debit),
+                payer_name,
+                payer_iban,
+                remittance: normalize_space(&remittance.join(" ")),
+                bank_reference,
+            });
+        }
+    }
+
+    Ok(transactions)
+}
+
+fn camt_amount(element: Option<&xml::Element>) -> Result<Money, String> {
+    let text = element.map(|a| a.text.trim()).unwrap_or_default();
+    text.parse::<Money>()
+        .map_err(|_| format!("Ungültiger Betrag '{}' im CAMT-Kontoauszug", text))
+}
+
+// ============================================================================
+// MT940
+// ============================================================================
+
+/// Transactions (:61: with the following :86:) of all statements in the file.
+/// :86: in the German structured format (?20-?29 remittance, ?31 IBAN, ?32/?33 name) or free text.
+pub fn parse_mt940(text: &str) -> Result<Vec<BankTransaction>, String> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for line in text.lines() {
+        let line = line.trim_end_matches('\r');
+        let tag = line
+            .strip_prefix(':')
+            .and_then(|rest| rest.split_once(':'))
+            .filter(|(tag, _)| (2..=3).contains(&tag.len()) && tag.chars().all(|c| c.is_ascii_alphanumeric()));
+        match tag {
+            Some((tag, value)) => fields.push((tag.to_string(), value.to_string())),
+            // End of a statement
+            None if line.trim() == "-" => {}
+            // Continuation line of the previous field
+            None => {
+                if let Some((_, value)) = fields.last_mut() {
+                    value.push('\n');
+                    value.push_str(line);
+                }
+            }
+        }
+    }
+
+    let mut transactions: Vec<BankTransaction> = Vec::new();
+    for (tag, value) in &fields {
+        match tag.as_str() {
+            "61" => transactions.push(mt940_statement_line(value)?),
+            "86" => {
+                if let Some(last) = transactions.last_mut() {
+                    let (remittance, payer_name, payer_iban) = mt940_information(value);
+                    last.remittance = remittance;
+                    last.payer_name = payer_name;
+                    last.payer_iban = payer_iban;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(transactions)
+}
+
+/// ":61:" YYMMDD[MMDD](C|D|RC|RD)[funds code]amount N<type><reference>[//bank reference]
+fn mt940_statement_line(value: &str) -> Result<BankTransaction, String> {
+    let invalid = || format!("Ungültige Umsatzzeile im MT940-Kontoauszug: {}", value.lines().next().unwrap_or(""));
+    let line = value.lines().next().unwrap_or("");
+
+    let value_date = line
+        .get(..6)
+        .and_then(|d| NaiveDate::parse_from_str(&format!("20{}", d), "%Y%m%d").ok())
+        .ok_or_else(invalid)?;
+    let mut rest = &line[6..];
+
+    // Optional entry date MMDD, year from the value date (may cross the turn of the year)
+    let mut booking_date = value_date;
+    if rest.len() >= 4 && rest[..4].chars().all(|c| c.is_ascii_digit()) {
+        let month: u32 = rest[..2].parse().map_err(|_| invalid())?;
+        let day: u32 = rest[2..4].parse().map_err(|_| invalid())?;
+        let year = match (value_date.month(), month) {
+            (1, 12) => value_date.year() - 1,
+            (12, 1) => value_date.year() + 1,
+            _ => value_date.year(),
+        };
+        booking_date = NaiveDate::from_ymd_opt(year, month, day).ok_or_else(invalid)?;
+        rest = &rest[4..];
+    }
+
+    let (debit, after_mark) = if let Some(r) = rest.strip_prefix("RC") {
+        (true, r)
+    } else if let Some(r) = rest.strip_prefix("RD") {
+        (false, r)
+    } else if let Some(r) = rest.strip_prefix('C') {
+        (false, r)
+    } else if let Some(r) = rest.strip_prefix('D') {
+        (true, r)
+    } else {
+        return Err(invalid());
+    };
+    // Optional third character of the currency code
+    let after_mark = after_mark.strip_prefix(|c: char| c.is_ascii_alphabetic()).unwrap_or(after_mark);
+
+    let amount_len = after_mark
+        .find(|c: char| !(c.is_ascii_digit() || c == ','))
+        .unwrap_or(after_mark.len());
+    let amount: Money = after_mark[..amount_len].parse().map_err(|_| invalid())?;
+
+    let bank_reference = after_mark[amount_len..]
+        .split_once("//")
+        .map(|(_, bank)| bank.trim().to_string())
+        .filter(|r| !r.is_empty());
+
+    Ok(BankTransaction {
+        booking_date,
+        amount: signed(amount, debit),
+        payer_name: None,
+        payer_iban: None,
+        remittance: String::new(),
+        bank_reference,
+    })
+}
+
+/// Remittance text, payer name and payer IBAN of a ":86:" field
+fn mt940_information(value: &str) -> (String, Option<String>, Option<String>) {
+    // Lines of the structured format are cut at fixed widths: join without separator
+    let joined: String = value.lines().collect();
+    if !joined.contains('?') {
+        return (normalize_space(&value.replace('\n', " ")), None, None);
+    }
+
+    let mut remittance = String::new();
+    let mut name = String::new();
+    let mut iban = None;
+    for part in joined.split('?').skip(1) {
+        let (code, text) = part.split_at(part.len().min(2));
+        match code.parse::<u32>() {
+            Ok(20..=29) | Ok(60..=63) => remittance.push_str(text),
+            Ok(31) => iban = Some(text.trim().to_string()).filter(|i| !i.is_empty()),
+            Ok(32) | Ok(33) => name.push_str(text),
+            _ => {}
+        }
+    }
+
+    let name = normalize_space(&name);
+    (normalize_space(&remittance), Some(name).filter(|n| !n.is_empty()), iban)
+}
+
+fn signed(amount: Money, debit: bool) -> Money {
+    if debit {
+        -amount.abs()
+    } else {
+        amount.abs()
+    }
+}
+
+fn normalize_space(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// ============================================================================
+// MATCHING
+// ============================================================================
+
+/// Bookings that the transfer probably pays, best first. Debits get no proposals.
+pub fn propose_matches(transaction: &BankTransaction, candidates: &[MatchCandidate]) -> Vec<MatchProposal> {
+    if !transaction.amount.is_positive() {
+        return Vec::new();
+    }
+
+    let remittance = transaction.remittance.to_uppercase();
+    let payer = fold_name(transaction.payer_name.as_deref().unwrap_or(""));
+    let payer_words: Vec<&str> = payer.split(' ').collect();
+
+    let mut proposals: Vec<MatchProposal> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let mut confidence: u32 = 0;
+            let mut reasons = Vec::new();
+
+            let reservation = contains_token(&remittance, &candidate.reservierungsnummer.to_uppercase());
+            let invoice = candidate
+                .invoice_numbers
+                .iter()
+                .find(|number| contains_token(&remittance, &number.to_uppercase()));
+            match (reservation, invoice) {
+                (true, Some(number)) => {
+                    confidence += 70;
+                    reasons.push(format!("Reservierungs- und Rechnungsnummer {} im Verwendungszweck", number));
+                }
+                (true, None) => {
+                    confidence += 60;
+                    reasons.push("Reservierungsnummer im Verwendungszweck".to_string());
+                }
+                (false, Some(number)) => {
+                    confidence += 60;
+                    reasons.push(format!("Rechnungsnummer {} im Verwendungszweck", number));
+                }
+                (false, None) => {}
+            }
+
+            if candidate.expected_amounts.contains(&transaction.amount) {
+                confidence += 25;
+                reasons.push("Betrag stimmt".to_string());
+            }
+
+            // Every word of the name, in any order ("MUELLER, MAX" pays for "Max Müller")
+            if !payer.is_empty()
+                && candidate.names.iter().any(|name| {
+                    let name = fold_name(name);
+                    !name.is_empty() && name.split(' ').all(|word| payer_words.contains(&word))
+                })
+            {
+                confidence += 15;
+                reasons.push("Name des Zahlers".to_string());
+            }
+
+            let confidence = confidence.min(100) as u8;
+            (confidence >= MIN_CONFIDENCE).then(|| MatchProposal {
+                booking_id: candidate.booking_id,
+                reservierungsnummer: candidate.reservierungsnummer.clone(),
+                confidence,
+                reasons,
+            })
+        })
+        .collect();
+
+    proposals.sort_by(|a, b| b.confidence.cmp(&a.confidence).then(a.booking_id.cmp(&b.booking_id)));
+    proposals.truncate(MAX_PROPOSALS);
+    proposals
+}
+
+/// `needle` occurs in `haystack` and is not part of a longer number or word
+/// ("2026-1" must not match "2026-12")
+fn contains_token(haystack: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return false;
+    }
+    haystack.match_indices(needle).any(|(start, _)| {
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[start + needle.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric()) && !after.is_some_and(|c| c.is_alphanumeric())
+    })
+}
+
+/// Upper case, umlauts as banks transmit them (Ä -> AE), words separated by single spaces
+fn fold_name(name: &str) -> String {
+    let mut folded = String::new();
+    for c in name.to_uppercase().chars() {
+        match c {
+            'Ä' => folded.push_str("AE"),
+            'Ö' => folded.push_str("OE"),
+            'Ü' => folded.push_str("UE"),
+            'ß' => folded.push_str("SS"),
+            c if c.is_alphanumeric() => folded.push(c),
+            _ => folded.push(' '),
+        }
+    }
+    normalize_space(&folded)
+}
+
+// ============================================================================
+// MINIMAL XML READER (elements and text, enough for CAMT)
+// ============================================================================
+
+mod xml {
+    #[derive(Debug, Default)]
+    pub struct Element {
+        /// Local name without namespace prefix
+        pub name: String,
+        pub text: String,
+        pub children: Vec<Element>,
+    }
+
+    impl Element {
+        pub fn child(&self, name: &str) -> Option<&Element> {
+            self.children.iter().find(|c| c.name == name)
+        }
+
+        pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+            self.children.iter().filter(move |c| c.name == name)
+        }
+
+        pub fn path(&self, names: &[&str]) -> Option<&Element> {
+            names.iter().try_fold(self, |element, name| element.child(name))
+        }
+
+        /// First element with this name (depth first, including self)
+        pub fn find(&self, name: &str) -> Option<&Element> {
+            if self.name == name {
+                return Some(self);
+            }
+            self.children.iter().find_map(|c| c.find(name))
+        }
+
+        /// All elements with this name, not descending into matches
+        pub fn descendants<'a>(&'a self, name: &str) -> Vec<&'a Element> {
+            let mut found = Vec::new();
+            for child in &self.children {
+                if child.name == name {
+                    found.push(child);
+                } else {
+                    found.extend(child.descendants(name));
+                }
+            }
+            found
+        }
+    }
+
+    /// Document root element. Attributes, comments and processing instructions are skipped.
+    pub fn parse(input: &str) -> Result<Element, String> {
+        let invalid = |what: &str| format!("Ungültiges XML: {}", what);
+        let mut stack: Vec<Element> = vec![Element::default()];
+        let mut rest = input;
+
+        while !rest.is_empty() {
+            let Some(start) = rest.find('<') else {
+                append_text(stack.last_mut().unwrap(), rest)?;
+                break;
+            };
+            append_text(stack.last_mut().unwrap(), &rest[..start])?;
+            rest = &rest[start..];
+
+            if let Some(after) = rest.strip_prefix("<![CDATA[") {
+                let end = after.find("]]>").ok_or_else(|| invalid("CDATA nicht geschlossen"))?;
+                stack.last_mut().unwrap().text.push_str(&after[..end]);
+                rest = &after[end + 3..];
+            } else if let Some(after) = rest.strip_prefix("<!--") {
+                let end = after.find("-->").ok_or_else(|| invalid("Kommentar nicht geschlossen"))?;
+                rest = &after[end + 3..];
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                let end = rest.find('>').ok_or_else(|| invalid("Deklaration nicht geschlossen"))?;
+                rest = &rest[end + 1..];
+            } else {
+                let end = rest.find('>').ok_or_else(|| invalid("Tag nicht geschlossen"))?;
+                let tag = &rest[1..end];
+                rest = &rest[end + 1..];
+
+                if let Some(name) = tag.strip_prefix('/') {
+                    let element = stack.pop().filter(|_| !stack.is_empty()).ok_or_else(|| invalid("unerwartetes End-Tag"))?;
+                    if element.name != local_name(name.trim()) {
+                        return Err(invalid(&format!("</{}> schließt <{}>", name.trim(), element.name)));
+                    }
+                    stack.last_mut().unwrap().children.push(element);
+                } else {
+                    let self_closing = tag.ends_with('/');
+                    let tag = tag.trim_end_matches('/');
+                    let name = tag.split_whitespace().next().ok_or_else(|| invalid("leeres Tag"))?;
+                    let element = Element { name: local_name(name).to_string(), ..Default::default() };
+                    if self_closing {
+                        stack.last_mut().unwrap().children.push(element);
+                    } else {
+                        stack.push(element);
+                    }
+                }
+            }
+        }
+
+        if stack.len() != 1 {
+            return Err(invalid("Element nicht geschlossen"));
+        }
+        stack.pop().unwrap().children.into_iter().next().ok_or_else(|| invalid("kein Wurzelelement"))
+    }
+
+    fn local_name(name: &str) -> &str {
+        name.rsplit_once(':').map(|(_, local)| local).unwrap_or(name)
+    }
+
+    fn append_text(element: &mut Element, raw: &str) -> Result<(), String> {
+        let mut rest = raw;
+        while let Some(start) = rest.find('&') {
+            element.text.push_str(&rest[..start]);
+            let end = rest[start..].find(';').ok_or("Ungültiges XML: Entität nicht geschlossen")? + start;
+            let entity = &rest[start + 1..end];
+            let decoded = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(|dec| dec.parse::<u32>()))
+                    .and_then(|code| code.ok())
+                    .and_then(char::from_u32),
+            };
+            element
+                .text
+                .push(decoded.ok_or_else(|| format!("Ungültiges XML: unbekannte Entität &{};", entity))?);
+            rest = &rest[end + 1..];
+        }
+        element.text.push_str(rest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAMT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+  <BkToCstmrStmt>
+    <Stmt>
+      <Ntry>
+        <Amt Ccy="EUR">370.37</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts>BOOK</Sts>
+        <BookgDt><Dt>2026-10-15</Dt></BookgDt>
+        <NtryDtls><TxDtls>
+          <Refs><AcctSvcrRef>2026101500123</AcctSvcrRef></Refs>
+          <RltdPties>
+            <Dbtr><Nm>Polizeigewerkschaft Bezirk Nord e.V.</Nm></Dbtr>
+            <DbtrAcct><Id><IBAN>DE02120300000000202051</IBAN></Id></DbtrAcct>
+          </RltdPties>
+          <RmtInf><Ustrd>RE-2026-0042 / 2026-169 Anzahlung</Ustrd><Ustrd>Seminar M&amp;K</Ustrd></RmtInf>
+        </TxDtls></NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">12.90</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd>
+        <Sts>BOOK</Sts>
+        <BookgDt><Dt>2026-10-16</Dt></BookgDt>
+        <AddtlNtryInf>Kontofuehrung</AddtlNtryInf>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">50.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts>PDNG</Sts>
+        <BookgDt><Dt>2026-10-17</Dt></BookgDt>
+      </Ntry>
+    </Stmt>
+  </BkToCstmrStmt>
+</Document>"#;
+
+    const MT940: &str = ":20:STARTUMS\r
+:25:12030000/0000202051\r
+:28C:00001/001\r
+:60F:C261014EUR1000,00\r
+:61:2610151015CR370,37NTRFNONREF//2026101500123\r
+:86:166?00SEPA-GUTSCHRIFT?20EREF+NOTPROVIDED?21SVWZ+RE-2026-0042 / 2026-\r
+169 Anzahlung?31DE02120300000000202051?32Polizeigewerkschaft Bezirk?33 Nord e.V.\r
+:61:261016D12,90NMSCNONREF\r
+:86:Kontofuehrung Oktober\r
+:62F:C261016EUR1357,47\r
+-";
+
+    fn candidates() -> Vec<MatchCandidate> {
+        vec![
+            MatchCandidate {
+                booking_id: 1,
+                reservierungsnummer: "2026-169".to_string(),
+                invoice_numbers: vec!["RE-2026-0042".to_string()],
+                names: vec!["Max Müller".to_string(), "Polizeigewerkschaft Bezirk Nord e.V.".to_string()],
+                expected_amounts: vec![Money::from_euros(1234.55), Money::from_euros(370.37)],
+            },
+            MatchCandidate {
+                booking_id: 2,
+                reservierungsnummer: "2026-16".to_string(),
+                invoice_numbers: Vec::new(),
+                names: vec!["Erika Schmidt".to_string()],
+                expected_amounts: vec![Money::from_euros(370.37)],
+            },
+        ]
+    }
+
+    #[test]
+    fn camt053_entries() {
+        let transactions = parse_statement(CAMT).unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        let credit = &transactions[0];
+        assert_eq!(credit.booking_date, NaiveDate::from_ymd_opt(2026, 10, 15).unwrap());
+        assert_eq!(credit.amount, Money::from_euros(370.37));
+        assert_eq!(credit.payer_name.as_deref(), Some("Polizeigewerkschaft Bezirk Nord e.V."));
+        assert_eq!(credit.payer_iban.as_deref(), Some("DE02120300000000202051"));
+        assert_eq!(credit.remittance, "RE-2026-0042 / 2026-169 Anzahlung Seminar M&K");
+        assert_eq!(credit.bank_reference.as_deref(), Some("2026101500123"));
+
+        assert_eq!(transactions[1].amount, Money::from_euros(-12.90));
+        assert_eq!(transactions[1].remittance, "Kontofuehrung");
+    }
+
+    #[test]
+    fn mt940_lines() {
+        let transactions = parse_statement(MT940).unwrap();
+        assert_eq!(transactions.len(), 2);
+
+        let credit = &transactions[0];
+        assert_eq!(credit.booking_date, NaiveDate::from_ymd_opt(2026, 10, 15).unwrap());
+        assert_eq!(credit.amount, Money::from_euros(370.37));
+        assert_eq!(credit.remittance, "EREF+NOTPROVIDEDSVWZ+RE-2026-0042 / 2026-169 Anzahlung");
+        assert_eq!(credit.payer_name.as_deref(), Some("Polizeigewerkschaft Bezirk Nord e.V."));
+        assert_eq!(credit.payer_iban.as_deref(), Some("DE02120300000000202051"));
+        assert_eq!(credit.bank_reference.as_deref(), Some("2026101500123"));
+
+        assert_eq!(transactions[1].amount, Money::from_euros(-12.90));
+        assert_eq!(transactions[1].remittance, "Kontofuehrung Oktober");
+    }
+
+    #[test]
+    fn matching_by_reference_amount_and_name() {
+        let transaction = parse_statement(CAMT).unwrap().remove(0);
+        let proposals = propose_matches(&transaction, &candidates());
+
+        // "2026-16" is not a token of "2026-169": booking 2 only matches by amount (below minimum)
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].booking_id, 1);
+        assert_eq!(proposals[0].confidence, 100);
+        assert_eq!(proposals[0].reasons.len(), 3);
+    }
+
+    #[test]
+    fn weak_and_debit_matches() {
+        let mut transaction = BankTransaction {
+            booking_date: NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(),
+            amount: Money::from_euros(370.37),
+            payer_name: Some("MUELLER, MAX".to_string()),
+            payer_iban: None,
+            remittance: "Zimmer Oktober".to_string(),
+            bank_reference: None,
+        };
+        let proposals = propose_matches(&transaction, &candidates());
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].confidence, 40);
+
+        transaction.amount = -transaction.amount;
+        assert!(propose_matches(&transaction, &candidates()).is_empty());
+    }
+
+    #[test]
+    fn invalid_files_are_rejected() {
+        assert!(parse_statement("Datum;Betrag").is_err());
+        assert!(parse_statement("<Document><BkToCstmrStmt></Document>").is_err());
+        assert!(parse_statement("<Document><Other/></Document>").is_err());
+    }
+}
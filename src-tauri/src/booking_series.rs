@@ -0,0 +1,217 @@
+// Booking Series - recurrence rules of regular bookings (e.g. a monthly seminar)
+// Pure functions only: no State, no DB access. BookingSeriesRepository expands the rule
+// into occurrences and books every occurrence with the availability check of a single
+// booking; occurrences that are not free end up in the conflict report.
+//
+// Rule:
+// - `woechentlich`: every `interval` weeks on the weekday of the first arrival
+// - `monatlich`: every `interval` months on the day of month of the first arrival; in
+//   shorter months the last day of the month is used (31.01. -> 28.02. -> 31.03.)
+// - The series ends after `count` occurrences or with the last arrival on or before
+//   `until`, whichever comes first (at least one of both is required)
+// - Every occurrence has the same number of nights; occurrences must not overlap
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Duration, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Upper bound of occurrences per series (two years of weekly seminars)
+pub const MAX_OCCURRENCES: usize = 104;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Frequency {
+    Woechentlich,
+    Monatlich,
+}
+
+impl Frequency {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Frequency::Woechentlich => "woechentlich",
+            Frequency::Monatlich => "monatlich",
+        }
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Frequency {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "woechentlich" => Ok(Frequency::Woechentlich),
+            "monatlich" => Ok(Frequency::Monatlich),
+            other => Err(format!("Unbekannter Rhythmus: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    /// Every n-th week / month (1 = every week / month)
+    #[serde(default = "default_interval")]
+    pub interval: u32,
+    /// Number of occurrences
+    pub count: Option<u32>,
+    /// Last possible arrival (YYYY-MM-DD)
+    pub until: Option<String>,
+}
+
+fn default_interval() -> u32 {
+    1
+}
+
+/// One stay of a series, numbered from 1
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Occurrence {
+    pub number: u32,
+    pub checkin: NaiveDate,
+    pub checkout: NaiveDate,
+}
+
+/// Expand a rule into the stays of the series, starting with the stay
+/// `first_checkin` .. `first_checkin + nights`. Errors are user-facing messages.
+pub fn occurrences(first_checkin: NaiveDate, nights: i64, rule: &RecurrenceRule) -> Result<Vec<Occurrence>, String> {
+    if nights < 1 {
+        return Err("Ein Termin der Serie muss mindestens eine Nacht dauern".to_string());
+    }
+    if rule.interval < 1 {
+        return Err("Der Abstand zwischen zwei Terminen muss mindestens 1 sein".to_string());
+    }
+    if rule.count.is_none() && rule.until.is_none() {
+        return Err("Bitte Anzahl der Termine oder ein Enddatum angeben".to_string());
+    }
+    if rule.count == Some(0) {
+        return Err("Die Serie braucht mindestens einen Termin".to_string());
+    }
+    let until = match &rule.until {
+        Some(until) => Some(
+            NaiveDate::parse_from_str(until, "%Y-%m-%d")
+                .map_err(|_| format!("Ungültiges Enddatum: {}", until))?,
+        ),
+        None => None,
+    };
+    if until.is_some_and(|until| until < first_checkin) {
+        return Err("Das Enddatum liegt vor dem ersten Termin".to_string());
+    }
+
+    let mut result = Vec::new();
+    for index in 0.. {
+        if rule.count.is_some_and(|count| index >= count) {
+            break;
+        }
+        let checkin = match nth_checkin(first_checkin, rule, index) {
+            Some(checkin) => checkin,
+            None => break,
+        };
+        if until.is_some_and(|until| checkin > until) {
+            break;
+        }
+        if result.len() == MAX_OCCURRENCES {
+            return Err(format!("Eine Serie kann höchstens {} Termine haben", MAX_OCCURRENCES));
+        }
+        result.push(Occurrence {
+            number: index + 1,
+            checkin,
+            checkout: checkin + Duration::days(nights),
+        });
+    }
+
+    if result.windows(2).any(|pair| pair[1].checkin < pair[0].checkout) {
+        return Err("Die Termine der Serie überschneiden sich - Abstand oder Nächte anpassen".to_string());
+    }
+
+    Ok(result)
+}
+
+/// Arrival of the occurrence with 0-based `index`. Months are always counted from the first
+/// arrival, so a short month does not move the following occurrences.
+fn nth_checkin(first_checkin: NaiveDate, rule: &RecurrenceRule, index: u32) -> Option<NaiveDate> {
+    let steps = index.checked_mul(rule.interval)?;
+    match rule.frequency {
+        Frequency::Woechentlich => first_checkin.checked_add_signed(Duration::weeks(steps as i64)),
+        Frequency::Monatlich => first_checkin.checked_add_months(Months::new(steps)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn rule(frequency: Frequency, interval: u32, count: Option<u32>, until: Option<&str>) -> RecurrenceRule {
+        RecurrenceRule {
+            frequency,
+            interval,
+            count,
+            until: until.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn weekly_with_count() {
+        let stays = occurrences(date(2026, 11, 2), 2, &rule(Frequency::Woechentlich, 1, Some(3), None)).unwrap();
+        let checkins: Vec<_> = stays.iter().map(|o| o.checkin).collect();
+        assert_eq!(checkins, vec![date(2026, 11, 2), date(2026, 11, 9), date(2026, 11, 16)]);
+        assert_eq!(stays[2].checkout, date(2026, 11, 18));
+        assert_eq!(stays[2].number, 3);
+    }
+
+    #[test]
+    fn monthly_keeps_day_of_month_after_short_month() {
+        let stays = occurrences(date(2027, 1, 31), 1, &rule(Frequency::Monatlich, 1, Some(3), None)).unwrap();
+        let checkins: Vec<_> = stays.iter().map(|o| o.checkin).collect();
+        assert_eq!(checkins, vec![date(2027, 1, 31), date(2027, 2, 28), date(2027, 3, 31)]);
+    }
+
+    #[test]
+    fn until_is_inclusive_and_count_wins_if_earlier() {
+        let until = rule(Frequency::Monatlich, 2, None, Some("2027-05-10"));
+        let stays = occurrences(date(2027, 1, 10), 3, &until).unwrap();
+        let checkins: Vec<_> = stays.iter().map(|o| o.checkin).collect();
+        assert_eq!(checkins, vec![date(2027, 1, 10), date(2027, 3, 10), date(2027, 5, 10)]);
+
+        let both = rule(Frequency::Monatlich, 2, Some(2), Some("2027-05-10"));
+        assert_eq!(occurrences(date(2027, 1, 10), 3, &both).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn overlapping_occurrences_are_rejected() {
+        let weekly = rule(Frequency::Woechentlich, 1, Some(2), None);
+        assert!(occurrences(date(2026, 11, 2), 8, &weekly).is_err());
+        // Departure on the arrival day of the next occurrence is fine
+        assert_eq!(occurrences(date(2026, 11, 2), 7, &weekly).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let first = date(2026, 11, 2);
+        assert!(occurrences(first, 1, &rule(Frequency::Woechentlich, 1, None, None)).is_err());
+        assert!(occurrences(first, 0, &rule(Frequency::Woechentlich, 1, Some(2), None)).is_err());
+        assert!(occurrences(first, 1, &rule(Frequency::Woechentlich, 0, Some(2), None)).is_err());
+        assert!(occurrences(first, 1, &rule(Frequency::Woechentlich, 1, None, Some("2026-10-01"))).is_err());
+        assert!(occurrences(first, 1, &rule(Frequency::Woechentlich, 1, Some(MAX_OCCURRENCES as u32 + 1), None)).is_err());
+    }
+
+    #[test]
+    fn frequency_round_trip() {
+        for frequency in [Frequency::Woechentlich, Frequency::Monatlich] {
+            assert_eq!(frequency.as_str().parse::<Frequency>(), Ok(frequency));
+        }
+        assert!("taeglich".parse::<Frequency>().is_err());
+    }
+}
@@ -0,0 +1,273 @@
+// Booking status - state machine of the booking lifecycle
+//
+//   anfrage ──> reserviert ──> bestaetigt ──> eingecheckt ──> ausgecheckt
+//   shortcuts: anfrage ──> bestaetigt, reserviert ──> eingecheckt
+//   anfrage / reserviert / bestaetigt ──> storniert
+//   optioniert (hold until option_expires_at) ──> reserviert / bestaetigt / storniert
+//
+// Transitions are validated in `BookingRepository` (`DbError::InvalidStatusTransition`),
+// the side effects of a transition (`effects`) are run by the command layer.
+// After check-in a booking can no longer be cancelled - an early departure is a check-out.
+// ausgecheckt and storniert are final. An expired option is cancelled by the hold-release job.
+//
+// DATABASE: stored as TEXT in bookings.status (CHECK constraint, migration 025),
+// JSON uses the same lowercase names as before ("bestaetigt", ...).
+
+use bytes::BytesMut;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use tokio_postgres::types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BookingStatus {
+    Anfrage,
+    Optioniert,
+    Reserviert,
+    Bestaetigt,
+    Eingecheckt,
+    Ausgecheckt,
+    Storniert,
+}
+
+/// Side effect of a status transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusEffect {
+    /// Queue the "bestaetigung" email (scheduled_emails)
+    ScheduleConfirmationEmail,
+    /// Cleaning task for the day of departure (early check-out)
+    CreateCleaningTask,
+    /// Refund guest credit used for the booking
+    ReleaseCredit,
+    /// Cancel pending emails of the booking
+    CancelScheduledEmails,
+    /// Remove cleaning tasks of the booking
+    RemoveCleaningTasks,
+    /// Offer the freed room to matching waitlist entries
+    OfferToWaitlist,
+}
+
+impl BookingStatus {
+    pub const ALL: [BookingStatus; 7] = [
+        BookingStatus::Anfrage,
+        BookingStatus::Optioniert,
+        BookingStatus::Reserviert,
+        BookingStatus::Bestaetigt,
+        BookingStatus::Eingecheckt,
+        BookingStatus::Ausgecheckt,
+        BookingStatus::Storniert,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BookingStatus::Anfrage => "anfrage",
+            BookingStatus::Optioniert => "optioniert",
+            BookingStatus::Reserviert => "reserviert",
+            BookingStatus::Bestaetigt => "bestaetigt",
+            BookingStatus::Eingecheckt => "eingecheckt",
+            BookingStatus::Ausgecheckt => "ausgecheckt",
+            BookingStatus::Storniert => "storniert",
+        }
+    }
+
+    /// Display name for messages
+    pub fn label(self) -> &'static str {
+        match self {
+            BookingStatus::Anfrage => "Anfrage",
+            BookingStatus::Optioniert => "Optioniert",
+            BookingStatus::Reserviert => "Reserviert",
+            BookingStatus::Bestaetigt => "Bestätigt",
+            BookingStatus::Eingecheckt => "Eingecheckt",
+            BookingStatus::Ausgecheckt => "Ausgecheckt",
+            BookingStatus::Storniert => "Storniert",
+        }
+    }
+
+    /// Statuses a new booking may be created with
+    pub fn is_initial(self) -> bool {
+        matches!(
+            self,
+            BookingStatus::Anfrage | BookingStatus::Optioniert | BookingStatus::Reserviert | BookingStatus::Bestaetigt
+        )
+    }
+
+    /// Provisional hold that expires unless it is confirmed
+    pub fn is_option(self) -> bool {
+        self == BookingStatus::Optioniert
+    }
+
+    /// No further transitions possible
+    pub fn is_final(self) -> bool {
+        matches!(self, BookingStatus::Ausgecheckt | BookingStatus::Storniert)
+    }
+
+    /// Transition table
+    pub fn allowed_transitions(self) -> &'static [BookingStatus] {
+        use BookingStatus::*;
+        match self {
+            Anfrage => &[Reserviert, Bestaetigt, Storniert],
+            Optioniert => &[Reserviert, Bestaetigt, Storniert],
+            Reserviert => &[Bestaetigt, Eingecheckt, Storniert],
+            Bestaetigt => &[Eingecheckt, Storniert],
+            Eingecheckt => &[Ausgecheckt],
+            Ausgecheckt | Storniert => &[],
+        }
+    }
+
+    pub fn can_transition_to(self, to: BookingStatus) -> bool {
+        self.allowed_transitions().contains(&to)
+    }
+
+    /// Ok for allowed transitions and for "no change", otherwise a German error message
+    pub fn validate_transition(self, to: BookingStatus) -> Result<(), String> {
+        if self == to || self.can_transition_to(to) {
+            return Ok(());
+        }
+        if self.is_final() {
+            return Err(format!(
+                "Buchung ist {} - der Status kann nicht mehr geändert werden",
+                self.label()
+            ));
+        }
+        let allowed: Vec<&str> = self.allowed_transitions().iter().map(|s| s.label()).collect();
+        Err(format!(
+            "Statuswechsel von {} nach {} ist nicht erlaubt (möglich: {})",
+            self.label(),
+            to.label(),
+            allowed.join(", ")
+        ))
+    }
+
+    /// Side effects of the transition `self -> to` (none if the status does not change)
+    pub fn effects(self, to: BookingStatus) -> Vec<StatusEffect> {
+        if self == to {
+            return Vec::new();
+        }
+        match to {
+            BookingStatus::Bestaetigt => vec![StatusEffect::ScheduleConfirmationEmail],
+            BookingStatus::Ausgecheckt => vec![StatusEffect::CreateCleaningTask],
+            BookingStatus::Storniert => vec![
+                StatusEffect::ReleaseCredit,
+                StatusEffect::CancelScheduledEmails,
+                StatusEffect::RemoveCleaningTasks,
+                StatusEffect::OfferToWaitlist,
+            ],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Next automatic step by date (check-in day -> eingecheckt, check-out day -> ausgecheckt).
+    /// Anfragen and options are never advanced automatically, they need a decision.
+    pub fn next_by_date(self, today: NaiveDate, checkin: NaiveDate, checkout: NaiveDate) -> Option<BookingStatus> {
+        match self {
+            BookingStatus::Reserviert | BookingStatus::Bestaetigt if today >= checkin => {
+                Some(BookingStatus::Eingecheckt)
+            }
+            BookingStatus::Eingecheckt if today >= checkout => Some(BookingStatus::Ausgecheckt),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BookingStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BookingStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BookingStatus::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s.trim())
+            .ok_or_else(|| format!("Unbekannter Buchungsstatus '{}'", s))
+    }
+}
+
+// ============================================================================
+// DATABASE (TEXT / VARCHAR)
+// ============================================================================
+
+impl<'a> FromSql<'a> for BookingStatus {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let value = <&str as FromSql>::from_sql(ty, raw)?;
+        Ok(value.parse()?)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as FromSql>::accepts(ty)
+    }
+}
+
+impl ToSql for BookingStatus {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_str().to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&str as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        use BookingStatus::*;
+
+        assert!(Anfrage.can_transition_to(Bestaetigt));
+        assert!(Reserviert.can_transition_to(Eingecheckt));
+        assert!(Eingecheckt.can_transition_to(Ausgecheckt));
+        assert!(!Eingecheckt.can_transition_to(Storniert));
+        assert!(!Bestaetigt.can_transition_to(Anfrage));
+        assert!(Storniert.validate_transition(Reserviert).is_err());
+        assert!(Ausgecheckt.validate_transition(Ausgecheckt).is_ok());
+
+        assert_eq!(Reserviert.effects(Bestaetigt), vec![StatusEffect::ScheduleConfirmationEmail]);
+        assert!(Bestaetigt.effects(Bestaetigt).is_empty());
+        assert!(Anfrage.effects(Storniert).contains(&StatusEffect::ReleaseCredit));
+        assert!(Bestaetigt.effects(Storniert).contains(&StatusEffect::OfferToWaitlist));
+        assert!(Optioniert.can_transition_to(Bestaetigt));
+        assert!(!Reserviert.can_transition_to(Optioniert));
+        assert!(Optioniert.effects(Storniert).contains(&StatusEffect::OfferToWaitlist));
+
+        for status in BookingStatus::ALL {
+            assert_eq!(status.as_str().parse::<BookingStatus>(), Ok(status));
+            assert_eq!(status.is_final(), status.allowed_transitions().is_empty());
+            assert!(!status.can_transition_to(status));
+        }
+        assert!("gebucht".parse::<BookingStatus>().is_err());
+    }
+
+    #[test]
+    fn test_next_by_date() {
+        use BookingStatus::*;
+
+        let date = |d: u32| NaiveDate::from_ymd_opt(2025, 6, d).unwrap();
+        let (checkin, checkout) = (date(10), date(14));
+
+        assert_eq!(Reserviert.next_by_date(date(9), checkin, checkout), None);
+        assert_eq!(Bestaetigt.next_by_date(date(10), checkin, checkout), Some(Eingecheckt));
+        assert_eq!(Eingecheckt.next_by_date(date(13), checkin, checkout), None);
+        assert_eq!(Eingecheckt.next_by_date(date(14), checkin, checkout), Some(Ausgecheckt));
+        assert_eq!(Anfrage.next_by_date(date(20), checkin, checkout), None);
+        assert_eq!(Optioniert.next_by_date(date(20), checkin, checkout), None);
+
+        // A past booking walks through every step
+        let mut status = Reserviert;
+        while let Some(next) = status.next_by_date(date(20), checkin, checkout) {
+            assert!(status.can_transition_to(next));
+            status = next;
+        }
+        assert_eq!(status, Ausgecheckt);
+    }
+}
@@ -1,10 +1,9 @@
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager, State};
-use headless_chrome::{Browser, LaunchOptions};
-use headless_chrome::types::PrintToPdfOptions;
 use std::collections::HashMap;
 use chrono::Datelike;
 use crate::database_pg::{pool::DbPool, repositories::cleaning_task_repository::CleaningTaskRepository};
+use crate::native_pdf::{self, Color, Document, Font};
 
 // PDF-kompatibles CleaningTask Struct (mapped von PostgreSQL Model)
 #[derive(Debug, Serialize, Clone)]
@@ -94,47 +93,15 @@ async fn get_cleaning_tasks_for_month(pool: &DbPool, year: i32, month: u32) -> R
 }
 
 /// Generiert HTML für Timeline-PDF mit modernem Design (A4 Querformat, 2 Tabellen)
-fn generate_timeline_html(tasks: Vec<PDFCleaningTask>, year: i32, month: u32) -> String {
+fn generate_timeline_html(tasks: &[PDFCleaningTask], year: i32, month: u32) -> String {
     let month_names = [
         "JANUAR", "FEBRUAR", "MÄRZ", "APRIL", "MAI", "JUNI",
         "JULI", "AUGUST", "SEPTEMBER", "OKTOBER", "NOVEMBER", "DEZEMBER"
     ];
     let month_name = month_names.get((month - 1) as usize).unwrap_or(&"");
 
-    let days_in_month = match month {
-        2 => if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) { 29 } else { 28 },
-        4 | 6 | 9 | 11 => 30,
-        _ => 31,
-    };
-
-    // Gruppiere Tasks nach Zimmer
-    let mut room_tasks: HashMap<String, Vec<PDFCleaningTask>> = HashMap::new();
-    for task in tasks.clone() {
-        room_tasks.entry(task.room_name.clone())
-            .or_insert_with(Vec::new)
-            .push(task);
-    }
-
-    // Sortiere Zimmer
-    let mut rooms: Vec<String> = room_tasks.keys().cloned().collect();
-    rooms.sort_by(|a, b| {
-        let a_location = room_tasks.get(a)
-            .and_then(|tasks| tasks.first())
-            .and_then(|t| t.room_location.as_ref())
-            .map(|s| s.as_str())
-            .unwrap_or("");
-        let b_location = room_tasks.get(b)
-            .and_then(|tasks| tasks.first())
-            .and_then(|t| t.room_location.as_ref())
-            .map(|s| s.as_str())
-            .unwrap_or("");
-
-        if a_location != b_location {
-            a_location.cmp(b_location)
-        } else {
-            alphanumeric_sort::compare_str(a, b)
-        }
-    });
+    let days_in_month = days_in_month(year, month);
+    let (rooms, room_tasks) = group_by_room(tasks);
 
     // Teile den Monat: Tag 1-15 und Tag 16-Ende
     let split_day = 16;
@@ -143,21 +110,7 @@ fn generate_timeline_html(tasks: Vec<PDFCleaningTask>, year: i32, month: u32) ->
     let table1 = generate_table_html(&rooms, &room_tasks, year, month, 1, split_day - 1);
     let table2 = generate_table_html(&rooms, &room_tasks, year, month, split_day, days_in_month);
 
-    // Count Stats
-    // FIX (2025-10-24): Zähle ALLE Checkout-Tasks, nicht nur die mit Emojis
-    // Check-out Tasks haben task_type="checkout" im extras JSON
-    let total_checkouts: usize = tasks.iter()
-        .filter(|t| {
-            // Parse extras JSON to check task_type
-            if let Ok(extras) = serde_json::from_str::<serde_json::Value>(&t.extras) {
-                extras.get("task_type").and_then(|v| v.as_str()) == Some("checkout")
-            } else {
-                // Fallback: Wenn JSON-Parsing fehlschlägt, prüfe ob emojis_end gesetzt ist
-                // (alte Logik als Backup für ältere Tasks ohne extras field)
-                !t.emojis_end.is_empty()
-            }
-        })
-        .count();
+    let total_checkouts = count_checkouts(tasks);
     let total_rooms = rooms.len();
 
     format!(
@@ -497,6 +450,81 @@ fn generate_timeline_html(tasks: Vec<PDFCleaningTask>, year: i32, month: u32) ->
     )
 }
 
+/// Tage im Monat (Schaltjahre berücksichtigt)
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 => if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Gruppiert Tasks nach Zimmer, Zimmer sortiert nach Standort und Nummer
+fn group_by_room(tasks: &[PDFCleaningTask]) -> (Vec<String>, HashMap<String, Vec<PDFCleaningTask>>) {
+    let mut room_tasks: HashMap<String, Vec<PDFCleaningTask>> = HashMap::new();
+    for task in tasks {
+        room_tasks.entry(task.room_name.clone())
+            .or_default()
+            .push(task.clone());
+    }
+
+    let mut rooms: Vec<String> = room_tasks.keys().cloned().collect();
+    rooms.sort_by(|a, b| {
+        let a_location = room_location(&room_tasks[a]);
+        let b_location = room_location(&room_tasks[b]);
+
+        if a_location != b_location {
+            a_location.cmp(b_location)
+        } else {
+            alphanumeric_sort::compare_str(a, b)
+        }
+    });
+
+    (rooms, room_tasks)
+}
+
+fn room_location(tasks: &[PDFCleaningTask]) -> &str {
+    tasks.first()
+        .and_then(|t| t.room_location.as_ref())
+        .map(|s| s.as_str())
+        .unwrap_or("")
+}
+
+/// Wochentag-Kürzel ("MO".."SO") und ob der Tag auf ein Wochenende fällt
+fn weekday_label(year: i32, month: u32, day: u32) -> (&'static str, bool) {
+    chrono::NaiveDate::from_ymd_opt(year, month, day)
+        .map(|d| {
+            let wd = d.weekday();
+            let short = match wd {
+                chrono::Weekday::Mon => "MO",
+                chrono::Weekday::Tue => "DI",
+                chrono::Weekday::Wed => "MI",
+                chrono::Weekday::Thu => "DO",
+                chrono::Weekday::Fri => "FR",
+                chrono::Weekday::Sat => "SA",
+                chrono::Weekday::Sun => "SO",
+            };
+            let weekend = matches!(wd, chrono::Weekday::Sat | chrono::Weekday::Sun);
+            (short, weekend)
+        })
+        .unwrap_or(("", false))
+}
+
+/// Zählt ALLE Checkout-Tasks, nicht nur die mit Emojis
+/// (Check-out Tasks haben task_type="checkout" im extras JSON)
+fn count_checkouts(tasks: &[PDFCleaningTask]) -> usize {
+    tasks.iter()
+        .filter(|t| {
+            if let Ok(extras) = serde_json::from_str::<serde_json::Value>(&t.extras) {
+                extras.get("task_type").and_then(|v| v.as_str()) == Some("checkout")
+            } else {
+                // Fallback für ältere Tasks ohne extras field
+                !t.emojis_end.is_empty()
+            }
+        })
+        .count()
+}
+
 /// Informationen über einen Tag im Zeitraum
 #[derive(Debug, Clone)]
 struct DayInfo {
@@ -508,6 +536,133 @@ struct DayInfo {
     guest_count: i64,
 }
 
+/// Belegung eines Zimmers pro Tag ("YYYY-MM-DD" -> DayInfo), nur Tage im gewählten Monat
+fn room_day_infos(room: &str, tasks: &[PDFCleaningTask], year: i32, month: u32) -> HashMap<String, DayInfo> {
+    // Gruppiere Tasks nach booking_id und verwende ORIGINAL checkin/checkout times
+    // FIX: Alle Felder müssen geupdated werden, nicht nur bei or_insert()!
+    let mut booking_ranges: HashMap<i64, (String, String, String, String, String, i64)> = HashMap::new();
+
+    println!("🔍 DEBUG: Verarbeite {} tasks für Zimmer {}", tasks.len(), room);
+    for task in tasks {
+        println!("  🔍 Task: booking_id={}, date={}, checkin_time={:?}, checkout_time='{}', emojis_start='{}', emojis_end='{}', guest_count={}",
+                 task.booking_id, task.date, task.checkin_time, task.checkout_time, task.emojis_start, task.emojis_end, task.guest_count);
+        println!("  🔍 EXTRAS JSON: {}", task.extras);
+
+        // Parse extras JSON um checkin_date und checkout_date zu holen
+        let extras_json: serde_json::Value = serde_json::from_str(&task.extras)
+            .unwrap_or(serde_json::json!({}));
+
+        let checkin_date = extras_json.get("checkin_date")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let checkout_date = extras_json.get("checkout_date")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+
+        println!("  📅 PARSED DATES: checkin_date='{}', checkout_date='{}'", checkin_date, checkout_date);
+
+        let entry = booking_ranges.entry(task.booking_id).or_insert((
+            String::new(),
+            String::new(),
+            task.guest_name.clone(),
+            String::new(), // emojis_start
+            String::new(), // emojis_end
+            task.guest_count, // guest_count
+        ));
+
+        // Update checkin_date wenn verfügbar (aus extras JSON!)
+        if !checkin_date.is_empty() {
+            println!("    ✅ Update checkin_date: {}", checkin_date);
+            entry.0 = checkin_date;
+        }
+
+        // Update checkout_date wenn verfügbar (aus extras JSON!)
+        if !checkout_date.is_empty() {
+            println!("    ✅ Update checkout_date: {}", checkout_date);
+            entry.1 = checkout_date;
+        }
+
+        // Sammle emojis_start (vom Check-in Task)
+        if !task.emojis_start.is_empty() {
+            entry.3 = task.emojis_start.clone();
+        }
+
+        // Sammle emojis_end (vom Check-out Task)
+        if !task.emojis_end.is_empty() {
+            entry.4 = task.emojis_end.clone();
+        }
+    }
+
+    println!("🔍 DEBUG: Aggregierte {} booking_ranges:", booking_ranges.len());
+    for (booking_id, (checkin, checkout, guest, emojis_s, emojis_e, guest_cnt)) in &booking_ranges {
+        println!("  📋 Booking {}: checkin='{}', checkout='{}', guest='{}', emojis_start='{}', emojis_end='{}', guest_count={}",
+                 booking_id, checkin, checkout, guest, emojis_s, emojis_e, guest_cnt);
+    }
+
+    // Erstelle vollständige Timeline mit allen Tagen zwischen Check-in und Check-out
+    let mut day_info_map: HashMap<String, DayInfo> = HashMap::new();
+
+    for (booking_id, (checkin_date, checkout_date, guest_name, emojis_start, emojis_end, guest_count)) in booking_ranges {
+        // FIX (2025-10-21): Verwende ORIGINAL Daten - KEINE Monatsgrenzen als Fallback!
+        // Dies verhindert, dass der gesamte Monat gefüllt wird
+
+        let effective_start = &checkin_date;
+        let effective_end = &checkout_date;
+
+        // Skip wenn Daten fehlen (keine Monatsgrenzen mehr!)
+        if checkin_date.is_empty() || checkout_date.is_empty() {
+            println!("⚠️  SKIPPING Booking {}: checkin='{}', checkout='{}' (LEER!)", booking_id, checkin_date, checkout_date);
+            continue; // Skip diese Buchung komplett
+        }
+
+        println!("✅ PROCESSING Booking {}: checkin='{}', checkout='{}'", booking_id, checkin_date, checkout_date);
+
+        let start = chrono::NaiveDate::parse_from_str(effective_start, "%Y-%m-%d").ok();
+        let end = chrono::NaiveDate::parse_from_str(effective_end, "%Y-%m-%d").ok();
+
+        if let (Some(start_date), Some(end_date)) = (start, end) {
+            let mut current = start_date;
+
+            // Berechne Monatsgrenzen für Filter
+            let month_start = chrono::NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+            let month_end = if month == 12 {
+                chrono::NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap().pred_opt().unwrap()
+            } else {
+                chrono::NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap().pred_opt().unwrap()
+            };
+
+            while current <= end_date {
+                // NUR Tage im gewählten Monat einfügen!
+                if current >= month_start && current <= month_end {
+                    let date_str = current.format("%Y-%m-%d").to_string();
+                    // FIX: is_checkin IMMER true am ersten Tag, egal ob Emojis vorhanden oder nicht!
+                    let is_checkin = current == start_date;
+                    let is_checkout = current == end_date; // IMMER rot bei Check-out, egal ob Emojis oder nicht
+
+                    println!("  ✅ Added day {} to map (in month range)", date_str);
+
+                    day_info_map.insert(date_str, DayInfo {
+                        guest_name: guest_name.clone(),
+                        is_checkin,
+                        is_checkout,
+                        emojis_start: if is_checkin { emojis_start.clone() } else { String::new() },
+                        emojis_end: if is_checkout { emojis_end.clone() } else { String::new() },
+                        guest_count,
+                    });
+                } else {
+                    println!("  ⏭️  Skipped day {} (outside month range)", current.format("%Y-%m-%d"));
+                }
+
+                current = current.succ_opt().unwrap();
+            }
+        }
+    }
+
+    day_info_map
+}
+
 /// Generiert eine einzelne Tabelle für einen Tag-Bereich
 fn generate_table_html(
     rooms: &[String],
@@ -523,25 +678,7 @@ fn generate_table_html(
     // Header generieren
     let mut header_html = String::from("<tr><th>Zimmer</th>");
     for day in start_day..=end_day {
-        let date_str = format!("{}-{:02}-{:02}", year, month, day);
-        let date_obj = chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").ok();
-
-        let (weekday_short, is_weekend) = date_obj
-            .map(|d| {
-                let wd = d.weekday();
-                let short = match wd {
-                    chrono::Weekday::Mon => "MO",
-                    chrono::Weekday::Tue => "DI",
-                    chrono::Weekday::Wed => "MI",
-                    chrono::Weekday::Thu => "DO",
-                    chrono::Weekday::Fri => "FR",
-                    chrono::Weekday::Sat => "SA",
-                    chrono::Weekday::Sun => "SO",
-                };
-                let weekend = matches!(wd, chrono::Weekday::Sat | chrono::Weekday::Sun);
-                (short, weekend)
-            })
-            .unwrap_or(("", false));
+        let (weekday_short, is_weekend) = weekday_label(year, month, day);
 
         let weekend_class = if is_weekend { " weekend" } else { "" };
 
@@ -566,10 +703,7 @@ fn generate_table_html(
 
     for room in rooms {
         let tasks = room_tasks.get(room).unwrap();
-        let location = tasks.first()
-            .and_then(|t| t.room_location.as_ref())
-            .map(|s| s.as_str())
-            .unwrap_or("");
+        let location = room_location(tasks);
 
         let location_icon = match location {
             "Fall" => "🍂",
@@ -582,127 +716,7 @@ fn generate_table_html(
             room, location_icon, location
         ));
 
-        // Gruppiere Tasks nach booking_id und verwende ORIGINAL checkin/checkout times
-        // FIX: Alle Felder müssen geupdated werden, nicht nur bei or_insert()!
-        let mut booking_ranges: HashMap<i64, (String, String, String, String, String, i64)> = HashMap::new();
-
-        println!("🔍 DEBUG: Verarbeite {} tasks für Zimmer {}", tasks.len(), room);
-        for task in tasks {
-            println!("  🔍 Task: booking_id={}, date={}, checkin_time={:?}, checkout_time='{}', emojis_start='{}', emojis_end='{}', guest_count={}",
-                     task.booking_id, task.date, task.checkin_time, task.checkout_time, task.emojis_start, task.emojis_end, task.guest_count);
-            println!("  🔍 EXTRAS JSON: {}", task.extras);
-
-            // Parse extras JSON um checkin_date und checkout_date zu holen
-            let extras_json: serde_json::Value = serde_json::from_str(&task.extras)
-                .unwrap_or(serde_json::json!({}));
-
-            let checkin_date = extras_json.get("checkin_date")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-            let checkout_date = extras_json.get("checkout_date")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
-
-            println!("  📅 PARSED DATES: checkin_date='{}', checkout_date='{}'", checkin_date, checkout_date);
-
-            let entry = booking_ranges.entry(task.booking_id).or_insert((
-                String::new(),
-                String::new(),
-                task.guest_name.clone(),
-                String::new(), // emojis_start
-                String::new(), // emojis_end
-                task.guest_count, // guest_count
-            ));
-
-            // Update checkin_date wenn verfügbar (aus extras JSON!)
-            if !checkin_date.is_empty() {
-                println!("    ✅ Update checkin_date: {}", checkin_date);
-                entry.0 = checkin_date;
-            }
-
-            // Update checkout_date wenn verfügbar (aus extras JSON!)
-            if !checkout_date.is_empty() {
-                println!("    ✅ Update checkout_date: {}", checkout_date);
-                entry.1 = checkout_date;
-            }
-
-            // Sammle emojis_start (vom Check-in Task)
-            if !task.emojis_start.is_empty() {
-                entry.3 = task.emojis_start.clone();
-            }
-
-            // Sammle emojis_end (vom Check-out Task)
-            if !task.emojis_end.is_empty() {
-                entry.4 = task.emojis_end.clone();
-            }
-        }
-
-        println!("🔍 DEBUG: Aggregierte {} booking_ranges:", booking_ranges.len());
-        for (booking_id, (checkin, checkout, guest, emojis_s, emojis_e, guest_cnt)) in &booking_ranges {
-            println!("  📋 Booking {}: checkin='{}', checkout='{}', guest='{}', emojis_start='{}', emojis_end='{}', guest_count={}",
-                     booking_id, checkin, checkout, guest, emojis_s, emojis_e, guest_cnt);
-        }
-
-        // Erstelle vollständige Timeline mit allen Tagen zwischen Check-in und Check-out
-        let mut day_info_map: HashMap<String, DayInfo> = HashMap::new();
-
-        for (booking_id, (checkin_date, checkout_date, guest_name, emojis_start, emojis_end, guest_count)) in booking_ranges {
-            // FIX (2025-10-21): Verwende ORIGINAL Daten - KEINE Monatsgrenzen als Fallback!
-            // Dies verhindert, dass der gesamte Monat gefüllt wird
-
-            let effective_start = &checkin_date;
-            let effective_end = &checkout_date;
-
-            // Skip wenn Daten fehlen (keine Monatsgrenzen mehr!)
-            if checkin_date.is_empty() || checkout_date.is_empty() {
-                println!("⚠️  SKIPPING Booking {}: checkin='{}', checkout='{}' (LEER!)", booking_id, checkin_date, checkout_date);
-                continue; // Skip diese Buchung komplett
-            }
-
-            println!("✅ PROCESSING Booking {}: checkin='{}', checkout='{}'", booking_id, checkin_date, checkout_date);
-
-            let start = chrono::NaiveDate::parse_from_str(effective_start, "%Y-%m-%d").ok();
-            let end = chrono::NaiveDate::parse_from_str(effective_end, "%Y-%m-%d").ok();
-
-            if let (Some(start_date), Some(end_date)) = (start, end) {
-                let mut current = start_date;
-
-                // Berechne Monatsgrenzen für Filter
-                let month_start = chrono::NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-                let month_end = if month == 12 {
-                    chrono::NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap().pred_opt().unwrap()
-                } else {
-                    chrono::NaiveDate::from_ymd_opt(year, month + 1, 1).unwrap().pred_opt().unwrap()
-                };
-
-                while current <= end_date {
-                    // NUR Tage im gewählten Monat einfügen!
-                    if current >= month_start && current <= month_end {
-                        let date_str = current.format("%Y-%m-%d").to_string();
-                        // FIX: is_checkin IMMER true am ersten Tag, egal ob Emojis vorhanden oder nicht!
-                        let is_checkin = current == start_date;
-                        let is_checkout = current == end_date; // IMMER rot bei Check-out, egal ob Emojis oder nicht
-
-                        println!("  ✅ Added day {} to map (in month range)", date_str);
-
-                        day_info_map.insert(date_str, DayInfo {
-                            guest_name: guest_name.clone(),
-                            is_checkin,
-                            is_checkout,
-                            emojis_start: if is_checkin { emojis_start.clone() } else { String::new() },
-                            emojis_end: if is_checkout { emojis_end.clone() } else { String::new() },
-                            guest_count,
-                        });
-                    } else {
-                        println!("  ⏭️  Skipped day {} (outside month range)", current.format("%Y-%m-%d"));
-                    }
-
-                    current = current.succ_opt().unwrap();
-                }
-            }
-        }
+        let day_info_map = room_day_infos(room, tasks, year, month);
 
         // Durchlaufe jeden Tag und zeige die passende Zelle
         for day in start_day..=end_day {
@@ -759,7 +773,217 @@ fn generate_table_html(
     )
 }
 
-/// Exportiert Timeline als PDF mit headless_chrome
+// ============================================================================
+// NATIVE PDF (pdf-writer) - gleiches Layout wie das HTML, A4 Querformat
+// ============================================================================
+
+const PAGE_WIDTH: f32 = native_pdf::A4_HEIGHT;
+const PAGE_HEIGHT: f32 = native_pdf::A4_WIDTH;
+const MARGIN: f32 = 22.0;
+const ROOM_COLUMN: f32 = 92.0;
+const DAY_COLUMNS: u32 = 16;
+const HEADER_ROW: f32 = 28.0;
+const ROW: f32 = 20.0;
+const LEGEND_HEIGHT: f32 = 24.0;
+
+const HEADER_BLUE: Color = Color(59, 130, 246);
+const TABLE_HEADER: Color = Color(30, 41, 59);
+const WEEKEND: Color = Color(254, 243, 199);
+const WEEKEND_TEXT: Color = Color(146, 64, 14);
+const OCCUPIED: Color = Color(37, 99, 235);
+const CHECKOUT: Color = Color(220, 38, 38);
+const BORDER: Color = Color(226, 232, 240);
+const TEXT_SECONDARY: Color = Color(71, 85, 105);
+const BG_SECONDARY: Color = Color(248, 250, 252);
+
+/// Rendert den Putzplan nativ. Emojis gibt es in Helvetica nicht - stattdessen zeigt
+/// ein weißer Punkt pro Service-Emoji an, dass an dem Tag etwas vorzubereiten ist.
+fn render_timeline_pdf(tasks: &[PDFCleaningTask], year: i32, month: u32) -> Result<Vec<u8>, String> {
+    let month_names = [
+        "JANUAR", "FEBRUAR", "MÄRZ", "APRIL", "MAI", "JUNI",
+        "JULI", "AUGUST", "SEPTEMBER", "OKTOBER", "NOVEMBER", "DEZEMBER"
+    ];
+    let month_name = month_names.get((month - 1) as usize).unwrap_or(&"");
+
+    let days_in_month = days_in_month(year, month);
+    let (rooms, room_tasks) = group_by_room(tasks);
+    let day_infos: HashMap<&String, HashMap<String, DayInfo>> = rooms
+        .iter()
+        .map(|room| (room, room_day_infos(room, &room_tasks[room], year, month)))
+        .collect();
+
+    let mut doc = Document::new(PAGE_WIDTH, PAGE_HEIGHT);
+    let content_width = PAGE_WIDTH - 2.0 * MARGIN;
+
+    // Header
+    doc.fill_rect(MARGIN, MARGIN, content_width, 52.0, HEADER_BLUE);
+    doc.text(MARGIN + 16.0, MARGIN + 22.0, 16.0, Font::Bold, Color::WHITE, "REINIGUNGSPLAN");
+    doc.text_right(
+        PAGE_WIDTH - MARGIN - 16.0,
+        MARGIN + 22.0,
+        13.0,
+        Font::Bold,
+        Color::WHITE,
+        &format!("{} {}", month_name, year),
+    );
+    let stats = format!(
+        "Zimmer gesamt: {}     Abreisen gesamt: {}",
+        rooms.len(),
+        count_checkouts(tasks)
+    );
+    doc.text(MARGIN + 16.0, MARGIN + 41.0, 9.5, Font::Regular, Color(219, 234, 254), &stats);
+
+    // Tabellen: Tag 1-15 und Tag 16-Ende
+    let mut y = MARGIN + 64.0;
+    for (start_day, end_day) in [(1, 15), (16, days_in_month)] {
+        y = timeline_table(&mut doc, &rooms, &room_tasks, &day_infos, year, month, start_day, end_day, y);
+        y += 12.0;
+    }
+
+    // Legende
+    if y + LEGEND_HEIGHT > PAGE_HEIGHT - MARGIN {
+        doc.add_page();
+        y = MARGIN;
+    }
+    doc.fill_rect(MARGIN, y, content_width, LEGEND_HEIGHT, BG_SECONDARY);
+    let mut x = MARGIN + 12.0;
+    for (color, label) in [
+        (OCCUPIED, "Belegt"),
+        (CHECKOUT, "Abreise (Reinigung erforderlich)"),
+    ] {
+        doc.fill_rect(x, y + 7.0, 20.0, 10.0, color);
+        doc.text(x + 26.0, y + 15.0, 8.5, Font::Regular, TEXT_SECONDARY, label);
+        x += 26.0 + native_pdf::text_width(label, 8.5, Font::Regular) + 24.0;
+    }
+    doc.fill_rect(x, y + 7.0, 20.0, 10.0, OCCUPIED);
+    service_markers(&mut doc, "*", x, y + 10.0);
+    doc.text(x + 26.0, y + 15.0, 8.5, Font::Regular, TEXT_SECONDARY, "= Service (Emoji in der App)");
+
+    Ok(doc.finish())
+}
+
+/// Zeichnet eine Tabelle ab `y`, bricht bei Bedarf um (Kopfzeile wird wiederholt).
+/// Gibt die y-Position unter der Tabelle zurück.
+#[allow(clippy::too_many_arguments)]
+fn timeline_table(
+    doc: &mut Document,
+    rooms: &[String],
+    room_tasks: &HashMap<String, Vec<PDFCleaningTask>>,
+    day_infos: &HashMap<&String, HashMap<String, DayInfo>>,
+    year: i32,
+    month: u32,
+    start_day: u32,
+    end_day: u32,
+    mut y: f32,
+) -> f32 {
+    let day_width = (PAGE_WIDTH - 2.0 * MARGIN - ROOM_COLUMN) / DAY_COLUMNS as f32;
+    let table_width = ROOM_COLUMN + day_width * (end_day - start_day + 1) as f32;
+    let day_x = |day: u32| MARGIN + ROOM_COLUMN + (day - start_day) as f32 * day_width;
+
+    if y + HEADER_ROW + ROW > PAGE_HEIGHT - MARGIN {
+        doc.add_page();
+        y = MARGIN;
+    }
+    timeline_table_header(doc, year, month, start_day, end_day, day_width, y);
+    y += HEADER_ROW;
+
+    for room in rooms {
+        if y + ROW > PAGE_HEIGHT - MARGIN {
+            doc.add_page();
+            y = MARGIN;
+            timeline_table_header(doc, year, month, start_day, end_day, day_width, y);
+            y += HEADER_ROW;
+        }
+
+        // Wochenend-Spalten hinterlegen
+        for day in start_day..=end_day {
+            if weekday_label(year, month, day).1 {
+                doc.fill_rect(day_x(day), y, day_width, ROW, WEEKEND);
+            }
+        }
+
+        doc.text(MARGIN + 4.0, y + 9.0, 8.0, Font::Bold, Color::BLACK, room);
+        let location = room_location(&room_tasks[room]);
+        doc.text(MARGIN + 4.0, y + 16.5, 6.0, Font::Regular, TEXT_SECONDARY, location);
+
+        let infos = &day_infos[room];
+        for day in start_day..=end_day {
+            let date_str = format!("{}-{:02}-{:02}", year, month, day);
+            let Some(info) = infos.get(&date_str) else {
+                continue;
+            };
+
+            let (x, cell_y) = (day_x(day) + 1.0, y + 1.5);
+            let (cell_width, cell_height) = (day_width - 2.0, ROW - 3.0);
+            if info.is_checkout {
+                // Check-out Tag - rote Zelle nur mit Service-Markern
+                doc.fill_rect(x, cell_y, cell_width, cell_height, CHECKOUT);
+                service_markers(doc, &info.emojis_end, x, cell_y + cell_height - 6.0);
+            } else if info.is_checkin {
+                // Check-in Tag - blaue Zelle mit Personenanzahl + Service-Markern
+                doc.fill_rect(x, cell_y, cell_width, cell_height, OCCUPIED);
+                if info.guest_count > 0 {
+                    let persons = format!("{} Pers.", info.guest_count);
+                    doc.text_right(x + cell_width - 2.0, cell_y + 7.0, 5.5, Font::Bold, Color::WHITE, &persons);
+                }
+                service_markers(doc, &info.emojis_start, x, cell_y + cell_height - 6.0);
+            } else {
+                // Belegt Tag dazwischen - blaue Zelle mit Gästename
+                doc.fill_rect(x, cell_y, cell_width, cell_height, OCCUPIED);
+                let name = native_pdf::truncate(&info.guest_name, 5.5, Font::Regular, cell_width - 3.0);
+                doc.text(x + 1.5, cell_y + 10.5, 5.5, Font::Regular, Color::WHITE, &name);
+            }
+        }
+
+        doc.line(MARGIN, y + ROW, MARGIN + table_width, y + ROW, 0.5, BORDER);
+        y += ROW;
+    }
+
+    y
+}
+
+#[allow(clippy::too_many_arguments)]
+fn timeline_table_header(
+    doc: &mut Document,
+    year: i32,
+    month: u32,
+    start_day: u32,
+    end_day: u32,
+    day_width: f32,
+    y: f32,
+) {
+    let month_names_short = ["JAN", "FEB", "MÄR", "APR", "MAI", "JUN", "JUL", "AUG", "SEP", "OKT", "NOV", "DEZ"];
+    let month_short = month_names_short.get((month - 1) as usize).unwrap_or(&"");
+
+    let table_width = ROOM_COLUMN + day_width * (end_day - start_day + 1) as f32;
+    doc.fill_rect(MARGIN, y, table_width, HEADER_ROW, TABLE_HEADER);
+    doc.text(MARGIN + 4.0, y + 17.0, 8.0, Font::Bold, Color::WHITE, "Zimmer");
+
+    for day in start_day..=end_day {
+        let x = MARGIN + ROOM_COLUMN + (day - start_day) as f32 * day_width;
+        let center = x + day_width / 2.0;
+        let (weekday_short, is_weekend) = weekday_label(year, month, day);
+        let text_color = if is_weekend {
+            doc.fill_rect(x, y, day_width, HEADER_ROW, WEEKEND);
+            WEEKEND_TEXT
+        } else {
+            Color::WHITE
+        };
+        doc.text_centered(center, y + 8.5, 5.5, Font::Bold, text_color, weekday_short);
+        doc.text_centered(center, y + 18.0, 9.0, Font::Bold, text_color, &day.to_string());
+        doc.text_centered(center, y + 25.0, 5.0, Font::Regular, text_color, month_short);
+    }
+}
+
+/// Ein weißer Punkt pro Service-Emoji (kommagetrennt aus cleaning_task_repository)
+fn service_markers(doc: &mut Document, emojis: &str, x: f32, y: f32) {
+    let count = emojis.split(',').filter(|e| !e.trim().is_empty()).count();
+    for i in 0..count.min(6) {
+        doc.fill_rect(x + 2.0 + i as f32 * 5.0, y, 3.5, 3.5, Color::WHITE);
+    }
+}
+
+/// Exportiert Timeline als PDF (nativ, headless Chrome nur als Fallback)
 #[tauri::command]
 pub async fn export_cleaning_timeline_pdf(
     pool: State<'_, DbPool>,
@@ -780,11 +1004,7 @@ pub async fn export_cleaning_timeline_pdf(
         return Err(format!("Keine Aufgaben für {}/{:02} gefunden", month, year));
     }
 
-    // 2. Generiere HTML
-    println!("📝 Generating timeline HTML...");
-    let html = generate_timeline_html(tasks, year, month);
-
-    // 3. Putzplan-Ordner im App-Datenverzeichnis (NICHT in src-tauri!)
+    // 2. Putzplan-Ordner im App-Datenverzeichnis (NICHT in src-tauri!)
     // FIX: Verhindert Endlosschleife durch Cargo-Rebuild bei Dateiänderungen
     let app_data_dir = _app.path().app_data_dir()
         .map_err(|e| format!("Fehler app_data_dir: {}", e))?;
@@ -795,78 +1015,30 @@ pub async fn export_cleaning_timeline_pdf(
 
     println!("📁 Putzplan-Ordner: {:?}", putzplan_dir);
 
-    // 4. Temp HTML schreiben
+    // 3. PDF rendern (A4 Querformat)
+    println!("📄 Generating PDF...");
+    let pdf_data = native_pdf::render_with_fallback(
+        || render_timeline_pdf(&tasks, year, month),
+        true,
+        || Ok(generate_timeline_html(&tasks, year, month)),
+    )?;
+
     let month_names = [
         "Januar", "Februar", "Maerz", "April", "Mai", "Juni",
         "Juli", "August", "September", "Oktober", "November", "Dezember"
     ];
     let month_name = month_names.get((month - 1) as usize).unwrap_or(&"Monat");
 
-    let temp_html_path = putzplan_dir.join(format!("temp_putzplan_{}_{:02}.html", year, month));
-    std::fs::write(&temp_html_path, &html)
-        .map_err(|e| format!("Fehler HTML schreiben: {}", e))?;
-
-    // 5. Headless Chrome starten
-    println!("🌐 Starting headless Chrome...");
-    let launch_options = LaunchOptions::default_builder()
-        .headless(true)
-        .build()
-        .map_err(|e| format!("Chrome launch options: {}", e))?;
-
-    let browser = Browser::new(launch_options)
-        .map_err(|e| format!("Chrome starten Fehler: {}", e))?;
-    println!("✅ Chrome started");
-
-    let tab = browser.new_tab()
-        .map_err(|e| format!("Chrome tab Fehler: {}", e))?;
-
-    // 6. HTML laden
-    let file_url = format!("file://{}", temp_html_path.display());
-    tab.navigate_to(&file_url)
-        .map_err(|e| format!("Chrome navigate Fehler: {}", e))?;
-
-    tab.wait_until_navigated()
-        .map_err(|e| format!("Chrome wait Fehler: {}", e))?;
-
-    std::thread::sleep(std::time::Duration::from_millis(500));
-
-    // 7. PDF generieren (A4 Querformat!)
-    println!("📄 Generating PDF...");
-    let pdf_data = tab.print_to_pdf(Some(PrintToPdfOptions {
-        landscape: Some(true),
-        display_header_footer: Some(false),
-        print_background: Some(true),
-        scale: Some(1.0),
-        paper_width: Some(11.69), // A4 width in inches
-        paper_height: Some(8.27), // A4 height in inches
-        margin_top: Some(0.0),
-        margin_bottom: Some(0.0),
-        margin_left: Some(0.0),
-        margin_right: Some(0.0),
-        page_ranges: None,
-        ignore_invalid_page_ranges: None,
-        header_template: None,
-        footer_template: None,
-        prefer_css_page_size: Some(true),
-        transfer_mode: None,
-        generate_document_outline: Some(false),
-        generate_tagged_pdf: Some(false),
-    }))
-    .map_err(|e| format!("PDF generieren Fehler: {}", e))?;
-
-    // 8. PDF speichern
+    // 4. PDF speichern
     let file_name = format!("DPolG_Putzplan_{}_{}.pdf", month_name, year);
     let output_path = putzplan_dir.join(&file_name);
 
     std::fs::write(&output_path, pdf_data)
         .map_err(|e| format!("PDF speichern Fehler: {}", e))?;
 
-    // 9. Temp HTML löschen
-    std::fs::remove_file(&temp_html_path).ok();
-
     println!("✅ PDF erstellt: {:?}", output_path);
 
-    // 10. PDF automatisch öffnen
+    // 5. PDF automatisch öffnen
     println!("📂 Öffne PDF...");
 
     #[cfg(target_os = "macos")]
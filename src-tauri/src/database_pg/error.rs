@@ -25,6 +25,8 @@ pub enum DbError {
     ConflictError(String),
     /// Double booking conflict - room is already booked for the requested period
     DoubleBookingError(String),
+    /// Booking status change not allowed by the state machine (booking_status.rs)
+    InvalidStatusTransition(String),
     /// General database error
     Other(String),
 }
@@ -40,6 +42,7 @@ impl fmt::Display for DbError {
             DbError::ConstraintViolation(msg) => write!(f, "Constraint violation: {}", msg),
             DbError::ConflictError(msg) => write!(f, "Conflict: {}", msg),
             DbError::DoubleBookingError(msg) => write!(f, "Doppelbuchung: {}", msg),
+            DbError::InvalidStatusTransition(msg) => write!(f, "Ungültiger Statuswechsel: {}", msg),
             DbError::Other(msg) => write!(f, "Database error: {}", msg),
         }
     }
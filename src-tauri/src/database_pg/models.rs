@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use tokio_postgres::Row;
 
+use crate::bank_import::MatchProposal;
+use crate::booking_status::BookingStatus;
+use crate::money::Money;
+
 // ============================================================================
 // ROOM MODELS
 // ============================================================================
@@ -11,15 +15,20 @@ pub struct Room {
     pub name: String,
     pub gebaeude_typ: String,
     pub capacity: i32,
-    pub nebensaison_preis: Option<f64>,
-    pub hauptsaison_preis: Option<f64>,
-    pub endreinigung: Option<f64>,
+    pub nebensaison_preis: Option<Money>,
+    pub hauptsaison_preis: Option<Money>,
+    pub endreinigung: Option<Money>,
+    pub basis_belegung: Option<i32>,
+    pub aufpreis_erwachsener: Option<Money>,
+    pub steuerkategorie: Option<String>,
     pub ort: String,
     pub schluesselcode: Option<String>,
     pub street_address: Option<String>,
     pub postal_code: Option<String>,
     pub city: Option<String>,
     pub notizen: Option<String>,
+    pub hunde_erlaubt: Option<bool>,
+    pub erdgeschoss: Option<bool>,
     pub created_by: Option<String>,
     pub updated_by: Option<String>,
 }
@@ -34,18 +43,31 @@ impl From<Row> for Room {
             nebensaison_preis: row.get("nebensaison_preis"),
             hauptsaison_preis: row.get("hauptsaison_preis"),
             endreinigung: row.get("endreinigung"),
+            basis_belegung: row.try_get("basis_belegung").ok().flatten(),
+            aufpreis_erwachsener: row.try_get("aufpreis_erwachsener").ok().flatten(),
+            steuerkategorie: row.try_get("steuerkategorie").ok().flatten(),
             ort: row.get("ort"),
             schluesselcode: row.get("schluesselcode"),
             street_address: row.get("street_address"),
             postal_code: row.get("postal_code"),
             city: row.get("city"),
             notizen: row.get("notizen"),
+            hunde_erlaubt: row.try_get("hunde_erlaubt").ok().flatten(),
+            erdgeschoss: row.try_get("erdgeschoss").ok().flatten(),
             created_by: row.get("created_by"),
             updated_by: row.get("updated_by"),
         }
     }
 }
 
+/// Room free for a whole stay, with the neighbouring stays or blocks (room search)
+#[derive(Debug, Clone)]
+pub struct FreeRoom {
+    pub room: Room,
+    pub previous_checkout: Option<String>,
+    pub next_checkin: Option<String>,
+}
+
 // ============================================================================
 // GUEST MODELS
 // ============================================================================
@@ -91,6 +113,15 @@ pub struct Guest {
     pub automail_sprache: Option<String>,
     pub created_by: Option<String>,
     pub updated_by: Option<String>,
+    // SEPA mandate (Lastschrift)
+    pub sepa_mandatsreferenz: Option<String>,
+    pub sepa_mandat_datum: Option<String>,
+    pub sepa_iban: Option<String>,
+    pub sepa_bic: Option<String>,
+    pub sepa_kontoinhaber: Option<String>,
+    pub sepa_mandat_typ: Option<String>,  // 'CORE' or 'B2B'
+    pub sepa_einmalig: Option<bool>,
+    pub sepa_letzte_lastschrift: Option<String>,
 }
 
 impl From<Row> for Guest {
@@ -134,6 +165,14 @@ impl From<Row> for Guest {
             automail_sprache: row.get("automail_sprache"),
             created_by: row.get("created_by"),
             updated_by: row.get("updated_by"),
+            sepa_mandatsreferenz: row.try_get("sepa_mandatsreferenz").ok().flatten(),
+            sepa_mandat_datum: row.try_get("sepa_mandat_datum").ok().flatten(),
+            sepa_iban: row.try_get("sepa_iban").ok().flatten(),
+            sepa_bic: row.try_get("sepa_bic").ok().flatten(),
+            sepa_kontoinhaber: row.try_get("sepa_kontoinhaber").ok().flatten(),
+            sepa_mandat_typ: row.try_get("sepa_mandat_typ").ok().flatten(),
+            sepa_einmalig: row.try_get("sepa_einmalig").ok().flatten(),
+            sepa_letzte_lastschrift: row.try_get("sepa_letzte_lastschrift").ok().flatten(),
         }
     }
 }
@@ -151,14 +190,19 @@ pub struct Booking {
     pub checkin_date: String,
     pub checkout_date: String,
     pub anzahl_gaeste: i32,
-    pub status: String,
-    pub gesamtpreis: f64,
+    pub status: BookingStatus,
+    pub gesamtpreis: Money,
     pub bemerkungen: Option<String>,
     pub created_at: Option<String>,
     pub anzahl_begleitpersonen: Option<i32>,
-    pub grundpreis: Option<f64>,
-    pub services_preis: Option<f64>,
-    pub rabatt_preis: Option<f64>,
+    pub grundpreis: Option<Money>,
+    pub services_preis: Option<Money>,
+    pub rabatt_preis: Option<Money>,
+    pub belegung_preis: Option<Money>,  // Occupancy surcharges, contained in grundpreis
+    pub geschaeftsreise: Option<bool>,
+    pub kurtaxe: Option<Money>,  // Pass-through levy, NOT contained in gesamtpreis
+    pub kurtaxe_personen: Option<i32>,
+    pub kurtaxe_befreit: Option<i32>,
     pub anzahl_naechte: Option<i32>,
     pub updated_at: Option<String>,
     pub bezahlt: Option<bool>,
@@ -171,9 +215,18 @@ pub struct Booking {
     pub payment_recipient_id: Option<i32>,
     pub putzplan_checkout_date: Option<String>,
     pub ist_dpolg_mitglied: Option<bool>,
-    pub credit_used: Option<f64>,
+    pub credit_used: Option<Money>,
     pub created_by: Option<String>,
     pub updated_by: Option<String>,
+    pub booking_group_id: Option<i32>,  // Room booking of a group (booking_groups)
+    pub allotment_id: Option<i32>,  // Drawn down from an allotment (allotments)
+    pub option_expires_at: Option<String>,  // Hold expires (status 'optioniert')
+    pub zimmer_fixiert: Option<bool>,  // Room promised to the guest, not moved by the optimiser
+    pub split_parent_id: Option<i32>,  // Later segment of a split stay, points to the first segment
+    pub series_id: Option<i32>,  // Occurrence of a booking series (booking_series)
+    pub bezahlter_betrag: Option<Money>,  // Sum of the payments ledger (booking_balances)
+    pub offener_betrag: Option<Money>,  // Amount due minus payments
+    pub payment_status: Option<String>,  // 'ausstehend' | 'teilweise_bezahlt' | 'bezahlt'
     // Optional services and discounts for TapeChart emoji display
     #[serde(skip_serializing_if = "Option::is_none")]
     pub services: Option<Vec<ServiceEmoji>>,
@@ -199,6 +252,11 @@ impl From<Row> for Booking {
             grundpreis: row.get("grundpreis"),
             services_preis: row.get("services_preis"),
             rabatt_preis: row.get("rabatt_preis"),
+            belegung_preis: row.try_get("belegung_preis").ok().flatten(),
+            geschaeftsreise: row.try_get("geschaeftsreise").ok().flatten(),
+            kurtaxe: row.try_get("kurtaxe").ok().flatten(),
+            kurtaxe_personen: row.try_get("kurtaxe_personen").ok().flatten(),
+            kurtaxe_befreit: row.try_get("kurtaxe_befreit").ok().flatten(),
             anzahl_naechte: row.get("anzahl_naechte"),
             updated_at: row.get("updated_at"),
             bezahlt: row.get("bezahlt"),
@@ -214,6 +272,15 @@ impl From<Row> for Booking {
             credit_used: row.try_get("credit_used").ok().flatten(),
             created_by: row.try_get("created_by").ok().flatten(),
             updated_by: row.try_get("updated_by").ok().flatten(),
+            booking_group_id: row.try_get("booking_group_id").ok().flatten(),
+            allotment_id: row.try_get("allotment_id").ok().flatten(),
+            option_expires_at: row.try_get("option_expires_at").ok().flatten(),
+            zimmer_fixiert: row.try_get("zimmer_fixiert").ok().flatten(),
+            split_parent_id: row.try_get("split_parent_id").ok().flatten(),
+            series_id: row.try_get("series_id").ok().flatten(),
+            bezahlter_betrag: row.try_get("bezahlter_betrag").ok().flatten(),
+            offener_betrag: row.try_get("offener_betrag").ok().flatten(),
+            payment_status: row.try_get("payment_status").ok().flatten(),
             // Initialize with None - will be populated by get_all_bookings_pg
             services: None,
             discounts: None,
@@ -281,12 +348,14 @@ pub struct AdditionalService {
     pub id: i64,
     pub booking_id: i64,
     pub service_name: String,
-    pub service_price: f64,  // Changed from f32 for precision consistency
+    pub service_price: Money,
     pub template_id: Option<i32>,
     pub price_type: String,
     pub original_value: f64,  // Changed from f32 for precision consistency
     pub applies_to: String,
     pub emoji: Option<String>,
+    /// Own category or the one of the template (see `pricing::TAX_STANDARD`)
+    pub steuerkategorie: Option<String>,
 }
 
 impl From<Row> for AdditionalService {
@@ -301,6 +370,7 @@ impl From<Row> for AdditionalService {
             original_value: row.get("original_value"),
             applies_to: row.get("applies_to"),
             emoji: row.get("emoji"),
+            steuerkategorie: row.try_get("steuerkategorie").ok().flatten(),
         }
     }
 }
@@ -316,7 +386,7 @@ pub struct Discount {
     pub discount_name: String,
     pub discount_type: String,
     pub discount_value: f64,
-    pub calculated_amount: Option<f64>,
+    pub calculated_amount: Option<Money>,
     pub emoji: Option<String>,
 }
 
@@ -455,6 +525,8 @@ pub struct ServiceTemplate {
     pub emoji: Option<String>,
     pub show_in_cleaning_plan: bool,
     pub cleaning_plan_position: String,
+    #[serde(default)]
+    pub steuerkategorie: Option<String>,
     pub created_at: Option<String>,
 }
 
@@ -471,6 +543,7 @@ impl From<Row> for ServiceTemplate {
             emoji: row.get("emoji"),
             show_in_cleaning_plan: row.get::<_, Option<bool>>("show_in_cleaning_plan").unwrap_or(false),
             cleaning_plan_position: row.get::<_, Option<String>>("cleaning_plan_position").unwrap_or_else(|| "start".to_string()),
+            steuerkategorie: row.try_get("steuerkategorie").ok().flatten(),
             created_at: row.get("created_at"),
         }
     }
@@ -558,6 +631,7 @@ pub struct CompanySettings {
     pub logo_path: Option<String>,
     pub logo_data: Option<String>,
     pub logo_mime_type: Option<String>,
+    pub reservierungsnummer_format: Option<String>,  // Pattern of new reservation numbers, e.g. "{YYYY}-{seq}"
     pub updated_at: Option<String>,
 }
 
@@ -580,6 +654,7 @@ impl From<Row> for CompanySettings {
             logo_path: row.get("logo_path"),
             logo_data: row.get("logo_data"),
             logo_mime_type: row.get("logo_mime_type"),
+            reservierungsnummer_format: row.try_get("reservierungsnummer_format").ok().flatten(),
             updated_at: row.get("updated_at"),
         }
     }
@@ -596,6 +671,12 @@ pub struct PricingSettings {
     pub mitglieder_rabatt_aktiv: Option<bool>,
     pub mitglieder_rabatt_prozent: Option<f64>,
     pub rabatt_basis: Option<String>,
+    /// VAT rate in percent for accommodation (default 7)
+    #[serde(default)]
+    pub mwst_beherbergung: Option<f64>,
+    /// VAT rate in percent for all other services (default 19)
+    #[serde(default)]
+    pub mwst_standard: Option<f64>,
     pub updated_at: Option<String>,
 }
 
@@ -609,6 +690,8 @@ impl From<Row> for PricingSettings {
             mitglieder_rabatt_aktiv: row.get("mitglieder_rabatt_aktiv"),
             mitglieder_rabatt_prozent: row.get("mitglieder_rabatt_prozent"),
             rabatt_basis: row.get("rabatt_basis"),
+            mwst_beherbergung: row.try_get("mwst_beherbergung").ok().flatten(),
+            mwst_standard: row.try_get("mwst_standard").ok().flatten(),
             updated_at: row.get("updated_at"),
         }
     }
@@ -692,6 +775,9 @@ pub struct NotificationSettings {
     pub auto_reminder_checkin: Option<bool>,
     pub auto_reminder_invoice: Option<bool>,
 
+    // Option bookings
+    pub option_hold_days: Option<i32>,
+
     pub updated_at: Option<String>,
 }
 
@@ -713,6 +799,9 @@ impl From<Row> for NotificationSettings {
             auto_reminder_checkin: row.get("auto_reminder_checkin"),
             auto_reminder_invoice: row.get("auto_reminder_invoice"),
 
+            // Option bookings
+            option_hold_days: row.try_get("option_hold_days").ok().flatten(),
+
             updated_at: row.get("updated_at"),
         }
     }
@@ -727,12 +816,14 @@ pub struct PaymentSettings {
     pub iban: Option<String>,
     pub bic: Option<String>,
     pub account_holder: Option<String>,
-    pub mwst_rate: Option<f64>,
+    pub mwst_rate: Option<f64>,  // Legacy single rate - invoices use the tax categories of PricingSettings
     pub payment_due_days: Option<i32>,
     pub reminder_after_days: Option<i32>,
     pub payment_text: Option<String>,
     pub updated_at: Option<String>,
     pub dpolg_rabatt: Option<f64>,
+    pub glaeubiger_id: Option<String>,      // SEPA creditor identifier, required for direct debits
+    pub sepa_vorlauf_tage: Option<i32>,     // Business days between export and collection date
 }
 
 impl From<Row> for PaymentSettings {
@@ -749,6 +840,8 @@ impl From<Row> for PaymentSettings {
             payment_text: row.get("payment_text"),
             updated_at: row.get("updated_at"),
             dpolg_rabatt: row.get("dpolg_rabatt"),
+            glaeubiger_id: row.try_get("glaeubiger_id").ok().flatten(),
+            sepa_vorlauf_tage: row.try_get("sepa_vorlauf_tage").ok().flatten(),
         }
     }
 }
@@ -813,3 +906,941 @@ impl From<Row> for AuditLog {
         }
     }
 }
+
+// ============================================================================
+// SEASON PRICE CALENDAR MODELS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Season {
+    pub id: i32,
+    pub name: String,
+    pub year: i32,
+    pub start_date: String,  // YYYY-MM-DD, first night
+    pub end_date: String,    // YYYY-MM-DD, last night (inclusive)
+    pub priority: i32,
+    pub color: Option<String>,
+    pub is_active: bool,
+    pub created_at: Option<String>,
+}
+
+impl From<Row> for Season {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            name: row.get("name"),
+            year: row.get("year"),
+            start_date: row.get("start_date"),
+            end_date: row.get("end_date"),
+            priority: row.get("priority"),
+            color: row.get("color"),
+            is_active: row.get("is_active"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomSeasonPrice {
+    pub id: i32,
+    pub room_id: i32,
+    pub season_id: i32,
+    pub price_per_night: Money,
+}
+
+impl From<Row> for RoomSeasonPrice {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            room_id: row.get("room_id"),
+            season_id: row.get("season_id"),
+            price_per_night: row.get("price_per_night"),
+        }
+    }
+}
+
+/// Season joined with the price of one room - input for the pricing engine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomSeasonRate {
+    pub season_id: i32,
+    pub season_name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub priority: i32,
+    pub price_per_night: Money,
+}
+
+impl From<Row> for RoomSeasonRate {
+    fn from(row: Row) -> Self {
+        Self {
+            season_id: row.get("season_id"),
+            season_name: row.get("season_name"),
+            start_date: row.get("start_date"),
+            end_date: row.get("end_date"),
+            priority: row.get("priority"),
+            price_per_night: row.get("price_per_night"),
+        }
+    }
+}
+
+// ============================================================================
+// OCCUPANCY PRICING MODELS
+// ============================================================================
+
+/// Nightly price for a child of an age band (persons above the room's base occupancy)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomChildPrice {
+    #[serde(default)]
+    pub id: i32,
+    #[serde(default)]
+    pub room_id: i32,
+    pub alter_von: i32,
+    pub alter_bis: i32,
+    pub preis_pro_nacht: Money,
+}
+
+impl From<Row> for RoomChildPrice {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            room_id: row.get("room_id"),
+            alter_von: row.get("alter_von"),
+            alter_bis: row.get("alter_bis"),
+            preis_pro_nacht: row.get("preis_pro_nacht"),
+        }
+    }
+}
+
+// ============================================================================
+// KURTAXE MODELS
+// ============================================================================
+
+/// Kurtaxe rate of a municipality (matched against `Room.ort`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KurtaxeRate {
+    pub id: i32,
+    pub ort: String,
+    pub betrag_pro_nacht: Money,
+    pub befreit_unter_alter: i32,
+    pub geschaeftsreise_befreit: bool,
+    pub is_active: bool,
+}
+
+impl From<Row> for KurtaxeRate {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            ort: row.get("ort"),
+            betrag_pro_nacht: row.get("betrag_pro_nacht"),
+            befreit_unter_alter: row.get("befreit_unter_alter"),
+            geschaeftsreise_befreit: row.get("geschaeftsreise_befreit"),
+            is_active: row.get("is_active"),
+        }
+    }
+}
+
+// ============================================================================
+// INVOICE MODELS
+// ============================================================================
+
+/// Issued invoice or credit note (GoBD): gap-free yearly number and immutable snapshot
+/// of the invoice document. Credit notes carry negative amounts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuedInvoice {
+    pub id: i32,
+    /// "rechnung" or "gutschrift" (see `invoice::DOCUMENT_INVOICE`)
+    pub document_type: String,
+    pub invoice_number: String,
+    pub year: i32,
+    pub sequence_number: i32,
+    pub booking_id: i32,
+    /// Consolidated group invoice: booking_id is the master booking of the group
+    pub booking_group_id: Option<i32>,
+    pub issue_date: String,
+    pub total: Money,
+    pub amount_due: Money,
+    pub document: crate::invoice::Invoice,
+    /// Credit notes: the corrected invoice
+    pub corrects_invoice_id: Option<i32>,
+    /// Credit notes: cancels the whole invoice (Stornorechnung)
+    pub is_cancellation: bool,
+    pub reason: Option<String>,
+    pub pdf_path: Option<String>,
+    pub created_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl TryFrom<Row> for IssuedInvoice {
+    type Error = serde_json::Error;
+
+    /// The snapshot is stored as JSONB and selected as text
+    fn try_from(row: Row) -> Result<Self, Self::Error> {
+        let document: String = row.get("document");
+        Ok(Self {
+            id: row.get("id"),
+            document_type: row.get("document_type"),
+            invoice_number: row.get("invoice_number"),
+            year: row.get("year"),
+            sequence_number: row.get("sequence_number"),
+            booking_id: row.get("booking_id"),
+            booking_group_id: row.get("booking_group_id"),
+            issue_date: row.get("issue_date"),
+            total: row.get("total"),
+            amount_due: row.get("amount_due"),
+            document: serde_json::from_str(&document)?,
+            corrects_invoice_id: row.get("corrects_invoice_id"),
+            is_cancellation: row.get("is_cancellation"),
+            reason: row.get("reason"),
+            pdf_path: row.get("pdf_path"),
+            created_at: row.get("created_at"),
+            created_by: row.get("created_by"),
+        })
+    }
+}
+
+// ============================================================================
+// BOOKING GROUP MODELS
+// ============================================================================
+
+/// Group booking of one organiser (e.g. a seminar): owns several room bookings
+/// and is invoiced once. Master booking = first room booking of the group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookingGroup {
+    pub id: i32,
+    pub name: String,
+    pub reservierungsnummer: String,
+    pub organiser_guest_id: i32,
+    pub payment_recipient_id: Option<i32>,
+    pub bemerkungen: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl From<Row> for BookingGroup {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            name: row.get("name"),
+            reservierungsnummer: row.get("reservierungsnummer"),
+            organiser_guest_id: row.get("organiser_guest_id"),
+            payment_recipient_id: row.get("payment_recipient_id"),
+            bemerkungen: row.get("bemerkungen"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            created_by: row.get("created_by"),
+        }
+    }
+}
+
+/// One room of a new group booking
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupRoomInput {
+    pub room_id: i32,
+    pub checkin_date: String,
+    pub checkout_date: String,
+    pub anzahl_gaeste: i32,
+    #[serde(default)]
+    pub guest_names: Vec<String>,
+}
+
+/// Room booking of a group with the names of the people in the room
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookingGroupRoom {
+    pub booking: Booking,
+    pub room_name: String,
+    pub guest_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookingGroupWithRooms {
+    #[serde(flatten)]
+    pub group: BookingGroup,
+    pub rooms: Vec<BookingGroupRoom>,
+}
+
+// ============================================================================
+// BOOKING SERIES MODELS
+// ============================================================================
+
+/// Recurring booking (e.g. monthly seminar): recurrence rule and rooms, one booking per
+/// occurrence and room (`bookings.series_id`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookingSeries {
+    pub id: i32,
+    pub name: String,
+    pub reservierungsnummer: String,
+    pub guest_id: i32,
+    pub payment_recipient_id: Option<i32>,
+    pub frequency: String,  // 'woechentlich' | 'monatlich'
+    pub interval_count: i32,
+    pub first_checkin: String,
+    pub nights: i32,
+    pub occurrences: Option<i32>,
+    pub until_date: Option<String>,
+    pub anzahl_gaeste: i32,
+    pub room_ids: Vec<i32>,
+    pub bemerkungen: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl From<Row> for BookingSeries {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            name: row.get("name"),
+            reservierungsnummer: row.get("reservierungsnummer"),
+            guest_id: row.get("guest_id"),
+            payment_recipient_id: row.get("payment_recipient_id"),
+            frequency: row.get("frequency"),
+            interval_count: row.get("interval_count"),
+            first_checkin: row.get("first_checkin"),
+            nights: row.get("nights"),
+            occurrences: row.get("occurrences"),
+            until_date: row.get("until_date"),
+            anzahl_gaeste: row.get("anzahl_gaeste"),
+            room_ids: row.get("room_ids"),
+            bemerkungen: row.get("bemerkungen"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            created_by: row.get("created_by"),
+        }
+    }
+}
+
+/// Occurrence of a series that could not be booked (conflict report)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesConflict {
+    pub occurrence: i32,
+    pub room_id: i32,
+    pub checkin_date: String,
+    pub checkout_date: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookingSeriesWithBookings {
+    #[serde(flatten)]
+    pub series: BookingSeries,
+    pub bookings: Vec<Booking>,
+    /// Only filled when the series is created or changed
+    pub conflicts: Vec<SeriesConflict>,
+}
+
+// ============================================================================
+// SPLIT STAY MODELS
+// ============================================================================
+
+/// One segment of a split stay (room change within the stay). Segments are consecutive:
+/// the check-out of one segment is the check-in of the next.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaySegmentInput {
+    pub room_id: i32,
+    pub checkin_date: String,
+    pub checkout_date: String,
+}
+
+// ============================================================================
+// ALLOTMENT MODELS
+// ============================================================================
+
+/// Room contingent held for a partner organisation: `quantity` rooms per night out of a
+/// room type or an explicit room set, released automatically on `release_date`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Allotment {
+    pub id: i32,
+    pub name: String,
+    pub guest_id: Option<i32>,
+    pub gebaeude_typ: Option<String>,
+    pub room_ids: Vec<i32>,
+    pub start_date: String,
+    pub end_date: String,
+    pub quantity: i32,
+    pub release_date: String,
+    pub status: String,  // 'aktiv' | 'freigegeben'
+    pub released_at: Option<String>,
+    pub bemerkungen: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl From<Row> for Allotment {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            name: row.get("name"),
+            guest_id: row.get("guest_id"),
+            gebaeude_typ: row.get("gebaeude_typ"),
+            room_ids: row.get("room_ids"),
+            start_date: row.get("start_date"),
+            end_date: row.get("end_date"),
+            quantity: row.get("quantity"),
+            release_date: row.get("release_date"),
+            status: row.get("status"),
+            released_at: row.get("released_at"),
+            bemerkungen: row.get("bemerkungen"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            created_by: row.get("created_by"),
+        }
+    }
+}
+
+/// Allotment usage of one night (TapeChart): held units, drawn down bookings and open units
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllotmentNight {
+    pub allotment_id: i32,
+    pub name: String,
+    pub date: String,
+    pub quantity: i32,
+    pub drawn: i32,
+    pub open: i32,
+}
+
+impl From<Row> for AllotmentNight {
+    fn from(row: Row) -> Self {
+        Self {
+            allotment_id: row.get("allotment_id"),
+            name: row.get("name"),
+            date: row.get("date"),
+            quantity: row.get("quantity"),
+            drawn: row.get("drawn"),
+            open: row.get("open"),
+        }
+    }
+}
+
+// ============================================================================
+// ROOM BLOCK MODELS
+// ============================================================================
+
+/// Room out of service in [start_date, end_date) - maintenance, renovation, out of order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomBlock {
+    pub id: i32,
+    pub room_id: i32,
+    pub room_name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub reason: String,
+    pub bemerkungen: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl From<Row> for RoomBlock {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            room_id: row.get("room_id"),
+            room_name: row.get("room_name"),
+            start_date: row.get("start_date"),
+            end_date: row.get("end_date"),
+            reason: row.get("reason"),
+            bemerkungen: row.get("bemerkungen"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            created_by: row.get("created_by"),
+        }
+    }
+}
+
+// ============================================================================
+// WAITLIST MODELS
+// ============================================================================
+
+/// Waitlist entry: guest waiting for a free room in a date range.
+/// Status: 'wartend' | 'angeboten' (freed room offered by email) | 'gebucht' | 'storniert'
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitlistEntry {
+    pub id: i32,
+    pub guest_id: i32,
+    pub guest_name: String,
+    pub checkin_date: String,
+    pub checkout_date: String,
+    pub anzahl_gaeste: i32,
+    pub room_ids: Vec<i32>,  // acceptable rooms (empty = any)
+    pub orte: Vec<String>,   // acceptable locations (empty = any)
+    pub status: String,
+    pub offered_room_id: Option<i32>,
+    pub offered_at: Option<String>,
+    pub offer_email_id: Option<i32>,
+    pub bemerkungen: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl From<Row> for WaitlistEntry {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            guest_id: row.get("guest_id"),
+            guest_name: row.get("guest_name"),
+            checkin_date: row.get("checkin_date"),
+            checkout_date: row.get("checkout_date"),
+            anzahl_gaeste: row.get("anzahl_gaeste"),
+            room_ids: row.get("room_ids"),
+            orte: row.get("orte"),
+            status: row.get("status"),
+            offered_room_id: row.get("offered_room_id"),
+            offered_at: row.get("offered_at"),
+            offer_email_id: row.get("offer_email_id"),
+            bemerkungen: row.get("bemerkungen"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            created_by: row.get("created_by"),
+        }
+    }
+}
+
+// ============================================================================
+// PAYMENT MODELS (ledger, deposit requests)
+// ============================================================================
+
+/// Payment (or refund, negative amount) of a booking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payment {
+    pub id: i32,
+    pub booking_id: i32,
+    pub amount: Money,
+    pub payment_date: String,
+    pub method: Option<String>,
+    pub reference: Option<String>,
+    pub notes: Option<String>,
+    pub recorded_by: Option<String>,
+    pub created_at: Option<String>,
+    /// Set on reversals: the payment this entry cancels
+    pub reversal_of: Option<i32>,
+}
+
+impl From<Row> for Payment {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            booking_id: row.get("booking_id"),
+            amount: row.get("amount"),
+            payment_date: row.get("payment_date"),
+            method: row.get("method"),
+            reference: row.get("reference"),
+            notes: row.get("notes"),
+            recorded_by: row.get("recorded_by"),
+            created_at: row.get("created_at"),
+            reversal_of: row.get("reversal_of"),
+        }
+    }
+}
+
+/// Requested deposit (Anzahlung) with the part of it covered by the payments so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositRequest {
+    pub id: i32,
+    pub booking_id: i32,
+    pub description: String,
+    pub amount: Money,
+    pub percentage: Option<f64>,
+    pub due_date: String,
+    pub created_at: Option<String>,
+    pub created_by: Option<String>,
+    pub covered: Money,
+    pub state: String,  // 'offen' | 'ueberfaellig' | 'bezahlt'
+}
+
+impl From<Row> for DepositRequest {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            booking_id: row.get("booking_id"),
+            description: row.get("description"),
+            amount: row.get("amount"),
+            percentage: row.get("percentage"),
+            due_date: row.get("due_date"),
+            created_at: row.get("created_at"),
+            created_by: row.get("created_by"),
+            // Filled by PaymentRepository from the paid total
+            covered: Money::ZERO,
+            state: "offen".to_string(),
+        }
+    }
+}
+
+/// Balance of a booking from the payments ledger (view booking_balances)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookingBalance {
+    pub booking_id: i32,
+    /// Booking total + Kurtaxe - guest credit used (0 for cancelled bookings)
+    pub total_due: Money,
+    pub paid: Money,
+    pub outstanding: Money,
+    pub payment_status: String,  // 'ausstehend' | 'teilweise_bezahlt' | 'bezahlt'
+}
+
+impl From<Row> for BookingBalance {
+    fn from(row: Row) -> Self {
+        Self {
+            booking_id: row.get("booking_id"),
+            total_due: row.get("total_due"),
+            paid: row.get("paid"),
+            outstanding: row.get("outstanding"),
+            payment_status: row.get("payment_status"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookingPayments {
+    #[serde(flatten)]
+    pub balance: BookingBalance,
+    pub payments: Vec<Payment>,
+    pub deposits: Vec<DepositRequest>,
+}
+
+// ============================================================================
+// BANK IMPORT MODELS (CAMT.053 / MT940)
+// ============================================================================
+
+/// Imported transfer with its match proposals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedBankTransaction {
+    pub id: i32,
+    pub booking_date: String,
+    pub amount: Money,
+    pub payer_name: Option<String>,
+    pub payer_iban: Option<String>,
+    pub remittance: String,
+    pub bank_reference: Option<String>,
+    pub file_name: Option<String>,
+    pub status: String,  // 'offen' | 'zugeordnet' | 'ignoriert'
+    pub booking_id: Option<i32>,
+    pub payment_id: Option<i32>,
+    pub confidence: Option<i16>,
+    pub imported_at: Option<String>,
+    pub matched_at: Option<String>,
+    pub matched_by: Option<String>,
+    /// Filled by BankTransactionRepository for open transactions (best first)
+    pub proposals: Vec<MatchProposal>,
+}
+
+impl From<Row> for ImportedBankTransaction {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            booking_date: row.get("booking_date"),
+            amount: row.get("amount"),
+            payer_name: row.get("payer_name"),
+            payer_iban: row.get("payer_iban"),
+            remittance: row.get("remittance"),
+            bank_reference: row.get("bank_reference"),
+            file_name: row.get("file_name"),
+            status: row.get("status"),
+            booking_id: row.get("booking_id"),
+            payment_id: row.get("payment_id"),
+            confidence: row.get("confidence"),
+            imported_at: row.get("imported_at"),
+            matched_at: row.get("matched_at"),
+            matched_by: row.get("matched_by"),
+            proposals: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankImportResult {
+    pub imported: usize,
+    /// Already imported with an earlier statement
+    pub duplicates: usize,
+    /// Outgoing transfers and fees are not imported
+    pub skipped_debits: usize,
+    pub transactions: Vec<ImportedBankTransaction>,
+}
+
+// ============================================================================
+// SEPA DIRECT DEBIT MODELS
+// ============================================================================
+
+/// Booking that can be collected by direct debit on the chosen date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SepaDueDebit {
+    pub booking_id: i32,
+    pub reservierungsnummer: String,
+    pub guest_id: i32,
+    pub guest_name: String,
+    pub checkout_date: String,
+    pub amount: Money,
+    pub mandate_reference: String,
+    pub sequence_type: Option<String>,  // FRST | RCUR | OOFF, None if the mandate cannot be used
+    /// Why the booking is not included in the export (e.g. mandate expired)
+    pub problem: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SepaDuePreview {
+    pub collection_date: String,
+    pub earliest_collection_date: String,
+    pub debits: Vec<SepaDueDebit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SepaBatch {
+    pub id: i32,
+    pub message_id: String,
+    pub collection_date: String,
+    pub transaction_count: i32,
+    pub total: Money,
+    pub file_path: Option<String>,
+    pub created_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl From<Row> for SepaBatch {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            message_id: row.get("message_id"),
+            collection_date: row.get("collection_date"),
+            transaction_count: row.get("transaction_count"),
+            total: row.get("total"),
+            file_path: row.get("file_path"),
+            created_at: row.get("created_at"),
+            created_by: row.get("created_by"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SepaCollection {
+    pub id: i32,
+    pub batch_id: i32,
+    pub booking_id: i32,
+    pub reservierungsnummer: String,
+    pub guest_id: i32,
+    pub guest_name: String,
+    pub amount: Money,
+    pub mandate_reference: String,
+    pub sequence_type: String,
+    pub end_to_end_id: String,
+    pub collection_date: String,
+    pub status: String,  // 'ausstehend' | 'eingezogen' | 'zurueckgegeben'
+    pub payment_id: Option<i32>,
+    pub return_reason: Option<String>,
+    pub return_reason_text: Option<String>,
+    pub returned_at: Option<String>,
+}
+
+impl From<Row> for SepaCollection {
+    fn from(row: Row) -> Self {
+        let return_reason: Option<String> = row.get("return_reason");
+        Self {
+            id: row.get("id"),
+            batch_id: row.get("batch_id"),
+            booking_id: row.get("booking_id"),
+            reservierungsnummer: row.get("reservierungsnummer"),
+            guest_id: row.get("guest_id"),
+            guest_name: row.get("guest_name"),
+            amount: row.get("amount"),
+            mandate_reference: row.get("mandate_reference"),
+            sequence_type: row.get("sequence_type"),
+            end_to_end_id: row.get("end_to_end_id"),
+            collection_date: row.get("collection_date"),
+            status: row.get("status"),
+            payment_id: row.get("payment_id"),
+            return_reason_text: return_reason
+                .as_deref()
+                .and_then(crate::sepa::return_reason_text)
+                .map(str::to_string),
+            return_reason,
+            returned_at: row.get("returned_at"),
+        }
+    }
+}
+
+// ============================================================================
+// DUNNING MODELS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DunningStage {
+    pub id: i32,
+    pub stage: i32,
+    pub name: String,
+    pub template_name: String,
+    pub frist_tage: i32,
+    pub gebuehr: Money,
+    pub updated_at: Option<String>,
+}
+
+impl From<Row> for DunningStage {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            stage: row.get("stage"),
+            name: row.get("name"),
+            template_name: row.get("template_name"),
+            frist_tage: row.get("frist_tage"),
+            gebuehr: row.get("gebuehr"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+/// Step of the dunning procedure of a booking (stage sent or hand-over to manual follow-up)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DunningEntry {
+    pub id: i32,
+    pub booking_id: i32,
+    pub event: String,  // 'mahnung' | 'uebergabe'
+    pub stage: Option<i32>,
+    pub stage_name: Option<String>,
+    pub template_name: Option<String>,
+    pub outstanding: Money,
+    pub gebuehr: Money,
+    pub zahlungsfrist: Option<String>,
+    pub service_id: Option<i32>,
+    pub reminder_id: Option<i32>,
+    /// Status of the email ('pending' | 'sent' | 'failed' | 'cancelled')
+    pub email_status: Option<String>,
+    pub created_at: Option<String>,
+}
+
+impl From<Row> for DunningEntry {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            booking_id: row.get("booking_id"),
+            event: row.get("event"),
+            stage: row.get("stage"),
+            stage_name: row.get("stage_name"),
+            template_name: row.get("template_name"),
+            outstanding: row.get("outstanding"),
+            gebuehr: row.get("gebuehr"),
+            zahlungsfrist: row.get("zahlungsfrist"),
+            service_id: row.get("service_id"),
+            reminder_id: row.get("reminder_id"),
+            email_status: row.get("email_status"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+// ============================================================================
+// VOUCHER MODELS
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Voucher {
+    pub id: i32,
+    pub code: String,
+    pub value: Money,
+    /// Value minus redemptions
+    pub remaining: Money,
+    pub issued_at: String,
+    pub expires_at: String,
+    pub purchaser_guest_id: Option<i32>,
+    pub purchaser_name: Option<String>,
+    pub recipient_name: Option<String>,
+    pub message: Option<String>,
+    pub notes: Option<String>,
+    pub status: crate::vouchers::VoucherStatus,
+    pub cancelled_at: Option<String>,
+    pub created_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl From<Row> for Voucher {
+    fn from(row: Row) -> Self {
+        let remaining: Money = row.get("remaining");
+        let expires_at: String = row.get("expires_at");
+        let cancelled_at: Option<String> = row.get("cancelled_at");
+        let status = chrono::NaiveDate::parse_from_str(&expires_at, "%Y-%m-%d")
+            .map(|expiry| {
+                crate::vouchers::status(remaining, expiry, cancelled_at.is_some(), chrono::Local::now().date_naive())
+            })
+            .unwrap_or(crate::vouchers::VoucherStatus::Abgelaufen);
+        Self {
+            id: row.get("id"),
+            code: row.get("code"),
+            value: row.get("value"),
+            remaining,
+            issued_at: row.get("issued_at"),
+            expires_at,
+            purchaser_guest_id: row.get("purchaser_guest_id"),
+            purchaser_name: row.get("purchaser_name"),
+            recipient_name: row.get("recipient_name"),
+            message: row.get("message"),
+            notes: row.get("notes"),
+            status,
+            cancelled_at,
+            created_at: row.get("created_at"),
+            created_by: row.get("created_by"),
+        }
+    }
+}
+
+/// Redemption of a voucher against a booking (a payment in the booking's ledger)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VoucherRedemption {
+    pub id: i32,
+    pub voucher_id: i32,
+    pub code: String,
+    pub booking_id: i32,
+    pub reservierungsnummer: String,
+    /// Guest of the booking (redeemed by)
+    pub guest_name: String,
+    pub amount: Money,
+    pub payment_id: i32,
+    pub created_at: Option<String>,
+    pub created_by: Option<String>,
+}
+
+impl From<Row> for VoucherRedemption {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            voucher_id: row.get("voucher_id"),
+            code: row.get("code"),
+            booking_id: row.get("booking_id"),
+            reservierungsnummer: row.get("reservierungsnummer"),
+            guest_name: row.get("guest_name"),
+            amount: row.get("amount"),
+            payment_id: row.get("payment_id"),
+            created_at: row.get("created_at"),
+            created_by: row.get("created_by"),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::database_pg::{DbPool, DbResult, AdditionalService};
+use crate::money::Money;
 
 pub struct AdditionalServiceRepository;
 
@@ -11,7 +12,7 @@ impl AdditionalServiceRepository {
             .query(
                 "SELECT a.id, a.booking_id, a.service_name, a.service_price,
                         a.template_id, a.price_type, a.original_value, a.applies_to,
-                        st.emoji
+                        st.emoji, COALESCE(a.steuerkategorie, st.steuerkategorie) as steuerkategorie
                  FROM additional_services a
                  LEFT JOIN service_templates st ON a.template_id = st.id
                  ORDER BY a.id DESC",
@@ -30,7 +31,7 @@ impl AdditionalServiceRepository {
             .query_one(
                 "SELECT a.id, a.booking_id, a.service_name, a.service_price,
                         a.template_id, a.price_type, a.original_value, a.applies_to,
-                        st.emoji
+                        st.emoji, COALESCE(a.steuerkategorie, st.steuerkategorie) as steuerkategorie
                  FROM additional_services a
                  LEFT JOIN service_templates st ON a.template_id = st.id
                  WHERE a.id = $1",
@@ -50,7 +51,7 @@ impl AdditionalServiceRepository {
             .query(
                 "SELECT a.id, a.booking_id, a.service_name, a.service_price,
                         a.template_id, a.price_type, a.original_value, a.applies_to,
-                        st.emoji
+                        st.emoji, COALESCE(a.steuerkategorie, st.steuerkategorie) as steuerkategorie
                  FROM additional_services a
                  LEFT JOIN service_templates st ON a.template_id = st.id
                  WHERE a.booking_id = $1
@@ -62,16 +63,19 @@ impl AdditionalServiceRepository {
         Ok(rows.into_iter().map(AdditionalService::from).collect())
     }
 
-    /// Create new service
+    /// Create new service.
+    /// `steuerkategorie` None = category of the template (or standard rate)
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &DbPool,
         booking_id: i64,
         service_name: String,
-        service_price: f64,  // Changed from f32
+        service_price: Money,
         template_id: Option<i32>,
         price_type: String,
         original_value: f64,  // Changed from f32
         applies_to: String,
+        steuerkategorie: Option<String>,
     ) -> DbResult<AdditionalService> {
         // Bug 7 fix: Validate service value
         if original_value < 0.0 {
@@ -79,7 +83,7 @@ impl AdditionalServiceRepository {
                 "Service-Wert darf nicht negativ sein".to_string()
             ));
         }
-        if service_price < 0.0 {
+        if service_price.is_negative() {
             return Err(crate::database_pg::DbError::ValidationError(
                 "Service-Preis darf nicht negativ sein".to_string()
             ));
@@ -92,18 +96,18 @@ impl AdditionalServiceRepository {
                 "WITH inserted AS (
                     INSERT INTO additional_services (
                         booking_id, service_name, service_price, template_id,
-                        price_type, original_value, applies_to
-                    ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+                        price_type, original_value, applies_to, steuerkategorie
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
                     RETURNING *
                  )
                  SELECT i.id, i.booking_id, i.service_name, i.service_price,
                         i.template_id, i.price_type, i.original_value, i.applies_to,
-                        st.emoji
+                        st.emoji, COALESCE(i.steuerkategorie, st.steuerkategorie) as steuerkategorie
                  FROM inserted i
                  LEFT JOIN service_templates st ON i.template_id = st.id",
                 &[
                     &booking_id, &service_name, &service_price, &template_id,
-                    &price_type, &original_value, &applies_to,
+                    &price_type, &original_value, &applies_to, &steuerkategorie,
                 ],
             )
             .await?;
@@ -112,16 +116,18 @@ impl AdditionalServiceRepository {
     }
 
     /// Update existing service
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &DbPool,
         id: i64,
         booking_id: i64,
         service_name: String,
-        service_price: f64,  // Changed from f32
+        service_price: Money,
         template_id: Option<i32>,
         price_type: String,
         original_value: f64,  // Changed from f32
         applies_to: String,
+        steuerkategorie: Option<String>,
     ) -> DbResult<AdditionalService> {
         // Bug 7 fix: Validate service value
         if original_value < 0.0 {
@@ -129,7 +135,7 @@ impl AdditionalServiceRepository {
                 "Service-Wert darf nicht negativ sein".to_string()
             ));
         }
-        if service_price < 0.0 {
+        if service_price.is_negative() {
             return Err(crate::database_pg::DbError::ValidationError(
                 "Service-Preis darf nicht negativ sein".to_string()
             ));
@@ -143,18 +149,18 @@ impl AdditionalServiceRepository {
                     UPDATE additional_services SET
                         booking_id = $2, service_name = $3, service_price = $4,
                         template_id = $5, price_type = $6, original_value = $7,
-                        applies_to = $8
+                        applies_to = $8, steuerkategorie = $9
                     WHERE id = $1
                     RETURNING *
                  )
                  SELECT u.id, u.booking_id, u.service_name, u.service_price,
                         u.template_id, u.price_type, u.original_value, u.applies_to,
-                        st.emoji
+                        st.emoji, COALESCE(u.steuerkategorie, st.steuerkategorie) as steuerkategorie
                  FROM updated u
                  LEFT JOIN service_templates st ON u.template_id = st.id",
                 &[
                     &id, &booking_id, &service_name, &service_price,
-                    &template_id, &price_type, &original_value, &applies_to,
+                    &template_id, &price_type, &original_value, &applies_to, &steuerkategorie,
                 ],
             )
             .await
@@ -182,7 +188,7 @@ impl AdditionalServiceRepository {
     }
 
     /// Calculate total services price for booking
-    pub async fn calculate_total_for_booking(pool: &DbPool, booking_id: i64) -> DbResult<f64> {
+    pub async fn calculate_total_for_booking(pool: &DbPool, booking_id: i64) -> DbResult<Money> {
         let client = pool.get().await?;
 
         let row = client
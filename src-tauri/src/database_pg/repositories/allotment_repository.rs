@@ -0,0 +1,611 @@
+use crate::booking_status::BookingStatus;
+use crate::database_pg::repositories::BookingRepository;
+use crate::database_pg::{Allotment, AllotmentNight, Booking, DbError, DbPool, DbResult};
+use crate::money::Money;
+use tokio_postgres::GenericClient;
+
+const ALLOTMENT_COLUMNS: &str = "id, name, guest_id, gebaeude_typ,
+    ARRAY(SELECT ar.room_id FROM allotment_rooms ar WHERE ar.allotment_id = allotments.id ORDER BY ar.room_id) AS room_ids,
+    start_date::text as start_date, end_date::text as end_date, quantity, release_date::text as release_date,
+    status, released_at::text as released_at, bemerkungen,
+    created_at::text as created_at, updated_at::text as updated_at, created_by";
+
+/// Rooms of allotment `a`: every room of its room type, or its explicit room set
+const POOL_MEMBER: &str = "((a.gebaeude_typ IS NOT NULL AND r.gebaeude_typ = a.gebaeude_typ)
+        OR EXISTS (SELECT 1 FROM allotment_rooms ar WHERE ar.allotment_id = a.id AND ar.room_id = r.id))";
+
+/// Repository for room allotments (contingents held for partner organisations)
+pub struct AllotmentRepository;
+
+impl AllotmentRepository {
+    /// Run allotments migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/027_allotments.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get all allotments, upcoming first
+    pub async fn get_all(pool: &DbPool) -> DbResult<Vec<Allotment>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!("SELECT {} FROM allotments ORDER BY start_date, id", ALLOTMENT_COLUMNS),
+                &[],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(Allotment::from).collect())
+    }
+
+    /// Get allotment by ID
+    pub async fn get_by_id(pool: &DbPool, id: i32) -> DbResult<Allotment> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(&format!("SELECT {} FROM allotments WHERE id = $1", ALLOTMENT_COLUMNS), &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Allotment with ID {} not found", id)))?;
+
+        Ok(Allotment::from(row))
+    }
+
+    /// Create an allotment. The rooms must still be free: on every night the room type / room set
+    /// needs at least `quantity` rooms without a booking.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &DbPool,
+        name: String,
+        guest_id: Option<i32>,
+        gebaeude_typ: Option<String>,
+        room_ids: Vec<i32>,
+        start_date: String,
+        end_date: String,
+        quantity: i32,
+        release_date: String,
+        bemerkungen: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<Allotment> {
+        let gebaeude_typ = gebaeude_typ.filter(|t| !t.trim().is_empty());
+        Self::validate(&gebaeude_typ, &room_ids, &start_date, &end_date, quantity, &release_date)?;
+
+        let mut client = pool.get().await?;
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::Serializable)
+            .start()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction start failed: {}", e)))?;
+
+        let id: i32 = transaction
+            .query_one(
+                "INSERT INTO allotments (name, guest_id, gebaeude_typ, start_date, end_date, quantity,
+                                         release_date, bemerkungen, created_by)
+                 VALUES ($1, $2, $3, $4::text::date, $5::text::date, $6, $7::text::date, $8, $9)
+                 RETURNING id",
+                &[&name, &guest_id, &gebaeude_typ, &start_date, &end_date, &quantity, &release_date, &bemerkungen, &created_by],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?
+            .get("id");
+
+        Self::insert_rooms(&transaction, id, &room_ids).await?;
+        Self::check_pool_capacity(&transaction, id).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction commit failed: {}", e)))?;
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Update an active allotment. Bookings already drawn down must stay inside it.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &DbPool,
+        id: i32,
+        name: String,
+        guest_id: Option<i32>,
+        gebaeude_typ: Option<String>,
+        room_ids: Vec<i32>,
+        start_date: String,
+        end_date: String,
+        quantity: i32,
+        release_date: String,
+        bemerkungen: Option<String>,
+    ) -> DbResult<Allotment> {
+        let gebaeude_typ = gebaeude_typ.filter(|t| !t.trim().is_empty());
+        Self::validate(&gebaeude_typ, &room_ids, &start_date, &end_date, quantity, &release_date)?;
+
+        let mut client = pool.get().await?;
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::Serializable)
+            .start()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction start failed: {}", e)))?;
+
+        let status: String = transaction
+            .query_opt("SELECT status FROM allotments WHERE id = $1 FOR UPDATE", &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Allotment with ID {} not found", id)))?
+            .get("status");
+        if status != "aktiv" {
+            return Err(DbError::ValidationError(
+                "Ein freigegebenes Kontingent kann nicht mehr geändert werden".to_string(),
+            ));
+        }
+
+        transaction
+            .execute(
+                "UPDATE allotments
+                 SET name = $2, guest_id = $3, gebaeude_typ = $4, start_date = $5::text::date,
+                     end_date = $6::text::date, quantity = $7, release_date = $8::text::date,
+                     bemerkungen = $9, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1",
+                &[&id, &name, &guest_id, &gebaeude_typ, &start_date, &end_date, &quantity, &release_date, &bemerkungen],
+            )
+            .await?;
+
+        transaction
+            .execute("DELETE FROM allotment_rooms WHERE allotment_id = $1", &[&id])
+            .await?;
+        Self::insert_rooms(&transaction, id, &room_ids).await?;
+
+        // Drawn down bookings must still fit: inside the date range, in the room pool, within the quantity
+        let outside = transaction
+            .query(
+                &format!(
+                    "SELECT b.reservierungsnummer
+                     FROM bookings b
+                     JOIN allotments a ON a.id = b.allotment_id
+                     JOIN rooms r ON r.id = b.room_id
+                     WHERE b.allotment_id = $1
+                       AND b.status != 'storniert'
+                       AND (b.checkin_date::date < a.start_date OR b.checkout_date::date > a.end_date
+                            OR NOT {})",
+                    POOL_MEMBER
+                ),
+                &[&id],
+            )
+            .await?;
+        if !outside.is_empty() {
+            let numbers: Vec<String> = outside.iter().map(|row| row.get("reservierungsnummer")).collect();
+            return Err(DbError::ValidationError(format!(
+                "Folgende Buchungen aus dem Kontingent liegen außerhalb des neuen Zeitraums oder Zimmerbestands: {}",
+                numbers.join(", ")
+            )));
+        }
+        let over_drawn = Self::nights_over_quantity(&transaction, id).await?;
+        if !over_drawn.is_empty() {
+            return Err(DbError::ValidationError(format!(
+                "Es sind bereits mehr Zimmer aus dem Kontingent gebucht als die neue Anzahl erlaubt ({})",
+                over_drawn.join(", ")
+            )));
+        }
+
+        Self::check_pool_capacity(&transaction, id).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction commit failed: {}", e)))?;
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Release the undrawn units of an allotment now (before the deadline)
+    pub async fn release(pool: &DbPool, id: i32) -> DbResult<Allotment> {
+        let client = pool.get().await?;
+
+        let updated = client
+            .execute(
+                "UPDATE allotments
+                 SET status = 'freigegeben', released_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1 AND status = 'aktiv'",
+                &[&id],
+            )
+            .await?;
+        if updated == 0 {
+            // Either unknown or already released
+            Self::get_by_id(pool, id).await?;
+            return Err(DbError::ValidationError("Das Kontingent ist bereits freigegeben".to_string()));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Release all active allotments whose deadline has been reached (background job)
+    pub async fn release_due(pool: &DbPool) -> DbResult<Vec<Allotment>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "UPDATE allotments
+                 SET status = 'freigegeben', released_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+                 WHERE status = 'aktiv' AND release_date <= CURRENT_DATE
+                 RETURNING id",
+                &[],
+            )
+            .await?;
+
+        let mut released = Vec::with_capacity(rows.len());
+        for row in rows {
+            released.push(Self::get_by_id(pool, row.get("id")).await?);
+        }
+        Ok(released)
+    }
+
+    /// Delete an allotment. Bookings drawn down from it stay as normal bookings.
+    pub async fn delete(pool: &DbPool, id: i32) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let deleted = client.execute("DELETE FROM allotments WHERE id = $1", &[&id]).await?;
+        if deleted == 0 {
+            return Err(DbError::NotFound(format!("Allotment with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Book a room of the allotment for the partner organisation. Uses up one held unit per night
+    /// instead of reducing the availability for everybody else.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn draw_down(
+        pool: &DbPool,
+        allotment_id: i32,
+        room_id: i32,
+        guest_id: i32,
+        checkin_date: String,
+        checkout_date: String,
+        anzahl_gaeste: i32,
+        status: BookingStatus,
+        bemerkungen: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<Booking> {
+        BookingRepository::validate_initial_status(status)?;
+        if checkout_date <= checkin_date {
+            return Err(DbError::ValidationError("Abreise muss nach der Anreise liegen".to_string()));
+        }
+
+        let mut client = pool.get().await?;
+        let reservierungsnummer = BookingRepository::next_reservation_number(&client).await?;
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::Serializable)
+            .start()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction start failed: {}", e)))?;
+
+        // 1. Allotment must be active, cover the stay and contain the room
+        let row = transaction
+            .query_opt(
+                &format!(
+                    "SELECT a.name, a.status,
+                            a.start_date <= $3::text::date AND a.end_date >= $4::text::date AS covers_stay,
+                            EXISTS (SELECT 1 FROM rooms r WHERE r.id = $2 AND {}) AS room_in_pool
+                     FROM allotments a
+                     WHERE a.id = $1
+                     FOR UPDATE",
+                    POOL_MEMBER
+                ),
+                &[&allotment_id, &room_id, &checkin_date, &checkout_date],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Allotment with ID {} not found", allotment_id)))?;
+
+        let name: String = row.get("name");
+        if row.get::<_, String>("status") != "aktiv" {
+            return Err(DbError::ValidationError(format!("Das Kontingent \"{}\" ist bereits freigegeben", name)));
+        }
+        if !row.get::<_, bool>("covers_stay") {
+            return Err(DbError::ValidationError(format!(
+                "Der Aufenthalt liegt außerhalb des Kontingents \"{}\"",
+                name
+            )));
+        }
+        if !row.get::<_, bool>("room_in_pool") {
+            return Err(DbError::ValidationError(format!(
+                "Das Zimmer gehört nicht zum Kontingent \"{}\"",
+                name
+            )));
+        }
+
+        // 2. A held unit must be left on every night of the stay
+        let exhausted = transaction
+            .query(
+                "SELECT TO_CHAR(n.night, 'DD.MM.YYYY') AS night
+                 FROM allotments a
+                 CROSS JOIN generate_series($2::text::date, $3::text::date - 1, INTERVAL '1 day') AS n(night)
+                 WHERE a.id = $1
+                   AND (SELECT COUNT(*) FROM bookings b
+                        WHERE b.allotment_id = a.id
+                          AND b.status != 'storniert'
+                          AND b.checkin_date::date <= n.night::date
+                          AND b.checkout_date::date > n.night::date) >= a.quantity
+                 ORDER BY n.night",
+                &[&allotment_id, &checkin_date, &checkout_date],
+            )
+            .await?;
+        if !exhausted.is_empty() {
+            let nights: Vec<String> = exhausted.iter().map(|row| row.get("night")).collect();
+            return Err(DbError::ValidationError(format!(
+                "Das Kontingent \"{}\" ist an folgenden Nächten ausgeschöpft: {}",
+                name,
+                nights.join(", ")
+            )));
+        }
+
+        // 3. The room itself must be free (held units of other allotments still apply)
+        let is_available = BookingRepository::check_and_lock_availability_in_allotment(
+            &transaction,
+            room_id,
+            &checkin_date,
+            &checkout_date,
+            None,
+            Some(allotment_id),
+        )
+        .await?;
+        if !is_available {
+            return Err(DbError::DoubleBookingError(format!(
+                "Das Zimmer ist für den Zeitraum {} bis {} bereits gebucht. \
+                 Bitte wählen Sie ein anderes Zimmer aus dem Kontingent.",
+                checkin_date, checkout_date
+            )));
+        }
+
+        // 4. Booking; prices are calculated afterwards by the caller (pricing engine)
+        let booking_id: i32 = transaction
+            .query_one(
+                "INSERT INTO bookings (
+                    room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
+                    anzahl_gaeste, status, gesamtpreis, bemerkungen, allotment_id,
+                    created_by, updated_by, created_at, updated_at
+                 ) VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+                 ) RETURNING id",
+                &[
+                    &room_id,
+                    &guest_id,
+                    &reservierungsnummer,
+                    &checkin_date,
+                    &checkout_date,
+                    &anzahl_gaeste,
+                    &status,
+                    &Money::ZERO,
+                    &bemerkungen,
+                    &allotment_id,
+                    &created_by,
+                ],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?
+            .get("id");
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction commit failed: {}", e)))?;
+
+        BookingRepository::get_by_id(pool, booking_id).await
+    }
+
+    /// Held, drawn down and open units per night of all active allotments in [from, to) (TapeChart)
+    pub async fn get_nights(pool: &DbPool, from: &str, to: &str) -> DbResult<Vec<AllotmentNight>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT allotment_id, name, night::text AS date, quantity, drawn,
+                        GREATEST(quantity - drawn, 0) AS open
+                 FROM (
+                     SELECT a.id AS allotment_id, a.name, n.night::date AS night, a.quantity,
+                            (SELECT COUNT(*) FROM bookings b
+                             WHERE b.allotment_id = a.id
+                               AND b.status != 'storniert'
+                               AND b.checkin_date::date <= n.night::date
+                               AND b.checkout_date::date > n.night::date)::int AS drawn
+                     FROM allotments a
+                     CROSS JOIN generate_series(
+                         GREATEST(a.start_date, $1::text::date),
+                         LEAST(a.end_date, $2::text::date) - 1,
+                         INTERVAL '1 day'
+                     ) AS n(night)
+                     WHERE a.status = 'aktiv'
+                       AND a.start_date < $2::text::date
+                       AND a.end_date > $1::text::date
+                 ) nights
+                 ORDER BY night, allotment_id",
+                &[&from, &to],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(AllotmentNight::from).collect())
+    }
+
+    /// Nights of a stay in `room_id` on which an active allotment would lose units it holds:
+    /// its open units (quantity - drawn down bookings) need that many free (unbooked, unblocked)
+    /// rooms in its pool besides this one. `own_allotment_id` is the allotment the stay is drawn
+    /// down from (it uses its own units); for existing bookings it is taken from `exclude_booking_id`.
+    pub async fn held_conflicts<C: GenericClient>(
+        client: &C,
+        room_id: i32,
+        checkin_date: &str,
+        checkout_date: &str,
+        exclude_booking_id: Option<i32>,
+        own_allotment_id: Option<i32>,
+    ) -> DbResult<Vec<String>> {
+        let sql = format!(
+            "SELECT a.name, TO_CHAR(n.night, 'DD.MM.YYYY') AS night
+             FROM allotments a
+             CROSS JOIN generate_series($2::text::date, $3::text::date - 1, INTERVAL '1 day') AS n(night)
+             JOIN rooms t ON t.id = $1
+             WHERE a.status = 'aktiv'
+               AND n.night >= a.start_date
+               AND n.night < a.end_date
+               AND a.id IS DISTINCT FROM COALESCE($5, (SELECT allotment_id FROM bookings WHERE id = $4))
+               AND ((a.gebaeude_typ IS NOT NULL AND t.gebaeude_typ = a.gebaeude_typ)
+                    OR EXISTS (SELECT 1 FROM allotment_rooms ar WHERE ar.allotment_id = a.id AND ar.room_id = t.id))
+               AND a.quantity
+                   - (SELECT COUNT(*) FROM bookings b
+                      WHERE b.allotment_id = a.id
+                        AND b.status != 'storniert'
+                        AND b.id IS DISTINCT FROM $4
+                        AND b.checkin_date::date <= n.night::date
+                        AND b.checkout_date::date > n.night::date)
+                   > (SELECT COUNT(*) FROM rooms r
+                      WHERE r.id != $1
+                        AND {}
+                        AND NOT EXISTS (
+                            SELECT 1 FROM bookings b
+                            WHERE b.room_id = r.id
+                              AND b.status != 'storniert'
+                              AND b.id IS DISTINCT FROM $4
+                              AND b.checkin_date::date <= n.night::date
+                              AND b.checkout_date::date > n.night::date)
+                        AND NOT EXISTS (
+                            SELECT 1 FROM room_blocks rb
+                            WHERE rb.room_id = r.id
+                              AND rb.start_date <= n.night::date
+                              AND rb.end_date > n.night::date))
+             ORDER BY n.night, a.id",
+            POOL_MEMBER
+        );
+        let rows = client
+            .query(sql.as_str(), &[&room_id, &checkin_date, &checkout_date, &exclude_booking_id, &own_allotment_id])
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| format!("{} ({})", row.get::<_, String>("name"), row.get::<_, String>("night")))
+            .collect())
+    }
+
+    async fn insert_rooms(
+        transaction: &tokio_postgres::Transaction<'_>,
+        allotment_id: i32,
+        room_ids: &[i32],
+    ) -> DbResult<()> {
+        for room_id in room_ids {
+            transaction
+                .execute(
+                    "INSERT INTO allotment_rooms (allotment_id, room_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+                    &[&allotment_id, room_id],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Open units must fit into the free (unbooked, unblocked) rooms of the pool on every night
+    async fn check_pool_capacity(transaction: &tokio_postgres::Transaction<'_>, allotment_id: i32) -> DbResult<()> {
+        let rows = transaction
+            .query(
+                &format!(
+                    "SELECT TO_CHAR(n.night, 'DD.MM.YYYY') AS night
+                     FROM allotments a
+                     CROSS JOIN generate_series(a.start_date, a.end_date - 1, INTERVAL '1 day') AS n(night)
+                     WHERE a.id = $1
+                       AND a.quantity
+                           - (SELECT COUNT(*) FROM bookings b
+                              WHERE b.allotment_id = a.id
+                                AND b.status != 'storniert'
+                                AND b.checkin_date::date <= n.night::date
+                                AND b.checkout_date::date > n.night::date)
+                           > (SELECT COUNT(*) FROM rooms r
+                              WHERE {}
+                                AND NOT EXISTS (
+                                    SELECT 1 FROM bookings b
+                                    WHERE b.room_id = r.id
+                                      AND b.status != 'storniert'
+                                      AND b.checkin_date::date <= n.night::date
+                                      AND b.checkout_date::date > n.night::date)
+                                AND NOT EXISTS (
+                                    SELECT 1 FROM room_blocks rb
+                                    WHERE rb.room_id = r.id
+                                      AND rb.start_date <= n.night::date
+                                      AND rb.end_date > n.night::date))
+                     ORDER BY n.night",
+                    POOL_MEMBER
+                ),
+                &[&allotment_id],
+            )
+            .await?;
+
+        if !rows.is_empty() {
+            let nights: Vec<String> = rows.iter().map(|row| row.get("night")).collect();
+            return Err(DbError::ConflictError(format!(
+                "Nicht genug freie Zimmer für das Kontingent an folgenden Nächten: {}",
+                nights.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Nights on which more bookings are drawn down than the allotment holds
+    async fn nights_over_quantity(
+        transaction: &tokio_postgres::Transaction<'_>,
+        allotment_id: i32,
+    ) -> DbResult<Vec<String>> {
+        let rows = transaction
+            .query(
+                "SELECT TO_CHAR(n.night, 'DD.MM.YYYY') AS night
+                 FROM allotments a
+                 CROSS JOIN generate_series(a.start_date, a.end_date - 1, INTERVAL '1 day') AS n(night)
+                 WHERE a.id = $1
+                   AND (SELECT COUNT(*) FROM bookings b
+                        WHERE b.allotment_id = a.id
+                          AND b.status != 'storniert'
+                          AND b.checkin_date::date <= n.night::date
+                          AND b.checkout_date::date > n.night::date) > a.quantity
+                 ORDER BY n.night",
+                &[&allotment_id],
+            )
+            .await?;
+
+        Ok(rows.iter().map(|row| row.get("night")).collect())
+    }
+
+    /// Either a room type or a room set, a valid date range and a deadline before arrival
+    fn validate(
+        gebaeude_typ: &Option<String>,
+        room_ids: &[i32],
+        start_date: &str,
+        end_date: &str,
+        quantity: i32,
+        release_date: &str,
+    ) -> DbResult<()> {
+        if gebaeude_typ.is_some() != room_ids.is_empty() {
+            return Err(DbError::ValidationError(
+                "Ein Kontingent umfasst entweder einen Zimmertyp oder eine Zimmerauswahl".to_string(),
+            ));
+        }
+        if end_date <= start_date {
+            return Err(DbError::ValidationError("Das Enddatum muss nach dem Startdatum liegen".to_string()));
+        }
+        if quantity < 1 {
+            return Err(DbError::ValidationError("Ein Kontingent umfasst mindestens ein Zimmer".to_string()));
+        }
+        if !room_ids.is_empty() && quantity as usize > room_ids.len() {
+            return Err(DbError::ValidationError(format!(
+                "Das Kontingent umfasst {} Zimmer, die Auswahl aber nur {}",
+                quantity,
+                room_ids.len()
+            )));
+        }
+        if release_date > start_date {
+            return Err(DbError::ValidationError(
+                "Die Freigabefrist muss spätestens am Anreisetag des Kontingents liegen".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+use crate::bank_import::{self, BankTransaction, MatchCandidate};
+use crate::database_pg::{BankImportResult, DbError, DbPool, DbResult, ImportedBankTransaction, Payment, PaymentRepository};
+use crate::money::Money;
+
+const TRANSACTION_COLUMNS: &str = "id, booking_date::text as booking_date, amount, payer_name, payer_iban, remittance,
+    bank_reference, file_name, status, booking_id, payment_id, confidence,
+    imported_at::text as imported_at, matched_at::text as matched_at, matched_by";
+
+/// Repository for imported bank statement transactions and their match to bookings
+pub struct BankTransactionRepository;
+
+impl BankTransactionRepository {
+    /// Run bank import migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/036_bank_import.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Store the incoming transfers of a parsed statement. Transactions imported before
+    /// (same date, amount, references and remittance text) are skipped.
+    pub async fn import(
+        pool: &DbPool,
+        file_name: Option<String>,
+        transactions: &[BankTransaction],
+        imported_by: Option<String>,
+    ) -> DbResult<BankImportResult> {
+        let client = pool.get().await?;
+
+        let mut imported = 0;
+        let mut duplicates = 0;
+        let mut skipped_debits = 0;
+        for transaction in transactions {
+            if !transaction.amount.is_positive() {
+                skipped_debits += 1;
+                continue;
+            }
+
+            let dedupe_source = format!(
+                "{}|{}|{}|{}|{}",
+                transaction.booking_date,
+                transaction.amount,
+                transaction.bank_reference.as_deref().unwrap_or(""),
+                transaction.payer_iban.as_deref().unwrap_or(""),
+                transaction.remittance
+            );
+            let booking_date = transaction.booking_date.format("%Y-%m-%d").to_string();
+
+            let rows_affected = client
+                .execute(
+                    "INSERT INTO bank_transactions
+                        (booking_date, amount, payer_name, payer_iban, remittance, bank_reference, file_name, dedupe_key, imported_by)
+                     VALUES ($1::text::date, $2, $3, $4, $5, $6, $7, md5($8), $9)
+                     ON CONFLICT (dedupe_key) DO NOTHING",
+                    &[
+                        &booking_date,
+                        &transaction.amount,
+                        &transaction.payer_name,
+                        &transaction.payer_iban,
+                        &transaction.remittance,
+                        &transaction.bank_reference,
+                        &file_name,
+                        &dedupe_source,
+                        &imported_by,
+                    ],
+                )
+                .await
+                .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+
+            if rows_affected == 0 {
+                duplicates += 1;
+            } else {
+                imported += 1;
+            }
+        }
+
+        Ok(BankImportResult {
+            imported,
+            duplicates,
+            skipped_debits,
+            transactions: Self::get_open(pool).await?,
+        })
+    }
+
+    /// Open transactions with their match proposals (best first)
+    pub async fn get_open(pool: &DbPool) -> DbResult<Vec<ImportedBankTransaction>> {
+        let mut transactions = Self::get_by_status(pool, "offen").await?;
+        let candidates = Self::get_candidates(pool).await?;
+
+        for transaction in transactions.iter_mut() {
+            let booking_date = chrono::NaiveDate::parse_from_str(&transaction.booking_date, "%Y-%m-%d")
+                .map_err(|e| DbError::Other(format!("Invalid booking date: {}", e)))?;
+            let parsed = BankTransaction {
+                booking_date,
+                amount: transaction.amount,
+                payer_name: transaction.payer_name.clone(),
+                payer_iban: transaction.payer_iban.clone(),
+                remittance: transaction.remittance.clone(),
+                bank_reference: transaction.bank_reference.clone(),
+            };
+            transaction.proposals = bank_import::propose_matches(&parsed, &candidates);
+        }
+
+        Ok(transactions)
+    }
+
+    /// Transactions by status ('offen', 'zugeordnet', 'ignoriert'), newest first
+    pub async fn get_by_status(pool: &DbPool, status: &str) -> DbResult<Vec<ImportedBankTransaction>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM bank_transactions WHERE status = $1 ORDER BY booking_date DESC, id DESC LIMIT 500",
+                    TRANSACTION_COLUMNS
+                ),
+                &[&status],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(ImportedBankTransaction::from).collect())
+    }
+
+    /// Record the transfer as payment of `booking_id`. `confidence` is the score of the
+    /// confirmed proposal (None when assigned by hand).
+    pub async fn confirm(
+        pool: &DbPool,
+        id: i32,
+        booking_id: i32,
+        confidence: Option<i16>,
+        matched_by: Option<String>,
+    ) -> DbResult<Payment> {
+        let client = pool.get().await?;
+
+        // Claim the transaction first: two users confirming at once must not pay twice
+        let row = client
+            .query_opt(
+                &format!(
+                    "UPDATE bank_transactions
+                     SET status = 'zugeordnet', booking_id = $2, confidence = $3,
+                         matched_at = CURRENT_TIMESTAMP, matched_by = $4
+                     WHERE id = $1 AND status = 'offen'
+                     RETURNING {}",
+                    TRANSACTION_COLUMNS
+                ),
+                &[&id, &booking_id, &confidence, &matched_by],
+            )
+            .await?
+            .ok_or_else(|| DbError::ConflictError(format!("Bank transaction {} is not open anymore", id)))?;
+        let transaction = ImportedBankTransaction::from(row);
+
+        let reference = transaction
+            .bank_reference
+            .clone()
+            .or_else(|| Some(transaction.remittance.chars().take(255).collect::<String>()))
+            .filter(|r| !r.is_empty());
+        let notes = [transaction.payer_name.as_deref(), Some(transaction.remittance.as_str())]
+            .into_iter()
+            .flatten()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(": ");
+
+        let payment = PaymentRepository::create(
+            pool,
+            booking_id,
+            transaction.amount,
+            transaction.booking_date.clone(),
+            Some("Überweisung".to_string()),
+            reference,
+            Some(notes).filter(|n| !n.is_empty()),
+            matched_by,
+        )
+        .await;
+
+        match payment {
+            Ok(payment) => {
+                client
+                    .execute("UPDATE bank_transactions SET payment_id = $2 WHERE id = $1", &[&id, &payment.id])
+                    .await?;
+                Ok(payment)
+            }
+            Err(e) => {
+                Self::reopen(&client, id).await?;
+                Err(e)
+            }
+        }
+    }
+
+    /// Not a booking payment (e.g. membership fee, other income)
+    pub async fn ignore(pool: &DbPool, id: i32, matched_by: Option<String>) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let rows_affected = client
+            .execute(
+                "UPDATE bank_transactions
+                 SET status = 'ignoriert', matched_at = CURRENT_TIMESTAMP, matched_by = $2
+                 WHERE id = $1 AND status = 'offen'",
+                &[&id, &matched_by],
+            )
+            .await?;
+        if rows_affected == 0 {
+            return Err(DbError::ConflictError(format!("Bank transaction {} is not open anymore", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Undo a match or ignore: the payment is deleted, the transaction is open again
+    pub async fn undo(pool: &DbPool, id: i32) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let payment_id: Option<i32> = client
+            .query_opt("SELECT payment_id FROM bank_transactions WHERE id = $1", &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Bank transaction with ID {} not found", id)))?
+            .get("payment_id");
+
+        match payment_id {
+            // PaymentRepository::delete reopens the transaction
+            Some(payment_id) => {
+                PaymentRepository::delete(pool, payment_id).await?;
+            }
+            None => Self::reopen(&client, id).await?,
+        }
+
+        Ok(())
+    }
+
+    async fn reopen(client: &tokio_postgres::Client, id: i32) -> DbResult<()> {
+        client
+            .execute(
+                "UPDATE bank_transactions
+                 SET status = 'offen', booking_id = NULL, payment_id = NULL, confidence = NULL,
+                     matched_at = NULL, matched_by = NULL
+                 WHERE id = $1",
+                &[&id],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Bookings with an open amount: reservation number, invoice numbers, guest and payment
+    /// recipient name, expected amounts (outstanding, invoice amount, deposit requests)
+    async fn get_candidates(pool: &DbPool) -> DbResult<Vec<MatchCandidate>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT b.id, b.reservierungsnummer, bb.outstanding,
+                        TRIM(g.vorname || ' ' || g.nachname) AS guest_name,
+                        pr.name AS recipient_name, pr.company AS recipient_company,
+                        ARRAY(SELECT i.invoice_number::text FROM invoices i
+                              WHERE i.booking_id = b.id AND i.document_type = 'rechnung') AS invoice_numbers,
+                        ARRAY(SELECT i.amount_due FROM invoices i
+                              WHERE i.booking_id = b.id AND i.document_type = 'rechnung') AS invoice_amounts,
+                        ARRAY(SELECT d.amount FROM deposit_requests d WHERE d.booking_id = b.id) AS deposit_amounts
+                 FROM bookings b
+                 JOIN booking_balances bb ON bb.booking_id = b.id
+                 JOIN guests g ON g.id = b.guest_id
+                 LEFT JOIN payment_recipients pr ON pr.id = b.payment_recipient_id
+                 WHERE b.status <> 'storniert' AND bb.outstanding > 0",
+                &[],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let outstanding: Money = row.get("outstanding");
+                let mut expected_amounts = vec![outstanding];
+                expected_amounts.extend(row.get::<_, Vec<Money>>("invoice_amounts"));
+                expected_amounts.extend(row.get::<_, Vec<Money>>("deposit_amounts"));
+
+                let names = [
+                    row.get::<_, Option<String>>("guest_name"),
+                    row.get("recipient_name"),
+                    row.get("recipient_company"),
+                ]
+                .into_iter()
+                .flatten()
+                .filter(|name| !name.trim().is_empty())
+                .collect();
+
+                MatchCandidate {
+                    booking_id: row.get("id"),
+                    reservierungsnummer: row.get("reservierungsnummer"),
+                    invoice_numbers: row.get("invoice_numbers"),
+                    names,
+                    expected_amounts,
+                }
+            })
+            .collect())
+    }
+}
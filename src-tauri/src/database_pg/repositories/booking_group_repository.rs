@@ -0,0 +1,343 @@
+use crate::booking_status::BookingStatus;
+use crate::database_pg::repositories::BookingRepository;
+use crate::database_pg::{
+    Booking, BookingGroup, BookingGroupRoom, BookingGroupWithRooms, DbError, DbPool, DbResult, GroupRoomInput,
+};
+use crate::money::Money;
+
+const GROUP_COLUMNS: &str = "id, name, reservierungsnummer, organiser_guest_id, payment_recipient_id, bemerkungen,
+    created_at::text as created_at, updated_at::text as updated_at, created_by";
+
+/// Repository for group bookings (several rooms of one organiser, one invoice)
+pub struct BookingGroupRepository;
+
+impl BookingGroupRepository {
+    /// Run booking groups migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/026_booking_groups.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Create a group with all its room bookings. Availability of every room is checked and
+    /// locked in one SERIALIZABLE transaction - either all rooms are booked or none.
+    /// Room bookings get the reservation number "<group number>-01", "-02", ... and the organiser
+    /// as guest; prices are calculated afterwards by the caller (pricing engine).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_with_bookings(
+        pool: &DbPool,
+        name: String,
+        organiser_guest_id: i32,
+        payment_recipient_id: Option<i32>,
+        status: BookingStatus,
+        bemerkungen: Option<String>,
+        rooms: &[GroupRoomInput],
+        created_by: Option<String>,
+    ) -> DbResult<BookingGroupWithRooms> {
+        BookingRepository::validate_initial_status(status)?;
+        Self::validate_rooms(rooms)?;
+
+        let mut client = pool.get().await?;
+        let reservierungsnummer = BookingRepository::next_reservation_number(&client).await?;
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::Serializable)
+            .start()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction start failed: {}", e)))?;
+
+        // 1. Check and lock all rooms, report every conflict at once
+        let mut conflicts = Vec::new();
+        for room in rooms {
+            let is_available = BookingRepository::check_and_lock_availability(
+                &transaction,
+                room.room_id,
+                &room.checkin_date,
+                &room.checkout_date,
+                None,
+            )
+            .await?;
+
+            if !is_available {
+                let room_name: String = transaction
+                    .query_opt("SELECT name FROM rooms WHERE id = $1", &[&room.room_id])
+                    .await?
+                    .map(|row| row.get("name"))
+                    .unwrap_or_else(|| room.room_id.to_string());
+                conflicts.push(format!("Zimmer {} ({} bis {})", room_name, room.checkin_date, room.checkout_date));
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(DbError::DoubleBookingError(format!(
+                "Folgende Zimmer sind im gewünschten Zeitraum bereits belegt: {}. Es wurde keine Buchung angelegt.",
+                conflicts.join(", ")
+            )));
+        }
+
+        // 2. Group
+        let group_id: i32 = transaction
+            .query_one(
+                "INSERT INTO booking_groups (name, reservierungsnummer, organiser_guest_id, payment_recipient_id,
+                                             bemerkungen, created_by)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 RETURNING id",
+                &[&name, &reservierungsnummer, &organiser_guest_id, &payment_recipient_id, &bemerkungen, &created_by],
+            )
+            .await?
+            .get("id");
+
+        // 3. Room bookings (first one = master booking) with their guest names
+        for (i, room) in rooms.iter().enumerate() {
+            let room_reservierungsnummer = format!("{}-{:02}", reservierungsnummer, i + 1);
+            let booking_id: i32 = transaction
+                .query_one(
+                    "INSERT INTO bookings (
+                        room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
+                        anzahl_gaeste, status, gesamtpreis, bemerkungen, payment_recipient_id,
+                        booking_group_id, created_by, updated_by, created_at, updated_at
+                     ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+                     ) RETURNING id",
+                    &[
+                        &room.room_id,
+                        &organiser_guest_id,
+                        &room_reservierungsnummer,
+                        &room.checkin_date,
+                        &room.checkout_date,
+                        &room.anzahl_gaeste,
+                        &status,
+                        &Money::ZERO,
+                        &bemerkungen,
+                        &payment_recipient_id,
+                        &group_id,
+                        &created_by,
+                    ],
+                )
+                .await
+                .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?
+                .get("id");
+
+            Self::insert_guest_names(&transaction, booking_id, &room.guest_names).await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction commit failed: {}", e)))?;
+
+        Self::get_with_rooms(pool, group_id).await
+    }
+
+    /// Get all groups, newest first
+    pub async fn get_all(pool: &DbPool) -> DbResult<Vec<BookingGroup>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!("SELECT {} FROM booking_groups ORDER BY created_at DESC, id DESC", GROUP_COLUMNS),
+                &[],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(BookingGroup::from).collect())
+    }
+
+    /// Get group by ID
+    pub async fn get_by_id(pool: &DbPool, id: i32) -> DbResult<BookingGroup> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(&format!("SELECT {} FROM booking_groups WHERE id = $1", GROUP_COLUMNS), &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Booking group with ID {} not found", id)))?;
+
+        Ok(BookingGroup::from(row))
+    }
+
+    /// Group with its room bookings (master booking first) and the guest names per room
+    pub async fn get_with_rooms(pool: &DbPool, id: i32) -> DbResult<BookingGroupWithRooms> {
+        let group = Self::get_by_id(pool, id).await?;
+        let bookings = Self::get_bookings(pool, id).await?;
+
+        let client = pool.get().await?;
+        let booking_ids: Vec<i32> = bookings.iter().map(|b| b.id).collect();
+        let room_rows = client
+            .query(
+                "SELECT b.id AS booking_id, r.name AS room_name
+                 FROM bookings b JOIN rooms r ON r.id = b.room_id
+                 WHERE b.id = ANY($1)",
+                &[&booking_ids],
+            )
+            .await?;
+        let name_rows = client
+            .query(
+                "SELECT booking_id, name FROM booking_guest_names WHERE booking_id = ANY($1) ORDER BY booking_id, position",
+                &[&booking_ids],
+            )
+            .await?;
+
+        let rooms = bookings
+            .into_iter()
+            .map(|booking| BookingGroupRoom {
+                room_name: room_rows
+                    .iter()
+                    .find(|row| row.get::<_, i32>("booking_id") == booking.id)
+                    .map(|row| row.get("room_name"))
+                    .unwrap_or_default(),
+                guest_names: name_rows
+                    .iter()
+                    .filter(|row| row.get::<_, i32>("booking_id") == booking.id)
+                    .map(|row| row.get("name"))
+                    .collect(),
+                booking,
+            })
+            .collect();
+
+        Ok(BookingGroupWithRooms { group, rooms })
+    }
+
+    /// Room bookings of a group in booking order (first = master booking)
+    pub async fn get_bookings(pool: &DbPool, id: i32) -> DbResult<Vec<Booking>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
+                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
+                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis, belegung_preis,
+                        geschaeftsreise, kurtaxe, kurtaxe_personen, kurtaxe_befreit,
+                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
+                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
+                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
+                        ist_dpolg_mitglied, NULL::double precision as credit_used,
+                        created_by, updated_by, booking_group_id, allotment_id,
+                        option_expires_at::text as option_expires_at, zimmer_fixiert, split_parent_id, series_id
+                 FROM bookings
+                 WHERE booking_group_id = $1
+                 ORDER BY id",
+                &[&id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(Booking::from).collect())
+    }
+
+    /// ID of the master booking (first room booking) of a group
+    pub async fn master_booking_id(pool: &DbPool, id: i32) -> DbResult<i32> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_one("SELECT MIN(id) AS master_id FROM bookings WHERE booking_group_id = $1", &[&id])
+            .await?;
+
+        row.get::<_, Option<i32>>("master_id")
+            .ok_or_else(|| DbError::NotFound(format!("Booking group {} has no room bookings", id)))
+    }
+
+    /// Update name, payment recipient and notes. The payment recipient is copied to all room bookings.
+    pub async fn update(
+        pool: &DbPool,
+        id: i32,
+        name: String,
+        payment_recipient_id: Option<i32>,
+        bemerkungen: Option<String>,
+    ) -> DbResult<BookingGroup> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let row = transaction
+            .query_opt(
+                &format!(
+                    "UPDATE booking_groups
+                     SET name = $2, payment_recipient_id = $3, bemerkungen = $4, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = $1
+                     RETURNING {}",
+                    GROUP_COLUMNS
+                ),
+                &[&id, &name, &payment_recipient_id, &bemerkungen],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Booking group with ID {} not found", id)))?;
+
+        transaction
+            .execute(
+                "UPDATE bookings SET payment_recipient_id = $2, updated_at = CURRENT_TIMESTAMP
+                 WHERE booking_group_id = $1",
+                &[&id, &payment_recipient_id],
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(BookingGroup::from(row))
+    }
+
+    /// Replace the guest name list of a room booking
+    pub async fn set_guest_names(pool: &DbPool, booking_id: i32, names: &[String]) -> DbResult<()> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        transaction
+            .execute("DELETE FROM booking_guest_names WHERE booking_id = $1", &[&booking_id])
+            .await?;
+        Self::insert_guest_names(&transaction, booking_id, names).await?;
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn insert_guest_names(
+        transaction: &tokio_postgres::Transaction<'_>,
+        booking_id: i32,
+        names: &[String],
+    ) -> DbResult<()> {
+        let names = names.iter().map(|n| n.trim()).filter(|n| !n.is_empty());
+        for (position, name) in names.enumerate() {
+            transaction
+                .execute(
+                    "INSERT INTO booking_guest_names (booking_id, position, name) VALUES ($1, $2, $3)",
+                    &[&booking_id, &(position as i32 + 1), &name],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// A group needs at least one room, valid stays and no room twice in overlapping stays
+    fn validate_rooms(rooms: &[GroupRoomInput]) -> DbResult<()> {
+        if rooms.is_empty() {
+            return Err(DbError::ValidationError("Eine Gruppenbuchung braucht mindestens ein Zimmer".to_string()));
+        }
+
+        for (i, room) in rooms.iter().enumerate() {
+            if room.checkout_date <= room.checkin_date {
+                return Err(DbError::ValidationError(format!(
+                    "Zimmer {}: Abreise muss nach der Anreise liegen",
+                    i + 1
+                )));
+            }
+            if room.anzahl_gaeste < 1 {
+                return Err(DbError::ValidationError(format!("Zimmer {}: mindestens ein Gast", i + 1)));
+            }
+            let overlapping = rooms[..i].iter().any(|other| {
+                other.room_id == room.room_id
+                    && other.checkin_date < room.checkout_date
+                    && other.checkout_date > room.checkin_date
+            });
+            if overlapping {
+                return Err(DbError::ValidationError(format!(
+                    "Zimmer {}: das Zimmer ist in der Gruppe mehrfach für denselben Zeitraum angegeben",
+                    i + 1
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,24 +1,90 @@
-use crate::database_pg::{DbPool, DbResult, Booking};
+use crate::booking_status::BookingStatus;
+use crate::database_pg::{DbPool, DbError, DbResult, Booking, StaySegmentInput};
+use crate::database_pg::repositories::{AllotmentRepository, RoomBlockRepository};
+use crate::money::Money;
+use crate::reservation_number;
+use crate::room_assignment::RoomMove;
+
+const BOOKING_COLUMNS: &str = "id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
+                               anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
+                               anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis, belegung_preis,
+                               geschaeftsreise, kurtaxe, kurtaxe_personen, kurtaxe_befreit,
+                               anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
+                               mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
+                               ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
+                               ist_dpolg_mitglied, NULL::double precision as credit_used,
+                               created_by, updated_by, booking_group_id, allotment_id,
+                               option_expires_at::text as option_expires_at, zimmer_fixiert, split_parent_id, series_id";
 
 pub struct BookingRepository;
 
 impl BookingRepository {
+    /// Run booking status migration (idempotent)
+    pub async fn run_status_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/025_booking_status.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Run option bookings migration (idempotent)
+    pub async fn run_option_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/029_option_bookings.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Run split stay migration (idempotent)
+    pub async fn run_split_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/032_booking_segments.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Run reservation number migration (idempotent)
+    pub async fn run_reservation_number_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/034_reservation_numbers.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
     /// Get all bookings from the database
     pub async fn get_all(pool: &DbPool) -> DbResult<Vec<Booking>> {
         let client = pool.get().await?;
 
         let rows = client
             .query(
-                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                        ist_dpolg_mitglied, NULL::double precision as credit_used,
-                        created_by, updated_by
-                 FROM bookings
-                 ORDER BY checkin_date DESC",
+                &format!(
+                    "SELECT {},
+                            bb.paid AS bezahlter_betrag, bb.outstanding AS offener_betrag, bb.payment_status
+                     FROM bookings
+                     LEFT JOIN booking_balances bb ON bb.booking_id = bookings.id
+                     ORDER BY checkin_date DESC",
+                    BOOKING_COLUMNS
+                ),
                 &[],
             )
             .await?;
@@ -32,16 +98,14 @@ impl BookingRepository {
 
         let row = client
             .query_one(
-                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                        ist_dpolg_mitglied, NULL::double precision as credit_used,
-                        created_by, updated_by
-                 FROM bookings
-                 WHERE id = $1",
+                &format!(
+                    "SELECT {},
+                            bb.paid AS bezahlter_betrag, bb.outstanding AS offener_betrag, bb.payment_status
+                     FROM bookings
+                     LEFT JOIN booking_balances bb ON bb.booking_id = bookings.id
+                     WHERE id = $1",
+                    BOOKING_COLUMNS
+                ),
                 &[&id],
             )
             .await
@@ -95,13 +159,13 @@ impl BookingRepository {
         checkin_date: String,
         checkout_date: String,
         anzahl_gaeste: i32,
-        status: String,
-        gesamtpreis: f64,
+        status: BookingStatus,
+        gesamtpreis: Money,
         bemerkungen: Option<String>,
         anzahl_begleitpersonen: Option<i32>,
-        grundpreis: Option<f64>,
-        services_preis: Option<f64>,
-        rabatt_preis: Option<f64>,
+        grundpreis: Option<Money>,
+        services_preis: Option<Money>,
+        rabatt_preis: Option<Money>,
         anzahl_naechte: Option<i32>,
         bezahlt: Option<bool>,
         bezahlt_am: Option<String>,
@@ -115,29 +179,28 @@ impl BookingRepository {
         ist_dpolg_mitglied: Option<bool>,
         created_by: Option<String>,
     ) -> DbResult<Booking> {
+        Self::validate_initial_status(status)?;
+
         let client = pool.get().await?;
 
         let row = client
             .query_one(
-                "INSERT INTO bookings (
-                    room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                    anzahl_gaeste, status, gesamtpreis, bemerkungen,
-                    anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                    anzahl_naechte, bezahlt, bezahlt_am, zahlungsmethode,
-                    mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                    ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                    ist_dpolg_mitglied, created_by, updated_by, created_at, updated_at
-                 ) VALUES (
-                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
-                    $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
-                    $21, $22, $23, $24, $25, $25, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
-                 ) RETURNING id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                             anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                             anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                             anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                             mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                             ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                             ist_dpolg_mitglied, NULL::double precision as credit_used, created_by, updated_by",
+                &format!(
+                    "INSERT INTO bookings (
+                        room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
+                        anzahl_gaeste, status, gesamtpreis, bemerkungen,
+                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
+                        anzahl_naechte, bezahlt, bezahlt_am, zahlungsmethode,
+                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
+                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
+                        ist_dpolg_mitglied, created_by, updated_by, created_at, updated_at
+                     ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+                        $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+                        $21, $22, $23, $24, $25, $25, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+                     ) RETURNING {}",
+                    BOOKING_COLUMNS
+                ),
                 &[
                     &room_id, &guest_id, &reservierungsnummer, &checkin_date, &checkout_date,
                     &anzahl_gaeste, &status, &gesamtpreis, &bemerkungen,
@@ -167,13 +230,13 @@ impl BookingRepository {
         checkin_date: String,
         checkout_date: String,
         anzahl_gaeste: i32,
-        status: String,
-        gesamtpreis: f64,
+        status: BookingStatus,
+        gesamtpreis: Money,
         bemerkungen: Option<String>,
         anzahl_begleitpersonen: Option<i32>,
-        grundpreis: Option<f64>,
-        services_preis: Option<f64>,
-        rabatt_preis: Option<f64>,
+        grundpreis: Option<Money>,
+        services_preis: Option<Money>,
+        rabatt_preis: Option<Money>,
         anzahl_naechte: Option<i32>,
         bezahlt: Option<bool>,
         bezahlt_am: Option<String>,
@@ -188,12 +251,23 @@ impl BookingRepository {
         updated_by: Option<String>,  // ← NEW: Audit trail
         expected_updated_at: Option<String>,  // ← NEW: Optimistic Locking parameter
     ) -> DbResult<Booking> {
-        let client = pool.get().await?;
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Status changes must follow the state machine - the row stays locked until the update
+        let current_status: BookingStatus = transaction
+            .query_opt("SELECT status FROM bookings WHERE id = $1 FOR UPDATE", &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Booking with ID {} not found", id)))?
+            .get("status");
+        current_status
+            .validate_transition(status)
+            .map_err(DbError::InvalidStatusTransition)?;
 
         // If expected_updated_at is provided, use optimistic locking
         if let Some(expected_ts) = expected_updated_at {
             // Try update with version check
-            let rows_affected = client
+            let rows_affected = transaction
                 .execute(
                     "UPDATE bookings SET
                         room_id = $2, guest_id = $3, reservierungsnummer = $4,
@@ -226,32 +300,30 @@ impl BookingRepository {
                     id
                 )));
             }
+            transaction.commit().await?;
 
             // Return updated booking
             Self::get_by_id(pool, id).await
         } else {
             // Fallback: Update without optimistic locking (for backward compatibility)
-            let row = client
+            let row = transaction
                 .query_one(
-                    "UPDATE bookings SET
-                        room_id = $2, guest_id = $3, reservierungsnummer = $4,
-                        checkin_date = $5, checkout_date = $6, anzahl_gaeste = $7,
-                        status = $8, gesamtpreis = $9, bemerkungen = $10,
-                        anzahl_begleitpersonen = $11, grundpreis = $12, services_preis = $13,
-                        rabatt_preis = $14, anzahl_naechte = $15, bezahlt = $16,
-                        bezahlt_am = $17, zahlungsmethode = $18, mahnung_gesendet_am = $19,
-                        rechnung_versendet_am = $20, rechnung_versendet_an = $21,
-                        ist_stiftungsfall = $22, payment_recipient_id = $23,
-                        putzplan_checkout_date = $24, ist_dpolg_mitglied = $25,
-                        updated_by = $26, updated_at = CURRENT_TIMESTAMP
-                     WHERE id = $1
-                     RETURNING id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                               anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                               anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                               anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                               mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                               ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                               ist_dpolg_mitglied, NULL::double precision as credit_used, created_by, updated_by",
+                    &format!(
+                        "UPDATE bookings SET
+                            room_id = $2, guest_id = $3, reservierungsnummer = $4,
+                            checkin_date = $5, checkout_date = $6, anzahl_gaeste = $7,
+                            status = $8, gesamtpreis = $9, bemerkungen = $10,
+                            anzahl_begleitpersonen = $11, grundpreis = $12, services_preis = $13,
+                            rabatt_preis = $14, anzahl_naechte = $15, bezahlt = $16,
+                            bezahlt_am = $17, zahlungsmethode = $18, mahnung_gesendet_am = $19,
+                            rechnung_versendet_am = $20, rechnung_versendet_an = $21,
+                            ist_stiftungsfall = $22, payment_recipient_id = $23,
+                            putzplan_checkout_date = $24, ist_dpolg_mitglied = $25,
+                            updated_by = $26, updated_at = CURRENT_TIMESTAMP
+                         WHERE id = $1
+                         RETURNING {}",
+                        BOOKING_COLUMNS
+                    ),
                     &[
                         &id, &room_id, &guest_id, &reservierungsnummer, &checkin_date, &checkout_date,
                         &anzahl_gaeste, &status, &gesamtpreis, &bemerkungen,
@@ -264,15 +336,575 @@ impl BookingRepository {
                 )
                 .await
                 .map_err(|_| crate::database_pg::DbError::NotFound(format!("Booking with ID {} not found", id)))?;
+            transaction.commit().await?;
 
             Ok(Booking::from(row))
         }
     }
 
+    /// Change only the status of a booking. The row is locked while the transition is
+    /// validated. Returns the previous status - the caller runs `previous.effects(to)`.
+    pub async fn change_status(
+        pool: &DbPool,
+        id: i32,
+        to: BookingStatus,
+        updated_by: Option<String>,
+    ) -> DbResult<(BookingStatus, Booking)> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let previous: BookingStatus = transaction
+            .query_opt("SELECT status FROM bookings WHERE id = $1 FOR UPDATE", &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Booking with ID {} not found", id)))?
+            .get("status");
+        previous
+            .validate_transition(to)
+            .map_err(DbError::InvalidStatusTransition)?;
+
+        let row = transaction
+            .query_one(
+                &format!(
+                    "UPDATE bookings SET status = $2, updated_by = $3, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = $1
+                     RETURNING {}",
+                    BOOKING_COLUMNS
+                ),
+                &[&id, &to, &updated_by],
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok((previous, Booking::from(row)))
+    }
+
+    /// Move the expiry of an option booking (must lie in the future)
+    pub async fn set_option_expiry(
+        pool: &DbPool,
+        id: i32,
+        expires_at: String,
+        updated_by: Option<String>,
+    ) -> DbResult<Booking> {
+        let client = pool.get().await?;
+
+        let updated = client
+            .execute(
+                "UPDATE bookings
+                 SET option_expires_at = $2::text::timestamp, updated_by = $3, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1 AND status = 'optioniert' AND $2::text::timestamp > CURRENT_TIMESTAMP",
+                &[&id, &expires_at, &updated_by],
+            )
+            .await
+            .map_err(|e| DbError::ValidationError(format!("Ungültiger Ablaufzeitpunkt: {}", e)))?;
+        if updated == 0 {
+            let booking = Self::get_by_id(pool, id).await?;
+            if !booking.status.is_option() {
+                return Err(DbError::ValidationError(format!(
+                    "Buchung ist {} - nur Optionen haben einen Ablaufzeitpunkt",
+                    booking.status.label()
+                )));
+            }
+            return Err(DbError::ValidationError(
+                "Der Ablaufzeitpunkt muss in der Zukunft liegen".to_string(),
+            ));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Cancel all options whose hold has expired. Atomic, so a confirmation racing the
+    /// release either wins (status no longer 'optioniert') or sees the cancelled booking.
+    pub async fn release_expired_options(pool: &DbPool) -> DbResult<Vec<Booking>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "UPDATE bookings
+                     SET status = 'storniert', updated_by = 'system', updated_at = CURRENT_TIMESTAMP
+                     WHERE status = 'optioniert' AND option_expires_at <= CURRENT_TIMESTAMP
+                     RETURNING {}",
+                    BOOKING_COLUMNS
+                ),
+                &[],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(Booking::from).collect())
+    }
+
+    /// Non-cancelled bookings overlapping [from, to) (room optimiser)
+    pub async fn get_active_overlapping(pool: &DbPool, from: &str, to: &str) -> DbResult<Vec<Booking>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {}
+                     FROM bookings
+                     WHERE checkin_date < $2 AND checkout_date > $1 AND status != 'storniert'
+                     ORDER BY checkin_date ASC",
+                    BOOKING_COLUMNS
+                ),
+                &[&from, &to],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(Booking::from).collect())
+    }
+
+    /// Fix a booking to its room (promised to the guest) or release it for the room optimiser
+    pub async fn set_room_fixed(
+        pool: &DbPool,
+        id: i32,
+        fixed: bool,
+        updated_by: Option<String>,
+    ) -> DbResult<Booking> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "UPDATE bookings SET zimmer_fixiert = $2, updated_by = $3, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = $1
+                     RETURNING {}",
+                    BOOKING_COLUMNS
+                ),
+                &[&id, &fixed, &updated_by],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Booking with ID {} not found", id)))?;
+
+        Ok(Booking::from(row))
+    }
+
+    /// Move several bookings to other rooms at once (room optimiser). All rooms are changed
+    /// first and checked afterwards, so bookings may swap rooms. Nothing is changed if a
+    /// booking was moved or fixed meanwhile or a new room is no longer free.
+    pub async fn reassign_rooms(
+        pool: &DbPool,
+        moves: &[RoomMove],
+        updated_by: Option<String>,
+    ) -> DbResult<Vec<Booking>> {
+        let mut client = pool.get().await?;
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::Serializable)
+            .start()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction start failed: {}", e)))?;
+
+        let mut stays = Vec::with_capacity(moves.len());
+        for m in moves {
+            let row = transaction
+                .query_opt(
+                    "SELECT room_id, reservierungsnummer, checkin_date, checkout_date, status, zimmer_fixiert
+                     FROM bookings WHERE id = $1 FOR UPDATE",
+                    &[&m.booking_id],
+                )
+                .await?
+                .ok_or_else(|| DbError::NotFound(format!("Booking with ID {} not found", m.booking_id)))?;
+
+            let reservierungsnummer: String = row.get("reservierungsnummer");
+            let status: BookingStatus = row.get("status");
+            if row.get::<_, i32>("room_id") != m.from_room_id
+                || row.get::<_, bool>("zimmer_fixiert")
+                || status == BookingStatus::Storniert
+            {
+                return Err(DbError::ConflictError(format!(
+                    "Buchung {} wurde zwischenzeitlich geändert - bitte den Vorschlag neu berechnen",
+                    reservierungsnummer
+                )));
+            }
+            stays.push((reservierungsnummer, row.get::<_, String>("checkin_date"), row.get::<_, String>("checkout_date")));
+        }
+
+        for m in moves {
+            transaction
+                .execute(
+                    "UPDATE bookings SET room_id = $2, updated_by = $3, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                    &[&m.booking_id, &m.to_room_id, &updated_by],
+                )
+                .await?;
+        }
+
+        for (m, (reservierungsnummer, checkin_date, checkout_date)) in moves.iter().zip(&stays) {
+            let is_available = Self::check_and_lock_availability(
+                &transaction,
+                m.to_room_id,
+                checkin_date,
+                checkout_date,
+                Some(m.booking_id),
+            )
+            .await?;
+            if !is_available {
+                return Err(DbError::DoubleBookingError(format!(
+                    "Das neue Zimmer für Buchung {} ist nicht mehr frei - bitte den Vorschlag neu berechnen",
+                    reservierungsnummer
+                )));
+            }
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction commit failed: {}", e)))?;
+
+        let mut bookings = Vec::with_capacity(moves.len());
+        for m in moves {
+            bookings.push(Self::get_by_id(pool, m.booking_id).await?);
+        }
+        Ok(bookings)
+    }
+
+    /// All segments of a split stay (first segment = master), ordered by check-in.
+    /// For a booking that was never split this is the booking itself.
+    pub async fn get_stay_segments(pool: &DbPool, master_id: i32) -> DbResult<Vec<Booking>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {}
+                     FROM bookings
+                     WHERE id = $1 OR split_parent_id = $1
+                     ORDER BY checkin_date ASC, id ASC",
+                    BOOKING_COLUMNS
+                ),
+                &[&master_id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(Booking::from).collect())
+    }
+
+    /// Split a stay into consecutive segments in different rooms (room change within the stay).
+    /// The booking keeps the first segment, every further segment becomes a new booking with the
+    /// same reservation number, guest, status and accompanying guests, linked to the first segment
+    /// of the stay. All rooms are checked and locked in one SERIALIZABLE transaction - either the
+    /// whole split is saved or nothing. Segments are fixed to their room (room optimiser).
+    /// A checked-in guest is checked in again in the next room, so later segments start as
+    /// 'bestaetigt'. Returns all segments of the stay; prices are recalculated by the caller.
+    pub async fn split_stay(
+        pool: &DbPool,
+        id: i32,
+        segments: &[StaySegmentInput],
+        updated_by: Option<String>,
+    ) -> DbResult<Vec<Booking>> {
+        Self::validate_segments(segments)?;
+
+        let mut client = pool.get().await?;
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::Serializable)
+            .start()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction start failed: {}", e)))?;
+
+        // 1. Lock and check the booking
+        let row = transaction
+            .query_opt(
+                "SELECT reservierungsnummer, checkin_date, checkout_date, status, booking_group_id,
+                        allotment_id, split_parent_id
+                 FROM bookings WHERE id = $1 FOR UPDATE",
+                &[&id],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Booking with ID {} not found", id)))?;
+
+        let reservierungsnummer: String = row.get("reservierungsnummer");
+        let status: BookingStatus = row.get("status");
+        if status.is_final() {
+            return Err(DbError::ValidationError(format!(
+                "Buchung {} ist {} und kann nicht mehr geteilt werden",
+                reservierungsnummer,
+                status.label()
+            )));
+        }
+        if row.get::<_, Option<i32>>("booking_group_id").is_some() {
+            return Err(DbError::ValidationError(
+                "Zimmer einer Gruppenbuchung werden über die Gruppe geändert".to_string(),
+            ));
+        }
+        if row.get::<_, Option<i32>>("allotment_id").is_some() {
+            return Err(DbError::ValidationError(
+                "Kontingentbuchungen können nicht auf mehrere Zimmer verteilt werden".to_string(),
+            ));
+        }
+
+        let checkin_date: String = row.get("checkin_date");
+        let checkout_date: String = row.get("checkout_date");
+        let first = &segments[0];
+        let last = &segments[segments.len() - 1];
+        if first.checkin_date != checkin_date || last.checkout_date != checkout_date {
+            return Err(DbError::ValidationError(format!(
+                "Die Abschnitte müssen den ganzen Aufenthalt abdecken ({} bis {})",
+                checkin_date, checkout_date
+            )));
+        }
+
+        // 2. The issued invoice would no longer match the stay (GoBD: cancel it first)
+        let master_id = row.get::<_, Option<i32>>("split_parent_id").unwrap_or(id);
+        let invoiced: bool = transaction
+            .query_one(
+                "SELECT EXISTS (
+                     SELECT 1 FROM invoices i
+                     WHERE booking_id IN ($1, $2)
+                       AND document_type = 'rechnung'
+                       AND NOT EXISTS (
+                           SELECT 1 FROM invoices c WHERE c.corrects_invoice_id = i.id AND c.is_cancellation
+                       )
+                 )",
+                &[&id, &master_id],
+            )
+            .await?
+            .get(0);
+        if invoiced {
+            return Err(DbError::ValidationError(
+                "Für den Aufenthalt wurde bereits eine Rechnung ausgestellt - bitte zuerst stornieren".to_string(),
+            ));
+        }
+
+        // 3. The booking becomes the first segment
+        transaction
+            .execute(
+                "UPDATE bookings
+                 SET room_id = $2, checkout_date = $3, zimmer_fixiert = TRUE,
+                     updated_by = $4, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1",
+                &[&id, &first.room_id, &first.checkout_date, &updated_by],
+            )
+            .await?;
+
+        // 4. Further segments copy the booking
+        let mut segment_ids = vec![id];
+        for segment in &segments[1..] {
+            let segment_id: i32 = transaction
+                .query_one(
+                    "INSERT INTO bookings (
+                        room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
+                        anzahl_gaeste, status, gesamtpreis, bemerkungen, anzahl_begleitpersonen,
+                        geschaeftsreise, kurtaxe_befreit, ist_stiftungsfall, payment_recipient_id,
+                        ist_dpolg_mitglied, option_expires_at, zimmer_fixiert, split_parent_id, series_id,
+                        created_by, updated_by, created_at, updated_at
+                     )
+                     SELECT $2, guest_id, reservierungsnummer, $3, $4,
+                            anzahl_gaeste,
+                            CASE WHEN status = 'eingecheckt' THEN 'bestaetigt' ELSE status END,
+                            $5, bemerkungen, anzahl_begleitpersonen,
+                            geschaeftsreise, kurtaxe_befreit, ist_stiftungsfall, payment_recipient_id,
+                            ist_dpolg_mitglied, option_expires_at, TRUE, $6,
+                            $7, $7, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+                     FROM bookings WHERE id = $1
+                     RETURNING id",
+                    &[
+                        &id,
+                        &segment.room_id,
+                        &segment.checkin_date,
+                        &segment.checkout_date,
+                        &Money::ZERO,
+                        &master_id,
+                        &updated_by,
+                    ],
+                )
+                .await?
+                .get("id");
+
+            transaction
+                .execute(
+                    "INSERT INTO accompanying_guests (booking_id, vorname, nachname, geburtsdatum)
+                     SELECT $2, vorname, nachname, geburtsdatum FROM accompanying_guests WHERE booking_id = $1",
+                    &[&id, &segment_id],
+                )
+                .await?;
+
+            // The cleaning task was generated on insert, before the accompanying guests existed
+            transaction
+                .execute(
+                    "UPDATE cleaning_tasks
+                     SET guest_count = 1 + (SELECT COUNT(*) FROM accompanying_guests WHERE booking_id = $1)
+                     WHERE booking_id = $1",
+                    &[&segment_id],
+                )
+                .await?;
+
+            segment_ids.push(segment_id);
+        }
+
+        // 5. Check and lock all rooms, report every conflict at once
+        let mut conflicts = Vec::new();
+        for (segment, segment_id) in segments.iter().zip(&segment_ids) {
+            let is_available = Self::check_and_lock_availability(
+                &transaction,
+                segment.room_id,
+                &segment.checkin_date,
+                &segment.checkout_date,
+                Some(*segment_id),
+            )
+            .await?;
+
+            if !is_available {
+                let room_name: String = transaction
+                    .query_opt("SELECT name FROM rooms WHERE id = $1", &[&segment.room_id])
+                    .await?
+                    .map(|row| row.get("name"))
+                    .unwrap_or_else(|| segment.room_id.to_string());
+                conflicts.push(format!("Zimmer {} ({} bis {})", room_name, segment.checkin_date, segment.checkout_date));
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(DbError::DoubleBookingError(format!(
+                "Folgende Zimmer sind im gewünschten Zeitraum bereits belegt: {}. Der Aufenthalt wurde nicht geteilt.",
+                conflicts.join(", ")
+            )));
+        }
+
+        // 6. Cleaning: the old departure task of the first segment is obsolete, the task on
+        //    the room change day tells housekeeping where the guest moves to
+        transaction
+            .execute(
+                "DELETE FROM cleaning_tasks
+                 WHERE booking_id = $1 AND status = 'pending' AND task_date <> $2::text::date",
+                &[&id, &first.checkout_date],
+            )
+            .await?;
+        for (i, segment_id) in segment_ids[..segment_ids.len() - 1].iter().enumerate() {
+            let next_room: String = transaction
+                .query_opt("SELECT name FROM rooms WHERE id = $1", &[&segments[i + 1].room_id])
+                .await?
+                .map(|row| row.get("name"))
+                .unwrap_or_default();
+            transaction
+                .execute(
+                    "UPDATE cleaning_tasks SET notes = $3, updated_at = CURRENT_TIMESTAMP
+                     WHERE booking_id = $1 AND task_date = $2::text::date",
+                    &[
+                        segment_id,
+                        &segments[i].checkout_date,
+                        &format!("Zimmerwechsel: Gast zieht nach Zimmer {} um", next_room),
+                    ],
+                )
+                .await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction commit failed: {}", e)))?;
+
+        Self::get_stay_segments(pool, master_id).await
+    }
+
+    fn validate_segments(segments: &[StaySegmentInput]) -> DbResult<()> {
+        if segments.len() < 2 {
+            return Err(DbError::ValidationError(
+                "Für einen Zimmerwechsel werden mindestens zwei Abschnitte benötigt".to_string(),
+            ));
+        }
+
+        for (i, segment) in segments.iter().enumerate() {
+            if segment.checkout_date <= segment.checkin_date {
+                return Err(DbError::ValidationError(format!(
+                    "Abschnitt {}: Abreise muss nach der Anreise liegen",
+                    i + 1
+                )));
+            }
+            if let Some(previous) = i.checked_sub(1).map(|p| &segments[p]) {
+                if previous.checkout_date != segment.checkin_date {
+                    return Err(DbError::ValidationError(format!(
+                        "Abschnitt {} muss am Abreisetag von Abschnitt {} beginnen",
+                        i + 1,
+                        i
+                    )));
+                }
+                if previous.room_id == segment.room_id {
+                    return Err(DbError::ValidationError(format!(
+                        "Abschnitt {} liegt im selben Zimmer wie Abschnitt {}",
+                        i + 1,
+                        i
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate_initial_status(status: BookingStatus) -> DbResult<()> {
+        if status.is_initial() {
+            Ok(())
+        } else {
+            Err(DbError::InvalidStatusTransition(format!(
+                "Neue Buchungen können nicht mit Status {} angelegt werden",
+                status.label()
+            )))
+        }
+    }
+
+    /// Allocate the next reservation number of the current year with the pattern from the
+    /// company settings. Runs as single statements outside the booking transaction: the
+    /// sequence row is locked only for the increment, so two users booking at the same time
+    /// get different numbers without a serialization failure. A rejected booking leaves a gap.
+    pub(crate) async fn next_reservation_number(client: &tokio_postgres::Client) -> DbResult<String> {
+        use chrono::Datelike;
+
+        let pattern: String = client
+            .query_opt("SELECT reservierungsnummer_format FROM company_settings ORDER BY id LIMIT 1", &[])
+            .await?
+            .map(|row| row.get("reservierungsnummer_format"))
+            .unwrap_or_else(|| reservation_number::DEFAULT_PATTERN.to_string());
+        let year = chrono::Local::now().year();
+
+        // Skip numbers that already exist (e.g. entered by hand or after a pattern change)
+        for _ in 0..1000 {
+            let sequence: i32 = client
+                .query_one(
+                    "INSERT INTO reservation_number_sequences (year, last_number) VALUES ($1, 1)
+                     ON CONFLICT (year)
+                     DO UPDATE SET last_number = reservation_number_sequences.last_number + 1
+                     RETURNING last_number",
+                    &[&year],
+                )
+                .await?
+                .get("last_number");
+            let number = reservation_number::format_number(&pattern, year, sequence).map_err(DbError::ValidationError)?;
+
+            let taken: bool = client
+                .query_one(
+                    "SELECT EXISTS (SELECT 1 FROM bookings WHERE reservierungsnummer = $1)
+                         OR EXISTS (SELECT 1 FROM booking_groups WHERE reservierungsnummer = $1)
+                         OR EXISTS (SELECT 1 FROM booking_series WHERE reservierungsnummer = $1) AS taken",
+                    &[&number],
+                )
+                .await?
+                .get("taken");
+            if !taken {
+                return Ok(number);
+            }
+        }
+
+        Err(DbError::ConflictError(format!(
+            "Keine freie Reservierungsnummer für das Format {} gefunden",
+            pattern
+        )))
+    }
+
     /// Delete a booking
     pub async fn delete(pool: &DbPool, id: i32) -> DbResult<()> {
         let client = pool.get().await?;
 
+        // GoBD: issued invoices are kept, so their booking must stay as well
+        let invoice_count: i64 = client
+            .query_one("SELECT COUNT(*) FROM invoices WHERE booking_id = $1", &[&id])
+            .await?
+            .get(0);
+        if invoice_count > 0 {
+            return Err(crate::database_pg::DbError::ValidationError(
+                "Für diese Buchung wurde bereits eine Rechnung ausgestellt - sie kann nicht gelöscht, nur storniert werden".to_string()
+            ));
+        }
+
         let rows_affected = client
             .execute("DELETE FROM bookings WHERE id = $1", &[&id])
             .await?;
@@ -293,16 +925,13 @@ impl BookingRepository {
 
         let rows = client
             .query(
-                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                        ist_dpolg_mitglied, NULL::double precision as credit_used, created_by, updated_by
-                 FROM bookings
-                 WHERE room_id = $1
-                 ORDER BY checkin_date DESC",
+                &format!(
+                    "SELECT {}
+                     FROM bookings
+                     WHERE room_id = $1
+                     ORDER BY checkin_date DESC",
+                    BOOKING_COLUMNS
+                ),
                 &[&room_id],
             )
             .await?;
@@ -316,16 +945,13 @@ impl BookingRepository {
 
         let rows = client
             .query(
-                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                        ist_dpolg_mitglied, NULL::double precision as credit_used, created_by, updated_by
-                 FROM bookings
-                 WHERE guest_id = $1
-                 ORDER BY checkin_date DESC",
+                &format!(
+                    "SELECT {}
+                     FROM bookings
+                     WHERE guest_id = $1
+                     ORDER BY checkin_date DESC",
+                    BOOKING_COLUMNS
+                ),
                 &[&guest_id],
             )
             .await?;
@@ -334,21 +960,18 @@ impl BookingRepository {
     }
 
     /// Get bookings by status
-    pub async fn get_by_status(pool: &DbPool, status: String) -> DbResult<Vec<Booking>> {
+    pub async fn get_by_status(pool: &DbPool, status: BookingStatus) -> DbResult<Vec<Booking>> {
         let client = pool.get().await?;
 
         let rows = client
             .query(
-                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                        ist_dpolg_mitglied
-                 FROM bookings
-                 WHERE status = $1
-                 ORDER BY checkin_date DESC",
+                &format!(
+                    "SELECT {}
+                     FROM bookings
+                     WHERE status = $1
+                     ORDER BY checkin_date DESC",
+                    BOOKING_COLUMNS
+                ),
                 &[&status],
             )
             .await?;
@@ -366,16 +989,13 @@ impl BookingRepository {
 
         let rows = client
             .query(
-                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                        ist_dpolg_mitglied
-                 FROM bookings
-                 WHERE checkin_date >= $1 AND checkout_date <= $2
-                 ORDER BY checkin_date ASC",
+                &format!(
+                    "SELECT {}
+                     FROM bookings
+                     WHERE checkin_date >= $1 AND checkout_date <= $2
+                     ORDER BY checkin_date ASC",
+                    BOOKING_COLUMNS
+                ),
                 &[&start_date, &end_date],
             )
             .await?;
@@ -389,16 +1009,13 @@ impl BookingRepository {
 
         let rows = client
             .query(
-                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                        ist_dpolg_mitglied
-                 FROM bookings
-                 WHERE bezahlt = FALSE OR bezahlt IS NULL
-                 ORDER BY checkin_date DESC",
+                &format!(
+                    "SELECT {}
+                     FROM bookings
+                     WHERE bezahlt = FALSE OR bezahlt IS NULL
+                     ORDER BY checkin_date DESC",
+                    BOOKING_COLUMNS
+                ),
                 &[],
             )
             .await?;
@@ -414,16 +1031,13 @@ impl BookingRepository {
 
         let rows = client
             .query(
-                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                        ist_dpolg_mitglied
-                 FROM bookings
-                 WHERE UPPER(reservierungsnummer) LIKE $1
-                 ORDER BY checkin_date DESC",
+                &format!(
+                    "SELECT {}
+                     FROM bookings
+                     WHERE UPPER(reservierungsnummer) LIKE $1
+                     ORDER BY checkin_date DESC",
+                    BOOKING_COLUMNS
+                ),
                 &[&search_pattern],
             )
             .await?;
@@ -450,6 +1064,27 @@ impl BookingRepository {
         checkin_date: &str,
         checkout_date: &str,
         exclude_booking_id: Option<i32>,
+    ) -> DbResult<bool> {
+        Self::check_and_lock_availability_in_allotment(
+            transaction,
+            room_id,
+            checkin_date,
+            checkout_date,
+            exclude_booking_id,
+            None,
+        )
+        .await
+    }
+
+    /// Same check for a stay drawn down from `allotment_id` (its own held units don't block it).
+    /// Units held by other active allotments fail with a DoubleBookingError naming the allotment.
+    pub async fn check_and_lock_availability_in_allotment(
+        transaction: &tokio_postgres::Transaction<'_>,
+        room_id: i32,
+        checkin_date: &str,
+        checkout_date: &str,
+        exclude_booking_id: Option<i32>,
+        allotment_id: Option<i32>,
     ) -> DbResult<bool> {
         // Lock all overlapping bookings for this room using FOR UPDATE
         // This prevents other transactions from inserting conflicting bookings
@@ -479,27 +1114,59 @@ impl BookingRepository {
             }
         };
 
-        Ok(rows.is_empty())
+        if !rows.is_empty() {
+            return Ok(false);
+        }
+
+        // Blocked rooms (maintenance, renovation) can't be booked
+        let blocks = RoomBlockRepository::overlapping(transaction, room_id, checkin_date, checkout_date).await?;
+        if !blocks.is_empty() {
+            return Err(crate::database_pg::DbError::DoubleBookingError(format!(
+                "Das Zimmer ist in diesem Zeitraum gesperrt: {}",
+                blocks.join(", ")
+            )));
+        }
+
+        // Rooms held for an allotment are not available to anybody else
+        let held = AllotmentRepository::held_conflicts(
+            transaction,
+            room_id,
+            checkin_date,
+            checkout_date,
+            exclude_booking_id,
+            allotment_id,
+        )
+        .await?;
+        if !held.is_empty() {
+            return Err(crate::database_pg::DbError::DoubleBookingError(format!(
+                "Das Zimmer wird in diesem Zeitraum für ein Kontingent freigehalten: {}",
+                held.join(", ")
+            )));
+        }
+
+        Ok(true)
     }
 
     /// Create a new booking with atomic availability check (prevents double bookings)
-    /// Uses database transaction with row-level locking
+    /// Uses database transaction with row-level locking.
+    /// Without `reservierungsnummer` the next number of the yearly sequence is allocated;
+    /// a number is only passed for numbers derived from a series number.
     #[allow(clippy::too_many_arguments)]
     pub async fn create_with_availability_check(
         pool: &DbPool,
         room_id: i32,
         guest_id: i32,
-        reservierungsnummer: String,
+        reservierungsnummer: Option<String>,
         checkin_date: String,
         checkout_date: String,
         anzahl_gaeste: i32,
-        status: String,
-        gesamtpreis: f64,
+        status: BookingStatus,
+        gesamtpreis: Money,
         bemerkungen: Option<String>,
         anzahl_begleitpersonen: Option<i32>,
-        grundpreis: Option<f64>,
-        services_preis: Option<f64>,
-        rabatt_preis: Option<f64>,
+        grundpreis: Option<Money>,
+        services_preis: Option<Money>,
+        rabatt_preis: Option<Money>,
         anzahl_naechte: Option<i32>,
         bezahlt: Option<bool>,
         bezahlt_am: Option<String>,
@@ -513,8 +1180,14 @@ impl BookingRepository {
         ist_dpolg_mitglied: Option<bool>,
         created_by: Option<String>,
     ) -> DbResult<Booking> {
-        // 1. Get connection from pool
+        Self::validate_initial_status(status)?;
+
+        // 1. Get connection from pool and allocate the reservation number
         let mut client = pool.get().await?;
+        let reservierungsnummer = match reservierungsnummer {
+            Some(number) => number,
+            None => Self::next_reservation_number(&client).await?,
+        };
 
         // 2. Start transaction with SERIALIZABLE isolation for maximum safety
         let transaction = client
@@ -545,25 +1218,22 @@ impl BookingRepository {
         // 4. Insert booking (still within transaction)
         let row = transaction
             .query_one(
-                "INSERT INTO bookings (
-                    room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                    anzahl_gaeste, status, gesamtpreis, bemerkungen,
-                    anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                    anzahl_naechte, bezahlt, bezahlt_am, zahlungsmethode,
-                    mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                    ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                    ist_dpolg_mitglied, created_by, updated_by, created_at, updated_at
-                 ) VALUES (
-                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
-                    $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
-                    $21, $22, $23, $24, $25, $25, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
-                 ) RETURNING id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                             anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                             anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                             anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                             mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                             ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                             ist_dpolg_mitglied, NULL::double precision as credit_used, created_by, updated_by",
+                &format!(
+                    "INSERT INTO bookings (
+                        room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
+                        anzahl_gaeste, status, gesamtpreis, bemerkungen,
+                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
+                        anzahl_naechte, bezahlt, bezahlt_am, zahlungsmethode,
+                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
+                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
+                        ist_dpolg_mitglied, created_by, updated_by, created_at, updated_at
+                     ) VALUES (
+                        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+                        $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+                        $21, $22, $23, $24, $25, $25, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+                     ) RETURNING {}",
+                    BOOKING_COLUMNS
+                ),
                 &[
                     &room_id, &guest_id, &reservierungsnummer, &checkin_date, &checkout_date,
                     &anzahl_gaeste, &status, &gesamtpreis, &bemerkungen,
@@ -641,19 +1311,16 @@ impl BookingRepository {
 
         // 5. Perform update (only room and dates, keep all other fields)
         let row = transaction.query_one(
-            "UPDATE bookings SET
-                room_id = $2,
-                checkin_date = $3,
-                checkout_date = $4,
-                updated_at = CURRENT_TIMESTAMP
-             WHERE id = $1
-             RETURNING id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
-                       anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
-                       anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis,
-                       anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
-                       mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
-                       ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
-                       ist_dpolg_mitglied",
+            &format!(
+                "UPDATE bookings SET
+                    room_id = $2,
+                    checkin_date = $3,
+                    checkout_date = $4,
+                    updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1
+                 RETURNING {}",
+                BOOKING_COLUMNS
+            ),
             &[&id, &room_id, &checkin_date, &checkout_date]
         ).await.map_err(|e| crate::database_pg::DbError::QueryError(format!("Update failed: {}", e)))?;
 
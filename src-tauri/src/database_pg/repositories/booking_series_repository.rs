@@ -0,0 +1,397 @@
+use crate::booking_series::{self, Frequency, RecurrenceRule};
+use crate::booking_status::BookingStatus;
+use crate::database_pg::repositories::BookingRepository;
+use crate::database_pg::{
+    Booking, BookingSeries, BookingSeriesWithBookings, DbError, DbPool, DbResult, SeriesConflict,
+};
+use crate::money::Money;
+
+const SERIES_COLUMNS: &str = "id, name, reservierungsnummer, guest_id, payment_recipient_id, frequency, interval_count,
+    first_checkin::text as first_checkin, nights, occurrences, until_date::text as until_date, anzahl_gaeste,
+    ARRAY(SELECT sr.room_id FROM booking_series_rooms sr WHERE sr.series_id = booking_series.id ORDER BY sr.room_id) AS room_ids,
+    bemerkungen, created_at::text as created_at, updated_at::text as updated_at, created_by";
+
+/// Open occurrences of a series that bulk changes apply to: not yet arrived, not cancelled
+const OPEN_OCCURRENCE: &str = "series_id = $1 AND checkin_date >= $2 AND status IN ('anfrage', 'optioniert', 'reserviert', 'bestaetigt')";
+
+/// Repository for booking series (recurring bookings of regular seminar groups)
+pub struct BookingSeriesRepository;
+
+impl BookingSeriesRepository {
+    /// Run booking series migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/033_booking_series.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get all series, newest first
+    pub async fn get_all(pool: &DbPool) -> DbResult<Vec<BookingSeries>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!("SELECT {} FROM booking_series ORDER BY created_at DESC, id DESC", SERIES_COLUMNS),
+                &[],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(BookingSeries::from).collect())
+    }
+
+    /// Get series by ID
+    pub async fn get_by_id(pool: &DbPool, id: i32) -> DbResult<BookingSeries> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(&format!("SELECT {} FROM booking_series WHERE id = $1", SERIES_COLUMNS), &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Booking series with ID {} not found", id)))?;
+
+        Ok(BookingSeries::from(row))
+    }
+
+    /// Series with its bookings in date order
+    pub async fn get_with_bookings(pool: &DbPool, id: i32) -> DbResult<BookingSeriesWithBookings> {
+        Ok(BookingSeriesWithBookings {
+            series: Self::get_by_id(pool, id).await?,
+            bookings: Self::get_bookings(pool, id).await?,
+            conflicts: Vec::new(),
+        })
+    }
+
+    /// All bookings of a series (every occurrence and room)
+    pub async fn get_bookings(pool: &DbPool, id: i32) -> DbResult<Vec<Booking>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT id, room_id, guest_id, reservierungsnummer, checkin_date, checkout_date,
+                        anzahl_gaeste, status, gesamtpreis, bemerkungen, created_at::text as created_at,
+                        anzahl_begleitpersonen, grundpreis, services_preis, rabatt_preis, belegung_preis,
+                        geschaeftsreise, kurtaxe, kurtaxe_personen, kurtaxe_befreit,
+                        anzahl_naechte, updated_at::text as updated_at, bezahlt, bezahlt_am, zahlungsmethode,
+                        mahnung_gesendet_am, rechnung_versendet_am, rechnung_versendet_an,
+                        ist_stiftungsfall, payment_recipient_id, putzplan_checkout_date,
+                        ist_dpolg_mitglied, NULL::double precision as credit_used,
+                        created_by, updated_by, booking_group_id, allotment_id,
+                        option_expires_at::text as option_expires_at, zimmer_fixiert, split_parent_id, series_id
+                 FROM bookings
+                 WHERE series_id = $1
+                 ORDER BY checkin_date, room_id",
+                &[&id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(Booking::from).collect())
+    }
+
+    /// Open occurrences (from `from_date` on) that bulk edits and cancellations apply to
+    pub async fn get_open_bookings(pool: &DbPool, id: i32, from_date: &str) -> DbResult<Vec<Booking>> {
+        let bookings = Self::get_bookings(pool, id).await?;
+        Ok(bookings
+            .into_iter()
+            .filter(|b| b.checkin_date.as_str() >= from_date && !b.status.is_final() && b.status != BookingStatus::Eingecheckt)
+            .collect())
+    }
+
+    /// Create a series and book every occurrence in every room of the series. Each booking
+    /// is created like a single booking (`BookingRepository::create_with_availability_check`,
+    /// its own SERIALIZABLE transaction), so occupied occurrences do not stop the others -
+    /// they are returned as conflicts. If no occurrence is free, nothing is saved.
+    /// Bookings get the reservation number "<series number>-01", "-02", ... per occurrence
+    /// (with a room suffix "-01-1", "-01-2", ... for series with several rooms).
+    /// Prices are calculated afterwards by the caller (pricing engine).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_with_bookings(
+        pool: &DbPool,
+        name: String,
+        guest_id: i32,
+        payment_recipient_id: Option<i32>,
+        status: BookingStatus,
+        room_ids: Vec<i32>,
+        anzahl_gaeste: i32,
+        first_checkin: String,
+        nights: i32,
+        rule: &RecurrenceRule,
+        bemerkungen: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<BookingSeriesWithBookings> {
+        BookingRepository::validate_initial_status(status)?;
+        if name.trim().is_empty() {
+            return Err(DbError::ValidationError("Bitte einen Namen für die Serie angeben".to_string()));
+        }
+        if room_ids.is_empty() {
+            return Err(DbError::ValidationError("Eine Serie braucht mindestens ein Zimmer".to_string()));
+        }
+        if anzahl_gaeste < 1 {
+            return Err(DbError::ValidationError("Mindestens ein Gast pro Zimmer".to_string()));
+        }
+        let first = chrono::NaiveDate::parse_from_str(&first_checkin, "%Y-%m-%d")
+            .map_err(|_| DbError::ValidationError(format!("Ungültiges Anreisedatum: {}", first_checkin)))?;
+        let occurrences =
+            booking_series::occurrences(first, nights as i64, rule).map_err(DbError::ValidationError)?;
+
+        // 1. Series and its rooms
+        let mut client = pool.get().await?;
+        let reservierungsnummer = BookingRepository::next_reservation_number(&client).await?;
+        let transaction = client.transaction().await?;
+        let occurrence_count = rule.count.map(|count| count as i32);
+        let series_id: i32 = transaction
+            .query_one(
+                "INSERT INTO booking_series (name, reservierungsnummer, guest_id, payment_recipient_id, frequency,
+                                             interval_count, first_checkin, nights, occurrences, until_date,
+                                             anzahl_gaeste, bemerkungen, created_by)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7::text::date, $8, $9, $10::text::date, $11, $12, $13)
+                 RETURNING id",
+                &[
+                    &name,
+                    &reservierungsnummer,
+                    &guest_id,
+                    &payment_recipient_id,
+                    &rule.frequency.as_str(),
+                    &(rule.interval as i32),
+                    &first_checkin,
+                    &nights,
+                    &occurrence_count,
+                    &rule.until,
+                    &anzahl_gaeste,
+                    &bemerkungen,
+                    &created_by,
+                ],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?
+            .get("id");
+        for room_id in &room_ids {
+            transaction
+                .execute(
+                    "INSERT INTO booking_series_rooms (series_id, room_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+                    &[&series_id, room_id],
+                )
+                .await?;
+        }
+        transaction.commit().await?;
+
+        // 2. One booking per occurrence and room
+        let mut conflicts = Vec::new();
+        let mut booked = 0;
+        for occurrence in &occurrences {
+            let checkin_date = occurrence.checkin.format("%Y-%m-%d").to_string();
+            let checkout_date = occurrence.checkout.format("%Y-%m-%d").to_string();
+
+            for (room_index, room_id) in room_ids.iter().enumerate() {
+                let booking_nr = if room_ids.len() == 1 {
+                    format!("{}-{:02}", reservierungsnummer, occurrence.number)
+                } else {
+                    format!("{}-{:02}-{}", reservierungsnummer, occurrence.number, room_index + 1)
+                };
+
+                let result = BookingRepository::create_with_availability_check(
+                    pool,
+                    *room_id,
+                    guest_id,
+                    Some(booking_nr),
+                    checkin_date.clone(),
+                    checkout_date.clone(),
+                    anzahl_gaeste,
+                    status,
+                    Money::ZERO,
+                    bemerkungen.clone(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    payment_recipient_id,
+                    None,
+                    None,
+                    created_by.clone(),
+                )
+                .await;
+
+                match result {
+                    Ok(booking) => {
+                        client
+                            .execute("UPDATE bookings SET series_id = $2 WHERE id = $1", &[&booking.id, &series_id])
+                            .await?;
+                        booked += 1;
+                    }
+                    Err(DbError::DoubleBookingError(_)) => conflicts.push(SeriesConflict {
+                        occurrence: occurrence.number as i32,
+                        room_id: *room_id,
+                        checkin_date: checkin_date.clone(),
+                        checkout_date: checkout_date.clone(),
+                        reason: "Zimmer im Zeitraum belegt oder gesperrt".to_string(),
+                    }),
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        if booked == 0 {
+            client.execute("DELETE FROM booking_series WHERE id = $1", &[&series_id]).await?;
+            return Err(DbError::DoubleBookingError(format!(
+                "Kein Termin der Serie ist frei ({} Termine geprüft). Es wurde keine Buchung angelegt.",
+                occurrences.len()
+            )));
+        }
+
+        let mut result = Self::get_with_bookings(pool, series_id).await?;
+        result.conflicts = conflicts;
+        Ok(result)
+    }
+
+    /// Bulk edit: name and payment recipient of the series, guests per room and remarks of the
+    /// series and of every open occurrence from `from_date` on. Returns the changed bookings
+    /// (prices are recalculated by the caller).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &DbPool,
+        id: i32,
+        name: String,
+        payment_recipient_id: Option<i32>,
+        anzahl_gaeste: i32,
+        bemerkungen: Option<String>,
+        from_date: &str,
+        updated_by: Option<String>,
+    ) -> DbResult<Vec<Booking>> {
+        if name.trim().is_empty() {
+            return Err(DbError::ValidationError("Bitte einen Namen für die Serie angeben".to_string()));
+        }
+        if anzahl_gaeste < 1 {
+            return Err(DbError::ValidationError("Mindestens ein Gast pro Zimmer".to_string()));
+        }
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let updated = transaction
+            .execute(
+                "UPDATE booking_series
+                 SET name = $2, payment_recipient_id = $3, anzahl_gaeste = $4, bemerkungen = $5,
+                     updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1",
+                &[&id, &name, &payment_recipient_id, &anzahl_gaeste, &bemerkungen],
+            )
+            .await?;
+        if updated == 0 {
+            return Err(DbError::NotFound(format!("Booking series with ID {} not found", id)));
+        }
+
+        let rows = transaction
+            .query(
+                &format!(
+                    "UPDATE bookings
+                     SET anzahl_gaeste = $3, bemerkungen = $4, payment_recipient_id = $5,
+                         updated_by = $6, updated_at = CURRENT_TIMESTAMP
+                     WHERE {}
+                     RETURNING id",
+                    OPEN_OCCURRENCE
+                ),
+                &[&id, &from_date, &anzahl_gaeste, &bemerkungen, &payment_recipient_id, &updated_by],
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        let mut bookings = Vec::with_capacity(rows.len());
+        for row in rows {
+            bookings.push(BookingRepository::get_by_id(pool, row.get("id")).await?);
+        }
+        Ok(bookings)
+    }
+
+    /// Bulk room change: open occurrences from `from_date` on move from one room of the series
+    /// to another, each with the availability check of a single room change. Occurrences whose
+    /// new room is not free stay in the old room and are returned as conflicts.
+    pub async fn move_room(
+        pool: &DbPool,
+        id: i32,
+        from_room_id: i32,
+        to_room_id: i32,
+        from_date: &str,
+    ) -> DbResult<(Vec<Booking>, Vec<SeriesConflict>)> {
+        let series = Self::get_by_id(pool, id).await?;
+        if !series.room_ids.contains(&from_room_id) {
+            return Err(DbError::ValidationError("Das Zimmer gehört nicht zur Serie".to_string()));
+        }
+        if series.room_ids.contains(&to_room_id) {
+            return Err(DbError::ValidationError("Das neue Zimmer gehört bereits zur Serie".to_string()));
+        }
+
+        let first = chrono::NaiveDate::parse_from_str(&series.first_checkin, "%Y-%m-%d")
+            .map_err(|e| DbError::QueryError(e.to_string()))?;
+        let occurrence_number = |checkin: &str| -> i32 {
+            let rule = RecurrenceRule {
+                frequency: series.frequency.parse().unwrap_or(Frequency::Monatlich),
+                interval: series.interval_count as u32,
+                count: series.occurrences.map(|c| c as u32),
+                until: series.until_date.clone(),
+            };
+            booking_series::occurrences(first, series.nights as i64, &rule)
+                .ok()
+                .and_then(|all| all.into_iter().find(|o| o.checkin.format("%Y-%m-%d").to_string() == checkin))
+                .map(|o| o.number as i32)
+                .unwrap_or(0)
+        };
+
+        let mut moved = Vec::new();
+        let mut conflicts = Vec::new();
+        for booking in Self::get_open_bookings(pool, id, from_date).await? {
+            if booking.room_id != from_room_id {
+                continue;
+            }
+            let result = BookingRepository::update_dates_with_availability_check(
+                pool,
+                booking.id,
+                to_room_id,
+                booking.checkin_date.clone(),
+                booking.checkout_date.clone(),
+                booking.updated_at.clone(),
+            )
+            .await;
+
+            match result {
+                Ok(updated) => moved.push(updated),
+                Err(DbError::DoubleBookingError(_)) | Err(DbError::ConflictError(_)) => {
+                    conflicts.push(SeriesConflict {
+                        occurrence: occurrence_number(&booking.checkin_date),
+                        room_id: to_room_id,
+                        checkin_date: booking.checkin_date.clone(),
+                        checkout_date: booking.checkout_date.clone(),
+                        reason: format!("Bleibt in Zimmer {} - neues Zimmer nicht frei", from_room_id),
+                    })
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // Later occurrences are planned in the new room
+        let client = pool.get().await?;
+        client
+            .execute(
+                "UPDATE booking_series_rooms SET room_id = $3 WHERE series_id = $1 AND room_id = $2",
+                &[&id, &from_room_id, &to_room_id],
+            )
+            .await?;
+        client
+            .execute("UPDATE booking_series SET updated_at = CURRENT_TIMESTAMP WHERE id = $1", &[&id])
+            .await?;
+
+        Ok((moved, conflicts))
+    }
+}
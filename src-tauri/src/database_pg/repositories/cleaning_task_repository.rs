@@ -440,6 +440,46 @@ impl CleaningTaskRepository {
         Ok(())
     }
 
+    /// Cleaning task on the actual day of departure (`departure_date` YYYY-MM-DD).
+    /// An early check-out moves the pending check-out task forward; without one a task is created.
+    pub async fn ensure_departure_task(pool: &DbPool, booking_id: i32, departure_date: &str) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let moved = client
+            .execute(
+                "UPDATE cleaning_tasks SET task_date = $2::text::date, updated_at = CURRENT_TIMESTAMP
+                 WHERE booking_id = $1 AND task_date > $2::text::date AND status = 'pending'",
+                &[&booking_id, &departure_date],
+            )
+            .await?;
+        if moved > 0 {
+            return Ok(());
+        }
+
+        // Same values as the trigger generate_cleaning_task_for_booking()
+        client
+            .execute(
+                "INSERT INTO cleaning_tasks (
+                    booking_id, room_id, task_date, checkout_time, priority, has_dog,
+                    change_bedding, guest_count, guest_name, status
+                 )
+                 SELECT b.id, b.room_id, $2::text::date, NULL, 'NORMAL',
+                        EXISTS(SELECT 1 FROM additional_services s WHERE s.booking_id = b.id AND s.service_name ILIKE '%hund%'),
+                        TRUE,
+                        1 + (SELECT COUNT(*) FROM accompanying_guests a WHERE a.booking_id = b.id),
+                        CONCAT(g.vorname, ' ', g.nachname),
+                        'pending'
+                 FROM bookings b
+                 JOIN guests g ON g.id = b.guest_id
+                 WHERE b.id = $1
+                 ON CONFLICT (booking_id, room_id, task_date) DO NOTHING",
+                &[&booking_id, &departure_date],
+            )
+            .await?;
+
+        Ok(())
+    }
+
     /// Mark task as synced to Turso
     pub async fn mark_synced(pool: &DbPool, id: i32) -> DbResult<()> {
         let client = pool.get().await?;
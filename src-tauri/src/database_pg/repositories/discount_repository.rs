@@ -1,4 +1,5 @@
 use crate::database_pg::{DbPool, DbResult, Discount};
+use crate::money::Money;
 
 pub struct DiscountRepository;
 
@@ -69,7 +70,7 @@ impl DiscountRepository {
         discount_name: String,
         discount_type: String,
         discount_value: f64,
-        calculated_amount: Option<f64>,
+        calculated_amount: Option<Money>,
     ) -> DbResult<Discount> {
         // Bug 7 fix: Validate discount value
         if discount_value < 0.0 {
@@ -113,7 +114,7 @@ impl DiscountRepository {
         discount_name: String,
         discount_type: String,
         discount_value: f64,
-        calculated_amount: Option<f64>,
+        calculated_amount: Option<Money>,
     ) -> DbResult<Discount> {
         // Bug 7 fix: Validate discount value
         if discount_value < 0.0 {
@@ -200,7 +201,7 @@ impl DiscountRepository {
     pub async fn update_calculated_amount(
         pool: &DbPool,
         discount_id: i64,
-        calculated_amount: f64,
+        calculated_amount: Money,
     ) -> DbResult<()> {
         let client = pool.get().await?;
 
@@ -0,0 +1,358 @@
+use chrono::NaiveDate;
+
+use crate::database_pg::{DbError, DbPool, DbResult, DunningEntry, DunningStage};
+use crate::dunning::{self, LastNotice, StageRule, HANDOVER_REMINDER_TYPE};
+use crate::money::Money;
+use crate::payments::{self, DepositInput, DepositState};
+
+const STAGE_COLUMNS: &str = "id, stage, name, template_name, frist_tage, gebuehr, updated_at::text as updated_at";
+
+const HISTORY_SELECT: &str = "SELECT h.id, h.booking_id, h.event, h.stage, h.stage_name, h.template_name,
+           h.outstanding, h.gebuehr, h.zahlungsfrist::text as zahlungsfrist, h.service_id, h.reminder_id,
+           se.status AS email_status, h.created_at::text as created_at
+    FROM booking_dunning_history h
+    LEFT JOIN scheduled_emails se ON se.id = h.scheduled_email_id";
+
+/// Unpaid booking in the dunning procedure (not yet handed over)
+#[derive(Debug, Clone)]
+pub struct DunningCandidate {
+    pub booking_id: i32,
+    pub guest_id: i32,
+    pub email: String,
+    pub reservierungsnummer: String,
+    /// Departure or due date of the first overdue deposit, whichever is earlier
+    pub due_since: NaiveDate,
+    pub last: Option<LastNotice>,
+}
+
+/// Repository for the dunning procedure: stages, candidates and history per booking
+pub struct DunningRepository;
+
+impl DunningRepository {
+    /// Run dunning migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/038_dunning.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Configured stages in order of escalation
+    pub async fn get_stages(pool: &DbPool) -> DbResult<Vec<DunningStage>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(&format!("SELECT {} FROM dunning_stages ORDER BY stage", STAGE_COLUMNS), &[])
+            .await?;
+
+        Ok(rows.into_iter().map(DunningStage::from).collect())
+    }
+
+    /// Replace the stage configuration. Stages are numbered in the given order; the history
+    /// keeps its own copy of name and template, so running procedures continue by number.
+    pub async fn save_stages(pool: &DbPool, rules: &[StageRule]) -> DbResult<Vec<DunningStage>> {
+        dunning::validate_stages(rules).map_err(DbError::ValidationError)?;
+
+        let mut client = pool.get().await?;
+        let template_names: Vec<String> = rules.iter().map(|r| r.template_name.clone()).collect();
+        let existing: Vec<String> = client
+            .query(
+                "SELECT template_name FROM email_templates WHERE template_name = ANY($1)",
+                &[&template_names],
+            )
+            .await?
+            .into_iter()
+            .map(|row| row.get("template_name"))
+            .collect();
+        if let Some(missing) = template_names.iter().find(|name| !existing.contains(name)) {
+            return Err(DbError::ValidationError(format!("E-Mail-Vorlage '{}' existiert nicht", missing)));
+        }
+
+        let transaction = client.transaction().await?;
+        transaction.execute("DELETE FROM dunning_stages", &[]).await?;
+        for (index, rule) in rules.iter().enumerate() {
+            transaction
+                .execute(
+                    "INSERT INTO dunning_stages (stage, name, template_name, frist_tage, gebuehr)
+                     VALUES ($1, $2, $3, $4, $5)",
+                    &[
+                        &(index as i32 + 1),
+                        &rule.name.trim(),
+                        &rule.template_name,
+                        &rule.frist_tage,
+                        &rule.gebuehr,
+                    ],
+                )
+                .await
+                .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+        }
+        transaction.commit().await?;
+
+        Self::get_stages(pool).await
+    }
+
+    /// Dunning history of a booking, oldest first
+    pub async fn get_history(pool: &DbPool, booking_id: i32) -> DbResult<Vec<DunningEntry>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!("{} WHERE h.booking_id = $1 ORDER BY h.created_at, h.id", HISTORY_SELECT),
+                &[&booking_id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(DunningEntry::from).collect())
+    }
+
+    /// Latest notice of a booking sent with this template (for the email placeholders)
+    pub async fn get_notice(pool: &DbPool, booking_id: i32, template_name: &str) -> DbResult<Option<DunningEntry>> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "{} WHERE h.booking_id = $1 AND h.template_name = $2 AND h.event = 'mahnung'
+                     ORDER BY h.created_at DESC, h.id DESC LIMIT 1",
+                    HISTORY_SELECT
+                ),
+                &[&booking_id, &template_name],
+            )
+            .await?;
+
+        Ok(row.map(DunningEntry::from))
+    }
+
+    /// Unpaid bookings that are due (departed or with an overdue deposit) and not handed over yet.
+    /// Guests without email address and bookings with a pending direct debit are left out.
+    pub async fn get_candidates(pool: &DbPool, today: NaiveDate) -> DbResult<Vec<DunningCandidate>> {
+        let client = pool.get().await?;
+        let today_str = today.format("%Y-%m-%d").to_string();
+
+        let rows = client
+            .query(
+                "SELECT b.id, b.guest_id, g.email, b.reservierungsnummer, b.checkout_date::text AS checkout_date,
+                        bb.paid,
+                        ARRAY(SELECT d.id FROM deposit_requests d WHERE d.booking_id = b.id ORDER BY d.id) AS deposit_ids,
+                        ARRAY(SELECT d.amount FROM deposit_requests d WHERE d.booking_id = b.id ORDER BY d.id) AS deposit_amounts,
+                        ARRAY(SELECT d.due_date::text FROM deposit_requests d WHERE d.booking_id = b.id ORDER BY d.id) AS deposit_due_dates,
+                        last.stage AS last_stage, last.zahlungsfrist::text AS last_zahlungsfrist
+                 FROM bookings b
+                 JOIN guests g ON g.id = b.guest_id
+                 JOIN booking_balances bb ON bb.booking_id = b.id
+                 LEFT JOIN LATERAL (
+                     SELECT h.stage, h.zahlungsfrist FROM booking_dunning_history h
+                     WHERE h.booking_id = b.id AND h.event = 'mahnung'
+                     ORDER BY h.stage DESC LIMIT 1
+                 ) last ON TRUE
+                 WHERE bb.payment_status IN ('ausstehend', 'teilweise_bezahlt')
+                   AND b.status <> 'storniert'
+                   AND COALESCE(g.email, '') <> ''
+                   AND (b.checkout_date::date < $1::text::date
+                        OR EXISTS (SELECT 1 FROM deposit_requests d
+                                   WHERE d.booking_id = b.id AND d.due_date < $1::text::date))
+                   AND NOT EXISTS (SELECT 1 FROM booking_dunning_history h
+                                   WHERE h.booking_id = b.id AND h.event = 'uebergabe')
+                   AND NOT EXISTS (SELECT 1 FROM sepa_collections c
+                                   WHERE c.booking_id = b.id AND c.status = 'ausstehend')
+                 ORDER BY b.id",
+                &[&today_str],
+            )
+            .await?;
+
+        let parse_date = |value: &str| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| DbError::Other(format!("Invalid date {}: {}", value, e)))
+        };
+
+        let mut candidates = Vec::new();
+        for row in rows {
+            let deposit_ids: Vec<i32> = row.get("deposit_ids");
+            let deposit_amounts: Vec<Money> = row.get("deposit_amounts");
+            let deposit_due_dates: Vec<String> = row.get("deposit_due_dates");
+            let deposits = deposit_ids
+                .into_iter()
+                .zip(deposit_amounts)
+                .zip(deposit_due_dates)
+                .map(|((id, amount), due_date)| Ok(DepositInput { id, amount, due_date: parse_date(&due_date)? }))
+                .collect::<DbResult<Vec<_>>>()?;
+
+            let first_overdue_deposit = payments::cover_deposits(&deposits, row.get("paid"), today)
+                .iter()
+                .filter(|c| c.state == DepositState::Ueberfaellig)
+                .filter_map(|c| deposits.iter().find(|d| d.id == c.id).map(|d| d.due_date))
+                .min();
+            let checkout_date = parse_date(&row.get::<_, String>("checkout_date"))?;
+            let departed = (checkout_date < today).then_some(checkout_date);
+            let Some(due_since) = departed.into_iter().chain(first_overdue_deposit).min() else {
+                // Only deposits that are covered by now
+                continue;
+            };
+
+            let last = match (
+                row.get::<_, Option<i32>>("last_stage"),
+                row.get::<_, Option<String>>("last_zahlungsfrist"),
+            ) {
+                (Some(stage), Some(zahlungsfrist)) => Some(LastNotice { stage, zahlungsfrist: parse_date(&zahlungsfrist)? }),
+                _ => None,
+            };
+
+            candidates.push(DunningCandidate {
+                booking_id: row.get("id"),
+                guest_id: row.get("guest_id"),
+                email: row.get("email"),
+                reservierungsnummer: row.get("reservierungsnummer"),
+                due_since,
+                last,
+            });
+        }
+
+        Ok(candidates)
+    }
+
+    /// Record a stage as sent: queue its email, remember the step and update
+    /// bookings.mahnung_gesendet_am. The late fee (service_id) is added by the caller beforehand,
+    /// so the open amount stored here includes it.
+    pub async fn record_notice(
+        pool: &DbPool,
+        candidate: &DunningCandidate,
+        stage: &DunningStage,
+        service_id: Option<i32>,
+        today: NaiveDate,
+    ) -> DbResult<DunningEntry> {
+        let mut client = pool.get().await?;
+        let today_str = today.format("%Y-%m-%d").to_string();
+        let zahlungsfrist = dunning::zahlungsfrist(today, stage.frist_tage).format("%Y-%m-%d").to_string();
+        let subject = format!(
+            "Stiftung DPolG - {} – Rechnung {}",
+            stage.name, candidate.reservierungsnummer
+        );
+
+        let transaction = client.transaction().await?;
+
+        // One email per booking and template: an older row (e.g. from the previous
+        // reminder scheduler) is kept as it is
+        let inserted = transaction
+            .query_opt(
+                "INSERT INTO scheduled_emails (
+                    booking_id, guest_id, template_name, recipient_email,
+                    subject, scheduled_for, status
+                 )
+                 VALUES ($1, $2, $3, $4, $5, NOW(), 'pending')
+                 ON CONFLICT DO NOTHING
+                 RETURNING id",
+                &[&candidate.booking_id, &candidate.guest_id, &stage.template_name, &candidate.email, &subject],
+            )
+            .await?;
+        let scheduled_email_id: Option<i32> = match inserted {
+            Some(row) => Some(row.get("id")),
+            None => transaction
+                .query_opt(
+                    "SELECT id FROM scheduled_emails WHERE booking_id = $1 AND template_name = $2
+                     ORDER BY id DESC LIMIT 1",
+                    &[&candidate.booking_id, &stage.template_name],
+                )
+                .await?
+                .map(|row| row.get("id")),
+        };
+
+        let row = transaction
+            .query_one(
+                "INSERT INTO booking_dunning_history (
+                    booking_id, event, stage, stage_name, template_name, outstanding, gebuehr,
+                    zahlungsfrist, service_id, scheduled_email_id
+                 )
+                 SELECT $1, 'mahnung', $2, $3, $4, GREATEST(bb.outstanding, 0), $5, $6::text::date, $7, $8
+                 FROM booking_balances bb WHERE bb.booking_id = $1
+                 RETURNING id",
+                &[
+                    &candidate.booking_id,
+                    &stage.stage,
+                    &stage.name,
+                    &stage.template_name,
+                    &stage.gebuehr,
+                    &zahlungsfrist,
+                    &service_id,
+                    &scheduled_email_id,
+                ],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+        let id: i32 = row.get("id");
+
+        transaction
+            .execute(
+                "UPDATE bookings SET mahnung_gesendet_am = $2 WHERE id = $1",
+                &[&candidate.booking_id, &today_str],
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Self::get_entry(pool, id).await
+    }
+
+    /// Deadline of the last stage passed: create a reminder for manual follow-up and end the
+    /// automatic procedure of the booking
+    pub async fn record_handover(pool: &DbPool, candidate: &DunningCandidate, today: NaiveDate) -> DbResult<DunningEntry> {
+        let mut client = pool.get().await?;
+        let today_str = today.format("%Y-%m-%d").to_string();
+
+        let transaction = client.transaction().await?;
+
+        let row = transaction
+            .query_one(
+                "INSERT INTO reminders (
+                    booking_id, reminder_type, title, description, due_date, priority,
+                    is_completed, is_snoozed, created_at, updated_at
+                 )
+                 SELECT b.id, $2,
+                        format('Mahnverfahren abgeschlossen - Buchung %s', b.reservierungsnummer),
+                        format('%s hat nach der letzten Mahnstufe noch %s € offen - weiteres Vorgehen klären',
+                               TRIM(g.vorname || ' ' || g.nachname), replace(bb.outstanding::text, '.', ',')),
+                        $3::text::date, 'high', FALSE, FALSE, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP
+                 FROM bookings b
+                 JOIN guests g ON g.id = b.guest_id
+                 JOIN booking_balances bb ON bb.booking_id = b.id
+                 WHERE b.id = $1
+                 ON CONFLICT (booking_id, reminder_type) WHERE is_completed = FALSE
+                 DO UPDATE SET description = EXCLUDED.description, updated_at = CURRENT_TIMESTAMP
+                 RETURNING id",
+                &[&candidate.booking_id, &HANDOVER_REMINDER_TYPE, &today_str],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+        let reminder_id: i32 = row.get("id");
+
+        let row = transaction
+            .query_one(
+                "INSERT INTO booking_dunning_history (booking_id, event, outstanding, reminder_id)
+                 SELECT $1, 'uebergabe', GREATEST(bb.outstanding, 0), $2
+                 FROM booking_balances bb WHERE bb.booking_id = $1
+                 RETURNING id",
+                &[&candidate.booking_id, &reminder_id],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+        let id: i32 = row.get("id");
+
+        transaction.commit().await?;
+
+        Self::get_entry(pool, id).await
+    }
+
+    async fn get_entry(pool: &DbPool, id: i32) -> DbResult<DunningEntry> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(&format!("{} WHERE h.id = $1", HISTORY_SELECT), &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Dunning entry with ID {} not found", id)))?;
+
+        Ok(DunningEntry::from(row))
+    }
+}
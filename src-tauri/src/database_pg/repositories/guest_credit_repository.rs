@@ -1,5 +1,9 @@
 use crate::database_pg::{DbPool, DbResult, DbError};
+use crate::money::Money;
+use crate::vouchers::{self, CreditLot};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use tokio_postgres::Row;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -7,29 +11,46 @@ pub struct GuestCreditTransaction {
     pub id: i32,
     pub guest_id: i32,
     pub booking_id: Option<i32>,
-    pub amount: f64,
-    pub transaction_type: String, // 'credit', 'debit', 'refund'
+    pub amount: Money,
+    pub transaction_type: String, // 'credit', 'debit', 'refund', 'expiry'
     pub description: Option<String>,
     pub created_at: String,
     pub created_by: Option<String>,
+    /// Credit only: valid until (incl.)
+    pub expires_at: Option<String>,
+}
+
+impl From<Row> for GuestCreditTransaction {
+    fn from(row: Row) -> Self {
+        Self {
+            id: row.get("id"),
+            guest_id: row.get("guest_id"),
+            booking_id: row.try_get("booking_id").ok().flatten(),
+            amount: row.get("amount"),
+            transaction_type: row.get("transaction_type"),
+            description: row.try_get("description").ok().flatten(),
+            created_at: row.get("created_at"),
+            created_by: row.try_get("created_by").ok().flatten(),
+            expires_at: row.try_get("expires_at").ok().flatten(),
+        }
+    }
 }
 
 pub struct GuestCreditRepository;
 
 impl GuestCreditRepository {
     /// Get credit balance for a guest
-    pub async fn get_balance(pool: &DbPool, guest_id: i32) -> DbResult<f64> {
+    pub async fn get_balance(pool: &DbPool, guest_id: i32) -> DbResult<Money> {
         let client = pool.get().await?;
 
         let row = client
             .query_one(
-                "SELECT get_guest_credit_balance($1)::text as balance",
+                "SELECT get_guest_credit_balance($1) as balance",
                 &[&guest_id],
             )
             .await?;
 
-        let balance: String = row.get("balance");
-        Ok(balance.parse().unwrap_or(0.0))
+        Ok(row.get::<_, Option<Money>>("balance").unwrap_or(Money::ZERO))
     }
 
     /// Get all transactions for a guest
@@ -41,8 +62,8 @@ impl GuestCreditRepository {
 
         let rows = client
             .query(
-                "SELECT id, guest_id, booking_id, amount::numeric::text as amount,
-                        transaction_type, description, created_at::text, created_by
+                "SELECT id, guest_id, booking_id, amount,
+                        transaction_type, description, created_at::text, created_by, expires_at::text
                  FROM guest_credit_transactions
                  WHERE guest_id = $1
                  ORDER BY created_at DESC",
@@ -50,21 +71,7 @@ impl GuestCreditRepository {
             )
             .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|row| {
-                GuestCreditTransaction {
-                    id: row.get("id"),
-                    guest_id: row.get("guest_id"),
-                    booking_id: row.try_get("booking_id").ok().flatten(),
-                    amount: row.get::<_, String>("amount").parse().unwrap_or(0.0),
-                    transaction_type: row.get("transaction_type"),
-                    description: row.try_get("description").ok().flatten(),
-                    created_at: row.get("created_at"),
-                    created_by: row.try_get("created_by").ok().flatten(),
-                }
-            })
-            .collect())
+        Ok(rows.into_iter().map(GuestCreditTransaction::from).collect())
     }
 
     /// Get transactions for a booking
@@ -76,8 +83,8 @@ impl GuestCreditRepository {
 
         let rows = client
             .query(
-                "SELECT id, guest_id, booking_id, amount::numeric::text as amount,
-                        transaction_type, description, created_at::text, created_by
+                "SELECT id, guest_id, booking_id, amount,
+                        transaction_type, description, created_at::text, created_by, expires_at::text
                  FROM guest_credit_transactions
                  WHERE booking_id = $1
                  ORDER BY created_at DESC",
@@ -85,53 +92,37 @@ impl GuestCreditRepository {
             )
             .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|row| {
-                GuestCreditTransaction {
-                    id: row.get("id"),
-                    guest_id: row.get("guest_id"),
-                    booking_id: row.try_get("booking_id").ok().flatten(),
-                    amount: row.get::<_, String>("amount").parse().unwrap_or(0.0),
-                    transaction_type: row.get("transaction_type"),
-                    description: row.try_get("description").ok().flatten(),
-                    created_at: row.get("created_at"),
-                    created_by: row.try_get("created_by").ok().flatten(),
-                }
-            })
-            .collect())
+        Ok(rows.into_iter().map(GuestCreditTransaction::from).collect())
     }
 
-    /// Add credit to a guest
+    /// Add credit to a guest, optionally valid until `expires_at` (YYYY-MM-DD, incl.)
     pub async fn add_credit(
         pool: &DbPool,
         guest_id: i32,
-        amount: f64,
+        amount: Money,
         description: Option<String>,
         created_by: Option<String>,
+        expires_at: Option<String>,
     ) -> DbResult<GuestCreditTransaction> {
+        let expires_at = expires_at.filter(|date| !date.trim().is_empty());
+        if let Some(date) = &expires_at {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| DbError::ValidationError(format!("Ungültiges Ablaufdatum: {}", date)))?;
+        }
+
         let client = pool.get().await?;
 
         let row = client
             .query_one(
-                "INSERT INTO guest_credit_transactions (guest_id, amount, transaction_type, description, created_by)
-                 VALUES ($1, $2, 'credit', $3, $4)
-                 RETURNING id, guest_id, booking_id, amount::numeric::text as amount,
-                           transaction_type, description, created_at::text, created_by",
-                &[&guest_id, &amount, &description, &created_by],
+                "INSERT INTO guest_credit_transactions (guest_id, amount, transaction_type, description, created_by, expires_at)
+                 VALUES ($1, $2, 'credit', $3, $4, $5::text::date)
+                 RETURNING id, guest_id, booking_id, amount,
+                           transaction_type, description, created_at::text, created_by, expires_at::text",
+                &[&guest_id, &amount, &description, &created_by, &expires_at],
             )
             .await?;
 
-        Ok(GuestCreditTransaction {
-            id: row.get("id"),
-            guest_id: row.get("guest_id"),
-            booking_id: row.try_get("booking_id").ok().flatten(),
-            amount: row.get::<_, String>("amount").parse().unwrap_or(0.0),
-            transaction_type: row.get("transaction_type"),
-            description: row.try_get("description").ok().flatten(),
-            created_at: row.get("created_at"),
-            created_by: row.try_get("created_by").ok().flatten(),
-        })
+        Ok(GuestCreditTransaction::from(row))
     }
 
     /// Use credit for a booking
@@ -139,7 +130,7 @@ impl GuestCreditRepository {
         pool: &DbPool,
         guest_id: i32,
         booking_id: i32,
-        amount: f64,
+        amount: Money,
         description: Option<String>,
         created_by: Option<String>,
     ) -> DbResult<GuestCreditTransaction> {
@@ -150,8 +141,8 @@ impl GuestCreditRepository {
             .query_one(
                 "INSERT INTO guest_credit_transactions (guest_id, booking_id, amount, transaction_type, description, created_by)
                  VALUES ($1, $2, $3, 'debit', $4, $5)
-                 RETURNING id, guest_id, booking_id, amount::numeric::text as amount,
-                           transaction_type, description, created_at::text, created_by",
+                 RETURNING id, guest_id, booking_id, amount,
+                           transaction_type, description, created_at::text, created_by, expires_at::text",
                 &[&guest_id, &booking_id, &amount, &description, &created_by],
             )
             .await
@@ -164,16 +155,7 @@ impl GuestCreditRepository {
                 }
             })?;
 
-        Ok(GuestCreditTransaction {
-            id: row.get("id"),
-            guest_id: row.get("guest_id"),
-            booking_id: row.try_get("booking_id").ok().flatten(),
-            amount: row.get::<_, String>("amount").parse().unwrap_or(0.0),
-            transaction_type: row.get("transaction_type"),
-            description: row.try_get("description").ok().flatten(),
-            created_at: row.get("created_at"),
-            created_by: row.try_get("created_by").ok().flatten(),
-        })
+        Ok(GuestCreditTransaction::from(row))
     }
 
     /// Refund credit from a booking
@@ -181,7 +163,7 @@ impl GuestCreditRepository {
         pool: &DbPool,
         guest_id: i32,
         booking_id: Option<i32>,
-        amount: f64,
+        amount: Money,
         description: Option<String>,
         created_by: Option<String>,
     ) -> DbResult<GuestCreditTransaction> {
@@ -191,22 +173,142 @@ impl GuestCreditRepository {
             .query_one(
                 "INSERT INTO guest_credit_transactions (guest_id, booking_id, amount, transaction_type, description, created_by)
                  VALUES ($1, $2, $3, 'refund', $4, $5)
-                 RETURNING id, guest_id, booking_id, amount::numeric::text as amount,
-                           transaction_type, description, created_at::text, created_by",
+                 RETURNING id, guest_id, booking_id, amount,
+                           transaction_type, description, created_at::text, created_by, expires_at::text",
                 &[&guest_id, &booking_id, &amount, &description, &created_by],
             )
             .await?;
 
-        Ok(GuestCreditTransaction {
-            id: row.get("id"),
-            guest_id: row.get("guest_id"),
-            booking_id: row.try_get("booking_id").ok().flatten(),
-            amount: row.get::<_, String>("amount").parse().unwrap_or(0.0),
-            transaction_type: row.get("transaction_type"),
-            description: row.try_get("description").ok().flatten(),
-            created_at: row.get("created_at"),
-            created_by: row.try_get("created_by").ok().flatten(),
-        })
+        Ok(GuestCreditTransaction::from(row))
+    }
+
+    /// Give back the credit a booking still holds (debits minus earlier refunds), e.g. on cancellation.
+    /// Returns None if nothing is left to release.
+    pub async fn release_for_booking(
+        pool: &DbPool,
+        booking_id: i32,
+        description: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<Option<GuestCreditTransaction>> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Lock the booking row so two cancellations cannot refund twice
+        transaction
+            .execute("SELECT id FROM bookings WHERE id = $1 FOR UPDATE", &[&booking_id])
+            .await?;
+
+        let held = transaction
+            .query_opt(
+                "SELECT guest_id,
+                        SUM(CASE WHEN transaction_type = 'debit' THEN amount ELSE -amount END) AS held
+                 FROM guest_credit_transactions
+                 WHERE booking_id = $1 AND transaction_type IN ('debit', 'refund')
+                 GROUP BY guest_id",
+                &[&booking_id],
+            )
+            .await?;
+
+        let Some(held) = held else {
+            return Ok(None);
+        };
+        let guest_id: i32 = held.get("guest_id");
+        let amount: Money = held.get("held");
+        if !amount.is_positive() {
+            return Ok(None);
+        }
+
+        let row = transaction
+            .query_one(
+                "INSERT INTO guest_credit_transactions (guest_id, booking_id, amount, transaction_type, description, created_by)
+                 VALUES ($1, $2, $3, 'refund', $4, $5)
+                 RETURNING id, guest_id, booking_id, amount,
+                           transaction_type, description, created_at::text, created_by, expires_at::text",
+                &[&guest_id, &booking_id, &amount, &description, &created_by],
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(Some(GuestCreditTransaction::from(row)))
+    }
+
+    /// Book what is left of expired credit as 'expiry' transaction (hourly job).
+    /// Returns the new expiry transactions.
+    pub async fn expire_credits(pool: &DbPool, today: NaiveDate) -> DbResult<Vec<GuestCreditTransaction>> {
+        let mut client = pool.get().await?;
+        let today_text = today.format("%Y-%m-%d").to_string();
+
+        let guest_ids: Vec<i32> = client
+            .query(
+                "SELECT DISTINCT guest_id FROM guest_credit_transactions
+                 WHERE transaction_type = 'credit' AND expires_at < $1::text::date",
+                &[&today_text],
+            )
+            .await?
+            .into_iter()
+            .map(|row| row.get("guest_id"))
+            .collect();
+
+        let mut expired = Vec::new();
+        for guest_id in guest_ids {
+            let transaction = client.transaction().await?;
+
+            // Lock the guest so two workstations cannot book the same expiry
+            transaction
+                .execute("SELECT id FROM guests WHERE id = $1 FOR UPDATE", &[&guest_id])
+                .await?;
+
+            let rows = transaction
+                .query(
+                    "SELECT transaction_type, amount, booking_id, expires_at::text
+                     FROM guest_credit_transactions WHERE guest_id = $1",
+                    &[&guest_id],
+                )
+                .await?;
+
+            let mut lots = Vec::new();
+            let mut used = Money::ZERO;
+            let mut already_expired = Money::ZERO;
+            for row in rows {
+                let amount: Money = row.get("amount");
+                let booking_id: Option<i32> = row.get("booking_id");
+                let expires_at: Option<String> = row.get("expires_at");
+                match row.get::<_, String>("transaction_type").as_str() {
+                    "credit" => lots.push(CreditLot {
+                        amount,
+                        expires_at: expires_at.and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()),
+                    }),
+                    "debit" => used += amount,
+                    // Credit given back from a booking reverses its debit, other refunds are new credit
+                    "refund" if booking_id.is_some() => used -= amount,
+                    "refund" => lots.push(CreditLot { amount, expires_at: None }),
+                    "expiry" => already_expired += amount,
+                    _ => {}
+                }
+            }
+
+            let amount = vouchers::expired_credit(&lots, used, already_expired, today);
+            if !amount.is_positive() {
+                transaction.commit().await?;
+                continue;
+            }
+
+            let row = transaction
+                .query_one(
+                    "INSERT INTO guest_credit_transactions (guest_id, amount, transaction_type, description, created_by)
+                     VALUES ($1, $2, 'expiry', 'Nicht genutztes Guthaben verfallen', 'system')
+                     RETURNING id, guest_id, booking_id, amount,
+                               transaction_type, description, created_at::text, created_by, expires_at::text",
+                    &[&guest_id, &amount],
+                )
+                .await?;
+            transaction.commit().await?;
+
+            expired.push(GuestCreditTransaction::from(row));
+        }
+
+        Ok(expired)
     }
 
     /// Delete a transaction (admin only - use with caution)
@@ -231,18 +333,17 @@ impl GuestCreditRepository {
     }
 
     /// Get total credit used for a booking
-    pub async fn get_booking_credit_usage(pool: &DbPool, booking_id: i32) -> DbResult<f64> {
+    pub async fn get_booking_credit_usage(pool: &DbPool, booking_id: i32) -> DbResult<Money> {
         let client = pool.get().await?;
 
         let row = client
             .query_one(
-                "SELECT get_booking_credit_usage($1)::text as total",
+                "SELECT get_booking_credit_usage($1) as total",
                 &[&booking_id],
             )
             .await?;
 
-        let total: String = row.get("total");
-        Ok(total.parse().unwrap_or(0.0))
+        Ok(row.get::<_, Option<Money>>("total").unwrap_or(Money::ZERO))
     }
 
     /// Get statistics for all guest credits
@@ -253,9 +354,10 @@ impl GuestCreditRepository {
             .query_one(
                 "SELECT
                     COUNT(DISTINCT guest_id) as guests_with_credit,
-                    COALESCE(SUM(CASE WHEN transaction_type = 'credit' THEN amount ELSE 0 END)::numeric::text, '0') as total_credits,
-                    COALESCE(SUM(CASE WHEN transaction_type = 'debit' THEN amount ELSE 0 END)::numeric::text, '0') as total_debits,
-                    COALESCE(SUM(CASE WHEN transaction_type = 'refund' THEN amount ELSE 0 END)::numeric::text, '0') as total_refunds,
+                    COALESCE(SUM(CASE WHEN transaction_type = 'credit' THEN amount ELSE 0 END), 0) as total_credits,
+                    COALESCE(SUM(CASE WHEN transaction_type = 'debit' THEN amount ELSE 0 END), 0) as total_debits,
+                    COALESCE(SUM(CASE WHEN transaction_type = 'refund' THEN amount ELSE 0 END), 0) as total_refunds,
+                    COALESCE(SUM(CASE WHEN transaction_type = 'expiry' THEN amount ELSE 0 END), 0) as total_expired,
                     COUNT(*) as total_transactions
                  FROM guest_credit_transactions",
                 &[],
@@ -264,9 +366,10 @@ impl GuestCreditRepository {
 
         Ok(GuestCreditStatistics {
             guests_with_credit: row.get::<_, i64>("guests_with_credit") as i32,
-            total_credits: row.get::<_, String>("total_credits").parse().unwrap_or(0.0),
-            total_debits: row.get::<_, String>("total_debits").parse().unwrap_or(0.0),
-            total_refunds: row.get::<_, String>("total_refunds").parse().unwrap_or(0.0),
+            total_credits: row.get("total_credits"),
+            total_debits: row.get("total_debits"),
+            total_refunds: row.get("total_refunds"),
+            total_expired: row.get("total_expired"),
             total_transactions: row.get::<_, i64>("total_transactions") as i32,
         })
     }
@@ -325,8 +428,9 @@ impl GuestCreditRepository {
 #[serde(rename_all = "camelCase")]
 pub struct GuestCreditStatistics {
     pub guests_with_credit: i32,
-    pub total_credits: f64,
-    pub total_debits: f64,
-    pub total_refunds: f64,
+    pub total_credits: Money,
+    pub total_debits: Money,
+    pub total_refunds: Money,
+    pub total_expired: Money,
     pub total_transactions: i32,
 }
@@ -1,4 +1,5 @@
 use crate::database_pg::{DbPool, DbResult, Guest};
+use crate::sepa;
 
 pub struct GuestRepository;
 
@@ -16,7 +17,10 @@ impl GuestRepository {
                         fax, geburtsdatum, geburtsort, sprache, nationalitaet,
                         identifikationsnummer, debitorenkonto, kennzeichen,
                         rechnungs_email, marketing_einwilligung, leitweg_id,
-                        kostenstelle, tags, automail, automail_sprache, created_by, updated_by
+                        kostenstelle, tags, automail, automail_sprache, created_by, updated_by,
+                        sepa_mandatsreferenz, sepa_mandat_datum::text as sepa_mandat_datum, sepa_iban, sepa_bic,
+                        sepa_kontoinhaber, sepa_mandat_typ, sepa_einmalig,
+                        sepa_letzte_lastschrift::text as sepa_letzte_lastschrift
                  FROM guests
                  ORDER BY nachname, vorname",
                 &[],
@@ -39,7 +43,10 @@ impl GuestRepository {
                         fax, geburtsdatum, geburtsort, sprache, nationalitaet,
                         identifikationsnummer, debitorenkonto, kennzeichen,
                         rechnungs_email, marketing_einwilligung, leitweg_id,
-                        kostenstelle, tags, automail, automail_sprache, created_by, updated_by
+                        kostenstelle, tags, automail, automail_sprache, created_by, updated_by,
+                        sepa_mandatsreferenz, sepa_mandat_datum::text as sepa_mandat_datum, sepa_iban, sepa_bic,
+                        sepa_kontoinhaber, sepa_mandat_typ, sepa_einmalig,
+                        sepa_letzte_lastschrift::text as sepa_letzte_lastschrift
                  FROM guests
                  WHERE id = $1",
                 &[&id],
@@ -115,7 +122,10 @@ impl GuestRepository {
                              fax, geburtsdatum, geburtsort, sprache, nationalitaet,
                              identifikationsnummer, debitorenkonto, kennzeichen,
                              rechnungs_email, marketing_einwilligung, leitweg_id,
-                             kostenstelle, tags, automail, automail_sprache, created_by, updated_by",
+                             kostenstelle, tags, automail, automail_sprache, created_by, updated_by,
+                             sepa_mandatsreferenz, sepa_mandat_datum::text as sepa_mandat_datum, sepa_iban, sepa_bic,
+                             sepa_kontoinhaber, sepa_mandat_typ, sepa_einmalig,
+                             sepa_letzte_lastschrift::text as sepa_letzte_lastschrift",
                 &[
                     &vorname, &nachname, &email, &telefon, &dpolg_mitglied,
                     &strasse, &plz, &ort, &mitgliedsnummer, &notizen, &beruf,
@@ -198,7 +208,10 @@ impl GuestRepository {
                            fax, geburtsdatum, geburtsort, sprache, nationalitaet,
                            identifikationsnummer, debitorenkonto, kennzeichen,
                            rechnungs_email, marketing_einwilligung, leitweg_id,
-                           kostenstelle, tags, automail, automail_sprache, created_by, updated_by",
+                           kostenstelle, tags, automail, automail_sprache, created_by, updated_by,
+                           sepa_mandatsreferenz, sepa_mandat_datum::text as sepa_mandat_datum, sepa_iban, sepa_bic,
+                           sepa_kontoinhaber, sepa_mandat_typ, sepa_einmalig,
+                           sepa_letzte_lastschrift::text as sepa_letzte_lastschrift",
                 &[
                     &id, &vorname, &nachname, &email, &telefon, &dpolg_mitglied,
                     &strasse, &plz, &ort, &mitgliedsnummer, &notizen, &beruf,
@@ -216,6 +229,84 @@ impl GuestRepository {
         Ok(Guest::from(row))
     }
 
+    /// Store the SEPA mandate of a guest. A new mandate reference starts again with a first
+    /// debit (FRST).
+    #[allow(clippy::too_many_arguments)]
+    pub async fn set_sepa_mandate(
+        pool: &DbPool,
+        id: i32,
+        mandatsreferenz: String,
+        mandat_datum: String,
+        iban: String,
+        bic: Option<String>,
+        kontoinhaber: Option<String>,
+        mandat_typ: String,
+        einmalig: bool,
+        updated_by: Option<String>,
+    ) -> DbResult<Guest> {
+        let validation = |e: String| crate::database_pg::DbError::ValidationError(e);
+        let mandatsreferenz = mandatsreferenz.trim().to_string();
+        sepa::validate_mandate_reference(&mandatsreferenz).map_err(validation)?;
+        let iban = sepa::normalize_iban(&iban).map_err(validation)?;
+        let bic = bic
+            .filter(|b| !b.trim().is_empty())
+            .map(|b| sepa::normalize_bic(&b))
+            .transpose()
+            .map_err(validation)?;
+        sepa::Scheme::parse(&mandat_typ).map_err(validation)?;
+        chrono::NaiveDate::parse_from_str(&mandat_datum, "%Y-%m-%d")
+            .map_err(|_| validation(format!("Ungültiges Unterschriftsdatum: {}", mandat_datum)))?;
+        let kontoinhaber = kontoinhaber.map(|k| k.trim().to_string()).filter(|k| !k.is_empty());
+
+        let client = pool.get().await?;
+
+        let taken = client
+            .query_opt(
+                "SELECT id FROM guests WHERE sepa_mandatsreferenz = $1 AND id <> $2",
+                &[&mandatsreferenz, &id],
+            )
+            .await?;
+        if taken.is_some() {
+            return Err(validation(format!("Die Mandatsreferenz {} ist bereits vergeben", mandatsreferenz)));
+        }
+
+        let rows_affected = client
+            .execute(
+                "UPDATE guests SET
+                    sepa_letzte_lastschrift = CASE WHEN sepa_mandatsreferenz IS DISTINCT FROM $2
+                                                   THEN NULL ELSE sepa_letzte_lastschrift END,
+                    sepa_mandatsreferenz = $2, sepa_mandat_datum = $3::text::date, sepa_iban = $4,
+                    sepa_bic = $5, sepa_kontoinhaber = $6, sepa_mandat_typ = $7, sepa_einmalig = $8,
+                    updated_by = $9
+                 WHERE id = $1",
+                &[&id, &mandatsreferenz, &mandat_datum, &iban, &bic, &kontoinhaber, &mandat_typ, &einmalig, &updated_by],
+            )
+            .await?;
+        if rows_affected == 0 {
+            return Err(crate::database_pg::DbError::NotFound(format!("Guest with ID {} not found", id)));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Remove the SEPA mandate (revoked by the guest or after a return)
+    pub async fn remove_sepa_mandate(pool: &DbPool, id: i32, updated_by: Option<String>) -> DbResult<Guest> {
+        let client = pool.get().await?;
+
+        client
+            .execute(
+                "UPDATE guests SET
+                    sepa_mandatsreferenz = NULL, sepa_mandat_datum = NULL, sepa_iban = NULL, sepa_bic = NULL,
+                    sepa_kontoinhaber = NULL, sepa_mandat_typ = NULL, sepa_einmalig = FALSE,
+                    sepa_letzte_lastschrift = NULL, updated_by = $2
+                 WHERE id = $1",
+                &[&id, &updated_by],
+            )
+            .await?;
+
+        Self::get_by_id(pool, id).await
+    }
+
     /// Delete a guest
     pub async fn delete(pool: &DbPool, id: i32) -> DbResult<()> {
         let client = pool.get().await?;
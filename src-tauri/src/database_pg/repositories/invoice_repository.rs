@@ -0,0 +1,396 @@
+use chrono::Datelike;
+use tokio_postgres::{Row, Transaction};
+
+use crate::database_pg::{DbPool, DbError, DbResult, IssuedInvoice};
+use crate::invoice::{self, Invoice};
+use crate::money::Money;
+
+const INVOICE_COLUMNS: &str = "id, document_type, invoice_number, year, sequence_number, booking_id, booking_group_id,
+    issue_date::text as issue_date, total, amount_due, document::text as document,
+    corrects_invoice_id, is_cancellation, reason, pdf_path, created_at::text as created_at, created_by";
+
+/// Repository for issued invoices and credit notes (GoBD: gap-free numbers, immutable snapshots)
+pub struct InvoiceRepository;
+
+impl InvoiceRepository {
+    /// Run invoices + credit notes migrations (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        for migration_sql in [
+            include_str!("../../../../migrations/023_invoices.sql"),
+            include_str!("../../../../migrations/024_credit_notes.sql"),
+        ] {
+            client
+                .batch_execute(migration_sql)
+                .await
+                .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Issue an invoice: allocates the next number of the issue year and stores the snapshot.
+    /// Number allocation and insert run in one transaction - the sequence row stays locked
+    /// until commit and a failed insert releases the number again, so numbers have no gaps.
+    /// If the booking already has an active invoice (e.g. issued by another workstation at
+    /// the same time), that invoice is returned and no number is allocated.
+    pub async fn issue(
+        pool: &DbPool,
+        booking_id: i32,
+        document: Invoice,
+        created_by: Option<String>,
+    ) -> DbResult<IssuedInvoice> {
+        if document.lines.is_empty() {
+            return Err(DbError::ValidationError("Rechnung ohne Positionen kann nicht ausgestellt werden".to_string()));
+        }
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Lock the booking, a second user issuing at the same time waits and then sees the invoice
+        transaction
+            .execute("SELECT id FROM bookings WHERE id = $1 FOR UPDATE", &[&booking_id])
+            .await?;
+        let active = transaction
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM invoices i
+                     WHERE booking_id = $1
+                       AND booking_group_id IS NULL
+                       AND document_type = 'rechnung'
+                       AND NOT EXISTS (
+                           SELECT 1 FROM invoices c WHERE c.corrects_invoice_id = i.id AND c.is_cancellation
+                       )
+                     ORDER BY year DESC, sequence_number DESC
+                     LIMIT 1",
+                    INVOICE_COLUMNS
+                ),
+                &[&booking_id],
+            )
+            .await?;
+        if let Some(active) = active {
+            return Self::map_row(active);
+        }
+
+        let row = Self::insert_document(
+            &transaction,
+            invoice::DOCUMENT_INVOICE,
+            booking_id,
+            None,
+            document,
+            None,
+            false,
+            None,
+            created_by,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        Self::map_row(row)
+    }
+
+    /// Issue the consolidated invoice of a booking group (invoice::group_invoice).
+    /// It is stored under the master booking and can only exist once per group until cancelled.
+    pub async fn issue_for_group(
+        pool: &DbPool,
+        booking_group_id: i32,
+        master_booking_id: i32,
+        document: Invoice,
+        created_by: Option<String>,
+    ) -> DbResult<IssuedInvoice> {
+        if document.lines.is_empty() {
+            return Err(DbError::ValidationError("Rechnung ohne Positionen kann nicht ausgestellt werden".to_string()));
+        }
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Lock the group, a second user issuing at the same time waits and then sees the invoice
+        transaction
+            .execute("SELECT id FROM booking_groups WHERE id = $1 FOR UPDATE", &[&booking_group_id])
+            .await?;
+        let active = transaction
+            .query_opt(
+                "SELECT invoice_number FROM invoices i
+                 WHERE booking_group_id = $1
+                   AND document_type = 'rechnung'
+                   AND NOT EXISTS (
+                       SELECT 1 FROM invoices c WHERE c.corrects_invoice_id = i.id AND c.is_cancellation
+                   )",
+                &[&booking_group_id],
+            )
+            .await?;
+        if let Some(active) = active {
+            return Err(DbError::ValidationError(format!(
+                "Für die Gruppe besteht bereits die Rechnung {}",
+                active.get::<_, String>("invoice_number")
+            )));
+        }
+
+        let row = Self::insert_document(
+            &transaction,
+            invoice::DOCUMENT_INVOICE,
+            master_booking_id,
+            Some(booking_group_id),
+            document,
+            None,
+            false,
+            None,
+            created_by,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        Self::map_row(row)
+    }
+
+    /// Issue a credit note for `original_id`. `document` holds the negative amounts
+    /// (invoice::cancellation_document / invoice::correction_document).
+    /// All credit notes of an invoice together can never exceed the invoiced total.
+    pub async fn issue_credit_note(
+        pool: &DbPool,
+        original_id: i32,
+        document: Invoice,
+        is_cancellation: bool,
+        reason: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<IssuedInvoice> {
+        if document.lines.is_empty() || !document.total.is_negative() {
+            return Err(DbError::ValidationError("Gutschrift muss einen negativen Betrag haben".to_string()));
+        }
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Lock the original invoice, concurrent credit notes for it wait here
+        let original = transaction
+            .query_opt(
+                "SELECT booking_id, booking_group_id, document_type, invoice_number, total
+                 FROM invoices WHERE id = $1 FOR UPDATE",
+                &[&original_id],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Invoice with ID {} not found", original_id)))?;
+
+        let document_type: String = original.get("document_type");
+        let invoice_number: String = original.get("invoice_number");
+        if document_type != invoice::DOCUMENT_INVOICE {
+            return Err(DbError::ValidationError(format!(
+                "{} ist eine Gutschrift und kann nicht korrigiert werden",
+                invoice_number
+            )));
+        }
+
+        let credit_notes = transaction
+            .query_one(
+                "SELECT COALESCE(SUM(total), 0) AS credited, COALESCE(BOOL_OR(is_cancellation), FALSE) AS cancelled
+                 FROM invoices WHERE corrects_invoice_id = $1",
+                &[&original_id],
+            )
+            .await?;
+
+        if credit_notes.get::<_, bool>("cancelled") {
+            return Err(DbError::ValidationError(format!("Rechnung {} ist bereits storniert", invoice_number)));
+        }
+
+        let invoiced: Money = original.get("total");
+        let credited: Money = credit_notes.get("credited");
+        if is_cancellation && !credited.is_zero() {
+            return Err(DbError::ValidationError(format!(
+                "Rechnung {} wurde bereits teilweise korrigiert - bitte den Restbetrag per Korrektur gutschreiben",
+                invoice_number
+            )));
+        }
+
+        let open = invoiced + credited;
+        if -document.total > open {
+            return Err(DbError::ValidationError(format!(
+                "Gutschrift ({}) übersteigt den offenen Betrag der Rechnung {} ({})",
+                (-document.total).format_eur(),
+                invoice_number,
+                open.format_eur()
+            )));
+        }
+
+        let row = Self::insert_document(
+            &transaction,
+            invoice::DOCUMENT_CREDIT_NOTE,
+            original.get("booking_id"),
+            original.get("booking_group_id"),
+            document,
+            Some(original_id),
+            is_cancellation,
+            reason,
+            created_by,
+        )
+        .await?;
+
+        transaction.commit().await?;
+
+        Self::map_row(row)
+    }
+
+    /// Get invoice or credit note by ID
+    pub async fn get_by_id(pool: &DbPool, id: i32) -> DbResult<IssuedInvoice> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(&format!("SELECT {} FROM invoices WHERE id = $1", INVOICE_COLUMNS), &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Invoice with ID {} not found", id)))?;
+
+        Self::map_row(row)
+    }
+
+    /// All invoices and credit notes of a booking, in issue order
+    pub async fn get_for_booking(pool: &DbPool, booking_id: i32) -> DbResult<Vec<IssuedInvoice>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM invoices WHERE booking_id = $1 ORDER BY created_at, id",
+                    INVOICE_COLUMNS
+                ),
+                &[&booking_id],
+            )
+            .await?;
+
+        rows.into_iter().map(Self::map_row).collect()
+    }
+
+    /// Latest invoice of a booking that has not been cancelled (None = not invoiced yet).
+    /// Group invoices are not included, see `get_active_for_group`.
+    pub async fn get_active_for_booking(pool: &DbPool, booking_id: i32) -> DbResult<Option<IssuedInvoice>> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM invoices i
+                     WHERE booking_id = $1
+                       AND booking_group_id IS NULL
+                       AND document_type = 'rechnung'
+                       AND NOT EXISTS (
+                           SELECT 1 FROM invoices c WHERE c.corrects_invoice_id = i.id AND c.is_cancellation
+                       )
+                     ORDER BY year DESC, sequence_number DESC
+                     LIMIT 1",
+                    INVOICE_COLUMNS
+                ),
+                &[&booking_id],
+            )
+            .await?;
+
+        row.map(Self::map_row).transpose()
+    }
+
+    /// Consolidated invoice of a booking group that has not been cancelled
+    pub async fn get_active_for_group(pool: &DbPool, booking_group_id: i32) -> DbResult<Option<IssuedInvoice>> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM invoices i
+                     WHERE booking_group_id = $1
+                       AND document_type = 'rechnung'
+                       AND NOT EXISTS (
+                           SELECT 1 FROM invoices c WHERE c.corrects_invoice_id = i.id AND c.is_cancellation
+                       )
+                     ORDER BY year DESC, sequence_number DESC
+                     LIMIT 1",
+                    INVOICE_COLUMNS
+                ),
+                &[&booking_group_id],
+            )
+            .await?;
+
+        row.map(Self::map_row).transpose()
+    }
+
+    /// Store the path of the rendered PDF (the only column that may change after issuing)
+    pub async fn set_pdf_path(pool: &DbPool, id: i32, pdf_path: &str) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let rows_affected = client
+            .execute("UPDATE invoices SET pdf_path = $2 WHERE id = $1", &[&id, &pdf_path])
+            .await?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound(format!("Invoice with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Allocate the next number of the document type and year and insert the snapshot
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_document(
+        transaction: &Transaction<'_>,
+        document_type: &str,
+        booking_id: i32,
+        booking_group_id: Option<i32>,
+        mut document: Invoice,
+        corrects_invoice_id: Option<i32>,
+        is_cancellation: bool,
+        reason: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<Row> {
+        let year = document.issue_date.year();
+        let sequence: i32 = transaction
+            .query_one(
+                "INSERT INTO invoice_number_sequences (document_type, year, last_number) VALUES ($1, $2, 1)
+                 ON CONFLICT (document_type, year)
+                 DO UPDATE SET last_number = invoice_number_sequences.last_number + 1
+                 RETURNING last_number",
+                &[&document_type, &year],
+            )
+            .await?
+            .get("last_number");
+
+        document.number = invoice::format_document_number(document_type, year, sequence);
+        let snapshot = serde_json::to_string(&document)
+            .map_err(|e| DbError::Other(format!("Rechnungs-Snapshot fehlgeschlagen: {}", e)))?;
+        let issue_date = document.issue_date.format("%Y-%m-%d").to_string();
+
+        let row = transaction
+            .query_one(
+                &format!(
+                    "INSERT INTO invoices (document_type, invoice_number, year, sequence_number, booking_id, issue_date,
+                                           total, amount_due, document, corrects_invoice_id, is_cancellation,
+                                           reason, created_by, booking_group_id)
+                     VALUES ($1, $2, $3, $4, $5, $6::text::date, $7, $8, $9::text::jsonb, $10, $11, $12, $13, $14)
+                     RETURNING {}",
+                    INVOICE_COLUMNS
+                ),
+                &[
+                    &document_type,
+                    &document.number,
+                    &year,
+                    &sequence,
+                    &booking_id,
+                    &issue_date,
+                    &document.total,
+                    &document.amount_due,
+                    &snapshot,
+                    &corrects_invoice_id,
+                    &is_cancellation,
+                    &reason,
+                    &created_by,
+                    &booking_group_id,
+                ],
+            )
+            .await?;
+
+        Ok(row)
+    }
+
+    fn map_row(row: Row) -> DbResult<IssuedInvoice> {
+        IssuedInvoice::try_from(row)
+            .map_err(|e| DbError::Other(format!("Rechnungs-Snapshot ungültig: {}", e)))
+    }
+}
@@ -0,0 +1,110 @@
+use crate::database_pg::{DbPool, DbError, DbResult, KurtaxeRate};
+use crate::money::Money;
+
+const KURTAXE_COLUMNS: &str = "id, ort, betrag_pro_nacht, befreit_unter_alter, geschaeftsreise_befreit, is_active";
+
+/// Repository for the Kurtaxe rates per municipality
+pub struct KurtaxeRepository;
+
+impl KurtaxeRepository {
+    /// Run Kurtaxe migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/021_kurtaxe.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get all Kurtaxe rates
+    pub async fn get_all(pool: &DbPool) -> DbResult<Vec<KurtaxeRate>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!("SELECT {} FROM kurtaxe_rates ORDER BY ort", KURTAXE_COLUMNS),
+                &[],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(KurtaxeRate::from).collect())
+    }
+
+    /// Active Kurtaxe rate of a municipality (None = no Kurtaxe)
+    pub async fn get_for_ort(pool: &DbPool, ort: &str) -> DbResult<Option<KurtaxeRate>> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM kurtaxe_rates WHERE ort = $1 AND is_active = TRUE",
+                    KURTAXE_COLUMNS
+                ),
+                &[&ort],
+            )
+            .await?;
+
+        Ok(row.map(KurtaxeRate::from))
+    }
+
+    /// Create or update the Kurtaxe rate of a municipality (UPSERT on ort)
+    pub async fn upsert(
+        pool: &DbPool,
+        ort: String,
+        betrag_pro_nacht: Money,
+        befreit_unter_alter: i32,
+        geschaeftsreise_befreit: bool,
+        is_active: bool,
+    ) -> DbResult<KurtaxeRate> {
+        if ort.trim().is_empty() {
+            return Err(DbError::ValidationError("Ort darf nicht leer sein".to_string()));
+        }
+        if betrag_pro_nacht.is_negative() {
+            return Err(DbError::ValidationError("Kurtaxe darf nicht negativ sein".to_string()));
+        }
+        if befreit_unter_alter < 0 {
+            return Err(DbError::ValidationError("Altersgrenze darf nicht negativ sein".to_string()));
+        }
+
+        let client = pool.get().await?;
+
+        let row = client
+            .query_one(
+                &format!(
+                    "INSERT INTO kurtaxe_rates (ort, betrag_pro_nacht, befreit_unter_alter, geschaeftsreise_befreit, is_active)
+                     VALUES ($1, $2, $3, $4, $5)
+                     ON CONFLICT (ort) DO UPDATE SET
+                        betrag_pro_nacht = EXCLUDED.betrag_pro_nacht,
+                        befreit_unter_alter = EXCLUDED.befreit_unter_alter,
+                        geschaeftsreise_befreit = EXCLUDED.geschaeftsreise_befreit,
+                        is_active = EXCLUDED.is_active,
+                        updated_at = CURRENT_TIMESTAMP
+                     RETURNING {}",
+                    KURTAXE_COLUMNS
+                ),
+                &[&ort.trim(), &betrag_pro_nacht, &befreit_unter_alter, &geschaeftsreise_befreit, &is_active],
+            )
+            .await?;
+
+        Ok(KurtaxeRate::from(row))
+    }
+
+    /// Delete Kurtaxe rate
+    pub async fn delete(pool: &DbPool, id: i32) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let rows_affected = client
+            .execute("DELETE FROM kurtaxe_rates WHERE id = $1", &[&id])
+            .await?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound(format!("Kurtaxe rate with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+}
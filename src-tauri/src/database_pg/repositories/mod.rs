@@ -22,6 +22,19 @@ pub mod guest_credit_repository;
 pub mod cleaning_task_repository;
 pub mod scheduled_email_repository;
 pub mod lock_repository;
+pub mod season_repository;
+pub mod kurtaxe_repository;
+pub mod invoice_repository;
+pub mod booking_group_repository;
+pub mod allotment_repository;
+pub mod waitlist_repository;
+pub mod room_block_repository;
+pub mod booking_series_repository;
+pub mod payment_repository;
+pub mod bank_transaction_repository;
+pub mod sepa_repository;
+pub mod dunning_repository;
+pub mod voucher_repository;
 
 pub use room_repository::RoomRepository;
 pub use guest_repository::GuestRepository;
@@ -44,5 +57,18 @@ pub use guest_credit_repository::GuestCreditRepository;
 pub use cleaning_task_repository::CleaningTaskRepository;
 pub use scheduled_email_repository::ScheduledEmailRepository;
 pub use lock_repository::LockRepository;
+pub use season_repository::SeasonRepository;
+pub use kurtaxe_repository::KurtaxeRepository;
+pub use invoice_repository::InvoiceRepository;
+pub use booking_group_repository::BookingGroupRepository;
+pub use allotment_repository::AllotmentRepository;
+pub use waitlist_repository::WaitlistRepository;
+pub use room_block_repository::RoomBlockRepository;
+pub use booking_series_repository::BookingSeriesRepository;
+pub use payment_repository::PaymentRepository;
+pub use bank_transaction_repository::BankTransactionRepository;
+pub use sepa_repository::SepaRepository;
+pub use dunning_repository::{DunningCandidate, DunningRepository};
+pub use voucher_repository::VoucherRepository;
 
 // More repositories will be added as needed
@@ -14,7 +14,7 @@ impl NotificationSettingsRepository {
                         payment_reminder_repeat_days, scheduler_interval_hours,
                         auto_reminder_incomplete_data, auto_reminder_payment,
                         payment_reminder_before_days,
-                        auto_reminder_checkin, auto_reminder_invoice,
+                        auto_reminder_checkin, auto_reminder_invoice, option_hold_days,
                         updated_at::text as updated_at
                  FROM notification_settings
                  LIMIT 1",
@@ -44,9 +44,9 @@ impl NotificationSettingsRepository {
                     payment_reminder_repeat_days, scheduler_interval_hours,
                     auto_reminder_incomplete_data, auto_reminder_payment,
                     payment_reminder_before_days,
-                    auto_reminder_checkin, auto_reminder_invoice, updated_at
+                    auto_reminder_checkin, auto_reminder_invoice, option_hold_days, updated_at
                  )
-                 VALUES (1, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, CURRENT_TIMESTAMP)
+                 VALUES (1, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, COALESCE($12, 3), CURRENT_TIMESTAMP)
                  ON CONFLICT (id) DO UPDATE SET
                     checkin_reminders_enabled = EXCLUDED.checkin_reminders_enabled,
                     checkin_reminder_before_days = EXCLUDED.checkin_reminder_before_days,
@@ -59,13 +59,14 @@ impl NotificationSettingsRepository {
                     payment_reminder_before_days = EXCLUDED.payment_reminder_before_days,
                     auto_reminder_checkin = EXCLUDED.auto_reminder_checkin,
                     auto_reminder_invoice = EXCLUDED.auto_reminder_invoice,
+                    option_hold_days = COALESCE($12, notification_settings.option_hold_days),
                     updated_at = CURRENT_TIMESTAMP
                  RETURNING id, checkin_reminders_enabled, checkin_reminder_before_days,
                            payment_reminders_enabled, payment_reminder_after_days,
                            payment_reminder_repeat_days, scheduler_interval_hours,
                            auto_reminder_incomplete_data, auto_reminder_payment,
                            payment_reminder_before_days,
-                           auto_reminder_checkin, auto_reminder_invoice,
+                           auto_reminder_checkin, auto_reminder_invoice, option_hold_days,
                            updated_at::text as updated_at",
                 &[
                     &settings.checkin_reminders_enabled,
@@ -79,6 +80,7 @@ impl NotificationSettingsRepository {
                     &settings.payment_reminder_before_days,
                     &settings.auto_reminder_checkin,
                     &settings.auto_reminder_invoice,
+                    &settings.option_hold_days,
                 ],
             )
             .await?;
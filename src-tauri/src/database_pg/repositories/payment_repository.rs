@@ -0,0 +1,404 @@
+use crate::database_pg::{BookingBalance, BookingPayments, DbError, DbPool, DbResult, DepositRequest, Payment};
+use crate::money::Money;
+use crate::payments::{self, DepositInput};
+use tokio_postgres::Transaction;
+
+const PAYMENT_COLUMNS: &str = "id, booking_id, amount, payment_date::text as payment_date, method, reference, notes,
+    recorded_by, created_at::text as created_at, reversal_of";
+
+const DEPOSIT_COLUMNS: &str = "id, booking_id, description, amount, percentage::double precision as percentage,
+    due_date::text as due_date, created_at::text as created_at, created_by";
+
+/// Repository for the payments ledger and deposit requests of bookings
+pub struct PaymentRepository;
+
+impl PaymentRepository {
+    /// Run payments migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/035_payments.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Balance of a booking (amount due, paid, outstanding, payment status)
+    pub async fn get_balance(pool: &DbPool, booking_id: i32) -> DbResult<BookingBalance> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                "SELECT booking_id, total_due, paid, outstanding, payment_status
+                 FROM booking_balances
+                 WHERE booking_id = $1",
+                &[&booking_id],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Booking with ID {} not found", booking_id)))?;
+
+        Ok(BookingBalance::from(row))
+    }
+
+    /// Paid total of several bookings (group or split stay invoiced together)
+    pub async fn get_paid_total(pool: &DbPool, booking_ids: &[i32]) -> DbResult<Money> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_one(
+                "SELECT COALESCE(SUM(amount), 0) AS paid FROM booking_payments WHERE booking_id = ANY($1)",
+                &[&booking_ids],
+            )
+            .await?;
+
+        Ok(row.get("paid"))
+    }
+
+    /// Payments in the order they were received
+    pub async fn get_for_booking(pool: &DbPool, booking_id: i32) -> DbResult<Vec<Payment>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM booking_payments WHERE booking_id = $1 ORDER BY payment_date, id",
+                    PAYMENT_COLUMNS
+                ),
+                &[&booking_id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(Payment::from).collect())
+    }
+
+    /// Deposit requests with the part covered by the paid total
+    pub async fn get_deposits(pool: &DbPool, booking_id: i32) -> DbResult<Vec<DepositRequest>> {
+        let balance = Self::get_balance(pool, booking_id).await?;
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM deposit_requests WHERE booking_id = $1 ORDER BY due_date, id",
+                    DEPOSIT_COLUMNS
+                ),
+                &[&booking_id],
+            )
+            .await?;
+
+        let mut deposits: Vec<DepositRequest> = rows.into_iter().map(DepositRequest::from).collect();
+        Self::apply_coverage(&mut deposits, balance.paid);
+        Ok(deposits)
+    }
+
+    /// Balance, payments and deposit requests of a booking
+    pub async fn get_overview(pool: &DbPool, booking_id: i32) -> DbResult<BookingPayments> {
+        Ok(BookingPayments {
+            balance: Self::get_balance(pool, booking_id).await?,
+            payments: Self::get_for_booking(pool, booking_id).await?,
+            deposits: Self::get_deposits(pool, booking_id).await?,
+        })
+    }
+
+    /// Record a payment (negative amount = refund)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &DbPool,
+        booking_id: i32,
+        amount: Money,
+        payment_date: String,
+        method: Option<String>,
+        reference: Option<String>,
+        notes: Option<String>,
+        recorded_by: Option<String>,
+    ) -> DbResult<Payment> {
+        if amount.is_zero() {
+            return Err(DbError::ValidationError("Der Betrag darf nicht 0 sein".to_string()));
+        }
+        chrono::NaiveDate::parse_from_str(&payment_date, "%Y-%m-%d")
+            .map_err(|_| DbError::ValidationError(format!("Ungültiges Zahlungsdatum: {}", payment_date)))?;
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let row = transaction
+            .query_one(
+                &format!(
+                    "INSERT INTO booking_payments (booking_id, amount, payment_date, method, reference, notes, recorded_by)
+                     VALUES ($1, $2, $3::text::date, $4, $5, $6, $7)
+                     RETURNING {}",
+                    PAYMENT_COLUMNS
+                ),
+                &[&booking_id, &amount, &payment_date, &method, &reference, &notes, &recorded_by],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+
+        Self::sync_paid_flag(&transaction, booking_id).await?;
+        transaction.commit().await?;
+
+        Ok(Payment::from(row))
+    }
+
+    /// Delete a payment (booking in the wrong ledger, typo). Returns the deleted payment.
+    pub async fn delete(pool: &DbPool, id: i32) -> DbResult<Payment> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        Self::reopen_bank_transaction(&transaction, id).await?;
+
+        let row = transaction
+            .query_opt(
+                &format!("DELETE FROM booking_payments WHERE id = $1 RETURNING {}", PAYMENT_COLUMNS),
+                &[&id],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Payment with ID {} not found", id)))?;
+        let payment = Payment::from(row);
+
+        Self::sync_paid_flag(&transaction, payment.booking_id).await?;
+        transaction.commit().await?;
+
+        Ok(payment)
+    }
+
+    /// "Als bezahlt markieren": record the outstanding amount as one payment.
+    /// Does nothing if nothing is outstanding.
+    pub async fn mark_paid(
+        pool: &DbPool,
+        booking_id: i32,
+        payment_date: Option<String>,
+        method: Option<String>,
+        recorded_by: Option<String>,
+    ) -> DbResult<Option<Payment>> {
+        let balance = Self::get_balance(pool, booking_id).await?;
+        if !balance.outstanding.is_positive() {
+            return Ok(None);
+        }
+
+        // bezahlt_am from the frontend may be a full timestamp
+        let payment_date = payment_date
+            .and_then(|date| date.get(..10).map(str::to_string))
+            .unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+
+        Self::create(
+            pool,
+            booking_id,
+            balance.outstanding,
+            payment_date,
+            method,
+            Some("Als bezahlt markiert".to_string()),
+            None,
+            recorded_by,
+        )
+        .await
+        .map(Some)
+    }
+
+    /// "Als unbezahlt markieren": reverse the latest payments until an amount is outstanding
+    /// again, e.g. the payment added by `mark_paid`. The ledger keeps the original payment,
+    /// each reversal is a counter-entry with the negative amount. Returns the reversals.
+    pub async fn unmark_paid(pool: &DbPool, booking_id: i32) -> DbResult<Vec<Payment>> {
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let rows = transaction
+            .query(
+                &format!(
+                    "SELECT {} FROM booking_payments p
+                     WHERE booking_id = $1 AND amount > 0 AND reversal_of IS NULL
+                       AND NOT EXISTS (SELECT 1 FROM booking_payments r WHERE r.reversal_of = p.id)
+                     ORDER BY payment_date DESC, id DESC FOR UPDATE",
+                    PAYMENT_COLUMNS
+                ),
+                &[&booking_id],
+            )
+            .await?;
+
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let mut reversals = Vec::new();
+        for row in rows {
+            let outstanding: Money = transaction
+                .query_one("SELECT outstanding FROM booking_balances WHERE booking_id = $1", &[&booking_id])
+                .await?
+                .get("outstanding");
+            if outstanding.is_positive() {
+                break;
+            }
+            let payment = Payment::from(row);
+            Self::reopen_bank_transaction(&transaction, payment.id).await?;
+            let notes = format!("Storno der Zahlung vom {} (als unbezahlt markiert)", payment.payment_date);
+            let reversal = transaction
+                .query_one(
+                    &format!(
+                        "INSERT INTO booking_payments (booking_id, amount, payment_date, method, reference, notes, reversal_of)
+                         VALUES ($1, $2, $3::text::date, $4, $5, $6, $7)
+                         RETURNING {}",
+                        PAYMENT_COLUMNS
+                    ),
+                    &[&booking_id, &-payment.amount, &today, &payment.method, &payment.reference, &notes, &payment.id],
+                )
+                .await
+                .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+            reversals.push(Payment::from(reversal));
+        }
+
+        Self::sync_paid_flag(&transaction, booking_id).await?;
+        transaction.commit().await?;
+
+        Ok(reversals)
+    }
+
+    /// Request a deposit: either a fixed amount or a percentage of the amount due
+    pub async fn create_deposit(
+        pool: &DbPool,
+        booking_id: i32,
+        description: Option<String>,
+        amount: Option<Money>,
+        percentage: Option<f64>,
+        due_date: String,
+        created_by: Option<String>,
+    ) -> DbResult<DepositRequest> {
+        chrono::NaiveDate::parse_from_str(&due_date, "%Y-%m-%d")
+            .map_err(|_| DbError::ValidationError(format!("Ungültiges Fälligkeitsdatum: {}", due_date)))?;
+
+        let amount = match (amount, percentage) {
+            (Some(amount), _) => amount,
+            (None, Some(percentage)) => {
+                let balance = Self::get_balance(pool, booking_id).await?;
+                payments::deposit_amount(balance.total_due, percentage).map_err(DbError::ValidationError)?
+            }
+            (None, None) => {
+                return Err(DbError::ValidationError("Bitte Betrag oder Prozentsatz der Anzahlung angeben".to_string()))
+            }
+        };
+        if !amount.is_positive() {
+            return Err(DbError::ValidationError("Der Betrag der Anzahlung muss größer als 0 sein".to_string()));
+        }
+        let description = description
+            .filter(|d| !d.trim().is_empty())
+            .unwrap_or_else(|| "Anzahlung".to_string());
+
+        let client = pool.get().await?;
+        let row = client
+            .query_one(
+                &format!(
+                    "INSERT INTO deposit_requests (booking_id, description, amount, percentage, due_date, created_by)
+                     VALUES ($1, $2, $3, $4::double precision, $5::text::date, $6)
+                     RETURNING {}",
+                    DEPOSIT_COLUMNS
+                ),
+                &[&booking_id, &description, &amount, &percentage, &due_date, &created_by],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+        let id: i32 = row.get("id");
+
+        Self::get_deposits(pool, booking_id)
+            .await?
+            .into_iter()
+            .find(|d| d.id == id)
+            .ok_or_else(|| DbError::NotFound(format!("Deposit request with ID {} not found", id)))
+    }
+
+    /// Delete a deposit request (payments stay in the ledger)
+    pub async fn delete_deposit(pool: &DbPool, id: i32) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let rows_affected = client.execute("DELETE FROM deposit_requests WHERE id = $1", &[&id]).await?;
+        if rows_affected == 0 {
+            return Err(DbError::NotFound(format!("Deposit request with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Bookings (not cancelled) with at least one overdue deposit request
+    pub async fn get_bookings_with_overdue_deposits(pool: &DbPool) -> DbResult<Vec<i32>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT DISTINCT d.booking_id
+                 FROM deposit_requests d
+                 JOIN booking_balances bb ON bb.booking_id = d.booking_id
+                 JOIN bookings b ON b.id = d.booking_id
+                 WHERE d.due_date < CURRENT_DATE
+                   AND bb.outstanding > 0
+                   AND b.status <> 'storniert'",
+                &[],
+            )
+            .await?;
+
+        let mut booking_ids = Vec::new();
+        for row in rows {
+            let booking_id: i32 = row.get("booking_id");
+            let deposits = Self::get_deposits(pool, booking_id).await?;
+            if deposits.iter().any(|d| d.state == payments::DepositState::Ueberfaellig.as_str()) {
+                booking_ids.push(booking_id);
+            }
+        }
+
+        Ok(booking_ids)
+    }
+
+    fn apply_coverage(deposits: &mut [DepositRequest], paid: Money) {
+        let inputs: Vec<DepositInput> = deposits
+            .iter()
+            .filter_map(|d| {
+                chrono::NaiveDate::parse_from_str(&d.due_date, "%Y-%m-%d")
+                    .ok()
+                    .map(|due_date| DepositInput { id: d.id, amount: d.amount, due_date })
+            })
+            .collect();
+        let coverage = payments::cover_deposits(&inputs, paid, chrono::Local::now().date_naive());
+
+        for deposit in deposits.iter_mut() {
+            if let Some(c) = coverage.iter().find(|c| c.id == deposit.id) {
+                deposit.covered = c.covered;
+                deposit.state = c.state.as_str().to_string();
+            }
+        }
+    }
+
+    /// A bank transfer recorded as this payment has to be matched again
+    async fn reopen_bank_transaction(transaction: &Transaction<'_>, payment_id: i32) -> DbResult<()> {
+        transaction
+            .execute(
+                "UPDATE bank_transactions
+                 SET status = 'offen', booking_id = NULL, payment_id = NULL, confidence = NULL,
+                     matched_at = NULL, matched_by = NULL
+                 WHERE payment_id = $1",
+                &[&payment_id],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Keep bookings.bezahlt / bezahlt_am / zahlungsmethode in line with the ledger
+    /// (list views, TapeChart and the existing payment toggle read these columns)
+    async fn sync_paid_flag(transaction: &Transaction<'_>, booking_id: i32) -> DbResult<()> {
+        transaction
+            .execute(
+                "UPDATE bookings b
+                 SET bezahlt = bb.payment_status = 'bezahlt' AND EXISTS (SELECT 1 FROM booking_payments WHERE booking_id = b.id),
+                     bezahlt_am = (SELECT MAX(payment_date)::text FROM booking_payments WHERE booking_id = b.id),
+                     zahlungsmethode = COALESCE(
+                         (SELECT method FROM booking_payments WHERE booking_id = b.id
+                          ORDER BY payment_date DESC, id DESC LIMIT 1),
+                         b.zahlungsmethode
+                     ),
+                     updated_at = CURRENT_TIMESTAMP
+                 FROM booking_balances bb
+                 WHERE b.id = $1 AND bb.booking_id = b.id",
+                &[&booking_id],
+            )
+            .await?;
+
+        Ok(())
+    }
+}
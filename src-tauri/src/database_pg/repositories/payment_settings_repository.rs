@@ -1,4 +1,5 @@
-use crate::database_pg::{DbPool, DbResult, PaymentSettings};
+use crate::database_pg::{DbError, DbPool, DbResult, PaymentSettings};
+use crate::sepa;
 
 pub struct PaymentSettingsRepository;
 
@@ -11,7 +12,8 @@ impl PaymentSettingsRepository {
             .query_one(
                 "SELECT id, bank_name, iban, bic, account_holder, mwst_rate,
                         payment_due_days, reminder_after_days, payment_text,
-                        updated_at::text as updated_at, dpolg_rabatt
+                        updated_at::text as updated_at, dpolg_rabatt,
+                        glaeubiger_id, sepa_vorlauf_tage
                  FROM payment_settings
                  LIMIT 1",
                 &[],
@@ -30,15 +32,23 @@ impl PaymentSettingsRepository {
         pool: &DbPool,
         settings: &PaymentSettings,
     ) -> DbResult<PaymentSettings> {
+        // Empty creditor id = no direct debits
+        let glaeubiger_id = match settings.glaeubiger_id.as_deref().map(str::trim) {
+            Some("") => Some(String::new()),
+            Some(id) => Some(sepa::normalize_creditor_id(id).map_err(DbError::ValidationError)?),
+            None => None,
+        };
+
         let client = pool.get().await?;
 
         let row = client
             .query_one(
                 "INSERT INTO payment_settings (
                     id, bank_name, iban, bic, account_holder, mwst_rate,
-                    payment_due_days, reminder_after_days, payment_text, updated_at, dpolg_rabatt
+                    payment_due_days, reminder_after_days, payment_text, updated_at, dpolg_rabatt,
+                    glaeubiger_id, sepa_vorlauf_tage
                  )
-                 VALUES (1, $1, $2, $3, $4, $5, $6, $7, $8, CURRENT_TIMESTAMP, $9)
+                 VALUES (1, $1, $2, $3, $4, $5, $6, $7, $8, CURRENT_TIMESTAMP, $9, $10, COALESCE($11, 2))
                  ON CONFLICT (id) DO UPDATE SET
                     bank_name = EXCLUDED.bank_name,
                     iban = EXCLUDED.iban,
@@ -49,10 +59,13 @@ impl PaymentSettingsRepository {
                     reminder_after_days = EXCLUDED.reminder_after_days,
                     payment_text = EXCLUDED.payment_text,
                     dpolg_rabatt = EXCLUDED.dpolg_rabatt,
+                    glaeubiger_id = COALESCE($10, payment_settings.glaeubiger_id),
+                    sepa_vorlauf_tage = COALESCE($11, payment_settings.sepa_vorlauf_tage),
                     updated_at = CURRENT_TIMESTAMP
                  RETURNING id, bank_name, iban, bic, account_holder, mwst_rate,
                            payment_due_days, reminder_after_days, payment_text,
-                           updated_at::text as updated_at, dpolg_rabatt",
+                           updated_at::text as updated_at, dpolg_rabatt,
+                           glaeubiger_id, sepa_vorlauf_tage",
                 &[
                     &settings.bank_name,
                     &settings.iban,
@@ -63,6 +76,8 @@ impl PaymentSettingsRepository {
                     &settings.reminder_after_days,
                     &settings.payment_text,
                     &settings.dpolg_rabatt,
+                    &glaeubiger_id,
+                    &settings.sepa_vorlauf_tage,
                 ],
             )
             .await?;
@@ -3,6 +3,19 @@ use crate::database_pg::{DbPool, DbResult, PricingSettings};
 pub struct PricingSettingsRepository;
 
 impl PricingSettingsRepository {
+    /// Run tax category migration (idempotent)
+    pub async fn run_tax_category_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/022_tax_categories.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| crate::database_pg::DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
     /// Get pricing settings (Singleton - should only have 1 record)
     pub async fn get(pool: &DbPool) -> DbResult<PricingSettings> {
         let client = pool.get().await?;
@@ -11,6 +24,7 @@ impl PricingSettingsRepository {
             .query_one(
                 "SELECT id, hauptsaison_aktiv, hauptsaison_start, hauptsaison_ende,
                         mitglieder_rabatt_aktiv, mitglieder_rabatt_prozent, rabatt_basis,
+                        mwst_beherbergung, mwst_standard,
                         updated_at::text as updated_at
                  FROM pricing_settings
                  LIMIT 1",
@@ -36,9 +50,10 @@ impl PricingSettingsRepository {
             .query_one(
                 "INSERT INTO pricing_settings (
                     id, hauptsaison_aktiv, hauptsaison_start, hauptsaison_ende,
-                    mitglieder_rabatt_aktiv, mitglieder_rabatt_prozent, rabatt_basis, updated_at
+                    mitglieder_rabatt_aktiv, mitglieder_rabatt_prozent, rabatt_basis,
+                    mwst_beherbergung, mwst_standard, updated_at
                  )
-                 VALUES (1, $1, $2, $3, $4, $5, $6, CURRENT_TIMESTAMP)
+                 VALUES (1, $1, $2, $3, $4, $5, $6, COALESCE($7, 7.0), COALESCE($8, 19.0), CURRENT_TIMESTAMP)
                  ON CONFLICT (id) DO UPDATE SET
                     hauptsaison_aktiv = EXCLUDED.hauptsaison_aktiv,
                     hauptsaison_start = EXCLUDED.hauptsaison_start,
@@ -46,9 +61,12 @@ impl PricingSettingsRepository {
                     mitglieder_rabatt_aktiv = EXCLUDED.mitglieder_rabatt_aktiv,
                     mitglieder_rabatt_prozent = EXCLUDED.mitglieder_rabatt_prozent,
                     rabatt_basis = EXCLUDED.rabatt_basis,
+                    mwst_beherbergung = COALESCE($7, pricing_settings.mwst_beherbergung),
+                    mwst_standard = COALESCE($8, pricing_settings.mwst_standard),
                     updated_at = CURRENT_TIMESTAMP
                  RETURNING id, hauptsaison_aktiv, hauptsaison_start, hauptsaison_ende,
                            mitglieder_rabatt_aktiv, mitglieder_rabatt_prozent, rabatt_basis,
+                           mwst_beherbergung, mwst_standard,
                            updated_at::text as updated_at",
                 &[
                     &settings.hauptsaison_aktiv,
@@ -57,6 +75,8 @@ impl PricingSettingsRepository {
                     &settings.mitglieder_rabatt_aktiv,
                     &settings.mitglieder_rabatt_prozent,
                     &settings.rabatt_basis,
+                    &settings.mwst_beherbergung,
+                    &settings.mwst_standard,
                 ],
             )
             .await?;
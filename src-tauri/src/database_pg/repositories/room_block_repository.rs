@@ -0,0 +1,282 @@
+use crate::database_pg::repositories::AllotmentRepository;
+use crate::database_pg::{DbError, DbPool, DbResult, RoomBlock};
+use std::collections::HashMap;
+use tokio_postgres::GenericClient;
+
+const ROOM_BLOCK_COLUMNS: &str = "rb.id, rb.room_id, r.name AS room_name,
+    rb.start_date::text as start_date, rb.end_date::text as end_date, rb.reason, rb.bemerkungen,
+    rb.created_at::text as created_at, rb.updated_at::text as updated_at, rb.created_by";
+
+/// Repository for room blocks (rooms out of service for maintenance or renovation)
+pub struct RoomBlockRepository;
+
+impl RoomBlockRepository {
+    /// Run room blocks migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/030_room_blocks.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Blocks overlapping [from, to) - all blocks without range (TapeChart, room list)
+    pub async fn get_all(pool: &DbPool, from: Option<String>, to: Option<String>) -> DbResult<Vec<RoomBlock>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM room_blocks rb JOIN rooms r ON r.id = rb.room_id
+                     WHERE ($1::text IS NULL OR rb.end_date > $1::text::date)
+                       AND ($2::text IS NULL OR rb.start_date < $2::text::date)
+                     ORDER BY rb.start_date, r.name",
+                    ROOM_BLOCK_COLUMNS
+                ),
+                &[&from, &to],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(RoomBlock::from).collect())
+    }
+
+    /// Get block by ID
+    pub async fn get_by_id(pool: &DbPool, id: i32) -> DbResult<RoomBlock> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM room_blocks rb JOIN rooms r ON r.id = rb.room_id WHERE rb.id = $1",
+                    ROOM_BLOCK_COLUMNS
+                ),
+                &[&id],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Room block with ID {} not found", id)))?;
+
+        Ok(RoomBlock::from(row))
+    }
+
+    /// Block a room. It must be free: no booking in the range and no allotment relying on it.
+    pub async fn create(
+        pool: &DbPool,
+        room_id: i32,
+        start_date: String,
+        end_date: String,
+        reason: String,
+        bemerkungen: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<RoomBlock> {
+        Self::validate(&start_date, &end_date, &reason)?;
+
+        let mut client = pool.get().await?;
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::Serializable)
+            .start()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction start failed: {}", e)))?;
+
+        Self::check_room_free(&transaction, room_id, &start_date, &end_date, None).await?;
+
+        let id: i32 = transaction
+            .query_one(
+                "INSERT INTO room_blocks (room_id, start_date, end_date, reason, bemerkungen, created_by)
+                 VALUES ($1, $2::text::date, $3::text::date, $4, $5, $6)
+                 RETURNING id",
+                &[&room_id, &start_date, &end_date, &reason, &bemerkungen, &created_by],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?
+            .get("id");
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction commit failed: {}", e)))?;
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Change room, range or reason of a block (same checks as `create`)
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &DbPool,
+        id: i32,
+        room_id: i32,
+        start_date: String,
+        end_date: String,
+        reason: String,
+        bemerkungen: Option<String>,
+    ) -> DbResult<RoomBlock> {
+        Self::validate(&start_date, &end_date, &reason)?;
+
+        let mut client = pool.get().await?;
+        let transaction = client
+            .build_transaction()
+            .isolation_level(tokio_postgres::IsolationLevel::Serializable)
+            .start()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction start failed: {}", e)))?;
+
+        Self::check_room_free(&transaction, room_id, &start_date, &end_date, Some(id)).await?;
+
+        let updated = transaction
+            .execute(
+                "UPDATE room_blocks
+                 SET room_id = $2, start_date = $3::text::date, end_date = $4::text::date,
+                     reason = $5, bemerkungen = $6, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1",
+                &[&id, &room_id, &start_date, &end_date, &reason, &bemerkungen],
+            )
+            .await?;
+        if updated == 0 {
+            return Err(DbError::NotFound(format!("Room block with ID {} not found", id)));
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DbError::QueryError(format!("Transaction commit failed: {}", e)))?;
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Delete a block (room is bookable again)
+    pub async fn delete(pool: &DbPool, id: i32) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let deleted = client.execute("DELETE FROM room_blocks WHERE id = $1", &[&id]).await?;
+        if deleted == 0 {
+            return Err(DbError::NotFound(format!("Room block with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Blocks of `room_id` overlapping a stay [checkin, checkout), formatted for error messages
+    pub async fn overlapping<C: GenericClient>(
+        client: &C,
+        room_id: i32,
+        checkin_date: &str,
+        checkout_date: &str,
+    ) -> DbResult<Vec<String>> {
+        let rows = client
+            .query(
+                "SELECT reason, TO_CHAR(start_date, 'DD.MM.YYYY') AS von, TO_CHAR(end_date, 'DD.MM.YYYY') AS bis
+                 FROM room_blocks
+                 WHERE room_id = $1
+                   AND start_date < $3::text::date
+                   AND end_date > $2::text::date
+                 ORDER BY start_date",
+                &[&room_id, &checkin_date, &checkout_date],
+            )
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                format!(
+                    "{} ({} - {})",
+                    row.get::<_, String>("reason"),
+                    row.get::<_, String>("von"),
+                    row.get::<_, String>("bis")
+                )
+            })
+            .collect())
+    }
+
+    /// Blocked nights per room inside [from, to) - taken out of the available nights in reports
+    pub async fn blocked_nights_by_room(pool: &DbPool, from: &str, to: &str) -> DbResult<HashMap<i32, i64>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT room_id,
+                        SUM(LEAST(end_date, $2::text::date) - GREATEST(start_date, $1::text::date))::bigint AS nights
+                 FROM room_blocks
+                 WHERE start_date < $2::text::date
+                   AND end_date > $1::text::date
+                 GROUP BY room_id",
+                &[&from, &to],
+            )
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get::<_, i32>("room_id"), row.get::<_, i64>("nights")))
+            .collect())
+    }
+
+    /// No booking, no allotment and no other block may use the room in the blocked range
+    async fn check_room_free(
+        transaction: &tokio_postgres::Transaction<'_>,
+        room_id: i32,
+        start_date: &str,
+        end_date: &str,
+        exclude_block_id: Option<i32>,
+    ) -> DbResult<()> {
+        let overlapping = transaction
+            .query_opt(
+                "SELECT reason FROM room_blocks
+                 WHERE room_id = $1
+                   AND start_date < $3::text::date
+                   AND end_date > $2::text::date
+                   AND id IS DISTINCT FROM $4
+                 LIMIT 1",
+                &[&room_id, &start_date, &end_date, &exclude_block_id],
+            )
+            .await?;
+        if let Some(row) = overlapping {
+            return Err(DbError::ConflictError(format!(
+                "Das Zimmer ist in diesem Zeitraum bereits gesperrt ({})",
+                row.get::<_, String>("reason")
+            )));
+        }
+
+        let rows = transaction
+            .query(
+                "SELECT reservierungsnummer FROM bookings
+                 WHERE room_id = $1
+                   AND checkin_date < $3
+                   AND checkout_date > $2
+                   AND status != 'storniert'
+                 ORDER BY checkin_date
+                 FOR UPDATE",
+                &[&room_id, &start_date, &end_date],
+            )
+            .await?;
+        if !rows.is_empty() {
+            let bookings: Vec<String> = rows.iter().map(|row| row.get("reservierungsnummer")).collect();
+            return Err(DbError::ConflictError(format!(
+                "Das Zimmer ist in diesem Zeitraum gebucht ({}) - bitte die Buchungen zuerst umbuchen",
+                bookings.join(", ")
+            )));
+        }
+
+        let held = AllotmentRepository::held_conflicts(transaction, room_id, start_date, end_date, None, None).await?;
+        if !held.is_empty() {
+            return Err(DbError::ConflictError(format!(
+                "Das Zimmer wird für ein Kontingent benötigt: {}",
+                held.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate(start_date: &str, end_date: &str, reason: &str) -> DbResult<()> {
+        if end_date <= start_date {
+            return Err(DbError::ValidationError("Das Ende der Sperrung muss nach dem Beginn liegen".to_string()));
+        }
+        if reason.trim().is_empty() {
+            return Err(DbError::ValidationError("Bitte einen Grund für die Sperrung angeben".to_string()));
+        }
+        Ok(())
+    }
+}
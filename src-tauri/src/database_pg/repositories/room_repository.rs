@@ -1,4 +1,6 @@
-use crate::database_pg::{DbPool, DbError, DbResult, models::Room};
+use crate::database_pg::repositories::AllotmentRepository;
+use crate::database_pg::{DbPool, DbError, DbResult, models::{FreeRoom, Room, RoomChildPrice}};
+use crate::money::Money;
 
 /// Repository for Room operations (Best Practice 2025)
 ///
@@ -17,8 +19,8 @@ impl RoomRepository {
         let rows = client
             .query(
                 "SELECT id, name, gebaeude_typ, capacity,
-                        nebensaison_preis, hauptsaison_preis, endreinigung, ort,
-                        schluesselcode, street_address, postal_code, city, notizen, created_by, updated_by
+                        nebensaison_preis, hauptsaison_preis, endreinigung, basis_belegung, aufpreis_erwachsener, steuerkategorie, ort,
+                        schluesselcode, street_address, postal_code, city, notizen, hunde_erlaubt, erdgeschoss, created_by, updated_by
                  FROM rooms
                  ORDER BY name",
                 &[],
@@ -37,8 +39,8 @@ impl RoomRepository {
         let row = client
             .query_one(
                 "SELECT id, name, gebaeude_typ, capacity,
-                        nebensaison_preis, hauptsaison_preis, endreinigung, ort,
-                        schluesselcode, street_address, postal_code, city, notizen, created_by, updated_by
+                        nebensaison_preis, hauptsaison_preis, endreinigung, basis_belegung, aufpreis_erwachsener, steuerkategorie, ort,
+                        schluesselcode, street_address, postal_code, city, notizen, hunde_erlaubt, erdgeschoss, created_by, updated_by
                  FROM rooms
                  WHERE id = $1",
                 &[&id],
@@ -56,20 +58,26 @@ impl RoomRepository {
     }
 
     /// Create new room
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &DbPool,
         name: String,
         gebaeude_typ: String,
         capacity: i32,
         ort: String,
-        nebensaison_preis: Option<f64>,
-        hauptsaison_preis: Option<f64>,
-        endreinigung: Option<f64>,
+        nebensaison_preis: Option<Money>,
+        hauptsaison_preis: Option<Money>,
+        endreinigung: Option<Money>,
+        basis_belegung: Option<i32>,
+        aufpreis_erwachsener: Option<Money>,
+        steuerkategorie: Option<String>,
         schluesselcode: Option<String>,
         street_address: Option<String>,
         postal_code: Option<String>,
         city: Option<String>,
         notizen: Option<String>,
+        hunde_erlaubt: Option<bool>,
+        erdgeschoss: Option<bool>,
         created_by: Option<String>,
     ) -> DbResult<Room> {
         let client = pool.get().await?;
@@ -78,12 +86,13 @@ impl RoomRepository {
             .query_one(
                 "INSERT INTO rooms (
                     name, gebaeude_typ, capacity,
-                    nebensaison_preis, hauptsaison_preis, endreinigung, ort,
-                    schluesselcode, street_address, postal_code, city, notizen, created_by, updated_by
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $13)
+                    nebensaison_preis, hauptsaison_preis, endreinigung, basis_belegung, aufpreis_erwachsener, steuerkategorie, ort,
+                    schluesselcode, street_address, postal_code, city, notizen, hunde_erlaubt, erdgeschoss, created_by, updated_by
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, COALESCE($16, 'beherbergung'), $9, $10, $11, $12, $13, $14,
+                          COALESCE($17, FALSE), COALESCE($18, FALSE), $15, $15)
                 RETURNING id, name, gebaeude_typ, capacity,
-                         nebensaison_preis, hauptsaison_preis, endreinigung, ort,
-                         schluesselcode, street_address, postal_code, city, notizen, created_by, updated_by",
+                         nebensaison_preis, hauptsaison_preis, endreinigung, basis_belegung, aufpreis_erwachsener, steuerkategorie, ort,
+                         schluesselcode, street_address, postal_code, city, notizen, hunde_erlaubt, erdgeschoss, created_by, updated_by",
                 &[
                     &name,
                     &gebaeude_typ,
@@ -91,6 +100,8 @@ impl RoomRepository {
                     &nebensaison_preis,
                     &hauptsaison_preis,
                     &endreinigung,
+                    &basis_belegung,
+                    &aufpreis_erwachsener,
                     &ort,
                     &schluesselcode,
                     &street_address,
@@ -98,6 +109,9 @@ impl RoomRepository {
                     &city,
                     &notizen,
                     &created_by,
+                    &steuerkategorie,
+                    &hunde_erlaubt,
+                    &erdgeschoss,
                 ],
             )
             .await?;
@@ -106,6 +120,7 @@ impl RoomRepository {
     }
 
     /// Update existing room
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &DbPool,
         id: i32,
@@ -113,14 +128,19 @@ impl RoomRepository {
         gebaeude_typ: String,
         capacity: i32,
         ort: String,
-        nebensaison_preis: Option<f64>,
-        hauptsaison_preis: Option<f64>,
-        endreinigung: Option<f64>,
+        nebensaison_preis: Option<Money>,
+        hauptsaison_preis: Option<Money>,
+        endreinigung: Option<Money>,
+        basis_belegung: Option<i32>,
+        aufpreis_erwachsener: Option<Money>,
+        steuerkategorie: Option<String>,
         schluesselcode: Option<String>,
         street_address: Option<String>,
         postal_code: Option<String>,
         city: Option<String>,
         notizen: Option<String>,
+        hunde_erlaubt: Option<bool>,
+        erdgeschoss: Option<bool>,
         updated_by: Option<String>,
     ) -> DbResult<Room> {
         let client = pool.get().await?;
@@ -134,17 +154,22 @@ impl RoomRepository {
                     nebensaison_preis = $5,
                     hauptsaison_preis = $6,
                     endreinigung = $7,
-                    ort = $8,
-                    schluesselcode = $9,
-                    street_address = $10,
-                    postal_code = $11,
-                    city = $12,
-                    notizen = $13,
-                    updated_by = $14
+                    basis_belegung = $8,
+                    aufpreis_erwachsener = $9,
+                    ort = $10,
+                    schluesselcode = $11,
+                    street_address = $12,
+                    postal_code = $13,
+                    city = $14,
+                    notizen = $15,
+                    updated_by = $16,
+                    steuerkategorie = COALESCE($17, steuerkategorie),
+                    hunde_erlaubt = COALESCE($18, hunde_erlaubt),
+                    erdgeschoss = COALESCE($19, erdgeschoss)
                  WHERE id = $1
                  RETURNING id, name, gebaeude_typ, capacity,
-                          nebensaison_preis, hauptsaison_preis, endreinigung, ort,
-                          schluesselcode, street_address, postal_code, city, notizen, created_by, updated_by",
+                          nebensaison_preis, hauptsaison_preis, endreinigung, basis_belegung, aufpreis_erwachsener, steuerkategorie, ort,
+                          schluesselcode, street_address, postal_code, city, notizen, hunde_erlaubt, erdgeschoss, created_by, updated_by",
                 &[
                     &id,
                     &name,
@@ -153,6 +178,8 @@ impl RoomRepository {
                     &nebensaison_preis,
                     &hauptsaison_preis,
                     &endreinigung,
+                    &basis_belegung,
+                    &aufpreis_erwachsener,
                     &ort,
                     &schluesselcode,
                     &street_address,
@@ -160,6 +187,9 @@ impl RoomRepository {
                     &city,
                     &notizen,
                     &updated_by,
+                    &steuerkategorie,
+                    &hunde_erlaubt,
+                    &erdgeschoss,
                 ],
             )
             .await
@@ -198,8 +228,8 @@ impl RoomRepository {
         let rows = client
             .query(
                 "SELECT id, name, gebaeude_typ, capacity,
-                        nebensaison_preis, hauptsaison_preis, endreinigung, ort,
-                        schluesselcode, street_address, postal_code, city, notizen, created_by, updated_by
+                        nebensaison_preis, hauptsaison_preis, endreinigung, basis_belegung, aufpreis_erwachsener, steuerkategorie, ort,
+                        schluesselcode, street_address, postal_code, city, notizen, hunde_erlaubt, erdgeschoss, created_by, updated_by
                  FROM rooms
                  WHERE name ILIKE $1 OR ort ILIKE $1 OR gebaeude_typ ILIKE $1
                  ORDER BY name",
@@ -211,4 +241,161 @@ impl RoomRepository {
 
         Ok(rooms)
     }
+
+    /// Rooms free for the whole stay [checkin, checkout): not booked, not blocked and not held
+    /// for an allotment. `exclude_booking_id` ignores the booking being moved.
+    pub async fn find_free_for_stay(
+        pool: &DbPool,
+        checkin_date: &str,
+        checkout_date: &str,
+        exclude_booking_id: Option<i32>,
+    ) -> DbResult<Vec<FreeRoom>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT r.id, r.name, r.gebaeude_typ, r.capacity,
+                        r.nebensaison_preis, r.hauptsaison_preis, r.endreinigung, r.basis_belegung, r.aufpreis_erwachsener,
+                        r.steuerkategorie, r.ort, r.schluesselcode, r.street_address, r.postal_code, r.city, r.notizen,
+                        r.hunde_erlaubt, r.erdgeschoss, r.created_by, r.updated_by,
+                        (SELECT MAX(x.until)::text FROM (
+                            SELECT b.checkout_date::date AS until FROM bookings b
+                             WHERE b.room_id = r.id AND b.status != 'storniert'
+                               AND b.checkout_date <= $1 AND b.id IS DISTINCT FROM $3
+                            UNION ALL
+                            SELECT rb.end_date FROM room_blocks rb
+                             WHERE rb.room_id = r.id AND rb.end_date <= $1::date
+                        ) x) AS previous_checkout,
+                        (SELECT MIN(x.since)::text FROM (
+                            SELECT b.checkin_date::date AS since FROM bookings b
+                             WHERE b.room_id = r.id AND b.status != 'storniert'
+                               AND b.checkin_date >= $2 AND b.id IS DISTINCT FROM $3
+                            UNION ALL
+                            SELECT rb.start_date FROM room_blocks rb
+                             WHERE rb.room_id = r.id AND rb.start_date >= $2::date
+                        ) x) AS next_checkin
+                 FROM rooms r
+                 WHERE NOT EXISTS (
+                         SELECT 1 FROM bookings b
+                          WHERE b.room_id = r.id AND b.status != 'storniert'
+                            AND b.checkin_date < $2 AND b.checkout_date > $1
+                            AND b.id IS DISTINCT FROM $3)
+                   AND NOT EXISTS (
+                         SELECT 1 FROM room_blocks rb
+                          WHERE rb.room_id = r.id
+                            AND rb.start_date < $2::date AND rb.end_date > $1::date)
+                 ORDER BY r.name",
+                &[&checkin_date, &checkout_date, &exclude_booking_id],
+            )
+            .await?;
+
+        let mut free = Vec::with_capacity(rows.len());
+        for row in rows {
+            let previous_checkout: Option<String> = row.get("previous_checkout");
+            let next_checkin: Option<String> = row.get("next_checkin");
+            let room = Room::from(row);
+
+            let held = AllotmentRepository::held_conflicts(&**client, room.id, checkin_date, checkout_date, exclude_booking_id, None).await?;
+            if held.is_empty() {
+                free.push(FreeRoom { room, previous_checkout, next_checkin });
+            }
+        }
+
+        Ok(free)
+    }
+
+    /// Run occupancy pricing migration (idempotent)
+    pub async fn run_occupancy_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/020_occupancy_pricing.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Run room features / room assignment migration (idempotent)
+    pub async fn run_assignment_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/031_room_assignment.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get child price bands of a room
+    pub async fn get_child_prices(pool: &DbPool, room_id: i32) -> DbResult<Vec<RoomChildPrice>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT id, room_id, alter_von, alter_bis, preis_pro_nacht
+                 FROM room_child_prices
+                 WHERE room_id = $1
+                 ORDER BY alter_von",
+                &[&room_id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(RoomChildPrice::from).collect())
+    }
+
+    /// Replace all child price bands of a room (one transaction)
+    pub async fn set_child_prices(
+        pool: &DbPool,
+        room_id: i32,
+        bands: &[RoomChildPrice],
+    ) -> DbResult<Vec<RoomChildPrice>> {
+        for band in bands {
+            if band.alter_von < 0 || band.alter_bis < band.alter_von {
+                return Err(DbError::ValidationError(format!(
+                    "Ungültige Altersgruppe: {} bis {} Jahre", band.alter_von, band.alter_bis
+                )));
+            }
+            if band.preis_pro_nacht.is_negative() {
+                return Err(DbError::ValidationError(
+                    "Kinderpreis darf nicht negativ sein".to_string()
+                ));
+            }
+        }
+        let overlaps = bands.iter().enumerate().any(|(i, a)| {
+            bands[i + 1..].iter().any(|b| a.alter_von <= b.alter_bis && b.alter_von <= a.alter_bis)
+        });
+        if overlaps {
+            return Err(DbError::ValidationError(
+                "Altersgruppen dürfen sich nicht überschneiden".to_string()
+            ));
+        }
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        transaction
+            .execute("DELETE FROM room_child_prices WHERE room_id = $1", &[&room_id])
+            .await?;
+
+        let mut saved = Vec::with_capacity(bands.len());
+        for band in bands {
+            let row = transaction
+                .query_one(
+                    "INSERT INTO room_child_prices (room_id, alter_von, alter_bis, preis_pro_nacht)
+                     VALUES ($1, $2, $3, $4)
+                     RETURNING id, room_id, alter_von, alter_bis, preis_pro_nacht",
+                    &[&room_id, &band.alter_von, &band.alter_bis, &band.preis_pro_nacht],
+                )
+                .await?;
+            saved.push(RoomChildPrice::from(row));
+        }
+
+        transaction.commit().await?;
+
+        Ok(saved)
+    }
 }
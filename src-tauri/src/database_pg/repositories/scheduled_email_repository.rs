@@ -136,6 +136,80 @@ impl ScheduledEmailRepository {
         Ok(())
     }
 
+    /// Queue an email for a booking right away, unless the same template is already pending
+    pub async fn schedule_now_once(
+        pool: &DbPool,
+        booking_id: i32,
+        guest_id: i32,
+        template_name: &str,
+        recipient_email: &str,
+        subject: &str,
+    ) -> DbResult<bool> {
+        let client = pool.get().await?;
+
+        let rows_affected = client
+            .execute(
+                "INSERT INTO scheduled_emails (booking_id, guest_id, template_name, recipient_email, subject, scheduled_for, status)
+                 VALUES ($1, $2, $3, $4, $5, NOW(), 'pending')
+                 ON CONFLICT (booking_id, template_name, status) DO NOTHING",
+                &[&booking_id, &guest_id, &template_name, &recipient_email, &subject],
+            )
+            .await?;
+
+        Ok(rows_affected > 0)
+    }
+
+    /// Queue an email to a guest that belongs to no booking (e.g. waitlist offer), returns its ID
+    pub async fn schedule_now_for_guest(
+        pool: &DbPool,
+        guest_id: i32,
+        template_name: &str,
+        recipient_email: &str,
+        subject: &str,
+    ) -> DbResult<i32> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_one(
+                "INSERT INTO scheduled_emails (booking_id, guest_id, template_name, recipient_email, subject, scheduled_for, status)
+                 VALUES (NULL, $1, $2, $3, $4, NOW(), 'pending')
+                 RETURNING id",
+                &[&guest_id, &template_name, &recipient_email, &subject],
+            )
+            .await?;
+
+        Ok(row.get("id"))
+    }
+
+    /// Cancel all pending emails of a booking, returns the number of cancelled emails
+    pub async fn cancel_pending_for_booking(pool: &DbPool, booking_id: i32) -> DbResult<u64> {
+        let client = pool.get().await?;
+
+        let rows_affected = client
+            .execute(
+                "UPDATE scheduled_emails SET status = 'cancelled'
+                 WHERE booking_id = $1 AND status = 'pending'
+                   AND NOT EXISTS (
+                       SELECT 1 FROM scheduled_emails c
+                       WHERE c.booking_id = scheduled_emails.booking_id
+                         AND c.template_name = scheduled_emails.template_name
+                         AND c.status = 'cancelled'
+                   )",
+                &[&booking_id],
+            )
+            .await?;
+
+        // Pending duplicates of already cancelled templates (UNIQUE booking/template/status)
+        client
+            .execute(
+                "DELETE FROM scheduled_emails WHERE booking_id = $1 AND status = 'pending'",
+                &[&booking_id],
+            )
+            .await?;
+
+        Ok(rows_affected)
+    }
+
     /// Create scheduled email
     pub async fn create(
         pool: &DbPool,
@@ -0,0 +1,300 @@
+use crate::database_pg::{DbPool, DbError, DbResult, RoomSeasonPrice, RoomSeasonRate, Season};
+use crate::money::Money;
+use chrono::{Datelike, NaiveDate};
+
+const SEASON_COLUMNS: &str = "id, name, year, start_date::text as start_date, end_date::text as end_date,
+                              priority, color, is_active, created_at::text as created_at";
+
+/// Repository for the season price calendar (seasons + per-room season prices)
+pub struct SeasonRepository;
+
+impl SeasonRepository {
+    /// Run season calendar migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/019_season_price_calendar.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get all seasons, optionally only those of one year
+    pub async fn get_all(pool: &DbPool, year: Option<i32>) -> DbResult<Vec<Season>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM seasons
+                     WHERE ($1::int IS NULL OR year = $1)
+                     ORDER BY start_date, priority DESC",
+                    SEASON_COLUMNS
+                ),
+                &[&year],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(Season::from).collect())
+    }
+
+    /// Create new season
+    pub async fn create(
+        pool: &DbPool,
+        name: String,
+        year: i32,
+        start_date: String,
+        end_date: String,
+        priority: i32,
+        color: Option<String>,
+    ) -> DbResult<Season> {
+        validate_range(year, &start_date, &end_date)?;
+
+        let client = pool.get().await?;
+
+        let row = client
+            .query_one(
+                &format!(
+                    "INSERT INTO seasons (name, year, start_date, end_date, priority, color)
+                     VALUES ($1, $2, $3::text::date, $4::text::date, $5, $6)
+                     RETURNING {}",
+                    SEASON_COLUMNS
+                ),
+                &[&name, &year, &start_date, &end_date, &priority, &color],
+            )
+            .await
+            .map_err(map_unique_violation)?;
+
+        Ok(Season::from(row))
+    }
+
+    /// Update existing season
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &DbPool,
+        id: i32,
+        name: String,
+        year: i32,
+        start_date: String,
+        end_date: String,
+        priority: i32,
+        color: Option<String>,
+        is_active: bool,
+    ) -> DbResult<Season> {
+        validate_range(year, &start_date, &end_date)?;
+
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "UPDATE seasons SET
+                        name = $2, year = $3, start_date = $4::text::date, end_date = $5::text::date,
+                        priority = $6, color = $7, is_active = $8, updated_at = CURRENT_TIMESTAMP
+                     WHERE id = $1
+                     RETURNING {}",
+                    SEASON_COLUMNS
+                ),
+                &[&id, &name, &year, &start_date, &end_date, &priority, &color, &is_active],
+            )
+            .await
+            .map_err(map_unique_violation)?
+            .ok_or_else(|| DbError::NotFound(format!("Season with ID {} not found", id)))?;
+
+        Ok(Season::from(row))
+    }
+
+    /// Delete season (room prices are removed by CASCADE)
+    pub async fn delete(pool: &DbPool, id: i32) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let rows_affected = client
+            .execute("DELETE FROM seasons WHERE id = $1", &[&id])
+            .await?;
+
+        if rows_affected == 0 {
+            return Err(DbError::NotFound(format!("Season with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Get all room prices of one season
+    pub async fn get_room_prices(pool: &DbPool, season_id: i32) -> DbResult<Vec<RoomSeasonPrice>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT id, room_id, season_id, price_per_night
+                 FROM room_season_prices
+                 WHERE season_id = $1
+                 ORDER BY room_id",
+                &[&season_id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(RoomSeasonPrice::from).collect())
+    }
+
+    /// Set the nightly price of a room for a season (UPSERT)
+    pub async fn set_room_price(
+        pool: &DbPool,
+        room_id: i32,
+        season_id: i32,
+        price_per_night: Money,
+    ) -> DbResult<RoomSeasonPrice> {
+        if price_per_night.is_negative() {
+            return Err(DbError::ValidationError(
+                "Saisonpreis darf nicht negativ sein".to_string()
+            ));
+        }
+
+        let client = pool.get().await?;
+
+        let row = client
+            .query_one(
+                "INSERT INTO room_season_prices (room_id, season_id, price_per_night)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (room_id, season_id) DO UPDATE SET
+                    price_per_night = EXCLUDED.price_per_night
+                 RETURNING id, room_id, season_id, price_per_night",
+                &[&room_id, &season_id, &price_per_night],
+            )
+            .await?;
+
+        Ok(RoomSeasonPrice::from(row))
+    }
+
+    /// Remove the season price of a room (room falls back to its base prices)
+    pub async fn delete_room_price(pool: &DbPool, room_id: i32, season_id: i32) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        client
+            .execute(
+                "DELETE FROM room_season_prices WHERE room_id = $1 AND season_id = $2",
+                &[&room_id, &season_id],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Active seasons with a price for this room that overlap the stay [checkin, checkout)
+    pub async fn get_rates_for_stay(
+        pool: &DbPool,
+        room_id: i32,
+        checkin: &str,
+        checkout: &str,
+    ) -> DbResult<Vec<RoomSeasonRate>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT s.id as season_id, s.name as season_name,
+                        s.start_date::text as start_date, s.end_date::text as end_date,
+                        s.priority, p.price_per_night
+                 FROM seasons s
+                 JOIN room_season_prices p ON p.season_id = s.id
+                 WHERE p.room_id = $1
+                   AND s.is_active = TRUE
+                   AND s.start_date < $3::text::date
+                   AND s.end_date >= $2::text::date
+                 ORDER BY s.priority DESC, s.start_date",
+                &[&room_id, &checkin, &checkout],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(RoomSeasonRate::from).collect())
+    }
+
+    /// Copy all seasons of one year (incl. room prices) to another year.
+    /// Dates are shifted by whole years; prices can be raised by a percentage.
+    /// Seasons that already exist in the target year (same name) are left untouched, incl. their prices.
+    /// Movable seasons (e.g. Ostern, Pfingsten) keep the calendar dates of the source year and
+    /// have to be corrected by hand. Returns the number of copied seasons.
+    pub async fn copy_year(
+        pool: &DbPool,
+        from_year: i32,
+        to_year: i32,
+        price_increase_percent: f64,
+    ) -> DbResult<u64> {
+        if from_year == to_year {
+            return Err(DbError::ValidationError(
+                "Quell- und Zieljahr müssen unterschiedlich sein".to_string()
+            ));
+        }
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let year_offset = to_year - from_year;
+        let copied: Vec<i32> = transaction
+            .query(
+                "INSERT INTO seasons (name, year, start_date, end_date, priority, color, is_active)
+                 SELECT name, $2,
+                        (start_date + make_interval(years => $3))::date,
+                        (end_date + make_interval(years => $3))::date,
+                        priority, color, is_active
+                 FROM seasons
+                 WHERE year = $1
+                 ON CONFLICT (name, year) DO NOTHING
+                 RETURNING id",
+                &[&from_year, &to_year, &year_offset],
+            )
+            .await?
+            .iter()
+            .map(|row| row.get("id"))
+            .collect();
+
+        transaction
+            .execute(
+                "INSERT INTO room_season_prices (room_id, season_id, price_per_night)
+                 SELECT p.room_id, target.id, ROUND(p.price_per_night * (1 + $3::float8::numeric / 100), 2)
+                 FROM room_season_prices p
+                 JOIN seasons source ON source.id = p.season_id AND source.year = $1
+                 JOIN seasons target ON target.name = source.name AND target.year = $2
+                 WHERE target.id = ANY($4)
+                 ON CONFLICT (room_id, season_id) DO NOTHING",
+                &[&from_year, &to_year, &price_increase_percent, &copied],
+            )
+            .await?;
+
+        transaction.commit().await?;
+
+        Ok(copied.len() as u64)
+    }
+}
+
+/// A season belongs to the year it starts in (a winter season may end in the next year)
+fn validate_range(year: i32, start_date: &str, end_date: &str) -> DbResult<()> {
+    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
+        .map_err(|_| DbError::ValidationError(format!("Ungültiges Startdatum: {}", start_date)))?;
+    let end = NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
+        .map_err(|_| DbError::ValidationError(format!("Ungültiges Enddatum: {}", end_date)))?;
+
+    if end < start {
+        return Err(DbError::ValidationError(
+            "Saisonende muss nach dem Saisonbeginn liegen".to_string()
+        ));
+    }
+    if start.year() != year {
+        return Err(DbError::ValidationError(format!(
+            "Der Saisonbeginn {} liegt nicht im Jahr {}",
+            start.format("%d.%m.%Y"),
+            year
+        )));
+    }
+
+    Ok(())
+}
+
+fn map_unique_violation(e: tokio_postgres::Error) -> DbError {
+    if e.code() == Some(&tokio_postgres::error::SqlState::UNIQUE_VIOLATION) {
+        DbError::ConstraintViolation("Eine Saison mit diesem Namen existiert für dieses Jahr bereits".to_string())
+    } else {
+        DbError::from(e)
+    }
+}
@@ -0,0 +1,471 @@
+use chrono::NaiveDate;
+
+use crate::database_pg::{
+    DbError, DbPool, DbResult, GuestRepository, PaymentRepository, PaymentSettingsRepository, SepaBatch,
+    SepaCollection, SepaDueDebit,
+};
+use crate::money::Money;
+use crate::payments::DepositInput;
+use crate::sepa::{self, Creditor, DirectDebit, Scheme, SequenceType};
+
+const BATCH_COLUMNS: &str = "id, message_id, collection_date::text as collection_date, transaction_count, total,
+    file_path, created_at::text as created_at, created_by";
+
+const COLLECTION_SELECT: &str = "SELECT c.id, c.batch_id, c.booking_id, b.reservierungsnummer, c.guest_id,
+           TRIM(g.vorname || ' ' || g.nachname) AS guest_name, c.amount, c.mandate_reference,
+           c.sequence_type, c.end_to_end_id, c.collection_date::text as collection_date, c.status,
+           c.payment_id, c.return_reason, c.returned_at::text as returned_at
+    FROM sepa_collections c
+    JOIN bookings b ON b.id = c.booking_id
+    JOIN guests g ON g.id = c.guest_id";
+
+/// Booking with mandate data, before the amount and sequence type are decided
+struct DueCandidate {
+    debit: SepaDueDebit,
+    mandate_signed: NaiveDate,
+    scheme: Scheme,
+    sequence: Option<SequenceType>,
+    debtor_name: String,
+    debtor_iban: String,
+    debtor_bic: Option<String>,
+}
+
+/// Repository for SEPA direct debits: due bookings, exported batches, collections and returns
+pub struct SepaRepository;
+
+impl SepaRepository {
+    /// Run SEPA direct debit migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/037_sepa_direct_debit.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Bookings of guests with a mandate that have an amount due on `collection_date`.
+    /// Bookings billed to a payment recipient and bookings with a pending debit are left out.
+    pub async fn get_due(pool: &DbPool, collection_date: NaiveDate) -> DbResult<Vec<SepaDueDebit>> {
+        Ok(Self::get_due_candidates(pool, collection_date)
+            .await?
+            .into_iter()
+            .map(|candidate| candidate.debit)
+            .collect())
+    }
+
+    async fn get_due_candidates(pool: &DbPool, collection_date: NaiveDate) -> DbResult<Vec<DueCandidate>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                "SELECT b.id, b.reservierungsnummer, b.checkout_date::text AS checkout_date, b.guest_id,
+                        TRIM(g.vorname || ' ' || g.nachname) AS guest_name, bb.outstanding, bb.paid,
+                        g.sepa_mandatsreferenz, g.sepa_mandat_datum::text AS sepa_mandat_datum,
+                        g.sepa_iban, g.sepa_bic, g.sepa_kontoinhaber, g.sepa_mandat_typ, g.sepa_einmalig,
+                        g.sepa_letzte_lastschrift::text AS sepa_letzte_lastschrift,
+                        ARRAY(SELECT d.id FROM deposit_requests d WHERE d.booking_id = b.id ORDER BY d.id) AS deposit_ids,
+                        ARRAY(SELECT d.amount FROM deposit_requests d WHERE d.booking_id = b.id ORDER BY d.id) AS deposit_amounts,
+                        ARRAY(SELECT d.due_date::text FROM deposit_requests d WHERE d.booking_id = b.id ORDER BY d.id) AS deposit_due_dates
+                 FROM bookings b
+                 JOIN booking_balances bb ON bb.booking_id = b.id
+                 JOIN guests g ON g.id = b.guest_id
+                 WHERE b.status NOT IN ('storniert', 'anfrage', 'optioniert')
+                   AND bb.outstanding > 0
+                   AND g.sepa_mandatsreferenz IS NOT NULL
+                   AND b.payment_recipient_id IS NULL
+                   AND NOT EXISTS (SELECT 1 FROM sepa_collections c
+                                   WHERE c.booking_id = b.id AND c.status = 'ausstehend')
+                 ORDER BY b.checkout_date, b.id",
+                &[],
+            )
+            .await?;
+
+        let parse_date = |value: &str| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| DbError::Other(format!("Invalid date {}: {}", value, e)))
+        };
+
+        let mut candidates = Vec::new();
+        // Debits on the same mandate within one file: only the first one is FRST / OOFF
+        let mut used_mandates: Vec<String> = Vec::new();
+        for row in rows {
+            let checkout_date: String = row.get("checkout_date");
+            let deposit_ids: Vec<i32> = row.get("deposit_ids");
+            let deposit_amounts: Vec<Money> = row.get("deposit_amounts");
+            let deposit_due_dates: Vec<String> = row.get("deposit_due_dates");
+            let deposits = deposit_ids
+                .into_iter()
+                .zip(deposit_amounts)
+                .zip(deposit_due_dates)
+                .map(|((id, amount), due_date)| Ok(DepositInput { id, amount, due_date: parse_date(&due_date)? }))
+                .collect::<DbResult<Vec<_>>>()?;
+
+            let amount = sepa::collectible_amount(
+                row.get("outstanding"),
+                row.get("paid"),
+                parse_date(&checkout_date)?,
+                &deposits,
+                collection_date,
+            );
+            if !amount.is_positive() {
+                continue;
+            }
+
+            let mandate_reference: String = row.get("sepa_mandatsreferenz");
+            let mandate_signed = row.get::<_, Option<String>>("sepa_mandat_datum").map(|d| parse_date(&d)).transpose()?;
+            let one_off = row.get::<_, Option<bool>>("sepa_einmalig").unwrap_or(false);
+            let last_collection = if used_mandates.contains(&mandate_reference) {
+                Some(collection_date)
+            } else {
+                row.get::<_, Option<String>>("sepa_letzte_lastschrift").map(|d| parse_date(&d)).transpose()?
+            };
+            let scheme = Scheme::parse(row.get::<_, Option<String>>("sepa_mandat_typ").as_deref().unwrap_or("CORE"));
+
+            let sequence = match (mandate_signed, &scheme) {
+                (None, _) => Err("Unterschriftsdatum des Mandats fehlt".to_string()),
+                (_, Err(e)) => Err(e.clone()),
+                (Some(signed), Ok(_)) => sepa::sequence_type(signed, one_off, last_collection, collection_date),
+            };
+            let debtor_iban: Option<String> = row.get("sepa_iban");
+            let sequence = sequence.and_then(|sequence| match &debtor_iban {
+                Some(iban) => sepa::normalize_iban(iban).map(|_| sequence),
+                None => Err("IBAN des Mandats fehlt".to_string()),
+            });
+            if sequence.is_ok() {
+                used_mandates.push(mandate_reference.clone());
+            }
+
+            let guest_name: String = row.get("guest_name");
+            candidates.push(DueCandidate {
+                debit: SepaDueDebit {
+                    booking_id: row.get("id"),
+                    reservierungsnummer: row.get("reservierungsnummer"),
+                    guest_id: row.get("guest_id"),
+                    guest_name: guest_name.clone(),
+                    checkout_date,
+                    amount,
+                    mandate_reference,
+                    sequence_type: sequence.as_ref().ok().map(|s| s.as_str().to_string()),
+                    problem: sequence.as_ref().err().cloned(),
+                },
+                mandate_signed: mandate_signed.unwrap_or(collection_date),
+                scheme: scheme.unwrap_or(Scheme::Core),
+                sequence: sequence.as_ref().ok().copied(),
+                debtor_name: row.get::<_, Option<String>>("sepa_kontoinhaber").unwrap_or(guest_name),
+                debtor_iban: debtor_iban.unwrap_or_default(),
+                debtor_bic: row.get("sepa_bic"),
+            });
+        }
+
+        Ok(candidates)
+    }
+
+    /// Create the pain.008 batch for all due bookings without problems. The debits stay
+    /// 'ausstehend' until the collection date (book_due_collections).
+    pub async fn create_batch(
+        pool: &DbPool,
+        collection_date: NaiveDate,
+        created_by: Option<String>,
+    ) -> DbResult<(SepaBatch, String)> {
+        let settings = PaymentSettingsRepository::get(pool).await?;
+        let validation = |message: &str| DbError::ValidationError(message.to_string());
+        let creditor = Creditor {
+            name: settings
+                .account_holder
+                .clone()
+                .filter(|name| !name.trim().is_empty())
+                .ok_or_else(|| validation("Kein Kontoinhaber in den Zahlungseinstellungen hinterlegt"))?,
+            iban: settings
+                .iban
+                .clone()
+                .filter(|iban| !iban.trim().is_empty())
+                .ok_or_else(|| validation("Keine IBAN in den Zahlungseinstellungen hinterlegt"))?,
+            bic: settings.bic.clone(),
+            creditor_id: settings
+                .glaeubiger_id
+                .clone()
+                .filter(|id| !id.is_empty())
+                .ok_or_else(|| validation("Keine Gläubiger-ID in den Zahlungseinstellungen hinterlegt"))?,
+        };
+
+        let now = chrono::Local::now().naive_local();
+        let earliest = sepa::earliest_collection_date(now.date(), settings.sepa_vorlauf_tage.unwrap_or(2).max(1) as u32);
+        if collection_date < earliest || !sepa::is_business_day(collection_date) {
+            return Err(DbError::ValidationError(format!(
+                "Das Einzugsdatum muss ein Bankarbeitstag ab dem {} sein",
+                earliest.format("%d.%m.%Y")
+            )));
+        }
+
+        let message_id = format!("SEPA{}", now.format("%Y%m%d%H%M%S"));
+        // Bookings with a problem (e.g. expired mandate) have no sequence type and are left out
+        let candidates: Vec<(DueCandidate, SequenceType)> = Self::get_due_candidates(pool, collection_date)
+            .await?
+            .into_iter()
+            .filter_map(|candidate| candidate.sequence.map(|sequence| (candidate, sequence)))
+            .collect();
+
+        let debits: Vec<DirectDebit> = candidates
+            .iter()
+            .map(|(candidate, sequence)| {
+                let debit = &candidate.debit;
+                DirectDebit {
+                    end_to_end_id: format!(
+                        "{}-{}",
+                        sepa::sepa_text(&debit.reservierungsnummer, 20).replace(' ', ""),
+                        now.format("%y%m%d%H%M%S")
+                    ),
+                    amount: debit.amount,
+                    mandate_reference: debit.mandate_reference.clone(),
+                    mandate_signed: candidate.mandate_signed,
+                    scheme: candidate.scheme,
+                    sequence: *sequence,
+                    debtor_name: candidate.debtor_name.clone(),
+                    debtor_iban: candidate.debtor_iban.clone(),
+                    debtor_bic: candidate.debtor_bic.clone(),
+                    remittance: format!("Reservierung {} {}", debit.reservierungsnummer, debit.guest_name),
+                }
+            })
+            .collect();
+
+        let xml = sepa::generate_pain008(&message_id, now, collection_date, &creditor, &debits)
+            .map_err(DbError::ValidationError)?;
+        let total: Money = debits.iter().map(|d| d.amount).sum();
+        let date = collection_date.format("%Y-%m-%d").to_string();
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        let row = transaction
+            .query_one(
+                &format!(
+                    "INSERT INTO sepa_batches (message_id, collection_date, transaction_count, total, xml, created_by)
+                     VALUES ($1, $2::text::date, $3, $4, $5, $6)
+                     RETURNING {}",
+                    BATCH_COLUMNS
+                ),
+                &[&message_id, &date, &(debits.len() as i32), &total, &xml, &created_by],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+        let batch = SepaBatch::from(row);
+
+        for ((candidate, _), debit) in candidates.iter().zip(&debits) {
+            transaction
+                .execute(
+                    "INSERT INTO sepa_collections
+                        (batch_id, booking_id, guest_id, amount, mandate_reference, sequence_type, end_to_end_id, collection_date)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8::text::date)",
+                    &[
+                        &batch.id,
+                        &candidate.debit.booking_id,
+                        &candidate.debit.guest_id,
+                        &debit.amount,
+                        &debit.mandate_reference,
+                        &debit.sequence.as_str(),
+                        &debit.end_to_end_id,
+                        &date,
+                    ],
+                )
+                .await
+                .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+            transaction
+                .execute(
+                    "UPDATE guests SET sepa_letzte_lastschrift = $2::text::date WHERE id = $1",
+                    &[&candidate.debit.guest_id, &date],
+                )
+                .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok((batch, xml))
+    }
+
+    /// Remember where the file of a batch was saved
+    pub async fn set_file_path(pool: &DbPool, id: i32, file_path: &str) -> DbResult<SepaBatch> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_one(
+                &format!("UPDATE sepa_batches SET file_path = $2 WHERE id = $1 RETURNING {}", BATCH_COLUMNS),
+                &[&id, &file_path],
+            )
+            .await?;
+
+        Ok(SepaBatch::from(row))
+    }
+
+    /// Exported batches, newest first
+    pub async fn get_batches(pool: &DbPool) -> DbResult<Vec<SepaBatch>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!("SELECT {} FROM sepa_batches ORDER BY created_at DESC, id DESC LIMIT 100", BATCH_COLUMNS),
+                &[],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(SepaBatch::from).collect())
+    }
+
+    /// Collections by status ('ausstehend', 'eingezogen', 'zurueckgegeben'), newest first
+    pub async fn get_collections(pool: &DbPool, status: &str) -> DbResult<Vec<SepaCollection>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "{} WHERE c.status = $1 ORDER BY c.collection_date DESC, c.id DESC LIMIT 500",
+                    COLLECTION_SELECT
+                ),
+                &[&status],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(SepaCollection::from).collect())
+    }
+
+    pub async fn get_collection(pool: &DbPool, id: i32) -> DbResult<SepaCollection> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(&format!("{} WHERE c.id = $1", COLLECTION_SELECT), &[&id])
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("SEPA collection with ID {} not found", id)))?;
+
+        Ok(SepaCollection::from(row))
+    }
+
+    /// Record pending debits whose collection date has come as payments of their bookings
+    pub async fn book_due_collections(pool: &DbPool, today: NaiveDate) -> DbResult<Vec<SepaCollection>> {
+        let client = pool.get().await?;
+        let today = today.format("%Y-%m-%d").to_string();
+
+        let ids: Vec<i32> = client
+            .query(
+                "SELECT id FROM sepa_collections WHERE status = 'ausstehend' AND collection_date <= $1::text::date ORDER BY id",
+                &[&today],
+            )
+            .await?
+            .into_iter()
+            .map(|row| row.get("id"))
+            .collect();
+
+        let mut booked = Vec::new();
+        for id in ids {
+            // Claim first: the job may run on several workstations
+            let claimed = client
+                .execute(
+                    "UPDATE sepa_collections SET status = 'eingezogen' WHERE id = $1 AND status = 'ausstehend'",
+                    &[&id],
+                )
+                .await?;
+            if claimed == 0 {
+                continue;
+            }
+
+            let collection = Self::get_collection(pool, id).await?;
+            let payment = PaymentRepository::create(
+                pool,
+                collection.booking_id,
+                collection.amount,
+                collection.collection_date.clone(),
+                Some("SEPA-Lastschrift".to_string()),
+                Some(collection.end_to_end_id.clone()),
+                Some(format!("Mandat {} ({})", collection.mandate_reference, collection.sequence_type)),
+                Some("system".to_string()),
+            )
+            .await;
+
+            match payment {
+                Ok(payment) => {
+                    client
+                        .execute("UPDATE sepa_collections SET payment_id = $2 WHERE id = $1", &[&id, &payment.id])
+                        .await?;
+                    booked.push(Self::get_collection(pool, id).await?);
+                }
+                Err(e) => {
+                    client
+                        .execute("UPDATE sepa_collections SET status = 'ausstehend' WHERE id = $1", &[&id])
+                        .await?;
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(booked)
+    }
+
+    /// Returned debit (Rücklastschrift): a collected amount is reversed in the payments ledger,
+    /// the mandate is removed when the reason makes further debits pointless (e.g. AC04)
+    pub async fn record_return(
+        pool: &DbPool,
+        id: i32,
+        reason_code: String,
+        returned_at: String,
+        recorded_by: Option<String>,
+    ) -> DbResult<SepaCollection> {
+        let reason_code = reason_code.trim().to_uppercase();
+        let reason_text = sepa::return_reason_text(&reason_code)
+            .ok_or_else(|| DbError::ValidationError(format!("Unbekannter Rückgabegrund: {}", reason_code)))?;
+        NaiveDate::parse_from_str(&returned_at, "%Y-%m-%d")
+            .map_err(|_| DbError::ValidationError(format!("Ungültiges Rückgabedatum: {}", returned_at)))?;
+
+        let collection = Self::get_collection(pool, id).await?;
+        if collection.status == "zurueckgegeben" {
+            return Err(DbError::ConflictError(format!("SEPA collection {} was already returned", id)));
+        }
+        let client = pool.get().await?;
+
+        // Claim with the status read above: the scheduler may book the debit meanwhile
+        let claimed = client
+            .execute(
+                "UPDATE sepa_collections SET status = 'zurueckgegeben', return_reason = $3, returned_at = $4::text::date
+                 WHERE id = $1 AND status = $2",
+                &[&id, &collection.status, &reason_code, &returned_at],
+            )
+            .await?;
+        if claimed == 0 {
+            return Err(DbError::ConflictError(format!("SEPA collection {} has changed, please reload", id)));
+        }
+
+        if collection.payment_id.is_some() {
+            PaymentRepository::create(
+                pool,
+                collection.booking_id,
+                -collection.amount,
+                returned_at.clone(),
+                Some("SEPA-Lastschrift".to_string()),
+                Some(collection.end_to_end_id.clone()),
+                Some(format!("Rücklastschrift {}: {}", reason_code, reason_text)),
+                recorded_by.clone(),
+            )
+            .await?;
+        }
+
+        // The returned debit does not count as use of the mandate (a returned FRST is repeated as FRST)
+        client
+            .execute(
+                "UPDATE guests SET sepa_letzte_lastschrift = (
+                    SELECT MAX(collection_date) FROM sepa_collections
+                    WHERE guest_id = $1 AND mandate_reference = $2 AND status <> 'zurueckgegeben')
+                 WHERE id = $1 AND sepa_mandatsreferenz = $2",
+                &[&collection.guest_id, &collection.mandate_reference],
+            )
+            .await?;
+
+        if sepa::revokes_mandate(&reason_code) {
+            let guest = GuestRepository::get_by_id(pool, collection.guest_id).await?;
+            if guest.sepa_mandatsreferenz.as_deref() == Some(collection.mandate_reference.as_str()) {
+                GuestRepository::remove_sepa_mandate(pool, collection.guest_id, recorded_by).await?;
+            }
+        }
+
+        Self::get_collection(pool, id).await
+    }
+}
@@ -10,7 +10,7 @@ impl ServiceTemplateRepository {
         let rows = client
             .query(
                 "SELECT id, name, description, price_type, price, applies_to, is_active,
-                        emoji, show_in_cleaning_plan, cleaning_plan_position, created_at::text as created_at
+                        emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie, created_at::text as created_at
                  FROM service_templates
                  ORDER BY name ASC",
                 &[],
@@ -27,7 +27,7 @@ impl ServiceTemplateRepository {
         let row = client
             .query_one(
                 "SELECT id, name, description, price_type, price, applies_to, is_active,
-                        emoji, show_in_cleaning_plan, cleaning_plan_position, created_at::text as created_at
+                        emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie, created_at::text as created_at
                  FROM service_templates
                  WHERE id = $1",
                 &[&id],
@@ -45,7 +45,7 @@ impl ServiceTemplateRepository {
         let rows = client
             .query(
                 "SELECT id, name, description, price_type, price, applies_to, is_active,
-                        emoji, show_in_cleaning_plan, cleaning_plan_position, created_at::text as created_at
+                        emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie, created_at::text as created_at
                  FROM service_templates
                  WHERE is_active = TRUE
                  ORDER BY name ASC",
@@ -57,6 +57,7 @@ impl ServiceTemplateRepository {
     }
 
     /// Create new service template
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &DbPool,
         service_name: String,
@@ -67,6 +68,7 @@ impl ServiceTemplateRepository {
         emoji: Option<String>,
         show_in_cleaning_plan: bool,
         cleaning_plan_position: String,
+        steuerkategorie: Option<String>,
     ) -> DbResult<ServiceTemplate> {
         let client = pool.get().await?;
 
@@ -74,12 +76,12 @@ impl ServiceTemplateRepository {
             .query_one(
                 "INSERT INTO service_templates (
                     name, description, price_type, price, applies_to, is_active,
-                    emoji, show_in_cleaning_plan, cleaning_plan_position, created_at::text as created_at
-                 ) VALUES ($1, $2, $3, $4, $5, TRUE, $6, $7, $8, CURRENT_TIMESTAMP)
+                    emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie, created_at
+                 ) VALUES ($1, $2, $3, $4, $5, TRUE, $6, $7, $8, COALESCE($9, 'standard'), CURRENT_TIMESTAMP)
                  RETURNING id, name, description, price_type, price, applies_to, is_active,
-                           emoji, show_in_cleaning_plan, cleaning_plan_position, created_at::text as created_at",
+                           emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie, created_at::text as created_at",
                 &[&service_name, &description, &price_type, &price, &applies_to,
-                  &emoji, &show_in_cleaning_plan, &cleaning_plan_position],
+                  &emoji, &show_in_cleaning_plan, &cleaning_plan_position, &steuerkategorie],
             )
             .await?;
 
@@ -87,6 +89,7 @@ impl ServiceTemplateRepository {
     }
 
     /// Update service template
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &DbPool,
         id: i32,
@@ -99,6 +102,7 @@ impl ServiceTemplateRepository {
         emoji: Option<String>,
         show_in_cleaning_plan: bool,
         cleaning_plan_position: String,
+        steuerkategorie: Option<String>,
     ) -> DbResult<ServiceTemplate> {
         let client = pool.get().await?;
 
@@ -107,12 +111,13 @@ impl ServiceTemplateRepository {
                 "UPDATE service_templates SET
                     name = $2, description = $3, price_type = $4, price = $5,
                     applies_to = $6, is_active = $7, emoji = $8,
-                    show_in_cleaning_plan = $9, cleaning_plan_position = $10
+                    show_in_cleaning_plan = $9, cleaning_plan_position = $10,
+                    steuerkategorie = COALESCE($11, steuerkategorie)
                  WHERE id = $1
                  RETURNING id, name, description, price_type, price, applies_to, is_active,
-                           emoji, show_in_cleaning_plan, cleaning_plan_position, created_at::text as created_at",
+                           emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie, created_at::text as created_at",
                 &[&id, &service_name, &description, &price_type, &price, &applies_to,
-                  &is_active, &emoji, &show_in_cleaning_plan, &cleaning_plan_position],
+                  &is_active, &emoji, &show_in_cleaning_plan, &cleaning_plan_position, &steuerkategorie],
             )
             .await
             .map_err(|_| crate::database_pg::DbError::NotFound(format!("Service template with ID {} not found", id)))?;
@@ -130,7 +135,7 @@ impl ServiceTemplateRepository {
                     is_active = NOT is_active
                  WHERE id = $1
                  RETURNING id, name, description, price_type, price, applies_to, is_active,
-                           emoji, show_in_cleaning_plan, cleaning_plan_position, created_at::text as created_at",
+                           emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie, created_at::text as created_at",
                 &[&id],
             )
             .await
@@ -0,0 +1,261 @@
+use chrono::NaiveDate;
+
+use crate::database_pg::{DbError, DbPool, DbResult, PaymentRepository, Voucher, VoucherRedemption};
+use crate::money::Money;
+use crate::vouchers;
+
+const VOUCHER_SELECT: &str = "SELECT v.id, v.code, v.value,
+           v.value - COALESCE((SELECT SUM(r.amount) FROM voucher_redemptions r WHERE r.voucher_id = v.id), 0) AS remaining,
+           v.issued_at::text as issued_at, v.expires_at::text as expires_at, v.purchaser_guest_id,
+           v.purchaser_name, v.recipient_name, v.message, v.notes, v.cancelled_at::text as cancelled_at,
+           v.created_at::text as created_at, v.created_by
+    FROM vouchers v";
+
+const REDEMPTION_SELECT: &str = "SELECT r.id, r.voucher_id, v.code, r.booking_id, b.reservierungsnummer,
+           TRIM(CONCAT(g.vorname, ' ', g.nachname)) AS guest_name, r.amount, r.payment_id,
+           r.created_at::text as created_at, r.created_by
+    FROM voucher_redemptions r
+    JOIN vouchers v ON v.id = r.voucher_id
+    JOIN bookings b ON b.id = r.booking_id
+    JOIN guests g ON g.id = b.guest_id";
+
+/// Attempts to find a free code before giving up (collisions are practically impossible)
+const CODE_ATTEMPTS: usize = 5;
+
+/// Repository for vouchers (Gutscheine) and their redemptions against bookings
+pub struct VoucherRepository;
+
+impl VoucherRepository {
+    /// Run voucher migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/039_vouchers.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// All vouchers, newest first. `status` filters by the derived status ('aktiv', ...).
+    pub async fn get_all(pool: &DbPool, status: Option<&str>) -> DbResult<Vec<Voucher>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(&format!("{} ORDER BY v.issued_at DESC, v.id DESC", VOUCHER_SELECT), &[])
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(Voucher::from)
+            .filter(|voucher| status.is_none_or(|status| voucher.status.as_str() == status))
+            .collect())
+    }
+
+    pub async fn get(pool: &DbPool, id: i32) -> DbResult<Voucher> {
+        let client = pool.get().await?;
+
+        client
+            .query_opt(&format!("{} WHERE v.id = $1", VOUCHER_SELECT), &[&id])
+            .await?
+            .map(Voucher::from)
+            .ok_or_else(|| DbError::NotFound(format!("Voucher with ID {} not found", id)))
+    }
+
+    /// Voucher by its code as typed in (checks the check character first)
+    pub async fn get_by_code(pool: &DbPool, code: &str) -> DbResult<Voucher> {
+        let code = vouchers::normalize_code(code).map_err(DbError::ValidationError)?;
+        let client = pool.get().await?;
+
+        client
+            .query_opt(&format!("{} WHERE v.code = $1", VOUCHER_SELECT), &[&code])
+            .await?
+            .map(Voucher::from)
+            .ok_or_else(|| DbError::ValidationError(format!("Gutschein {} ist nicht bekannt", code)))
+    }
+
+    pub async fn get_redemptions(pool: &DbPool, voucher_id: i32) -> DbResult<Vec<VoucherRedemption>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!("{} WHERE r.voucher_id = $1 ORDER BY r.created_at, r.id", REDEMPTION_SELECT),
+                &[&voucher_id],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(VoucherRedemption::from).collect())
+    }
+
+    /// Sell a voucher. Without `expires_at` it is valid until the end of the third year;
+    /// without `purchaser_name` the name of the purchasing guest is used.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &DbPool,
+        value: Money,
+        issued_at: Option<String>,
+        expires_at: Option<String>,
+        purchaser_guest_id: Option<i32>,
+        purchaser_name: Option<String>,
+        recipient_name: Option<String>,
+        message: Option<String>,
+        notes: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<Voucher> {
+        if !value.is_positive() {
+            return Err(DbError::ValidationError("Der Wert des Gutscheins muss größer als 0 sein".to_string()));
+        }
+        let parse = |date: &str, label: &str| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| DbError::ValidationError(format!("Ungültiges {}: {}", label, date)))
+        };
+        let issued_at = match issued_at.filter(|d| !d.trim().is_empty()) {
+            Some(date) => parse(&date, "Ausstellungsdatum")?,
+            None => chrono::Local::now().date_naive(),
+        };
+        let expires_at = match expires_at.filter(|d| !d.trim().is_empty()) {
+            Some(date) => parse(&date, "Ablaufdatum")?,
+            None => vouchers::default_expiry(issued_at),
+        };
+        if expires_at < issued_at {
+            return Err(DbError::ValidationError("Das Ablaufdatum liegt vor dem Ausstellungsdatum".to_string()));
+        }
+
+        let issued_at = issued_at.format("%Y-%m-%d").to_string();
+        let expires_at = expires_at.format("%Y-%m-%d").to_string();
+        let blank_to_none = |text: Option<String>| text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        let purchaser_name = blank_to_none(purchaser_name);
+        let recipient_name = blank_to_none(recipient_name);
+        let message = blank_to_none(message);
+        let notes = blank_to_none(notes);
+
+        let client = pool.get().await?;
+        for _ in 0..CODE_ATTEMPTS {
+            let code = vouchers::generate_code(&mut rand::thread_rng());
+            let row = client
+                .query_opt(
+                    "INSERT INTO vouchers (code, value, issued_at, expires_at, purchaser_guest_id, purchaser_name,
+                                           recipient_name, message, notes, created_by)
+                     VALUES ($1, $2, $3::text::date, $4::text::date, $5,
+                             COALESCE($6, (SELECT TRIM(CONCAT(vorname, ' ', nachname)) FROM guests WHERE id = $5)),
+                             $7, $8, $9, $10)
+                     ON CONFLICT (code) DO NOTHING
+                     RETURNING id",
+                    &[
+                        &code, &value, &issued_at, &expires_at, &purchaser_guest_id, &purchaser_name,
+                        &recipient_name, &message, &notes, &created_by,
+                    ],
+                )
+                .await
+                .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?;
+
+            if let Some(row) = row {
+                return Self::get(pool, row.get("id")).await;
+            }
+        }
+
+        Err(DbError::QueryError("Kein freier Gutscheincode gefunden".to_string()))
+    }
+
+    /// Cancel a voucher that was not redeemed (e.g. the purchase was reversed)
+    pub async fn cancel(pool: &DbPool, id: i32) -> DbResult<Voucher> {
+        let voucher = Self::get(pool, id).await?;
+        if voucher.cancelled_at.is_some() {
+            return Ok(voucher);
+        }
+        if voucher.remaining != voucher.value {
+            return Err(DbError::ValidationError(
+                "Der Gutschein wurde bereits eingelöst. Zuerst die Gutschein-Zahlungen in den Buchungen löschen."
+                    .to_string(),
+            ));
+        }
+
+        let client = pool.get().await?;
+        client
+            .execute(
+                "UPDATE vouchers SET cancelled_at = CURRENT_TIMESTAMP
+                 WHERE id = $1 AND cancelled_at IS NULL
+                   AND NOT EXISTS (SELECT 1 FROM voucher_redemptions WHERE voucher_id = $1)",
+                &[&id],
+            )
+            .await?;
+
+        Self::get(pool, id).await
+    }
+
+    /// Redeem a voucher against a booking: the amount (default: as much as possible) is
+    /// recorded as payment 'Gutschein' with the code as reference. Deleting that payment
+    /// in the ledger gives the amount back to the voucher.
+    pub async fn redeem(
+        pool: &DbPool,
+        code: &str,
+        booking_id: i32,
+        amount: Option<Money>,
+        recorded_by: Option<String>,
+    ) -> DbResult<VoucherRedemption> {
+        let code = vouchers::normalize_code(code).map_err(DbError::ValidationError)?;
+
+        let mut client = pool.get().await?;
+        let transaction = client.transaction().await?;
+
+        // Lock the voucher until the redemption is stored: no second workstation can spend the rest value
+        let voucher = transaction
+            .query_opt(&format!("{} WHERE v.code = $1 FOR UPDATE OF v", VOUCHER_SELECT), &[&code])
+            .await?
+            .map(Voucher::from)
+            .ok_or_else(|| DbError::ValidationError(format!("Gutschein {} ist nicht bekannt", code)))?;
+        let balance = PaymentRepository::get_balance(pool, booking_id).await?;
+        let amount = vouchers::redemption_amount(voucher.status, voucher.remaining, balance.outstanding, amount)
+            .map_err(DbError::ValidationError)?;
+
+        let payment = PaymentRepository::create(
+            pool,
+            booking_id,
+            amount,
+            chrono::Local::now().format("%Y-%m-%d").to_string(),
+            Some("Gutschein".to_string()),
+            Some(code.clone()),
+            None,
+            recorded_by.clone(),
+        )
+        .await?;
+
+        let stored = transaction
+            .query_one(
+                "INSERT INTO voucher_redemptions (voucher_id, booking_id, amount, payment_id, created_by)
+                 VALUES ($1, $2, $3, $4, $5)
+                 RETURNING id",
+                &[&voucher.id, &booking_id, &amount, &payment.id, &recorded_by],
+            )
+            .await;
+        let stored = match stored {
+            Ok(row) => transaction.commit().await.map(|_| row.get::<_, i32>("id")),
+            Err(e) => {
+                // Roll back first: the failed insert holds a lock on the payment
+                drop(transaction);
+                Err(e)
+            }
+        };
+        let id = match stored {
+            Ok(id) => id,
+            Err(e) => {
+                PaymentRepository::delete(pool, payment.id).await?;
+                return Err(DbError::from(e));
+            }
+        };
+
+        Self::get_redemption(pool, id).await
+    }
+
+    async fn get_redemption(pool: &DbPool, id: i32) -> DbResult<VoucherRedemption> {
+        let client = pool.get().await?;
+
+        client
+            .query_opt(&format!("{} WHERE r.id = $1", REDEMPTION_SELECT), &[&id])
+            .await?
+            .map(VoucherRedemption::from)
+            .ok_or_else(|| DbError::NotFound(format!("Voucher redemption with ID {} not found", id)))
+    }
+}
@@ -0,0 +1,288 @@
+use crate::database_pg::repositories::AllotmentRepository;
+use crate::database_pg::{DbError, DbPool, DbResult, WaitlistEntry};
+
+const WAITLIST_COLUMNS: &str = "w.id, w.guest_id, CONCAT(g.vorname, ' ', g.nachname) AS guest_name,
+    w.checkin_date::text as checkin_date, w.checkout_date::text as checkout_date, w.anzahl_gaeste,
+    w.room_ids, w.orte::text[] as orte, w.status, w.offered_room_id, w.offered_at::text as offered_at,
+    w.offer_email_id, w.bemerkungen, w.created_at::text as created_at, w.updated_at::text as updated_at,
+    w.created_by";
+
+const STATUSES: [&str; 4] = ["wartend", "angeboten", "gebucht", "storniert"];
+
+/// Repository for the waitlist (guests waiting for a room to become free)
+pub struct WaitlistRepository;
+
+impl WaitlistRepository {
+    /// Run waitlist migration (idempotent)
+    pub async fn run_migration(pool: &DbPool) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let migration_sql = include_str!("../../../../migrations/028_waitlist.sql");
+        client
+            .batch_execute(migration_sql)
+            .await
+            .map_err(|e| DbError::QueryError(format!("Migration failed: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Get all entries, by arrival date and then first come first served
+    pub async fn get_all(pool: &DbPool) -> DbResult<Vec<WaitlistEntry>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {} FROM waitlist_entries w JOIN guests g ON g.id = w.guest_id
+                     ORDER BY w.checkin_date, w.created_at, w.id",
+                    WAITLIST_COLUMNS
+                ),
+                &[],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(WaitlistEntry::from).collect())
+    }
+
+    /// Get entry by ID
+    pub async fn get_by_id(pool: &DbPool, id: i32) -> DbResult<WaitlistEntry> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM waitlist_entries w JOIN guests g ON g.id = w.guest_id WHERE w.id = $1",
+                    WAITLIST_COLUMNS
+                ),
+                &[&id],
+            )
+            .await?
+            .ok_or_else(|| DbError::NotFound(format!("Waitlist entry with ID {} not found", id)))?;
+
+        Ok(WaitlistEntry::from(row))
+    }
+
+    /// Entry whose offer was sent with the given scheduled email
+    pub async fn get_by_offer_email(pool: &DbPool, scheduled_email_id: i32) -> DbResult<WaitlistEntry> {
+        let client = pool.get().await?;
+
+        let row = client
+            .query_opt(
+                &format!(
+                    "SELECT {} FROM waitlist_entries w JOIN guests g ON g.id = w.guest_id WHERE w.offer_email_id = $1",
+                    WAITLIST_COLUMNS
+                ),
+                &[&scheduled_email_id],
+            )
+            .await?
+            .ok_or_else(|| {
+                DbError::NotFound(format!("No waitlist entry for scheduled email {}", scheduled_email_id))
+            })?;
+
+        Ok(WaitlistEntry::from(row))
+    }
+
+    /// Put a guest on the waitlist
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &DbPool,
+        guest_id: i32,
+        checkin_date: String,
+        checkout_date: String,
+        anzahl_gaeste: i32,
+        room_ids: Vec<i32>,
+        orte: Vec<String>,
+        bemerkungen: Option<String>,
+        created_by: Option<String>,
+    ) -> DbResult<WaitlistEntry> {
+        Self::validate(&checkin_date, &checkout_date, anzahl_gaeste)?;
+
+        let client = pool.get().await?;
+        let id: i32 = client
+            .query_one(
+                "INSERT INTO waitlist_entries (guest_id, checkin_date, checkout_date, anzahl_gaeste, room_ids, orte,
+                                               bemerkungen, created_by)
+                 VALUES ($1, $2::text::date, $3::text::date, $4, $5, $6, $7, $8)
+                 RETURNING id",
+                &[&guest_id, &checkin_date, &checkout_date, &anzahl_gaeste, &room_ids, &orte, &bemerkungen, &created_by],
+            )
+            .await
+            .map_err(|e| DbError::QueryError(format!("Insert failed: {}", e)))?
+            .get("id");
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Change the wish of an entry
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &DbPool,
+        id: i32,
+        checkin_date: String,
+        checkout_date: String,
+        anzahl_gaeste: i32,
+        room_ids: Vec<i32>,
+        orte: Vec<String>,
+        bemerkungen: Option<String>,
+    ) -> DbResult<WaitlistEntry> {
+        Self::validate(&checkin_date, &checkout_date, anzahl_gaeste)?;
+
+        let client = pool.get().await?;
+        let updated = client
+            .execute(
+                "UPDATE waitlist_entries
+                 SET checkin_date = $2::text::date, checkout_date = $3::text::date, anzahl_gaeste = $4,
+                     room_ids = $5, orte = $6, bemerkungen = $7, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1",
+                &[&id, &checkin_date, &checkout_date, &anzahl_gaeste, &room_ids, &orte, &bemerkungen],
+            )
+            .await?;
+        if updated == 0 {
+            return Err(DbError::NotFound(format!("Waitlist entry with ID {} not found", id)));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Set the status, e.g. 'gebucht' after the offer was accepted. Back to 'wartend' clears the offer.
+    pub async fn set_status(pool: &DbPool, id: i32, status: &str) -> DbResult<WaitlistEntry> {
+        if !STATUSES.contains(&status) {
+            return Err(DbError::ValidationError(format!("Ungültiger Wartelisten-Status: {}", status)));
+        }
+
+        let client = pool.get().await?;
+        let updated = client
+            .execute(
+                "UPDATE waitlist_entries
+                 SET status = $2,
+                     offered_room_id = CASE WHEN $2 = 'wartend' THEN NULL ELSE offered_room_id END,
+                     offered_at = CASE WHEN $2 = 'wartend' THEN NULL ELSE offered_at END,
+                     offer_email_id = CASE WHEN $2 = 'wartend' THEN NULL ELSE offer_email_id END,
+                     updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1",
+                &[&id, &status],
+            )
+            .await?;
+        if updated == 0 {
+            return Err(DbError::NotFound(format!("Waitlist entry with ID {} not found", id)));
+        }
+
+        Self::get_by_id(pool, id).await
+    }
+
+    /// Delete an entry
+    pub async fn delete(pool: &DbPool, id: i32) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        let deleted = client.execute("DELETE FROM waitlist_entries WHERE id = $1", &[&id]).await?;
+        if deleted == 0 {
+            return Err(DbError::NotFound(format!("Waitlist entry with ID {} not found", id)));
+        }
+
+        Ok(())
+    }
+
+    /// Waiting entries that can get `room_id` now that [from, to) became free: the room is
+    /// acceptable, big enough and free for the whole wished stay. First come first served;
+    /// entries overlapping an earlier match are left waiting (one room, one guest per night).
+    pub async fn matches_for_freed_room(
+        pool: &DbPool,
+        room_id: i32,
+        from: &str,
+        to: &str,
+    ) -> DbResult<Vec<WaitlistEntry>> {
+        let client = pool.get().await?;
+
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT {}
+                     FROM waitlist_entries w
+                     JOIN guests g ON g.id = w.guest_id
+                     JOIN rooms r ON r.id = $1
+                     WHERE w.status = 'wartend'
+                       AND w.checkin_date >= CURRENT_DATE
+                       AND w.checkin_date < $3::text::date
+                       AND w.checkout_date > $2::text::date
+                       AND (cardinality(w.room_ids) = 0 OR r.id = ANY(w.room_ids))
+                       AND (cardinality(w.orte) = 0 OR r.ort = ANY(w.orte))
+                       AND r.capacity >= w.anzahl_gaeste
+                       AND NOT EXISTS (
+                           SELECT 1 FROM bookings b
+                           WHERE b.room_id = r.id
+                             AND b.status != 'storniert'
+                             AND b.checkin_date::date < w.checkout_date
+                             AND b.checkout_date::date > w.checkin_date
+                       )
+                       AND NOT EXISTS (
+                           SELECT 1 FROM room_blocks rb
+                           WHERE rb.room_id = r.id
+                             AND rb.start_date < w.checkout_date
+                             AND rb.end_date > w.checkin_date
+                       )
+                     ORDER BY w.created_at, w.id",
+                    WAITLIST_COLUMNS
+                ),
+                &[&room_id, &from, &to],
+            )
+            .await?;
+
+        let mut matches: Vec<WaitlistEntry> = Vec::new();
+        for entry in rows.into_iter().map(WaitlistEntry::from) {
+            let overlaps_match = matches
+                .iter()
+                .any(|m| m.checkin_date < entry.checkout_date && m.checkout_date > entry.checkin_date);
+            if overlaps_match {
+                continue;
+            }
+
+            // Rooms held for an allotment are not offered either
+            let held = AllotmentRepository::held_conflicts(
+                &**client,
+                room_id,
+                &entry.checkin_date,
+                &entry.checkout_date,
+                None,
+                None,
+            )
+            .await?;
+            if held.is_empty() {
+                matches.push(entry);
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Mark an entry as offered (room + queued offer email)
+    pub async fn mark_offered(
+        pool: &DbPool,
+        id: i32,
+        room_id: i32,
+        offer_email_id: Option<i32>,
+    ) -> DbResult<()> {
+        let client = pool.get().await?;
+
+        client
+            .execute(
+                "UPDATE waitlist_entries
+                 SET status = 'angeboten', offered_room_id = $2, offered_at = CURRENT_TIMESTAMP,
+                     offer_email_id = $3, updated_at = CURRENT_TIMESTAMP
+                 WHERE id = $1",
+                &[&id, &room_id, &offer_email_id],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn validate(checkin_date: &str, checkout_date: &str, anzahl_gaeste: i32) -> DbResult<()> {
+        if checkout_date <= checkin_date {
+            return Err(DbError::ValidationError("Abreise muss nach der Anreise liegen".to_string()));
+        }
+        if anzahl_gaeste < 1 {
+            return Err(DbError::ValidationError("Mindestens ein Gast".to_string()));
+        }
+        Ok(())
+    }
+}
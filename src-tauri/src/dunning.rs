@@ -0,0 +1,199 @@
+// Dunning (Mahnwesen) - escalation of unpaid bookings through the configured stages
+// Pure functions only: no State, no DB access. The scheduler loads the stages and the last
+// dunning step of every unpaid booking and asks this module what to do next.
+//
+// Rules:
+// - A booking is due from its departure or from the due date of its first overdue deposit
+//   (whichever is earlier); the first stage is sent `start_after_days` after that
+// - Every stage sets a payment deadline (frist_tage). Only when it has passed unpaid the next
+//   stage is sent - one stage per run, stages are never skipped or repeated
+// - After the deadline of the last stage nothing is sent anymore: the booking is handed over
+//   to manual follow-up (a reminder), once
+// - Stages configured later than the last step of a booking continue from the next higher stage
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+
+/// Upper bound for the number of stages (Zahlungserinnerung + Mahnungen)
+pub const MAX_STAGES: usize = 5;
+
+/// Reminder type of the hand-over to manual follow-up
+pub const HANDOVER_REMINDER_TYPE: &str = "auto_dunning";
+
+/// Stage as configured in the settings (in order of escalation)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageRule {
+    pub name: String,
+    pub template_name: String,
+    pub frist_tage: i32,
+    pub gebuehr: Money,
+}
+
+/// Last stage sent for a booking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastNotice {
+    pub stage: i32,
+    pub zahlungsfrist: NaiveDate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Nothing to do yet
+    Wait,
+    /// Send the stage with this number
+    Send(i32),
+    /// Deadline of the last stage passed: hand over to manual follow-up
+    HandOver,
+}
+
+/// Next dunning step of an unpaid booking. `stages` are the configured stage numbers.
+pub fn next_step(
+    stages: &[i32],
+    start_after_days: i64,
+    due_since: NaiveDate,
+    last: Option<LastNotice>,
+    today: NaiveDate,
+) -> Step {
+    match last {
+        None => match stages.iter().min() {
+            Some(&first) if today >= due_since + Duration::days(start_after_days) => Step::Send(first),
+            _ => Step::Wait,
+        },
+        Some(notice) if today > notice.zahlungsfrist => {
+            match stages.iter().filter(|&&s| s > notice.stage).min() {
+                Some(&next) => Step::Send(next),
+                None => Step::HandOver,
+            }
+        }
+        Some(_) => Step::Wait,
+    }
+}
+
+/// Payment deadline named in a notice sent on `sent_on`
+pub fn zahlungsfrist(sent_on: NaiveDate, frist_tage: i32) -> NaiveDate {
+    sent_on + Duration::days(frist_tage as i64)
+}
+
+/// Name of the service that carries the late fee of a stage
+pub fn fee_service_name(stage_name: &str) -> String {
+    format!("Mahngebühr ({})", stage_name)
+}
+
+/// Check a stage configuration before it replaces the current one
+pub fn validate_stages(stages: &[StageRule]) -> Result<(), String> {
+    if stages.is_empty() {
+        return Err("Mindestens eine Mahnstufe ist erforderlich".to_string());
+    }
+    if stages.len() > MAX_STAGES {
+        return Err(format!("Höchstens {} Mahnstufen sind möglich", MAX_STAGES));
+    }
+
+    for (index, stage) in stages.iter().enumerate() {
+        let position = index + 1;
+        if stage.name.trim().is_empty() {
+            return Err(format!("Mahnstufe {}: Bezeichnung fehlt", position));
+        }
+        if stage.template_name.trim().is_empty() {
+            return Err(format!("Mahnstufe {}: E-Mail-Vorlage fehlt", position));
+        }
+        if stages[..index].iter().any(|s| s.template_name == stage.template_name) {
+            return Err(format!(
+                "Mahnstufe {}: Die Vorlage '{}' wird bereits von einer anderen Stufe verwendet",
+                position, stage.template_name
+            ));
+        }
+        if !(1..=365).contains(&stage.frist_tage) {
+            return Err(format!("Mahnstufe {}: Die Zahlungsfrist muss zwischen 1 und 365 Tagen liegen", position));
+        }
+        if stage.gebuehr.is_negative() {
+            return Err(format!("Mahnstufe {}: Die Mahngebühr darf nicht negativ sein", position));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn rule(name: &str, template_name: &str, gebuehr: f64) -> StageRule {
+        StageRule {
+            name: name.to_string(),
+            template_name: template_name.to_string(),
+            frist_tage: 14,
+            gebuehr: Money::from_euros(gebuehr),
+        }
+    }
+
+    const STAGES: [i32; 3] = [1, 2, 3];
+
+    #[test]
+    fn first_stage_after_start_period() {
+        let due = date(2026, 10, 1);
+        assert_eq!(next_step(&STAGES, 14, due, None, date(2026, 10, 14)), Step::Wait);
+        assert_eq!(next_step(&STAGES, 14, due, None, date(2026, 10, 15)), Step::Send(1));
+    }
+
+    #[test]
+    fn next_stage_only_after_deadline() {
+        let last = LastNotice { stage: 1, zahlungsfrist: date(2026, 10, 29) };
+        let due = date(2026, 10, 1);
+        assert_eq!(next_step(&STAGES, 14, due, Some(last), date(2026, 10, 29)), Step::Wait);
+        assert_eq!(next_step(&STAGES, 14, due, Some(last), date(2026, 10, 30)), Step::Send(2));
+        // A long pause does not skip a stage
+        assert_eq!(next_step(&STAGES, 14, due, Some(last), date(2027, 3, 1)), Step::Send(2));
+    }
+
+    #[test]
+    fn hand_over_after_last_stage() {
+        let last = LastNotice { stage: 3, zahlungsfrist: date(2026, 12, 1) };
+        let due = date(2026, 10, 1);
+        assert_eq!(next_step(&STAGES, 14, due, Some(last), date(2026, 12, 1)), Step::Wait);
+        assert_eq!(next_step(&STAGES, 14, due, Some(last), date(2026, 12, 2)), Step::HandOver);
+    }
+
+    #[test]
+    fn reconfigured_stages_continue_from_next_higher() {
+        let last = LastNotice { stage: 2, zahlungsfrist: date(2026, 11, 1) };
+        let due = date(2026, 10, 1);
+        assert_eq!(next_step(&[1, 4], 14, due, Some(last), date(2026, 11, 2)), Step::Send(4));
+        assert_eq!(next_step(&[1, 2], 14, due, Some(last), date(2026, 11, 2)), Step::HandOver);
+        assert_eq!(next_step(&[], 14, due, None, date(2027, 1, 1)), Step::Wait);
+    }
+
+    #[test]
+    fn deadline_and_fee_name() {
+        assert_eq!(zahlungsfrist(date(2026, 12, 20), 14), date(2027, 1, 3));
+        assert_eq!(fee_service_name("1. Mahnung"), "Mahngebühr (1. Mahnung)");
+    }
+
+    #[test]
+    fn stage_configuration_is_validated() {
+        let stages = vec![
+            rule("Zahlungserinnerung", "payment_reminder", 0.0),
+            rule("1. Mahnung", "mahnung_1", 5.0),
+        ];
+        assert!(validate_stages(&stages).is_ok());
+        assert!(validate_stages(&[]).is_err());
+
+        let mut duplicate = stages.clone();
+        duplicate[1].template_name = "payment_reminder".to_string();
+        assert!(validate_stages(&duplicate).unwrap_err().contains("bereits"));
+
+        let mut negative = stages.clone();
+        negative[1].gebuehr = Money::from_euros(-1.0);
+        assert!(validate_stages(&negative).is_err());
+
+        let mut no_deadline = stages;
+        no_deadline[0].frist_tage = 0;
+        assert!(validate_stages(&no_deadline).is_err());
+    }
+}
@@ -0,0 +1,644 @@
+// Invoice Document - positions and totals of a booking invoice
+// Pure functions only: no State, no DB access. Built once from the loaded booking
+// and shared by the HTML/PDF invoice and the XRechnung export, so both documents
+// always carry the same numbers.
+//
+// All amounts are gross (German prices include VAT). Order of the positions:
+// 1. Übernachtung (grundpreis without the occupancy surcharges)
+// 2. Occupancy surcharges (extra adults, children)
+// 3. Endreinigung of the room, unless already present as service
+// 4. Services
+// 5. Kurtaxe - pass-through levy, not discounted, not part of the booking total
+//
+// Credit notes (Gutschrift/Stornorechnung) use the same document with negative amounts:
+// a cancellation negates the whole original invoice, a correction credits single positions.
+//
+// Group bookings get one consolidated invoice: the documents of the room bookings are merged,
+// every position names its room and the tax lines are summed per category.
+//
+// Payments already received (deposits) come from the payments ledger and are applied with
+// `Invoice::apply_payments` right before the invoice is issued.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::database_pg::{BookingWithDetails, PaymentSettings, PricingSettings, Room};
+use crate::money::Money;
+use crate::pricing::{self, OccupancyLine, TaxLine, TaxPosition};
+
+/// UN/ECE Rec. 20 unit codes used for the billed quantity
+pub const UNIT_NIGHT: &str = "DAY";
+pub const UNIT_LUMP_SUM: &str = "LS";
+
+/// One invoice position (gross amounts)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceLine {
+    pub position: i32,
+    pub description: String,
+    pub details: String,
+    /// Billed quantity (nights, person-nights or 1 for lump sums)
+    pub quantity: i64,
+    pub unit_code: String,
+    /// Quantity as printed on the invoice, e.g. "3 Nächte"
+    pub quantity_label: String,
+    pub unit_price: Money,
+    pub total: Money,
+    pub tax_category: String,
+    pub tax_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvoiceDiscount {
+    pub name: String,
+    pub amount: Money,
+}
+
+/// Complete invoice of a booking
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Invoice {
+    /// Invoice number, e.g. "RE-2026-0042". Empty until the invoice is issued
+    /// (InvoiceRepository::issue allocates the gap-free number).
+    pub number: String,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub checkin_date: String,
+    pub checkout_date: String,
+    pub nights: i32,
+    pub lines: Vec<InvoiceLine>,
+    /// Sum of all positions incl. Kurtaxe
+    pub subtotal: Money,
+    pub discounts: Vec<InvoiceDiscount>,
+    pub discounts_total: Money,
+    pub kurtaxe: Money,
+    pub tax_lines: Vec<TaxLine>,
+    /// Invoice amount after discounts (incl. Kurtaxe)
+    pub total: Money,
+    pub credit_used: Money,
+    /// Payments received before the invoice was issued (deposits). Missing in invoices
+    /// issued before the payments ledger existed.
+    #[serde(default)]
+    pub paid: Money,
+    /// total - credit_used - paid
+    pub amount_due: Money,
+}
+
+impl Invoice {
+    /// Deduct the payments already received from the amount due
+    pub fn apply_payments(&mut self, paid: Money) {
+        self.paid = paid;
+        self.amount_due = self.total - self.credit_used - paid;
+    }
+}
+
+/// Document types of the invoices table, each with its own gap-free yearly sequence
+pub const DOCUMENT_INVOICE: &str = "rechnung";
+pub const DOCUMENT_CREDIT_NOTE: &str = "gutschrift";
+
+/// Document number from the yearly sequence: "RE-<year>-<sequence>" / "GS-<year>-<sequence>"
+pub fn format_document_number(document_type: &str, year: i32, sequence: i32) -> String {
+    let prefix = if document_type == DOCUMENT_CREDIT_NOTE { "GS" } else { "RE" };
+    format!("{}-{}-{:04}", prefix, year, sequence)
+}
+
+/// Position to credit in a partial correction (gross, positive amounts)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorrectionInput {
+    pub description: String,
+    pub quantity: i64,
+    pub unit_price: Money,
+    #[serde(alias = "steuerkategorie")]
+    pub tax_category: String,
+}
+
+/// Build the (not yet numbered) invoice of a booking. `occupancy_lines` are the surcharges contained in grundpreis.
+pub fn build_invoice(
+    booking: &BookingWithDetails,
+    room: &Room,
+    pricing_settings: &PricingSettings,
+    payment: &PaymentSettings,
+    occupancy_lines: &[OccupancyLine],
+    issue_date: NaiveDate,
+) -> Invoice {
+    let b = &booking.booking;
+
+    let nights = match (
+        NaiveDate::parse_from_str(&b.checkin_date, "%Y-%m-%d"),
+        NaiveDate::parse_from_str(&b.checkout_date, "%Y-%m-%d"),
+    ) {
+        (Ok(checkin), Ok(checkout)) => (checkout - checkin).num_days() as i32,
+        _ => 1,
+    };
+
+    let room_category = room
+        .steuerkategorie
+        .clone()
+        .unwrap_or_else(|| pricing::TAX_ACCOMMODATION.to_string());
+
+    let mut lines: Vec<InvoiceLine> = Vec::new();
+    let mut push_line = |description: String,
+                         details: String,
+                         quantity: i64,
+                         unit_code: &str,
+                         quantity_label: String,
+                         unit_price: Money,
+                         total: Money,
+                         tax_category: String| {
+        let tax_rate = pricing::tax_rate(pricing_settings, &tax_category);
+        lines.push(InvoiceLine {
+            position: lines.len() as i32 + 1,
+            description,
+            details,
+            quantity,
+            unit_code: unit_code.to_string(),
+            quantity_label,
+            unit_price,
+            total,
+            tax_category,
+            tax_rate,
+        });
+    };
+
+    // 1. Übernachtung - grundpreis contains the occupancy surcharges, they get their own lines
+    let grundpreis = b.grundpreis.unwrap_or_else(|| {
+        // Fallback für alte Buchungen ohne gespeicherten Grundpreis
+        room.nebensaison_preis.unwrap_or(Money::ZERO) * nights as i64
+    });
+    let occupancy_total: Money = occupancy_lines.iter().map(|l| l.total).sum();
+    let zimmerpreis = grundpreis - occupancy_total;
+    push_line(
+        format!("Übernachtung {}", room.gebaeude_typ),
+        format!("Zimmer {}, {} Personen", room.name, b.anzahl_gaeste),
+        nights.max(1) as i64,
+        UNIT_NIGHT,
+        format!("{} Nächte", nights),
+        zimmerpreis.divide(nights.max(1) as i64),
+        zimmerpreis,
+        room_category.clone(),
+    );
+
+    // 2. Personenaufpreise - Teil der Übernachtung
+    for line in occupancy_lines {
+        push_line(
+            line.description.clone(),
+            format!("{} Person(en) × {} Nächte", line.persons, line.nights),
+            (line.persons as i64 * line.nights as i64).max(1),
+            UNIT_NIGHT,
+            format!("{} Nächte", line.nights),
+            line.price_per_night,
+            line.total,
+            room_category.clone(),
+        );
+    }
+
+    // 3. Endreinigung - nur wenn NICHT schon als Service vorhanden
+    let has_endreinigung_in_services = booking.services.iter().any(|s| {
+        let name = s.service_name.to_lowercase();
+        name.contains("endreinigung") || name.contains("cleaning")
+    });
+    let endreinigung = if has_endreinigung_in_services {
+        Money::ZERO
+    } else {
+        room.endreinigung.unwrap_or(Money::ZERO)
+    };
+    if endreinigung.is_positive() {
+        push_line(
+            "Endreinigung".to_string(),
+            format!("Zimmer {}", room.name),
+            1,
+            UNIT_LUMP_SUM,
+            "1 Pauschal".to_string(),
+            endreinigung,
+            endreinigung,
+            pricing::TAX_STANDARD.to_string(),
+        );
+    }
+
+    // 4. Services (Zusatzleistungen)
+    for service in &booking.services {
+        push_line(
+            service.service_name.clone(),
+            String::new(),
+            1,
+            UNIT_LUMP_SUM,
+            "1 Pauschal".to_string(),
+            service.service_price,
+            service.service_price,
+            service
+                .steuerkategorie
+                .clone()
+                .unwrap_or_else(|| pricing::TAX_STANDARD.to_string()),
+        );
+    }
+
+    // 5. Kurtaxe - durchlaufender Posten
+    let kurtaxe = b.kurtaxe.unwrap_or(Money::ZERO);
+    if kurtaxe.is_positive() {
+        let personen = b.kurtaxe_personen.unwrap_or(0);
+        let person_nights = (personen as i64 * nights as i64).max(1);
+        push_line(
+            format!("Kurtaxe {}", room.ort),
+            format!("{} Person(en) × {} Nächte, keine MwSt.", personen, nights),
+            person_nights,
+            UNIT_NIGHT,
+            format!("{} Nächte", nights),
+            kurtaxe.divide(person_nights),
+            kurtaxe,
+            pricing::TAX_EXEMPT.to_string(),
+        );
+    }
+
+    let subtotal: Money = lines.iter().map(|l| l.total).sum();
+    let subtotal_without_kurtaxe = subtotal - kurtaxe;
+
+    // Rabatte: gespeicherter Betrag, Fallback Neuberechnung für alte Buchungen
+    let rabatt_basis = pricing_settings
+        .rabatt_basis
+        .clone()
+        .unwrap_or("zimmerpreis".to_string());
+    let discount_base = if rabatt_basis == "gesamtpreis" {
+        subtotal_without_kurtaxe
+    } else {
+        grundpreis
+    };
+    let discounts: Vec<InvoiceDiscount> = booking
+        .discounts
+        .iter()
+        .map(|d| InvoiceDiscount {
+            name: d.discount_name.clone(),
+            amount: d.calculated_amount.unwrap_or_else(|| {
+                if d.discount_type == "percent" {
+                    discount_base.percent(d.discount_value)
+                } else {
+                    Money::from_euros(d.discount_value)
+                }
+            }),
+        })
+        .collect();
+    let discounts_total = b
+        .rabatt_preis
+        .unwrap_or_else(|| discounts.iter().map(|d| d.amount).sum());
+
+    // MwSt. pro Steuerkategorie - nach Rabatten, anteilig verteilt.
+    // The room lines are always discountable, services only with rabatt_basis "gesamtpreis".
+    let services_discountable = rabatt_basis == "gesamtpreis";
+    let room_lines = 1 + occupancy_lines.len();
+    let tax_positions: Vec<TaxPosition> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| TaxPosition {
+            category: l.tax_category.clone(),
+            gross: l.total,
+            discountable: l.tax_category != pricing::TAX_EXEMPT && (i < room_lines || services_discountable),
+        })
+        .collect();
+    let tax_lines = pricing::tax_summary(pricing_settings, &tax_positions, discounts_total);
+
+    let total = subtotal - discounts_total;
+    let credit_used = b.credit_used.unwrap_or(Money::ZERO);
+    let due_days = payment.payment_due_days.unwrap_or(14) as i64;
+
+    Invoice {
+        number: String::new(),
+        issue_date,
+        due_date: issue_date + chrono::Duration::days(due_days),
+        checkin_date: b.checkin_date.clone(),
+        checkout_date: b.checkout_date.clone(),
+        nights,
+        lines,
+        subtotal,
+        discounts,
+        discounts_total,
+        kurtaxe,
+        tax_lines,
+        total,
+        credit_used,
+        paid: Money::ZERO,
+        amount_due: total - credit_used,
+    }
+}
+
+/// Credit note cancelling `original` completely (Stornorechnung): every amount negated
+pub fn cancellation_document(original: &Invoice, issue_date: NaiveDate) -> Invoice {
+    let negate_tax = |line: &TaxLine| TaxLine {
+        category: line.category.clone(),
+        rate: line.rate,
+        gross: -line.gross,
+        net: -line.net,
+        tax: -line.tax,
+    };
+
+    Invoice {
+        number: String::new(),
+        issue_date,
+        due_date: issue_date,
+        checkin_date: original.checkin_date.clone(),
+        checkout_date: original.checkout_date.clone(),
+        nights: original.nights,
+        lines: original
+            .lines
+            .iter()
+            .map(|l| InvoiceLine {
+                unit_price: -l.unit_price,
+                total: -l.total,
+                ..l.clone()
+            })
+            .collect(),
+        subtotal: -original.subtotal,
+        discounts: original
+            .discounts
+            .iter()
+            .map(|d| InvoiceDiscount { name: d.name.clone(), amount: -d.amount })
+            .collect(),
+        discounts_total: -original.discounts_total,
+        kurtaxe: -original.kurtaxe,
+        tax_lines: original.tax_lines.iter().map(negate_tax).collect(),
+        total: -original.total,
+        credit_used: -original.credit_used,
+        paid: -original.paid,
+        amount_due: -original.amount_due,
+    }
+}
+
+/// Credit note for a partial correction of `original`, e.g. one night less.
+/// Only categories of the original invoice can be credited, at most their invoiced gross amount,
+/// always at the tax rate of the original invoice.
+pub fn correction_document(
+    original: &Invoice,
+    corrections: &[CorrectionInput],
+    issue_date: NaiveDate,
+) -> Result<Invoice, String> {
+    if corrections.is_empty() {
+        return Err("Keine Korrekturpositionen angegeben".to_string());
+    }
+
+    let mut lines = Vec::new();
+    for (i, correction) in corrections.iter().enumerate() {
+        if correction.description.trim().is_empty() {
+            return Err(format!("Position {}: Beschreibung fehlt", i + 1));
+        }
+        if correction.quantity <= 0 || !correction.unit_price.is_positive() {
+            return Err(format!("Position {}: Menge und Einzelpreis müssen positiv sein", i + 1));
+        }
+        let invoiced = original
+            .tax_lines
+            .iter()
+            .find(|t| t.category == correction.tax_category)
+            .ok_or_else(|| {
+                format!(
+                    "Position {}: Steuerkategorie '{}' kommt in der Rechnung nicht vor",
+                    i + 1,
+                    correction.tax_category
+                )
+            })?;
+
+        lines.push(InvoiceLine {
+            position: i as i32 + 1,
+            description: correction.description.trim().to_string(),
+            details: format!("Korrektur zu Rechnung {}", original.number),
+            quantity: correction.quantity,
+            unit_code: UNIT_LUMP_SUM.to_string(),
+            quantity_label: correction.quantity.to_string(),
+            unit_price: -correction.unit_price,
+            total: -(correction.unit_price * correction.quantity),
+            tax_category: correction.tax_category.clone(),
+            tax_rate: invoiced.rate,
+        });
+    }
+
+    let mut tax_lines = Vec::new();
+    for invoiced in &original.tax_lines {
+        let gross: Money = lines
+            .iter()
+            .filter(|l| l.tax_category == invoiced.category)
+            .map(|l| l.total)
+            .sum();
+        if gross.is_zero() {
+            continue;
+        }
+        if -gross > invoiced.gross {
+            return Err(format!(
+                "Gutschrift für '{}' ({}) übersteigt den Rechnungsbetrag ({})",
+                invoiced.category,
+                (-gross).format_eur(),
+                invoiced.gross.format_eur()
+            ));
+        }
+        let tax = gross.included_tax(invoiced.rate);
+        tax_lines.push(TaxLine {
+            category: invoiced.category.clone(),
+            rate: invoiced.rate,
+            gross,
+            net: gross - tax,
+            tax,
+        });
+    }
+    let total: Money = lines.iter().map(|l| l.total).sum();
+    let kurtaxe: Money = lines
+        .iter()
+        .filter(|l| l.tax_category == pricing::TAX_EXEMPT)
+        .map(|l| l.total)
+        .sum();
+
+    Ok(Invoice {
+        number: String::new(),
+        issue_date,
+        due_date: issue_date,
+        checkin_date: original.checkin_date.clone(),
+        checkout_date: original.checkout_date.clone(),
+        nights: original.nights,
+        lines,
+        subtotal: total,
+        discounts: Vec::new(),
+        discounts_total: Money::ZERO,
+        kurtaxe,
+        tax_lines,
+        total,
+        credit_used: Money::ZERO,
+        paid: Money::ZERO,
+        amount_due: total,
+    })
+}
+
+/// Consolidated invoice of a booking group from the (not yet numbered) invoices of its
+/// room bookings. `parts` pairs each document with its room label, e.g. "Zimmer 101".
+pub fn group_invoice(parts: &[(String, Invoice)], issue_date: NaiveDate, due_date: NaiveDate) -> Invoice {
+    let mut lines = Vec::new();
+    let mut discounts = Vec::new();
+    let mut tax_lines: Vec<TaxLine> = Vec::new();
+
+    for (room, part) in parts {
+        for line in &part.lines {
+            lines.push(InvoiceLine {
+                position: lines.len() as i32 + 1,
+                details: if line.details.is_empty() {
+                    room.clone()
+                } else {
+                    format!("{}: {}", room, line.details)
+                },
+                ..line.clone()
+            });
+        }
+        for discount in &part.discounts {
+            discounts.push(InvoiceDiscount {
+                name: format!("{} ({})", discount.name, room),
+                amount: discount.amount,
+            });
+        }
+        for tax in &part.tax_lines {
+            match tax_lines.iter_mut().find(|t| t.category == tax.category && t.rate == tax.rate) {
+                Some(sum) => {
+                    sum.gross += tax.gross;
+                    sum.net += tax.net;
+                    sum.tax += tax.tax;
+                }
+                None => tax_lines.push(tax.clone()),
+            }
+        }
+    }
+
+    let sum = |field: fn(&Invoice) -> Money| -> Money { parts.iter().map(|(_, p)| field(p)).sum() };
+    let checkin_date = parts.iter().map(|(_, p)| p.checkin_date.clone()).min().unwrap_or_default();
+    let checkout_date = parts.iter().map(|(_, p)| p.checkout_date.clone()).max().unwrap_or_default();
+    let nights = match (
+        NaiveDate::parse_from_str(&checkin_date, "%Y-%m-%d"),
+        NaiveDate::parse_from_str(&checkout_date, "%Y-%m-%d"),
+    ) {
+        (Ok(checkin), Ok(checkout)) => (checkout - checkin).num_days() as i32,
+        _ => parts.iter().map(|(_, p)| p.nights).max().unwrap_or(0),
+    };
+
+    Invoice {
+        number: String::new(),
+        issue_date,
+        due_date,
+        checkin_date,
+        checkout_date,
+        nights,
+        lines,
+        subtotal: sum(|p| p.subtotal),
+        discounts,
+        discounts_total: sum(|p| p.discounts_total),
+        kurtaxe: sum(|p| p.kurtaxe),
+        tax_lines,
+        total: sum(|p| p.total),
+        credit_used: sum(|p| p.credit_used),
+        paid: sum(|p| p.paid),
+        amount_due: sum(|p| p.amount_due),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invoice() -> Invoice {
+        let settings = pricing::default_pricing_settings();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+        let line = InvoiceLine {
+            position: 1,
+            description: "Übernachtung".to_string(),
+            details: String::new(),
+            quantity: 3,
+            unit_code: UNIT_NIGHT.to_string(),
+            quantity_label: "3 Nächte".to_string(),
+            unit_price: Money::from_euros(100.0),
+            total: Money::from_euros(300.0),
+            tax_category: pricing::TAX_ACCOMMODATION.to_string(),
+            tax_rate: pricing::tax_rate(&settings, pricing::TAX_ACCOMMODATION),
+        };
+        let positions = [TaxPosition {
+            category: line.tax_category.clone(),
+            gross: line.total,
+            discountable: true,
+        }];
+
+        Invoice {
+            number: "RE-2026-0007".to_string(),
+            issue_date: date,
+            due_date: date,
+            checkin_date: "2026-03-01".to_string(),
+            checkout_date: "2026-03-04".to_string(),
+            nights: 3,
+            tax_lines: pricing::tax_summary(&settings, &positions, Money::ZERO),
+            lines: vec![line],
+            subtotal: Money::from_euros(300.0),
+            discounts: Vec::new(),
+            discounts_total: Money::ZERO,
+            kurtaxe: Money::ZERO,
+            total: Money::from_euros(300.0),
+            credit_used: Money::ZERO,
+            paid: Money::ZERO,
+            amount_due: Money::from_euros(300.0),
+        }
+    }
+
+    #[test]
+    fn test_payments_reduce_amount_due() {
+        let mut document = invoice();
+        document.credit_used = Money::from_euros(20.0);
+        document.apply_payments(Money::from_euros(90.0));
+        assert_eq!(document.amount_due, Money::from_euros(190.0));
+
+        let storno = cancellation_document(&document, document.issue_date);
+        assert_eq!(storno.paid, Money::from_euros(-90.0));
+        assert_eq!(storno.amount_due, Money::from_euros(-190.0));
+    }
+
+    #[test]
+    fn test_credit_notes() {
+        let original = invoice();
+        let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+        let storno = cancellation_document(&original, date);
+        assert_eq!(storno.total, Money::from_euros(-300.0));
+        assert_eq!(storno.tax_lines[0].tax, -original.tax_lines[0].tax);
+
+        // One night less
+        let night = CorrectionInput {
+            description: "Übernachtung (1 Nacht weniger)".to_string(),
+            quantity: 1,
+            unit_price: Money::from_euros(100.0),
+            tax_category: pricing::TAX_ACCOMMODATION.to_string(),
+        };
+        let correction = correction_document(&original, std::slice::from_ref(&night), date).unwrap();
+        assert_eq!(correction.total, Money::from_euros(-100.0));
+        assert_eq!(correction.tax_lines[0].tax, Money::from_euros(-6.54));
+        assert!(correction.lines[0].details.contains("RE-2026-0007"));
+
+        // More than invoiced, or a category the invoice does not contain
+        let too_much = CorrectionInput { quantity: 4, ..night.clone() };
+        assert!(correction_document(&original, &[too_much], date).is_err());
+        let other = CorrectionInput { tax_category: pricing::TAX_STANDARD.to_string(), ..night };
+        assert!(correction_document(&original, &[other], date).is_err());
+    }
+
+    #[test]
+    fn test_group_invoice() {
+        let first = invoice();
+        let second = Invoice {
+            checkin_date: "2026-03-02".to_string(),
+            checkout_date: "2026-03-06".to_string(),
+            ..invoice()
+        };
+        let date = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+        let group = group_invoice(
+            &[("Zimmer 101".to_string(), first.clone()), ("Zimmer 102".to_string(), second)],
+            date,
+            date,
+        );
+        assert_eq!(group.lines.len(), 2);
+        assert_eq!(group.lines[1].position, 2);
+        assert_eq!(group.lines[1].details, "Zimmer 102");
+        assert_eq!(group.total, Money::from_euros(600.0));
+        assert_eq!(group.amount_due, Money::from_euros(600.0));
+        assert_eq!(group.tax_lines.len(), 1);
+        assert_eq!(group.tax_lines[0].tax, first.tax_lines[0].tax * 2);
+        assert_eq!((group.checkin_date.as_str(), group.checkout_date.as_str()), ("2026-03-01", "2026-03-06"));
+        assert_eq!(group.nights, 5);
+    }
+}
@@ -0,0 +1,572 @@
+// Invoice PDF - native layout of invoices and credit notes (see native_pdf.rs)
+// Renders the issued invoice snapshot (invoice.rs) in the layout of
+// invoice_modern_template.html / credit_note_template.html: header with logo,
+// recipient cards, meta row, positions table (with page breaks), totals with the
+// USt.-Aufstellung, EPC QR code and payment footer.
+
+use base64::{engine::general_purpose, Engine as _};
+use chrono::NaiveDate;
+
+use crate::database_pg::{Booking, CompanySettings, Guest, IssuedInvoice, PaymentRecipient, PaymentSettings, Room};
+use crate::invoice::Invoice;
+use crate::money::Money;
+use crate::native_pdf::{self, Color, Document, Font, Image, A4_HEIGHT, A4_WIDTH};
+use crate::pricing;
+
+pub(crate) const MARGIN: f32 = 40.0;
+const CONTENT_WIDTH: f32 = A4_WIDTH - 2.0 * MARGIN;
+/// Content must end above this line, below is the page number
+const PAGE_BOTTOM: f32 = A4_HEIGHT - 50.0;
+
+pub(crate) const HEADER: Color = Color(30, 58, 138);
+const HEADER_CREDIT_NOTE: Color = Color(153, 27, 27);
+const TABLE_HEADER: Color = Color(30, 41, 59);
+pub(crate) const TEXT_SECONDARY: Color = Color(71, 85, 105);
+pub(crate) const TEXT_TERTIARY: Color = Color(148, 163, 184);
+pub(crate) const BORDER: Color = Color(226, 232, 240);
+pub(crate) const BG_SECONDARY: Color = Color(248, 250, 252);
+const EXTERNAL: Color = Color(37, 99, 235);
+const SUCCESS: Color = Color(16, 185, 129);
+
+/// Footer line of the HTML templates
+pub(crate) const FOOTER_TEXT: &str = "Stiftung der Deutschen Polizeigewerkschaft | Gemeinnützige Stiftung | \
+                           Finanzamt Zwiesel St.-Nr. 123/456/78900 | www.dpolg-stiftung.de";
+
+/// Invoice or credit note (with the reference to the corrected invoice)
+pub enum DocumentKind<'a> {
+    Invoice {
+        payment: &'a PaymentSettings,
+    },
+    CreditNote {
+        original: &'a IssuedInvoice,
+        is_cancellation: bool,
+        reason: Option<&'a str>,
+    },
+}
+
+/// Everything shown on the PDF
+pub struct InvoicePdf<'a> {
+    pub kind: DocumentKind<'a>,
+    pub document: &'a Invoice,
+    pub company: &'a CompanySettings,
+    pub booking: &'a Booking,
+    pub guest: &'a Guest,
+    pub room: &'a Room,
+    pub payment_recipient: Option<&'a PaymentRecipient>,
+    /// Group invoice: the rooms of the group, shown instead of `room`
+    pub rooms_label: Option<&'a str>,
+}
+
+/// EPC QR code payload (European Payment Council, SEPA credit transfer)
+pub fn epc_payload(iban: &str, bic: &str, account_holder: &str, amount: Money, reference: &str) -> String {
+    format!("BCD\n002\n1\nSCT\n{}\n{}\n{}\nEUR{}\n\n\n{}", bic, account_holder, iban, amount, reference)
+}
+
+/// Render the invoice or credit note as PDF
+pub fn render(data: &InvoicePdf) -> Result<Vec<u8>, String> {
+    let mut doc = Document::new(A4_WIDTH, A4_HEIGHT);
+
+    let mut y = header(&mut doc, data);
+    y = recipient_cards(&mut doc, data, y + 18.0);
+    y = meta_row(&mut doc, data, y + 12.0);
+    y = positions_table(&mut doc, data, y + 20.0);
+    y = totals(&mut doc, data, y + 14.0)?;
+    footer(&mut doc, data, y + 18.0);
+
+    // Page numbers once the page count is known
+    let pages = doc.page_count();
+    if pages > 1 {
+        for page in 0..pages {
+            doc.select_page(page);
+            let label = format!("{} - Seite {} von {}", data.document.number, page + 1, pages);
+            doc.text_right(A4_WIDTH - MARGIN, A4_HEIGHT - 25.0, 7.0, Font::Regular, TEXT_TERTIARY, &label);
+        }
+    }
+
+    Ok(doc.finish())
+}
+
+fn title(data: &InvoicePdf) -> &'static str {
+    match data.kind {
+        DocumentKind::Invoice { .. } => "Rechnungsnummer",
+        DocumentKind::CreditNote { is_cancellation: true, .. } => "Stornorechnung",
+        DocumentKind::CreditNote { .. } => "Rechnungskorrektur",
+    }
+}
+
+fn german_date(date: &str) -> String {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+pub(crate) fn opt(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("")
+}
+
+/// Logo from the company settings: Base64 data from the DB, fallback file at logo_path
+fn load_logo(company: &CompanySettings) -> Option<Image> {
+    let bytes = match (&company.logo_data, &company.logo_path) {
+        (Some(data), _) if !data.is_empty() => general_purpose::STANDARD.decode(data.trim()).ok(),
+        (_, Some(path)) if !path.is_empty() => std::fs::read(path).ok(),
+        _ => None,
+    }?;
+
+    match Image::decode(&bytes) {
+        Ok(image) => Some(image),
+        Err(e) => {
+            println!("⚠️ [PDF] Logo wird übersprungen: {}", e);
+            None
+        }
+    }
+}
+
+fn header(doc: &mut Document, data: &InvoicePdf) -> f32 {
+    let band = match data.kind {
+        DocumentKind::Invoice { .. } => HEADER,
+        DocumentKind::CreditNote { .. } => HEADER_CREDIT_NOTE,
+    };
+    company_header(doc, data.company, band, title(data), &data.document.number)
+}
+
+/// Colored header band with company, document label and number and logo (also used by
+/// the other documents of the company, e.g. vouchers). Returns the y below it.
+pub(crate) fn company_header(doc: &mut Document, company: &CompanySettings, band: Color, label: &str, number: &str) -> f32 {
+    let height = 96.0;
+    doc.fill_rect(0.0, 0.0, A4_WIDTH, height, band);
+
+    doc.text(MARGIN, 34.0, 16.0, Font::Bold, Color::WHITE, &company.company_name);
+    let details = [
+        opt(&company.street_address).to_string(),
+        format!("{} {}, {}", opt(&company.plz), opt(&company.city), company.country.as_deref().unwrap_or("Deutschland")),
+        format!("Tel: {}   {}", opt(&company.phone), opt(&company.email)),
+        format!("Steuernummer: {}", opt(&company.tax_id)),
+    ];
+    for (i, line) in details.iter().enumerate() {
+        doc.text(MARGIN, 50.0 + i as f32 * 10.5, 8.0, Font::Regular, Color(219, 234, 254), line);
+    }
+
+    // Logo box on the right, document number left of it
+    let mut right = A4_WIDTH - MARGIN;
+    if let Some(logo) = load_logo(company) {
+        let (width, logo_height) = logo.fit(90.0, 62.0);
+        let logo_id = doc.add_image(logo);
+        doc.fill_rect(right - 100.0, 13.0, 100.0, 70.0, Color::WHITE);
+        doc.draw_image(logo_id, right - 50.0 - width / 2.0, 17.0 + (62.0 - logo_height) / 2.0, width, logo_height);
+        right -= 112.0;
+    }
+    doc.text_right(right, 40.0, 8.0, Font::Bold, Color(191, 219, 254), &label.to_uppercase());
+    doc.text_right(right, 60.0, 15.0, Font::Bold, Color::WHITE, number);
+
+    height
+}
+
+/// One card: label, bold name, detail lines
+struct Card {
+    label: String,
+    name: String,
+    lines: Vec<String>,
+    external: bool,
+}
+
+fn recipient_cards(doc: &mut Document, data: &InvoicePdf, y: f32) -> f32 {
+    let guest = data.guest;
+    let b = data.booking;
+    let mut cards = vec![Card {
+        label: "Gast".to_string(),
+        name: format!("{} {}", guest.vorname, guest.nachname),
+        lines: vec![
+            opt(&guest.strasse).to_string(),
+            format!("{} {}", opt(&guest.plz), opt(&guest.ort)),
+        ],
+        external: false,
+    }];
+
+    if let Some(recipient) = data.payment_recipient {
+        let mut lines = Vec::new();
+        if let Some(company) = &recipient.company {
+            lines.push(company.clone());
+        }
+        if let Some(street) = &recipient.street {
+            lines.push(street.clone());
+        }
+        lines.push(format!("{} {}", opt(&recipient.plz), opt(&recipient.city)));
+        if let Some(country) = recipient.country.as_deref().filter(|c| *c != "Deutschland") {
+            lines.push(country.to_string());
+        }
+        if let Some(contact) = &recipient.contact_person {
+            lines.push(format!("Ansprechpartner: {}", contact));
+        }
+        cards.push(Card {
+            label: "Externe Rechnung".to_string(),
+            name: recipient.name.clone(),
+            lines,
+            external: true,
+        });
+    }
+
+    let room = match data.rooms_label {
+        Some(label) => label.to_string(),
+        None => format!("Zimmer {} - {}", data.room.name, data.room.gebaeude_typ),
+    };
+    let stay = format!("{} - {}", german_date(&b.checkin_date), german_date(&b.checkout_date));
+    match &data.kind {
+        DocumentKind::Invoice { .. } => cards.push(Card {
+            label: "Buchungsdetails".to_string(),
+            name: room,
+            lines: vec![
+                format!("Check-in: {}", german_date(&b.checkin_date)),
+                format!("Check-out: {}", german_date(&b.checkout_date)),
+                format!("{} Übernachtungen", data.document.nights),
+                format!("{} {}", b.anzahl_gaeste, if b.anzahl_gaeste == 1 { "Gast" } else { "Gäste" }),
+            ],
+            external: false,
+        }),
+        DocumentKind::CreditNote { original, .. } => cards.push(Card {
+            label: "Bezug".to_string(),
+            name: format!("Rechnung {}", original.invoice_number),
+            lines: vec![
+                format!("vom {}", german_date(&original.issue_date)),
+                room,
+                format!("Aufenthalt: {}", stay),
+            ],
+            external: false,
+        }),
+    }
+
+    let gap = 10.0;
+    let width = (CONTENT_WIDTH - gap * (cards.len() - 1) as f32) / cards.len() as f32;
+    let wrapped: Vec<Vec<String>> = cards
+        .iter()
+        .map(|card| {
+            card.lines
+                .iter()
+                .filter(|l| !l.trim().is_empty())
+                .flat_map(|l| native_pdf::wrap_text(l, 8.0, Font::Regular, width - 20.0))
+                .collect()
+        })
+        .collect();
+    let max_lines = wrapped.iter().map(|l| l.len()).max().unwrap_or(0);
+    let height = 44.0 + max_lines as f32 * 10.0;
+
+    for (i, (card, lines)) in cards.iter().zip(&wrapped).enumerate() {
+        let x = MARGIN + i as f32 * (width + gap);
+        let (background, label_color, text_color, detail_color) = if card.external {
+            (EXTERNAL, Color(191, 219, 254), Color::WHITE, Color(219, 234, 254))
+        } else {
+            (BG_SECONDARY, TEXT_TERTIARY, Color::BLACK, TEXT_SECONDARY)
+        };
+        doc.fill_rect(x, y, width, height, background);
+        if !card.external {
+            doc.stroke_rect(x, y, width, height, 0.5, BORDER);
+        }
+        doc.text(x + 10.0, y + 14.0, 7.0, Font::Bold, label_color, &card.label.to_uppercase());
+        let name = native_pdf::truncate(&card.name, 10.0, Font::Bold, width - 20.0);
+        doc.text(x + 10.0, y + 28.0, 10.0, Font::Bold, text_color, &name);
+        for (j, line) in lines.iter().enumerate() {
+            doc.text(x + 10.0, y + 41.0 + j as f32 * 10.0, 8.0, Font::Regular, detail_color, line);
+        }
+    }
+
+    y + height
+}
+
+fn meta_row(doc: &mut Document, data: &InvoicePdf, y: f32) -> f32 {
+    let b = data.booking;
+    let document = data.document;
+    let stay = format!("{} - {}", german_date(&b.checkin_date), german_date(&b.checkout_date));
+    let issue_date = document.issue_date.format("%d.%m.%Y").to_string();
+
+    let mut items: Vec<(&str, String)> = match &data.kind {
+        DocumentKind::Invoice { .. } => vec![
+            ("Rechnungsdatum", issue_date),
+            ("Aufenthalt", stay),
+            ("Fälligkeit", document.due_date.format("%d.%m.%Y").to_string()),
+            ("Buchungsnummer", b.reservierungsnummer.clone()),
+        ],
+        DocumentKind::CreditNote { reason, .. } => vec![
+            ("Datum", issue_date),
+            ("Buchungsnummer", b.reservierungsnummer.clone()),
+            ("Grund", reason.unwrap_or("-").to_string()),
+        ],
+    };
+    if let (DocumentKind::Invoice { .. }, Some(membership)) = (&data.kind, data.guest.mitgliedsnummer.as_ref()) {
+        if !membership.is_empty() {
+            items.push(("Mitgliedsnummer", membership.clone()));
+        }
+    }
+
+    let width = CONTENT_WIDTH / items.len() as f32;
+    let height = 36.0;
+    doc.fill_rect(MARGIN, y, CONTENT_WIDTH, height, BG_SECONDARY);
+    for (i, (label, value)) in items.iter().enumerate() {
+        let x = MARGIN + 10.0 + i as f32 * width;
+        doc.text(x, y + 14.0, 7.0, Font::Bold, TEXT_TERTIARY, &label.to_uppercase());
+        let value = native_pdf::truncate(value, 9.5, Font::Bold, width - 14.0);
+        doc.text(x, y + 27.0, 9.5, Font::Bold, Color::BLACK, &value);
+    }
+
+    y + height
+}
+
+/// Column layout of the positions table: (x, width)
+const COL_POS: (f32, f32) = (MARGIN, CONTENT_WIDTH * 0.08);
+const COL_DESCRIPTION: (f32, f32) = (MARGIN + CONTENT_WIDTH * 0.08, CONTENT_WIDTH * 0.42);
+const COL_QUANTITY: (f32, f32) = (MARGIN + CONTENT_WIDTH * 0.50, CONTENT_WIDTH * 0.15);
+const COL_UNIT_PRICE: (f32, f32) = (MARGIN + CONTENT_WIDTH * 0.65, CONTENT_WIDTH * 0.15);
+const COL_TOTAL: (f32, f32) = (MARGIN + CONTENT_WIDTH * 0.80, CONTENT_WIDTH * 0.20);
+
+fn table_header(doc: &mut Document, y: f32) -> f32 {
+    let height = 20.0;
+    doc.fill_rect(MARGIN, y, CONTENT_WIDTH, height, TABLE_HEADER);
+    let baseline = y + 13.0;
+    doc.text(COL_POS.0 + 8.0, baseline, 7.5, Font::Bold, Color::WHITE, "POS");
+    doc.text(COL_DESCRIPTION.0 + 4.0, baseline, 7.5, Font::Bold, Color::WHITE, "BESCHREIBUNG");
+    doc.text(COL_QUANTITY.0 + 4.0, baseline, 7.5, Font::Bold, Color::WHITE, "ANZAHL");
+    doc.text_right(COL_UNIT_PRICE.0 + COL_UNIT_PRICE.1 - 6.0, baseline, 7.5, Font::Bold, Color::WHITE, "EINZELPREIS");
+    doc.text_right(COL_TOTAL.0 + COL_TOTAL.1 - 8.0, baseline, 7.5, Font::Bold, Color::WHITE, "GESAMTPREIS");
+    y + height
+}
+
+fn positions_table(doc: &mut Document, data: &InvoicePdf, y: f32) -> f32 {
+    let section = match data.kind {
+        DocumentKind::Invoice { .. } => "Leistungsübersicht",
+        DocumentKind::CreditNote { is_cancellation: true, .. } => "Stornierte Leistungen",
+        DocumentKind::CreditNote { .. } => "Korrigierte Leistungen",
+    };
+    doc.text(MARGIN, y, 11.0, Font::Bold, Color::BLACK, section);
+    let mut y = table_header(doc, y + 8.0);
+
+    for line in &data.document.lines {
+        let description = native_pdf::wrap_text(&line.description, 9.0, Font::Bold, COL_DESCRIPTION.1 - 8.0);
+        let details = if line.details.is_empty() {
+            Vec::new()
+        } else {
+            native_pdf::wrap_text(&line.details, 7.5, Font::Regular, COL_DESCRIPTION.1 - 8.0)
+        };
+        let quantity = native_pdf::wrap_text(&line.quantity_label, 8.5, Font::Regular, COL_QUANTITY.1 - 8.0);
+        let text_lines = (description.len() as f32 * 11.0 + details.len() as f32 * 9.5).max(quantity.len() as f32 * 10.5);
+        let height = text_lines + 12.0;
+
+        if y + height > PAGE_BOTTOM {
+            doc.add_page();
+            y = table_header(doc, MARGIN);
+        }
+
+        let mut text_y = y + 15.0;
+        doc.text(COL_POS.0 + 8.0, text_y, 8.5, Font::Regular, TEXT_SECONDARY, &format!("{:02}", line.position));
+        for (i, part) in quantity.iter().enumerate() {
+            doc.text(COL_QUANTITY.0 + 4.0, text_y + i as f32 * 10.5, 8.5, Font::Regular, Color::BLACK, part);
+        }
+        doc.text_right(COL_UNIT_PRICE.0 + COL_UNIT_PRICE.1 - 6.0, text_y, 8.5, Font::Regular, Color::BLACK, &line.unit_price.format_eur());
+        doc.text_right(COL_TOTAL.0 + COL_TOTAL.1 - 8.0, text_y, 8.5, Font::Bold, Color::BLACK, &line.total.format_eur());
+        for part in &description {
+            doc.text(COL_DESCRIPTION.0 + 4.0, text_y, 9.0, Font::Bold, Color::BLACK, part);
+            text_y += 11.0;
+        }
+        for part in &details {
+            doc.text(COL_DESCRIPTION.0 + 4.0, text_y - 1.0, 7.5, Font::Regular, TEXT_SECONDARY, part);
+            text_y += 9.5;
+        }
+
+        y += height;
+        doc.line(MARGIN, y, MARGIN + CONTENT_WIDTH, y, 0.5, BORDER);
+    }
+
+    y
+}
+
+/// Totals (right), QR code or note (left), tax table below the totals
+fn totals(doc: &mut Document, data: &InvoicePdf, y: f32) -> Result<f32, String> {
+    let document = data.document;
+    let is_invoice = matches!(data.kind, DocumentKind::Invoice { .. });
+
+    let mut rows: Vec<(String, String, Color, Font)> = Vec::new();
+    if is_invoice {
+        rows.push(("Zwischensumme".to_string(), document.subtotal.format_eur(), TEXT_SECONDARY, Font::Regular));
+        for discount in &document.discounts {
+            rows.push((discount.name.clone(), format!("- {}", discount.amount.format_eur()), SUCCESS, Font::Regular));
+        }
+        if document.credit_used.is_positive() {
+            rows.push((
+                "Verrechnetes Gast-Guthaben".to_string(),
+                format!("- {}", document.credit_used.format_eur()),
+                SUCCESS,
+                Font::Bold,
+            ));
+        }
+        if document.paid.is_positive() {
+            rows.push((
+                "Bereits bezahlt (Anzahlungen)".to_string(),
+                format!("- {}", document.paid.format_eur()),
+                SUCCESS,
+                Font::Bold,
+            ));
+        }
+    } else {
+        for discount in &document.discounts {
+            rows.push((discount.name.clone(), (-discount.amount).format_eur(), SUCCESS, Font::Regular));
+        }
+    }
+
+    let tax_height = 16.0 + document.tax_lines.len() as f32 * 12.0;
+    let height = (rows.len() as f32 * 15.0 + 30.0 + tax_height).max(120.0);
+    let mut y = y;
+    if y + height > PAGE_BOTTOM {
+        doc.add_page();
+        y = MARGIN;
+    }
+
+    // Right column: totals
+    let left = MARGIN + CONTENT_WIDTH * 0.5;
+    let right = MARGIN + CONTENT_WIDTH;
+    let mut row_y = y + 12.0;
+    for (label, amount, color, font) in &rows {
+        let label = native_pdf::truncate(label, 9.0, *font, CONTENT_WIDTH * 0.32);
+        doc.text(left, row_y, 9.0, *font, *color, &label);
+        doc.text_right(right, row_y, 9.0, *font, *color, amount);
+        row_y += 15.0;
+    }
+    doc.line(left, row_y - 6.0, right, row_y - 6.0, 1.0, TABLE_HEADER);
+    let (total_label, total) = if is_invoice {
+        (if document.paid.is_positive() { "Restbetrag" } else { "Endbetrag" }, document.amount_due)
+    } else {
+        ("Gutschriftsbetrag", document.total)
+    };
+    doc.text(left, row_y + 10.0, 12.0, Font::Bold, Color::BLACK, total_label);
+    doc.text_right(right, row_y + 10.0, 12.0, Font::Bold, Color::BLACK, &total.format_eur());
+    let tax_y = tax_table(doc, document, left, right, row_y + 24.0);
+
+    // Left column: QR code (invoice) or refund note (credit note)
+    let note_width = CONTENT_WIDTH * 0.5 - 20.0;
+    match &data.kind {
+        DocumentKind::Invoice { payment } => {
+            let qr_size = 84.0;
+            let payload = epc_payload(
+                opt(&payment.iban),
+                opt(&payment.bic),
+                opt(&payment.account_holder),
+                document.amount_due,
+                &payment_reference(data),
+            );
+            doc.qr_code(&payload, MARGIN, y + 4.0, qr_size)?;
+            let text_x = MARGIN + qr_size + 12.0;
+            doc.text(text_x, y + 16.0, 9.0, Font::Bold, Color::BLACK, "Schnellzahlung via QR");
+            let description = "Scannen Sie den QR-Code mit Ihrer Banking-App für eine direkte Überweisung mit allen Zahlungsdaten.";
+            for (i, line) in native_pdf::wrap_text(description, 7.5, Font::Regular, note_width - qr_size - 12.0).iter().enumerate() {
+                doc.text(text_x, y + 29.0 + i as f32 * 9.5, 7.5, Font::Regular, TEXT_SECONDARY, line);
+            }
+        }
+        DocumentKind::CreditNote { original, is_cancellation, .. } => {
+            let note = if *is_cancellation {
+                format!(
+                    "Rechnung {} wird hiermit vollständig storniert. Bereits gezahlte Beträge werden erstattet.",
+                    original.invoice_number
+                )
+            } else {
+                format!(
+                    "Der Betrag von {} wird mit offenen Forderungen verrechnet bzw. erstattet. \
+                     Rechnung {} ist nur noch in Höhe des verbleibenden Betrags zu begleichen.",
+                    (-document.total).format_eur(),
+                    original.invoice_number
+                )
+            };
+            doc.text(MARGIN, y + 12.0, 9.0, Font::Bold, Color::BLACK, "Hinweis");
+            for (i, line) in native_pdf::wrap_text(&note, 8.0, Font::Regular, note_width).iter().enumerate() {
+                doc.text(MARGIN, y + 25.0 + i as f32 * 10.0, 8.0, Font::Regular, TEXT_SECONDARY, line);
+            }
+        }
+    }
+
+    Ok(tax_y.max(y + 96.0))
+}
+
+fn tax_table(doc: &mut Document, document: &Invoice, left: f32, right: f32, y: f32) -> f32 {
+    let columns = [left + 80.0, left + 130.0, left + 180.0, right];
+    doc.text(left, y + 8.0, 7.0, Font::Bold, TEXT_TERTIARY, "STEUERSATZ");
+    for (column, label) in columns.iter().zip(["", "NETTO", "MWST.", "BRUTTO"]).skip(1) {
+        doc.text_right(*column, y + 8.0, 7.0, Font::Bold, TEXT_TERTIARY, label);
+    }
+    let mut y = y + 12.0;
+    doc.line(left, y - 1.0, right, y - 1.0, 0.5, BORDER);
+
+    for line in &document.tax_lines {
+        let label = match line.category.as_str() {
+            pricing::TAX_ACCOMMODATION => format!("{}% Beherbergung", line.rate),
+            pricing::TAX_EXEMPT => "nicht steuerbar".to_string(),
+            _ => format!("{}% Zusatzleistungen", line.rate),
+        };
+        y += 10.0;
+        doc.text(left, y, 7.5, Font::Regular, TEXT_SECONDARY, &label);
+        doc.text_right(columns[1], y, 7.5, Font::Regular, TEXT_SECONDARY, &line.net.format_eur());
+        doc.text_right(columns[2], y, 7.5, Font::Regular, TEXT_SECONDARY, &line.tax.format_eur());
+        doc.text_right(columns[3], y, 7.5, Font::Regular, TEXT_SECONDARY, &line.gross.format_eur());
+    }
+
+    y + 4.0
+}
+
+fn payment_reference(data: &InvoicePdf) -> String {
+    format!("{} / {}", data.document.number, data.booking.reservierungsnummer)
+}
+
+/// Payment details (invoice), conditions and contact, then the foundation line
+fn footer(doc: &mut Document, data: &InvoicePdf, y: f32) {
+    let company = data.company;
+    let height = 100.0;
+    let mut y = y;
+    if y + height > PAGE_BOTTOM {
+        doc.add_page();
+        y = MARGIN;
+    }
+
+    doc.line(MARGIN, y, MARGIN + CONTENT_WIDTH, y, 0.5, BORDER);
+    let width = CONTENT_WIDTH / 3.0;
+    let mut sections: Vec<(&str, Vec<String>)> = Vec::new();
+    if let DocumentKind::Invoice { payment } = &data.kind {
+        sections.push((
+            "Zahlungsinformationen",
+            vec![
+                format!("Empfänger: {}", opt(&payment.account_holder)),
+                format!("Bank: {}", opt(&payment.bank_name)),
+                format!("IBAN: {}", opt(&payment.iban)),
+                format!("BIC: {}", opt(&payment.bic)),
+                format!("Verwendung: {}", payment_reference(data)),
+            ],
+        ));
+        sections.push((
+            "Zahlungsbedingungen",
+            vec![format!(
+                "Zahlbar ohne Abzug bis {}.",
+                data.document.due_date.format("%d.%m.%Y")
+            )],
+        ));
+    }
+    sections.push((
+        "Kontakt & Service",
+        vec![
+            company.company_name.clone(),
+            opt(&company.street_address).to_string(),
+            format!("{} {}", opt(&company.plz), opt(&company.city)),
+            opt(&company.email).to_string(),
+            opt(&company.phone).to_string(),
+        ],
+    ));
+
+    for (i, (heading, lines)) in sections.iter().enumerate() {
+        let x = MARGIN + i as f32 * width;
+        doc.text(x, y + 16.0, 8.5, Font::Bold, Color::BLACK, heading);
+        let wrapped = lines
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .flat_map(|l| native_pdf::wrap_text(l, 7.5, Font::Regular, width - 12.0));
+        for (j, line) in wrapped.enumerate() {
+            doc.text(x, y + 29.0 + j as f32 * 9.5, 7.5, Font::Regular, TEXT_SECONDARY, &line);
+        }
+    }
+
+    let bottom = y + height - 8.0;
+    for (i, line) in native_pdf::wrap_text(FOOTER_TEXT, 6.5, Font::Regular, CONTENT_WIDTH).iter().enumerate() {
+        doc.text_centered(A4_WIDTH / 2.0, bottom + i as f32 * 8.0, 6.5, Font::Regular, TEXT_TERTIARY, line);
+    }
+}
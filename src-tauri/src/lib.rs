@@ -5,5 +5,21 @@
 pub mod lib_pg;
 pub mod config;
 pub mod database_pg;
+pub mod money;
+pub mod pricing;
+pub mod booking_status;
+pub mod room_assignment;
+pub mod booking_series;
+pub mod reservation_number;
+pub mod payments;
+pub mod bank_import;
+pub mod sepa;
+pub mod dunning;
+pub mod vouchers;
+pub mod invoice;
+pub mod xrechnung;
+pub mod native_pdf;
+pub mod invoice_pdf;
+pub mod voucher_pdf;
 pub mod turso_sync;
 pub mod cleaning_timeline_pdf;
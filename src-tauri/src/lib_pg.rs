@@ -27,9 +27,38 @@ use crate::database_pg::{
         notification_settings_repository::NotificationSettingsRepository,
         payment_settings_repository::PaymentSettingsRepository,
         lock_repository::LockRepository,
+        season_repository::SeasonRepository,
+        kurtaxe_repository::KurtaxeRepository,
+        invoice_repository::InvoiceRepository,
+        booking_group_repository::BookingGroupRepository,
+        allotment_repository::AllotmentRepository,
+        waitlist_repository::WaitlistRepository,
+        room_block_repository::RoomBlockRepository,
+        booking_series_repository::BookingSeriesRepository,
+        payment_repository::PaymentRepository,
+        bank_transaction_repository::BankTransactionRepository,
+        sepa_repository::SepaRepository,
+        dunning_repository::DunningRepository,
+        voucher_repository::VoucherRepository,
+        guest_credit_repository::GuestCreditRepository,
     },
 };
 use crate::turso_sync;
+use crate::money::Money;
+use crate::booking_status::{BookingStatus, StatusEffect};
+use crate::booking_series;
+use crate::bank_import;
+use crate::sepa;
+use crate::dunning;
+use crate::vouchers;
+use crate::invoice;
+use crate::invoice_pdf;
+use crate::voucher_pdf;
+use crate::native_pdf;
+use crate::reservation_number;
+use crate::pricing::{self, ServiceInput, DiscountInput, FullPriceBreakdown};
+use crate::room_assignment::{self, RoomMove, RoomRanking, RoomSearchCriteria};
+use crate::xrechnung;
 use tauri::{Manager, State};
 use lettre::{
     transport::smtp::authentication::Credentials,
@@ -143,20 +172,26 @@ async fn get_room_by_id_pg(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn create_room_pg(
     pool: State<'_, DbPool>,
     name: String,
     gebaeude_typ: String,
     capacity: i32,
     ort: String,
-    nebensaison_preis: Option<f64>,
-    hauptsaison_preis: Option<f64>,
-    endreinigung: Option<f64>,
+    nebensaison_preis: Option<Money>,
+    hauptsaison_preis: Option<Money>,
+    endreinigung: Option<Money>,
+    basis_belegung: Option<i32>,
+    aufpreis_erwachsener: Option<Money>,
+    steuerkategorie: Option<String>,
     schluesselcode: Option<String>,
     street_address: Option<String>,
     postal_code: Option<String>,
     city: Option<String>,
     notizen: Option<String>,
+    hunde_erlaubt: Option<bool>,
+    erdgeschoss: Option<bool>,
     current_user: Option<String>,
 ) -> Result<database_pg::Room, String> {
     println!("create_room_pg called: {}", name);
@@ -170,11 +205,16 @@ async fn create_room_pg(
         nebensaison_preis,
         hauptsaison_preis,
         endreinigung,
+        basis_belegung,
+        aufpreis_erwachsener,
+        steuerkategorie,
         schluesselcode,
         street_address,
         postal_code,
         city,
         notizen,
+        hunde_erlaubt,
+        erdgeschoss,
         current_user,
     ).await {
         Ok(room) => {
@@ -189,6 +229,7 @@ async fn create_room_pg(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn update_room_pg(
     pool: State<'_, DbPool>,
     id: i32,
@@ -196,14 +237,19 @@ async fn update_room_pg(
     gebaeude_typ: String,
     capacity: i32,
     ort: String,
-    nebensaison_preis: Option<f64>,
-    hauptsaison_preis: Option<f64>,
-    endreinigung: Option<f64>,
+    nebensaison_preis: Option<Money>,
+    hauptsaison_preis: Option<Money>,
+    endreinigung: Option<Money>,
+    basis_belegung: Option<i32>,
+    aufpreis_erwachsener: Option<Money>,
+    steuerkategorie: Option<String>,
     schluesselcode: Option<String>,
     street_address: Option<String>,
     postal_code: Option<String>,
     city: Option<String>,
     notizen: Option<String>,
+    hunde_erlaubt: Option<bool>,
+    erdgeschoss: Option<bool>,
     current_user: Option<String>,
 ) -> Result<database_pg::Room, String> {
     println!("update_room_pg called: id={}, name={}", id, name);
@@ -218,11 +264,16 @@ async fn update_room_pg(
         nebensaison_preis,
         hauptsaison_preis,
         endreinigung,
+        basis_belegung,
+        aufpreis_erwachsener,
+        steuerkategorie,
         schluesselcode,
         street_address,
         postal_code,
         city,
         notizen,
+        hunde_erlaubt,
+        erdgeschoss,
         current_user,
     ).await {
         Ok(room) => {
@@ -274,6 +325,21 @@ async fn search_rooms_pg(
     }
 }
 
+#[tauri::command]
+async fn get_room_child_prices_pg(pool: State<'_, DbPool>, room_id: i32) -> Result<Vec<database_pg::RoomChildPrice>, String> {
+    RoomRepository::get_child_prices(&pool, room_id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn set_room_child_prices_pg(
+    pool: State<'_, DbPool>,
+    room_id: i32,
+    prices: Vec<database_pg::RoomChildPrice>,
+) -> Result<Vec<database_pg::RoomChildPrice>, String> {
+    println!("set_room_child_prices_pg called: room_id={}, {} bands", room_id, prices.len());
+    RoomRepository::set_child_prices(&pool, room_id, &prices).await.map_err(|e| e.to_string())
+}
+
 // ============================================================================
 // GUEST MANAGEMENT COMMANDS (PostgreSQL Version)
 // ============================================================================
@@ -534,13 +600,13 @@ async fn update_booking_pg(
     checkin_date: String,
     checkout_date: String,
     anzahl_gaeste: i32,
-    status: String,
-    gesamtpreis: f64,
+    status: BookingStatus,
+    gesamtpreis: Money,
     bemerkungen: Option<String>,
     anzahl_begleitpersonen: Option<i32>,
-    grundpreis: Option<f64>,
-    services_preis: Option<f64>,
-    rabatt_preis: Option<f64>,
+    grundpreis: Option<Money>,
+    services_preis: Option<Money>,
+    rabatt_preis: Option<Money>,
     anzahl_naechte: Option<i32>,
     bezahlt: Option<bool>,
     bezahlt_am: Option<String>,
@@ -609,22 +675,23 @@ async fn update_booking_pg(
         }
     };
 
-    // 3. Sync updated cleaning tasks to Turso (mobile app)
+    // 3. Side effects of a status change (confirmation email, credit release, ...)
+    run_status_effects(&pool, &booking, old_booking.status, booking.updated_by.clone()).await;
+
+    // 4. Sync updated cleaning tasks to Turso (mobile app)
     println!("🔄 Auto-syncing updated cleaning tasks to Turso for booking {}", id);
     if let Err(e) = turso_sync::sync_booking_tasks_to_turso(&pool, id).await {
         eprintln!("⚠️ Failed to sync cleaning tasks to Turso: {}", e);
         // Don't fail the booking update - mobile sync can be retried later
     }
 
-    // 4. Automatic price recalculation if price-relevant fields changed
-    // NOTE: anzahl_gaeste is NOT checked because guest count is not price-relevant
-    // in this system. Room prices are per-room, not per-person. If per-person
-    // pricing is added in the future (e.g., price_type="per_person"),
-    // anzahl_gaeste must be added to this condition.
+    // 5. Automatic price recalculation if price-relevant fields changed
+    // anzahl_gaeste is price-relevant for rooms with occupancy pricing (basis_belegung)
     let needs_price_recalc = checkin_date != old_booking.checkin_date
         || checkout_date != old_booking.checkout_date
         || room_id != old_booking.room_id
-        || guest_id != old_booking.guest_id;
+        || guest_id != old_booking.guest_id
+        || anzahl_gaeste != old_booking.anzahl_gaeste;
 
     if needs_price_recalc {
         println!("💰 Auto-recalculating prices for booking {} (price-relevant fields changed)", id);
@@ -693,12 +760,11 @@ async fn get_scheduler_interval(pool: &DbPool) -> Result<u64, String> {
     Ok(settings.scheduler_interval_hours.unwrap_or(1) as u64)
 }
 
-/// Erstellt geplante Zahlungserinnerungen für überfällige Buchungen
+/// Mahnwesen: eskaliert überfällige Buchungen stufenweise (Zahlungserinnerung, Mahnungen)
+/// und übergibt sie nach der letzten Stufe an die manuelle Nachverfolgung
 async fn schedule_payment_reminders(pool: &DbPool) -> Result<(), String> {
     use crate::database_pg::repositories::NotificationSettingsRepository;
 
-    let client = pool.get().await.map_err(|e| e.to_string())?;
-
     // 1. Lade Notification Settings
     let settings = NotificationSettingsRepository::get(pool)
         .await
@@ -709,58 +775,71 @@ async fn schedule_payment_reminders(pool: &DbPool) -> Result<(), String> {
         return Ok(());
     }
 
-    let after_days = settings.payment_reminder_after_days.unwrap_or(14) as i32;
-    let repeat_days = settings.payment_reminder_repeat_days.unwrap_or(14) as i32;
+    let after_days = settings.payment_reminder_after_days.unwrap_or(14) as i64;
+    let today = chrono::Local::now().date_naive();
 
-    // 2. Query: Finde unbezahlte Buchungen älter als X Tage
-    let sql = "
-        SELECT b.id, b.guest_id, g.email, b.reservierungsnummer
-        FROM bookings b
-        JOIN guests g ON b.guest_id = g.id
-        WHERE b.payment_status IN ('ausstehend', 'teilweise_bezahlt')
-          AND b.checkout_date < (CURRENT_DATE - INTERVAL '1 day' * $1)
-          AND b.id NOT IN (
-              -- Bereits Erinnerung in letzten X Tagen geschickt?
-              SELECT DISTINCT se.booking_id
-              FROM scheduled_emails se
-              WHERE se.template_name = 'payment_reminder'
-                AND se.status IN ('sent', 'pending')
-                AND se.scheduled_for > (NOW() - INTERVAL '1 day' * $2)
-          )
-    ";
+    // 2. Mahnstufen und überfällige Buchungen laden
+    let stages = DunningRepository::get_stages(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let stage_numbers: Vec<i32> = stages.iter().map(|s| s.stage).collect();
 
-    let rows = client.query(sql, &[&after_days, &repeat_days]).await
-        .map_err(|e| format!("Payment reminder query failed: {}", e))?;
+    let candidates = DunningRepository::get_candidates(pool, today)
+        .await
+        .map_err(|e| format!("Dunning query failed: {}", e))?;
+
+    // 3. Pro Buchung höchstens ein Schritt
+    let mut scheduled = 0;
+    for candidate in &candidates {
+        match dunning::next_step(&stage_numbers, after_days, candidate.due_since, candidate.last, today) {
+            dunning::Step::Wait => {}
+            dunning::Step::Send(number) => {
+                let Some(stage) = stages.iter().find(|s| s.stage == number) else {
+                    continue;
+                };
 
-    // 3. Erstelle scheduled_emails Einträge
-    for row in &rows {
-        let booking_id: i32 = row.get("id");
-        let guest_id: i32 = row.get("guest_id");
-        let email: String = row.get("email");
-        let reservierungsnummer: String = row.get("reservierungsnummer");
-
-        let subject = format!("Stiftung DPolG - Zahlungserinnerung – Rechnung {}", reservierungsnummer);
-
-        // INSERT mit ON CONFLICT
-        let insert_sql = "
-            INSERT INTO scheduled_emails (
-                booking_id, guest_id, template_name, recipient_email,
-                subject, scheduled_for, status
-            )
-            VALUES ($1, $2, 'payment_reminder', $3, $4, NOW(), 'pending')
-            ON CONFLICT (booking_id, template_name, status)
-            DO NOTHING
-        ";
+                // Mahngebühr als Zusatzleistung (nicht steuerbar), danach Preis neu berechnen
+                let mut service_id = None;
+                if stage.gebuehr.is_positive() {
+                    let service = AdditionalServiceRepository::create(
+                        pool,
+                        candidate.booking_id as i64,
+                        dunning::fee_service_name(&stage.name),
+                        stage.gebuehr,
+                        None,
+                        "fixed".to_string(),
+                        stage.gebuehr.to_euros(),
+                        "overnight_price".to_string(),
+                        Some(pricing::TAX_EXEMPT.to_string()),
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to add dunning fee: {}", e))?;
+                    service_id = Some(service.id as i32);
+
+                    if let Err(e) = recalculate_and_save_booking_prices(pool, candidate.booking_id).await {
+                        eprintln!("⚠️ Failed to recalculate prices: {}", e);
+                    }
+                }
 
-        client.execute(insert_sql, &[&booking_id, &guest_id, &email, &subject])
-            .await
-            .map_err(|e| format!("Failed to schedule payment reminder: {}", e))?;
+                DunningRepository::record_notice(pool, candidate, stage, service_id, today)
+                    .await
+                    .map_err(|e| format!("Failed to schedule dunning notice: {}", e))?;
+                scheduled += 1;
+
+                println!("📧 [Dunning] '{}' scheduled for booking {} (guest {})", stage.name, candidate.booking_id, candidate.guest_id);
+            }
+            dunning::Step::HandOver => {
+                DunningRepository::record_handover(pool, candidate, today)
+                    .await
+                    .map_err(|e| format!("Failed to hand over booking {}: {}", candidate.booking_id, e))?;
 
-        println!("📧 [Payment Reminder] Scheduled for booking {} (guest {})", booking_id, guest_id);
+                println!("📋 [Dunning] Booking {} handed over to manual follow-up", candidate.booking_id);
+            }
+        }
     }
 
-    if !rows.is_empty() {
-        println!("📧 [Payment Reminder] Scheduled {} payment reminders", rows.len());
+    if scheduled > 0 {
+        println!("📧 [Dunning] Scheduled {} dunning notices", scheduled);
     }
 
     Ok(())
@@ -787,6 +866,14 @@ async fn run_email_check(pool: &DbPool) -> Result<String, String> {
         .await
         .map_err(|e| format!("Failed to get notification settings: {}", e))?;
 
+    // Templates of the dunning stages belong to the payment reminder feature
+    let dunning_templates: Vec<String> = DunningRepository::get_stages(pool)
+        .await
+        .map_err(|e| format!("Failed to get dunning stages: {}", e))?
+        .into_iter()
+        .map(|stage| stage.template_name)
+        .collect();
+
     let mut sent_count = 0;
     let mut failed_count = 0;
     let mut cancelled_count = 0;
@@ -805,6 +892,7 @@ async fn run_email_check(pool: &DbPool) -> Result<String, String> {
         let should_send = match scheduled_email.template_name.as_str() {
             "booking_reminder" => settings.checkin_reminders_enabled.unwrap_or(false),
             "payment_reminder" => settings.payment_reminders_enabled.unwrap_or(false),
+            name if dunning_templates.iter().any(|t| t == name) => settings.payment_reminders_enabled.unwrap_or(false),
             _ => true, // Other templates always send
         };
 
@@ -816,44 +904,48 @@ async fn run_email_check(pool: &DbPool) -> Result<String, String> {
             continue;
         }
 
-        // Send email
-        let result = if let Some(booking_id) = scheduled_email.booking_id {
-            match process_scheduled_booking_email(pool, booking_id, &scheduled_email.template_name).await {
-                Ok(_) => {
-                    sent_count += 1;
-                    let _ = ScheduledEmailRepository::update_status(pool, scheduled_email.id, "sent").await;
-                    let _ = EmailLogRepository::create(
-                        pool,
-                        Some(booking_id),
-                        scheduled_email.guest_id.unwrap_or(0),
-                        scheduled_email.template_name.clone(),
-                        scheduled_email.recipient_email.clone(),
-                        scheduled_email.subject.clone(),
-                        "gesendet".to_string(),
-                        None,
-                    ).await;
-                    Ok(())
-                }
-                Err(e) => {
-                    failed_count += 1;
-                    let _ = ScheduledEmailRepository::update_status_with_error(pool, scheduled_email.id, "failed", &e).await;
-                    let _ = EmailLogRepository::create(
-                        pool,
-                        Some(booking_id),
-                        scheduled_email.guest_id.unwrap_or(0),
-                        scheduled_email.template_name.clone(),
-                        scheduled_email.recipient_email.clone(),
-                        scheduled_email.subject.clone(),
-                        "fehler".to_string(),
-                        Some(e.clone()),
-                    ).await;
-                    Err(e)
-                }
-            }
+        // Send email (booking emails, or waitlist offers which belong to no booking)
+        let send_result = if let Some(booking_id) = scheduled_email.booking_id {
+            process_scheduled_booking_email(pool, booking_id, &scheduled_email.template_name).await
+        } else if scheduled_email.template_name == WAITLIST_OFFER_TEMPLATE {
+            process_waitlist_offer_email(pool, scheduled_email.id).await
         } else {
             continue;
         };
 
+        let result = match send_result {
+            Ok(_) => {
+                sent_count += 1;
+                let _ = ScheduledEmailRepository::update_status(pool, scheduled_email.id, "sent").await;
+                let _ = EmailLogRepository::create(
+                    pool,
+                    scheduled_email.booking_id,
+                    scheduled_email.guest_id.unwrap_or(0),
+                    scheduled_email.template_name.clone(),
+                    scheduled_email.recipient_email.clone(),
+                    scheduled_email.subject.clone(),
+                    "gesendet".to_string(),
+                    None,
+                ).await;
+                Ok(())
+            }
+            Err(e) => {
+                failed_count += 1;
+                let _ = ScheduledEmailRepository::update_status_with_error(pool, scheduled_email.id, "failed", &e).await;
+                let _ = EmailLogRepository::create(
+                    pool,
+                    scheduled_email.booking_id,
+                    scheduled_email.guest_id.unwrap_or(0),
+                    scheduled_email.template_name.clone(),
+                    scheduled_email.recipient_email.clone(),
+                    scheduled_email.subject.clone(),
+                    "fehler".to_string(),
+                    Some(e.clone()),
+                ).await;
+                Err(e)
+            }
+        };
+
         if result.is_ok() {
             println!("✅ [Scheduler] Email {} sent successfully", scheduled_email.id);
         }
@@ -870,6 +962,100 @@ async fn run_email_check(pool: &DbPool) -> Result<String, String> {
     Ok(message)
 }
 
+// ============================================================================
+// HOLD RELEASE (Background Task)
+// ============================================================================
+
+/// Template of the email sent when an option booking expired
+const OPTION_EXPIRED_TEMPLATE: &str = "option_abgelaufen";
+
+/// Release holds whose deadline has been reached (checked hourly): allotments and
+/// option bookings
+fn start_hold_release_job(pool: DbPool) {
+    use std::time::Duration;
+
+    tauri::async_runtime::spawn(async move {
+        println!("📦 Hold release job started");
+
+        loop {
+            match AllotmentRepository::release_due(&pool).await {
+                Ok(released) => {
+                    for allotment in &released {
+                        println!("📦 [Allotments] Released '{}' (deadline {})", allotment.name, allotment.release_date);
+                    }
+                }
+                Err(e) => eprintln!("❌ [Allotments] Release check failed: {}", e),
+            }
+
+            match BookingRepository::release_expired_options(&pool).await {
+                Ok(released) => {
+                    for booking in &released {
+                        println!("⏳ [Options] Released option {} (expired {})",
+                                 booking.reservierungsnummer,
+                                 booking.option_expires_at.as_deref().unwrap_or("-"));
+                        run_status_effects(&pool, booking, BookingStatus::Optioniert, Some("system".to_string())).await;
+
+                        // After the effects - cancelling the booking cancels its pending emails
+                        if let Err(e) = schedule_option_expired_email(&pool, booking).await {
+                            eprintln!("⚠️ [Options] Expiry email for booking {} failed: {}", booking.id, e);
+                        }
+                    }
+                }
+                Err(e) => eprintln!("❌ [Options] Release check failed: {}", e),
+            }
+
+            // Direct debits are recorded as payments on their collection date
+            match SepaRepository::book_due_collections(&pool, chrono::Local::now().date_naive()).await {
+                Ok(booked) => {
+                    for collection in &booked {
+                        println!("🏦 [SEPA] Collected {} EUR for booking {}", collection.amount, collection.reservierungsnummer);
+                    }
+                }
+                Err(e) => eprintln!("❌ [SEPA] Booking due collections failed: {}", e),
+            }
+
+            // Unused guest credit expires after its expiry date
+            match GuestCreditRepository::expire_credits(&pool, chrono::Local::now().date_naive()).await {
+                Ok(expired) => {
+                    for transaction in &expired {
+                        println!("💳 [Credit] {} EUR of guest {} expired", transaction.amount, transaction.guest_id);
+                    }
+                }
+                Err(e) => eprintln!("❌ [Credit] Expiry check failed: {}", e),
+            }
+
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        }
+    });
+}
+
+/// Queue the "option_abgelaufen" email to the guest of a released option
+async fn schedule_option_expired_email(pool: &DbPool, booking: &database_pg::Booking) -> Result<(), String> {
+    use crate::database_pg::repositories::ScheduledEmailRepository;
+
+    let guest = GuestRepository::get_by_id(pool, booking.guest_id)
+        .await
+        .map_err(|e| format!("Gast nicht gefunden: {}", e))?;
+    if guest.email.is_empty() {
+        println!("⏭️ Option {} released - guest has no email address", booking.id);
+        return Ok(());
+    }
+
+    let subject = format!("Ihre Option {} ist abgelaufen", booking.reservierungsnummer);
+    ScheduledEmailRepository::schedule_now_once(
+        pool,
+        booking.id,
+        guest.id,
+        OPTION_EXPIRED_TEMPLATE,
+        &guest.email,
+        &subject,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
 // ============================================================================
 // APPLICATION SETUP & RUN
 // ============================================================================
@@ -958,6 +1144,166 @@ pub fn run_pg() {
                             }
                         }
 
+                        // Run Occupancy Pricing migration (idempotent)
+                        println!("🔧 Running Occupancy Pricing migration...");
+                        if let Err(e) = RoomRepository::run_occupancy_migration(&pool).await {
+                            eprintln!("⚠️ Occupancy Pricing migration warning: {}", e);
+                        } else {
+                            println!("✅ Occupancy Pricing ready");
+                        }
+
+                        // Run Season Price Calendar migration (idempotent)
+                        println!("🔧 Running Season Price Calendar migration...");
+                        if let Err(e) = SeasonRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Season Price Calendar migration warning: {}", e);
+                        } else {
+                            println!("✅ Season Price Calendar ready");
+                        }
+
+                        // Run Kurtaxe migration (idempotent)
+                        println!("🔧 Running Kurtaxe migration...");
+                        if let Err(e) = KurtaxeRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Kurtaxe migration warning: {}", e);
+                        } else {
+                            println!("✅ Kurtaxe ready");
+                        }
+
+                        // Run Tax Categories migration (idempotent)
+                        println!("🔧 Running Tax Categories migration...");
+                        if let Err(e) = PricingSettingsRepository::run_tax_category_migration(&pool).await {
+                            eprintln!("⚠️ Tax Categories migration warning: {}", e);
+                        } else {
+                            println!("✅ Tax Categories ready");
+                        }
+
+                        // Run Booking Status migration (idempotent)
+                        println!("🔧 Running Booking Status migration...");
+                        if let Err(e) = BookingRepository::run_status_migration(&pool).await {
+                            eprintln!("⚠️ Booking Status migration warning: {}", e);
+                        } else {
+                            println!("✅ Booking Status ready");
+                        }
+
+                        // Run Invoices migration (idempotent)
+                        println!("🔧 Running Invoices migration...");
+                        if let Err(e) = InvoiceRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Invoices migration warning: {}", e);
+                        } else {
+                            println!("✅ Invoices ready");
+                        }
+
+                        // Run Booking Groups migration (idempotent, after invoices: adds invoices.booking_group_id)
+                        println!("🔧 Running Booking Groups migration...");
+                        if let Err(e) = BookingGroupRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Booking Groups migration warning: {}", e);
+                        } else {
+                            println!("✅ Booking Groups ready");
+                        }
+
+                        // Run Allotments migration (idempotent, adds bookings.allotment_id)
+                        println!("🔧 Running Allotments migration...");
+                        if let Err(e) = AllotmentRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Allotments migration warning: {}", e);
+                        } else {
+                            println!("✅ Allotments ready");
+                        }
+
+                        // Run Waitlist migration (idempotent)
+                        println!("🔧 Running Waitlist migration...");
+                        if let Err(e) = WaitlistRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Waitlist migration warning: {}", e);
+                        } else {
+                            println!("✅ Waitlist ready");
+                        }
+
+                        // Run Option bookings migration (idempotent)
+                        println!("🔧 Running Option bookings migration...");
+                        if let Err(e) = BookingRepository::run_option_migration(&pool).await {
+                            eprintln!("⚠️ Option bookings migration warning: {}", e);
+                        } else {
+                            println!("✅ Option bookings ready");
+                        }
+
+                        // Run Room blocks migration (idempotent)
+                        println!("🔧 Running Room blocks migration...");
+                        if let Err(e) = RoomBlockRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Room blocks migration warning: {}", e);
+                        } else {
+                            println!("✅ Room blocks ready");
+                        }
+
+                        // Run Room assignment migration (idempotent)
+                        println!("🔧 Running Room assignment migration...");
+                        if let Err(e) = RoomRepository::run_assignment_migration(&pool).await {
+                            eprintln!("⚠️ Room assignment migration warning: {}", e);
+                        } else {
+                            println!("✅ Room assignment ready");
+                        }
+
+                        // Run Split stays migration (idempotent)
+                        println!("🔧 Running Split stays migration...");
+                        if let Err(e) = BookingRepository::run_split_migration(&pool).await {
+                            eprintln!("⚠️ Split stays migration warning: {}", e);
+                        } else {
+                            println!("✅ Split stays ready");
+                        }
+
+                        // Run Booking series migration (idempotent)
+                        println!("🔧 Running Booking series migration...");
+                        if let Err(e) = BookingSeriesRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Booking series migration warning: {}", e);
+                        } else {
+                            println!("✅ Booking series ready");
+                        }
+
+                        // Run Reservation numbers migration (idempotent)
+                        println!("🔧 Running Reservation numbers migration...");
+                        if let Err(e) = BookingRepository::run_reservation_number_migration(&pool).await {
+                            eprintln!("⚠️ Reservation numbers migration warning: {}", e);
+                        } else {
+                            println!("✅ Reservation numbers ready");
+                        }
+
+                        // Run Payments migration (idempotent)
+                        println!("🔧 Running Payments migration...");
+                        if let Err(e) = PaymentRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Payments migration warning: {}", e);
+                        } else {
+                            println!("✅ Payments ready");
+                        }
+
+                        // Run Bank import migration (idempotent)
+                        println!("🔧 Running Bank import migration...");
+                        if let Err(e) = BankTransactionRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Bank import migration warning: {}", e);
+                        } else {
+                            println!("✅ Bank import ready");
+                        }
+
+                        // Run SEPA direct debit migration (idempotent)
+                        println!("🔧 Running SEPA direct debit migration...");
+                        if let Err(e) = SepaRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ SEPA direct debit migration warning: {}", e);
+                        } else {
+                            println!("✅ SEPA direct debit ready");
+                        }
+
+                        // Run dunning migration (idempotent)
+                        println!("🔧 Running dunning migration...");
+                        if let Err(e) = DunningRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Dunning migration warning: {}", e);
+                        } else {
+                            println!("✅ Dunning ready");
+                        }
+
+                        // Run voucher migration (idempotent)
+                        println!("🔧 Running voucher migration...");
+                        if let Err(e) = VoucherRepository::run_migration(&pool).await {
+                            eprintln!("⚠️ Voucher migration warning: {}", e);
+                        } else {
+                            println!("✅ Vouchers ready");
+                        }
+
                         // Start PostgreSQL LISTEN/NOTIFY listener for real-time updates
                         let app_handle = app.handle().clone();
                         database_pg::listener::start_pg_listener(app_handle, db_url_for_listener.clone());
@@ -966,6 +1312,9 @@ pub fn run_pg() {
                         // Start email scheduler background task
                         start_email_scheduler(pool.clone());
 
+                        // Release allotments and option bookings at their deadline, book due direct debits
+                        start_hold_release_job(pool.clone());
+
                         Ok(())
                     }
                     Err(e) => {
@@ -987,6 +1336,8 @@ pub fn run_pg() {
             update_room_pg,
             delete_room_pg,
             search_rooms_pg,
+            get_room_child_prices_pg,
+            set_room_child_prices_pg,
 
             // Guest Management (PostgreSQL)
             get_all_guests_pg,
@@ -1002,12 +1353,16 @@ pub fn run_pg() {
             get_all_bookings_pg,
             get_booking_with_details_by_id_pg,
             create_booking_pg,
+            recalculate_booking_prices_pg,
             update_booking_pg,
             delete_booking_pg,
             update_booking_status_pg,
             update_booking_statuses_pg,
+            update_option_expiry_pg,
             update_booking_payment_pg,
             update_booking_dates_and_room_pg,
+            split_booking_pg,
+            get_stay_segments_pg,
 
             // Cleaning Tasks / Putzplan (Legacy Mobile App Support)
             delete_booking_tasks,
@@ -1019,6 +1374,87 @@ pub fn run_pg() {
             calculate_batch_booking_prices_pg,
             check_room_availability_pg,
 
+            // Booking Groups (multi-room group bookings)
+            create_booking_group_pg,
+            get_booking_groups_pg,
+            get_booking_group_pg,
+            update_booking_group_pg,
+            set_booking_guest_names_pg,
+            cancel_booking_group_command,
+
+            // Booking Series (recurring bookings)
+            create_booking_series_pg,
+            get_booking_series_list_pg,
+            get_booking_series_pg,
+            update_booking_series_pg,
+            move_booking_series_room_pg,
+            cancel_booking_series_command,
+
+            // Payments (ledger, deposit requests)
+            get_booking_payments_pg,
+            create_payment_pg,
+            delete_payment_pg,
+            create_deposit_request_pg,
+            delete_deposit_request_pg,
+
+            // Bank statement import (CAMT.053 / MT940)
+            import_bank_statement_pg,
+            get_bank_transactions_pg,
+            confirm_bank_transaction_pg,
+            ignore_bank_transaction_pg,
+            undo_bank_transaction_pg,
+
+            // SEPA direct debit (mandates, pain.008 export, returns)
+            set_guest_sepa_mandate_pg,
+            remove_guest_sepa_mandate_pg,
+            get_sepa_due_debits_pg,
+            export_sepa_direct_debits_pg,
+            get_sepa_batches_pg,
+            get_sepa_collections_pg,
+            record_sepa_return_pg,
+
+            // Dunning (Mahnstufen, history per booking)
+            get_dunning_stages_pg,
+            save_dunning_stages_pg,
+            get_booking_dunning_history_pg,
+
+            // Vouchers (Gutscheine, redemption against bookings, PDF)
+            get_vouchers_pg,
+            get_voucher_by_code_pg,
+            get_voucher_redemptions_pg,
+            create_voucher_pg,
+            cancel_voucher_pg,
+            redeem_voucher_pg,
+            generate_voucher_pdf_pg,
+
+            // Allotments (room contingents)
+            get_allotments_pg,
+            create_allotment_pg,
+            update_allotment_pg,
+            release_allotment_pg,
+            delete_allotment_pg,
+            book_from_allotment_pg,
+            get_allotment_nights_pg,
+
+            // Waitlist
+            get_waitlist_pg,
+            create_waitlist_entry_pg,
+            update_waitlist_entry_pg,
+            set_waitlist_status_pg,
+            delete_waitlist_entry_pg,
+
+            // Room Blocks (maintenance, out of order)
+            get_room_blocks_pg,
+            create_room_block_pg,
+            update_room_block_pg,
+            delete_room_block_pg,
+
+            // Room Search & Assignment
+            find_free_rooms_pg,
+            suggest_room_assignment_pg,
+            apply_room_assignment_pg,
+            set_booking_room_fixed_pg,
+
             // Additional Services
             get_all_additional_services_pg,
             get_additional_service_by_id_pg,
@@ -1111,6 +1547,21 @@ pub fn run_pg() {
             get_pricing_settings_pg,
             update_pricing_settings_pg,
 
+            // Settings - Season Price Calendar
+            get_seasons_pg,
+            create_season_pg,
+            update_season_pg,
+            delete_season_pg,
+            get_room_season_prices_pg,
+            set_room_season_price_pg,
+            delete_room_season_price_pg,
+
+            // Settings - Kurtaxe
+            get_kurtaxe_rates_pg,
+            save_kurtaxe_rate_pg,
+            delete_kurtaxe_rate_pg,
+            set_booking_geschaeftsreise_pg,
+
             // Settings - Email Config
             get_email_config_pg,
             update_email_config_pg,
@@ -1155,9 +1606,15 @@ pub fn run_pg() {
             calculate_nights_command,
             get_report_stats_command,
             get_room_occupancy_command,
+            get_kurtaxe_report_command,
 
             // Invoice & Credit Commands
             get_invoice_pdfs_for_booking_command,
+            get_invoices_for_booking_command,
+            cancel_invoice_command,
+            correct_invoice_command,
+            generate_credit_note_pdf_command,
+            generate_group_invoice_pdf_command,
 
             // Cleaning & Email Commands (STUB)
             cleanup_cleaning_tasks,
@@ -1171,6 +1628,7 @@ pub fn run_pg() {
             export_cleaning_timeline_pdf,
             generate_and_send_invoice_command,
             generate_invoice_pdf_command,
+            generate_xrechnung_command,
             get_backup_settings_command,
             get_booking_command,
             get_guest_credit_transactions,
@@ -1180,7 +1638,7 @@ pub fn run_pg() {
             debug_scheduled_emails_pg,
             list_backups_command,
             mark_invoice_sent_command,
-            migrate_to_price_list_2025_command,
+            copy_price_list_to_year_command,
             fix_booking_status_values_command,
             fix_deadlock_triggers_command,
             open_backup_folder_command,
@@ -1333,17 +1791,16 @@ async fn create_booking_pg(
     pool: State<'_, DbPool>,
     room_id: i32,
     guest_id: i32,
-    reservierungsnummer: String,
     checkin_date: String,
     checkout_date: String,
     anzahl_gaeste: i32,
-    status: String,
-    gesamtpreis: f64,
+    status: BookingStatus,
+    gesamtpreis: Money,
     bemerkungen: Option<String>,
     anzahl_begleitpersonen: Option<i32>,
-    grundpreis: Option<f64>,
-    services_preis: Option<f64>,
-    rabatt_preis: Option<f64>,
+    grundpreis: Option<Money>,
+    services_preis: Option<Money>,
+    rabatt_preis: Option<Money>,
     anzahl_naechte: Option<i32>,
     bezahlt: Option<bool>,
     bezahlt_am: Option<String>,
@@ -1360,12 +1817,13 @@ async fn create_booking_pg(
     println!("create_booking_pg called with availability check");
 
     // 1. Create booking in PostgreSQL with atomic availability check (prevents double bookings)
-    // Uses transaction with row-level locking to prevent race conditions
+    // Uses transaction with row-level locking to prevent race conditions.
+    // The reservation number is allocated from the yearly sequence (not by the frontend).
     let booking = BookingRepository::create_with_availability_check(
         &pool,
         room_id,
         guest_id,
-        reservierungsnummer,
+        None,
         checkin_date,
         checkout_date,
         anzahl_gaeste,
@@ -1406,7 +1864,22 @@ async fn create_booking_pg(
         // Don't fail the booking creation - mobile sync can be retried later
     }
 
-    Ok(booking)
+    // 3. Price snapshot incl. occupancy surcharge, Kurtaxe and member discount (as for groups)
+    if let Err(e) = recalculate_and_save_booking_prices(&pool, booking.id).await {
+        eprintln!("⚠️ Failed to calculate price for booking {}: {}", booking.id, e);
+        return Ok(booking);
+    }
+    BookingRepository::get_by_id(&pool, booking.id).await.map_err(|e| e.to_string())
+}
+
+/// Recalculate and store the prices of a booking, e.g. after the booking form
+/// attached companions, services and discounts to a new booking
+#[tauri::command]
+async fn recalculate_booking_prices_pg(pool: State<'_, DbPool>, booking_id: i32) -> Result<database_pg::Booking, String> {
+    println!("💰 recalculate_booking_prices_pg called: booking_id={}", booking_id);
+
+    recalculate_and_save_booking_prices(&pool, booking_id).await?;
+    BookingRepository::get_by_id(&pool, booking_id).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -1440,52 +1913,36 @@ async fn delete_booking_pg(pool: State<'_, DbPool>, id: i32) -> Result<(), Strin
 async fn update_booking_status_pg(
     pool: State<'_, DbPool>,
     id: i32,
-    status: String,
+    status: BookingStatus,
 ) -> Result<database_pg::Booking, String> {
     println!("update_booking_status_pg called: id={}, status={}", id, status);
 
-    // Get current booking to preserve other fields
-    let booking = BookingRepository::get_by_id(&pool, id).await.map_err(|e| {
-        eprintln!("❌ Error getting booking: {}", e);
-        e.to_string()
-    })?;
+    let (previous, booking) = BookingRepository::change_status(&pool, id, status, None)
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error updating booking status: {}", e);
+            e.to_string()
+        })?;
 
-    // Update with new status
-    BookingRepository::update(
-        &pool,
-        id,
-        booking.room_id,
-        booking.guest_id,
-        booking.reservierungsnummer,
-        booking.checkin_date,
-        booking.checkout_date,
-        booking.anzahl_gaeste,
-        status, // ← Only field changing
-        booking.gesamtpreis,
-        booking.bemerkungen,
-        booking.anzahl_begleitpersonen,
-        booking.grundpreis,
-        booking.services_preis,
-        booking.rabatt_preis,
-        booking.anzahl_naechte,
-        booking.bezahlt,
-        booking.bezahlt_am,
-        booking.zahlungsmethode,
-        booking.mahnung_gesendet_am,
-        booking.rechnung_versendet_am,
-        booking.rechnung_versendet_an,
-        booking.ist_stiftungsfall,
-        booking.payment_recipient_id,
-        booking.putzplan_checkout_date,
-        booking.ist_dpolg_mitglied,
-        None, // No user tracking for internal status updates
-        booking.updated_at, // Optimistic locking
-    )
-    .await
-    .map_err(|e| {
-        eprintln!("❌ Error updating booking status: {}", e);
-        e.to_string()
-    })
+    run_status_effects(&pool, &booking, previous, None).await;
+    Ok(booking)
+}
+
+/// Extend (or shorten) the hold of an option booking
+#[tauri::command]
+async fn update_option_expiry_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    expires_at: String,
+) -> Result<database_pg::Booking, String> {
+    println!("update_option_expiry_pg called: id={}, expires_at={}", id, expires_at);
+
+    BookingRepository::set_option_expiry(&pool, id, expires_at, None)
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error updating option expiry: {}", e);
+            e.to_string()
+        })
 }
 
 #[tauri::command]
@@ -1493,7 +1950,7 @@ async fn update_booking_statuses_pg(pool: State<'_, DbPool>) -> Result<Vec<datab
     println!("update_booking_statuses_pg called - updating all bookings based on current date");
 
     use chrono::NaiveDate;
-    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let today = chrono::Local::now().date_naive();
 
     // Get all bookings
     let bookings = BookingRepository::get_all(&pool).await.map_err(|e| {
@@ -1504,70 +1961,35 @@ async fn update_booking_statuses_pg(pool: State<'_, DbPool>) -> Result<Vec<datab
     let mut updated_bookings = Vec::new();
 
     for booking in bookings {
+        // Stiftungsfall bookings are managed manually
+        if booking.ist_stiftungsfall.unwrap_or(false) {
+            continue;
+        }
+
         // Parse dates
         let checkin = NaiveDate::parse_from_str(&booking.checkin_date, "%Y-%m-%d")
             .map_err(|e| format!("Invalid checkin date: {}", e))?;
         let checkout = NaiveDate::parse_from_str(&booking.checkout_date, "%Y-%m-%d")
             .map_err(|e| format!("Invalid checkout date: {}", e))?;
-        let today_date = NaiveDate::parse_from_str(&today, "%Y-%m-%d")
-            .map_err(|e| format!("Invalid today date: {}", e))?;
-
-        // Determine new status based on booking dates
-        // Status progression: reserviert -> bestaetigt -> eingecheckt -> ausgecheckt
-        let new_status = if today_date < checkin {
-            // Before check-in: keep as 'reserviert' or 'bestaetigt' (don't auto-change confirmed bookings)
-            if booking.status == "bestaetigt" {
-                "bestaetigt".to_string()
-            } else {
-                "reserviert".to_string()
-            }
-        } else if today_date >= checkin && today_date < checkout {
-            "eingecheckt".to_string()
-        } else {
-            "ausgecheckt".to_string()
-        };
 
-        // Only update if status changed (skip storniert and Stiftungsfall bookings)
-        if booking.status != new_status
-           && booking.status != "storniert"
-           && !booking.ist_stiftungsfall.unwrap_or(false) {
-            let updated = BookingRepository::update(
-                &pool,
-                booking.id,
-                booking.room_id,
-                booking.guest_id,
-                booking.reservierungsnummer,
-                booking.checkin_date,
-                booking.checkout_date,
-                booking.anzahl_gaeste,
-                new_status,
-                booking.gesamtpreis,
-                booking.bemerkungen,
-                booking.anzahl_begleitpersonen,
-                booking.grundpreis,
-                booking.services_preis,
-                booking.rabatt_preis,
-                booking.anzahl_naechte,
-                booking.bezahlt,
-                booking.bezahlt_am,
-                booking.zahlungsmethode,
-                booking.mahnung_gesendet_am,
-                booking.rechnung_versendet_am,
-                booking.rechnung_versendet_an,
-                booking.ist_stiftungsfall,
-                booking.payment_recipient_id,
-                booking.putzplan_checkout_date,
-                booking.ist_dpolg_mitglied,
-                Some("System".to_string()), // Auto-update by system
-                booking.updated_at, // Optimistic locking
-            )
-            .await
-            .map_err(|e| {
-                eprintln!("❌ Error updating booking {}: {}", booking.id, e);
-                e.to_string()
-            })?;
+        // Walk the state machine step by step (reserviert -> eingecheckt -> ausgecheckt),
+        // so every transition runs its side effects
+        let mut current = booking;
+        let mut changed = false;
+        while let Some(next) = current.status.next_by_date(today, checkin, checkout) {
+            let (previous, updated) = BookingRepository::change_status(&pool, current.id, next, Some("System".to_string()))
+                .await
+                .map_err(|e| {
+                    eprintln!("❌ Error updating booking {}: {}", current.id, e);
+                    e.to_string()
+                })?;
+            run_status_effects(&pool, &updated, previous, Some("System".to_string())).await;
+            current = updated;
+            changed = true;
+        }
 
-            updated_bookings.push(updated);
+        if changed {
+            updated_bookings.push(current);
         }
     }
 
@@ -1585,6 +2007,26 @@ async fn update_booking_payment_pg(
 ) -> Result<database_pg::Booking, String> {
     println!("update_booking_payment_pg called: id={}, bezahlt={}", id, bezahlt);
 
+    // The payment status is derived from the payments ledger: "bezahlt" records the outstanding
+    // amount as payment, "unbezahlt" reverses the latest payments until an amount is open again
+    if bezahlt {
+        let payment = PaymentRepository::mark_paid(&pool, id, bezahlt_am.clone(), zahlungsmethode.clone(), None)
+            .await
+            .map_err(|e| {
+                eprintln!("❌ Error recording payment: {}", e);
+                e.to_string()
+            })?;
+        if payment.is_some() {
+            return BookingRepository::get_by_id(&pool, id).await.map_err(|e| e.to_string());
+        }
+        // Nothing due (e.g. fully paid with guest credit): only set the flag below
+    } else {
+        PaymentRepository::unmark_paid(&pool, id).await.map_err(|e| {
+            eprintln!("❌ Error removing payments: {}", e);
+            e.to_string()
+        })?;
+    }
+
     let booking = BookingRepository::get_by_id(&pool, id).await.map_err(|e| {
         eprintln!("❌ Error getting booking: {}", e);
         e.to_string()
@@ -1645,7 +2087,7 @@ async fn update_booking_dates_and_room_pg(
     })?;
 
     // 1. Update booking with atomic availability check (prevents double bookings)
-    let _updated_booking = BookingRepository::update_dates_with_availability_check(
+    let updated_booking = BookingRepository::update_dates_with_availability_check(
         &pool,
         id,
         room_id,
@@ -1678,22 +2120,86 @@ async fn update_booking_dates_and_room_pg(
         // Don't fail the booking update - prices can be manually corrected
     }
 
-    // 4. Reload booking to get updated prices in response
+    // 4. Nights of the old stay that are free now are offered to the waitlist
+    let stay_changed = updated_booking.room_id != booking.room_id
+        || updated_booking.checkin_date != booking.checkin_date
+        || updated_booking.checkout_date != booking.checkout_date;
+    if stay_changed {
+        if let Err(e) = offer_freed_room_to_waitlist(&pool, booking.room_id, &booking.checkin_date, &booking.checkout_date).await {
+            eprintln!("⚠️ Failed to offer freed room to waitlist: {}", e);
+        }
+    }
+
+    // 5. Reload booking to get updated prices in response
     let updated_booking_with_prices = BookingRepository::get_by_id(&pool, id).await
         .map_err(|e| format!("Failed to reload booking with updated prices: {}", e))?;
 
     Ok(updated_booking_with_prices)
 }
 
-// ============================================================================
-// CLEANING TASKS / PUTZPLAN COMMANDS (Stubs - Legacy Mobile App Support)
-// ============================================================================
-
-/// STUB: Legacy command for cleaning tasks synchronization
-/// The mobile cleaning app (Turso DB) syncs tasks separately
-/// This command is called by frontend after booking CRUD operations
+/// Split a stay into consecutive segments in different rooms (room change within the stay).
+/// Same follow-ups as a room change: cleaning tasks, prices per segment, waitlist.
 #[tauri::command]
-async fn delete_booking_tasks(booking_id: i32, pool: State<'_, DbPool>) -> Result<(), String> {
+async fn split_booking_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    segments: Vec<database_pg::StaySegmentInput>,
+    current_user: Option<String>,
+) -> Result<Vec<database_pg::Booking>, String> {
+    println!("✂️ split_booking_pg called: booking {} into {} segments", id, segments.len());
+
+    let booking = BookingRepository::get_by_id(&pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let split = BookingRepository::split_stay(&pool, id, &segments, current_user)
+        .await
+        .map_err(|e| match e {
+            database_pg::DbError::DoubleBookingError(msg) => format!("DOUBLE_BOOKING:{}", msg),
+            database_pg::DbError::ConflictError(msg) => format!("CONFLICT:{}", msg),
+            _ => e.to_string(),
+        })?;
+
+    for segment in &split {
+        if let Err(e) = turso_sync::sync_booking_tasks_to_turso(&pool, segment.id).await {
+            eprintln!("⚠️ Failed to sync cleaning tasks to Turso: {}", e);
+        }
+        if let Err(e) = recalculate_and_save_booking_prices(&pool, segment.id).await {
+            eprintln!("⚠️ Failed to recalculate prices: {}", e);
+        }
+    }
+
+    // Nights of the old room after the room change are free now
+    if let Err(e) = offer_freed_room_to_waitlist(&pool, booking.room_id, &booking.checkin_date, &booking.checkout_date).await {
+        eprintln!("⚠️ Failed to offer freed room to waitlist: {}", e);
+    }
+
+    let master_id = booking.split_parent_id.unwrap_or(id);
+    BookingRepository::get_stay_segments(&pool, master_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// All segments of the stay a booking belongs to (the booking itself if it was never split)
+#[tauri::command]
+async fn get_stay_segments_pg(pool: State<'_, DbPool>, id: i32) -> Result<Vec<database_pg::Booking>, String> {
+    let booking = BookingRepository::get_by_id(&pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    BookingRepository::get_stay_segments(&pool, booking.split_parent_id.unwrap_or(id))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// CLEANING TASKS / PUTZPLAN COMMANDS (Stubs - Legacy Mobile App Support)
+// ============================================================================
+
+/// STUB: Legacy command for cleaning tasks synchronization
+/// The mobile cleaning app (Turso DB) syncs tasks separately
+/// This command is called by frontend after booking CRUD operations
+#[tauri::command]
+async fn delete_booking_tasks(booking_id: i32, pool: State<'_, DbPool>) -> Result<(), String> {
     use crate::database_pg::repositories::CleaningTaskRepository;
     use crate::turso_sync;
 
@@ -1762,87 +2268,12 @@ async fn sync_week_ahead(pool: State<'_, DbPool>) -> Result<String, String> {
 }
 
 // ============================================================================
-// PRICING & VALIDATION COMMANDS (Stubs - TODO: Implement full logic)
+// PRICING & VALIDATION COMMANDS (delegate to crate::pricing)
 // ============================================================================
 
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ServiceInput {
-    #[serde(alias = "name", alias = "serviceName", default)]
-    service_name: String,
-    #[serde(alias = "price", alias = "originalValue", default)]
-    original_value: f64,  // Changed from f32 for precision consistency
-    #[serde(alias = "priceType", default = "default_price_type")]
-    price_type: String,
-    #[serde(alias = "appliesTo", default = "default_applies_to")]
-    applies_to: String,
-}
-
-fn default_price_type() -> String {
-    "fixed".to_string()
-}
-
-fn default_applies_to() -> String {
-    "overnight_price".to_string()
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct DiscountInput {
-    #[serde(alias = "name", alias = "discountName", default)]
-    discount_name: String,
-    #[serde(alias = "value", alias = "originalValue", default)]
-    original_value: f64,  // Changed from f32 for precision consistency
-    #[serde(alias = "priceType", alias = "discountType", default = "default_discount_type")]
-    price_type: String,
-    #[serde(alias = "appliesTo", default = "default_applies_to")]
-    applies_to: String,
-}
-
-fn default_discount_type() -> String {
-    "percent".to_string()
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ServiceCalculation {
-    name: String,
-    price_type: String,
-    original_value: f64,
-    applies_to: String,
-    calculated_price: f64,
-    base_amount: Option<f64>,
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct DiscountCalculation {
-    name: String,
-    discount_type: String,
-    original_value: f64,
-    calculated_amount: f64,
-    base_amount: Option<f64>,
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct FullPriceBreakdown {
-    base_price: f64,
-    nights: i32,
-    price_per_night: f64,
-    is_hauptsaison: bool,
-    hauptsaison_nights: i32,
-    nebensaison_nights: i32,
-    services: Vec<ServiceCalculation>,
-    services_total: f64,
-    discounts: Vec<DiscountCalculation>,
-    discounts_total: f64,
-    subtotal: f64,
-    total: f64,
-}
-
-/// TODO: Implement full price calculation logic with PostgreSQL
+/// Calculate the full price breakdown for a booking form (live preview)
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn calculate_full_booking_price_pg(
     pool: State<'_, DbPool>,
     room_id: i32,
@@ -1851,9 +2282,10 @@ async fn calculate_full_booking_price_pg(
     is_member: bool,
     services: Option<Vec<ServiceInput>>,
     discounts: Option<Vec<DiscountInput>>,
+    anzahl_gaeste: Option<i32>,
+    companion_birthdates: Option<Vec<Option<String>>>,
+    geschaeftsreise: Option<bool>,
 ) -> Result<FullPriceBreakdown, String> {
-    use chrono::{NaiveDate, Datelike};
-
     // Get room details
     let room = RoomRepository::get_by_id(&pool, room_id)
         .await
@@ -1862,216 +2294,35 @@ async fn calculate_full_booking_price_pg(
     // Load PricingSettings for Hauptsaison and Mitgliederrabatt
     let pricing_settings = PricingSettingsRepository::get(&pool)
         .await
-        .unwrap_or_else(|_| database_pg::PricingSettings {
-            id: 1,
-            hauptsaison_aktiv: Some(false),
-            hauptsaison_start: Some("06-01".to_string()),
-            hauptsaison_ende: Some("08-31".to_string()),
-            mitglieder_rabatt_aktiv: Some(false),
-            mitglieder_rabatt_prozent: Some(15.0),
-            rabatt_basis: Some("zimmerpreis".to_string()),
-            updated_at: None,
-        });
-
-    println!("🔧 [PRICING TEST] Loaded PricingSettings: rabatt_aktiv={}, rabatt_prozent={}, rabatt_basis={}, is_member={}",
-             pricing_settings.mitglieder_rabatt_aktiv.unwrap_or(false),
-             pricing_settings.mitglieder_rabatt_prozent.unwrap_or(0.0),
-             pricing_settings.rabatt_basis.as_ref().unwrap_or(&"none".to_string()),
-             is_member);
-
-    // Calculate nights
-    let checkin_date = NaiveDate::parse_from_str(&checkin, "%Y-%m-%d")
-        .map_err(|e| format!("Ungültiges Check-in Datum: {}", e))?;
-    let checkout_date = NaiveDate::parse_from_str(&checkout, "%Y-%m-%d")
-        .map_err(|e| format!("Ungültiges Check-out Datum: {}", e))?;
-    let nights = (checkout_date - checkin_date).num_days() as i32;
-
-    if nights <= 0 {
-        return Err("Check-out muss nach Check-in liegen".to_string());
-    }
-
-    // Fix 5: Per-night season calculation - check each night individually
-    let nebensaison_price = room.nebensaison_preis.unwrap_or(0.0);
-    let hauptsaison_price = room.hauptsaison_preis.unwrap_or(nebensaison_price);
-
-    let (base_price, hauptsaison_nights, nebensaison_nights) = if pricing_settings.hauptsaison_aktiv.unwrap_or(false) {
-        let start_str = pricing_settings.hauptsaison_start.clone().unwrap_or("06-01".to_string());
-        let ende_str = pricing_settings.hauptsaison_ende.clone().unwrap_or("08-31".to_string());
-
-        let mut total = 0.0;
-        let mut hs_nights = 0i32;
-        for offset in 0..nights {
-            let night_date = checkin_date + chrono::Duration::days(offset as i64);
-            let night_mmdd = format!("{:02}-{:02}", night_date.month(), night_date.day());
-            let is_hs = if start_str > ende_str {
-                night_mmdd >= start_str || night_mmdd <= ende_str
-            } else {
-                night_mmdd >= start_str && night_mmdd <= ende_str
-            };
-            if is_hs {
-                total += hauptsaison_price;
-                hs_nights += 1;
-            } else {
-                total += nebensaison_price;
-            }
-        }
-        (total, hs_nights, nights - hs_nights)
-    } else {
-        (nebensaison_price * nights as f64, 0, nights)
-    };
-
-    let is_hauptsaison = hauptsaison_nights > 0;
-    let price_per_night = if nights > 0 { base_price / nights as f64 } else { 0.0 };
-
-    println!("📊 Hauptsaison: {} Nächte HS + {} Nächte NS, Durchschnitt {:.2}€/Nacht",
-             hauptsaison_nights, nebensaison_nights, price_per_night);
-
-    // Bug 6 fix: Two-pass service calculation for correct applies_to handling
-    let services_list = services.unwrap_or_default();
-
-    // Pass 1: Calculate fixed services and percent services that apply to overnight_price
-    let mut service_calculations: Vec<ServiceCalculation> = Vec::new();
-    let mut first_pass_total: f64 = 0.0;
-
-    for s in &services_list {
-        if s.price_type == "fixed" || s.applies_to == "overnight_price" {
-            let calculated = if s.price_type == "percent" {
-                base_price * (s.original_value / 100.0)  // percent of overnight_price
-            } else {
-                s.original_value  // fixed amount
-            };
-            first_pass_total += calculated;
-            service_calculations.push(ServiceCalculation {
-                name: s.service_name.clone(),
-                price_type: s.price_type.clone(),
-                original_value: s.original_value,
-                applies_to: s.applies_to.clone(),
-                calculated_price: calculated,
-                base_amount: Some(base_price),
-            });
-        }
-    }
-
-    // Add Endreinigung (cleaning fee) from room if it exists AND not already in services
-    // SAFEGUARD: This prevents double-counting when user manually adds "Endreinigung" service
-    // The check is case-insensitive and matches "endreinigung" or "cleaning" in service name
-    let has_endreinigung = services_list.iter()
-        .any(|s| s.service_name.to_lowercase().contains("endreinigung") || s.service_name.to_lowercase().contains("cleaning"));
-
-    if !has_endreinigung {
-        if let Some(endreinigung) = room.endreinigung {
-            if endreinigung > 0.0 {
-                first_pass_total += endreinigung;
-                service_calculations.push(ServiceCalculation {
-                    name: "Endreinigung".to_string(),
-                    price_type: "fixed".to_string(),
-                    original_value: endreinigung,
-                    applies_to: "total_price".to_string(),
-                    calculated_price: endreinigung,
-                    base_amount: Some(base_price),
-                });
-            }
-        }
-    }
-
-    // Pass 2: Calculate percent services that apply to total_price
-    let total_price_base = base_price + first_pass_total;
-    for s in &services_list {
-        if s.price_type == "percent" && s.applies_to == "total_price" {
-            let calculated = total_price_base * (s.original_value / 100.0);
-            service_calculations.push(ServiceCalculation {
-                name: s.service_name.clone(),
-                price_type: s.price_type.clone(),
-                original_value: s.original_value,
-                applies_to: s.applies_to.clone(),
-                calculated_price: calculated,
-                base_amount: Some(total_price_base),
-            });
-        }
-    }
-
-    let services_total: f64 = service_calculations.iter().map(|s| s.calculated_price).sum();
-
-    // Calculate discounts
-    let subtotal_before_discount = base_price + services_total;
-    let mut discount_calculations: Vec<DiscountCalculation> = discounts
-        .unwrap_or_default()
-        .iter()
-        .map(|d| {
-            let calculated = if d.price_type == "percent" {
-                subtotal_before_discount * (d.original_value / 100.0)
-            } else {
-                d.original_value
-            };
-            DiscountCalculation {
-                name: d.discount_name.clone(),
-                discount_type: d.price_type.clone(),
-                original_value: d.original_value,
-                calculated_amount: calculated,
-                base_amount: Some(subtotal_before_discount),
-            }
-        })
-        .collect();
-
-    // Apply automatic Mitgliederrabatt from PricingSettings if member and enabled
-    if is_member && pricing_settings.mitglieder_rabatt_aktiv.unwrap_or(false) {
-        let rabatt_prozent = pricing_settings.mitglieder_rabatt_prozent.unwrap_or(15.0);
-        let rabatt_basis = pricing_settings.rabatt_basis.clone().unwrap_or("zimmerpreis".to_string());
-
-        println!("🔧 [PRICING TEST] Calculating Mitgliederrabatt: prozent={}%, basis={}", rabatt_prozent, rabatt_basis);
-
-        // Calculate base for discount
-        let rabatt_base = if rabatt_basis == "gesamtpreis" {
-            println!("🔧 [PRICING TEST] Using gesamtpreis as basis: {:.2}€", subtotal_before_discount);
-            subtotal_before_discount
-        } else {
-            println!("🔧 [PRICING TEST] Using zimmerpreis as basis: {:.2}€", base_price);
-            base_price // zimmerpreis
-        };
-
-        let auto_rabatt = rabatt_base * (rabatt_prozent / 100.0);
-
-        println!("🔧 [PRICING TEST] Calculated auto_rabatt: {:.2}€ ({:.2}€ * {}%)", auto_rabatt, rabatt_base, rabatt_prozent);
+        .unwrap_or_else(|_| pricing::default_pricing_settings());
 
-        // Check if DPolG Rabatt already exists in discounts (avoid duplicates)
-        let has_dpolg_rabatt = discount_calculations.iter()
-            .any(|d| d.name.to_lowercase().contains("dpolg") || d.name.to_lowercase().contains("mitglieder"));
+    let (checkin_date, checkout_date) = pricing::parse_stay_dates(&checkin, &checkout)?;
 
-        println!("🔧 [PRICING TEST] has_dpolg_rabatt={}, auto_rabatt={:.2}", has_dpolg_rabatt, auto_rabatt);
-
-        if !has_dpolg_rabatt && auto_rabatt > 0.0 {
-            println!("📊 Adding automatic DPolG Mitgliederrabatt: {}% = {:.2}€ (basis: {})",
-                     rabatt_prozent, auto_rabatt, rabatt_basis);
-
-            discount_calculations.push(DiscountCalculation {
-                name: format!("DPolG Mitgliederrabatt ({}%)", rabatt_prozent),
-                discount_type: "percent".to_string(),
-                original_value: rabatt_prozent,
-                calculated_amount: auto_rabatt,
-                base_amount: Some(rabatt_base),
-            });
-        }
-    }
+    let rates = load_room_rates(&pool, &room, &checkin, &checkout).await;
+    let mut occupancy = pricing::occupancy_from_guests(
+        anzahl_gaeste.unwrap_or(1),
+        &companion_birthdates.unwrap_or_default(),
+        checkin_date,
+        &rates.child_prices,
+    );
+    occupancy.business_travel = geschaeftsreise.unwrap_or(false);
 
-    let discounts_total: f64 = discount_calculations.iter().map(|d| d.calculated_amount).sum();
+    let breakdown = pricing::calculate_price(
+        &room,
+        &pricing_settings,
+        &rates,
+        &occupancy,
+        checkin_date,
+        checkout_date,
+        is_member,
+        &services.unwrap_or_default(),
+        &discounts.unwrap_or_default(),
+    )?;
 
-    let subtotal = base_price + services_total;
-    let total = subtotal - discounts_total;
+    println!("📊 Hauptsaison: {} Nächte HS + {} Nächte NS, Durchschnitt {:.2}€/Nacht",
+             breakdown.hauptsaison_nights, breakdown.nebensaison_nights, breakdown.price_per_night);
 
-    Ok(FullPriceBreakdown {
-        base_price,
-        nights,
-        price_per_night,
-        is_hauptsaison,
-        hauptsaison_nights,
-        nebensaison_nights,
-        services: service_calculations,
-        services_total,
-        discounts: discount_calculations,
-        discounts_total,
-        subtotal,
-        total,
-    })
+    Ok(breakdown)
 }
 
 // ============================================================================
@@ -2083,10 +2334,88 @@ async fn calculate_full_booking_price_pg(
 struct BookingPriceResult {
     booking_id: i64,
     nights: i32,
-    base_price: f64,
-    services_total: f64,
-    discounts_total: f64,
-    total: f64,
+    base_price: Money,
+    services_total: Money,
+    discounts_total: Money,
+    total: Money,
+}
+
+/// Load the season calendar rates, child price bands and Kurtaxe of a room for a stay.
+/// Falls back to empty rules (Hauptsaison/Nebensaison prices, no child bands, no Kurtaxe) if the tables are unavailable.
+async fn load_room_rates(pool: &DbPool, room: &database_pg::Room, checkin: &str, checkout: &str) -> pricing::RoomRates {
+    let room_id = room.id;
+    let seasons = SeasonRepository::get_rates_for_stay(pool, room_id, checkin, checkout)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️ Could not load season calendar for room {}: {}", room_id, e);
+            Vec::new()
+        });
+    let child_prices = RoomRepository::get_child_prices(pool, room_id)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️ Could not load child prices for room {}: {}", room_id, e);
+            Vec::new()
+        });
+    let kurtaxe = KurtaxeRepository::get_for_ort(pool, &room.ort)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("⚠️ Could not load Kurtaxe for {}: {}", room.ort, e);
+            None
+        });
+
+    pricing::RoomRates { seasons, child_prices, kurtaxe }
+}
+
+/// Load room, guest, services and discounts of a stored booking and run the pricing engine
+async fn calculate_stored_booking_price(
+    pool: &DbPool,
+    booking: &database_pg::Booking,
+    pricing_settings: &database_pg::PricingSettings,
+) -> Result<FullPriceBreakdown, String> {
+    let room = RoomRepository::get_by_id(pool, booking.room_id).await
+        .map_err(|e| format!("Failed to load room: {}", e))?;
+    let guest = GuestRepository::get_by_id(pool, booking.guest_id).await
+        .map_err(|e| format!("Failed to load guest: {}", e))?;
+    let services = AdditionalServiceRepository::get_by_booking(pool, booking.id as i64).await
+        .map_err(|e| format!("Failed to load services: {}", e))?;
+    let discounts = DiscountRepository::get_by_booking(pool, booking.id as i64).await
+        .map_err(|e| format!("Failed to load discounts: {}", e))?;
+    let companions = AccompanyingGuestRepository::get_by_booking(pool, booking.id as i64).await
+        .map_err(|e| format!("Failed to load accompanying guests: {}", e))?;
+
+    let service_inputs: Vec<ServiceInput> = services.iter().map(|s| ServiceInput {
+        service_name: s.service_name.clone(),
+        price_type: s.price_type.clone(),
+        original_value: s.original_value,
+        applies_to: s.applies_to.clone(),
+        tax_category: s.steuerkategorie.clone(),
+    }).collect();
+
+    let discount_inputs: Vec<DiscountInput> = discounts.iter().map(|d| DiscountInput {
+        discount_name: d.discount_name.clone(),
+        price_type: d.discount_type.clone(),
+        original_value: d.discount_value,
+        applies_to: "total_price".to_string(),
+    }).collect();
+
+    let (checkin_date, checkout_date) = pricing::parse_stay_dates(&booking.checkin_date, &booking.checkout_date)?;
+
+    let rates = load_room_rates(pool, &room, &booking.checkin_date, &booking.checkout_date).await;
+    let companion_birthdates: Vec<Option<String>> = companions.into_iter().map(|c| c.geburtsdatum).collect();
+    let mut occupancy = pricing::occupancy_from_guests(booking.anzahl_gaeste, &companion_birthdates, checkin_date, &rates.child_prices);
+    occupancy.business_travel = booking.geschaeftsreise.unwrap_or(false);
+
+    pricing::calculate_price(
+        &room,
+        pricing_settings,
+        &rates,
+        &occupancy,
+        checkin_date,
+        checkout_date,
+        guest.dpolg_mitglied,
+        &service_inputs,
+        &discount_inputs,
+    )
 }
 
 /// Calculate prices for multiple bookings at once (batch operation)
@@ -2096,7 +2425,7 @@ async fn calculate_batch_booking_prices_pg(
     pool: State<'_, DbPool>,
     booking_ids: Vec<i64>,
 ) -> Result<Vec<BookingPriceResult>, String> {
-    use chrono::{NaiveDate, Datelike};
+    use chrono::NaiveDate;
 
     println!("═══════════════════════════════════════════════════════════════");
     println!("🔍 calculate_batch_booking_prices_pg CALLED");
@@ -2106,16 +2435,7 @@ async fn calculate_batch_booking_prices_pg(
     // Load PricingSettings once for all bookings (Bug 1 fix)
     let pricing_settings = PricingSettingsRepository::get(&pool)
         .await
-        .unwrap_or_else(|_| database_pg::PricingSettings {
-            id: 1,
-            hauptsaison_aktiv: Some(false),
-            hauptsaison_start: None,
-            hauptsaison_ende: None,
-            mitglieder_rabatt_aktiv: Some(false),
-            mitglieder_rabatt_prozent: Some(15.0),
-            rabatt_basis: Some("zimmerpreis".to_string()),
-            updated_at: None,
-        });
+        .unwrap_or_else(|_| pricing::default_pricing_settings());
 
     let mut results = Vec::new();
 
@@ -2131,9 +2451,9 @@ async fn calculate_batch_booking_prices_pg(
 
         // Fix 3: Use stored snapshot values when available (konsistente Preise)
         if booking.grundpreis.is_some() {
-            let grundpreis = booking.grundpreis.unwrap_or(0.0);
-            let services_preis = booking.services_preis.unwrap_or(0.0);
-            let rabatt_preis = booking.rabatt_preis.unwrap_or(0.0);
+            let grundpreis = booking.grundpreis.unwrap_or(Money::ZERO);
+            let services_preis = booking.services_preis.unwrap_or(Money::ZERO);
+            let rabatt_preis = booking.rabatt_preis.unwrap_or(Money::ZERO);
             let nights = booking.anzahl_naechte.unwrap_or_else(|| {
                 // Fallback: Nächte aus Datum berechnen
                 NaiveDate::parse_from_str(&booking.checkout_date, "%Y-%m-%d")
@@ -2153,537 +2473,1431 @@ async fn calculate_batch_booking_prices_pg(
         }
 
         // Fallback: Neuberechnung für alte Buchungen ohne Snapshots
-        // Get room for price calculation
-        let room = match RoomRepository::get_by_id(&pool, booking.room_id).await {
-            Ok(r) => r,
-            Err(e) => {
-                println!("⚠️ Could not load room for booking {}: {}", booking_id, e);
-                continue;
-            }
-        };
-
-        // Get guest for member status
-        let guest = match GuestRepository::get_by_id(&pool, booking.guest_id).await {
-            Ok(g) => g,
+        let breakdown = match calculate_stored_booking_price(&pool, &booking, &pricing_settings).await {
+            Ok(b) => b,
             Err(e) => {
-                println!("⚠️ Could not load guest for booking {}: {}", booking_id, e);
+                println!("⚠️ Could not calculate price for booking {}: {}", booking_id, e);
                 continue;
             }
         };
 
-        // Calculate nights
-        let checkin_date = match NaiveDate::parse_from_str(&booking.checkin_date, "%Y-%m-%d") {
-            Ok(d) => d,
-            Err(_) => continue,
-        };
-        let checkout_date = match NaiveDate::parse_from_str(&booking.checkout_date, "%Y-%m-%d") {
-            Ok(d) => d,
-            Err(_) => continue,
-        };
-        let nights = (checkout_date - checkin_date).num_days() as i32;
-
-        // Fix 5: Per-night season calculation
-        let ns_price = room.nebensaison_preis.unwrap_or(0.0);
-        let hs_price = room.hauptsaison_preis.unwrap_or(ns_price);
-
-        let base_price = if pricing_settings.hauptsaison_aktiv.unwrap_or(false) {
-            let start_str = pricing_settings.hauptsaison_start.clone().unwrap_or("06-01".to_string());
-            let ende_str = pricing_settings.hauptsaison_ende.clone().unwrap_or("08-31".to_string());
-            let mut total = 0.0;
-            for offset in 0..nights {
-                let night_date = checkin_date + chrono::Duration::days(offset as i64);
-                let night_mmdd = format!("{:02}-{:02}", night_date.month(), night_date.day());
-                let is_hs = if start_str > ende_str {
-                    night_mmdd >= start_str || night_mmdd <= ende_str
-                } else {
-                    night_mmdd >= start_str && night_mmdd <= ende_str
-                };
-                total += if is_hs { hs_price } else { ns_price };
-            }
-            total
-        } else {
-            ns_price * nights as f64
-        };
+        results.push(BookingPriceResult {
+            booking_id,
+            nights: breakdown.nights,
+            base_price: breakdown.base_price,
+            services_total: breakdown.services_total,
+            discounts_total: breakdown.discounts_total,
+            total: breakdown.total,
+        });
+    }
 
-        // Get services for this booking
-        let services = AdditionalServiceRepository::get_by_booking(&pool, booking_id)
-            .await
-            .unwrap_or_default();
-
-        // Two-pass service calculation for correct applies_to handling
-        let mut first_pass_total: f64 = 0.0;
-        for s in &services {
-            if s.price_type == "fixed" || s.applies_to == "overnight_price" {
-                let calculated = if s.price_type == "percent" {
-                    base_price * (s.original_value / 100.0)
-                } else {
-                    s.original_value
-                };
-                first_pass_total += calculated;
-            }
-        }
+    println!("✅ Calculated prices for {} bookings", results.len());
+    Ok(results)
+}
 
-        let total_price_base = base_price + first_pass_total;
-        let mut second_pass_total: f64 = 0.0;
-        for s in &services {
-            if s.price_type == "percent" && s.applies_to == "total_price" {
-                second_pass_total += total_price_base * (s.original_value / 100.0);
-            }
+/// Helper: Recalculate prices after the companions of a booking changed.
+/// Only rooms with occupancy pricing depend on companions (birthdates → child prices).
+async fn recalculate_after_companion_change(pool: &DbPool, booking_id: i64) {
+    let booking = match BookingRepository::get_by_id(pool, booking_id as i32).await {
+        Ok(b) => b,
+        Err(e) => {
+            eprintln!("⚠️ Could not load booking {} for price recalculation: {}", booking_id, e);
+            return;
         }
+    };
+    let has_occupancy_pricing = RoomRepository::get_by_id(pool, booking.room_id).await
+        .map(|room| room.basis_belegung.is_some())
+        .unwrap_or(false);
 
-        let services_total = first_pass_total + second_pass_total;
-
-        // Get discounts for this booking
-        let discounts = DiscountRepository::get_by_booking(&pool, booking_id)
-            .await
-            .unwrap_or_default();
-
-        // Calculate manual discounts total
-        let subtotal_before_discount = base_price + services_total;
-        let mut discounts_total: f64 = discounts.iter().map(|d| {
-            // Use stored calculated_amount if available
-            d.calculated_amount.unwrap_or_else(|| {
-                if d.discount_type == "percent" {
-                    subtotal_before_discount * (d.discount_value) / 100.0
-                } else {
-                    d.discount_value
-                }
-            })
-        }).sum();
-
-        // Apply automatic DPolG member discount from PricingSettings
-        if guest.dpolg_mitglied && pricing_settings.mitglieder_rabatt_aktiv.unwrap_or(false) {
-            let rabatt_prozent = pricing_settings.mitglieder_rabatt_prozent.unwrap_or(15.0);
-            let rabatt_basis = pricing_settings.rabatt_basis.clone().unwrap_or("zimmerpreis".to_string());
-
-            let rabatt_base = if rabatt_basis == "gesamtpreis" {
-                subtotal_before_discount
-            } else {
-                base_price
-            };
-
-            let auto_rabatt = rabatt_base * (rabatt_prozent / 100.0);
-
-            let has_dpolg_rabatt = discounts.iter()
-                .any(|d| d.discount_name.to_lowercase().contains("dpolg")
-                      || d.discount_name.to_lowercase().contains("mitglieder"));
+    // Without occupancy pricing only the Kurtaxe depends on the companions (age exemption)
+    let result = if has_occupancy_pricing {
+        recalculate_and_save_booking_prices(pool, booking.id).await
+    } else {
+        save_kurtaxe_snapshot(pool, booking.id).await
+    };
+    if let Err(e) = result {
+        eprintln!("⚠️ Failed to recalculate prices: {}", e);
+    }
+}
 
-            if !has_dpolg_rabatt && auto_rabatt > 0.0 {
-                discounts_total += auto_rabatt;
-            }
-        }
+/// Helper: Update only the Kurtaxe snapshot of a booking.
+/// Kurtaxe is not part of gesamtpreis, so the stored prices stay untouched.
+async fn save_kurtaxe_snapshot(pool: &DbPool, booking_id: i32) -> Result<(), String> {
+    let booking = BookingRepository::get_by_id(pool, booking_id).await
+        .map_err(|e| format!("Failed to load booking: {}", e))?;
+    let pricing_settings = PricingSettingsRepository::get(pool)
+        .await
+        .unwrap_or_else(|_| pricing::default_pricing_settings());
 
-        let total = base_price + services_total - discounts_total;
+    let breakdown = calculate_stored_booking_price(pool, &booking, &pricing_settings).await?;
+    let kurtaxe = breakdown.kurtaxe.as_ref();
 
-        results.push(BookingPriceResult {
-            booking_id,
-            nights,
-            base_price,
-            services_total,
-            discounts_total,
-            total,
-        });
-    }
+    let client = pool.get().await
+        .map_err(|e| format!("Failed to get DB connection: {}", e))?;
+    client.execute(
+        "UPDATE bookings SET kurtaxe = $1, kurtaxe_personen = $2, kurtaxe_befreit = $3 WHERE id = $4",
+        &[
+            &kurtaxe.map(|k| k.total),
+            &kurtaxe.map(|k| k.persons),
+            &kurtaxe.map(|k| k.exempt_persons),
+            &booking_id,
+        ],
+    ).await.map_err(|e| format!("Failed to update Kurtaxe: {}", e))?;
 
-    println!("✅ Calculated prices for {} bookings", results.len());
-    Ok(results)
+    println!("🏛️ [KURTAXE] Booking {}: {:.2}€", booking_id, breakdown.kurtaxe_total);
+    Ok(())
 }
 
 /// Helper: Recalculate and update price snapshot for a booking
-/// Called automatically when booking dates/room/guest change
+/// Called automatically when booking dates/room/guest/guest count change
 async fn recalculate_and_save_booking_prices(
     pool: &DbPool,
     booking_id: i32,
 ) -> Result<(), String> {
-    use crate::database_pg::repositories::{
-        BookingRepository, GuestRepository, RoomRepository, AdditionalServiceRepository, DiscountRepository, PricingSettingsRepository
-    };
-    use chrono::{NaiveDate, Datelike};
-
     println!("💰 [PRICE RECALC] Recalculating prices for booking {}", booking_id);
 
-    // 1. Load booking
+    // 1. Load booking and pricing settings
     let booking = BookingRepository::get_by_id(pool, booking_id).await
         .map_err(|e| format!("Failed to load booking: {}", e))?;
 
-    // 2. Load guest (for DPolG member status)
-    let guest = GuestRepository::get_by_id(pool, booking.guest_id).await
-        .map_err(|e| format!("Failed to load guest: {}", e))?;
+    let pricing_settings = PricingSettingsRepository::get(pool)
+        .await
+        .unwrap_or_else(|_| pricing::default_pricing_settings());
 
-    // 3. Load services and discounts
-    let services = AdditionalServiceRepository::get_by_booking(pool, booking_id as i64).await
-        .map_err(|e| format!("Failed to load services: {}", e))?;
-    let discounts = DiscountRepository::get_by_booking(pool, booking_id as i64).await
-        .map_err(|e| format!("Failed to load discounts: {}", e))?;
+    // 2. Calculate prices with the pricing engine (same logic as the booking form)
+    let breakdown = calculate_stored_booking_price(pool, &booking, &pricing_settings).await?;
 
-    println!("🔍 [DEBUG] Loaded {} discounts from DB:", discounts.len());
-    for d in &discounts {
-        println!("  - ID: {}, Name: '{}', Type: {}, Value: {}, calculated_amount: {:?}",
-                 d.id, d.discount_name, d.discount_type, d.discount_value, d.calculated_amount);
-    }
+    println!("📊 [PRICE RECALC] Calculated: base={:.2}€, services={:.2}€, discounts={:.2}€, total={:.2}€, nights={}",
+             breakdown.base_price, breakdown.services_total, breakdown.discounts_total, breakdown.total, breakdown.nights);
 
-    // 4. Convert to ServiceInput and DiscountInput
-    let service_inputs: Vec<ServiceInput> = services.iter().map(|s| ServiceInput {
-        service_name: s.service_name.clone(),
-        price_type: s.price_type.clone(),
-        original_value: s.original_value,
-        applies_to: s.applies_to.clone(),
-    }).collect();
+    // 3. Update booking with new prices (raw SQL to avoid circular dependencies)
+    let client = pool.get().await
+        .map_err(|e| format!("Failed to get DB connection: {}", e))?;
 
-    let discount_inputs: Vec<DiscountInput> = discounts.iter().map(|d| DiscountInput {
-        discount_name: d.discount_name.clone(),
-        price_type: d.discount_type.clone(),
-        original_value: d.discount_value,
-        applies_to: "total_price".to_string(),
-    }).collect();
+    client.execute(
+        "UPDATE bookings SET
+            grundpreis = $1,
+            services_preis = $2,
+            rabatt_preis = $3,
+            gesamtpreis = $4,
+            anzahl_naechte = $5,
+            belegung_preis = $6,
+            kurtaxe = $7,
+            kurtaxe_personen = $8,
+            kurtaxe_befreit = $9,
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = $10",
+        &[
+            &breakdown.base_price,
+            &breakdown.services_total,
+            &breakdown.discounts_total,
+            &breakdown.total,
+            &breakdown.nights,
+            &breakdown.occupancy_total,
+            &breakdown.kurtaxe.as_ref().map(|k| k.total),
+            &breakdown.kurtaxe.as_ref().map(|k| k.persons),
+            &breakdown.kurtaxe.as_ref().map(|k| k.exempt_persons),
+            &booking_id,
+        ],
+    ).await.map_err(|e| format!("Failed to update prices: {}", e))?;
 
-    // 5. Calculate prices using the batch calculation logic (reuse existing logic)
-    // This is a simplified version that reuses the batch calculation approach
-    let pricing_settings = PricingSettingsRepository::get(pool)
-        .await
-        .unwrap_or_else(|_| database_pg::PricingSettings {
-            id: 1,
-            hauptsaison_aktiv: Some(false),
-            hauptsaison_start: Some("06-01".to_string()),
-            hauptsaison_ende: Some("08-31".to_string()),
-            mitglieder_rabatt_aktiv: Some(false),
-            mitglieder_rabatt_prozent: Some(15.0),
-            rabatt_basis: Some("zimmerpreis".to_string()),
-            updated_at: None,
-        });
+    println!("✅ [PRICE RECALC] Prices saved to bookings table for booking {}", booking_id);
+
+    // 4. Update calculated_amount for each manual discount in database
+    let booking_id_i64 = booking_id as i64;  // Cast to i64 for PostgreSQL queries
 
-    let checkin_date = NaiveDate::parse_from_str(&booking.checkin_date, "%Y-%m-%d")
-        .map_err(|e| format!("Invalid check-in date: {}", e))?;
-    let checkout_date = NaiveDate::parse_from_str(&booking.checkout_date, "%Y-%m-%d")
-        .map_err(|e| format!("Invalid check-out date: {}", e))?;
-    let nights = (checkout_date - checkin_date).num_days() as i32;
+    for discount in breakdown.discounts.iter().filter(|d| !d.is_automatic) {
+        let rows_affected = client.execute(
+            "UPDATE discounts SET calculated_amount = $1 WHERE booking_id = $2 AND discount_name = $3",
+            &[&discount.calculated_amount, &booking_id_i64, &discount.name],
+        ).await.map_err(|e| format!("Failed to update discount calculated_amount: {}", e))?;
 
-    if nights <= 0 {
-        return Err("Check-out must be after check-in".to_string());
+        if rows_affected == 0 {
+            println!("    ⚠️  WARNING: Discount '{}' NOT FOUND in DB for booking {}", discount.name, booking_id);
+        } else {
+            println!("📊 [PRICE RECALC] Updated discount '{}' calculated_amount to {:.2}€", discount.name, discount.calculated_amount);
+        }
     }
 
-    // 5. Load room for current prices
-    let room = RoomRepository::get_by_id(pool, booking.room_id).await
-        .map_err(|e| format!("Failed to load room: {}", e))?;
+    // 5. Handle automatic DPolG discount - insert or update in DB
+    if let Some(auto_discount) = breakdown.automatic_discount() {
+        let existing = client.query_opt(
+            "SELECT id FROM discounts WHERE booking_id = $1 AND discount_name = $2",
+            &[&booking_id_i64, &auto_discount.name],
+        ).await.map_err(|e| format!("Failed to query auto discount: {}", e))?;
+
+        if let Some(row) = existing {
+            let discount_id: i64 = row.get(0);  // discounts.id is BIGINT (i64)
+            client.execute(
+                "UPDATE discounts SET calculated_amount = $1, discount_value = $2 WHERE id = $3",
+                &[&auto_discount.calculated_amount, &auto_discount.original_value, &discount_id],
+            ).await.map_err(|e| format!("Failed to update auto discount: {}", e))?;
 
-    // 6. Calculate base price with per-night season logic
-    let nebensaison_price = room.nebensaison_preis.unwrap_or(0.0);
-    let hauptsaison_price = room.hauptsaison_preis.unwrap_or(nebensaison_price);
+            println!("📊 [PRICE RECALC] Updated auto DPolG discount (ID: {}) calculated_amount to {:.2}€", discount_id, auto_discount.calculated_amount);
+        } else {
+            client.execute(
+                "INSERT INTO discounts (booking_id, discount_name, discount_type, discount_value, calculated_amount)
+                 VALUES ($1, $2, 'percent', $3, $4)",
+                &[&booking_id_i64, &auto_discount.name, &auto_discount.original_value, &auto_discount.calculated_amount],
+            ).await.map_err(|e| format!("Failed to insert auto discount: {}", e))?;
 
-    let base_price = if pricing_settings.hauptsaison_aktiv.unwrap_or(false) {
-        let start_str = pricing_settings.hauptsaison_start.clone().unwrap_or("06-01".to_string());
-        let ende_str = pricing_settings.hauptsaison_ende.clone().unwrap_or("08-31".to_string());
+            println!("📊 [PRICE RECALC] Created auto DPolG discount with calculated_amount {:.2}€", auto_discount.calculated_amount);
+        }
+    }
 
-        let mut total = 0.0;
-        for offset in 0..nights {
-            let night_date = checkin_date + chrono::Duration::days(offset as i64);
-            let night_mmdd = format!("{:02}-{:02}", night_date.month(), night_date.day());
+    println!("✅ [PRICE RECALC] All prices and discounts updated for booking {}", booking_id);
 
-            let is_hs = if start_str > ende_str {
-                night_mmdd >= start_str || night_mmdd <= ende_str
-            } else {
-                night_mmdd >= start_str && night_mmdd <= ende_str
-            };
+    Ok(())
+}
 
-            if is_hs {
-                total += hauptsaison_price;
-            } else {
-                total += nebensaison_price;
-            }
-        }
-        total
+/// TODO: Implement room availability check with PostgreSQL
+#[tauri::command]
+async fn check_room_availability_pg(
+    pool: State<'_, DbPool>,
+    room_id: i32,
+    checkin: String,
+    checkout: String,
+    exclude_booking_id: Option<i32>,
+) -> Result<bool, String> {
+    println!("🏠 Checking room availability: room_id={}, {} to {}, exclude={:?}",
+             room_id, checkin, checkout, exclude_booking_id);
+
+    let client = pool.get().await.map_err(|e| e.to_string())?;
+
+    // Check for overlapping bookings
+    // Date overlap logic: (checkin < other_checkout) AND (checkout > other_checkin)
+    let query = if let Some(exclude_id) = exclude_booking_id {
+        "SELECT COUNT(*) as count
+         FROM bookings
+         WHERE room_id = $1
+           AND checkin_date < $3
+           AND checkout_date > $2
+           AND status != 'storniert'
+           AND id != $4"
     } else {
-        nebensaison_price * nights as f64
+        "SELECT COUNT(*) as count
+         FROM bookings
+         WHERE room_id = $1
+           AND checkin_date < $3
+           AND checkout_date > $2
+           AND status != 'storniert'"
     };
 
-    println!("📊 [PRICE RECALC] Base price calculated: {:.2}€ for {} nights (NS: {:.2}€, HS: {:.2}€)",
-             base_price, nights, nebensaison_price, hauptsaison_price);
+    let row = if let Some(exclude_id) = exclude_booking_id {
+        client.query_one(query, &[&room_id, &checkin, &checkout, &exclude_id])
+            .await
+            .map_err(|e| format!("Fehler bei Verfügbarkeitsprüfung: {}", e))?
+    } else {
+        client.query_one(query, &[&room_id, &checkin, &checkout])
+            .await
+            .map_err(|e| format!("Fehler bei Verfügbarkeitsprüfung: {}", e))?
+    };
 
-    // 7. Calculate services (Two-Pass Algorithm)
-    let mut first_pass_total = 0.0;
+    let count: i64 = row.get("count");
 
-    // Pass 1: Fixed services and services that apply to overnight_price
-    for s in &service_inputs {
-        if s.price_type == "fixed" || s.applies_to == "overnight_price" {
-            let calculated = if s.price_type == "percent" {
-                base_price * (s.original_value / 100.0)
-            } else {
-                s.original_value
-            };
-            first_pass_total += calculated;
-        }
-    }
+    // Rooms held for an allotment count as occupied for everybody else
+    let held = AllotmentRepository::held_conflicts(&**client, room_id, &checkin, &checkout, exclude_booking_id, None)
+        .await
+        .map_err(|e| format!("Fehler bei Verfügbarkeitsprüfung: {}", e))?;
+    let blocks = RoomBlockRepository::overlapping(&**client, room_id, &checkin, &checkout)
+        .await
+        .map_err(|e| format!("Fehler bei Verfügbarkeitsprüfung: {}", e))?;
+    let is_available = count == 0 && held.is_empty() && blocks.is_empty();
 
-    // 8. Add cleaning fee from room if not in services (consistency with calculate_full_booking_price_pg)
-    let has_endreinigung = service_inputs.iter()
-        .any(|s| s.service_name.to_lowercase().contains("endreinigung")
-              || s.service_name.to_lowercase().contains("cleaning"));
+    println!("✅ Room {} is {}: {} overlapping bookings found, {} allotment conflicts, {} blocks",
+             room_id,
+             if is_available { "available" } else { "NOT available" },
+             count,
+             held.len(),
+             blocks.len());
 
-    if !has_endreinigung {
-        if let Some(endreinigung) = room.endreinigung {
-            if endreinigung > 0.0 {
-                first_pass_total += endreinigung;
-                println!("💶 [PRICE RECALC] Added cleaning fee: {:.2}€", endreinigung);
-            }
+    Ok(is_available)
+}
+
+// ============================================================================
+// BOOKING GROUP COMMANDS (multi-room group bookings)
+// ============================================================================
+
+/// Book several rooms for one organiser at once (all or nothing).
+/// Prices of the room bookings are calculated with the pricing engine afterwards.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn create_booking_group_pg(
+    pool: State<'_, DbPool>,
+    name: String,
+    organiser_guest_id: i32,
+    payment_recipient_id: Option<i32>,
+    status: BookingStatus,
+    bemerkungen: Option<String>,
+    rooms: Vec<database_pg::GroupRoomInput>,
+    current_user: Option<String>,
+) -> Result<database_pg::BookingGroupWithRooms, String> {
+    println!("👥 create_booking_group_pg called: '{}' with {} rooms", name, rooms.len());
+
+    let group = BookingGroupRepository::create_with_bookings(
+        &pool,
+        name,
+        organiser_guest_id,
+        payment_recipient_id,
+        status,
+        bemerkungen,
+        &rooms,
+        current_user,
+    )
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error creating booking group: {}", e);
+        match e {
+            database_pg::DbError::DoubleBookingError(msg) => format!("DOUBLE_BOOKING:{}", msg),
+            _ => e.to_string(),
         }
-    }
+    })?;
 
-    // Pass 2: Percent services that apply to total_price
-    let total_price_base = base_price + first_pass_total;
-    for s in &service_inputs {
-        if s.price_type == "percent" && s.applies_to == "total_price" {
-            first_pass_total += total_price_base * (s.original_value / 100.0);
+    for room in &group.rooms {
+        if let Err(e) = recalculate_and_save_booking_prices(&pool, room.booking.id).await {
+            eprintln!("⚠️ Failed to calculate price for booking {}: {}", room.booking.id, e);
+        }
+        if let Err(e) = turso_sync::sync_booking_tasks_to_turso(&pool, room.booking.id).await {
+            eprintln!("⚠️ Failed to sync cleaning tasks to Turso: {}", e);
         }
     }
 
-    let services_total = first_pass_total;
+    println!("✅ Booking group {} created ({} rooms)", group.group.reservierungsnummer, group.rooms.len());
+    BookingGroupRepository::get_with_rooms(&pool, group.group.id)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    // 9. Calculate discounts - track calculations for DB update
-    let mut discounts_total = 0.0;
-    let subtotal_before_discount = base_price + services_total;
-    let mut discount_calculations = Vec::new();
+#[tauri::command]
+async fn get_booking_groups_pg(pool: State<'_, DbPool>) -> Result<Vec<database_pg::BookingGroup>, String> {
+    BookingGroupRepository::get_all(&pool).await.map_err(|e| e.to_string())
+}
 
-    // Load rabatt_basis for all percentage discounts
-    let rabatt_basis = pricing_settings.rabatt_basis.clone().unwrap_or("zimmerpreis".to_string());
-    let discount_base = if rabatt_basis == "gesamtpreis" {
-        subtotal_before_discount
-    } else {
-        base_price
-    };
+#[tauri::command]
+async fn get_booking_group_pg(pool: State<'_, DbPool>, id: i32) -> Result<database_pg::BookingGroupWithRooms, String> {
+    BookingGroupRepository::get_with_rooms(&pool, id).await.map_err(|e| e.to_string())
+}
 
-    println!("🔍 [DEBUG] Using rabatt_basis: '{}' for all percentage discounts", rabatt_basis);
-    println!("🔍 [DEBUG] Discount base amount: {:.2}€ (subtotal_before_discount: {:.2}€)", discount_base, subtotal_before_discount);
-    println!("🔍 [DEBUG] Calculating discounts:");
+#[tauri::command]
+async fn update_booking_group_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    name: String,
+    payment_recipient_id: Option<i32>,
+    bemerkungen: Option<String>,
+) -> Result<database_pg::BookingGroup, String> {
+    BookingGroupRepository::update(&pool, id, name, payment_recipient_id, bemerkungen)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    for d in &discount_inputs {
-        let calculated = if d.price_type == "percent" {
-            // Use same logic as Auto-DPolG: apply rabatt_basis setting
-            discount_base * (d.original_value / 100.0)
-        } else {
-            d.original_value
-        };
-        discounts_total += calculated;
-        discount_calculations.push((d.discount_name.clone(), calculated));
-        println!("  - '{}' ({}): {:.2}% = {:.2}€", d.discount_name, d.price_type, d.original_value, calculated);
+/// Names of the people staying in one room of a group
+#[tauri::command]
+async fn set_booking_guest_names_pg(
+    pool: State<'_, DbPool>,
+    booking_id: i32,
+    guest_names: Vec<String>,
+) -> Result<(), String> {
+    BookingGroupRepository::set_guest_names(&pool, booking_id, &guest_names)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Cancel all open room bookings of a group and its consolidated invoice
+#[tauri::command]
+async fn cancel_booking_group_command(pool: State<'_, DbPool>, group_id: i32) -> Result<(), String> {
+    println!("🚫 Cancelling booking group {}", group_id);
+
+    let bookings = BookingGroupRepository::get_bookings(&pool, group_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Gruppe: {}", e))?;
+
+    // Checked-in rooms block the cancellation, already cancelled rooms are skipped
+    let open: Vec<_> = bookings.into_iter().filter(|b| b.status != BookingStatus::Storniert).collect();
+    for booking in &open {
+        booking
+            .status
+            .validate_transition(BookingStatus::Storniert)
+            .map_err(|e| format!("Fehler beim Stornieren ({}): {}", booking.reservierungsnummer, e))?;
     }
 
-    // Apply automatic DPolG member discount
-    let mut auto_dpolg_discount: Option<(String, f64)> = None;
-    println!("🔍 [DEBUG] Auto DPolG check: is_member={}, rabatt_aktiv={}",
-             guest.dpolg_mitglied, pricing_settings.mitglieder_rabatt_aktiv.unwrap_or(false));
+    // Rooms first, the Stornorechnung last: a credit note can never be deleted again
+    for booking in open {
+        let (previous, cancelled) = BookingRepository::change_status(&pool, booking.id, BookingStatus::Storniert, None)
+            .await
+            .map_err(|e| format!("Fehler beim Stornieren: {}", e))?;
+        run_status_effects(&pool, &cancelled, previous, None).await;
+    }
 
-    if guest.dpolg_mitglied && pricing_settings.mitglieder_rabatt_aktiv.unwrap_or(false) {
-        let rabatt_prozent = pricing_settings.mitglieder_rabatt_prozent.unwrap_or(15.0);
-        let rabatt_basis = pricing_settings.rabatt_basis.clone().unwrap_or("zimmerpreis".to_string());
+    let active_invoice = InvoiceRepository::get_active_for_group(&pool, group_id)
+        .await
+        .map_err(|e| format!("Gruppe storniert, Fehler beim Laden der Rechnung: {}", e))?;
+    if let Some(original) = active_invoice {
+        issue_cancellation(&pool, &original, "Stornierung der Gruppenbuchung")
+            .await
+            .map_err(|e| format!("Gruppe storniert, die Rechnung bitte manuell stornieren: {}", e))?;
+    }
 
-        let rabatt_base = if rabatt_basis == "gesamtpreis" {
-            subtotal_before_discount
-        } else {
-            base_price
-        };
+    println!("✅ Booking group {} cancelled", group_id);
+    Ok(())
+}
 
-        let auto_rabatt = rabatt_base * (rabatt_prozent / 100.0);
+// ============================================================================
+// BOOKING SERIES COMMANDS (recurring bookings of regular seminar groups)
+// ============================================================================
 
-        let has_dpolg_rabatt = discount_inputs.iter()
-            .any(|d| d.discount_name.to_lowercase().contains("dpolg")
-                  || d.discount_name.to_lowercase().contains("mitglieder"));
+/// Create a series and book every occurrence with the availability check of a single booking.
+/// Occurrences that are not free are returned in `conflicts`, the others are booked.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn create_booking_series_pg(
+    pool: State<'_, DbPool>,
+    name: String,
+    guest_id: i32,
+    payment_recipient_id: Option<i32>,
+    status: BookingStatus,
+    room_ids: Vec<i32>,
+    anzahl_gaeste: i32,
+    first_checkin: String,
+    nights: i32,
+    rule: booking_series::RecurrenceRule,
+    bemerkungen: Option<String>,
+    current_user: Option<String>,
+) -> Result<database_pg::BookingSeriesWithBookings, String> {
+    println!("🔁 create_booking_series_pg called: '{}' ({} {}, {} rooms)", name, rule.frequency, first_checkin, room_ids.len());
 
-        println!("🔍 [DEBUG] Auto DPolG: basis={}, base={:.2}€, prozent={:.2}%, calculated={:.2}€, already_has_dpolg={}",
-                 rabatt_basis, rabatt_base, rabatt_prozent, auto_rabatt, has_dpolg_rabatt);
+    let created = BookingSeriesRepository::create_with_bookings(
+        &pool,
+        name,
+        guest_id,
+        payment_recipient_id,
+        status,
+        room_ids,
+        anzahl_gaeste,
+        first_checkin,
+        nights,
+        &rule,
+        bemerkungen,
+        current_user,
+    )
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error creating booking series: {}", e);
+        match e {
+            database_pg::DbError::DoubleBookingError(msg) => format!("DOUBLE_BOOKING:{}", msg),
+            _ => e.to_string(),
+        }
+    })?;
 
-        if !has_dpolg_rabatt && auto_rabatt > 0.0 {
-            discounts_total += auto_rabatt;
-            let discount_name = format!("DPolG Mitgliederrabatt ({}%)", rabatt_prozent as i32);
-            auto_dpolg_discount = Some((discount_name.clone(), auto_rabatt));
-            discount_calculations.push((discount_name.clone(), auto_rabatt));
-            println!("  ✅ Adding auto DPolG discount: '{}' = {:.2}€", discount_name, auto_rabatt);
+    for booking in &created.bookings {
+        if let Err(e) = recalculate_and_save_booking_prices(&pool, booking.id).await {
+            eprintln!("⚠️ Failed to calculate price for booking {}: {}", booking.id, e);
+        }
+        if let Err(e) = turso_sync::sync_booking_tasks_to_turso(&pool, booking.id).await {
+            eprintln!("⚠️ Failed to sync cleaning tasks to Turso: {}", e);
         }
     }
 
-    let total = base_price + services_total - discounts_total;
-
-    println!("📊 [PRICE RECALC] Calculated: base={:.2}€, services={:.2}€, discounts={:.2}€, total={:.2}€, nights={}",
-             base_price, services_total, discounts_total, total, nights);
+    println!(
+        "✅ Booking series {} created ({} bookings, {} conflicts)",
+        created.series.reservierungsnummer,
+        created.bookings.len(),
+        created.conflicts.len()
+    );
+    let mut result = BookingSeriesRepository::get_with_bookings(&pool, created.series.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    result.conflicts = created.conflicts;
+    Ok(result)
+}
 
-    // 10. Update booking with new prices (raw SQL to avoid circular dependencies)
-    let client = pool.get().await
-        .map_err(|e| format!("Failed to get DB connection: {}", e))?;
+#[tauri::command]
+async fn get_booking_series_list_pg(pool: State<'_, DbPool>) -> Result<Vec<database_pg::BookingSeries>, String> {
+    BookingSeriesRepository::get_all(&pool).await.map_err(|e| e.to_string())
+}
 
-    client.execute(
-        "UPDATE bookings SET
-            grundpreis = $1,
-            services_preis = $2,
-            rabatt_preis = $3,
-            gesamtpreis = $4,
-            anzahl_naechte = $5,
-            updated_at = CURRENT_TIMESTAMP
-         WHERE id = $6",
-        &[
-            &base_price,
-            &services_total,
-            &discounts_total,
-            &total,
-            &nights,
-            &booking_id,
-        ],
-    ).await.map_err(|e| format!("Failed to update prices: {}", e))?;
+#[tauri::command]
+async fn get_booking_series_pg(pool: State<'_, DbPool>, id: i32) -> Result<database_pg::BookingSeriesWithBookings, String> {
+    BookingSeriesRepository::get_with_bookings(&pool, id).await.map_err(|e| e.to_string())
+}
 
-    println!("✅ [PRICE RECALC] Prices saved to bookings table for booking {}", booking_id);
+/// Bulk edit of a series: applies to all open occurrences from `from_date` on (default: today)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn update_booking_series_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    name: String,
+    payment_recipient_id: Option<i32>,
+    anzahl_gaeste: i32,
+    bemerkungen: Option<String>,
+    from_date: Option<String>,
+    current_user: Option<String>,
+) -> Result<database_pg::BookingSeriesWithBookings, String> {
+    let from_date = from_date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    println!("🔁 update_booking_series_pg called: series {} from {}", id, from_date);
 
-    // 11. Update calculated_amount for each discount in database
-    println!("🔍 [DEBUG] Starting discount calculated_amount updates ({} discounts)...", discount_calculations.len());
-    let booking_id_i64 = booking_id as i64;  // Cast to i64 for PostgreSQL queries
+    let changed = BookingSeriesRepository::update(
+        &pool,
+        id,
+        name,
+        payment_recipient_id,
+        anzahl_gaeste,
+        bemerkungen,
+        &from_date,
+        current_user,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
 
-    for (discount_name, calculated_amount) in &discount_calculations {
-        println!("  🔄 Processing: '{}' = {:.2}€", discount_name, calculated_amount);
-        // Skip auto DPolG discount - handle separately
-        if let Some((auto_name, _)) = &auto_dpolg_discount {
-            if discount_name == auto_name {
-                println!("    ⏭️  Skipping auto DPolG (handled separately)");
-                continue;
-            }
+    // Guests per room change the price (Kurtaxe, per-person services)
+    for booking in &changed {
+        if let Err(e) = recalculate_and_save_booking_prices(&pool, booking.id).await {
+            eprintln!("⚠️ Failed to recalculate prices: {}", e);
         }
+    }
 
-        // Find discount ID by name
-        println!("    🔎 Searching for discount in DB: booking_id={}, name='{}'", booking_id, discount_name);
-        let discount_row = client.query_opt(
-            "SELECT id FROM discounts WHERE booking_id = $1 AND discount_name = $2",
-            &[&booking_id_i64, discount_name],
-        ).await.map_err(|e| format!("Failed to query discount: {}", e))?;
-
-        if let Some(row) = discount_row {
-            let discount_id: i64 = row.get(0);  // discounts.id is BIGINT (i64)
-            println!("    ✅ Found discount ID: {}", discount_id);
+    BookingSeriesRepository::get_with_bookings(&pool, id)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-            let rows_affected = client.execute(
-                "UPDATE discounts SET calculated_amount = $1 WHERE id = $2",
-                &[calculated_amount, &discount_id],
-            ).await.map_err(|e| format!("Failed to update discount calculated_amount: {}", e))?;
+/// Bulk room change of a series from `from_date` on (default: today). Occurrences whose new
+/// room is not free stay in the old room and are returned in `conflicts`.
+#[tauri::command]
+async fn move_booking_series_room_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    from_room_id: i32,
+    to_room_id: i32,
+    from_date: Option<String>,
+) -> Result<database_pg::BookingSeriesWithBookings, String> {
+    let from_date = from_date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    println!("🔁 move_booking_series_room_pg called: series {} room {} -> {} from {}", id, from_room_id, to_room_id, from_date);
+
+    let (moved, conflicts) = BookingSeriesRepository::move_room(&pool, id, from_room_id, to_room_id, &from_date)
+        .await
+        .map_err(|e| e.to_string())?;
 
-            println!("    💾 UPDATE executed: {} rows affected", rows_affected);
-            println!("📊 [PRICE RECALC] Updated discount '{}' (ID: {}) calculated_amount to {:.2}€", discount_name, discount_id, calculated_amount);
-        } else {
-            println!("    ⚠️  WARNING: Discount '{}' NOT FOUND in DB for booking {}", discount_name, booking_id);
+    for booking in &moved {
+        if let Err(e) = turso_sync::sync_booking_tasks_to_turso(&pool, booking.id).await {
+            eprintln!("⚠️ Failed to sync cleaning tasks to Turso: {}", e);
+        }
+        if let Err(e) = recalculate_and_save_booking_prices(&pool, booking.id).await {
+            eprintln!("⚠️ Failed to recalculate prices: {}", e);
+        }
+        if let Err(e) = offer_freed_room_to_waitlist(&pool, from_room_id, &booking.checkin_date, &booking.checkout_date).await {
+            eprintln!("⚠️ Failed to offer freed room to waitlist: {}", e);
         }
     }
 
-    // 12. Handle automatic DPolG discount - insert or update in DB
-    if let Some((discount_name, calculated_amount)) = auto_dpolg_discount {
-        println!("🔍 [DEBUG] Handling auto DPolG discount in DB: '{}' = {:.2}€", discount_name, calculated_amount);
-        let rabatt_prozent = pricing_settings.mitglieder_rabatt_prozent.unwrap_or(15.0);
+    let mut result = BookingSeriesRepository::get_with_bookings(&pool, id)
+        .await
+        .map_err(|e| e.to_string())?;
+    result.conflicts = conflicts;
+    Ok(result)
+}
+
+/// Cancel all open occurrences of a series from `from_date` on (default: today).
+/// Past and checked-in occurrences are kept. Returns the number of cancelled bookings.
+#[tauri::command]
+async fn cancel_booking_series_command(
+    pool: State<'_, DbPool>,
+    series_id: i32,
+    from_date: Option<String>,
+) -> Result<usize, String> {
+    let from_date = from_date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+    println!("🚫 Cancelling booking series {} from {}", series_id, from_date);
+
+    let open = BookingSeriesRepository::get_open_bookings(&pool, series_id, &from_date)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Serie: {}", e))?;
+
+    for booking in &open {
+        // Booking first, the Stornorechnung last: a credit note can never be deleted again
+        let (previous, cancelled) = BookingRepository::change_status(&pool, booking.id, BookingStatus::Storniert, None)
+            .await
+            .map_err(|e| format!("Fehler beim Stornieren ({}): {}", booking.reservierungsnummer, e))?;
+        run_status_effects(&pool, &cancelled, previous, None).await;
+
+        let active_invoice = InvoiceRepository::get_active_for_booking(&pool, booking.id)
+            .await
+            .map_err(|e| format!("{} storniert, Fehler beim Laden der Rechnung: {}", booking.reservierungsnummer, e))?;
+        if let Some(original) = active_invoice {
+            issue_cancellation(&pool, &original, "Stornierung der Serienbuchung")
+                .await
+                .map_err(|e| format!("{} storniert, die Rechnung bitte manuell stornieren: {}", booking.reservierungsnummer, e))?;
+        }
+    }
+
+    println!("✅ Booking series {}: {} bookings cancelled", series_id, open.len());
+    Ok(open.len())
+}
+
+// ============================================================================
+// PAYMENT COMMANDS (payments ledger, deposit requests)
+// ============================================================================
+
+#[tauri::command]
+async fn get_booking_payments_pg(pool: State<'_, DbPool>, booking_id: i32) -> Result<database_pg::BookingPayments, String> {
+    PaymentRepository::get_overview(&pool, booking_id).await.map_err(|e| e.to_string())
+}
+
+/// Record a payment or refund (negative amount) and return the new balance
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn create_payment_pg(
+    pool: State<'_, DbPool>,
+    booking_id: i32,
+    amount: Money,
+    payment_date: String,
+    method: Option<String>,
+    reference: Option<String>,
+    notes: Option<String>,
+    current_user: Option<String>,
+) -> Result<database_pg::BookingPayments, String> {
+    println!("💶 create_payment_pg called: booking {} amount {}", booking_id, amount);
+
+    PaymentRepository::create(&pool, booking_id, amount, payment_date, method, reference, notes, current_user)
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error recording payment: {}", e);
+            e.to_string()
+        })?;
+
+    PaymentRepository::get_overview(&pool, booking_id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_payment_pg(pool: State<'_, DbPool>, id: i32) -> Result<database_pg::BookingPayments, String> {
+    println!("🗑️ delete_payment_pg called: id={}", id);
+
+    let payment = PaymentRepository::delete(&pool, id).await.map_err(|e| e.to_string())?;
+    PaymentRepository::get_overview(&pool, payment.booking_id).await.map_err(|e| e.to_string())
+}
+
+/// Request a deposit: fixed `amount` or `percentage` of the amount due (e.g. 30 % before the seminar)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn create_deposit_request_pg(
+    pool: State<'_, DbPool>,
+    booking_id: i32,
+    description: Option<String>,
+    amount: Option<Money>,
+    percentage: Option<f64>,
+    due_date: String,
+    current_user: Option<String>,
+) -> Result<database_pg::BookingPayments, String> {
+    println!("💶 create_deposit_request_pg called: booking {} due {}", booking_id, due_date);
+
+    PaymentRepository::create_deposit(&pool, booking_id, description, amount, percentage, due_date, current_user)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    PaymentRepository::get_overview(&pool, booking_id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_deposit_request_pg(pool: State<'_, DbPool>, id: i32, booking_id: i32) -> Result<database_pg::BookingPayments, String> {
+    PaymentRepository::delete_deposit(&pool, id).await.map_err(|e| e.to_string())?;
+    PaymentRepository::get_overview(&pool, booking_id).await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// BANK IMPORT COMMANDS (CAMT.053 / MT940 statements, matching to bookings)
+// ============================================================================
+
+/// Import a bank statement file and return the open transactions with match proposals
+#[tauri::command]
+async fn import_bank_statement_pg(
+    pool: State<'_, DbPool>,
+    file_path: String,
+    current_user: Option<String>,
+) -> Result<database_pg::BankImportResult, String> {
+    println!("🏦 import_bank_statement_pg called: {}", file_path);
+
+    let bytes = std::fs::read(&file_path).map_err(|e| format!("Datei konnte nicht gelesen werden: {}", e))?;
+    // MT940 files of German banks are often ISO-8859-1
+    let content = String::from_utf8(bytes).unwrap_or_else(|e| e.into_bytes().iter().map(|&b| b as char).collect());
+    let transactions = bank_import::parse_statement(&content)?;
+
+    let file_name = std::path::Path::new(&file_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+    let result = BankTransactionRepository::import(&pool, file_name, &transactions, current_user)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        "✅ Bank statement imported: {} new, {} duplicates, {} debits skipped",
+        result.imported, result.duplicates, result.skipped_debits
+    );
+    Ok(result)
+}
+
+/// Transactions by status; open ones ('offen', default) come with match proposals
+#[tauri::command]
+async fn get_bank_transactions_pg(
+    pool: State<'_, DbPool>,
+    status: Option<String>,
+) -> Result<Vec<database_pg::ImportedBankTransaction>, String> {
+    match status.as_deref() {
+        None | Some("offen") => BankTransactionRepository::get_open(&pool).await,
+        Some(status) => BankTransactionRepository::get_by_status(&pool, status).await,
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Record the transfer as payment of the booking, returns the remaining open transactions
+#[tauri::command]
+async fn confirm_bank_transaction_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    booking_id: i32,
+    confidence: Option<i16>,
+    current_user: Option<String>,
+) -> Result<Vec<database_pg::ImportedBankTransaction>, String> {
+    println!("🏦 confirm_bank_transaction_pg called: transaction {} -> booking {}", id, booking_id);
+
+    BankTransactionRepository::confirm(&pool, id, booking_id, confidence, current_user)
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error confirming bank transaction: {}", e);
+            e.to_string()
+        })?;
+
+    BankTransactionRepository::get_open(&pool).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn ignore_bank_transaction_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    current_user: Option<String>,
+) -> Result<Vec<database_pg::ImportedBankTransaction>, String> {
+    BankTransactionRepository::ignore(&pool, id, current_user)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    BankTransactionRepository::get_open(&pool).await.map_err(|e| e.to_string())
+}
+
+/// Undo a match (deletes the payment) or an ignore: the transaction is open again
+#[tauri::command]
+async fn undo_bank_transaction_pg(pool: State<'_, DbPool>, id: i32) -> Result<(), String> {
+    BankTransactionRepository::undo(&pool, id).await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// SEPA DIRECT DEBIT COMMANDS (mandates on guests, pain.008 export, returns)
+// ============================================================================
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn set_guest_sepa_mandate_pg(
+    pool: State<'_, DbPool>,
+    guest_id: i32,
+    mandatsreferenz: String,
+    mandat_datum: String,
+    iban: String,
+    bic: Option<String>,
+    kontoinhaber: Option<String>,
+    mandat_typ: String,
+    einmalig: bool,
+    current_user: Option<String>,
+) -> Result<database_pg::Guest, String> {
+    println!("🏦 set_guest_sepa_mandate_pg called: guest {} mandate {}", guest_id, mandatsreferenz);
+
+    GuestRepository::set_sepa_mandate(
+        &pool, guest_id, mandatsreferenz, mandat_datum, iban, bic, kontoinhaber, mandat_typ, einmalig, current_user,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn remove_guest_sepa_mandate_pg(
+    pool: State<'_, DbPool>,
+    guest_id: i32,
+    current_user: Option<String>,
+) -> Result<database_pg::Guest, String> {
+    GuestRepository::remove_sepa_mandate(&pool, guest_id, current_user)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Bookings that would be collected on `collection_date` (default: earliest possible date)
+#[tauri::command]
+async fn get_sepa_due_debits_pg(
+    pool: State<'_, DbPool>,
+    collection_date: Option<String>,
+) -> Result<database_pg::SepaDuePreview, String> {
+    let settings = PaymentSettingsRepository::get(&pool).await.map_err(|e| e.to_string())?;
+    let lead_days = settings.sepa_vorlauf_tage.unwrap_or(2).max(1) as u32;
+    let earliest = sepa::earliest_collection_date(chrono::Local::now().date_naive(), lead_days);
+
+    let collection_date = match collection_date {
+        Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|_| format!("Ungültiges Einzugsdatum: {}", date))?,
+        None => earliest,
+    };
+    let debits = SepaRepository::get_due(&pool, collection_date).await.map_err(|e| e.to_string())?;
+
+    Ok(database_pg::SepaDuePreview {
+        collection_date: collection_date.format("%Y-%m-%d").to_string(),
+        earliest_collection_date: earliest.format("%Y-%m-%d").to_string(),
+        debits,
+    })
+}
+
+/// Export the due direct debits as pain.008 file for the online banking. The debits are
+/// pending until the collection date, then they are recorded as payments.
+#[tauri::command]
+async fn export_sepa_direct_debits_pg(
+    app: tauri::AppHandle,
+    collection_date: String,
+    current_user: Option<String>,
+) -> Result<database_pg::SepaBatch, String> {
+    use tauri::Manager;
+
+    println!("🏦 export_sepa_direct_debits_pg called: collection date {}", collection_date);
+
+    let pool = app.state::<DbPool>();
+    let collection_date = chrono::NaiveDate::parse_from_str(&collection_date, "%Y-%m-%d")
+        .map_err(|_| format!("Ungültiges Einzugsdatum: {}", collection_date))?;
+    let (batch, xml) = SepaRepository::create_batch(pool.inner(), collection_date, current_user)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let sepa_dir = app.path().app_data_dir()
+        .map_err(|e| format!("App directory error: {}", e))?
+        .join("sepa");
+    std::fs::create_dir_all(&sepa_dir)
+        .map_err(|e| format!("Create sepa dir error: {}", e))?;
+
+    let xml_path = sepa_dir.join(format!("Lastschriften_{}.xml", batch.message_id));
+    std::fs::write(&xml_path, xml)
+        .map_err(|e| format!("Save SEPA file error (batch {} is stored): {}", batch.message_id, e))?;
+
+    println!("✅ SEPA batch {}: {} debits, {} EUR -> {:?}", batch.message_id, batch.transaction_count, batch.total, xml_path);
+    SepaRepository::set_file_path(pool.inner(), batch.id, &xml_path.to_string_lossy())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_sepa_batches_pg(pool: State<'_, DbPool>) -> Result<Vec<database_pg::SepaBatch>, String> {
+    SepaRepository::get_batches(&pool).await.map_err(|e| e.to_string())
+}
+
+/// Direct debits by status ('ausstehend', 'eingezogen', 'zurueckgegeben')
+#[tauri::command]
+async fn get_sepa_collections_pg(
+    pool: State<'_, DbPool>,
+    status: String,
+) -> Result<Vec<database_pg::SepaCollection>, String> {
+    SepaRepository::get_collections(&pool, &status).await.map_err(|e| e.to_string())
+}
+
+/// Record a returned debit (Rücklastschrift) with its ISO reason code
+#[tauri::command]
+async fn record_sepa_return_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    reason_code: String,
+    returned_at: String,
+    current_user: Option<String>,
+) -> Result<database_pg::SepaCollection, String> {
+    println!("🏦 record_sepa_return_pg called: collection {} reason {}", id, reason_code);
+
+    SepaRepository::record_return(&pool, id, reason_code, returned_at, current_user)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// DUNNING COMMANDS (Mahnstufen)
+// ============================================================================
+
+#[tauri::command]
+async fn get_dunning_stages_pg(pool: State<'_, DbPool>) -> Result<Vec<database_pg::DunningStage>, String> {
+    DunningRepository::get_stages(&pool).await.map_err(|e| e.to_string())
+}
+
+/// Replace the dunning stages (in order of escalation)
+#[tauri::command]
+async fn save_dunning_stages_pg(
+    pool: State<'_, DbPool>,
+    stages: Vec<dunning::StageRule>,
+) -> Result<Vec<database_pg::DunningStage>, String> {
+    println!("📨 save_dunning_stages_pg called: {} stages", stages.len());
+
+    DunningRepository::save_stages(&pool, &stages).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_booking_dunning_history_pg(
+    pool: State<'_, DbPool>,
+    booking_id: i32,
+) -> Result<Vec<database_pg::DunningEntry>, String> {
+    DunningRepository::get_history(&pool, booking_id).await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// VOUCHER COMMANDS (Gutscheine)
+// ============================================================================
+
+/// Vouchers, optionally only those with the given status ('aktiv', 'eingeloest', 'abgelaufen', 'storniert')
+#[tauri::command]
+async fn get_vouchers_pg(pool: State<'_, DbPool>, status: Option<String>) -> Result<Vec<database_pg::Voucher>, String> {
+    VoucherRepository::get_all(&pool, status.as_deref()).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_voucher_by_code_pg(pool: State<'_, DbPool>, code: String) -> Result<database_pg::Voucher, String> {
+    VoucherRepository::get_by_code(&pool, &code).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_voucher_redemptions_pg(
+    pool: State<'_, DbPool>,
+    voucher_id: i32,
+) -> Result<Vec<database_pg::VoucherRedemption>, String> {
+    VoucherRepository::get_redemptions(&pool, voucher_id).await.map_err(|e| e.to_string())
+}
+
+/// Sell a voucher (default expiry: 31.12. of the third year)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn create_voucher_pg(
+    pool: State<'_, DbPool>,
+    value: Money,
+    issued_at: Option<String>,
+    expires_at: Option<String>,
+    purchaser_guest_id: Option<i32>,
+    purchaser_name: Option<String>,
+    recipient_name: Option<String>,
+    message: Option<String>,
+    notes: Option<String>,
+    current_user: Option<String>,
+) -> Result<database_pg::Voucher, String> {
+    println!("🎁 create_voucher_pg called: value {}", value);
+
+    VoucherRepository::create(
+        &pool, value, issued_at, expires_at, purchaser_guest_id, purchaser_name, recipient_name, message, notes,
+        current_user,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cancel_voucher_pg(pool: State<'_, DbPool>, id: i32) -> Result<database_pg::Voucher, String> {
+    println!("🎁 cancel_voucher_pg called: id={}", id);
+
+    VoucherRepository::cancel(&pool, id).await.map_err(|e| e.to_string())
+}
+
+/// Redeem a voucher against a booking (as payment 'Gutschein'). Without `amount` the rest
+/// value is used, at most the outstanding amount of the booking.
+#[tauri::command]
+async fn redeem_voucher_pg(
+    pool: State<'_, DbPool>,
+    code: String,
+    booking_id: i32,
+    amount: Option<Money>,
+    current_user: Option<String>,
+) -> Result<database_pg::BookingPayments, String> {
+    println!("🎁 redeem_voucher_pg called: {} for booking {}", code, booking_id);
+
+    VoucherRepository::redeem(&pool, &code, booking_id, amount, current_user)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    PaymentRepository::get_overview(&pool, booking_id).await.map_err(|e| e.to_string())
+}
+
+/// Printable voucher PDF, stored in the app data directory
+#[tauri::command]
+async fn generate_voucher_pdf_pg(app: tauri::AppHandle, id: i32) -> Result<String, String> {
+    use tauri::Manager;
+
+    let pool = app.state::<DbPool>();
+    let voucher = VoucherRepository::get(pool.inner(), id).await.map_err(|e| e.to_string())?;
+    if voucher.status == vouchers::VoucherStatus::Storniert {
+        return Err("Stornierte Gutscheine können nicht gedruckt werden".to_string());
+    }
+    let company = CompanySettingsRepository::get(pool.inner())
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Firmeneinstellungen: {}", e))?;
+
+    let vouchers_dir = app.path().app_data_dir()
+        .map_err(|e| format!("App directory error: {}", e))?
+        .join("vouchers");
+    std::fs::create_dir_all(&vouchers_dir)
+        .map_err(|e| format!("Create vouchers dir error: {}", e))?;
+
+    let pdf_data = voucher_pdf::render(&voucher_pdf::VoucherPdf { voucher: &voucher, company: &company })?;
+    let pdf_path = vouchers_dir.join(format!("Gutschein_{}.pdf", voucher.code));
+    std::fs::write(&pdf_path, pdf_data)
+        .map_err(|e| format!("Save PDF error: {}", e))?;
+
+    println!("✅ Voucher PDF {} -> {:?}", voucher.code, pdf_path);
+    Ok(pdf_path.to_string_lossy().to_string())
+}
+
+// ============================================================================
+// ALLOTMENT COMMANDS (room contingents for partner organisations)
+// ============================================================================
+
+#[tauri::command]
+async fn get_allotments_pg(pool: State<'_, DbPool>) -> Result<Vec<database_pg::Allotment>, String> {
+    AllotmentRepository::get_all(&pool).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn create_allotment_pg(
+    pool: State<'_, DbPool>,
+    name: String,
+    guest_id: Option<i32>,
+    gebaeude_typ: Option<String>,
+    room_ids: Vec<i32>,
+    start_date: String,
+    end_date: String,
+    quantity: i32,
+    release_date: String,
+    bemerkungen: Option<String>,
+    current_user: Option<String>,
+) -> Result<database_pg::Allotment, String> {
+    println!("📦 create_allotment_pg called: '{}' ({} rooms, {} to {})", name, quantity, start_date, end_date);
+
+    AllotmentRepository::create(
+        &pool,
+        name,
+        guest_id,
+        gebaeude_typ,
+        room_ids,
+        start_date,
+        end_date,
+        quantity,
+        release_date,
+        bemerkungen,
+        current_user,
+    )
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error creating allotment: {}", e);
+        e.to_string()
+    })
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn update_allotment_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    name: String,
+    guest_id: Option<i32>,
+    gebaeude_typ: Option<String>,
+    room_ids: Vec<i32>,
+    start_date: String,
+    end_date: String,
+    quantity: i32,
+    release_date: String,
+    bemerkungen: Option<String>,
+) -> Result<database_pg::Allotment, String> {
+    AllotmentRepository::update(
+        &pool,
+        id,
+        name,
+        guest_id,
+        gebaeude_typ,
+        room_ids,
+        start_date,
+        end_date,
+        quantity,
+        release_date,
+        bemerkungen,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Release the undrawn rooms of an allotment before its deadline
+#[tauri::command]
+async fn release_allotment_pg(pool: State<'_, DbPool>, id: i32) -> Result<database_pg::Allotment, String> {
+    AllotmentRepository::release(&pool, id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_allotment_pg(pool: State<'_, DbPool>, id: i32) -> Result<(), String> {
+    AllotmentRepository::delete(&pool, id).await.map_err(|e| e.to_string())
+}
+
+/// Book a room of an allotment for the partner organisation (draw down one held unit per night)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn book_from_allotment_pg(
+    pool: State<'_, DbPool>,
+    allotment_id: i32,
+    room_id: i32,
+    guest_id: i32,
+    checkin_date: String,
+    checkout_date: String,
+    anzahl_gaeste: i32,
+    status: BookingStatus,
+    bemerkungen: Option<String>,
+    current_user: Option<String>,
+) -> Result<database_pg::Booking, String> {
+    println!("📦 book_from_allotment_pg called: allotment {} room {} ({} to {})",
+             allotment_id, room_id, checkin_date, checkout_date);
+
+    let booking = AllotmentRepository::draw_down(
+        &pool,
+        allotment_id,
+        room_id,
+        guest_id,
+        checkin_date,
+        checkout_date,
+        anzahl_gaeste,
+        status,
+        bemerkungen,
+        current_user,
+    )
+    .await
+    .map_err(|e| {
+        eprintln!("❌ Error booking from allotment: {}", e);
+        match e {
+            database_pg::DbError::DoubleBookingError(msg) => format!("DOUBLE_BOOKING:{}", msg),
+            _ => e.to_string(),
+        }
+    })?;
+
+    if let Err(e) = recalculate_and_save_booking_prices(&pool, booking.id).await {
+        eprintln!("⚠️ Failed to calculate price for booking {}: {}", booking.id, e);
+    }
+    if let Err(e) = turso_sync::sync_booking_tasks_to_turso(&pool, booking.id).await {
+        eprintln!("⚠️ Failed to sync cleaning tasks to Turso: {}", e);
+    }
+
+    BookingRepository::get_by_id(&pool, booking.id).await.map_err(|e| e.to_string())
+}
+
+/// Held / drawn down / open allotment units per night for the TapeChart
+#[tauri::command]
+async fn get_allotment_nights_pg(
+    pool: State<'_, DbPool>,
+    from: String,
+    to: String,
+) -> Result<Vec<database_pg::AllotmentNight>, String> {
+    AllotmentRepository::get_nights(&pool, &from, &to).await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// WAITLIST COMMANDS
+// ============================================================================
+
+#[tauri::command]
+async fn get_waitlist_pg(pool: State<'_, DbPool>) -> Result<Vec<database_pg::WaitlistEntry>, String> {
+    WaitlistRepository::get_all(&pool).await.map_err(|e| e.to_string())
+}
+
+/// Put a guest on the waitlist (e.g. when no room is available for the request)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn create_waitlist_entry_pg(
+    pool: State<'_, DbPool>,
+    guest_id: i32,
+    checkin_date: String,
+    checkout_date: String,
+    anzahl_gaeste: i32,
+    room_ids: Vec<i32>,
+    orte: Vec<String>,
+    bemerkungen: Option<String>,
+    current_user: Option<String>,
+) -> Result<database_pg::WaitlistEntry, String> {
+    println!("📋 create_waitlist_entry_pg called: guest {} ({} to {})", guest_id, checkin_date, checkout_date);
+
+    WaitlistRepository::create(
+        &pool,
+        guest_id,
+        checkin_date,
+        checkout_date,
+        anzahl_gaeste,
+        room_ids,
+        orte,
+        bemerkungen,
+        current_user,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn update_waitlist_entry_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    checkin_date: String,
+    checkout_date: String,
+    anzahl_gaeste: i32,
+    room_ids: Vec<i32>,
+    orte: Vec<String>,
+    bemerkungen: Option<String>,
+) -> Result<database_pg::WaitlistEntry, String> {
+    WaitlistRepository::update(&pool, id, checkin_date, checkout_date, anzahl_gaeste, room_ids, orte, bemerkungen)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Status 'gebucht' / 'storniert' after the guest answered, 'wartend' puts the entry back in the queue
+#[tauri::command]
+async fn set_waitlist_status_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    status: String,
+) -> Result<database_pg::WaitlistEntry, String> {
+    WaitlistRepository::set_status(&pool, id, &status).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_waitlist_entry_pg(pool: State<'_, DbPool>, id: i32) -> Result<(), String> {
+    WaitlistRepository::delete(&pool, id).await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// ROOM BLOCK COMMANDS (maintenance, renovation, out of order)
+// ============================================================================
+
+/// Blocks overlapping [from, to) (TapeChart month), all blocks without range
+#[tauri::command]
+async fn get_room_blocks_pg(
+    pool: State<'_, DbPool>,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Vec<database_pg::RoomBlock>, String> {
+    RoomBlockRepository::get_all(&pool, from, to).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn create_room_block_pg(
+    pool: State<'_, DbPool>,
+    room_id: i32,
+    start_date: String,
+    end_date: String,
+    reason: String,
+    bemerkungen: Option<String>,
+    current_user: Option<String>,
+) -> Result<database_pg::RoomBlock, String> {
+    println!("🚧 create_room_block_pg called: room {} from {} to {} ({})", room_id, start_date, end_date, reason);
+
+    RoomBlockRepository::create(&pool, room_id, start_date, end_date, reason, bemerkungen, current_user)
+        .await
+        .map_err(|e| {
+            eprintln!("❌ Error creating room block: {}", e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+async fn update_room_block_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    room_id: i32,
+    start_date: String,
+    end_date: String,
+    reason: String,
+    bemerkungen: Option<String>,
+) -> Result<database_pg::RoomBlock, String> {
+    RoomBlockRepository::update(&pool, id, room_id, start_date, end_date, reason, bemerkungen)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_room_block_pg(pool: State<'_, DbPool>, id: i32) -> Result<(), String> {
+    RoomBlockRepository::delete(&pool, id).await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// ROOM SEARCH & ASSIGNMENT COMMANDS (delegate to crate::room_assignment)
+// ============================================================================
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RoomSuggestion {
+    room: database_pg::Room,
+    #[serde(flatten)]
+    ranking: RoomRanking,
+    total_price: Money,
+    price_per_night: Money,
+}
+
+/// Free rooms for a stay, ranked by preferences and gap fit, with prices from the pricing engine
+#[tauri::command]
+async fn find_free_rooms_pg(
+    pool: State<'_, DbPool>,
+    criteria: RoomSearchCriteria,
+    is_member: Option<bool>,
+    exclude_booking_id: Option<i32>,
+) -> Result<Vec<RoomSuggestion>, String> {
+    let (checkin, checkout) = pricing::parse_stay_dates(&criteria.checkin, &criteria.checkout)?;
+    if criteria.anzahl_gaeste < 1 {
+        return Err("Bitte mindestens einen Gast angeben".to_string());
+    }
+
+    let free_rooms = RoomRepository::find_free_for_stay(&pool, &criteria.checkin, &criteria.checkout, exclude_booking_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let parse = |d: &Option<String>| d.as_deref().and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+    let candidates: Vec<room_assignment::RoomCandidate> = free_rooms
+        .iter()
+        .map(|f| room_assignment::RoomCandidate {
+            room: &f.room,
+            previous_checkout: parse(&f.previous_checkout),
+            next_checkin: parse(&f.next_checkin),
+        })
+        .collect();
 
-        // Check if auto discount already exists
-        let existing = client.query_opt(
-            "SELECT id FROM discounts WHERE booking_id = $1 AND discount_name = $2",
-            &[&booking_id_i64, &discount_name],
-        ).await.map_err(|e| format!("Failed to query auto discount: {}", e))?;
+    let rankings = room_assignment::rank_rooms(&criteria, checkin, checkout, &candidates);
 
-        if let Some(row) = existing {
-            // Update existing
-            let discount_id: i64 = row.get(0);  // discounts.id is BIGINT (i64)
-            println!("  ✅ Auto DPolG discount exists in DB (ID: {}), updating...", discount_id);
+    let pricing_settings = PricingSettingsRepository::get(&pool)
+        .await
+        .unwrap_or_else(|_| pricing::default_pricing_settings());
 
-            let rows_affected = client.execute(
-                "UPDATE discounts SET calculated_amount = $1, discount_value = $2 WHERE id = $3",
-                &[&calculated_amount, &rabatt_prozent, &discount_id],
-            ).await.map_err(|e| format!("Failed to update auto discount: {}", e))?;
+    let mut suggestions = Vec::with_capacity(rankings.len());
+    for ranking in rankings {
+        let Some(free) = free_rooms.iter().find(|f| f.room.id == ranking.room_id) else {
+            continue;
+        };
+        let rates = load_room_rates(&pool, &free.room, &criteria.checkin, &criteria.checkout).await;
+        let occupancy = pricing::occupancy_from_guests(criteria.anzahl_gaeste, &[], checkin, &rates.child_prices);
+        let breakdown = pricing::calculate_price(
+            &free.room,
+            &pricing_settings,
+            &rates,
+            &occupancy,
+            checkin,
+            checkout,
+            is_member.unwrap_or(false),
+            &[],
+            &[],
+        )?;
+        suggestions.push(RoomSuggestion {
+            room: free.room.clone(),
+            ranking,
+            total_price: breakdown.total,
+            price_per_night: breakdown.price_per_night,
+        });
+    }
 
-            println!("  💾 UPDATE executed: {} rows affected", rows_affected);
-            println!("📊 [PRICE RECALC] Updated auto DPolG discount (ID: {}) calculated_amount to {:.2}€", discount_id, calculated_amount);
-        } else {
-            // Insert new auto discount
-            println!("  ➕ Auto DPolG discount NOT in DB, inserting new...");
+    // Same score: cheaper room first
+    suggestions.sort_by(|a, b| b.ranking.score.cmp(&a.ranking.score).then(a.total_price.cmp(&b.total_price)));
 
-            let rows_affected = client.execute(
-                "INSERT INTO discounts (booking_id, discount_name, discount_type, discount_value, calculated_amount)
-                 VALUES ($1, $2, 'percent', $3, $4)",
-                &[&booking_id_i64, &discount_name, &rabatt_prozent, &calculated_amount],
-            ).await.map_err(|e| format!("Failed to insert auto discount: {}", e))?;
+    Ok(suggestions)
+}
 
-            println!("  💾 INSERT executed: {} rows affected", rows_affected);
-            println!("📊 [PRICE RECALC] Created auto DPolG discount with calculated_amount {:.2}€", calculated_amount);
-        }
-    } else {
-        println!("🔍 [DEBUG] No auto DPolG discount to handle (either not member or already has manual DPolG discount)");
-    }
+/// Proposal to re-shuffle movable future bookings in [from, to) to close short gaps
+#[tauri::command]
+async fn suggest_room_assignment_pg(
+    pool: State<'_, DbPool>,
+    from: String,
+    to: String,
+) -> Result<room_assignment::AssignmentPlan, String> {
+    pricing::parse_stay_dates(&from, &to)?;
 
-    println!("✅ [PRICE RECALC] All prices and discounts updated for booking {}", booking_id);
+    let rooms = RoomRepository::get_all(&pool).await.map_err(|e| e.to_string())?;
+    let bookings = BookingRepository::get_active_overlapping(&pool, &from, &to)
+        .await
+        .map_err(|e| e.to_string())?;
+    let blocks = RoomBlockRepository::get_all(&pool, Some(from.clone()), Some(to.clone()))
+        .await
+        .map_err(|e| e.to_string())?;
 
-    // 13. VERIFICATION: Re-read discounts from DB to verify they were updated
-    println!("\n🔍 [DEBUG VERIFICATION] Re-reading discounts from DB to verify updates...");
-    let verification_rows = client.query(
-        "SELECT id, discount_name, discount_type, discount_value, calculated_amount
-         FROM discounts
-         WHERE booking_id = $1
-         ORDER BY id",
-        &[&booking_id_i64],
-    ).await.map_err(|e| format!("Failed to verify discounts: {}", e))?;
-
-    for row in verification_rows {
-        let id: i64 = row.get(0);  // discounts.id is BIGINT (i64)
-        let name: String = row.get(1);
-        let dtype: String = row.get(2);
-        let value: f64 = row.get(3);
-        let calc: Option<f64> = row.get(4);
-        println!("  ✓ ID: {}, Name: '{}', Type: {}, Value: {}, calculated_amount: {:?}",
-                 id, name, dtype, value, calc);
+    let today = chrono::Local::now().date_naive();
+    let mut stays = Vec::with_capacity(bookings.len());
+    for b in &bookings {
+        let (checkin, checkout) = pricing::parse_stay_dates(&b.checkin_date, &b.checkout_date)?;
+        // Only future stays that nobody relies on may move
+        let movable = checkin > today
+            && b.status != BookingStatus::Eingecheckt
+            && !b.status.is_final()
+            && b.booking_group_id.is_none()
+            && b.allotment_id.is_none()
+            && !b.zimmer_fixiert.unwrap_or(false);
+        stays.push(room_assignment::PlannedStay {
+            booking_id: b.id,
+            room_id: b.room_id,
+            checkin,
+            checkout,
+            anzahl_gaeste: b.anzahl_gaeste,
+            movable,
+        });
     }
+    let blocked: Vec<room_assignment::BlockedRange> = blocks
+        .iter()
+        .filter_map(|rb| {
+            let (start, end) = pricing::parse_stay_dates(&rb.start_date, &rb.end_date).ok()?;
+            Some(room_assignment::BlockedRange { room_id: rb.room_id, start, end })
+        })
+        .collect();
 
-    Ok(())
+    let plan = room_assignment::optimise_assignment(&rooms, &stays, &blocked);
+    println!("🧩 Room assignment {} - {}: {} moves, short-gap nights {} -> {}",
+             from, to, plan.moves.len(), plan.short_gap_nights_before, plan.short_gap_nights_after);
+
+    Ok(plan)
 }
 
-/// TODO: Implement room availability check with PostgreSQL
+/// Apply the moves of a room assignment proposal (all or nothing)
 #[tauri::command]
-async fn check_room_availability_pg(
+async fn apply_room_assignment_pg(
     pool: State<'_, DbPool>,
-    room_id: i32,
-    checkin: String,
-    checkout: String,
-    exclude_booking_id: Option<i32>,
-) -> Result<bool, String> {
-    println!("🏠 Checking room availability: room_id={}, {} to {}, exclude={:?}",
-             room_id, checkin, checkout, exclude_booking_id);
-
-    let client = pool.get().await.map_err(|e| e.to_string())?;
-
-    // Check for overlapping bookings
-    // Date overlap logic: (checkin < other_checkout) AND (checkout > other_checkin)
-    let query = if let Some(exclude_id) = exclude_booking_id {
-        "SELECT COUNT(*) as count
-         FROM bookings
-         WHERE room_id = $1
-           AND checkin_date < $3
-           AND checkout_date > $2
-           AND status != 'storniert'
-           AND id != $4"
-    } else {
-        "SELECT COUNT(*) as count
-         FROM bookings
-         WHERE room_id = $1
-           AND checkin_date < $3
-           AND checkout_date > $2
-           AND status != 'storniert'"
-    };
+    moves: Vec<RoomMove>,
+    current_user: Option<String>,
+) -> Result<Vec<database_pg::Booking>, String> {
+    println!("🧩 apply_room_assignment_pg called: {} moves", moves.len());
 
-    let row = if let Some(exclude_id) = exclude_booking_id {
-        client.query_one(query, &[&room_id, &checkin, &checkout, &exclude_id])
-            .await
-            .map_err(|e| format!("Fehler bei Verfügbarkeitsprüfung: {}", e))?
-    } else {
-        client.query_one(query, &[&room_id, &checkin, &checkout])
-            .await
-            .map_err(|e| format!("Fehler bei Verfügbarkeitsprüfung: {}", e))?
-    };
+    let moved = BookingRepository::reassign_rooms(&pool, &moves, current_user)
+        .await
+        .map_err(|e| match e {
+            database_pg::DbError::DoubleBookingError(msg) => format!("DOUBLE_BOOKING:{}", msg),
+            database_pg::DbError::ConflictError(msg) => format!("CONFLICT:{}", msg),
+            _ => e.to_string(),
+        })?;
 
-    let count: i64 = row.get("count");
-    let is_available = count == 0;
+    // Same follow-ups as a single room change: cleaning tasks, prices, waitlist
+    for (m, booking) in moves.iter().zip(&moved) {
+        if let Err(e) = turso_sync::sync_booking_tasks_to_turso(&pool, booking.id).await {
+            eprintln!("⚠️ Failed to sync cleaning tasks to Turso: {}", e);
+        }
+        if let Err(e) = recalculate_and_save_booking_prices(&pool, booking.id).await {
+            eprintln!("⚠️ Failed to recalculate prices: {}", e);
+        }
+        if let Err(e) = offer_freed_room_to_waitlist(&pool, m.from_room_id, &booking.checkin_date, &booking.checkout_date).await {
+            eprintln!("⚠️ Failed to offer freed room to waitlist: {}", e);
+        }
+    }
 
-    println!("✅ Room {} is {}: {} overlapping bookings found",
-             room_id,
-             if is_available { "available" } else { "NOT available" },
-             count);
+    let mut bookings = Vec::with_capacity(moved.len());
+    for booking in moved {
+        bookings.push(BookingRepository::get_by_id(&pool, booking.id).await.unwrap_or(booking));
+    }
+    Ok(bookings)
+}
 
-    Ok(is_available)
+/// Fix a booking to its room (never moved by the room optimiser) or release it
+#[tauri::command]
+async fn set_booking_room_fixed_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    fixed: bool,
+    current_user: Option<String>,
+) -> Result<database_pg::Booking, String> {
+    BookingRepository::set_room_fixed(&pool, id, fixed, current_user)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 // ============================================================================
@@ -2710,13 +3924,14 @@ async fn create_additional_service_pg(
     pool: State<'_, DbPool>,
     booking_id: i64,
     service_name: String,
-    service_price: f64,  // Changed from f32
+    service_price: Money,
     template_id: Option<i32>,
     price_type: String,
     original_value: f64,  // Changed from f32
     applies_to: String,
+    steuerkategorie: Option<String>,
 ) -> Result<database_pg::AdditionalService, String> {
-    AdditionalServiceRepository::create(&pool, booking_id, service_name, service_price, template_id, price_type, original_value, applies_to)
+    AdditionalServiceRepository::create(&pool, booking_id, service_name, service_price, template_id, price_type, original_value, applies_to, steuerkategorie)
         .await.map_err(|e| e.to_string())
 }
 
@@ -2726,13 +3941,14 @@ async fn update_additional_service_pg(
     id: i64,
     booking_id: i64,
     service_name: String,
-    service_price: f64,  // Changed from f32
+    service_price: Money,
     template_id: Option<i32>,
     price_type: String,
     original_value: f64,  // Changed from f32
     applies_to: String,
+    steuerkategorie: Option<String>,
 ) -> Result<database_pg::AdditionalService, String> {
-    AdditionalServiceRepository::update(&pool, id, booking_id, service_name, service_price, template_id, price_type, original_value, applies_to)
+    AdditionalServiceRepository::update(&pool, id, booking_id, service_name, service_price, template_id, price_type, original_value, applies_to, steuerkategorie)
         .await.map_err(|e| e.to_string())
 }
 
@@ -2742,7 +3958,7 @@ async fn delete_additional_service_pg(pool: State<'_, DbPool>, id: i64) -> Resul
 }
 
 #[tauri::command]
-async fn calculate_additional_services_total_pg(pool: State<'_, DbPool>, booking_id: i64) -> Result<f64, String> {
+async fn calculate_additional_services_total_pg(pool: State<'_, DbPool>, booking_id: i64) -> Result<Money, String> {
     AdditionalServiceRepository::calculate_total_for_booking(&pool, booking_id).await.map_err(|e| e.to_string())
 }
 
@@ -2772,7 +3988,7 @@ async fn create_discount_pg(
     discount_name: String,
     discount_type: String,
     discount_value: f64,
-    calculated_amount: Option<f64>,
+    calculated_amount: Option<Money>,
 ) -> Result<database_pg::Discount, String> {
     DiscountRepository::create(&pool, booking_id, discount_name, discount_type, discount_value, calculated_amount)
         .await.map_err(|e| e.to_string())
@@ -2786,7 +4002,7 @@ async fn update_discount_pg(
     discount_name: String,
     discount_type: String,
     discount_value: f64,
-    calculated_amount: Option<f64>,
+    calculated_amount: Option<Money>,
 ) -> Result<database_pg::Discount, String> {
     DiscountRepository::update(&pool, id, booking_id, discount_name, discount_type, discount_value, calculated_amount)
         .await.map_err(|e| e.to_string())
@@ -3037,7 +4253,9 @@ async fn create_accompanying_guest_pg(
     }
     println!("═══════════════════════════════════════════════════════════════");
 
-    result.map_err(|e| e.to_string())
+    let guest = result.map_err(|e| e.to_string())?;
+    recalculate_after_companion_change(&pool, guest.booking_id).await;
+    Ok(guest)
 }
 
 #[tauri::command]
@@ -3048,13 +4266,18 @@ async fn update_accompanying_guest_pg(
     nachname: String,
     geburtsdatum: Option<String>,
 ) -> Result<database_pg::AccompanyingGuest, String> {
-    AccompanyingGuestRepository::update(&pool, id, vorname, nachname, geburtsdatum)
-        .await.map_err(|e| e.to_string())
+    let guest = AccompanyingGuestRepository::update(&pool, id, vorname, nachname, geburtsdatum)
+        .await.map_err(|e| e.to_string())?;
+    recalculate_after_companion_change(&pool, guest.booking_id).await;
+    Ok(guest)
 }
 
 #[tauri::command]
 async fn delete_accompanying_guest_pg(pool: State<'_, DbPool>, id: i64) -> Result<(), String> {
-    AccompanyingGuestRepository::delete(&pool, id).await.map_err(|e| e.to_string())
+    let guest = AccompanyingGuestRepository::get_by_id(&pool, id).await.map_err(|e| e.to_string())?;
+    AccompanyingGuestRepository::delete(&pool, id).await.map_err(|e| e.to_string())?;
+    recalculate_after_companion_change(&pool, guest.booking_id).await;
+    Ok(())
 }
 
 // ============================================================================
@@ -3091,10 +4314,11 @@ async fn create_service_template_pg(
     emoji: Option<String>,
     show_in_cleaning_plan: bool,
     cleaning_plan_position: String,
+    steuerkategorie: Option<String>,
 ) -> Result<database_pg::ServiceTemplate, String> {
     ServiceTemplateRepository::create(
         &pool, name, description, price_type, price.into(), applies_to,
-        emoji, show_in_cleaning_plan, cleaning_plan_position
+        emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie
     ).await.map_err(|e| e.to_string())
 }
 
@@ -3111,10 +4335,11 @@ async fn update_service_template_pg(
     emoji: Option<String>,
     show_in_cleaning_plan: bool,
     cleaning_plan_position: String,
+    steuerkategorie: Option<String>,
 ) -> Result<database_pg::ServiceTemplate, String> {
     ServiceTemplateRepository::update(
         &pool, id, name, description, price_type, price.into(), applies_to, is_active,
-        emoji, show_in_cleaning_plan, cleaning_plan_position
+        emoji, show_in_cleaning_plan, cleaning_plan_position, steuerkategorie
     ).await.map_err(|e| e.to_string())
 }
 
@@ -3359,6 +4584,9 @@ async fn update_company_settings_pg(
     pool: State<'_, DbPool>,
     settings: database_pg::CompanySettings,
 ) -> Result<database_pg::CompanySettings, String> {
+    if let Some(pattern) = &settings.reservierungsnummer_format {
+        reservation_number::validate_pattern(pattern)?;
+    }
     CompanySettingsRepository::update(&pool, &settings)
         .await.map_err(|e| e.to_string())
 }
@@ -3377,6 +4605,133 @@ async fn update_pricing_settings_pg(
         .await.map_err(|e| e.to_string())
 }
 
+// ============================================================================
+// SEASON PRICE CALENDAR COMMANDS
+// ============================================================================
+
+#[tauri::command]
+async fn get_seasons_pg(pool: State<'_, DbPool>, year: Option<i32>) -> Result<Vec<database_pg::Season>, String> {
+    SeasonRepository::get_all(&pool, year).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn create_season_pg(
+    pool: State<'_, DbPool>,
+    name: String,
+    year: i32,
+    start_date: String,
+    end_date: String,
+    priority: Option<i32>,
+    color: Option<String>,
+) -> Result<database_pg::Season, String> {
+    SeasonRepository::create(&pool, name, year, start_date, end_date, priority.unwrap_or(0), color)
+        .await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn update_season_pg(
+    pool: State<'_, DbPool>,
+    id: i32,
+    name: String,
+    year: i32,
+    start_date: String,
+    end_date: String,
+    priority: Option<i32>,
+    color: Option<String>,
+    is_active: bool,
+) -> Result<database_pg::Season, String> {
+    SeasonRepository::update(&pool, id, name, year, start_date, end_date, priority.unwrap_or(0), color, is_active)
+        .await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_season_pg(pool: State<'_, DbPool>, id: i32) -> Result<(), String> {
+    SeasonRepository::delete(&pool, id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_room_season_prices_pg(pool: State<'_, DbPool>, season_id: i32) -> Result<Vec<database_pg::RoomSeasonPrice>, String> {
+    SeasonRepository::get_room_prices(&pool, season_id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn set_room_season_price_pg(
+    pool: State<'_, DbPool>,
+    room_id: i32,
+    season_id: i32,
+    price_per_night: Money,
+) -> Result<database_pg::RoomSeasonPrice, String> {
+    SeasonRepository::set_room_price(&pool, room_id, season_id, price_per_night)
+        .await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_room_season_price_pg(pool: State<'_, DbPool>, room_id: i32, season_id: i32) -> Result<(), String> {
+    SeasonRepository::delete_room_price(&pool, room_id, season_id).await.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// KURTAXE COMMANDS
+// ============================================================================
+
+#[tauri::command]
+async fn get_kurtaxe_rates_pg(pool: State<'_, DbPool>) -> Result<Vec<database_pg::KurtaxeRate>, String> {
+    KurtaxeRepository::get_all(&pool).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn save_kurtaxe_rate_pg(
+    pool: State<'_, DbPool>,
+    ort: String,
+    betrag_pro_nacht: Money,
+    befreit_unter_alter: Option<i32>,
+    geschaeftsreise_befreit: Option<bool>,
+    is_active: Option<bool>,
+) -> Result<database_pg::KurtaxeRate, String> {
+    KurtaxeRepository::upsert(
+        &pool,
+        ort,
+        betrag_pro_nacht,
+        befreit_unter_alter.unwrap_or(0),
+        geschaeftsreise_befreit.unwrap_or(true),
+        is_active.unwrap_or(true),
+    )
+    .await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn delete_kurtaxe_rate_pg(pool: State<'_, DbPool>, id: i32) -> Result<(), String> {
+    KurtaxeRepository::delete(&pool, id).await.map_err(|e| e.to_string())
+}
+
+/// Mark a booking as business trip (Kurtaxe exemption) and update its Kurtaxe snapshot
+#[tauri::command]
+async fn set_booking_geschaeftsreise_pg(
+    pool: State<'_, DbPool>,
+    booking_id: i32,
+    geschaeftsreise: bool,
+) -> Result<database_pg::Booking, String> {
+    println!("set_booking_geschaeftsreise_pg called: booking_id={}, geschaeftsreise={}", booking_id, geschaeftsreise);
+
+    let client = pool.get().await
+        .map_err(|e| format!("Datenbankfehler: {}", e))?;
+    let rows_affected = client
+        .execute(
+            "UPDATE bookings SET geschaeftsreise = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
+            &[&geschaeftsreise, &booking_id],
+        )
+        .await
+        .map_err(|e| format!("Fehler beim Speichern: {}", e))?;
+    if rows_affected == 0 {
+        return Err(format!("Buchung {} nicht gefunden", booking_id));
+    }
+
+    save_kurtaxe_snapshot(&pool, booking_id).await?;
+
+    BookingRepository::get_by_id(&pool, booking_id).await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn get_email_config_pg(pool: State<'_, DbPool>) -> Result<database_pg::EmailConfig, String> {
     EmailConfigRepository::get(&pool).await.map_err(|e| e.to_string())
@@ -3428,7 +4783,8 @@ async fn update_notification_settings_pg(
         let cancelled_count = pool_conn.execute(
             "UPDATE scheduled_emails
              SET status = 'cancelled'
-             WHERE template_name = 'payment_reminder'
+             WHERE (template_name = 'payment_reminder'
+                    OR template_name IN (SELECT template_name FROM dunning_stages))
              AND status = 'pending'",
             &[]
         ).await.map_err(|e| e.to_string())?;
@@ -3475,7 +4831,7 @@ struct GuestCompanion {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct GuestCreditBalance {
-    balance: f64,
+    balance: Money,
 }
 
 #[tauri::command]
@@ -3898,104 +5254,255 @@ async fn link_service_template_to_booking_command(
         &pool,
         booking_id,
         template.service_name.clone(),
-        template.original_value, // Initial price = template value (now f64)
+        Money::from_euros(template.original_value), // Initial price = template value
         Some(service_template_id as i32),
         template.price_type,
         template.original_value,  // Now f64
         template.applies_to,
+        None,  // Tax category of the template
     )
     .await
     .map_err(|e| format!("Failed to create additional service: {}", e))?;
 
-    println!("✅ Created additional service with id: {}", service.id);
+    println!("✅ Created additional service with id: {}", service.id);
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn link_discount_template_to_booking_command(
+    booking_id: i64,
+    discount_template_id: i64,
+    pool: State<'_, DbPool>,
+) -> Result<(), String> {
+    use crate::database_pg::repositories::{DiscountTemplateRepository, DiscountRepository};
+
+    println!("📦 link_discount_template_to_booking_command called");
+    println!("   booking_id: {}, discount_template_id: {}", booking_id, discount_template_id);
+
+    // Get the discount template
+    let template = DiscountTemplateRepository::get_by_id(&pool, discount_template_id as i32)
+        .await
+        .map_err(|e| format!("Failed to get discount template: {}", e))?;
+
+    println!("   Found template: {} (type: {}, value: {})",
+             template.discount_name, template.discount_type, template.discount_value);
+
+    // Create discount for this booking (no pre-calculated amount for template-linked discounts)
+    let discount = DiscountRepository::create(
+        &pool,
+        booking_id,
+        template.discount_name.clone(),
+        template.discount_type,
+        template.discount_value,
+        None,
+    )
+    .await
+    .map_err(|e| format!("Failed to create discount: {}", e))?;
+
+    println!("✅ Created discount with id: {}", discount.id);
+
+    Ok(())
+}
+
+// ============================================================================
+// MISSING STUB COMMANDS (Bug #6-10)
+// ============================================================================
+
+#[tauri::command]
+async fn cancel_booking_command(pool: State<'_, DbPool>, booking_id: i64) -> Result<(), String> {
+    println!("🚫 Cancelling booking {}", booking_id);
+
+    // Load existing booking
+    let booking = BookingRepository::get_by_id(&pool, booking_id as i32)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
+
+    // Cancel first (locks the booking and checks the transition), only then the Stornorechnung:
+    // a credit note can never be deleted again, a failed status change must not leave one behind.
+    let (previous, cancelled) = BookingRepository::change_status(&pool, booking.id, BookingStatus::Storniert, None)
+        .await
+        .map_err(|e| format!("Fehler beim Stornieren: {}", e))?;
+
+    // Releases used credit, cancels pending emails and removes cleaning tasks
+    run_status_effects(&pool, &cancelled, previous, None).await;
+
+    // Issued invoice stays valid until cancelled by a Stornorechnung (GoBD).
+    // Split stays are invoiced on the first segment, a new invoice covers the remaining segments.
+    let invoice_booking_id = booking.split_parent_id.unwrap_or(booking.id);
+    let active_invoice = InvoiceRepository::get_active_for_booking(&pool, invoice_booking_id)
+        .await
+        .map_err(|e| format!("Buchung storniert, Fehler beim Laden der Rechnung: {}", e))?;
+    if let Some(original) = active_invoice {
+        issue_cancellation(&pool, &original, "Stornierung der Buchung")
+            .await
+            .map_err(|e| format!("Buchung storniert, die Rechnung bitte manuell stornieren: {}", e))?;
+    }
+
+    println!("✅ Booking {} cancelled successfully", booking_id);
+    Ok(())
+}
+
+/// Run the side effects of a booking status transition (see booking_status.rs).
+/// The status change is already committed, failing effects are only logged.
+async fn run_status_effects(
+    pool: &DbPool,
+    booking: &database_pg::Booking,
+    previous: BookingStatus,
+    changed_by: Option<String>,
+) {
+    use crate::database_pg::repositories::{CleaningTaskRepository, GuestCreditRepository, ScheduledEmailRepository};
+
+    let effects = previous.effects(booking.status);
+    if effects.is_empty() {
+        return;
+    }
+    println!("🔀 Booking {}: {} → {}", booking.id, previous, booking.status);
+
+    for effect in &effects {
+        let result = match effect {
+            StatusEffect::ScheduleConfirmationEmail => schedule_confirmation_email(pool, booking).await,
+            StatusEffect::CreateCleaningTask => {
+                // Early check-out: the room is free (and needs cleaning) today
+                let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+                let departure = std::cmp::min(today, booking.checkout_date.clone());
+                CleaningTaskRepository::ensure_departure_task(pool, booking.id, &departure)
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            StatusEffect::ReleaseCredit => GuestCreditRepository::release_for_booking(
+                pool,
+                booking.id,
+                Some(format!("Stornierung Buchung {}", booking.reservierungsnummer)),
+                changed_by.clone(),
+            )
+            .await
+            .map(|refund| {
+                if let Some(refund) = refund {
+                    println!("💰 Released credit {} for booking {}", refund.amount.format_eur(), booking.id);
+                }
+            })
+            .map_err(|e| e.to_string()),
+            StatusEffect::CancelScheduledEmails => ScheduledEmailRepository::cancel_pending_for_booking(pool, booking.id)
+                .await
+                .map(|count| println!("📧 Cancelled {} pending emails for booking {}", count, booking.id))
+                .map_err(|e| e.to_string()),
+            StatusEffect::RemoveCleaningTasks => {
+                if let Err(e) = turso_sync::delete_tasks_from_turso(booking.id).await {
+                    eprintln!("⚠️ Failed to delete tasks from Turso: {}", e);
+                }
+                CleaningTaskRepository::delete_for_booking(pool, booking.id)
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            StatusEffect::OfferToWaitlist => {
+                offer_freed_room_to_waitlist(pool, booking.room_id, &booking.checkin_date, &booking.checkout_date).await
+            }
+        };
+
+        if let Err(e) = result {
+            eprintln!("⚠️ Status effect {:?} failed for booking {}: {}", effect, booking.id, e);
+        }
+    }
 
-    Ok(())
+    if effects.contains(&StatusEffect::CreateCleaningTask) {
+        if let Err(e) = turso_sync::sync_booking_tasks_to_turso(pool, booking.id).await {
+            eprintln!("⚠️ Failed to sync cleaning tasks to Turso: {}", e);
+        }
+    }
 }
 
-#[tauri::command]
-async fn link_discount_template_to_booking_command(
-    booking_id: i64,
-    discount_template_id: i64,
-    pool: State<'_, DbPool>,
-) -> Result<(), String> {
-    use crate::database_pg::repositories::{DiscountTemplateRepository, DiscountRepository};
+/// Template of the waitlist offer email (queued without booking, see run_email_check)
+const WAITLIST_OFFER_TEMPLATE: &str = "warteliste_angebot";
 
-    println!("📦 link_discount_template_to_booking_command called");
-    println!("   booking_id: {}, discount_template_id: {}", booking_id, discount_template_id);
+/// A room became free in [from, to) (cancellation, shortened or moved stay): offer it to the
+/// matching waitlist entries and queue their offer emails
+async fn offer_freed_room_to_waitlist(pool: &DbPool, room_id: i32, from: &str, to: &str) -> Result<(), String> {
+    use crate::database_pg::repositories::ScheduledEmailRepository;
 
-    // Get the discount template
-    let template = DiscountTemplateRepository::get_by_id(&pool, discount_template_id as i32)
+    let matches = WaitlistRepository::matches_for_freed_room(pool, room_id, from, to)
         .await
-        .map_err(|e| format!("Failed to get discount template: {}", e))?;
+        .map_err(|e| format!("Wartelisten-Abgleich fehlgeschlagen: {}", e))?;
+    if matches.is_empty() {
+        return Ok(());
+    }
 
-    println!("   Found template: {} (type: {}, value: {})",
-             template.discount_name, template.discount_type, template.discount_value);
+    let room = RoomRepository::get_by_id(pool, room_id).await.map_err(|e| e.to_string())?;
+    for entry in matches {
+        let guest = GuestRepository::get_by_id(pool, entry.guest_id).await.map_err(|e| e.to_string())?;
 
-    // Create discount for this booking (no pre-calculated amount for template-linked discounts)
-    let discount = DiscountRepository::create(
-        &pool,
-        booking_id,
-        template.discount_name.clone(),
-        template.discount_type,
-        template.discount_value,
-        None,
-    )
-    .await
-    .map_err(|e| format!("Failed to create discount: {}", e))?;
+        // Without email address the entry is still marked, staff follows up by phone
+        let offer_email_id = if guest.email.is_empty() {
+            println!("⏭️ Waitlist entry {}: guest has no email address", entry.id);
+            None
+        } else {
+            let subject = format!(
+                "Ein Zimmer ist frei geworden: {} vom {} bis {}",
+                room.name, entry.checkin_date, entry.checkout_date
+            );
+            let id = ScheduledEmailRepository::schedule_now_for_guest(
+                pool,
+                guest.id,
+                WAITLIST_OFFER_TEMPLATE,
+                &guest.email,
+                &subject,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            Some(id)
+        };
 
-    println!("✅ Created discount with id: {}", discount.id);
+        WaitlistRepository::mark_offered(pool, entry.id, room_id, offer_email_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        println!("📋 Waitlist entry {} ({}) offered room {}", entry.id, entry.guest_name, room.name);
+    }
 
     Ok(())
 }
 
-// ============================================================================
-// MISSING STUB COMMANDS (Bug #6-10)
-// ============================================================================
+/// Queue the confirmation email ("bestaetigung" template) - sent by the email scheduler
+async fn schedule_confirmation_email(pool: &DbPool, booking: &database_pg::Booking) -> Result<(), String> {
+    use crate::database_pg::repositories::ScheduledEmailRepository;
 
-#[tauri::command]
-async fn cancel_booking_command(pool: State<'_, DbPool>, booking_id: i64) -> Result<(), String> {
-    println!("🚫 Cancelling booking {}", booking_id);
+    // Group bookings: the organiser gets one confirmation (for the master booking)
+    if let Some(group_id) = booking.booking_group_id {
+        let master_id = BookingGroupRepository::master_booking_id(pool, group_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        if master_id != booking.id {
+            return Ok(());
+        }
+    }
 
-    // Load existing booking
-    let booking = BookingRepository::get_by_id(&pool, booking_id as i32)
+    // Split stays: one confirmation for the whole stay (first segment)
+    if booking.split_parent_id.is_some() {
+        return Ok(());
+    }
+
+    let guest = GuestRepository::get_by_id(pool, booking.guest_id)
         .await
-        .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
+        .map_err(|e| format!("Gast nicht gefunden: {}", e))?;
+    if guest.email.is_empty() {
+        println!("⏭️ Booking {} confirmed - guest has no email address", booking.id);
+        return Ok(());
+    }
 
-    // Update with status 'storniert'
-    BookingRepository::update(
-        &pool,
+    let subject = format!("Buchungsbestätigung {}", booking.reservierungsnummer);
+    let scheduled = ScheduledEmailRepository::schedule_now_once(
+        pool,
         booking.id,
-        booking.room_id,
-        booking.guest_id,
-        booking.reservierungsnummer,
-        booking.checkin_date,
-        booking.checkout_date,
-        booking.anzahl_gaeste,
-        "storniert".to_string(),  // ← Changed status
-        booking.gesamtpreis,
-        booking.bemerkungen,
-        booking.anzahl_begleitpersonen,
-        booking.grundpreis,
-        booking.services_preis,
-        booking.rabatt_preis,
-        booking.anzahl_naechte,
-        booking.bezahlt,
-        booking.bezahlt_am,
-        booking.zahlungsmethode,
-        booking.mahnung_gesendet_am,
-        booking.rechnung_versendet_am,
-        booking.rechnung_versendet_an,
-        booking.ist_stiftungsfall,
-        booking.payment_recipient_id,
-        booking.putzplan_checkout_date,
-        booking.ist_dpolg_mitglied,
-        None,  // No user tracking for cancel
-        None,  // No optimistic locking for cancel
+        guest.id,
+        "bestaetigung",
+        &guest.email,
+        &subject,
     )
     .await
-    .map_err(|e| format!("Fehler beim Stornieren: {}", e))?;
+    .map_err(|e| e.to_string())?;
 
-    println!("✅ Booking {} cancelled successfully", booking_id);
+    if scheduled {
+        println!("📧 Confirmation email queued for booking {}", booking.id);
+    }
     Ok(())
 }
 
@@ -4135,13 +5642,17 @@ async fn get_report_stats_command(
             .map(|r| r.get(0))
             .unwrap_or(10);
 
-        // Calculate available nights (rooms * days in period)
+        // Calculate available nights (rooms * days in period, without blocked room nights)
         let available_nights = match (&start_date, &end_date) {
             (Some(start), Some(end)) => {
                 let days = chrono::NaiveDate::parse_from_str(end, "%Y-%m-%d")
                     .and_then(|e| chrono::NaiveDate::parse_from_str(start, "%Y-%m-%d").map(|s| (e - s).num_days()))
                     .unwrap_or(30);
-                room_count * days.max(1)
+                let blocked_nights: i64 = RoomBlockRepository::blocked_nights_by_room(&pool, start, end)
+                    .await
+                    .map(|blocked| blocked.values().sum())
+                    .unwrap_or(0);
+                room_count * days.max(1) - blocked_nights
             }
             _ => room_count * 30,
         };
@@ -4226,18 +5737,26 @@ async fn get_room_occupancy_command(
         .map_err(|e| format!("Fehler beim Laden der Auslastung: {}", e))?;
 
     // Calculate total available nights in period
-    let total_days = match (start_date, end_date) {
+    let total_days = match (&start_date, &end_date) {
         (Some(start), Some(end)) => {
             use chrono::NaiveDate;
-            let start_date = NaiveDate::parse_from_str(&start, "%Y-%m-%d")
+            let start_date = NaiveDate::parse_from_str(start, "%Y-%m-%d")
                 .map_err(|e| format!("Ungültiges Startdatum: {}", e))?;
-            let end_date = NaiveDate::parse_from_str(&end, "%Y-%m-%d")
+            let end_date = NaiveDate::parse_from_str(end, "%Y-%m-%d")
                 .map_err(|e| format!("Ungültiges Enddatum: {}", e))?;
             (end_date - start_date).num_days() as i32
         },
         _ => 365,
     };
 
+    // Blocked nights (maintenance, renovation) are not available
+    let blocked_nights = match (&start_date, &end_date) {
+        (Some(start), Some(end)) => RoomBlockRepository::blocked_nights_by_room(&pool, start, end)
+            .await
+            .map_err(|e| format!("Fehler beim Laden der Zimmersperrungen: {}", e))?,
+        _ => std::collections::HashMap::new(),
+    };
+
     let occupancies: Vec<RoomOccupancy> = rows
         .iter()
         .map(|row| {
@@ -4248,8 +5767,9 @@ async fn get_room_occupancy_command(
             let total_revenue_str: String = row.get("total_revenue");
             let total_revenue = total_revenue_str.parse::<f64>().unwrap_or(0.0);
 
-            let occupancy_rate = if total_days > 0 {
-                (total_nights_booked as f64 / total_days as f64) * 100.0
+            let available_days = total_days as i64 - blocked_nights.get(&room_id).copied().unwrap_or(0);
+            let occupancy_rate = if available_days > 0 {
+                (total_nights_booked as f64 / available_days as f64) * 100.0
             } else {
                 0.0
             };
@@ -4269,6 +5789,98 @@ async fn get_room_occupancy_command(
     Ok(occupancies)
 }
 
+#[derive(Serialize)]
+struct KurtaxeReportRow {
+    ort: String,
+    total_bookings: i64,
+    liable_nights: i64,
+    exempt_age_nights: i64,
+    exempt_business_nights: i64,
+    total_kurtaxe: Money,
+}
+
+/// Monthly Kurtaxe report per municipality (rooms.ort) for the filing with the municipalities.
+/// Counts person-nights inside the month; the Kurtaxe of stays across a month boundary is
+/// split by nights so that the months add up to the booking's Kurtaxe.
+/// Based on the Kurtaxe snapshot of the bookings (bookings without snapshot are not included).
+#[tauri::command]
+async fn get_kurtaxe_report_command(
+    pool: State<'_, DbPool>,
+    year: i32,
+    month: i32,
+) -> Result<Vec<KurtaxeReportRow>, String> {
+    println!("🏛️ get_kurtaxe_report_command called: {}-{:02}", year, month);
+
+    let month_start = u32::try_from(month)
+        .ok()
+        .and_then(|month| NaiveDate::from_ymd_opt(year, month, 1))
+        .ok_or_else(|| "Ungültiger Monat".to_string())?;
+
+    let client = pool.get().await
+        .map_err(|e| format!("Datenbankfehler: {}", e))?;
+
+    let rows = client
+        .query(
+            "WITH period AS (
+                SELECT make_date($1, $2, 1) AS start_date,
+                       (make_date($1, $2, 1) + INTERVAL '1 month')::date AS end_date
+             )
+             SELECT r.ort,
+                    COALESCE(b.geschaeftsreise, FALSE) AS geschaeftsreise,
+                    COALESCE(b.kurtaxe_personen, 0)::bigint AS personen,
+                    COALESCE(b.kurtaxe_befreit, 0)::bigint AS befreit,
+                    b.kurtaxe,
+                    b.checkin_date::date::text AS checkin_date,
+                    b.checkout_date::date::text AS checkout_date,
+                    (LEAST(b.checkout_date::date, p.end_date) - GREATEST(b.checkin_date::date, p.start_date))::bigint AS nights
+             FROM bookings b
+             JOIN rooms r ON r.id = b.room_id
+             CROSS JOIN period p
+             WHERE b.status NOT IN ('cancelled', 'storniert')
+               AND b.kurtaxe IS NOT NULL
+               AND b.checkin_date::date < p.end_date
+               AND b.checkout_date::date > p.start_date
+             ORDER BY r.ort",
+            &[&year, &month],
+        )
+        .await
+        .map_err(|e| format!("Fehler beim Laden des Kurtaxe-Berichts: {}", e))?;
+
+    let mut report: Vec<KurtaxeReportRow> = Vec::new();
+    for row in &rows {
+        let ort: String = row.get("ort");
+        let geschaeftsreise: bool = row.get("geschaeftsreise");
+        let personen: i64 = row.get("personen");
+        let befreit: i64 = row.get("befreit");
+        let nights: i64 = row.get("nights");
+        let kurtaxe: Money = row.get("kurtaxe");
+        let (checkin, checkout) = pricing::parse_stay_dates(row.get("checkin_date"), row.get("checkout_date"))?;
+
+        if report.last().is_none_or(|entry| entry.ort != ort) {
+            report.push(KurtaxeReportRow {
+                ort,
+                total_bookings: 0,
+                liable_nights: 0,
+                exempt_age_nights: 0,
+                exempt_business_nights: 0,
+                total_kurtaxe: Money::ZERO,
+            });
+        }
+        let Some(entry) = report.last_mut() else { continue };
+        entry.total_bookings += 1;
+        entry.liable_nights += personen * nights;
+        if geschaeftsreise && personen == 0 {
+            entry.exempt_business_nights += befreit * nights;
+        } else {
+            entry.exempt_age_nights += befreit * nights;
+        }
+        entry.total_kurtaxe += pricing::kurtaxe_month_share(kurtaxe, checkin, checkout, month_start);
+    }
+
+    println!("✅ Kurtaxe report: {} municipalities", report.len());
+    Ok(report)
+}
+
 // ============================================================================
 // INVOICE & CREDIT COMMANDS (STUB)
 // ============================================================================
@@ -4320,9 +5932,9 @@ async fn get_invoice_pdfs_for_booking_command(
             if let Some(filename) = path.file_name() {
                 let filename_str = filename.to_string_lossy();
 
-                // Only match PDFs that contain this booking's reservierungsnummer
-                // Formats: "Rechnung_2025-169.pdf", "Rechnung_RES-2025-169.pdf", etc.
-                if filename_str.ends_with(".pdf") && filename_str.contains(&reservierungsnummer) {
+                // Only match PDFs of exactly this booking's reservierungsnummer
+                // Formats: "Rechnung_RE-2026-0001_2026-169.pdf", old "Rechnung_2025-169.pdf", "Rechnung_RES-2025-169.pdf"
+                if reservation_number::invoice_file_matches(&filename_str, &reservierungsnummer) {
                     if let Ok(metadata) = std::fs::metadata(&path) {
                         pdfs.push(InvoicePdfInfo {
                             filename: filename_str.to_string(),
@@ -4339,6 +5951,81 @@ async fn get_invoice_pdfs_for_booking_command(
     Ok(pdfs)
 }
 
+/// Issued invoices of a booking (numbers, totals and snapshots)
+#[tauri::command]
+async fn get_invoices_for_booking_command(
+    pool: State<'_, DbPool>,
+    booking_id: i32,
+) -> Result<Vec<database_pg::IssuedInvoice>, String> {
+    InvoiceRepository::get_for_booking(&pool, booking_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Rechnungen: {}", e))
+}
+
+/// Cancel an issued invoice completely (Stornorechnung with all positions negated)
+#[tauri::command]
+async fn cancel_invoice_command(
+    pool: State<'_, DbPool>,
+    invoice_id: i32,
+    reason: String,
+) -> Result<database_pg::IssuedInvoice, String> {
+    println!("🚫 Cancelling invoice {}", invoice_id);
+
+    let original = InvoiceRepository::get_by_id(&pool, invoice_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Rechnung: {}", e))?;
+
+    issue_cancellation(&pool, &original, &reason).await
+}
+
+/// Partial correction of an issued invoice (e.g. one night less) as credit note
+#[tauri::command]
+async fn correct_invoice_command(
+    pool: State<'_, DbPool>,
+    invoice_id: i32,
+    corrections: Vec<invoice::CorrectionInput>,
+    reason: String,
+) -> Result<database_pg::IssuedInvoice, String> {
+    println!("✏️ Correcting invoice {} ({} positions)", invoice_id, corrections.len());
+
+    if reason.trim().is_empty() {
+        return Err("Bitte einen Grund für die Korrektur angeben".to_string());
+    }
+
+    let original = InvoiceRepository::get_by_id(&pool, invoice_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Rechnung: {}", e))?;
+
+    let document = invoice::correction_document(&original.document, &corrections, chrono::Local::now().date_naive())?;
+
+    let credit_note = InvoiceRepository::issue_credit_note(&pool, original.id, document, false, Some(reason.trim().to_string()), None)
+        .await
+        .map_err(|e| format!("Fehler beim Ausstellen der Gutschrift: {}", e))?;
+
+    println!("✅ Issued correction {} for invoice {} ({:.2} €)", credit_note.invoice_number, original.invoice_number, credit_note.total);
+    Ok(credit_note)
+}
+
+/// Issue the Stornorechnung for `original` (shared by invoice and booking cancellation)
+async fn issue_cancellation(
+    pool: &DbPool,
+    original: &database_pg::IssuedInvoice,
+    reason: &str,
+) -> Result<database_pg::IssuedInvoice, String> {
+    if reason.trim().is_empty() {
+        return Err("Bitte einen Grund für die Stornierung angeben".to_string());
+    }
+
+    let document = invoice::cancellation_document(&original.document, chrono::Local::now().date_naive());
+
+    let credit_note = InvoiceRepository::issue_credit_note(pool, original.id, document, true, Some(reason.trim().to_string()), None)
+        .await
+        .map_err(|e| format!("Fehler beim Stornieren der Rechnung: {}", e))?;
+
+    println!("✅ Issued cancellation {} for invoice {}", credit_note.invoice_number, original.invoice_number);
+    Ok(credit_note)
+}
+
 // ============================================================================
 // CLEANING & EMAIL COMMANDS (STUB)
 // ============================================================================
@@ -4454,7 +6141,13 @@ async fn send_confirmation_email_command(pool: State<'_, DbPool>, booking_id: i6
 // ============================================================================
 
 #[tauri::command]
-async fn add_guest_credit(guest_id: i64, amount: f64, description: String, pool: State<'_, DbPool>) -> Result<(), String> {
+async fn add_guest_credit(
+    guest_id: i64,
+    amount: Money,
+    description: String,
+    expires_at: Option<String>,
+    pool: State<'_, DbPool>,
+) -> Result<(), String> {
     use crate::database_pg::repositories::GuestCreditRepository;
 
     println!("➕ add_guest_credit: guest_id={}, amount={:.2}€, description={}", guest_id, amount, description);
@@ -4465,6 +6158,7 @@ async fn add_guest_credit(guest_id: i64, amount: f64, description: String, pool:
         amount,
         Some(description),
         Some("System".to_string()),
+        expires_at,
     )
     .await
     .map_err(|e| format!("Failed to add credit: {}", e))?;
@@ -4724,19 +6418,16 @@ async fn generate_invoice_pdf_command(
     booking_id: i64,
 ) -> Result<String, String> {
     use tauri::Manager;
-    use headless_chrome::{Browser, LaunchOptions};
-    use headless_chrome::types::PrintToPdfOptions;
 
     println!("════════════════════════════════════════════════════════");
     println!("🔵 PDF GENERATION STARTED (PostgreSQL)");
     println!("📋 Booking ID: {}", booking_id);
     println!("════════════════════════════════════════════════════════");
 
-    // 1. Load booking data from PostgreSQL
+    // 1. Load booking, settings and invoice positions from PostgreSQL
     let pool = app.state::<crate::database_pg::pool::DbPool>();
-    let booking = BookingRepository::get_with_details(pool.inner(), booking_id as i32)
-        .await
-        .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
+    let context = load_invoice_context(pool.inner(), booking_id as i32).await?;
+    let booking = &context.booking;
 
     let reservierungsnummer = &booking.booking.reservierungsnummer;
     println!("✅ Booking loaded: {}", reservierungsnummer);
@@ -4748,114 +6439,230 @@ async fn generate_invoice_pdf_command(
     std::fs::create_dir_all(&invoices_dir)
         .map_err(|e| format!("Create invoices dir error: {}", e))?;
 
-    // 3. Generate filename
-    let filename = format!("Rechnung_{}.pdf", reservierungsnummer);
+    // 3. Generate filename (invoice number + reservation number for the booking's PDF list)
+    let filename = format!("Rechnung_{}_{}.pdf", context.issued.invoice_number, reservierungsnummer);
     let pdf_path = invoices_dir.join(&filename);
 
-    // 4. Load company settings for invoice
+    // 4. Invoice layout from the issued snapshot
+    let guest = booking.guest.as_ref().ok_or("Kein Gast gefunden")?;
+    let room = booking.room.as_ref().ok_or("Kein Zimmer gefunden")?;
+
+    let pdf = invoice_pdf::InvoicePdf {
+        kind: invoice_pdf::DocumentKind::Invoice { payment: &context.payment_settings },
+        document: &context.issued.document,
+        company: &context.company_settings,
+        booking: &booking.booking,
+        guest,
+        room,
+        payment_recipient: context.payment_recipient.as_ref(),
+        rooms_label: context.rooms_label.as_deref(),
+    };
+
+    // 5. Render PDF natively (headless Chrome only as fallback)
+    let pdf_data = native_pdf::render_with_fallback(|| invoice_pdf::render(&pdf), false, || {
+        generate_invoice_html_pg(
+            booking,
+            guest,
+            room,
+            &context.company_settings,
+            &context.payment_settings,
+            context.payment_recipient.as_ref(),
+            &context.issued.document,
+        )
+    })?;
+    std::fs::write(&pdf_path, pdf_data)
+        .map_err(|e| format!("Save PDF error: {}", e))?;
+
+    let path_str = pdf_path.to_string_lossy().to_string();
+    InvoiceRepository::set_pdf_path(pool.inner(), context.issued.id, &path_str)
+        .await
+        .map_err(|e| format!("Fehler beim Speichern des PDF-Pfads: {}", e))?;
+
+    println!("✅ PDF SUCCESSFULLY GENERATED!");
+    println!("📄 Path: {}", path_str);
+    println!("════════════════════════════════════════════════════════");
+
+    Ok(path_str)
+}
+
+/// Consolidated invoice PDF of a booking group (one invoice for all rooms)
+#[tauri::command]
+async fn generate_group_invoice_pdf_command(
+    app: tauri::AppHandle,
+    group_id: i32,
+) -> Result<String, String> {
+    use tauri::Manager;
+
+    println!("🧾 Group invoice PDF for group {}", group_id);
+
+    let pool = app.state::<crate::database_pg::pool::DbPool>();
+    let context = load_group_invoice_context(pool.inner(), group_id).await?;
+    let booking = &context.booking;
+
+    let guest = booking.guest.as_ref().ok_or("Kein Gast gefunden")?;
+    let room = booking.room.as_ref().ok_or("Kein Zimmer gefunden")?;
+    let invoices_dir = app.path().app_data_dir()
+        .map_err(|e| format!("App directory error: {}", e))?
+        .join("invoices");
+    std::fs::create_dir_all(&invoices_dir)
+        .map_err(|e| format!("Create invoices dir error: {}", e))?;
+
+    let pdf = invoice_pdf::InvoicePdf {
+        kind: invoice_pdf::DocumentKind::Invoice { payment: &context.payment_settings },
+        document: &context.issued.document,
+        company: &context.company_settings,
+        booking: &booking.booking,
+        guest,
+        room,
+        payment_recipient: context.payment_recipient.as_ref(),
+        rooms_label: context.rooms_label.as_deref(),
+    };
+    let pdf_data = native_pdf::render_with_fallback(|| invoice_pdf::render(&pdf), false, || {
+        generate_invoice_html_pg(
+            booking,
+            guest,
+            room,
+            &context.company_settings,
+            &context.payment_settings,
+            context.payment_recipient.as_ref(),
+            &context.issued.document,
+        )
+    })?;
+
+    let pdf_path = invoices_dir.join(format!(
+        "Rechnung_{}_{}.pdf",
+        context.issued.invoice_number, booking.booking.reservierungsnummer
+    ));
+    std::fs::write(&pdf_path, pdf_data)
+        .map_err(|e| format!("Save PDF error: {}", e))?;
+
+    let path_str = pdf_path.to_string_lossy().to_string();
+    InvoiceRepository::set_pdf_path(pool.inner(), context.issued.id, &path_str)
+        .await
+        .map_err(|e| format!("Fehler beim Speichern des PDF-Pfads: {}", e))?;
+
+    println!("✅ Group invoice PDF generated: {}", path_str);
+    Ok(path_str)
+}
+
+/// PDF of a credit note (Stornorechnung / Rechnungskorrektur), rendered from its snapshot
+#[tauri::command]
+async fn generate_credit_note_pdf_command(
+    app: tauri::AppHandle,
+    credit_note_id: i32,
+) -> Result<String, String> {
+    use tauri::Manager;
+
+    println!("🧾 Credit note PDF for {}", credit_note_id);
+
+    let pool = app.state::<crate::database_pg::pool::DbPool>();
+    let credit_note = InvoiceRepository::get_by_id(pool.inner(), credit_note_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Gutschrift: {}", e))?;
+    let original_id = credit_note.corrects_invoice_id
+        .ok_or_else(|| format!("{} ist keine Gutschrift", credit_note.invoice_number))?;
+    let original = InvoiceRepository::get_by_id(pool.inner(), original_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Rechnung: {}", e))?;
+
+    let booking = BookingRepository::get_with_details(pool.inner(), credit_note.booking_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
     let company_settings = CompanySettingsRepository::get(pool.inner())
         .await
         .map_err(|e| format!("Fehler beim Laden der Firmeneinstellungen: {}", e))?;
+    let payment_recipient = match booking.booking.payment_recipient_id {
+        Some(recipient_id) => PaymentRecipientRepository::get_by_id(pool.inner(), recipient_id).await.ok(),
+        None => None,
+    };
 
-    // 5. Load payment settings
-    let payment_settings = PaymentSettingsRepository::get(pool.inner())
-        .await
-        .map_err(|e| format!("Fehler beim Laden der Zahlungseinstellungen: {}", e))?;
+    let guest = booking.guest.as_ref().ok_or("Kein Gast gefunden")?;
+    let room = booking.room.as_ref().ok_or("Kein Zimmer gefunden")?;
+    let invoices_dir = app.path().app_data_dir()
+        .map_err(|e| format!("App directory error: {}", e))?
+        .join("invoices");
+    std::fs::create_dir_all(&invoices_dir)
+        .map_err(|e| format!("Create invoices dir error: {}", e))?;
 
-    // 5b. Load external payment recipient if set
-    let payment_recipient = if let Some(recipient_id) = booking.booking.payment_recipient_id {
-        match PaymentRecipientRepository::get_by_id(pool.inner(), recipient_id).await {
-            Ok(recipient) => {
-                println!("✅ Loaded external payment recipient: {} (ID: {})", recipient.name, recipient_id);
-                Some(recipient)
-            }
-            Err(e) => {
-                println!("⚠️ Could not load payment recipient {}: {}", recipient_id, e);
-                None
-            }
-        }
-    } else {
-        None
+    let pdf = invoice_pdf::InvoicePdf {
+        kind: invoice_pdf::DocumentKind::CreditNote {
+            original: &original,
+            is_cancellation: credit_note.is_cancellation,
+            reason: credit_note.reason.as_deref(),
+        },
+        document: &credit_note.document,
+        company: &company_settings,
+        booking: &booking.booking,
+        guest,
+        room,
+        payment_recipient: payment_recipient.as_ref(),
+        rooms_label: None,
     };
+    let pdf_data = native_pdf::render_with_fallback(|| invoice_pdf::render(&pdf), false, || {
+        generate_credit_note_html_pg(
+            &booking,
+            guest,
+            room,
+            &company_settings,
+            payment_recipient.as_ref(),
+            &credit_note,
+            &original,
+        )
+    })?;
+
+    let pdf_path = invoices_dir.join(format!("Gutschrift_{}_{}.pdf", credit_note.invoice_number, booking.booking.reservierungsnummer));
+    std::fs::write(&pdf_path, pdf_data)
+        .map_err(|e| format!("Save PDF error: {}", e))?;
+
+    let path_str = pdf_path.to_string_lossy().to_string();
+    InvoiceRepository::set_pdf_path(pool.inner(), credit_note.id, &path_str)
+        .await
+        .map_err(|e| format!("Fehler beim Speichern des PDF-Pfads: {}", e))?;
 
-    // 5c. Load pricing settings for discount basis (Bug 2 fix)
-    let pricing_settings = PricingSettingsRepository::get(pool.inner())
-        .await
-        .unwrap_or_else(|_| database_pg::PricingSettings {
-            id: 1,
-            hauptsaison_aktiv: Some(false),
-            hauptsaison_start: None,
-            hauptsaison_ende: None,
-            mitglieder_rabatt_aktiv: Some(false),
-            mitglieder_rabatt_prozent: Some(15.0),
-            rabatt_basis: Some("zimmerpreis".to_string()),
-            updated_at: None,
-        });
+    println!("✅ Credit note PDF generated: {}", path_str);
+    Ok(path_str)
+}
 
-    // 6. Generate HTML invoice
-    let guest = booking.guest.as_ref().ok_or("Kein Gast gefunden")?;
-    let room = booking.room.as_ref().ok_or("Kein Zimmer gefunden")?;
+/// XRechnung (CII XML) of a booking for public-sector payers, stored next to the PDF invoices.
+/// Missing mandatory fields (Leitweg-ID, seller contact, IBAN, ...) are returned as error list.
+#[tauri::command]
+async fn generate_xrechnung_command(
+    app: tauri::AppHandle,
+    booking_id: i64,
+) -> Result<String, String> {
+    use tauri::Manager;
 
-    let html = generate_invoice_html_pg(
-        &booking,
-        guest,
-        room,
-        &company_settings,
-        &payment_settings,
-        payment_recipient.as_ref(),
-        &pricing_settings,  // Bug 2 fix: Pass pricing settings
-    )?;
+    println!("🧾 XRechnung export for booking {}", booking_id);
 
-    // 7. Launch headless Chrome and generate PDF
-    println!("🚀 Starting headless Chrome...");
-
-    let launch_options = LaunchOptions::default_builder()
-        .headless(true)
-        .sandbox(true)
-        .build()
-        .map_err(|e| format!("Chrome launch options error: {}", e))?;
-
-    let browser = Browser::new(launch_options)
-        .map_err(|e| format!("Chrome start error: {}", e))?;
-
-    let tab = browser.new_tab()
-        .map_err(|e| format!("Chrome tab error: {}", e))?;
-
-    // Load HTML content
-    let html_base64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &html);
-    let data_url = format!("data:text/html;base64,{}", html_base64);
-
-    tab.navigate_to(&data_url)
-        .map_err(|e| format!("Navigate error: {}", e))?;
-
-    tab.wait_until_navigated()
-        .map_err(|e| format!("Wait error: {}", e))?;
-
-    // Generate PDF
-    let pdf_options = PrintToPdfOptions {
-        landscape: Some(false),
-        print_background: Some(true),
-        paper_width: Some(8.27),  // A4
-        paper_height: Some(11.69),
-        margin_top: Some(0.4),
-        margin_bottom: Some(0.4),
-        margin_left: Some(0.4),
-        margin_right: Some(0.4),
-        ..Default::default()
-    };
+    let pool = app.state::<crate::database_pg::pool::DbPool>();
+    let context = load_invoice_context(pool.inner(), booking_id as i32).await?;
+    let guest = context.booking.guest.as_ref().ok_or("Kein Gast gefunden")?;
 
-    let pdf_data = tab.print_to_pdf(Some(pdf_options))
-        .map_err(|e| format!("PDF generation error: {}", e))?;
+    let parties = xrechnung::XRechnungParties::new(
+        &context.company_settings,
+        &context.payment_settings,
+        guest,
+        context.payment_recipient.as_ref(),
+    );
+    let reservierungsnummer = &context.booking.booking.reservierungsnummer;
+    let xml = xrechnung::generate_xrechnung(&context.issued.document, &parties, reservierungsnummer)
+        .map_err(|errors| {
+            println!("⚠️ XRechnung incomplete: {:?}", errors);
+            format!("XRechnung unvollständig:\n- {}", errors.join("\n- "))
+        })?;
 
-    // Save PDF
-    std::fs::write(&pdf_path, pdf_data)
-        .map_err(|e| format!("Save PDF error: {}", e))?;
+    let invoices_dir = app.path().app_data_dir()
+        .map_err(|e| format!("App directory error: {}", e))?
+        .join("invoices");
+    std::fs::create_dir_all(&invoices_dir)
+        .map_err(|e| format!("Create invoices dir error: {}", e))?;
 
-    let path_str = pdf_path.to_string_lossy().to_string();
-    println!("✅ PDF SUCCESSFULLY GENERATED!");
-    println!("📄 Path: {}", path_str);
-    println!("════════════════════════════════════════════════════════");
+    let xml_path = invoices_dir.join(format!("XRechnung_{}_{}.xml", context.issued.invoice_number, reservierungsnummer));
+    std::fs::write(&xml_path, xml)
+        .map_err(|e| format!("Save XRechnung error: {}", e))?;
 
+    let path_str = xml_path.to_string_lossy().to_string();
+    println!("✅ XRechnung generated: {}", path_str);
     Ok(path_str)
 }
 
@@ -4871,19 +6678,12 @@ fn generate_payment_qr_code(
     iban: &str,
     bic: &str,
     account_holder: &str,
-    amount: f64,
+    amount: Money,
     reference: &str,
 ) -> Result<String, String> {
     // EPC QR Code Format (European Payment Council)
     // https://www.europeanpaymentscouncil.eu/document-library/guidance-documents/quick-response-code-guidelines-enable-data-capture-initiation
-    let epc_data = format!(
-        "BCD\n002\n1\nSCT\n{}\n{}\n{}\nEUR{:.2}\n\n\n{}",
-        bic,
-        account_holder,
-        iban,
-        amount,
-        reference
-    );
+    let epc_data = invoice_pdf::epc_payload(iban, bic, account_holder, amount, reference);
 
     println!("🔧 [QR] Generating EPC QR Code:");
     println!("   IBAN: {}", iban);
@@ -4914,6 +6714,319 @@ fn generate_payment_qr_code(
     Ok(format!("data:image/png;base64,{}", base64_image))
 }
 
+/// Everything the invoice documents of a booking are built from (PDF and XRechnung)
+struct InvoiceContext {
+    booking: crate::database_pg::BookingWithDetails,
+    company_settings: crate::database_pg::models::CompanySettings,
+    payment_settings: crate::database_pg::models::PaymentSettings,
+    payment_recipient: Option<crate::database_pg::models::PaymentRecipient>,
+    issued: crate::database_pg::IssuedInvoice,
+    /// Room line of the invoice header for several rooms (group, split stay)
+    rooms_label: Option<String>,
+}
+
+/// Load booking, settings and external payment recipient and the issued invoice of the booking.
+/// GoBD: the invoice is issued (numbered and frozen) on first use, every later PDF or
+/// XRechnung is rendered from that snapshot. After a Stornorechnung a new invoice is issued.
+/// A split stay has one invoice for all segments, issued on the first segment.
+async fn load_invoice_context(pool: &DbPool, booking_id: i32) -> Result<InvoiceContext, String> {
+    let mut booking = BookingRepository::get_with_details(pool, booking_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
+
+    let booking_id = match booking.booking.split_parent_id {
+        Some(master_id) => {
+            booking = BookingRepository::get_with_details(pool, master_id)
+                .await
+                .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
+            master_id
+        }
+        None => booking_id,
+    };
+    let segments: Vec<_> = BookingRepository::get_stay_segments(pool, booking_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?
+        .into_iter()
+        .filter(|segment| segment.status != BookingStatus::Storniert)
+        .collect();
+    let is_split = segments.len() > 1;
+
+    if booking.booking.booking_group_id.is_some() {
+        return Err(format!(
+            "Buchung {} gehört zu einer Gruppenbuchung - bitte die Gruppenrechnung erstellen",
+            booking.booking.reservierungsnummer
+        ));
+    }
+
+    let company_settings = CompanySettingsRepository::get(pool)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Firmeneinstellungen: {}", e))?;
+
+    let payment_settings = PaymentSettingsRepository::get(pool)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Zahlungseinstellungen: {}", e))?;
+
+    // External payment recipient if set
+    let payment_recipient = if let Some(recipient_id) = booking.booking.payment_recipient_id {
+        match PaymentRecipientRepository::get_by_id(pool, recipient_id).await {
+            Ok(recipient) => {
+                println!("✅ Loaded external payment recipient: {} (ID: {})", recipient.name, recipient_id);
+                Some(recipient)
+            }
+            Err(e) => {
+                println!("⚠️ Could not load payment recipient {}: {}", recipient_id, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let existing = InvoiceRepository::get_active_for_booking(pool, booking_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Rechnung: {}", e))?;
+
+    let issued = match existing {
+        Some(issued) => {
+            println!("📄 Using issued invoice {} (snapshot)", issued.invoice_number);
+            issued
+        }
+        None => {
+            // Pricing settings for discount basis and tax rates (Bug 2 fix)
+            let pricing_settings = PricingSettingsRepository::get(pool)
+                .await
+                .unwrap_or_else(|_| pricing::default_pricing_settings());
+
+            let mut document = if is_split {
+                split_stay_invoice(pool, &segments, &pricing_settings, &payment_settings).await?
+            } else {
+                // Occupancy surcharge lines (extra adults, children)
+                let occupancy_lines = invoice_occupancy_lines(pool, &booking.booking, &pricing_settings).await;
+
+                let room = booking.room.as_ref().ok_or("Kein Zimmer gefunden")?;
+                invoice::build_invoice(
+                    &booking,
+                    room,
+                    &pricing_settings,
+                    &payment_settings,
+                    &occupancy_lines,
+                    chrono::Local::now().date_naive(),
+                )
+            };
+
+            // Deposits received so far (all segments of a split stay)
+            let paid_ids: Vec<i32> = if is_split {
+                segments.iter().map(|s| s.id).collect()
+            } else {
+                vec![booking_id]
+            };
+            let paid = PaymentRepository::get_paid_total(pool, &paid_ids)
+                .await
+                .map_err(|e| format!("Fehler beim Laden der Zahlungen: {}", e))?;
+            document.apply_payments(paid);
+
+            let issued = InvoiceRepository::issue(pool, booking_id, document, None)
+                .await
+                .map_err(|e| format!("Fehler beim Ausstellen der Rechnung: {}", e))?;
+            println!("🧾 Issued invoice {} for booking {}", issued.invoice_number, booking_id);
+            issued
+        }
+    };
+
+    // Invoice header shows the whole stay and every room of it
+    let rooms_label = if is_split {
+        booking.booking.checkin_date = issued.document.checkin_date.clone();
+        booking.booking.checkout_date = issued.document.checkout_date.clone();
+        let mut room_names = Vec::new();
+        for segment in &segments {
+            let room = RoomRepository::get_by_id(pool, segment.room_id)
+                .await
+                .map_err(|e| format!("Fehler beim Laden des Zimmers: {}", e))?;
+            room_names.push(room.name);
+        }
+        Some(format!("Zimmerwechsel - Zimmer {}", room_names.join(" → ")))
+    } else {
+        None
+    };
+
+    Ok(InvoiceContext {
+        booking,
+        company_settings,
+        payment_settings,
+        payment_recipient,
+        issued,
+        rooms_label,
+    })
+}
+
+/// Invoice of a split stay: the invoices of all segments combined, each line labelled with
+/// the room and the nights of its segment
+async fn split_stay_invoice(
+    pool: &DbPool,
+    segments: &[database_pg::Booking],
+    pricing_settings: &database_pg::PricingSettings,
+    payment_settings: &crate::database_pg::models::PaymentSettings,
+) -> Result<invoice::Invoice, String> {
+    let issue_date = chrono::Local::now().date_naive();
+    let short_date = |date: &str| {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map(|d| d.format("%d.%m.").to_string())
+            .unwrap_or_else(|_| date.to_string())
+    };
+
+    let mut parts = Vec::new();
+    for segment in segments {
+        let details = BookingRepository::get_with_details(pool, segment.id)
+            .await
+            .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
+        let room = details.room.as_ref().ok_or("Kein Zimmer gefunden")?;
+        let occupancy_lines = invoice_occupancy_lines(pool, &details.booking, pricing_settings).await;
+        let document = invoice::build_invoice(
+            &details,
+            room,
+            pricing_settings,
+            payment_settings,
+            &occupancy_lines,
+            issue_date,
+        );
+        let label = format!(
+            "Zimmer {} ({} - {})",
+            room.name,
+            short_date(&segment.checkin_date),
+            short_date(&segment.checkout_date)
+        );
+        parts.push((label, document));
+    }
+
+    let due_date = issue_date + chrono::Duration::days(payment_settings.payment_due_days.unwrap_or(14) as i64);
+    Ok(invoice::group_invoice(&parts, issue_date, due_date))
+}
+
+/// Invoice context of a booking group: the consolidated invoice of all room bookings that are
+/// not cancelled, issued on first use like a single invoice. `booking` is the master booking
+/// carrying the group's reservation number, the whole stay and all guests.
+async fn load_group_invoice_context(pool: &DbPool, group_id: i32) -> Result<InvoiceContext, String> {
+    let group = BookingGroupRepository::get_with_rooms(pool, group_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Gruppe: {}", e))?;
+    let rooms: Vec<_> = group
+        .rooms
+        .iter()
+        .filter(|r| r.booking.status != BookingStatus::Storniert)
+        .collect();
+    let master = rooms.first().ok_or("Die Gruppe hat keine aktiven Zimmerbuchungen")?;
+
+    let mut booking = BookingRepository::get_with_details(pool, master.booking.id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
+
+    let company_settings = CompanySettingsRepository::get(pool)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Firmeneinstellungen: {}", e))?;
+
+    let payment_settings = PaymentSettingsRepository::get(pool)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Zahlungseinstellungen: {}", e))?;
+
+    let payment_recipient = match group.group.payment_recipient_id {
+        Some(recipient_id) => PaymentRecipientRepository::get_by_id(pool, recipient_id).await.ok(),
+        None => None,
+    };
+
+    let existing = InvoiceRepository::get_active_for_group(pool, group_id)
+        .await
+        .map_err(|e| format!("Fehler beim Laden der Rechnung: {}", e))?;
+
+    let issued = match existing {
+        Some(issued) => {
+            println!("📄 Using issued group invoice {} (snapshot)", issued.invoice_number);
+            issued
+        }
+        None => {
+            let pricing_settings = PricingSettingsRepository::get(pool)
+                .await
+                .unwrap_or_else(|_| pricing::default_pricing_settings());
+            let issue_date = chrono::Local::now().date_naive();
+
+            let mut parts = Vec::new();
+            for room in &rooms {
+                let details = BookingRepository::get_with_details(pool, room.booking.id)
+                    .await
+                    .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
+                let room_data = details.room.as_ref().ok_or("Kein Zimmer gefunden")?;
+                let occupancy_lines = invoice_occupancy_lines(pool, &details.booking, &pricing_settings).await;
+                let document = invoice::build_invoice(
+                    &details,
+                    room_data,
+                    &pricing_settings,
+                    &payment_settings,
+                    &occupancy_lines,
+                    issue_date,
+                );
+                parts.push((format!("Zimmer {}", room.room_name), document));
+            }
+
+            let due_date = issue_date + chrono::Duration::days(payment_settings.payment_due_days.unwrap_or(14) as i64);
+            let mut document = invoice::group_invoice(&parts, issue_date, due_date);
+
+            let room_ids: Vec<i32> = rooms.iter().map(|r| r.booking.id).collect();
+            let paid = PaymentRepository::get_paid_total(pool, &room_ids)
+                .await
+                .map_err(|e| format!("Fehler beim Laden der Zahlungen: {}", e))?;
+            document.apply_payments(paid);
+
+            let issued = InvoiceRepository::issue_for_group(pool, group_id, master.booking.id, document, None)
+                .await
+                .map_err(|e| format!("Fehler beim Ausstellen der Rechnung: {}", e))?;
+            println!("🧾 Issued group invoice {} for group {}", issued.invoice_number, group_id);
+            issued
+        }
+    };
+
+    // Invoice header shows the group, not the master room
+    booking.booking.reservierungsnummer = group.group.reservierungsnummer.clone();
+    booking.booking.checkin_date = issued.document.checkin_date.clone();
+    booking.booking.checkout_date = issued.document.checkout_date.clone();
+    booking.booking.anzahl_gaeste = rooms.iter().map(|r| r.booking.anzahl_gaeste).sum();
+    booking.booking.payment_recipient_id = group.group.payment_recipient_id;
+
+    let room_names: Vec<&str> = rooms.iter().map(|r| r.room_name.as_str()).collect();
+    let rooms_label = format!("{} - {} Zimmer ({})", group.group.name, rooms.len(), room_names.join(", "));
+
+    Ok(InvoiceContext {
+        booking,
+        company_settings,
+        payment_settings,
+        payment_recipient,
+        issued,
+        rooms_label: Some(rooms_label),
+    })
+}
+
+/// Occupancy lines for the invoice. The stored `belegung_preis` snapshot is authoritative:
+/// the detailed lines of a recalculation are only used if they add up to it.
+async fn invoice_occupancy_lines(
+    pool: &DbPool,
+    booking: &database_pg::Booking,
+    pricing_settings: &database_pg::PricingSettings,
+) -> Vec<pricing::OccupancyLine> {
+    let stored = booking.belegung_preis.unwrap_or(Money::ZERO);
+    if !stored.is_positive() {
+        return Vec::new();
+    }
+
+    match calculate_stored_booking_price(pool, booking, pricing_settings).await {
+        Ok(breakdown) if breakdown.occupancy_total == stored => breakdown.occupancy,
+        _ => vec![pricing::OccupancyLine {
+            description: "Personenaufpreis".to_string(),
+            persons: 1,
+            nights: booking.anzahl_naechte.unwrap_or(1),
+            price_per_night: stored.divide(booking.anzahl_naechte.unwrap_or(1).max(1) as i64),
+            total: stored,
+        }],
+    }
+}
+
 fn generate_invoice_html_pg(
     booking: &crate::database_pg::BookingWithDetails,
     guest: &crate::database_pg::models::Guest,
@@ -4921,7 +7034,7 @@ fn generate_invoice_html_pg(
     company: &crate::database_pg::models::CompanySettings,
     payment: &crate::database_pg::models::PaymentSettings,
     payment_recipient: Option<&crate::database_pg::models::PaymentRecipient>,
-    pricing_settings: &crate::database_pg::models::PricingSettings,  // Bug 2 fix: Added for discount basis
+    invoice: &crate::invoice::Invoice,
 ) -> Result<String, String> {
     println!("┌─────────────────────────────────────────────────────┐");
     println!("│  INVOICE HTML GENERATOR (PostgreSQL - PORTED)       │");
@@ -4946,22 +7059,9 @@ fn generate_invoice_html_pg(
         }
     };
 
-    let calculate_nights = |checkin: &str, checkout: &str| -> i32 {
-        if let (Ok(date_in), Ok(date_out)) = (
-            NaiveDate::parse_from_str(checkin, "%Y-%m-%d"),
-            NaiveDate::parse_from_str(checkout, "%Y-%m-%d")
-        ) {
-            (date_out - date_in).num_days() as i32
-        } else {
-            1
-        }
-    };
-
-    let format_currency = |amount: f64| -> String {
-        format!("{:.2} €", amount).replace(".", ",")
-    };
+    let format_currency = |amount: Money| -> String { amount.format_eur() };
 
-    let nights = calculate_nights(&b.checkin_date, &b.checkout_date);
+    let nights = invoice.nights;
 
     // ============================================================================
     // HEADER - Company Info (EXAKT GLEICH WIE SQLite VERSION)
@@ -4976,53 +7076,13 @@ fn generate_invoice_html_pg(
     html = html.replace("{{TAX_ID}}", company.tax_id.as_deref().unwrap_or(""));
 
     // Logo (als Base64 Data-URL für PDF-Generation mit headless-chrome)
-    // Primary: Use logo_data from DB (Base64). Fallback: Read from logo_path file.
-    let logo_html = if let (Some(ref logo_data), Some(ref mime_type)) = (&company.logo_data, &company.logo_mime_type) {
-        if !logo_data.is_empty() {
-            let data_url = format!("data:{};base64,{}", mime_type, logo_data);
-            println!("🖼️ [INVOICE] Logo aus DB geladen ({} bytes Base64)", logo_data.len());
-            format!(r#"<img src="{}" alt="Company Logo" style="max-width: 100%; max-height: 100%; object-fit: contain;" />"#, data_url)
-        } else {
-            format!(r#"<div class="logo-placeholder">[LOGO]<br>{}</div>"#, &company.company_name)
-        }
-    } else if let Some(ref logo_path) = company.logo_path {
-        if !logo_path.is_empty() && std::path::Path::new(logo_path).exists() {
-            match std::fs::read(logo_path) {
-                Ok(logo_bytes) => {
-                    let base64_logo = general_purpose::STANDARD.encode(&logo_bytes);
-                    let mime_type = if logo_path.to_lowercase().ends_with(".png") {
-                        "image/png"
-                    } else if logo_path.to_lowercase().ends_with(".jpg") || logo_path.to_lowercase().ends_with(".jpeg") {
-                        "image/jpeg"
-                    } else {
-                        "image/png"
-                    };
-                    let data_url = format!("data:{};base64,{}", mime_type, base64_logo);
-                    println!("🖼️ [INVOICE] Logo aus Datei geladen: {} ({} bytes)", logo_path, logo_bytes.len());
-                    format!(r#"<img src="{}" alt="Company Logo" style="max-width: 100%; max-height: 100%; object-fit: contain;" />"#, data_url)
-                },
-                Err(e) => {
-                    println!("⚠️ [INVOICE] Fehler beim Laden des Logos: {}", e);
-                    format!(r#"<div class="logo-placeholder">[LOGO]<br>{}</div>"#, &company.company_name)
-                }
-            }
-        } else {
-            println!("⚠️ [INVOICE] Logo-Pfad existiert nicht: {:?}", logo_path);
-            format!(r#"<div class="logo-placeholder">[LOGO]<br>{}</div>"#, &company.company_name)
-        }
-    } else {
-        println!("⚠️ [INVOICE] Kein Logo in Company Settings");
-        format!(r#"<div class="logo-placeholder">[LOGO]<br>{}</div>"#, &company.company_name)
-    };
+    let logo_html = company_logo_html(company);
     html = html.replace("{{LOGO_HTML}}", &logo_html);
 
     // ============================================================================
     // HEADER - Invoice Number (EXAKT GLEICH WIE SQLite VERSION)
     // ============================================================================
-    let invoice_number = format!("#{}-{:04}",
-        chrono::Local::now().format("%Y"),
-        b.id
-    );
+    let invoice_number = invoice.number.clone();
     html = html.replace("{{INVOICE_NUMBER}}", &invoice_number);
 
     // ============================================================================
@@ -5040,51 +7100,7 @@ fn generate_invoice_html_pg(
     html = html.replace("{{GUEST_ADDRESS}}", &guest_address);
 
     // Rechnungsempfänger (optional) - EXAKT GLEICH WIE SQLite VERSION
-    println!("🔍 [INVOICE] payment_recipient provided: {}", payment_recipient.is_some());
-
-    let invoice_recipient_card = if let Some(recipient) = payment_recipient {
-        println!("✅ [INVOICE] External payment recipient: {} ({})",
-            recipient.name,
-            recipient.city.as_deref().unwrap_or("")
-        );
-
-        let recipient_address = format!(
-            "{}{}{} {}{}",
-            recipient.street.as_ref().map(|s| format!("{}<br>", s)).unwrap_or_default(),
-            recipient.plz.as_ref().map(|p| format!("{} ", p)).unwrap_or_default(),
-            recipient.city.as_deref().unwrap_or(""),
-            if recipient.country.as_deref().unwrap_or("Deutschland") != "Deutschland" {
-                format!("<br>{}", recipient.country.as_deref().unwrap_or(""))
-            } else {
-                "".to_string()
-            },
-            if let Some(ref contact) = recipient.contact_person {
-                format!("<br><small style='color: #64748b;'>Ansprechpartner: {}</small>", contact)
-            } else {
-                "".to_string()
-            }
-        );
-
-        let card_html = format!(
-            r#"<div class="recipient-card" style="padding: 8px; background: linear-gradient(135deg, #3b82f6 0%, #2563eb 100%); border-radius: 8px; border: 1px solid #1d4ed8; display: flex; flex-direction: column; justify-content: center; min-height: 85px;">
-                <div style="font-size: 8px; color: rgba(255,255,255,0.8); text-transform: uppercase; font-weight: 600; letter-spacing: 0.05em; margin-bottom: 5px;">⚠️ EXTERNE RECHNUNG</div>
-                <div style="font-size: 11px; font-weight: bold; color: white; margin-bottom: 3px;">{}{}</div>
-                <div style="font-size: 9px; color: rgba(255,255,255,0.9); line-height: 1.3;">{}</div>
-                <div style="margin-top: 6px; padding-top: 6px; border-top: 1px solid rgba(255,255,255,0.2); font-size: 8px; color: rgba(255,255,255,0.7); line-height: 1.3;">
-                    Diese Rechnung wird an den oben angegebenen externen Empfänger adressiert.
-                </div>
-            </div>"#,
-            recipient.name,
-            recipient.company.as_ref().map(|c| format!("<br><span style='font-size: 10px; font-weight: normal;'>{}</span>", c)).unwrap_or_default(),
-            recipient_address
-        );
-
-        println!("📦 [INVOICE] Generated card HTML ({} bytes)", card_html.len());
-        card_html
-    } else {
-        println!("ℹ️ [INVOICE] payment_recipient is None - no external recipient");
-        "".to_string()
-    };
+    let invoice_recipient_card = recipient_card_html(payment_recipient);
 
     println!("🔄 [INVOICE] Replacing {{{{INVOICE_RECIPIENT_CARD}}}} with content ({} bytes)", invoice_recipient_card.len());
     html = html.replace("{{INVOICE_RECIPIENT_CARD}}", &invoice_recipient_card);
@@ -5106,7 +7122,7 @@ fn generate_invoice_html_pg(
     // ============================================================================
     // META - Dates & Booking Number (EXAKT GLEICH WIE SQLite VERSION)
     // ============================================================================
-    let invoice_date = chrono::Local::now().format("%d.%m.%Y").to_string();
+    let invoice_date = invoice.issue_date.format("%d.%m.%Y").to_string();
     html = html.replace("{{INVOICE_DATE}}", &invoice_date);
 
     let stay_period = format!(
@@ -5117,11 +7133,7 @@ fn generate_invoice_html_pg(
     html = html.replace("{{STAY_PERIOD}}", &stay_period);
 
     // Fälligkeitsdatum (EXAKT GLEICH WIE SQLite VERSION)
-    let due_date = chrono::Local::now()
-        .checked_add_signed(chrono::Duration::days(payment.payment_due_days.unwrap_or(14) as i64))
-        .unwrap()
-        .format("%d.%m.%Y")
-        .to_string();
+    let due_date = invoice.due_date.format("%d.%m.%Y").to_string();
     html = html.replace("{{DUE_DATE}}", &due_date);
 
     html = html.replace("{{BOOKING_NUMBER}}", &b.reservierungsnummer);
@@ -5135,214 +7147,52 @@ fn generate_invoice_html_pg(
                     <div class="meta-value">{}</div>
                 </div>"#,
                 membership_id
-            )
-        } else {
-            "".to_string()
-        }
-    } else {
-        "".to_string()
-    };
-    html = html.replace("{{MEMBERSHIP_META}}", &membership_meta);
-
-    // ============================================================================
-    // SERVICES TABLE (EXAKT GLEICH WIE SQLite VERSION)
-    // ============================================================================
-    let mut service_rows = Vec::new();
-    let mut pos = 1;
-
-    // 1. Zimmerpreis (Übernachtung)
-    let grundpreis = b.grundpreis.unwrap_or_else(|| {
-        // Fallback für alte Buchungen ohne gespeicherten Grundpreis:
-        // Berechne aus aktuellem Zimmerpreis × Nächte
-        let price = room.nebensaison_preis.unwrap_or(0.0);
-        price * nights as f64
-    });
-    let room_price_per_night = grundpreis / nights as f64;
-    service_rows.push(format!(
-        r#"<tr>
-            <td>{:02}</td>
-            <td>
-                <div class="item-description">Übernachtung {}</div>
-                <div class="item-details">Zimmer {}, {} Personen</div>
-            </td>
-            <td>{} Nächte</td>
-            <td>{}</td>
-            <td>{}</td>
-        </tr>"#,
-        pos,
-        room.gebaeude_typ,
-        room.name,
-        b.anzahl_gaeste,
-        nights,
-        format_currency(room_price_per_night),
-        format_currency(grundpreis)
-    ));
-    pos += 1;
-
-    // 2. Endreinigung - NUR anzeigen wenn NICHT schon als Service vorhanden
-    // FIX: Verhindert Doppelzählung wenn Endreinigung bereits in Services-Liste ist
-    let has_endreinigung_in_services = booking.services.iter()
-        .any(|s| s.service_name.to_lowercase().contains("endreinigung")
-              || s.service_name.to_lowercase().contains("cleaning"));
-
-    let endreinigung = room.endreinigung.unwrap_or(0.0);
-    if endreinigung > 0.0 && !has_endreinigung_in_services {
-        service_rows.push(format!(
-            r#"<tr>
-                <td>{:02}</td>
-                <td>
-                    <div class="item-description">Endreinigung</div>
-                    <div class="item-details">Zimmer {}</div>
-                </td>
-                <td>1 Pauschal</td>
-                <td>{}</td>
-                <td>{}</td>
-            </tr>"#,
-            pos,
-            room.name,
-            format_currency(endreinigung),
-            format_currency(endreinigung)
-        ));
-        pos += 1;
-    }
-
-    // 3. Services (Zusatzleistungen) - EXAKT GLEICH WIE SQLite VERSION
-    for service in &booking.services {
-        let quantity_label = "1 Pauschal".to_string();
-        let total_price = service.service_price as f64;
-
-        service_rows.push(format!(
-            r#"<tr>
-                <td>{:02}</td>
-                <td>
-                    <div class="item-description">{}</div>
-                    <div class="item-details">{}</div>
-                </td>
-                <td>{}</td>
-                <td>{}</td>
-                <td>{}</td>
-            </tr>"#,
-            pos,
-            service.service_name,
-            "", // AdditionalService hat keine description
-            quantity_label,
-            format_currency(total_price),
-            format_currency(total_price)
-        ));
-        pos += 1;
-    }
+            )
+        } else {
+            "".to_string()
+        }
+    } else {
+        "".to_string()
+    };
+    html = html.replace("{{MEMBERSHIP_META}}", &membership_meta);
 
-    html = html.replace("{{SERVICE_ROWS}}", &service_rows.join("\n"));
+    // ============================================================================
+    // SERVICES TABLE - Positionen aus dem gemeinsamen Rechnungsdokument (invoice.rs)
+    // ============================================================================
+    let service_rows = invoice_line_rows(&invoice.lines);
+    html = html.replace("{{SERVICE_ROWS}}", &service_rows);
 
     // ============================================================================
-    // TOTALS - Calculate prices (FIX: Berechne Services direkt!)
+    // TOTALS
     // ============================================================================
     // ⚠️ WICHTIG: Alle Preise in Deutschland sind BRUTTOPREISE (inkl. MwSt.)
-
-    // Services aus booking.services summieren (NICHT aus b.services_preis!)
-    let services_from_list: f64 = booking.services.iter()
-        .map(|s| s.service_price)
-        .sum();
-
-    // Endreinigung NUR hinzufügen wenn NICHT schon in Services enthalten
-    // FIX: has_endreinigung_in_services wurde oben bereits ermittelt
-    let endreinigung_to_add = if has_endreinigung_in_services {
-        0.0  // Schon in services_from_list enthalten
-    } else {
-        room.endreinigung.unwrap_or(0.0)
-    };
-
-    // Gesamte Zusatzleistungen = Services + Endreinigung (wenn nicht bereits enthalten)
-    let services_preis = services_from_list + endreinigung_to_add;
-
-    // Subtotal = Übernachtung + Alle Zusatzleistungen
-    let subtotal = grundpreis + services_preis; // Brutto (inkl. MwSt.)
-
+    // Kurtaxe ist in der Zwischensumme enthalten, kommt aber nicht in die Rabatte
     println!("📊 [INVOICE] Price breakdown:");
-    println!("   Grundpreis (Übernachtung): {:.2} €", grundpreis);
-    println!("   Services aus Liste: {:.2} €", services_from_list);
-    println!("   Endreinigung (hinzugefügt): {:.2} € (in Services: {})", endreinigung_to_add, has_endreinigung_in_services);
-    println!("   Services gesamt: {:.2} €", services_preis);
-    println!("   Subtotal: {:.2} €", subtotal);
-
-    // MwSt.-Anteil berechnen (informativ - bereits im Preis enthalten!)
-    let tax_7_base = grundpreis; // Übernachtung = 7% MwSt.
-    let tax_19_base = services_preis; // Services = 19% MwSt.
-
-    let tax_7 = tax_7_base / 1.07 * 0.07; // In Brutto enthaltene MwSt.
-    let tax_19 = tax_19_base / 1.19 * 0.19; // In Brutto enthaltene MwSt.
+    println!("   Zwischensumme (inkl. Kurtaxe): {:.2} €", invoice.subtotal);
+    println!("   Rabatte: {:.2} €", invoice.discounts_total);
+    println!("   Kurtaxe: {:.2} €", invoice.kurtaxe);
+    println!("💰 [INVOICE] Credit used for booking {}: {:.2} €", b.id, invoice.credit_used);
 
     println!("📊 [INVOICE] Tax calculation:");
-    println!("   Davon MwSt. 7%: {:.2} €", tax_7);
-    println!("   Davon MwSt. 19%: {:.2} €", tax_19);
-
-    // Rabatt-Basis für Discount-Berechnung (wird vor rabatt_preis benötigt)
-    let rabatt_basis = pricing_settings.rabatt_basis.clone().unwrap_or("zimmerpreis".to_string());
-    let discount_base = if rabatt_basis == "gesamtpreis" {
-        subtotal
-    } else {
-        grundpreis
-    };
-
-    // Rabatte + Guthaben (ANGEPASST FÜR PostgreSQL)
-    let rabatt_preis = b.rabatt_preis.unwrap_or_else(|| {
-        // Fallback für Buchungen mit NULL rabatt_preis:
-        // Summe aus Discount-Records berechnen
-        booking.discounts.iter().map(|d| {
-            d.calculated_amount.unwrap_or_else(|| {
-                if d.discount_type == "percent" {
-                    discount_base * (d.discount_value / 100.0)
-                } else {
-                    d.discount_value
-                }
-            })
-        }).sum()
-    });
-    let credit_used = b.credit_used.unwrap_or(0.0);
-    println!("💰 [INVOICE] Credit used for booking {}: {:.2} €", b.id, credit_used);
+    for line in &invoice.tax_lines {
+        println!("   {} ({}%): brutto {:.2} €, netto {:.2} €, MwSt. {:.2} €", line.category, line.rate, line.gross, line.net, line.tax);
+    }
 
     // ✅ FIX: MwSt. ist bereits in Subtotal enthalten
-    let grand_total = subtotal - rabatt_preis - credit_used;
+    let grand_total = invoice.amount_due;
     println!("💵 [INVOICE] Grand total: {:.2} €", grand_total);
 
-    html = html.replace("{{SUBTOTAL}}", &format_currency(subtotal));
+    html = html.replace("{{SUBTOTAL}}", &format_currency(invoice.subtotal));
 
-    // Tax Rows (EXAKT GLEICH WIE SQLite VERSION)
-    let mut tax_rows = String::new();
-    if tax_7 > 0.0 {
-        tax_rows.push_str(&format!(
-            r#"<div class="total-row tax">
-                <span class="total-label">MwSt. 7% (Übernachtung)</span>
-                <span>{}</span>
-            </div>"#,
-            format_currency(tax_7)
-        ));
-    }
-    if tax_19 > 0.0 {
-        tax_rows.push_str(&format!(
-            r#"<div class="total-row tax">
-                <span class="total-label">MwSt. 19% (Zusatzleistungen)</span>
-                <span>{}</span>
-            </div>"#,
-            format_currency(tax_19)
-        ));
-    }
+    // Tax Rows - USt.-Aufstellung pro Steuersatz (Netto / MwSt. / Brutto)
+    let tax_rows = tax_table_html(&invoice.tax_lines);
     html = html.replace("{{TAX_ROWS}}", &tax_rows);
 
     // Discount Rows - ALLE Rabatte direkt aus der discounts-Tabelle anzeigen
-    // Verwende gespeicherten calculated_amount wenn vorhanden (Fix 2: Konsistente Rabatte)
+    // (gespeicherter calculated_amount, Fallback Neuberechnung - siehe invoice.rs)
     let mut discount_rows = String::new();
 
-    for d in &booking.discounts {
-        // Gespeicherten Betrag verwenden, Fallback: Neuberechnung für alte Buchungen
-        let amount = d.calculated_amount.unwrap_or_else(|| {
-            if d.discount_type == "percent" {
-                discount_base * (d.discount_value / 100.0)
-            } else {
-                d.discount_value
-            }
-        });
-
+    for d in &invoice.discounts {
         if !discount_rows.is_empty() {
             discount_rows.push_str("\n");
         }
@@ -5352,19 +7202,19 @@ fn generate_invoice_html_pg(
                 <span class="total-label">{}</span>
                 <span>- {}</span>
             </div>"#,
-            d.discount_name,
-            format_currency(amount)
+            d.name,
+            format_currency(d.amount)
         ));
     }
 
     // 💰 Credit Row (wenn Guthaben verrechnet wurde)
-    if credit_used > 0.0 {
+    if invoice.credit_used.is_positive() {
         let credit_row = format!(
             r#"<div class="total-row" style="color: #10b981; font-size: 13px; font-weight: 600;">
                 <span class="total-label">💰 Verrechnetes Gast-Guthaben</span>
                 <span>- {}</span>
             </div>"#,
-            format_currency(credit_used)
+            format_currency(invoice.credit_used)
         );
         if !discount_rows.is_empty() {
             discount_rows.push_str("\n");
@@ -5372,6 +7222,21 @@ fn generate_invoice_html_pg(
         discount_rows.push_str(&credit_row);
     }
 
+    // Bereits eingegangene Zahlungen (Anzahlungen)
+    if invoice.paid.is_positive() {
+        let paid_row = format!(
+            r#"<div class="total-row" style="color: #10b981; font-size: 13px; font-weight: 600;">
+                <span class="total-label">Bereits bezahlt (Anzahlungen)</span>
+                <span>- {}</span>
+            </div>"#,
+            format_currency(invoice.paid)
+        );
+        if !discount_rows.is_empty() {
+            discount_rows.push('\n');
+        }
+        discount_rows.push_str(&paid_row);
+    }
+
     html = html.replace("{{DISCOUNT_ROWS}}", &discount_rows);
     html = html.replace("{{GRAND_TOTAL}}", &format_currency(grand_total));
 
@@ -5416,6 +7281,252 @@ fn generate_invoice_html_pg(
     Ok(html)
 }
 
+/// Company logo as Base64 data URL for the PDF rendering with headless-chrome.
+/// Primary: logo_data from DB (Base64). Fallback: read from logo_path file.
+fn company_logo_html(company: &crate::database_pg::models::CompanySettings) -> String {
+    if let (Some(ref logo_data), Some(ref mime_type)) = (&company.logo_data, &company.logo_mime_type) {
+        if !logo_data.is_empty() {
+            let data_url = format!("data:{};base64,{}", mime_type, logo_data);
+            println!("🖼️ [INVOICE] Logo aus DB geladen ({} bytes Base64)", logo_data.len());
+            format!(r#"<img src="{}" alt="Company Logo" style="max-width: 100%; max-height: 100%; object-fit: contain;" />"#, data_url)
+        } else {
+            format!(r#"<div class="logo-placeholder">[LOGO]<br>{}</div>"#, &company.company_name)
+        }
+    } else if let Some(ref logo_path) = company.logo_path {
+        if !logo_path.is_empty() && std::path::Path::new(logo_path).exists() {
+            match std::fs::read(logo_path) {
+                Ok(logo_bytes) => {
+                    let base64_logo = general_purpose::STANDARD.encode(&logo_bytes);
+                    let mime_type = if logo_path.to_lowercase().ends_with(".png") {
+                        "image/png"
+                    } else if logo_path.to_lowercase().ends_with(".jpg") || logo_path.to_lowercase().ends_with(".jpeg") {
+                        "image/jpeg"
+                    } else {
+                        "image/png"
+                    };
+                    let data_url = format!("data:{};base64,{}", mime_type, base64_logo);
+                    println!("🖼️ [INVOICE] Logo aus Datei geladen: {} ({} bytes)", logo_path, logo_bytes.len());
+                    format!(r#"<img src="{}" alt="Company Logo" style="max-width: 100%; max-height: 100%; object-fit: contain;" />"#, data_url)
+                },
+                Err(e) => {
+                    println!("⚠️ [INVOICE] Fehler beim Laden des Logos: {}", e);
+                    format!(r#"<div class="logo-placeholder">[LOGO]<br>{}</div>"#, &company.company_name)
+                }
+            }
+        } else {
+            println!("⚠️ [INVOICE] Logo-Pfad existiert nicht: {:?}", logo_path);
+            format!(r#"<div class="logo-placeholder">[LOGO]<br>{}</div>"#, &company.company_name)
+        }
+    } else {
+        println!("⚠️ [INVOICE] Kein Logo in Company Settings");
+        format!(r#"<div class="logo-placeholder">[LOGO]<br>{}</div>"#, &company.company_name)
+    }
+}
+
+/// Card for an external invoice recipient (empty if the guest pays)
+fn recipient_card_html(payment_recipient: Option<&crate::database_pg::models::PaymentRecipient>) -> String {
+    println!("🔍 [INVOICE] payment_recipient provided: {}", payment_recipient.is_some());
+
+    if let Some(recipient) = payment_recipient {
+        println!("✅ [INVOICE] External payment recipient: {} ({})",
+            recipient.name,
+            recipient.city.as_deref().unwrap_or("")
+        );
+
+        let recipient_address = format!(
+            "{}{}{} {}{}",
+            recipient.street.as_ref().map(|s| format!("{}<br>", s)).unwrap_or_default(),
+            recipient.plz.as_ref().map(|p| format!("{} ", p)).unwrap_or_default(),
+            recipient.city.as_deref().unwrap_or(""),
+            if recipient.country.as_deref().unwrap_or("Deutschland") != "Deutschland" {
+                format!("<br>{}", recipient.country.as_deref().unwrap_or(""))
+            } else {
+                "".to_string()
+            },
+            if let Some(ref contact) = recipient.contact_person {
+                format!("<br><small style='color: #64748b;'>Ansprechpartner: {}</small>", contact)
+            } else {
+                "".to_string()
+            }
+        );
+
+        let card_html = format!(
+            r#"<div class="recipient-card" style="padding: 8px; background: linear-gradient(135deg, #3b82f6 0%, #2563eb 100%); border-radius: 8px; border: 1px solid #1d4ed8; display: flex; flex-direction: column; justify-content: center; min-height: 85px;">
+                <div style="font-size: 8px; color: rgba(255,255,255,0.8); text-transform: uppercase; font-weight: 600; letter-spacing: 0.05em; margin-bottom: 5px;">⚠️ EXTERNE RECHNUNG</div>
+                <div style="font-size: 11px; font-weight: bold; color: white; margin-bottom: 3px;">{}{}</div>
+                <div style="font-size: 9px; color: rgba(255,255,255,0.9); line-height: 1.3;">{}</div>
+                <div style="margin-top: 6px; padding-top: 6px; border-top: 1px solid rgba(255,255,255,0.2); font-size: 8px; color: rgba(255,255,255,0.7); line-height: 1.3;">
+                    Diese Rechnung wird an den oben angegebenen externen Empfänger adressiert.
+                </div>
+            </div>"#,
+            recipient.name,
+            recipient.company.as_ref().map(|c| format!("<br><span style='font-size: 10px; font-weight: normal;'>{}</span>", c)).unwrap_or_default(),
+            recipient_address
+        );
+
+        println!("📦 [INVOICE] Generated card HTML ({} bytes)", card_html.len());
+        card_html
+    } else {
+        println!("ℹ️ [INVOICE] payment_recipient is None - no external recipient");
+        "".to_string()
+    }
+}
+
+/// Table rows of the invoice positions (shared by invoice and credit note)
+fn invoice_line_rows(lines: &[crate::invoice::InvoiceLine]) -> String {
+    let rows: Vec<String> = lines.iter().map(|line| {
+        format!(
+            r#"<tr>
+                <td>{:02}</td>
+                <td>
+                    <div class="item-description">{}</div>
+                    <div class="item-details">{}</div>
+                </td>
+                <td>{}</td>
+                <td>{}</td>
+                <td>{}</td>
+            </tr>"#,
+            line.position,
+            line.description,
+            line.details,
+            line.quantity_label,
+            line.unit_price.format_eur(),
+            line.total.format_eur()
+        )
+    }).collect();
+    rows.join("\n")
+}
+
+/// USt.-Aufstellung pro Steuersatz (Netto / MwSt. / Brutto)
+fn tax_table_html(tax_lines: &[pricing::TaxLine]) -> String {
+    let rows: Vec<String> = tax_lines.iter().map(|line| {
+        let label = match line.category.as_str() {
+            pricing::TAX_ACCOMMODATION => format!("{}% Beherbergung", line.rate),
+            pricing::TAX_EXEMPT => "nicht steuerbar".to_string(),
+            _ => format!("{}% Zusatzleistungen", line.rate),
+        };
+        format!(
+            r#"<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>"#,
+            label,
+            line.net.format_eur(),
+            line.tax.format_eur(),
+            line.gross.format_eur()
+        )
+    }).collect();
+    format!(
+        r#"<table class="tax-summary">
+                <thead><tr><th>Steuersatz</th><th>Netto</th><th>MwSt.</th><th>Brutto</th></tr></thead>
+                <tbody>{}</tbody>
+            </table>"#,
+        rows.join("")
+    )
+}
+
+/// Credit note (Stornorechnung / Rechnungskorrektur) - own template, references the original invoice
+#[allow(clippy::too_many_arguments)]
+fn generate_credit_note_html_pg(
+    booking: &crate::database_pg::BookingWithDetails,
+    guest: &crate::database_pg::models::Guest,
+    room: &crate::database_pg::models::Room,
+    company: &crate::database_pg::models::CompanySettings,
+    payment_recipient: Option<&crate::database_pg::models::PaymentRecipient>,
+    credit_note: &crate::database_pg::IssuedInvoice,
+    original: &crate::database_pg::IssuedInvoice,
+) -> Result<String, String> {
+    println!("🧾 [CREDIT NOTE] Generating HTML for {} (Bezug {})", credit_note.invoice_number, original.invoice_number);
+
+    let b = &booking.booking;
+    let document = &credit_note.document;
+
+    let mut html = include_str!("../../credit_note_template.html").to_string();
+
+    let format_german_date = |date_str: &str| -> String {
+        NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+            .map(|date| date.format("%d.%m.%Y").to_string())
+            .unwrap_or_else(|_| date_str.to_string())
+    };
+
+    let (document_title, lines_title) = if credit_note.is_cancellation {
+        ("Stornorechnung", "Stornierte Leistungen")
+    } else {
+        ("Rechnungskorrektur", "Korrigierte Leistungen")
+    };
+    html = html.replace("{{DOCUMENT_TITLE}}", document_title);
+    html = html.replace("{{LINES_TITLE}}", lines_title);
+    html = html.replace("{{CREDIT_NOTE_NUMBER}}", &credit_note.invoice_number);
+
+    // Header - Company Info
+    html = html.replace("{{COMPANY_NAME}}", &company.company_name);
+    html = html.replace("{{STREET_ADDRESS}}", company.street_address.as_deref().unwrap_or(""));
+    html = html.replace("{{PLZ}}", company.plz.as_deref().unwrap_or(""));
+    html = html.replace("{{CITY}}", company.city.as_deref().unwrap_or(""));
+    html = html.replace("{{COUNTRY}}", company.country.as_deref().unwrap_or("Deutschland"));
+    html = html.replace("{{PHONE}}", company.phone.as_deref().unwrap_or(""));
+    html = html.replace("{{EMAIL}}", company.email.as_deref().unwrap_or(""));
+    html = html.replace("{{TAX_ID}}", company.tax_id.as_deref().unwrap_or(""));
+    html = html.replace("{{LOGO_HTML}}", &company_logo_html(company));
+
+    // Recipients
+    html = html.replace("{{GUEST_NAME}}", &format!("{} {}", guest.vorname, guest.nachname));
+    html = html.replace("{{GUEST_ADDRESS}}", &format!(
+        "{}<br>{} {}",
+        guest.strasse.as_deref().unwrap_or(""),
+        guest.plz.as_deref().unwrap_or(""),
+        guest.ort.as_deref().unwrap_or("")
+    ));
+    html = html.replace("{{INVOICE_RECIPIENT_CARD}}", &recipient_card_html(payment_recipient));
+
+    // Bezug auf die Originalrechnung (Pflichtangabe § 14 UStG bei Rechnungskorrekturen)
+    html = html.replace("{{ORIGINAL_INVOICE_NUMBER}}", &original.invoice_number);
+    html = html.replace("{{ORIGINAL_INVOICE_DATE}}", &format_german_date(&original.issue_date));
+    html = html.replace("{{ROOM_NAME}}", &format!("Zimmer {} - {}", room.name, room.gebaeude_typ));
+    html = html.replace("{{STAY_PERIOD}}", &format!(
+        "{} - {}",
+        format_german_date(&b.checkin_date),
+        format_german_date(&b.checkout_date)
+    ));
+
+    // Meta
+    html = html.replace("{{CREDIT_NOTE_DATE}}", &document.issue_date.format("%d.%m.%Y").to_string());
+    html = html.replace("{{BOOKING_NUMBER}}", &b.reservierungsnummer);
+    html = html.replace("{{REASON}}", credit_note.reason.as_deref().unwrap_or("-"));
+
+    // Positions, discounts and totals (negative amounts)
+    html = html.replace("{{SERVICE_ROWS}}", &invoice_line_rows(&document.lines));
+
+    let discount_rows: Vec<String> = document.discounts.iter().map(|d| {
+        format!(
+            r#"<div class="total-row" style="color: var(--success); font-size: 13px;">
+                <span class="total-label">{}</span>
+                <span>{}</span>
+            </div>"#,
+            d.name,
+            (-d.amount).format_eur()
+        )
+    }).collect();
+    html = html.replace("{{DISCOUNT_ROWS}}", &discount_rows.join("\n"));
+    html = html.replace("{{GRAND_TOTAL}}", &document.total.format_eur());
+    html = html.replace("{{TAX_ROWS}}", &tax_table_html(&document.tax_lines));
+
+    let refund_note = if credit_note.is_cancellation {
+        format!(
+            "Rechnung {} wird hiermit vollständig storniert. Bereits gezahlte Beträge werden erstattet.",
+            original.invoice_number
+        )
+    } else {
+        format!(
+            "Der Betrag von {} wird mit offenen Forderungen verrechnet bzw. erstattet. \
+             Rechnung {} ist nur noch in Höhe des verbleibenden Betrags zu begleichen.",
+            (-document.total).format_eur(),
+            original.invoice_number
+        )
+    };
+    html = html.replace("{{REFUND_NOTE}}", &refund_note);
+
+    println!("✅ Credit note HTML generated");
+    Ok(html)
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct BackupSettings {
@@ -5475,10 +7586,11 @@ struct CreditTransaction {
     id: i64,
     guest_id: i64,
     booking_id: Option<i64>,
-    amount: f64,
+    amount: Money,
     transaction_type: String,
     description: String,
     created_at: String,
+    expires_at: Option<String>,
 }
 
 #[tauri::command]
@@ -5501,6 +7613,7 @@ async fn get_guest_credit_transactions(guest_id: i64, pool: State<'_, DbPool>) -
             transaction_type: t.transaction_type,
             description: t.description.unwrap_or_default(),
             created_at: t.created_at,
+            expires_at: t.expires_at,
         })
         .collect();
 
@@ -5868,45 +7981,26 @@ async fn mark_invoice_sent_command(
     Ok(())
 }
 
+/// Create the price list of a new year from an existing one (seasons, dates and room prices).
+/// Replaces the former hard-coded 2025 migration: yearly price lists are data now.
 #[tauri::command]
-async fn migrate_to_price_list_2025_command(
+async fn copy_price_list_to_year_command(
     pool: State<'_, DbPool>,
+    from_year: i32,
+    to_year: i32,
+    price_increase_percent: Option<f64>,
 ) -> Result<String, String> {
-    println!("🔄 migrate_to_price_list_2025_command called");
-
-    let client = pool.get().await
-        .map_err(|e| format!("Datenbankfehler: {}", e))?;
+    println!("🔄 copy_price_list_to_year_command called: {} → {}", from_year, to_year);
 
-    // Update pricing settings for 2025
-    // Example: Set new season dates for 2025
-    let updated_rows = client
-        .execute(
-            "UPDATE pricing_settings
-             SET hauptsaison_start = '2025-06-01',
-                 hauptsaison_ende = '2025-09-30',
-                 hauptsaison_aktiv = TRUE,
-                 updated_at = CURRENT_TIMESTAMP
-             WHERE id = 1",
-            &[],
-        )
+    let copied = SeasonRepository::copy_year(&pool, from_year, to_year, price_increase_percent.unwrap_or(0.0))
         .await
-        .map_err(|e| format!("Fehler beim Aktualisieren der Preisliste: {}", e))?;
-
-    if updated_rows == 0 {
-        // Create default pricing settings if not exists
-        client
-            .execute(
-                "INSERT INTO pricing_settings (id, hauptsaison_start, hauptsaison_ende, hauptsaison_aktiv, mitglieder_rabatt_aktiv, mitglieder_rabatt_prozent)
-                 VALUES (1, '2025-06-01', '2025-09-30', TRUE, TRUE, 10.0)
-                 ON CONFLICT (id) DO NOTHING",
-                &[],
-            )
-            .await
-            .map_err(|e| format!("Fehler beim Erstellen der Preisliste: {}", e))?;
-    }
+        .map_err(|e| format!("Fehler beim Kopieren der Preisliste: {}", e))?;
 
-    println!("✅ Price list migrated to 2025");
-    Ok("Preisliste erfolgreich auf 2025 migriert".to_string())
+    println!("✅ Copied {} seasons from {} to {}", copied, from_year, to_year);
+    Ok(format!(
+        "{} Saisons von {} nach {} übernommen. Bitte die Daten beweglicher Saisons (z.B. Ostern, Pfingsten) prüfen.",
+        copied, from_year, to_year
+    ))
 }
 
 #[tauri::command]
@@ -5939,14 +8033,19 @@ async fn fix_booking_status_values_command(pool: State<'_, DbPool>) -> Result<St
         .await
         .map_err(|e| format!("Fehler beim Löschen der Constraint: {}", e))?;
 
-    // Add new constraint with correct values
+    // Add new constraint with the value set of the state machine (same as migration 025)
+    let allowed = BookingStatus::ALL
+        .iter()
+        .map(|status| format!("'{}'", status.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ");
     client
-        .execute(
+        .batch_execute(&format!(
             "ALTER TABLE bookings
              ADD CONSTRAINT bookings_status_check
-             CHECK (status IN ('reserviert', 'bestaetigt', 'eingecheckt', 'ausgecheckt', 'storniert'))",
-            &[],
-        )
+             CHECK (status IN ({}))",
+            allowed
+        ))
         .await
         .map_err(|e| format!("Fehler beim Erstellen der Constraint: {}", e))?;
 
@@ -6271,9 +8370,24 @@ fn replace_template_placeholders(template: &str, booking: &database_pg::Booking,
         .replace("{{checkin}}", &booking.checkin_date)
         .replace("{{checkout}}", &booking.checkout_date)
         .replace("{{naechte}}", &booking.anzahl_naechte.unwrap_or(1).to_string())
-        .replace("{{gesamtpreis}}", &format!("{:.2} €", booking.gesamtpreis).replace(".", ","))
+        .replace("{{gesamtpreis}}", &booking.gesamtpreis.format_eur())
         .replace("{{buchungs_id}}", &booking.id.to_string())
-        .replace("{{status}}", &booking.status)
+        .replace("{{status}}", booking.status.as_str())
+}
+
+fn replace_dunning_placeholders(template: &str, notice: &database_pg::DunningEntry) -> String {
+    let zahlungsfrist = notice
+        .zahlungsfrist
+        .as_deref()
+        .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or_default();
+
+    template
+        .replace("{{mahnstufe}}", notice.stage_name.as_deref().unwrap_or(""))
+        .replace("{{offener_betrag}}", &notice.outstanding.format_eur())
+        .replace("{{mahngebuehr}}", &notice.gebuehr.format_eur())
+        .replace("{{zahlungsfrist}}", &zahlungsfrist)
 }
 
 #[tauri::command]
@@ -6810,6 +8924,50 @@ async fn trigger_email_check(pool: State<'_, DbPool>) -> Result<String, String>
     Ok(message)
 }
 
+/// Send a queued waitlist offer (scheduled email without booking)
+async fn process_waitlist_offer_email(pool: &DbPool, scheduled_email_id: i32) -> Result<(), String> {
+    let entry = WaitlistRepository::get_by_offer_email(pool, scheduled_email_id)
+        .await
+        .map_err(|e| format!("Wartelisten-Eintrag nicht gefunden: {}", e))?;
+
+    let guest = GuestRepository::get_by_id(pool, entry.guest_id)
+        .await
+        .map_err(|e| format!("Gast nicht gefunden: {}", e))?;
+    if guest.email.is_empty() {
+        return Err("Gast hat keine Email-Adresse".to_string());
+    }
+
+    let room_id = entry.offered_room_id.ok_or("Angebotenes Zimmer nicht mehr vorhanden")?;
+    let room = RoomRepository::get_by_id(pool, room_id)
+        .await
+        .map_err(|e| format!("Zimmer nicht gefunden: {}", e))?;
+
+    let replace = |template: &str| {
+        template
+            .replace("{{gast_name}}", &format!("{} {}", guest.vorname, guest.nachname))
+            .replace("{{gast_vorname}}", &guest.vorname)
+            .replace("{{gast_nachname}}", &guest.nachname)
+            .replace("{{zimmer}}", &room.name)
+            .replace("{{checkin}}", &entry.checkin_date)
+            .replace("{{checkout}}", &entry.checkout_date)
+    };
+
+    let template = EmailTemplateRepository::get_by_name(pool, WAITLIST_OFFER_TEMPLATE.to_string())
+        .await
+        .map_err(|e| format!("Email-Template '{}' nicht gefunden: {}", WAITLIST_OFFER_TEMPLATE, e))?;
+
+    send_email_helper(
+        pool,
+        &guest.email,
+        &format!("{} {}", guest.vorname, guest.nachname),
+        &replace(&template.subject),
+        &replace(&template.body),
+    ).await?;
+
+    println!("✅ Sent waitlist offer to {} (entry {})", guest.email, entry.id);
+    Ok(())
+}
+
 /// Helper function to process a scheduled booking email
 async fn process_scheduled_booking_email(
     pool: &DbPool,
@@ -6842,8 +9000,17 @@ async fn process_scheduled_booking_email(
         .map_err(|e| format!("Email-Template '{}' nicht gefunden: {}", template_name, e))?;
 
     // Replace placeholders
-    let subject = replace_template_placeholders(&template.subject, &booking, &guest, &room.name);
-    let body = replace_template_placeholders(&template.body, &booking, &guest, &room.name);
+    let mut subject = replace_template_placeholders(&template.subject, &booking, &guest, &room.name);
+    let mut body = replace_template_placeholders(&template.body, &booking, &guest, &room.name);
+
+    // Dunning notices: amounts and deadline of the stage
+    if let Some(notice) = DunningRepository::get_notice(pool, booking_id, template_name)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        subject = replace_dunning_placeholders(&subject, &notice);
+        body = replace_dunning_placeholders(&body, &notice);
+    }
 
     // Send email
     send_email_helper(
@@ -6965,7 +9132,7 @@ async fn upload_logo_command(source_path: String) -> Result<LogoUploadResult, St
 }
 
 #[tauri::command]
-async fn use_guest_credit_for_booking(guest_id: i64, booking_id: i64, amount: f64, pool: State<'_, DbPool>) -> Result<(), String> {
+async fn use_guest_credit_for_booking(guest_id: i64, booking_id: i64, amount: Money, pool: State<'_, DbPool>) -> Result<(), String> {
     use crate::database_pg::repositories::GuestCreditRepository;
 
     println!("💸 use_guest_credit_for_booking: guest_id={}, booking_id={}, amount={:.2}€", guest_id, booking_id, amount);
@@ -6993,14 +9160,14 @@ async fn use_guest_credit_for_booking(guest_id: i64, booking_id: i64, amount: f6
 }
 
 #[tauri::command]
-async fn get_booking_credit_usage(pool: State<'_, DbPool>, booking_id: i32) -> Result<f64, String> {
+async fn get_booking_credit_usage(pool: State<'_, DbPool>, booking_id: i32) -> Result<Money, String> {
     println!("💰 get_booking_credit_usage called for booking_id: {}", booking_id);
 
     let booking = BookingRepository::get_by_id(&pool, booking_id)
         .await
         .map_err(|e| format!("Fehler beim Laden der Buchung: {}", e))?;
 
-    let credit_used = booking.credit_used.unwrap_or(0.0);
+    let credit_used = booking.credit_used.unwrap_or(Money::ZERO);
     println!("💰 Credit used for booking {}: {:.2} €", booking_id, credit_used);
 
     Ok(credit_used)
@@ -0,0 +1,473 @@
+// Money - Fixed-point currency type (Euro, stored as whole cents)
+//
+// All prices, totals and credit amounts use `Money` instead of f64 so that
+// invoice lines always add up to the invoice total.
+//
+// ROUNDING RULES (kaufmännische Rundung):
+// 1. Every amount is an integer number of cents. There are no fractions of a cent.
+// 2. Conversions from f64 (legacy DOUBLE PRECISION / REAL columns, frontend numbers)
+//    round to the nearest cent, ties away from zero (1.005 → 1.01, -0.125 → -0.13).
+//    Values are first rounded to 6 decimals so binary float artefacts
+//    (1.005 is stored as 1.00499999…) do not flip the result.
+// 3. Percentage amounts (percent services, discounts, member discount) are rounded
+//    per line with rule 2. Totals are the sum of the already rounded lines, never
+//    a rounded sum of unrounded values.
+// 4. Included VAT (`included_tax`) is rounded per tax rate from the gross sum.
+// 5. Division (e.g. average price per night) rounds with rule 2 and is for display only.
+// 6. Proportional splits (`allocate`, e.g. a discount over tax rates) round every share
+//    except the last one; the last share takes the remainder so the shares add up exactly.
+//
+// DATABASE: Money reads and writes NUMERIC, DOUBLE PRECISION and REAL columns directly,
+// so no `::numeric::text` round trip is needed.
+
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use tokio_postgres::types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    pub const fn cents(self) -> i64 {
+        self.0
+    }
+
+    /// Convert a Euro amount given as f64 (rounding rule 2)
+    pub fn from_euros(euros: f64) -> Self {
+        if !euros.is_finite() {
+            return Money::ZERO;
+        }
+        let micro = (euros * 1_000_000.0).round();
+        Money((micro / 10_000.0).round() as i64)
+    }
+
+    /// Euro value as f64 - only for display, JSON and legacy float columns
+    pub fn to_euros(self) -> f64 {
+        self.0 as f64 / 100.0
+    }
+
+    /// `percent` % of this amount, rounded to cents (rounding rule 3)
+    pub fn percent(self, percent: f64) -> Self {
+        Money::from_euros(self.to_euros() * percent / 100.0)
+    }
+
+    /// VAT contained in this gross amount for the given rate in percent (rounding rule 4)
+    pub fn included_tax(self, rate_percent: f64) -> Self {
+        Money::from_euros(self.to_euros() * rate_percent / (100.0 + rate_percent))
+    }
+
+    /// Divide into `parts` equal shares, rounded to cents (rounding rule 5)
+    pub fn divide(self, parts: i64) -> Self {
+        if parts == 0 {
+            return Money::ZERO;
+        }
+        Money(div_round_half_away(self.0 as i128, parts as i128) as i64)
+    }
+
+    /// Split proportionally to `weights` (rounding rule 6). Zero weights give an equal split.
+    pub fn allocate(self, weights: &[Money]) -> Vec<Money> {
+        if weights.is_empty() {
+            return Vec::new();
+        }
+        let total: i128 = weights.iter().map(|w| w.0 as i128).sum();
+        let mut remaining = self;
+        let mut shares = Vec::with_capacity(weights.len());
+        for (i, weight) in weights.iter().enumerate() {
+            let share = if i == weights.len() - 1 {
+                remaining
+            } else if total == 0 {
+                self.divide(weights.len() as i64)
+            } else {
+                Money(div_round_half_away(self.0 as i128 * weight.0 as i128, total) as i64)
+            };
+            remaining -= share;
+            shares.push(share);
+        }
+        shares
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn abs(self) -> Self {
+        Money(self.0.abs())
+    }
+
+    pub fn max(self, other: Money) -> Self {
+        Money(self.0.max(other.0))
+    }
+
+    pub fn min(self, other: Money) -> Self {
+        Money(self.0.min(other.0))
+    }
+
+    /// German currency format for invoices and emails: "1234,50 €"
+    pub fn format_eur(self) -> String {
+        format!("{} €", self).replace('.', ",")
+    }
+}
+
+/// Integer division rounding half away from zero
+fn div_round_half_away(value: i128, divisor: i128) -> i128 {
+    let quotient = value / divisor;
+    let remainder = value % divisor;
+    if remainder.abs() * 2 >= divisor.abs() {
+        if (value < 0) != (divisor < 0) {
+            quotient - 1
+        } else {
+            quotient + 1
+        }
+    } else {
+        quotient
+    }
+}
+
+impl fmt::Display for Money {
+    /// Plain decimal with two places and a dot: "-12.05"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        write!(f, "{}{}.{:02}", sign, abs / 100, abs % 100)
+    }
+}
+
+impl FromStr for Money {
+    type Err = String;
+
+    /// Parse a decimal string exactly ("12.345" → 12.35, "7,5" → 7.50)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim().replace(',', ".");
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(&trimmed)),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+
+        let is_digits = |p: &str| p.chars().all(|c| c.is_ascii_digit());
+        if (int_part.is_empty() && frac_part.is_empty()) || !is_digits(int_part) || !is_digits(frac_part) {
+            return Err(format!("Ungültiger Betrag: '{}'", s));
+        }
+
+        let euros: i64 = if int_part.is_empty() {
+            0
+        } else {
+            int_part.parse().map_err(|_| format!("Betrag zu groß: '{}'", s))?
+        };
+        let frac_digit = |i: usize| frac_part.as_bytes().get(i).map(|b| (b - b'0') as i64).unwrap_or(0);
+        let mut cents = euros
+            .checked_mul(100)
+            .ok_or_else(|| format!("Betrag zu groß: '{}'", s))?
+            + frac_digit(0) * 10
+            + frac_digit(1);
+        if frac_digit(2) >= 5 {
+            cents += 1;
+        }
+
+        Ok(Money(if negative { -cents } else { cents }))
+    }
+}
+
+// ============================================================================
+// ARITHMETIC
+// ============================================================================
+
+impl Add for Money {
+    type Output = Money;
+    fn add(self, rhs: Money) -> Money {
+        Money(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+    fn sub(self, rhs: Money) -> Money {
+        Money(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, rhs: Money) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, rhs: Money) {
+        self.0 -= rhs.0;
+    }
+}
+
+/// Quantity times unit price (e.g. nights × price per night)
+impl Mul<i64> for Money {
+    type Output = Money;
+    fn mul(self, rhs: i64) -> Money {
+        Money(self.0 * rhs)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+// ============================================================================
+// SERDE (JSON number for the frontend, accepts numbers and strings)
+// ============================================================================
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_euros())
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(f64),
+            Text(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Number(n) => Ok(Money::from_euros(n)),
+            Repr::Text(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+// ============================================================================
+// POSTGRESQL (NUMERIC, DOUBLE PRECISION, REAL)
+// ============================================================================
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+
+/// Decode the binary NUMERIC format (base-10000 digits) into cents
+fn numeric_to_cents(raw: &[u8]) -> Result<i64, Box<dyn Error + Sync + Send>> {
+    let read_u16 = |pos: usize| -> Result<u16, Box<dyn Error + Sync + Send>> {
+        raw.get(pos..pos + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .ok_or_else(|| "invalid NUMERIC value".into())
+    };
+
+    let ndigits = read_u16(0)? as usize;
+    let weight = read_u16(2)? as i16 as i32;
+    let sign = read_u16(4)?;
+    if sign == NUMERIC_NAN {
+        return Err("NUMERIC NaN is not a valid amount".into());
+    }
+
+    // Accumulate in units of 1/10000 €, digits below that only influence rounding
+    let mut units: i128 = 0;
+    for i in 0..ndigits {
+        let digit = read_u16(8 + i * 2)? as i128;
+        let exponent = weight - i as i32;
+        if exponent >= -1 {
+            units += digit * 10_000i128.pow((exponent + 1) as u32);
+        }
+    }
+
+    let cents = div_round_half_away(units, 100);
+    let cents = i64::try_from(cents).map_err(|_| "NUMERIC value out of range")?;
+    Ok(if sign == NUMERIC_NEG { -cents } else { cents })
+}
+
+/// Encode cents in the binary NUMERIC format with scale 2
+fn cents_to_numeric(cents: i64, out: &mut BytesMut) {
+    let abs = cents.unsigned_abs();
+    let mut int_part = abs / 100;
+    let frac_group = ((abs % 100) * 100) as i16;
+
+    let mut int_groups: Vec<i16> = Vec::new();
+    while int_part > 0 {
+        int_groups.push((int_part % 10_000) as i16);
+        int_part /= 10_000;
+    }
+    int_groups.reverse();
+
+    let weight = int_groups.len() as i16 - 1;
+    let mut digits = int_groups;
+    digits.push(frac_group);
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    // Leading zero groups only occur when the integer part is 0 (weight -1)
+    let mut weight = weight;
+    while digits.first() == Some(&0) {
+        digits.remove(0);
+        weight -= 1;
+    }
+    if digits.is_empty() {
+        weight = 0;
+    }
+
+    out.put_i16(digits.len() as i16);
+    out.put_i16(weight);
+    out.put_u16(if cents < 0 { NUMERIC_NEG } else { NUMERIC_POS });
+    out.put_i16(2);
+    for digit in digits {
+        out.put_i16(digit);
+    }
+}
+
+impl<'a> FromSql<'a> for Money {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        match *ty {
+            Type::NUMERIC => Ok(Money(numeric_to_cents(raw)?)),
+            Type::FLOAT4 => Ok(Money::from_euros(f32::from_sql(ty, raw)? as f64)),
+            _ => Ok(Money::from_euros(f64::from_sql(ty, raw)?)),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::NUMERIC | Type::FLOAT8 | Type::FLOAT4)
+    }
+}
+
+impl ToSql for Money {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match *ty {
+            Type::NUMERIC => {
+                cents_to_numeric(self.0, out);
+                Ok(IsNull::No)
+            }
+            Type::FLOAT4 => (self.to_euros() as f32).to_sql(ty, out),
+            _ => self.to_euros().to_sql(ty, out),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::NUMERIC | Type::FLOAT8 | Type::FLOAT4)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_euros_rounds_half_away_from_zero() {
+        assert_eq!(Money::from_euros(1.005).cents(), 101);
+        assert_eq!(Money::from_euros(2.675).cents(), 268);
+        assert_eq!(Money::from_euros(-0.125).cents(), -13);
+        assert_eq!(Money::from_euros(0.1 + 0.2).cents(), 30);
+        assert_eq!(Money::from_euros(f64::NAN), Money::ZERO);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!("12.345".parse::<Money>().unwrap().cents(), 1235);
+        assert_eq!("7,5".parse::<Money>().unwrap().cents(), 750);
+        assert_eq!("-0.05".parse::<Money>().unwrap().cents(), -5);
+        assert_eq!(".99".parse::<Money>().unwrap().cents(), 99);
+        assert!("abc".parse::<Money>().is_err());
+        assert!("".parse::<Money>().is_err());
+
+        assert_eq!(Money::from_cents(123450).to_string(), "1234.50");
+        assert_eq!(Money::from_cents(-5).to_string(), "-0.05");
+        assert_eq!(Money::from_cents(123450).format_eur(), "1234,50 €");
+    }
+
+    #[test]
+    fn test_percent_and_tax() {
+        assert_eq!(Money::from_cents(33333).percent(15.0).cents(), 5000);
+        assert_eq!(Money::from_cents(10700).included_tax(7.0).cents(), 700);
+        assert_eq!(Money::from_cents(11900).included_tax(19.0).cents(), 1900);
+    }
+
+    #[test]
+    fn test_divide() {
+        assert_eq!(Money::from_cents(10000).divide(3).cents(), 3333);
+        assert_eq!(Money::from_cents(20000).divide(3).cents(), 6667);
+        assert_eq!(Money::from_cents(-20000).divide(3).cents(), -6667);
+        assert_eq!(Money::from_cents(100).divide(0), Money::ZERO);
+    }
+
+    #[test]
+    fn test_allocate_adds_up() {
+        let weights = [Money::from_cents(10000), Money::from_cents(2000), Money::from_cents(1000)];
+        let shares = Money::from_cents(1000).allocate(&weights);
+        assert_eq!(shares.iter().map(|s| s.cents()).collect::<Vec<_>>(), vec![769, 154, 77]);
+        assert_eq!(shares.iter().copied().sum::<Money>().cents(), 1000);
+    }
+
+    #[test]
+    fn test_sum_of_rounded_lines() {
+        let lines = [Money::from_euros(0.1), Money::from_euros(0.2), Money::from_euros(0.3)];
+        let total: Money = lines.iter().sum();
+        assert_eq!(total.cents(), 60);
+    }
+
+    #[test]
+    fn test_numeric_roundtrip() {
+        for cents in [0, 5, 99, 100, 1234, 1_000_000, 123_456_789, -1, -250_075] {
+            let mut buf = BytesMut::new();
+            cents_to_numeric(cents, &mut buf);
+            assert_eq!(numeric_to_cents(&buf).unwrap(), cents, "roundtrip failed for {cents}");
+        }
+    }
+
+    #[test]
+    fn test_numeric_decodes_postgres_layout() {
+        // 12.345 as sent by PostgreSQL: ndigits=2, weight=0, sign=+, dscale=3, digits [12, 3450]
+        let raw = [0, 2, 0, 0, 0, 0, 0, 3, 0, 12, 0x0D, 0x7A];
+        assert_eq!(numeric_to_cents(&raw).unwrap(), 1235);
+
+        // 10000.00: ndigits=1, weight=1, digits [1]
+        let raw = [0, 1, 0, 1, 0, 0, 0, 2, 0, 1];
+        assert_eq!(numeric_to_cents(&raw).unwrap(), 1_000_000);
+
+        // -0.05: ndigits=1, weight=-1, sign=-, digits [500]
+        let raw = [0, 1, 0xFF, 0xFF, 0x40, 0, 0, 2, 0x01, 0xF4];
+        assert_eq!(numeric_to_cents(&raw).unwrap(), -5);
+    }
+
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&Money::from_cents(1999)).unwrap();
+        assert_eq!(json, "19.99");
+
+        let from_number: Money = serde_json::from_str("19.985").unwrap();
+        assert_eq!(from_number.cents(), 1999);
+        let from_text: Money = serde_json::from_str("\"19.99\"").unwrap();
+        assert_eq!(from_text.cents(), 1999);
+    }
+}
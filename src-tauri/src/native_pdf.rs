@@ -0,0 +1,589 @@
+// Native PDF rendering - small drawing layer on top of pdf-writer
+// Used for invoices, credit notes and the cleaning plan, so PDFs no longer need
+// a Chrome installation (headless_chrome is only an optional fallback, feature "chrome-pdf").
+//
+// Coordinates are in points (1/72 inch) with the origin at the TOP LEFT of the page,
+// text positions are baselines. Fonts are the PDF standard fonts Helvetica and
+// Helvetica-Bold with WinAnsiEncoding (umlauts, ß and € included) - no font files needed.
+// Characters outside WinAnsi (emojis) are dropped.
+
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref, Str};
+use qrcode::QrCode;
+
+/// A4 in points
+pub const A4_WIDTH: f32 = 595.28;
+pub const A4_HEIGHT: f32 = 841.89;
+
+const FONT_REGULAR: Name<'static> = Name(b"F1");
+const FONT_BOLD: Name<'static> = Name(b"F2");
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+/// RGB color (0-255 per channel)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(15, 23, 42);
+    pub const WHITE: Color = Color(255, 255, 255);
+
+    fn components(self) -> (f32, f32, f32) {
+        (self.0 as f32 / 255.0, self.1 as f32 / 255.0, self.2 as f32 / 255.0)
+    }
+}
+
+/// Decoded raster image (logo), stored Flate-compressed as RGB plus optional alpha mask
+pub struct Image {
+    width: u32,
+    height: u32,
+    rgb: Vec<u8>,
+    alpha: Option<Vec<u8>>,
+}
+
+impl Image {
+    /// Decode PNG or JPEG bytes
+    pub fn decode(bytes: &[u8]) -> Result<Image, String> {
+        let decoded = image::load_from_memory(bytes)
+            .map_err(|e| format!("Bild konnte nicht gelesen werden: {}", e))?;
+        let rgba = decoded.to_rgba8();
+        let (width, height) = rgba.dimensions();
+
+        let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+        let mut alpha = Vec::with_capacity((width * height) as usize);
+        for pixel in rgba.pixels() {
+            rgb.extend_from_slice(&pixel.0[..3]);
+            alpha.push(pixel.0[3]);
+        }
+        let transparent = alpha.iter().any(|&a| a < 255);
+
+        Ok(Image {
+            width,
+            height,
+            rgb: compress(&rgb),
+            alpha: if transparent { Some(compress(&alpha)) } else { None },
+        })
+    }
+
+    /// Size that fits into `max_width` x `max_height` keeping the aspect ratio
+    pub fn fit(&self, max_width: f32, max_height: f32) -> (f32, f32) {
+        let scale = (max_width / self.width as f32).min(max_height / self.height as f32);
+        (self.width as f32 * scale, self.height as f32 * scale)
+    }
+}
+
+/// Handle of an image added to a document
+#[derive(Debug, Clone, Copy)]
+pub struct ImageId(usize);
+
+/// Multi-page document, every page has the same size
+pub struct Document {
+    width: f32,
+    height: f32,
+    pages: Vec<Content>,
+    current: usize,
+    images: Vec<Image>,
+}
+
+impl Document {
+    pub fn new(width: f32, height: f32) -> Self {
+        Document {
+            width,
+            height,
+            pages: vec![Content::new()],
+            current: 0,
+            images: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Append a page and draw on it
+    pub fn add_page(&mut self) {
+        self.pages.push(Content::new());
+        self.current = self.pages.len() - 1;
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Draw on an earlier page again (e.g. page numbers once the page count is known)
+    pub fn select_page(&mut self, index: usize) {
+        self.current = index.min(self.pages.len() - 1);
+    }
+
+    fn content(&mut self) -> &mut Content {
+        &mut self.pages[self.current]
+    }
+
+    /// Text with its baseline at `y`
+    pub fn text(&mut self, x: f32, y: f32, size: f32, font: Font, color: Color, text: &str) {
+        let encoded = encode_win_ansi(text);
+        if encoded.is_empty() {
+            return;
+        }
+        let (r, g, b) = color.components();
+        let y = self.height - y;
+        let content = self.content();
+        content.set_fill_rgb(r, g, b);
+        content.begin_text();
+        content.set_font(font_name(font), size);
+        content.set_text_matrix([1.0, 0.0, 0.0, 1.0, x, y]);
+        content.show(Str(&encoded));
+        content.end_text();
+    }
+
+    /// Text ending at `right`
+    pub fn text_right(&mut self, right: f32, y: f32, size: f32, font: Font, color: Color, text: &str) {
+        let x = right - text_width(text, size, font);
+        self.text(x, y, size, font, color, text);
+    }
+
+    /// Text centered on `center`
+    pub fn text_centered(&mut self, center: f32, y: f32, size: f32, font: Font, color: Color, text: &str) {
+        let x = center - text_width(text, size, font) / 2.0;
+        self.text(x, y, size, font, color, text);
+    }
+
+    /// Filled rectangle, (x, y) is the top left corner
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let (r, g, b) = color.components();
+        let y = self.height - y - height;
+        let content = self.content();
+        content.set_fill_rgb(r, g, b);
+        content.rect(x, y, width, height);
+        content.fill_nonzero();
+    }
+
+    /// Rectangle outline, (x, y) is the top left corner
+    #[allow(clippy::too_many_arguments)]
+    pub fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, line_width: f32, color: Color) {
+        let (r, g, b) = color.components();
+        let y = self.height - y - height;
+        let content = self.content();
+        content.set_stroke_rgb(r, g, b);
+        content.set_line_width(line_width);
+        content.rect(x, y, width, height);
+        content.stroke();
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, line_width: f32, color: Color) {
+        let (r, g, b) = color.components();
+        let (y1, y2) = (self.height - y1, self.height - y2);
+        let content = self.content();
+        content.set_stroke_rgb(r, g, b);
+        content.set_line_width(line_width);
+        content.move_to(x1, y1);
+        content.line_to(x2, y2);
+        content.stroke();
+    }
+
+    pub fn add_image(&mut self, image: Image) -> ImageId {
+        self.images.push(image);
+        ImageId(self.images.len() - 1)
+    }
+
+    /// Draw an image into the box with top left corner (x, y)
+    pub fn draw_image(&mut self, image: ImageId, x: f32, y: f32, width: f32, height: f32) {
+        let name = image_name(image.0);
+        let y = self.height - y - height;
+        let content = self.content();
+        content.save_state();
+        content.transform([width, 0.0, 0.0, height, x, y]);
+        content.x_object(Name(name.as_bytes()));
+        content.restore_state();
+    }
+
+    /// QR code as vector graphic (sharp in print), `size` includes no quiet zone
+    pub fn qr_code(&mut self, data: &str, x: f32, y: f32, size: f32) -> Result<(), String> {
+        let code = QrCode::new(data.as_bytes())
+            .map_err(|e| format!("Fehler beim Erstellen des QR-Codes: {}", e))?;
+        let modules = code.width();
+        let colors = code.to_colors();
+        let module = size / modules as f32;
+
+        for row in 0..modules {
+            // Merge dark modules of a row into runs - fewer drawing operations
+            let mut col = 0;
+            while col < modules {
+                if colors[row * modules + col] != qrcode::Color::Dark {
+                    col += 1;
+                    continue;
+                }
+                let start = col;
+                while col < modules && colors[row * modules + col] == qrcode::Color::Dark {
+                    col += 1;
+                }
+                self.fill_rect(
+                    x + start as f32 * module,
+                    y + row as f32 * module,
+                    (col - start) as f32 * module,
+                    module,
+                    Color(0, 0, 0),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serialize the document
+    pub fn finish(self) -> Vec<u8> {
+        let mut next_id = 0;
+        let mut alloc = || {
+            next_id += 1;
+            Ref::new(next_id)
+        };
+
+        let catalog_id = alloc();
+        let page_tree_id = alloc();
+        let regular_id = alloc();
+        let bold_id = alloc();
+        let image_ids: Vec<(Ref, Option<Ref>)> = self
+            .images
+            .iter()
+            .map(|image| (alloc(), image.alpha.as_ref().map(|_| alloc())))
+            .collect();
+        let page_ids: Vec<(Ref, Ref)> = self.pages.iter().map(|_| (alloc(), alloc())).collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().map(|(page_id, _)| *page_id))
+            .count(page_ids.len() as i32);
+
+        pdf.type1_font(regular_id)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+        pdf.type1_font(bold_id)
+            .base_font(Name(b"Helvetica-Bold"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+        for (image, (image_id, mask_id)) in self.images.iter().zip(&image_ids) {
+            let mut xobject = pdf.image_xobject(*image_id, &image.rgb);
+            xobject.filter(Filter::FlateDecode);
+            xobject.width(image.width as i32);
+            xobject.height(image.height as i32);
+            xobject.color_space().device_rgb();
+            xobject.bits_per_component(8);
+            if let Some(mask_id) = mask_id {
+                xobject.s_mask(*mask_id);
+            }
+            xobject.finish();
+
+            if let (Some(alpha), Some(mask_id)) = (&image.alpha, mask_id) {
+                let mut mask = pdf.image_xobject(*mask_id, alpha);
+                mask.filter(Filter::FlateDecode);
+                mask.width(image.width as i32);
+                mask.height(image.height as i32);
+                mask.color_space().device_gray();
+                mask.bits_per_component(8);
+                mask.finish();
+            }
+        }
+
+        for (content, (page_id, content_id)) in self.pages.into_iter().zip(&page_ids) {
+            let mut page = pdf.page(*page_id);
+            page.media_box(Rect::new(0.0, 0.0, self.width, self.height));
+            page.parent(page_tree_id);
+            page.contents(*content_id);
+
+            let mut resources = page.resources();
+            resources.fonts().pair(FONT_REGULAR, regular_id).pair(FONT_BOLD, bold_id);
+            let mut x_objects = resources.x_objects();
+            for (index, (image_id, _)) in image_ids.iter().enumerate() {
+                let name = image_name(index);
+                x_objects.pair(Name(name.as_bytes()), *image_id);
+            }
+            x_objects.finish();
+            resources.finish();
+            page.finish();
+
+            let data = compress(&content.finish());
+            pdf.stream(*content_id, &data).filter(Filter::FlateDecode);
+        }
+
+        pdf.finish()
+    }
+}
+
+// ============================================================================
+// CHROME FALLBACK (feature "chrome-pdf")
+// ============================================================================
+
+/// Render natively; the HTML version is printed with headless Chrome instead if native
+/// rendering fails or PDF_RENDERER=chrome is set. Without feature "chrome-pdf" the
+/// HTML closure is never called.
+#[cfg_attr(not(feature = "chrome-pdf"), allow(unused_variables))]
+pub fn render_with_fallback(
+    native: impl FnOnce() -> Result<Vec<u8>, String>,
+    landscape: bool,
+    html: impl FnOnce() -> Result<String, String>,
+) -> Result<Vec<u8>, String> {
+    #[cfg(feature = "chrome-pdf")]
+    {
+        let prefer_chrome = std::env::var("PDF_RENDERER")
+            .map(|v| v.eq_ignore_ascii_case("chrome"))
+            .unwrap_or(false);
+        if prefer_chrome {
+            println!("🌐 PDF_RENDERER=chrome - using headless Chrome");
+            return render_with_chrome(&html()?, landscape);
+        }
+        match native() {
+            Ok(pdf_data) => Ok(pdf_data),
+            Err(e) => {
+                println!("⚠️ Native PDF rendering failed ({}), falling back to headless Chrome", e);
+                render_with_chrome(&html()?, landscape)
+            }
+        }
+    }
+
+    #[cfg(not(feature = "chrome-pdf"))]
+    native().map_err(|e| format!("PDF-Erstellung fehlgeschlagen: {}", e))
+}
+
+/// Print an HTML document to an A4 PDF with headless Chrome
+#[cfg(feature = "chrome-pdf")]
+fn render_with_chrome(html: &str, landscape: bool) -> Result<Vec<u8>, String> {
+    use headless_chrome::types::PrintToPdfOptions;
+    use headless_chrome::{Browser, LaunchOptions};
+
+    println!("🚀 Starting headless Chrome...");
+
+    let launch_options = LaunchOptions::default_builder()
+        .headless(true)
+        .sandbox(true)
+        .build()
+        .map_err(|e| format!("Chrome launch options error: {}", e))?;
+
+    let browser = Browser::new(launch_options).map_err(|e| format!("Chrome start error: {}", e))?;
+
+    let tab = browser.new_tab().map_err(|e| format!("Chrome tab error: {}", e))?;
+
+    // Load HTML content
+    let html_base64 = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, html);
+    let data_url = format!("data:text/html;base64,{}", html_base64);
+
+    tab.navigate_to(&data_url).map_err(|e| format!("Navigate error: {}", e))?;
+
+    tab.wait_until_navigated().map_err(|e| format!("Wait error: {}", e))?;
+
+    // Landscape = cleaning plan: full page, web fonts need a moment
+    let margin = if landscape {
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        0.0
+    } else {
+        0.4
+    };
+    let pdf_options = PrintToPdfOptions {
+        landscape: Some(landscape),
+        print_background: Some(true),
+        paper_width: Some(if landscape { 11.69 } else { 8.27 }), // A4
+        paper_height: Some(if landscape { 8.27 } else { 11.69 }),
+        margin_top: Some(margin),
+        margin_bottom: Some(margin),
+        margin_left: Some(margin),
+        margin_right: Some(margin),
+        prefer_css_page_size: Some(landscape),
+        ..Default::default()
+    };
+
+    tab.print_to_pdf(Some(pdf_options))
+        .map_err(|e| format!("PDF generation error: {}", e))
+}
+
+fn font_name(font: Font) -> Name<'static> {
+    match font {
+        Font::Regular => FONT_REGULAR,
+        Font::Bold => FONT_BOLD,
+    }
+}
+
+fn image_name(index: usize) -> String {
+    format!("Im{}", index + 1)
+}
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).expect("writing to Vec cannot fail");
+    encoder.finish().expect("writing to Vec cannot fail")
+}
+
+// ============================================================================
+// TEXT: WinAnsi encoding and Helvetica metrics
+// ============================================================================
+
+/// Glyph widths (1/1000 em) of ASCII 32..=126 from the Adobe AFM files
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, //
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, //
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, //
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, //
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, //
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, //
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, //
+    975, 722, 722, 722, 722, 667, 611, 778, 722, 278, 556, 722, 611, 833, 722, 778, //
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 333, 278, 333, 584, 556, //
+    333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556, 278, 889, 611, 611, //
+    611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// WinAnsi byte of a character (None = not representable)
+fn win_ansi(c: char) -> Option<u8> {
+    match c {
+        ' '..='~' => Some(c as u8),
+        '\u{a0}'..='\u{ff}' => Some(c as u32 as u8),
+        '€' => Some(0x80),
+        '‚' => Some(0x82),
+        '„' => Some(0x84),
+        '…' => Some(0x85),
+        '‘' => Some(0x91),
+        '’' => Some(0x92),
+        '“' => Some(0x93),
+        '”' => Some(0x94),
+        '•' => Some(0x95),
+        '–' => Some(0x96),
+        '—' => Some(0x97),
+        '™' => Some(0x99),
+        _ => None,
+    }
+}
+
+/// Encode text for the standard fonts. Letters outside WinAnsi become '?',
+/// other characters (emojis, variation selectors) are dropped.
+fn encode_win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .filter_map(|c| match win_ansi(c) {
+            Some(byte) => Some(byte),
+            None if c.is_alphabetic() => Some(b'?'),
+            None => None,
+        })
+        .collect()
+}
+
+/// Width of a WinAnsi byte in 1/1000 em
+fn glyph_width(byte: u8, font: Font) -> u16 {
+    let widths = match font {
+        Font::Regular => &HELVETICA_WIDTHS,
+        Font::Bold => &HELVETICA_BOLD_WIDTHS,
+    };
+    let bold = font == Font::Bold;
+    // Accented letters have the width of their base letter
+    let base = match byte {
+        32..=126 => return widths[(byte - 32) as usize],
+        0xC0..=0xC5 => b'A',
+        0xC7 => b'C',
+        0xC8..=0xCB => b'E',
+        0xCC..=0xCF => b'I',
+        0xD1 => b'N',
+        0xD2..=0xD6 | 0xD8 => b'O',
+        0xD9..=0xDC => b'U',
+        0xDD => b'Y',
+        0xE0..=0xE5 => b'a',
+        0xE7 => b'c',
+        0xE8..=0xEB => b'e',
+        0xF1 => b'n',
+        0xF2..=0xF6 | 0xF8 => b'o',
+        0xF9..=0xFC => b'u',
+        0xFD | 0xFF => b'y',
+        0xEC..=0xEF | 0xA0 => b' ',
+        0xD7 => b'+',
+        0x82 | 0x91 | 0x92 => return if bold { 278 } else { 222 },
+        0x84 | 0x93 | 0x94 => return if bold { 500 } else { 333 },
+        0x85 | 0x97 => return 1000,
+        0x95 => return 350,
+        0xB0 => return 400,
+        _ => return 556, // €, ß, §, – and the rest
+    };
+    widths[(base - 32) as usize]
+}
+
+/// Width of `text` in points
+pub fn text_width(text: &str, size: f32, font: Font) -> f32 {
+    let units: u32 = encode_win_ansi(text).into_iter().map(|b| glyph_width(b, font) as u32).sum();
+    units as f32 * size / 1000.0
+}
+
+/// Break `text` into lines of at most `max_width` (words longer than a line are split)
+pub fn wrap_text(text: &str, size: f32, font: Font, max_width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+            if text_width(&candidate, size, font) <= max_width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in word.chars() {
+                line.push(c);
+                if text_width(&line, size, font) > max_width && line.chars().count() > 1 {
+                    line.pop();
+                    lines.push(std::mem::replace(&mut line, c.to_string()));
+                }
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Cut `text` to `max_width`, marking the cut with "…"
+pub fn truncate(text: &str, size: f32, font: Font, max_width: f32) -> String {
+    if text_width(text, size, font) <= max_width {
+        return text.to_string();
+    }
+    let mut result: String = text.to_string();
+    while !result.is_empty() && text_width(&format!("{}…", result), size, font) > max_width {
+        result.pop();
+    }
+    if result.is_empty() {
+        String::new()
+    } else {
+        format!("{}…", result.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_metrics() {
+        assert_eq!(encode_win_ansi("Grüße 12,50 €"), b"Gr\xfc\xdfe 12,50 \x80".to_vec());
+        assert_eq!(encode_win_ansi("🧹 Łódź"), b" ?\xf3d?".to_vec());
+        // "Hallo" in Helvetica: 722 + 556 + 222 + 222 + 556 = 2278
+        assert!((text_width("Hallo", 10.0, Font::Regular) - 22.78).abs() < 0.001);
+        assert!(text_width("Hallo", 10.0, Font::Bold) > text_width("Hallo", 10.0, Font::Regular));
+
+        let lines = wrap_text("Übernachtung im Doppelzimmer mit Frühstück", 10.0, Font::Regular, 100.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| text_width(l, 10.0, Font::Regular) <= 100.0));
+        assert_eq!(lines.join(" "), "Übernachtung im Doppelzimmer mit Frühstück");
+
+        let cut = truncate("Familie Mustermann-Schmidt", 8.0, Font::Regular, 40.0);
+        assert!(cut.ends_with('…') && text_width(&cut, 8.0, Font::Regular) <= 40.0);
+    }
+}
@@ -0,0 +1,134 @@
+// Payments - deposit requests (Anzahlungen) against the payments ledger of a booking
+// Pure functions only: no State, no DB access. Balance and payment status of a booking come
+// from the view booking_balances; this module decides which deposit requests are covered.
+//
+// Rules:
+// - Payments are not assigned to a request by hand: the paid total of the booking covers the
+//   requests in order of their due dates (earliest first)
+// - A request is `bezahlt` when the paid total covers it completely, `ueberfaellig` when it is
+//   not covered after its due date, otherwise `offen`
+// - Percentage requests are rounded per request to cents (Money::percent)
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DepositState {
+    Offen,
+    Ueberfaellig,
+    Bezahlt,
+}
+
+impl DepositState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DepositState::Offen => "offen",
+            DepositState::Ueberfaellig => "ueberfaellig",
+            DepositState::Bezahlt => "bezahlt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepositInput {
+    pub id: i32,
+    pub amount: Money,
+    pub due_date: NaiveDate,
+}
+
+/// Share of the paid total that covers a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepositCoverage {
+    pub id: i32,
+    pub covered: Money,
+    pub state: DepositState,
+}
+
+/// Apply the paid total of a booking to its deposit requests (earliest due date first)
+pub fn cover_deposits(deposits: &[DepositInput], paid: Money, today: NaiveDate) -> Vec<DepositCoverage> {
+    let mut ordered: Vec<&DepositInput> = deposits.iter().collect();
+    ordered.sort_by_key(|d| (d.due_date, d.id));
+
+    let mut remaining = paid.max(Money::ZERO);
+    let mut coverage: Vec<DepositCoverage> = ordered
+        .into_iter()
+        .map(|deposit| {
+            let covered = remaining.min(deposit.amount);
+            remaining -= covered;
+            let state = if covered == deposit.amount {
+                DepositState::Bezahlt
+            } else if deposit.due_date < today {
+                DepositState::Ueberfaellig
+            } else {
+                DepositState::Offen
+            };
+            DepositCoverage { id: deposit.id, covered, state }
+        })
+        .collect();
+
+    // Back in the order of the input
+    coverage.sort_by_key(|c| deposits.iter().position(|d| d.id == c.id));
+    coverage
+}
+
+/// Amount of a deposit requested as percentage of the amount due
+pub fn deposit_amount(total_due: Money, percentage: f64) -> Result<Money, String> {
+    if !(percentage > 0.0 && percentage <= 100.0) {
+        return Err("Die Anzahlung muss zwischen 0 und 100 % liegen".to_string());
+    }
+    Ok(total_due.percent(percentage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn eur(euros: f64) -> Money {
+        Money::from_euros(euros)
+    }
+
+    fn deposits() -> Vec<DepositInput> {
+        vec![
+            DepositInput { id: 2, amount: eur(700.0), due_date: date(2026, 12, 10) },
+            DepositInput { id: 1, amount: eur(300.0), due_date: date(2026, 11, 1) },
+        ]
+    }
+
+    #[test]
+    fn earliest_request_is_covered_first() {
+        let coverage = cover_deposits(&deposits(), eur(500.0), date(2026, 11, 15));
+        assert_eq!(coverage[0], DepositCoverage { id: 2, covered: eur(200.0), state: DepositState::Offen });
+        assert_eq!(coverage[1], DepositCoverage { id: 1, covered: eur(300.0), state: DepositState::Bezahlt });
+    }
+
+    #[test]
+    fn uncovered_request_after_due_date_is_overdue() {
+        let coverage = cover_deposits(&deposits(), eur(100.0), date(2026, 11, 2));
+        assert_eq!(coverage[1].state, DepositState::Ueberfaellig);
+        assert_eq!(coverage[0].state, DepositState::Offen);
+
+        // Due today is not yet overdue
+        let coverage = cover_deposits(&deposits(), Money::ZERO, date(2026, 11, 1));
+        assert_eq!(coverage[1].state, DepositState::Offen);
+    }
+
+    #[test]
+    fn refunds_never_cover_negative_amounts() {
+        let coverage = cover_deposits(&deposits(), eur(-50.0), date(2026, 10, 1));
+        assert!(coverage.iter().all(|c| c.covered == Money::ZERO));
+    }
+
+    #[test]
+    fn percentage_deposit() {
+        assert_eq!(deposit_amount(eur(1234.55), 30.0).unwrap(), eur(370.37));
+        assert!(deposit_amount(eur(100.0), 0.0).is_err());
+        assert!(deposit_amount(eur(100.0), 120.0).is_err());
+    }
+}
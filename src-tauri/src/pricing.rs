@@ -0,0 +1,1110 @@
+// Pricing Engine - Single Source of Truth for booking price calculation
+// Pure functions only: no State, no DB access. All Tauri commands that need a
+// price (single calculation, batch lists, snapshot recalculation) delegate here.
+//
+// Algorithm (see docs/PRICING_LOGIC.md):
+// 1. Base price: per-night season detection (season calendar, then the
+//    Hauptsaison window of pricing_settings, then Nebensaison)
+//    + occupancy surcharges for persons above the room's base occupancy
+// 2. Services pass 1: fixed services + percent services on overnight_price
+//    (+ Endreinigung from room, unless already present as service)
+// 3. Services pass 2: percent services on total_price
+// 4. Discounts: percent discounts on the global `rabatt_basis`
+//    (+ automatic DPolG member discount, unless already present)
+// 5. Kurtaxe: per liable person and night of the room's municipality.
+//    Pass-through levy - never discounted, not part of `total`
+// 6. VAT summary: gross per tax category after discounts, net and included VAT per rate
+//
+// All amounts are `Money` (whole cents). Every service and discount line is
+// rounded to cents on its own; totals are sums of rounded lines (see money.rs).
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::database_pg::{KurtaxeRate, PricingSettings, Room, RoomChildPrice, RoomSeasonRate};
+use crate::money::Money;
+
+// ============================================================================
+// INPUT TYPES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceInput {
+    #[serde(alias = "name", alias = "serviceName", default)]
+    pub service_name: String,
+    #[serde(alias = "price", alias = "originalValue", default)]
+    pub original_value: f64,  // Changed from f32 for precision consistency
+    #[serde(alias = "priceType", default = "default_price_type")]
+    pub price_type: String,
+    #[serde(alias = "appliesTo", default = "default_applies_to")]
+    pub applies_to: String,
+    /// Tax category (`TAX_STANDARD` if not set)
+    #[serde(alias = "steuerkategorie", default)]
+    pub tax_category: Option<String>,
+}
+
+fn default_price_type() -> String {
+    "fixed".to_string()
+}
+
+fn default_applies_to() -> String {
+    "overnight_price".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscountInput {
+    #[serde(alias = "name", alias = "discountName", default)]
+    pub discount_name: String,
+    #[serde(alias = "value", alias = "originalValue", default)]
+    pub original_value: f64,  // Changed from f32 for precision consistency
+    #[serde(alias = "priceType", alias = "discountType", default = "default_discount_type")]
+    pub price_type: String,
+    #[serde(alias = "appliesTo", default = "default_applies_to")]
+    pub applies_to: String,
+}
+
+fn default_discount_type() -> String {
+    "percent".to_string()
+}
+
+/// Price rules of a room that live outside the rooms table
+#[derive(Debug, Clone, Default)]
+pub struct RoomRates {
+    /// Calendar seasons with a price for this room (see `SeasonRepository::get_rates_for_stay`)
+    pub seasons: Vec<RoomSeasonRate>,
+    /// Child price bands (see `RoomRepository::get_child_prices`)
+    pub child_prices: Vec<RoomChildPrice>,
+    /// Kurtaxe of the room's municipality (see `KurtaxeRepository::get_for_ort`)
+    pub kurtaxe: Option<KurtaxeRate>,
+}
+
+/// Who stays: adults (incl. main guest) and the ages of children on the check-in date
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Occupancy {
+    pub adults: i32,
+    pub child_ages: Vec<i32>,
+    /// Ages of all companions with known birthdate (Kurtaxe age exemption)
+    #[serde(default)]
+    pub companion_ages: Vec<i32>,
+    #[serde(default)]
+    pub business_travel: bool,
+}
+
+// ============================================================================
+// RESULT TYPES
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceCalculation {
+    pub name: String,
+    pub price_type: String,
+    pub original_value: f64,
+    pub applies_to: String,
+    pub calculated_price: Money,
+    pub base_amount: Option<Money>,
+    pub tax_category: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscountCalculation {
+    pub name: String,
+    pub discount_type: String,
+    pub original_value: f64,
+    pub calculated_amount: Money,
+    pub base_amount: Option<Money>,
+    /// true for the automatic DPolG member discount (not stored as manual discount yet)
+    #[serde(default)]
+    pub is_automatic: bool,
+}
+
+/// Occupancy surcharge line, e.g. "Kind (6–11 Jahre)" × 2 persons × 3 nights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OccupancyLine {
+    pub description: String,
+    pub persons: i32,
+    pub nights: i32,
+    pub price_per_night: Money,
+    pub total: Money,
+}
+
+/// Gross, net and included VAT of one tax category (after discounts)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxLine {
+    pub category: String,
+    pub rate: f64,
+    pub gross: Money,
+    pub net: Money,
+    pub tax: Money,
+}
+
+/// Gross amount of one price position with its tax category.
+/// Discountable positions carry the booking's discounts pro rata.
+#[derive(Debug, Clone)]
+pub struct TaxPosition {
+    pub category: String,
+    pub gross: Money,
+    pub discountable: bool,
+}
+
+/// Kurtaxe of a stay: liable persons × nights × rate of the municipality
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KurtaxeLine {
+    pub ort: String,
+    pub persons: i32,
+    pub exempt_persons: i32,
+    pub nights: i32,
+    pub rate_per_night: Money,
+    pub total: Money,
+}
+
+/// Nights of a stay grouped by the season (and nightly price) they were priced with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SeasonNights {
+    pub season_name: String,
+    pub nights: i32,
+    pub price_per_night: Money,
+    pub total: Money,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullPriceBreakdown {
+    /// Accommodation: room nights + occupancy surcharges (stored as `grundpreis`)
+    pub base_price: Money,
+    /// Room nights only (sum of `season_nights`)
+    pub room_price: Money,
+    pub occupancy: Vec<OccupancyLine>,
+    /// Occupancy share of `base_price` (stored as `belegung_preis`)
+    pub occupancy_total: Money,
+    pub nights: i32,
+    pub price_per_night: Money,
+    pub is_hauptsaison: bool,
+    /// Nights in the legacy Hauptsaison/Nebensaison window; calendar nights only appear in `season_nights`
+    pub hauptsaison_nights: i32,
+    pub nebensaison_nights: i32,
+    pub season_nights: Vec<SeasonNights>,
+    pub services: Vec<ServiceCalculation>,
+    pub services_total: Money,
+    pub discounts: Vec<DiscountCalculation>,
+    pub discounts_total: Money,
+    pub subtotal: Money,
+    /// Accommodation total after discounts (stored as `gesamtpreis`)
+    pub total: Money,
+    pub kurtaxe: Option<KurtaxeLine>,
+    /// Stored as `bookings.kurtaxe`
+    pub kurtaxe_total: Money,
+    /// What the guest pays: `total` + Kurtaxe
+    pub amount_due: Money,
+    /// VAT summary per tax category, adds up to `amount_due`
+    pub tax_lines: Vec<TaxLine>,
+}
+
+impl FullPriceBreakdown {
+    /// The automatic DPolG member discount, if one was applied
+    pub fn automatic_discount(&self) -> Option<&DiscountCalculation> {
+        self.discounts.iter().find(|d| d.is_automatic)
+    }
+}
+
+// ============================================================================
+// SETTINGS & DATES
+// ============================================================================
+
+/// Fallback settings when the pricing_settings table is empty or unreadable
+pub fn default_pricing_settings() -> PricingSettings {
+    PricingSettings {
+        id: 1,
+        hauptsaison_aktiv: Some(false),
+        hauptsaison_start: Some("06-01".to_string()),
+        hauptsaison_ende: Some("08-31".to_string()),
+        mitglieder_rabatt_aktiv: Some(false),
+        mitglieder_rabatt_prozent: Some(15.0),
+        rabatt_basis: Some("zimmerpreis".to_string()),
+        mwst_beherbergung: Some(7.0),
+        mwst_standard: Some(19.0),
+        updated_at: None,
+    }
+}
+
+/// Parse a check-in/check-out pair (YYYY-MM-DD) and validate that checkout is after checkin
+pub fn parse_stay_dates(checkin: &str, checkout: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let checkin_date = NaiveDate::parse_from_str(checkin, "%Y-%m-%d")
+        .map_err(|e| format!("Ungültiges Check-in Datum: {}", e))?;
+    let checkout_date = NaiveDate::parse_from_str(checkout, "%Y-%m-%d")
+        .map_err(|e| format!("Ungültiges Check-out Datum: {}", e))?;
+
+    if checkout_date <= checkin_date {
+        return Err("Check-out muss nach Check-in liegen".to_string());
+    }
+
+    Ok((checkin_date, checkout_date))
+}
+
+/// Check whether a single night falls into the Hauptsaison window.
+/// The window is stored as MM-DD strings and may wrap around the year end (e.g. 12-01 to 02-28).
+pub fn is_hauptsaison_night(settings: &PricingSettings, night: NaiveDate) -> bool {
+    if !settings.hauptsaison_aktiv.unwrap_or(false) {
+        return false;
+    }
+
+    let start = settings.hauptsaison_start.as_deref().unwrap_or("06-01");
+    let ende = settings.hauptsaison_ende.as_deref().unwrap_or("08-31");
+    let night_mmdd = format!("{:02}-{:02}", night.month(), night.day());
+
+    if start > ende {
+        night_mmdd.as_str() >= start || night_mmdd.as_str() <= ende
+    } else {
+        night_mmdd.as_str() >= start && night_mmdd.as_str() <= ende
+    }
+}
+
+/// A calendar season with parsed dates
+struct CalendarEntry<'a> {
+    rate: &'a RoomSeasonRate,
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+fn parse_calendar(seasons: &[RoomSeasonRate]) -> Vec<CalendarEntry<'_>> {
+    seasons
+        .iter()
+        .filter_map(|rate| {
+            let start = NaiveDate::parse_from_str(&rate.start_date, "%Y-%m-%d").ok()?;
+            let end = NaiveDate::parse_from_str(&rate.end_date, "%Y-%m-%d").ok()?;
+            Some(CalendarEntry { rate, start, end })
+        })
+        .collect()
+}
+
+/// Find the calendar season that prices a night. Highest priority wins; on a tie the
+/// shorter (more specific) season wins, e.g. "Ostern" inside "Frühjahr".
+fn season_for_night<'a>(calendar: &'a [CalendarEntry<'a>], night: NaiveDate) -> Option<&'a CalendarEntry<'a>> {
+    calendar
+        .iter()
+        .filter(|e| e.start <= night && night <= e.end)
+        .max_by_key(|e| (e.rate.priority, -(e.end - e.start).num_days(), e.rate.season_id))
+}
+
+fn add_season_night(season_nights: &mut Vec<SeasonNights>, season_name: &str, price: Money) {
+    match season_nights
+        .iter_mut()
+        .find(|s| s.season_name == season_name && s.price_per_night == price)
+    {
+        Some(group) => {
+            group.nights += 1;
+            group.total += price;
+        }
+        None => season_nights.push(SeasonNights {
+            season_name: season_name.to_string(),
+            nights: 1,
+            price_per_night: price,
+            total: price,
+        }),
+    }
+}
+
+/// Age in completed years on a given date
+pub fn age_on(birthdate: NaiveDate, date: NaiveDate) -> i32 {
+    let mut age = date.year() - birthdate.year();
+    if (date.month(), date.day()) < (birthdate.month(), birthdate.day()) {
+        age -= 1;
+    }
+    age
+}
+
+fn child_band(child_prices: &[RoomChildPrice], age: i32) -> Option<&RoomChildPrice> {
+    child_prices.iter().find(|b| b.alter_von <= age && age <= b.alter_bis)
+}
+
+/// Build the occupancy of a stay from the guest count and the companions' birthdates.
+/// Companions whose age on check-in falls into a child band of the room count as children,
+/// everybody else (incl. the main guest and companions without birthdate) as adults.
+pub fn occupancy_from_guests(
+    anzahl_gaeste: i32,
+    companion_birthdates: &[Option<String>],
+    checkin: NaiveDate,
+    child_prices: &[RoomChildPrice],
+) -> Occupancy {
+    let companion_ages: Vec<i32> = companion_birthdates
+        .iter()
+        .flatten()
+        .filter_map(|b| NaiveDate::parse_from_str(b, "%Y-%m-%d").ok())
+        .map(|b| age_on(b, checkin))
+        .collect();
+    let child_ages: Vec<i32> = companion_ages
+        .iter()
+        .copied()
+        .filter(|age| child_band(child_prices, *age).is_some())
+        .collect();
+    let persons = anzahl_gaeste.max(1 + companion_birthdates.len() as i32);
+
+    Occupancy {
+        adults: persons - child_ages.len() as i32,
+        child_ages,
+        companion_ages,
+        business_travel: false,
+    }
+}
+
+/// Kurtaxe for a stay. Persons younger than `befreit_unter_alter` are exempt,
+/// business trips are fully exempt if the municipality allows it.
+pub fn calculate_kurtaxe(rate: &KurtaxeRate, occupancy: &Occupancy, nights: i32) -> KurtaxeLine {
+    let persons = occupancy.adults.max(0) + occupancy.child_ages.len() as i32;
+    let exempt_persons = if occupancy.business_travel && rate.geschaeftsreise_befreit {
+        persons
+    } else {
+        let too_young = occupancy
+            .companion_ages
+            .iter()
+            .filter(|age| **age < rate.befreit_unter_alter)
+            .count() as i32;
+        too_young.min(persons)
+    };
+    let liable = persons - exempt_persons;
+
+    KurtaxeLine {
+        ort: rate.ort.clone(),
+        persons: liable,
+        exempt_persons,
+        nights,
+        rate_per_night: rate.betrag_pro_nacht,
+        total: rate.betrag_pro_nacht * (liable as i64 * nights as i64),
+    }
+}
+
+/// Share of a stay's Kurtaxe that falls into the month starting at `month_start`
+/// (monthly report). The Kurtaxe is split over the months of the stay in proportion
+/// to the nights (rounding rule 6), so the months always add up to the booking's Kurtaxe.
+pub fn kurtaxe_month_share(total: Money, checkin: NaiveDate, checkout: NaiveDate, month_start: NaiveDate) -> Money {
+    let mut weights = Vec::new();
+    let mut target = None;
+    let mut start = checkin;
+    while start < checkout {
+        let next_month = NaiveDate::from_ymd_opt(start.year(), start.month(), 1)
+            .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+            .unwrap_or(checkout);
+        let end = next_month.min(checkout);
+        if start.year() == month_start.year() && start.month() == month_start.month() {
+            target = Some(weights.len());
+        }
+        weights.push(Money::from_cents((end - start).num_days()));
+        start = end;
+    }
+
+    match target {
+        Some(index) => total.allocate(&weights)[index],
+        None => Money::ZERO,
+    }
+}
+
+/// Surcharge lines for all persons above the room's base occupancy.
+/// The base occupancy covers the most expensive persons first (adults before children).
+fn occupancy_lines(room: &Room, child_prices: &[RoomChildPrice], occupancy: &Occupancy, nights: i32) -> Vec<OccupancyLine> {
+    let Some(basis_belegung) = room.basis_belegung else {
+        return Vec::new();
+    };
+    let adult = ("Zusätzlicher Erwachsener".to_string(), room.aufpreis_erwachsener.unwrap_or(Money::ZERO));
+
+    let mut persons: Vec<(String, Money)> = vec![adult.clone(); occupancy.adults.max(0) as usize];
+    for age in &occupancy.child_ages {
+        persons.push(match child_band(child_prices, *age) {
+            Some(b) => (format!("Kind ({}–{} Jahre)", b.alter_von, b.alter_bis), b.preis_pro_nacht),
+            None => adult.clone(),
+        });
+    }
+    persons.sort_by_key(|p| std::cmp::Reverse(p.1));
+
+    let mut lines: Vec<OccupancyLine> = Vec::new();
+    for (description, price) in persons.into_iter().skip(basis_belegung.max(0) as usize) {
+        if !price.is_positive() {
+            continue;
+        }
+        match lines.iter_mut().find(|l| l.description == description && l.price_per_night == price) {
+            Some(line) => {
+                line.persons += 1;
+                line.total += price * nights as i64;
+            }
+            None => lines.push(OccupancyLine {
+                description,
+                persons: 1,
+                nights,
+                price_per_night: price,
+                total: price * nights as i64,
+            }),
+        }
+    }
+    lines
+}
+
+// ============================================================================
+// TAX
+// ============================================================================
+
+/// Accommodation incl. occupancy surcharges (reduced rate)
+pub const TAX_ACCOMMODATION: &str = "beherbergung";
+/// Extras like breakfast, parking, cleaning (standard rate)
+pub const TAX_STANDARD: &str = "standard";
+/// Not taxable, e.g. Kurtaxe
+pub const TAX_EXEMPT: &str = "steuerfrei";
+
+/// VAT rate in percent of a tax category
+pub fn tax_rate(settings: &PricingSettings, category: &str) -> f64 {
+    match category {
+        TAX_ACCOMMODATION => settings.mwst_beherbergung.unwrap_or(7.0),
+        TAX_EXEMPT => 0.0,
+        _ => settings.mwst_standard.unwrap_or(19.0),
+    }
+}
+
+/// VAT summary per tax category. The discounts are split over the discountable
+/// positions pro rata, VAT is computed from the discounted gross per category.
+pub fn tax_summary(settings: &PricingSettings, positions: &[TaxPosition], discounts_total: Money) -> Vec<TaxLine> {
+    let weights: Vec<Money> = positions
+        .iter()
+        .map(|p| if p.discountable { p.gross } else { Money::ZERO })
+        .collect();
+    let shares = if discounts_total.is_zero() || weights.iter().all(|w| w.is_zero()) {
+        vec![Money::ZERO; positions.len()]
+    } else {
+        discounts_total.allocate(&weights)
+    };
+
+    let mut lines: Vec<TaxLine> = Vec::new();
+    for (position, share) in positions.iter().zip(shares) {
+        let gross = position.gross - share;
+        match lines.iter_mut().find(|l| l.category == position.category) {
+            Some(line) => line.gross += gross,
+            None => lines.push(TaxLine {
+                category: position.category.clone(),
+                rate: tax_rate(settings, &position.category),
+                gross,
+                net: Money::ZERO,
+                tax: Money::ZERO,
+            }),
+        }
+    }
+
+    lines.retain(|l| !l.gross.is_zero());
+    for line in &mut lines {
+        line.tax = line.gross.included_tax(line.rate);
+        line.net = line.gross - line.tax;
+    }
+    lines
+}
+
+fn is_cleaning_service(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.contains("endreinigung") || name.contains("cleaning")
+}
+
+fn is_member_discount(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.contains("dpolg") || name.contains("mitglieder")
+}
+
+// ============================================================================
+// CALCULATION
+// ============================================================================
+
+/// Calculate the full price breakdown for a stay.
+///
+/// `rates.seasons` are the calendar seasons with a price for this room. Nights outside every
+/// season fall back to the room's Hauptsaison/Nebensaison prices. Persons in `occupancy` above
+/// the room's `basis_belegung` add surcharge lines (adult surcharge or child band price).
+///
+/// `discounts` are the manual discounts of the booking. If the guest is a member and the
+/// member discount is enabled, an automatic DPolG discount is appended unless a discount
+/// with "dpolg"/"mitglieder" in its name already exists.
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_price(
+    room: &Room,
+    settings: &PricingSettings,
+    rates: &RoomRates,
+    occupancy: &Occupancy,
+    checkin: NaiveDate,
+    checkout: NaiveDate,
+    is_member: bool,
+    services: &[ServiceInput],
+    discounts: &[DiscountInput],
+) -> Result<FullPriceBreakdown, String> {
+    let nights = (checkout - checkin).num_days() as i32;
+    if nights <= 0 {
+        return Err("Check-out muss nach Check-in liegen".to_string());
+    }
+
+    // 1. Base price with per-night season detection
+    let nebensaison_price = room.nebensaison_preis.unwrap_or(Money::ZERO);
+    let hauptsaison_price = room.hauptsaison_preis.unwrap_or(nebensaison_price);
+
+    let calendar = parse_calendar(&rates.seasons);
+
+    let mut room_price = Money::ZERO;
+    let mut hauptsaison_nights = 0i32;
+    let mut nebensaison_nights = 0i32;
+    let mut season_nights: Vec<SeasonNights> = Vec::new();
+    for offset in 0..nights {
+        let night = checkin + chrono::Duration::days(offset as i64);
+        let (season_name, price) = if let Some(entry) = season_for_night(&calendar, night) {
+            (entry.rate.season_name.as_str(), entry.rate.price_per_night)
+        } else if is_hauptsaison_night(settings, night) {
+            hauptsaison_nights += 1;
+            ("Hauptsaison", hauptsaison_price)
+        } else {
+            nebensaison_nights += 1;
+            ("Nebensaison", nebensaison_price)
+        };
+        room_price += price;
+        add_season_night(&mut season_nights, season_name, price);
+    }
+
+    let surcharges = occupancy_lines(room, &rates.child_prices, occupancy, nights);
+    let occupancy_total: Money = surcharges.iter().map(|l| l.total).sum();
+
+    let base_price = room_price + occupancy_total;
+    let price_per_night = base_price.divide(nights as i64);
+
+    // 2. Services pass 1: fixed services and percent services on overnight_price
+    let mut service_calculations: Vec<ServiceCalculation> = Vec::new();
+    let mut first_pass_total = Money::ZERO;
+
+    for s in services {
+        if s.price_type == "fixed" || s.applies_to == "overnight_price" {
+            let calculated = if s.price_type == "percent" {
+                base_price.percent(s.original_value)
+            } else {
+                Money::from_euros(s.original_value)
+            };
+            first_pass_total += calculated;
+            service_calculations.push(ServiceCalculation {
+                name: s.service_name.clone(),
+                price_type: s.price_type.clone(),
+                original_value: s.original_value,
+                applies_to: s.applies_to.clone(),
+                calculated_price: calculated,
+                base_amount: Some(base_price),
+                tax_category: service_tax_category(s),
+            });
+        }
+    }
+
+    // Endreinigung SAFEGUARD: only add the room's cleaning fee if the user
+    // did not add an "Endreinigung"/"cleaning" service manually
+    let has_endreinigung = services.iter().any(|s| is_cleaning_service(&s.service_name));
+    if !has_endreinigung {
+        if let Some(endreinigung) = room.endreinigung.filter(|e| e.is_positive()) {
+            first_pass_total += endreinigung;
+            service_calculations.push(ServiceCalculation {
+                name: "Endreinigung".to_string(),
+                price_type: "fixed".to_string(),
+                original_value: endreinigung.to_euros(),
+                applies_to: "total_price".to_string(),
+                calculated_price: endreinigung,
+                base_amount: Some(base_price),
+                tax_category: TAX_STANDARD.to_string(),
+            });
+        }
+    }
+
+    // 3. Services pass 2: percent services on total_price
+    let total_price_base = base_price + first_pass_total;
+    for s in services {
+        if s.price_type == "percent" && s.applies_to == "total_price" {
+            service_calculations.push(ServiceCalculation {
+                name: s.service_name.clone(),
+                price_type: s.price_type.clone(),
+                original_value: s.original_value,
+                applies_to: s.applies_to.clone(),
+                calculated_price: total_price_base.percent(s.original_value),
+                base_amount: Some(total_price_base),
+                tax_category: service_tax_category(s),
+            });
+        }
+    }
+
+    let services_total: Money = service_calculations.iter().map(|s| s.calculated_price).sum();
+    let subtotal = base_price + services_total;
+
+    // 4. Discounts: all percent discounts share the global rabatt_basis
+    let rabatt_basis = settings.rabatt_basis.as_deref().unwrap_or("zimmerpreis");
+    let discount_base = if rabatt_basis == "gesamtpreis" { subtotal } else { base_price };
+
+    let mut discount_calculations: Vec<DiscountCalculation> = discounts
+        .iter()
+        .map(|d| {
+            let calculated = if d.price_type == "percent" {
+                discount_base.percent(d.original_value)
+            } else {
+                Money::from_euros(d.original_value)
+            };
+            DiscountCalculation {
+                name: d.discount_name.clone(),
+                discount_type: d.price_type.clone(),
+                original_value: d.original_value,
+                calculated_amount: calculated,
+                base_amount: Some(discount_base),
+                is_automatic: false,
+            }
+        })
+        .collect();
+
+    // Automatic DPolG member discount (avoid duplicates with a manual one)
+    if is_member && settings.mitglieder_rabatt_aktiv.unwrap_or(false) {
+        let rabatt_prozent = settings.mitglieder_rabatt_prozent.unwrap_or(15.0);
+        let auto_rabatt = discount_base.percent(rabatt_prozent);
+        let has_dpolg_rabatt = discounts.iter().any(|d| is_member_discount(&d.discount_name));
+
+        if !has_dpolg_rabatt && auto_rabatt.is_positive() {
+            discount_calculations.push(DiscountCalculation {
+                name: format!("DPolG Mitgliederrabatt ({}%)", rabatt_prozent as i32),
+                discount_type: "percent".to_string(),
+                original_value: rabatt_prozent,
+                calculated_amount: auto_rabatt,
+                base_amount: Some(discount_base),
+                is_automatic: true,
+            });
+        }
+    }
+
+    let discounts_total: Money = discount_calculations.iter().map(|d| d.calculated_amount).sum();
+    let total = subtotal - discounts_total;
+
+    // 5. Kurtaxe: added after discounts, never discounted
+    let kurtaxe = rates.kurtaxe.as_ref().map(|rate| calculate_kurtaxe(rate, occupancy, nights));
+    let kurtaxe_total = kurtaxe.as_ref().map(|k| k.total).unwrap_or(Money::ZERO);
+
+    // 6. VAT summary (discounts follow rabatt_basis, Kurtaxe is never discounted)
+    let mut positions = vec![TaxPosition {
+        category: room.steuerkategorie.clone().unwrap_or_else(|| TAX_ACCOMMODATION.to_string()),
+        gross: base_price,
+        discountable: true,
+    }];
+    positions.extend(service_calculations.iter().map(|s| TaxPosition {
+        category: s.tax_category.clone(),
+        gross: s.calculated_price,
+        discountable: rabatt_basis == "gesamtpreis",
+    }));
+    positions.push(TaxPosition {
+        category: TAX_EXEMPT.to_string(),
+        gross: kurtaxe_total,
+        discountable: false,
+    });
+    let tax_lines = tax_summary(settings, &positions, discounts_total);
+
+    Ok(FullPriceBreakdown {
+        base_price,
+        room_price,
+        occupancy: surcharges,
+        occupancy_total,
+        nights,
+        price_per_night,
+        is_hauptsaison: hauptsaison_nights > 0,
+        hauptsaison_nights,
+        nebensaison_nights,
+        season_nights,
+        services: service_calculations,
+        services_total,
+        discounts: discount_calculations,
+        discounts_total,
+        subtotal,
+        total,
+        kurtaxe,
+        kurtaxe_total,
+        amount_due: total + kurtaxe_total,
+        tax_lines,
+    })
+}
+
+fn service_tax_category(service: &ServiceInput) -> String {
+    service.tax_category.clone().unwrap_or_else(|| TAX_STANDARD.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(ns: f64, hs: f64, endreinigung: Option<f64>) -> Room {
+        Room {
+            id: 1,
+            name: "Zimmer 1".to_string(),
+            gebaeude_typ: "Haus".to_string(),
+            capacity: 2,
+            nebensaison_preis: Some(Money::from_euros(ns)),
+            hauptsaison_preis: Some(Money::from_euros(hs)),
+            endreinigung: endreinigung.map(Money::from_euros),
+            basis_belegung: None,
+            aufpreis_erwachsener: None,
+            steuerkategorie: None,
+            ort: "Fall".to_string(),
+            schluesselcode: None,
+            street_address: None,
+            postal_code: None,
+            city: None,
+            notizen: None,
+            hunde_erlaubt: None,
+            erdgeschoss: None,
+            created_by: None,
+            updated_by: None,
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn service(name: &str, value: f64, price_type: &str, applies_to: &str) -> ServiceInput {
+        ServiceInput {
+            service_name: name.to_string(),
+            original_value: value,
+            price_type: price_type.to_string(),
+            applies_to: applies_to.to_string(),
+            tax_category: None,
+        }
+    }
+
+    fn discount(name: &str, value: f64, price_type: &str) -> DiscountInput {
+        DiscountInput {
+            discount_name: name.to_string(),
+            original_value: value,
+            price_type: price_type.to_string(),
+            applies_to: "total_price".to_string(),
+        }
+    }
+
+    fn assert_money(actual: Money, expected: f64) {
+        assert_eq!(actual, Money::from_euros(expected), "expected {expected}, got {actual}");
+    }
+
+    #[test]
+    fn test_fixed_service() {
+        let settings = default_pricing_settings();
+        let services = vec![service("Parkplatz", 10.0, "fixed", "overnight_price")];
+        let result = calculate_price(&room(100.0, 120.0, None), &settings, &RoomRates::default(), &Occupancy::default(),
+            date("2025-03-01"), date("2025-03-04"), false, &services, &[]).unwrap();
+
+        assert_eq!(result.nights, 3);
+        assert_money(result.base_price, 300.0);
+        assert_money(result.total, 310.0);
+    }
+
+    #[test]
+    fn test_percent_service_on_total_price() {
+        let settings = default_pricing_settings();
+        let services = vec![
+            service("Frühstück", 10.0, "fixed", "overnight_price"),
+            service("Kurtaxe", 5.0, "percent", "total_price"),
+        ];
+        let result = calculate_price(&room(100.0, 100.0, None), &settings, &RoomRates::default(), &Occupancy::default(),
+            date("2025-03-01"), date("2025-03-04"), false, &services, &[]).unwrap();
+
+        assert_money(result.services_total, 25.5);
+        assert_money(result.total, 325.5);
+    }
+
+    #[test]
+    fn test_endreinigung_safeguard() {
+        let settings = default_pricing_settings();
+        let r = room(100.0, 100.0, Some(50.0));
+
+        let auto = calculate_price(&r, &settings, &RoomRates::default(), &Occupancy::default(), date("2025-03-01"), date("2025-03-02"), false, &[], &[]).unwrap();
+        assert_money(auto.services_total, 50.0);
+
+        let manual = vec![service("Endreinigung", 50.0, "fixed", "overnight_price")];
+        let result = calculate_price(&r, &settings, &RoomRates::default(), &Occupancy::default(), date("2025-03-01"), date("2025-03-02"), false, &manual, &[]).unwrap();
+        assert_money(result.services_total, 50.0);
+        assert_eq!(result.services.len(), 1);
+    }
+
+    #[test]
+    fn test_hauptsaison_per_night() {
+        let mut settings = default_pricing_settings();
+        settings.hauptsaison_aktiv = Some(true);
+
+        // 30.05. + 31.05. Nebensaison, 01.06. Hauptsaison
+        let result = calculate_price(&room(100.0, 150.0, None), &settings, &RoomRates::default(), &Occupancy::default(),
+            date("2025-05-30"), date("2025-06-02"), false, &[], &[]).unwrap();
+
+        assert_eq!(result.hauptsaison_nights, 1);
+        assert_eq!(result.nebensaison_nights, 2);
+        assert!(result.is_hauptsaison);
+        assert_money(result.base_price, 350.0);
+    }
+
+    fn season(id: i32, name: &str, start: &str, end: &str, priority: i32, price: f64) -> RoomSeasonRate {
+        RoomSeasonRate {
+            season_id: id,
+            season_name: name.to_string(),
+            start_date: start.to_string(),
+            end_date: end.to_string(),
+            priority,
+            price_per_night: Money::from_euros(price),
+        }
+    }
+
+    #[test]
+    fn test_season_calendar_per_night() {
+        let mut settings = default_pricing_settings();
+        settings.hauptsaison_aktiv = Some(true);
+        let seasons = vec![
+            season(1, "Frühjahr", "2026-03-01", "2026-05-31", 0, 110.0),
+            season(2, "Ostern", "2026-04-03", "2026-04-06", 0, 140.0),
+        ];
+
+        // 02.04. Frühjahr, 03.-06.04. Ostern (kürzere Saison gewinnt), 07.04. Frühjahr
+        let result = calculate_price(&room(100.0, 150.0, None), &settings, &RoomRates { seasons, ..Default::default() }, &Occupancy::default(),
+            date("2026-04-02"), date("2026-04-08"), false, &[], &[]).unwrap();
+
+        assert_money(result.base_price, 4.0 * 140.0 + 2.0 * 110.0);
+        assert_eq!(result.season_nights.len(), 2);
+        assert_eq!(result.season_nights[0].season_name, "Frühjahr");
+        assert_eq!(result.season_nights[0].nights, 2);
+        assert_eq!(result.season_nights[1].season_name, "Ostern");
+        assert_money(result.season_nights[1].total, 560.0);
+        assert_eq!(result.hauptsaison_nights + result.nebensaison_nights, 0);
+    }
+
+    #[test]
+    fn test_season_priority_and_fallback() {
+        let mut settings = default_pricing_settings();
+        settings.hauptsaison_aktiv = Some(true);
+        let seasons = vec![
+            season(1, "Hochsaison", "2026-07-01", "2026-08-31", 5, 180.0),
+            season(2, "Sommerferien-Aktion", "2026-07-01", "2026-07-05", 0, 90.0),
+        ];
+
+        // 30.06. Hauptsaison-Fenster (kein Kalendereintrag), 01.07. Hochsaison (höhere Priorität)
+        let result = calculate_price(&room(100.0, 150.0, None), &settings, &RoomRates { seasons, ..Default::default() }, &Occupancy::default(),
+            date("2026-06-30"), date("2026-07-02"), false, &[], &[]).unwrap();
+
+        assert_money(result.base_price, 150.0 + 180.0);
+        assert_eq!(result.hauptsaison_nights, 1);
+        assert_eq!(result.season_nights[1].season_name, "Hochsaison");
+    }
+
+    fn child_price(von: i32, bis: i32, price: f64) -> RoomChildPrice {
+        RoomChildPrice {
+            id: 0,
+            room_id: 1,
+            alter_von: von,
+            alter_bis: bis,
+            preis_pro_nacht: Money::from_euros(price),
+        }
+    }
+
+    #[test]
+    fn test_occupancy_surcharges() {
+        let settings = default_pricing_settings();
+        let mut r = room(80.0, 80.0, None);
+        r.basis_belegung = Some(2);
+        r.aufpreis_erwachsener = Some(Money::from_euros(25.0));
+        let rates = RoomRates {
+            child_prices: vec![child_price(0, 2, 0.0), child_price(3, 11, 12.5)],
+            ..Default::default()
+        };
+
+        // Familie: 2 Erwachsene (Basis) + 1 weiterer Erwachsener + Kind 7 J. + Kleinkind 1 J.
+        let companions = vec![
+            None,
+            Some("2019-03-10".to_string()),
+            Some("2025-01-15".to_string()),
+            Some("2008-05-01".to_string()),
+        ];
+        let occupancy = occupancy_from_guests(5, &companions, date("2026-07-01"), &rates.child_prices);
+        assert_eq!(occupancy.adults, 3);
+        assert_eq!(occupancy.child_ages, vec![7, 1]);
+
+        let result = calculate_price(&r, &settings, &rates, &occupancy,
+            date("2026-07-01"), date("2026-07-04"), false, &[], &[]).unwrap();
+
+        assert_money(result.room_price, 240.0);
+        assert_eq!(result.occupancy.len(), 2);
+        assert_eq!(result.occupancy[0].description, "Zusätzlicher Erwachsener");
+        assert_money(result.occupancy[0].total, 75.0);
+        assert_eq!(result.occupancy[1].description, "Kind (3–11 Jahre)");
+        assert_money(result.occupancy[1].total, 37.5);
+        assert_money(result.occupancy_total, 112.5);
+        assert_money(result.base_price, 352.5);
+    }
+
+    fn kurtaxe_rate(betrag: f64, befreit_unter_alter: i32) -> KurtaxeRate {
+        KurtaxeRate {
+            id: 1,
+            ort: "Fall".to_string(),
+            betrag_pro_nacht: Money::from_euros(betrag),
+            befreit_unter_alter,
+            geschaeftsreise_befreit: true,
+            is_active: true,
+        }
+    }
+
+    #[test]
+    fn test_kurtaxe_exemptions() {
+        let settings = default_pricing_settings();
+        let rates = RoomRates {
+            kurtaxe: Some(kurtaxe_rate(2.5, 16)),
+            ..Default::default()
+        };
+
+        // 2 Erwachsene + Kind 7 J. (befreit) + Jugendlicher 17 J. (pflichtig), 3 Nächte
+        let companions = vec![
+            None,
+            Some("2019-03-10".to_string()),
+            Some("2009-01-01".to_string()),
+        ];
+        let mut occupancy = occupancy_from_guests(4, &companions, date("2026-07-01"), &rates.child_prices);
+        assert_eq!(occupancy.companion_ages, vec![7, 17]);
+
+        let discounts = vec![discount("Aktion", 50.0, "percent")];
+        let result = calculate_price(&room(100.0, 100.0, None), &settings, &rates, &occupancy,
+            date("2026-07-01"), date("2026-07-04"), false, &[], &discounts).unwrap();
+
+        let kurtaxe = result.kurtaxe.as_ref().unwrap();
+        assert_eq!(kurtaxe.persons, 3);
+        assert_eq!(kurtaxe.exempt_persons, 1);
+        assert_money(result.kurtaxe_total, 22.5);
+        // Rabatt wirkt nicht auf die Kurtaxe, Kurtaxe ist nicht im Gesamtpreis
+        assert_money(result.total, 150.0);
+        assert_money(result.amount_due, 172.5);
+
+        occupancy.business_travel = true;
+        let kurtaxe = calculate_kurtaxe(rates.kurtaxe.as_ref().unwrap(), &occupancy, 3);
+        assert_eq!(kurtaxe.persons, 0);
+        assert_eq!(kurtaxe.exempt_persons, 4);
+        assert_money(kurtaxe.total, 0.0);
+    }
+
+    #[test]
+    fn test_kurtaxe_month_shares_add_up() {
+        // 10,00 € over 3 nights: 2 in January, 1 in February
+        let total = Money::from_euros(10.0);
+        let (checkin, checkout) = (date("2026-01-30"), date("2026-02-02"));
+        let january = kurtaxe_month_share(total, checkin, checkout, date("2026-01-01"));
+        let february = kurtaxe_month_share(total, checkin, checkout, date("2026-02-01"));
+        assert_money(january, 6.67);
+        assert_money(february, 3.33);
+        assert_eq!(january + february, total);
+        assert_eq!(kurtaxe_month_share(total, checkin, checkout, date("2026-03-01")), Money::ZERO);
+    }
+
+    #[test]
+    fn test_tax_summary_per_category() {
+        let mut settings = default_pricing_settings();
+        settings.rabatt_basis = Some("gesamtpreis".to_string());
+        let mut fruehstueck = service("Frühstück", 60.0, "fixed", "overnight_price");
+        fruehstueck.tax_category = Some(TAX_STANDARD.to_string());
+        let services = vec![fruehstueck];
+        let discounts = vec![discount("Aktion", 10.0, "percent")];
+
+        let result = calculate_price(&room(100.0, 100.0, Some(40.0)), &settings, &RoomRates::default(), &Occupancy::default(),
+            date("2026-03-01"), date("2026-03-04"), false, &services, &discounts).unwrap();
+
+        // 300 Übernachtung + 60 Frühstück + 40 Endreinigung = 400, 10% Rabatt anteilig
+        assert_money(result.total, 360.0);
+        assert_eq!(result.tax_lines.len(), 2);
+        let accommodation = &result.tax_lines[0];
+        assert_eq!(accommodation.category, TAX_ACCOMMODATION);
+        assert_money(accommodation.gross, 270.0);
+        assert_money(accommodation.tax, 17.66);
+        assert_money(accommodation.net, 252.34);
+        let standard = &result.tax_lines[1];
+        assert_eq!(standard.rate, 19.0);
+        assert_money(standard.gross, 90.0);
+        assert_money(standard.tax, 14.37);
+
+        let sum: Money = result.tax_lines.iter().map(|l| l.gross).sum();
+        assert_eq!(sum, result.amount_due);
+    }
+
+    #[test]
+    fn test_age_on_checkin() {
+        assert_eq!(age_on(date("2014-07-02"), date("2026-07-01")), 11);
+        assert_eq!(age_on(date("2014-07-01"), date("2026-07-01")), 12);
+    }
+
+    #[test]
+    fn test_hauptsaison_wraps_year_end() {
+        let mut settings = default_pricing_settings();
+        settings.hauptsaison_aktiv = Some(true);
+        settings.hauptsaison_start = Some("12-15".to_string());
+        settings.hauptsaison_ende = Some("01-10".to_string());
+
+        assert!(is_hauptsaison_night(&settings, date("2025-12-31")));
+        assert!(is_hauptsaison_night(&settings, date("2026-01-05")));
+        assert!(!is_hauptsaison_night(&settings, date("2026-02-01")));
+    }
+
+    #[test]
+    fn test_member_discount_on_zimmerpreis() {
+        let mut settings = default_pricing_settings();
+        settings.mitglieder_rabatt_aktiv = Some(true);
+        let services = vec![service("Frühstück", 20.0, "fixed", "overnight_price")];
+
+        let result = calculate_price(&room(100.0, 100.0, None), &settings, &RoomRates::default(), &Occupancy::default(),
+            date("2025-03-01"), date("2025-03-04"), true, &services, &[]).unwrap();
+
+        assert_money(result.discounts_total, 45.0);
+        assert_money(result.total, 275.0);
+        assert!(result.automatic_discount().is_some());
+    }
+
+    #[test]
+    fn test_member_discount_on_gesamtpreis() {
+        let mut settings = default_pricing_settings();
+        settings.mitglieder_rabatt_aktiv = Some(true);
+        settings.rabatt_basis = Some("gesamtpreis".to_string());
+        let services = vec![service("Frühstück", 20.0, "fixed", "overnight_price")];
+
+        let result = calculate_price(&room(100.0, 100.0, None), &settings, &RoomRates::default(), &Occupancy::default(),
+            date("2025-03-01"), date("2025-03-04"), true, &services, &[]).unwrap();
+
+        assert_money(result.discounts_total, 48.0);
+        assert_money(result.total, 272.0);
+    }
+
+    #[test]
+    fn test_manual_member_discount_prevents_duplicate() {
+        let mut settings = default_pricing_settings();
+        settings.mitglieder_rabatt_aktiv = Some(true);
+        let discounts = vec![discount("DPolG Mitgliederrabatt (15%)", 15.0, "percent")];
+
+        let result = calculate_price(&room(100.0, 100.0, None), &settings, &RoomRates::default(), &Occupancy::default(),
+            date("2025-03-01"), date("2025-03-04"), true, &[], &discounts).unwrap();
+
+        assert_eq!(result.discounts.len(), 1);
+        assert!(result.automatic_discount().is_none());
+        assert_money(result.discounts_total, 45.0);
+    }
+
+    #[test]
+    fn test_lines_are_rounded_and_add_up() {
+        let mut settings = default_pricing_settings();
+        settings.mitglieder_rabatt_aktiv = Some(true);
+        settings.mitglieder_rabatt_prozent = Some(12.5);
+        let services = vec![service("Frühstück", 7.0, "percent", "overnight_price")];
+
+        // 3 × 33,33 € = 99,99 €; 7 % = 6,9993 → 7,00 €; 12,5 % Rabatt = 12,49875 → 12,50 €
+        let result = calculate_price(&room(33.33, 33.33, None), &settings, &RoomRates::default(), &Occupancy::default(),
+            date("2025-03-01"), date("2025-03-04"), true, &services, &[]).unwrap();
+
+        assert_money(result.services[0].calculated_price, 7.0);
+        assert_money(result.discounts_total, 12.5);
+        assert_eq!(result.total, result.base_price + result.services_total - result.discounts_total);
+        assert_money(result.total, 94.49);
+    }
+
+    #[test]
+    fn test_invalid_dates() {
+        assert!(parse_stay_dates("2025-03-04", "2025-03-01").is_err());
+        assert!(parse_stay_dates("2025-03-04", "2025-03-04").is_err());
+        assert!(parse_stay_dates("04.03.2025", "2025-03-05").is_err());
+        assert!(parse_stay_dates("2025-03-01", "2025-03-04").is_ok());
+    }
+}
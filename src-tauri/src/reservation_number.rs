@@ -0,0 +1,129 @@
+// Reservation Numbers - pattern of server-side allocated reservation numbers
+// Pure functions only: no State, no DB access. BookingRepository::next_reservation_number
+// takes the next value of the yearly sequence and formats it with the pattern from the
+// company settings.
+//
+// Placeholders:
+// - `{YYYY}` year (2026), `{YY}` two-digit year (26)
+// - `{seq}` sequence number of the year (1, 2, ...), `{seq:N}` zero-padded to N digits
+//
+// The number ends up in PDF file names ("Rechnung_RE-2026-0001_<number>.pdf"), so besides the
+// placeholders only letters, digits, '-' and '.' are allowed.
+
+use std::fmt::Write;
+
+/// Pattern used until a different one is configured (matches the numbers created before)
+pub const DEFAULT_PATTERN: &str = "{YYYY}-{seq}";
+
+/// Check a pattern: `{seq}` must occur exactly once, only known placeholders and
+/// file-name-safe characters. Errors are user-facing messages.
+pub fn validate_pattern(pattern: &str) -> Result<(), String> {
+    format_number(pattern, 2026, 1).map(|_| ())
+}
+
+/// Reservation number for sequence `sequence` of `year`
+pub fn format_number(pattern: &str, year: i32, sequence: i32) -> Result<String, String> {
+    let mut result = String::new();
+    let mut seq_count = 0;
+    let mut rest = pattern;
+
+    while let Some(start) = rest.find('{') {
+        push_literal(&mut result, &rest[..start])?;
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| "Nicht geschlossener Platzhalter im Format der Reservierungsnummer".to_string())?;
+
+        match &rest[start + 1..end] {
+            "YYYY" => write!(result, "{:04}", year).unwrap(),
+            "YY" => write!(result, "{:02}", year.rem_euclid(100)).unwrap(),
+            "seq" => {
+                seq_count += 1;
+                write!(result, "{}", sequence).unwrap();
+            }
+            placeholder => match placeholder.strip_prefix("seq:").and_then(|width| width.parse::<usize>().ok()) {
+                Some(width) if (1..=9).contains(&width) => {
+                    seq_count += 1;
+                    write!(result, "{:0width$}", sequence, width = width).unwrap();
+                }
+                _ => return Err(format!("Unbekannter Platzhalter {{{}}} im Format der Reservierungsnummer", placeholder)),
+            },
+        }
+        rest = &rest[end + 1..];
+    }
+    push_literal(&mut result, rest)?;
+
+    if seq_count != 1 {
+        return Err("Das Format der Reservierungsnummer braucht genau einen Platzhalter {seq}".to_string());
+    }
+    if result.len() > 64 {
+        return Err("Die Reservierungsnummer darf höchstens 64 Zeichen lang sein".to_string());
+    }
+
+    Ok(result)
+}
+
+fn push_literal(result: &mut String, literal: &str) -> Result<(), String> {
+    if let Some(invalid) = literal.chars().find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '.')) {
+        return Err(format!("Das Zeichen '{}' ist im Format der Reservierungsnummer nicht erlaubt", invalid));
+    }
+    result.push_str(literal);
+    Ok(())
+}
+
+/// Whether an invoice PDF in the invoices folder belongs to the reservation number.
+/// Compares the number part exactly ("2026-1" must not match "Rechnung_2026-169.pdf"). Formats:
+/// - "<Dokument>_<Rechnungsnummer>_<Reservierungsnummer>.pdf" (issued invoices, credit notes)
+/// - "Rechnung_<Reservierungsnummer>.pdf" and "Rechnung_RES-<Reservierungsnummer>.pdf" (old PDFs)
+pub fn invoice_file_matches(file_name: &str, reservierungsnummer: &str) -> bool {
+    let Some(stem) = file_name.strip_suffix(".pdf") else {
+        return false;
+    };
+    let Some((_, rest)) = stem.split_once('_') else {
+        return false;
+    };
+
+    rest == reservierungsnummer
+        || rest.strip_prefix("RES-") == Some(reservierungsnummer)
+        || rest.rsplit_once('_').is_some_and(|(_, number)| number == reservierungsnummer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_pattern_matches_old_numbers() {
+        assert_eq!(format_number(DEFAULT_PATTERN, 2026, 169).unwrap(), "2026-169");
+    }
+
+    #[test]
+    fn placeholders() {
+        assert_eq!(format_number("RES-{YY}-{seq:4}", 2026, 7).unwrap(), "RES-26-0007");
+        assert_eq!(format_number("{seq:3}.{YYYY}", 2027, 1234).unwrap(), "1234.2027");
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        assert!(validate_pattern("{YYYY}").is_err());
+        assert!(validate_pattern("{seq}-{seq}").is_err());
+        assert!(validate_pattern("{YYYY}-{nr}").is_err());
+        assert!(validate_pattern("{YYYY}-{seq").is_err());
+        assert!(validate_pattern("{YYYY}/{seq}").is_err());
+        assert!(validate_pattern("{YYYY}_{seq}").is_err());
+        assert!(validate_pattern("{seq:0}").is_err());
+        assert!(validate_pattern(DEFAULT_PATTERN).is_ok());
+    }
+
+    #[test]
+    fn invoice_files_match_exactly() {
+        assert!(invoice_file_matches("Rechnung_RE-2026-0001_2026-1.pdf", "2026-1"));
+        assert!(invoice_file_matches("Gutschrift_GS-2026-0003_2026-1.pdf", "2026-1"));
+        assert!(invoice_file_matches("Rechnung_2025-169.pdf", "2025-169"));
+        assert!(invoice_file_matches("Rechnung_RES-2025-169.pdf", "2025-169"));
+
+        assert!(!invoice_file_matches("Rechnung_2026-169.pdf", "2026-1"));
+        assert!(!invoice_file_matches("Rechnung_RE-2026-0001_2026-169.pdf", "2026-1"));
+        assert!(!invoice_file_matches("XRechnung_RE-2026-0001_2026-1.xml", "2026-1"));
+    }
+}
@@ -0,0 +1,479 @@
+// Room Assignment - room search ranking and room optimiser
+// Pure functions only: no State, no DB access. The Tauri commands load free rooms,
+// bookings and room blocks, delegate here and price the result with crate::pricing.
+//
+// Room search (rank_rooms):
+// - Hard filters: capacity >= party size, dogs only in rooms with `hunde_erlaubt`
+// - Preferences add points: location (`ort`), building type (`gebaeude_typ`), ground floor
+// - Unused beds cost points (big rooms stay free for big parties)
+// - Gap fit: a stay that starts on the departure day of the previous stay (or ends on the
+//   arrival day of the next one) earns points, a leftover gap of 1-2 nights costs points
+//   because such short gaps are hard to sell
+//
+// Room optimiser (optimise_assignment):
+// - Fixed stays (today/past, checked in, group/allotment bookings, `zimmer_fixiert`) and
+//   room blocks never move
+// - Movable stays are placed again in arrival order; each one takes the compatible room
+//   (same `ort` and `gebaeude_typ`, enough capacity, at least the features of its current
+//   room) that leaves the fewest short-gap nights, staying in its own room on a tie
+// - The plan is only proposed if it reduces the short-gap nights of the whole house
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::database_pg::Room;
+
+/// Gaps of up to this many nights between two stays count as "short gaps" (hard to sell)
+pub const SHORT_GAP_NIGHTS: i64 = 2;
+
+const WEIGHT_ORT: i32 = 40;
+const WEIGHT_GEBAEUDE_TYP: i32 = 20;
+const WEIGHT_ERDGESCHOSS: i32 = 15;
+const WEIGHT_SEAMLESS: i32 = 10;
+const PENALTY_SHORT_GAP: i32 = 8;
+const PENALTY_SPARE_BED: i32 = 5;
+
+// ============================================================================
+// ROOM SEARCH
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomSearchCriteria {
+    pub checkin: String,
+    pub checkout: String,
+    pub anzahl_gaeste: i32,
+    pub ort: Option<String>,
+    pub gebaeude_typ: Option<String>,
+    #[serde(default)]
+    pub hund: bool,
+    #[serde(default)]
+    pub erdgeschoss: bool,
+}
+
+/// A room that is free for the whole stay, with the neighbouring stays (or blocks) of that room
+#[derive(Debug, Clone)]
+pub struct RoomCandidate<'a> {
+    pub room: &'a Room,
+    pub previous_checkout: Option<NaiveDate>,
+    pub next_checkin: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomRanking {
+    pub room_id: i32,
+    pub score: i32,
+    pub gap_before: Option<i64>,  // Free nights between the previous stay and the check-in
+    pub gap_after: Option<i64>,   // Free nights between the check-out and the next stay
+    pub hinweise: Vec<String>,
+}
+
+/// Rank free rooms for a stay, best room first. Rooms failing a hard filter are left out.
+pub fn rank_rooms(
+    criteria: &RoomSearchCriteria,
+    checkin: NaiveDate,
+    checkout: NaiveDate,
+    candidates: &[RoomCandidate],
+) -> Vec<RoomRanking> {
+    let mut ranked: Vec<(RoomRanking, &str)> = candidates
+        .iter()
+        .filter(|c| c.room.capacity >= criteria.anzahl_gaeste)
+        .filter(|c| !criteria.hund || c.room.hunde_erlaubt.unwrap_or(false))
+        .map(|c| (rank_room(criteria, checkin, checkout, c), c.room.name.as_str()))
+        .collect();
+
+    ranked.sort_by(|(a, a_name), (b, b_name)| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| alphanumeric_sort::compare_str(a_name, b_name))
+    });
+
+    ranked.into_iter().map(|(ranking, _)| ranking).collect()
+}
+
+fn rank_room(criteria: &RoomSearchCriteria, checkin: NaiveDate, checkout: NaiveDate, candidate: &RoomCandidate) -> RoomRanking {
+    let room = candidate.room;
+    let mut score = 0;
+    let mut hinweise = Vec::new();
+
+    if let Some(ort) = criteria.ort.as_deref().filter(|o| !o.is_empty()) {
+        if room.ort.eq_ignore_ascii_case(ort) {
+            score += WEIGHT_ORT;
+        } else {
+            hinweise.push(format!("Anderer Ort ({})", room.ort));
+        }
+    }
+
+    if let Some(typ) = criteria.gebaeude_typ.as_deref().filter(|t| !t.is_empty()) {
+        if room.gebaeude_typ.eq_ignore_ascii_case(typ) {
+            score += WEIGHT_GEBAEUDE_TYP;
+        } else {
+            hinweise.push(format!("Anderer Zimmertyp ({})", room.gebaeude_typ));
+        }
+    }
+
+    if criteria.erdgeschoss {
+        if room.erdgeschoss.unwrap_or(false) {
+            score += WEIGHT_ERDGESCHOSS;
+        } else {
+            hinweise.push("Nicht im Erdgeschoss".to_string());
+        }
+    }
+
+    let spare_beds = room.capacity - criteria.anzahl_gaeste;
+    if spare_beds > 0 {
+        score -= spare_beds * PENALTY_SPARE_BED;
+        hinweise.push(format!("{} Bett(en) frei", spare_beds));
+    }
+
+    let gap_before = candidate.previous_checkout.map(|d| (checkin - d).num_days());
+    let gap_after = candidate.next_checkin.map(|d| (d - checkout).num_days());
+    for (gap, seamless, short) in [
+        (gap_before, "Schließt direkt an die Vorbuchung an", "vor der Anreise"),
+        (gap_after, "Nachfolgende Buchung schließt direkt an", "nach der Abreise"),
+    ] {
+        match gap {
+            Some(0) => {
+                score += WEIGHT_SEAMLESS;
+                hinweise.push(seamless.to_string());
+            }
+            Some(nights) if is_short_gap(nights) => {
+                score -= PENALTY_SHORT_GAP;
+                hinweise.push(format!("Lässt {} Nacht/Nächte Lücke {}", nights, short));
+            }
+            _ => {}
+        }
+    }
+
+    RoomRanking {
+        room_id: room.id,
+        score,
+        gap_before,
+        gap_after,
+        hinweise,
+    }
+}
+
+// ============================================================================
+// ROOM OPTIMISER
+// ============================================================================
+
+/// A stay (booking) in the planning window
+#[derive(Debug, Clone)]
+pub struct PlannedStay {
+    pub booking_id: i32,
+    pub room_id: i32,
+    pub checkin: NaiveDate,
+    pub checkout: NaiveDate,
+    pub anzahl_gaeste: i32,
+    pub movable: bool,
+}
+
+/// A room block in the planning window (never moves)
+#[derive(Debug, Clone)]
+pub struct BlockedRange {
+    pub room_id: i32,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomMove {
+    pub booking_id: i32,
+    pub from_room_id: i32,
+    pub to_room_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentPlan {
+    pub moves: Vec<RoomMove>,
+    pub short_gap_nights_before: i64,
+    pub short_gap_nights_after: i64,
+}
+
+/// Re-shuffle the movable stays to close short gaps. Returns no moves if nothing improves.
+pub fn optimise_assignment(rooms: &[Room], stays: &[PlannedStay], blocks: &[BlockedRange]) -> AssignmentPlan {
+    let rooms_by_id: HashMap<i32, &Room> = rooms.iter().map(|r| (r.id, r)).collect();
+
+    let mut current: HashMap<i32, Vec<(NaiveDate, NaiveDate)>> = HashMap::new();
+    let mut placed: HashMap<i32, Vec<(NaiveDate, NaiveDate)>> = HashMap::new();
+    for block in blocks {
+        current.entry(block.room_id).or_default().push((block.start, block.end));
+        placed.entry(block.room_id).or_default().push((block.start, block.end));
+    }
+    for stay in stays {
+        current.entry(stay.room_id).or_default().push((stay.checkin, stay.checkout));
+        if !stay.movable {
+            placed.entry(stay.room_id).or_default().push((stay.checkin, stay.checkout));
+        }
+    }
+    let short_gap_nights_before = short_gap_nights(&current);
+    let unchanged = AssignmentPlan {
+        moves: Vec::new(),
+        short_gap_nights_before,
+        short_gap_nights_after: short_gap_nights_before,
+    };
+
+    let mut movable: Vec<&PlannedStay> = stays.iter().filter(|s| s.movable).collect();
+    movable.sort_by(|a, b| {
+        a.checkin
+            .cmp(&b.checkin)
+            .then_with(|| b.checkout.cmp(&a.checkout))
+            .then_with(|| a.booking_id.cmp(&b.booking_id))
+    });
+
+    let mut moves = Vec::new();
+    for stay in movable {
+        let Some(own_room) = rooms_by_id.get(&stay.room_id) else {
+            return unchanged;
+        };
+
+        // (short gap nights, is a move, gap before, room id) - smallest wins
+        let best = rooms
+            .iter()
+            .filter(|r| r.id == stay.room_id || can_take(r, own_room, stay.anzahl_gaeste))
+            .filter_map(|r| {
+                let ranges = placed.get(&r.id).map(Vec::as_slice).unwrap_or(&[]);
+                if ranges.iter().any(|&(s, e)| s < stay.checkout && e > stay.checkin) {
+                    return None;
+                }
+                let gap_before = ranges
+                    .iter()
+                    .filter(|&&(_, e)| e <= stay.checkin)
+                    .map(|&(_, e)| (stay.checkin - e).num_days())
+                    .min();
+                let gap_after = ranges
+                    .iter()
+                    .filter(|&&(s, _)| s >= stay.checkout)
+                    .map(|&(s, _)| (s - stay.checkout).num_days())
+                    .min();
+                let cost: i64 = [gap_before, gap_after]
+                    .into_iter()
+                    .flatten()
+                    .filter(|&g| is_short_gap(g))
+                    .sum();
+                Some((cost, r.id != stay.room_id, gap_before.unwrap_or(i64::MAX), r.id))
+            })
+            .min();
+
+        let Some((_, is_move, _, room_id)) = best else {
+            // Earlier stays took every fitting room - keep the current assignment
+            return unchanged;
+        };
+        placed.entry(room_id).or_default().push((stay.checkin, stay.checkout));
+        if is_move {
+            moves.push(RoomMove {
+                booking_id: stay.booking_id,
+                from_room_id: stay.room_id,
+                to_room_id: room_id,
+            });
+        }
+    }
+
+    let short_gap_nights_after = short_gap_nights(&placed);
+    if moves.is_empty() || short_gap_nights_after >= short_gap_nights_before {
+        return unchanged;
+    }
+
+    AssignmentPlan {
+        moves,
+        short_gap_nights_before,
+        short_gap_nights_after,
+    }
+}
+
+/// A booking may move to `target` if it is the same kind of room in the same place,
+/// big enough and offers at least the features (dogs, ground floor) of its current room
+fn can_take(target: &Room, current: &Room, anzahl_gaeste: i32) -> bool {
+    target.ort == current.ort
+        && target.gebaeude_typ == current.gebaeude_typ
+        && target.capacity >= anzahl_gaeste
+        && (target.hunde_erlaubt.unwrap_or(false) || !current.hunde_erlaubt.unwrap_or(false))
+        && (target.erdgeschoss.unwrap_or(false) || !current.erdgeschoss.unwrap_or(false))
+}
+
+fn is_short_gap(nights: i64) -> bool {
+    (1..=SHORT_GAP_NIGHTS).contains(&nights)
+}
+
+/// Sum of the nights in short gaps between consecutive stays of each room
+fn short_gap_nights(ranges_by_room: &HashMap<i32, Vec<(NaiveDate, NaiveDate)>>) -> i64 {
+    ranges_by_room
+        .values()
+        .map(|ranges| {
+            let mut sorted = ranges.clone();
+            sorted.sort();
+            sorted
+                .windows(2)
+                .map(|w| (w[1].0 - w[0].1).num_days())
+                .filter(|&g| is_short_gap(g))
+                .sum::<i64>()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(id: i32, capacity: i32, ort: &str) -> Room {
+        Room {
+            id,
+            name: format!("Zimmer {}", id),
+            gebaeude_typ: "Haus".to_string(),
+            capacity,
+            nebensaison_preis: None,
+            hauptsaison_preis: None,
+            endreinigung: None,
+            basis_belegung: None,
+            aufpreis_erwachsener: None,
+            steuerkategorie: None,
+            ort: ort.to_string(),
+            schluesselcode: None,
+            street_address: None,
+            postal_code: None,
+            city: None,
+            notizen: None,
+            hunde_erlaubt: None,
+            erdgeschoss: None,
+            created_by: None,
+            updated_by: None,
+        }
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 7, day).unwrap()
+    }
+
+    fn criteria(anzahl_gaeste: i32) -> RoomSearchCriteria {
+        RoomSearchCriteria {
+            checkin: "2026-07-10".to_string(),
+            checkout: "2026-07-14".to_string(),
+            anzahl_gaeste,
+            ort: None,
+            gebaeude_typ: None,
+            hund: false,
+            erdgeschoss: false,
+        }
+    }
+
+    fn candidate(room: &Room) -> RoomCandidate<'_> {
+        RoomCandidate { room, previous_checkout: None, next_checkin: None }
+    }
+
+    fn stay(booking_id: i32, room_id: i32, checkin: u32, checkout: u32, movable: bool) -> PlannedStay {
+        PlannedStay {
+            booking_id,
+            room_id,
+            checkin: date(checkin),
+            checkout: date(checkout),
+            anzahl_gaeste: 2,
+            movable,
+        }
+    }
+
+    #[test]
+    fn test_rank_filters_capacity_and_dogs() {
+        let small = room(1, 1, "Fall");
+        let mut dogs = room(2, 2, "Fall");
+        dogs.hunde_erlaubt = Some(true);
+        let no_dogs = room(3, 2, "Fall");
+        let candidates = [candidate(&small), candidate(&dogs), candidate(&no_dogs)];
+
+        let mut c = criteria(2);
+        let ranked = rank_rooms(&c, date(10), date(14), &candidates);
+        assert_eq!(ranked.iter().map(|r| r.room_id).collect::<Vec<_>>(), vec![2, 3]);
+
+        c.hund = true;
+        let ranked = rank_rooms(&c, date(10), date(14), &candidates);
+        assert_eq!(ranked.iter().map(|r| r.room_id).collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_rank_prefers_location_and_fitting_size() {
+        let other_place = room(1, 2, "Lenggries");
+        let big = room(2, 6, "Fall");
+        let fitting = room(3, 2, "Fall");
+        let candidates = [candidate(&other_place), candidate(&big), candidate(&fitting)];
+
+        let mut c = criteria(2);
+        c.ort = Some("Fall".to_string());
+        let ranked = rank_rooms(&c, date(10), date(14), &candidates);
+
+        assert_eq!(ranked.iter().map(|r| r.room_id).collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert!(ranked[2].hinweise.contains(&"Anderer Ort (Lenggries)".to_string()));
+    }
+
+    #[test]
+    fn test_rank_prefers_seamless_over_short_gap() {
+        let a = room(1, 2, "Fall");
+        let b = room(2, 2, "Fall");
+        let candidates = [
+            RoomCandidate { room: &a, previous_checkout: Some(date(8)), next_checkin: None },
+            RoomCandidate { room: &b, previous_checkout: Some(date(10)), next_checkin: None },
+        ];
+
+        let ranked = rank_rooms(&criteria(2), date(10), date(14), &candidates);
+
+        assert_eq!(ranked[0].room_id, 2);
+        assert_eq!(ranked[0].gap_before, Some(0));
+        assert_eq!(ranked[1].gap_before, Some(2));
+        assert!(ranked[0].score > ranked[1].score);
+    }
+
+    #[test]
+    fn test_optimise_closes_short_gap() {
+        let rooms = [room(1, 2, "Fall"), room(2, 2, "Fall")];
+        // Room 1: 1-5 fixed, movable stay 6-10 leaves one night gap
+        // Room 2: 1-6 fixed, the stay fits seamlessly
+        let stays = [stay(10, 1, 1, 5, false), stay(11, 2, 1, 6, false), stay(12, 1, 6, 10, true)];
+
+        let plan = optimise_assignment(&rooms, &stays, &[]);
+
+        assert_eq!(plan.moves, vec![RoomMove { booking_id: 12, from_room_id: 1, to_room_id: 2 }]);
+        assert_eq!(plan.short_gap_nights_before, 1);
+        assert_eq!(plan.short_gap_nights_after, 0);
+    }
+
+    #[test]
+    fn test_optimise_keeps_fixed_stays_and_blocks() {
+        let rooms = [room(1, 2, "Fall"), room(2, 2, "Fall")];
+        // Same gap, but the stay is fixed to its room
+        let stays = [stay(10, 1, 1, 5, false), stay(11, 2, 1, 6, false), stay(12, 1, 6, 10, false)];
+        assert!(optimise_assignment(&rooms, &stays, &[]).moves.is_empty());
+
+        // Movable, but room 2 is blocked during the stay
+        let stays = [stay(10, 1, 1, 5, false), stay(11, 2, 1, 6, false), stay(12, 1, 6, 10, true)];
+        let blocks = [BlockedRange { room_id: 2, start: date(8), end: date(12) }];
+        let plan = optimise_assignment(&rooms, &stays, &blocks);
+        assert!(plan.moves.is_empty());
+        assert_eq!(plan.short_gap_nights_before, plan.short_gap_nights_after);
+    }
+
+    #[test]
+    fn test_optimise_respects_room_features() {
+        let mut dog_room = room(1, 2, "Fall");
+        dog_room.hunde_erlaubt = Some(true);
+        let rooms = [dog_room, room(2, 2, "Fall"), room(3, 2, "Lenggries")];
+        let stays = [stay(10, 1, 1, 5, false), stay(11, 2, 1, 6, false), stay(12, 3, 1, 6, false), stay(13, 1, 6, 10, true)];
+
+        // Guest in the dog room never moves to a room without dogs or to another place
+        assert!(optimise_assignment(&rooms, &stays, &[]).moves.is_empty());
+    }
+
+    #[test]
+    fn test_optimise_without_improvement_proposes_nothing() {
+        let rooms = [room(1, 2, "Fall"), room(2, 2, "Fall")];
+        let stays = [stay(10, 1, 1, 5, false), stay(11, 1, 5, 9, true), stay(12, 2, 3, 8, true)];
+
+        let plan = optimise_assignment(&rooms, &stays, &[]);
+
+        assert!(plan.moves.is_empty());
+        assert_eq!(plan.short_gap_nights_before, 0);
+    }
+}
@@ -0,0 +1,557 @@
+// SEPA direct debit (Lastschrift) - mandates, collection dates and the pain.008 file
+// Pure functions only: no State, no DB access. Which bookings are due and what has been collected
+// comes from SepaRepository; this module validates mandates and writes the file for the bank.
+//
+// Rules:
+// - The first debit on a recurring mandate is FRST, every further one RCUR. A one-off mandate
+//   (OOFF) is used up after its debit
+// - A mandate expires when it has not been used for 36 months (SEPA rulebook)
+// - The collection date must be a TARGET2 business day at least `lead_days` business days ahead
+// - Names and remittance texts are reduced to the SEPA character set (umlauts transliterated)
+
+use chrono::{Datelike, Months, NaiveDate, NaiveDateTime, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+use crate::payments::{self, DepositInput};
+use crate::xrechnung::XmlWriter;
+
+pub const PAIN_008_NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:pain.008.001.08";
+
+/// Months after which an unused mandate expires
+const MANDATE_EXPIRY_MONTHS: u32 = 36;
+
+/// Return reasons (ISO 20022) with German text and whether the mandate can no longer be used
+pub const RETURN_REASONS: &[(&str, &str, bool)] = &[
+    ("AC01", "IBAN fehlerhaft", true),
+    ("AC04", "Konto aufgelöst", true),
+    ("AC06", "Konto gesperrt", true),
+    ("AG01", "Lastschrift auf diesem Konto nicht zulässig", true),
+    ("AM04", "Deckung unzureichend", false),
+    ("AM05", "Doppelte Einreichung", false),
+    ("BE05", "Gläubiger-ID unbekannt", false),
+    ("FF01", "Ungültiges Dateiformat", false),
+    ("MD01", "Kein gültiges Mandat", true),
+    ("MD02", "Mandatsdaten fehlen oder sind fehlerhaft", false),
+    ("MD06", "Rückgabe auf Verlangen des Zahlers", false),
+    ("MD07", "Zahler verstorben", true),
+    ("MS02", "Widerspruch des Zahlers", false),
+    ("MS03", "Grund nicht angegeben", false),
+    ("SL01", "Lastschriftsperre der Bank des Zahlers", false),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Scheme {
+    /// SEPA-Basislastschrift
+    Core,
+    /// SEPA-Firmenlastschrift
+    B2b,
+}
+
+impl Scheme {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scheme::Core => "CORE",
+            Scheme::B2b => "B2B",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "CORE" => Ok(Scheme::Core),
+            "B2B" => Ok(Scheme::B2b),
+            other => Err(format!("Unbekannter Mandatstyp '{}' (CORE oder B2B)", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SequenceType {
+    Frst,
+    Rcur,
+    Ooff,
+}
+
+impl SequenceType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SequenceType::Frst => "FRST",
+            SequenceType::Rcur => "RCUR",
+            SequenceType::Ooff => "OOFF",
+        }
+    }
+}
+
+/// Sequence type of the next debit on a mandate, or why the mandate cannot be used anymore
+pub fn sequence_type(
+    signed: NaiveDate,
+    one_off: bool,
+    last_collection: Option<NaiveDate>,
+    collection_date: NaiveDate,
+) -> Result<SequenceType, String> {
+    if signed > collection_date {
+        return Err("Das Mandat ist erst nach dem Einzugsdatum unterschrieben".to_string());
+    }
+    if one_off && last_collection.is_some() {
+        return Err("Das einmalige Mandat wurde bereits verwendet".to_string());
+    }
+
+    let last_use = last_collection.unwrap_or(signed);
+    if last_use
+        .checked_add_months(Months::new(MANDATE_EXPIRY_MONTHS))
+        .is_some_and(|expiry| expiry <= collection_date)
+    {
+        return Err("Das Mandat ist verfallen (36 Monate nicht genutzt)".to_string());
+    }
+
+    Ok(match (one_off, last_collection) {
+        (true, _) => SequenceType::Ooff,
+        (false, None) => SequenceType::Frst,
+        (false, Some(_)) => SequenceType::Rcur,
+    })
+}
+
+/// Amount to collect from a booking on `collection_date`: everything that is open once the
+/// guest has left, before that only the uncovered deposit requests due by then
+pub fn collectible_amount(
+    outstanding: Money,
+    paid: Money,
+    checkout: NaiveDate,
+    deposits: &[DepositInput],
+    collection_date: NaiveDate,
+) -> Money {
+    if !outstanding.is_positive() {
+        return Money::ZERO;
+    }
+    if checkout <= collection_date {
+        return outstanding;
+    }
+
+    let due_deposits: Money = payments::cover_deposits(deposits, paid, collection_date)
+        .iter()
+        .zip(deposits)
+        .filter(|(_, deposit)| deposit.due_date <= collection_date)
+        .map(|(coverage, deposit)| deposit.amount - coverage.covered)
+        .sum();
+    due_deposits.min(outstanding)
+}
+
+/// Earliest collection date for a file handed in today
+pub fn earliest_collection_date(today: NaiveDate, lead_days: u32) -> NaiveDate {
+    let mut date = today;
+    let mut remaining = lead_days.max(1);
+    while remaining > 0 {
+        date = date.succ_opt().expect("date in range");
+        if is_business_day(date) {
+            remaining -= 1;
+        }
+    }
+    date
+}
+
+/// TARGET2 business day: no weekend, New Year, Good Friday, Easter Monday, 1 May, 25/26 December
+pub fn is_business_day(date: NaiveDate) -> bool {
+    if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+        return false;
+    }
+    if matches!((date.month(), date.day()), (1, 1) | (5, 1) | (12, 25) | (12, 26)) {
+        return false;
+    }
+    let easter = easter_sunday(date.year());
+    date != easter - chrono::Duration::days(2) && date != easter + chrono::Duration::days(1)
+}
+
+/// Easter Sunday (anonymous Gregorian algorithm)
+fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).expect("valid easter date")
+}
+
+// ============================================================================
+// VALIDATION
+// ============================================================================
+
+/// IBAN without spaces in upper case, if the check digits are correct
+pub fn normalize_iban(iban: &str) -> Result<String, String> {
+    let iban: String = iban.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    let valid_format = (15..=34).contains(&iban.len())
+        && iban.chars().take(2).all(|c| c.is_ascii_uppercase())
+        && iban.chars().skip(2).take(2).all(|c| c.is_ascii_digit())
+        && iban.chars().all(|c| c.is_ascii_alphanumeric());
+    if !valid_format || mod97(&format!("{}{}", &iban[4..], &iban[..4])) != 1 {
+        return Err(format!("Ungültige IBAN: {}", iban));
+    }
+    Ok(iban)
+}
+
+/// BIC in upper case (8 or 11 characters)
+pub fn normalize_bic(bic: &str) -> Result<String, String> {
+    let bic = bic.trim().to_uppercase();
+    let valid = (bic.len() == 8 || bic.len() == 11)
+        && bic.chars().take(6).all(|c| c.is_ascii_uppercase())
+        && bic.chars().skip(6).all(|c| c.is_ascii_alphanumeric());
+    if !valid {
+        return Err(format!("Ungültige BIC: {}", bic));
+    }
+    Ok(bic)
+}
+
+/// Creditor identifier (Gläubiger-Identifikationsnummer), e.g. DE98ZZZ09999999999. The check
+/// digits cover country and national identifier, the business code (ZZZ) is not checked.
+pub fn normalize_creditor_id(creditor_id: &str) -> Result<String, String> {
+    let id: String = creditor_id.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+    let valid_format = (8..=35).contains(&id.len())
+        && id.chars().take(2).all(|c| c.is_ascii_uppercase())
+        && id.chars().skip(2).take(2).all(|c| c.is_ascii_digit())
+        && id.chars().all(|c| c.is_ascii_alphanumeric());
+    if !valid_format || mod97(&format!("{}{}", &id[7..], &id[..4])) != 1 {
+        return Err(format!("Ungültige Gläubiger-ID: {}", id));
+    }
+    Ok(id)
+}
+
+/// Mandate reference: 1-35 characters, letters, digits and + ? / - : ( ) . , '
+pub fn validate_mandate_reference(reference: &str) -> Result<(), String> {
+    let valid = !reference.is_empty()
+        && reference.chars().count() <= 35
+        && reference.chars().all(|c| c.is_ascii_alphanumeric() || "+?/-:().,'".contains(c));
+    if !valid {
+        return Err(format!(
+            "Ungültige Mandatsreferenz '{}' (max. 35 Zeichen: Buchstaben, Ziffern und + ? / - : ( ) . , ')",
+            reference
+        ));
+    }
+    Ok(())
+}
+
+/// Mod 97 of an alphanumeric string, letters count as 10..35 (IBAN / creditor id check)
+fn mod97(value: &str) -> u32 {
+    value.chars().fold(0, |remainder, c| match c.to_digit(36) {
+        Some(n) if n >= 10 => (remainder * 100 + n) % 97,
+        Some(n) => (remainder * 10 + n) % 97,
+        None => remainder,
+    })
+}
+
+/// Text reduced to the SEPA character set, at most `max` characters
+pub fn sepa_text(text: &str, max: usize) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        match c {
+            'ä' => out.push_str("ae"),
+            'ö' => out.push_str("oe"),
+            'ü' => out.push_str("ue"),
+            'Ä' => out.push_str("Ae"),
+            'Ö' => out.push_str("Oe"),
+            'Ü' => out.push_str("Ue"),
+            'ß' => out.push_str("ss"),
+            '&' => out.push('+'),
+            c if c.is_ascii_alphanumeric() || " /-?:().,'+".contains(c) => out.push(c),
+            _ => out.push(' '),
+        }
+    }
+    let collapsed = out.split_whitespace().collect::<Vec<_>>().join(" ");
+    collapsed.chars().take(max).collect()
+}
+
+/// German text of a return reason code
+pub fn return_reason_text(code: &str) -> Option<&'static str> {
+    RETURN_REASONS.iter().find(|(c, _, _)| *c == code).map(|(_, text, _)| *text)
+}
+
+/// Whether further debits on the mandate are pointless after this return
+pub fn revokes_mandate(code: &str) -> bool {
+    RETURN_REASONS.iter().any(|(c, _, revokes)| *c == code && *revokes)
+}
+
+// ============================================================================
+// PAIN.008
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct Creditor {
+    pub name: String,
+    pub iban: String,
+    pub bic: Option<String>,
+    pub creditor_id: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DirectDebit {
+    pub end_to_end_id: String,
+    pub amount: Money,
+    pub mandate_reference: String,
+    pub mandate_signed: NaiveDate,
+    pub scheme: Scheme,
+    pub sequence: SequenceType,
+    pub debtor_name: String,
+    pub debtor_iban: String,
+    pub debtor_bic: Option<String>,
+    pub remittance: String,
+}
+
+/// pain.008.001.08 file with one payment information block per scheme and sequence type
+pub fn generate_pain008(
+    message_id: &str,
+    created_at: NaiveDateTime,
+    collection_date: NaiveDate,
+    creditor: &Creditor,
+    debits: &[DirectDebit],
+) -> Result<String, String> {
+    if debits.is_empty() {
+        return Err("Keine fälligen Lastschriften".to_string());
+    }
+    let creditor_iban = normalize_iban(&creditor.iban)?;
+    let creditor_bic = creditor.bic.as_deref().filter(|b| !b.trim().is_empty()).map(normalize_bic).transpose()?;
+    let creditor_id = normalize_creditor_id(&creditor.creditor_id)?;
+    for debit in debits {
+        if !debit.amount.is_positive() {
+            return Err(format!("Lastschrift {}: Betrag muss positiv sein", debit.end_to_end_id));
+        }
+        validate_mandate_reference(&debit.mandate_reference)?;
+        normalize_iban(&debit.debtor_iban)?;
+    }
+
+    let mut groups: Vec<((Scheme, SequenceType), Vec<&DirectDebit>)> = Vec::new();
+    for debit in debits {
+        let key = (debit.scheme, debit.sequence);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(debit),
+            None => groups.push((key, vec![debit])),
+        }
+    }
+
+    let total: Money = debits.iter().map(|d| d.amount).sum();
+    let mut xml = XmlWriter::default();
+    xml.out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.open_attrs("Document", &[("xmlns", PAIN_008_NAMESPACE)]);
+    xml.open("CstmrDrctDbtInitn");
+
+    xml.open("GrpHdr");
+    xml.leaf("MsgId", message_id);
+    xml.leaf("CreDtTm", &created_at.format("%Y-%m-%dT%H:%M:%S").to_string());
+    xml.leaf("NbOfTxs", &debits.len().to_string());
+    xml.leaf("CtrlSum", &total.to_string());
+    xml.open("InitgPty");
+    xml.leaf("Nm", &sepa_text(&creditor.name, 70));
+    xml.close("InitgPty");
+    xml.close("GrpHdr");
+
+    for (index, ((scheme, sequence), group)) in groups.iter().enumerate() {
+        let group_total: Money = group.iter().map(|d| d.amount).sum();
+        xml.open("PmtInf");
+        xml.leaf("PmtInfId", &format!("{}-{}", message_id, index + 1));
+        xml.leaf("PmtMtd", "DD");
+        xml.leaf("BtchBookg", "true");
+        xml.leaf("NbOfTxs", &group.len().to_string());
+        xml.leaf("CtrlSum", &group_total.to_string());
+        xml.open("PmtTpInf");
+        xml.open("SvcLvl");
+        xml.leaf("Cd", "SEPA");
+        xml.close("SvcLvl");
+        xml.open("LclInstrm");
+        xml.leaf("Cd", scheme.as_str());
+        xml.close("LclInstrm");
+        xml.leaf("SeqTp", sequence.as_str());
+        xml.close("PmtTpInf");
+        xml.leaf("ReqdColltnDt", &collection_date.format("%Y-%m-%d").to_string());
+        xml.open("Cdtr");
+        xml.leaf("Nm", &sepa_text(&creditor.name, 70));
+        xml.close("Cdtr");
+        write_account(&mut xml, "CdtrAcct", &creditor_iban);
+        write_agent(&mut xml, "CdtrAgt", creditor_bic.as_deref());
+        xml.leaf("ChrgBr", "SLEV");
+        xml.open("CdtrSchmeId");
+        xml.open("Id");
+        xml.open("PrvtId");
+        xml.open("Othr");
+        xml.leaf("Id", &creditor_id);
+        xml.open("SchmeNm");
+        xml.leaf("Prtry", "SEPA");
+        xml.close("SchmeNm");
+        xml.close("Othr");
+        xml.close("PrvtId");
+        xml.close("Id");
+        xml.close("CdtrSchmeId");
+
+        for debit in group {
+            let debtor_bic = debit.debtor_bic.as_deref().filter(|b| !b.trim().is_empty()).map(normalize_bic).transpose()?;
+            xml.open("DrctDbtTxInf");
+            xml.open("PmtId");
+            xml.leaf("EndToEndId", &debit.end_to_end_id);
+            xml.close("PmtId");
+            xml.leaf_attrs("InstdAmt", &[("Ccy", "EUR")], &debit.amount.to_string());
+            xml.open("DrctDbtTx");
+            xml.open("MndtRltdInf");
+            xml.leaf("MndtId", &debit.mandate_reference);
+            xml.leaf("DtOfSgntr", &debit.mandate_signed.format("%Y-%m-%d").to_string());
+            xml.close("MndtRltdInf");
+            xml.close("DrctDbtTx");
+            write_agent(&mut xml, "DbtrAgt", debtor_bic.as_deref());
+            xml.open("Dbtr");
+            xml.leaf("Nm", &sepa_text(&debit.debtor_name, 70));
+            xml.close("Dbtr");
+            write_account(&mut xml, "DbtrAcct", &normalize_iban(&debit.debtor_iban)?);
+            xml.open("RmtInf");
+            xml.leaf("Ustrd", &sepa_text(&debit.remittance, 140));
+            xml.close("RmtInf");
+            xml.close("DrctDbtTxInf");
+        }
+        xml.close("PmtInf");
+    }
+
+    xml.close("CstmrDrctDbtInitn");
+    xml.close("Document");
+    Ok(xml.out)
+}
+
+fn write_account(xml: &mut XmlWriter, tag: &str, iban: &str) {
+    xml.open(tag);
+    xml.open("Id");
+    xml.leaf("IBAN", iban);
+    xml.close("Id");
+    xml.close(tag);
+}
+
+/// Bank by BIC, or NOTPROVIDED (IBAN-only, allowed within the EEA)
+fn write_agent(xml: &mut XmlWriter, tag: &str, bic: Option<&str>) {
+    xml.open(tag);
+    xml.open("FinInstnId");
+    match bic {
+        Some(bic) => xml.leaf("BICFI", bic),
+        None => {
+            xml.open("Othr");
+            xml.leaf("Id", "NOTPROVIDED");
+            xml.close("Othr");
+        }
+    }
+    xml.close("FinInstnId");
+    xml.close(tag);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn iban_bic_and_creditor_id() {
+        assert_eq!(normalize_iban("de89 3704 0044 0532 0130 00").unwrap(), "DE89370400440532013000");
+        assert!(normalize_iban("DE88370400440532013000").is_err());
+        assert!(normalize_iban("DE89").is_err());
+        assert_eq!(normalize_bic("cobadeffxxx").unwrap(), "COBADEFFXXX");
+        assert!(normalize_bic("COBADE").is_err());
+        assert_eq!(normalize_creditor_id("DE98 ZZZ0 9999 9999 99").unwrap(), "DE98ZZZ09999999999");
+        assert!(normalize_creditor_id("DE97ZZZ09999999999").is_err());
+        assert!(validate_mandate_reference("MITGL-2026/0815").is_ok());
+        assert!(validate_mandate_reference("MIT GLIED").is_err());
+        assert!(validate_mandate_reference(&"A".repeat(36)).is_err());
+    }
+
+    #[test]
+    fn sequence_types_and_expiry() {
+        let signed = date(2026, 1, 15);
+        let collect = date(2026, 10, 20);
+        assert_eq!(sequence_type(signed, false, None, collect), Ok(SequenceType::Frst));
+        assert_eq!(sequence_type(signed, false, Some(date(2026, 5, 2)), collect), Ok(SequenceType::Rcur));
+        assert_eq!(sequence_type(signed, true, None, collect), Ok(SequenceType::Ooff));
+        assert!(sequence_type(signed, true, Some(date(2026, 5, 2)), collect).is_err());
+        // Unused for 36 months
+        assert!(sequence_type(date(2023, 10, 20), false, None, collect).is_err());
+        assert!(sequence_type(signed, false, Some(date(2023, 10, 19)), collect).is_err());
+        assert_eq!(sequence_type(signed, false, Some(date(2023, 10, 21)), collect), Ok(SequenceType::Rcur));
+    }
+
+    #[test]
+    fn collection_date_skips_weekends_and_holidays() {
+        // Friday + 2 business days = Tuesday
+        assert_eq!(earliest_collection_date(date(2026, 10, 16), 2), date(2026, 10, 20));
+        // Easter 2027: 26 March Good Friday, 29 March Easter Monday
+        assert_eq!(earliest_collection_date(date(2027, 3, 25), 1), date(2027, 3, 30));
+        assert_eq!(earliest_collection_date(date(2026, 12, 24), 1), date(2026, 12, 28));
+    }
+
+    #[test]
+    fn amount_before_and_after_checkout() {
+        let deposits = vec![
+            DepositInput { id: 1, amount: Money::from_euros(60.0), due_date: date(2026, 10, 1) },
+            DepositInput { id: 2, amount: Money::from_euros(40.0), due_date: date(2026, 11, 1) },
+        ];
+        let outstanding = Money::from_euros(180.0);
+        let paid = Money::from_euros(20.0);
+        // Before checkout: only the rest of the first deposit
+        assert_eq!(
+            collectible_amount(outstanding, paid, date(2026, 12, 3), &deposits, date(2026, 10, 20)),
+            Money::from_euros(40.0)
+        );
+        // After checkout: everything open
+        assert_eq!(collectible_amount(outstanding, paid, date(2026, 10, 18), &deposits, date(2026, 10, 20)), outstanding);
+        assert_eq!(collectible_amount(Money::ZERO, paid, date(2026, 10, 18), &deposits, date(2026, 10, 20)), Money::ZERO);
+    }
+
+    #[test]
+    fn pain008_groups_by_sequence() {
+        let creditor = Creditor {
+            name: "DPolG Stiftung & Service".to_string(),
+            iban: "DE89370400440532013000".to_string(),
+            bic: None,
+            creditor_id: "DE98ZZZ09999999999".to_string(),
+        };
+        let debit = |id: &str, sequence, amount| DirectDebit {
+            end_to_end_id: id.to_string(),
+            amount: Money::from_euros(amount),
+            mandate_reference: format!("M-{}", id),
+            mandate_signed: date(2026, 1, 15),
+            scheme: Scheme::Core,
+            sequence,
+            debtor_name: "Jürgen Müller".to_string(),
+            debtor_iban: "DE89 3704 0044 0532 0130 00".to_string(),
+            debtor_bic: Some("COBADEFFXXX".to_string()),
+            remittance: "Buchung 2026-12".to_string(),
+        };
+        let debits = vec![debit("A1", SequenceType::Frst, 100.0), debit("A2", SequenceType::Rcur, 50.5), debit("A3", SequenceType::Frst, 10.0)];
+        let created = date(2026, 10, 17).and_hms_opt(9, 30, 0).unwrap();
+
+        let xml = generate_pain008("SEPA20261017093000", created, date(2026, 10, 20), &creditor, &debits).unwrap();
+        assert!(xml.contains("<CtrlSum>160.50</CtrlSum>"));
+        assert_eq!(xml.matches("<PmtInf>").count(), 2);
+        assert!(xml.contains("<PmtInfId>SEPA20261017093000-1</PmtInfId>"));
+        assert!(xml.contains("<SeqTp>FRST</SeqTp>"));
+        assert!(xml.contains("<CtrlSum>110.00</CtrlSum>"));
+        assert!(xml.contains("<Id>NOTPROVIDED</Id>"));
+        assert!(xml.contains("<BICFI>COBADEFFXXX</BICFI>"));
+        assert!(xml.contains("<Nm>Juergen Mueller</Nm>"));
+        assert!(xml.contains("<Nm>DPolG Stiftung + Service</Nm>"));
+        assert!(xml.contains("<IBAN>DE89370400440532013000</IBAN>"));
+
+        assert!(generate_pain008("X", created, date(2026, 10, 20), &creditor, &[]).is_err());
+        let invalid = Creditor { creditor_id: "DE00ZZZ09999999999".to_string(), ..creditor };
+        assert!(generate_pain008("X", created, date(2026, 10, 20), &invalid, &debits).is_err());
+    }
+
+    #[test]
+    fn return_reasons() {
+        assert!(revokes_mandate("AC04"));
+        assert!(!revokes_mandate("AM04"));
+        assert_eq!(return_reason_text("MD06"), Some("Rückgabe auf Verlangen des Zahlers"));
+        assert_eq!(return_reason_text("XX99"), None);
+    }
+}
@@ -0,0 +1,103 @@
+// Voucher PDF - printable voucher (Gutschein) in the layout of the invoices (see invoice_pdf.rs)
+// Header band with logo, value, recipient and personal message, the code with a QR code for
+// the front desk, validity and the redemption conditions.
+
+use chrono::NaiveDate;
+
+use crate::database_pg::{CompanySettings, Voucher};
+use crate::invoice_pdf::{self, opt, BG_SECONDARY, BORDER, HEADER, MARGIN, TEXT_SECONDARY, TEXT_TERTIARY};
+use crate::money::Money;
+use crate::native_pdf::{self, Color, Document, Font, A4_HEIGHT, A4_WIDTH};
+
+const CONTENT_WIDTH: f32 = A4_WIDTH - 2.0 * MARGIN;
+
+const CONDITIONS: &str = "Der Gutschein ist übertragbar und kann für Übernachtungen und Leistungen in unseren \
+                          Häusern eingelöst werden, auch in Teilbeträgen. Bitte geben Sie bei der Buchung den \
+                          Gutscheincode an. Eine Barauszahlung ist nicht möglich.";
+
+pub struct VoucherPdf<'a> {
+    pub voucher: &'a Voucher,
+    pub company: &'a CompanySettings,
+}
+
+fn german_date(date: &str) -> String {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+/// Render the voucher as PDF
+pub fn render(data: &VoucherPdf) -> Result<Vec<u8>, String> {
+    let voucher = data.voucher;
+    let center = A4_WIDTH / 2.0;
+    let mut doc = Document::new(A4_WIDTH, A4_HEIGHT);
+
+    let mut y = invoice_pdf::company_header(&mut doc, data.company, HEADER, "Gutschein", &voucher.code);
+
+    // Title, recipient and value
+    y += 80.0;
+    doc.text_centered(center, y, 40.0, Font::Bold, HEADER, "Gutschein");
+    if let Some(recipient) = voucher.recipient_name.as_deref() {
+        y += 34.0;
+        doc.text_centered(center, y, 15.0, Font::Regular, TEXT_SECONDARY, &format!("für {}", recipient));
+    }
+
+    y += 30.0;
+    let box_height = 90.0;
+    doc.fill_rect(MARGIN + 60.0, y, CONTENT_WIDTH - 120.0, box_height, BG_SECONDARY);
+    doc.stroke_rect(MARGIN + 60.0, y, CONTENT_WIDTH - 120.0, box_height, 1.0, BORDER);
+    doc.text_centered(center, y + 28.0, 9.0, Font::Bold, TEXT_TERTIARY, "IM WERT VON");
+    doc.text_centered(center, y + 68.0, 34.0, Font::Bold, Color::BLACK, &voucher.value.format_eur());
+    y += box_height;
+
+    if voucher.remaining < voucher.value {
+        y += 18.0;
+        let rest = format!("Restwert: {}", voucher.remaining.max(Money::ZERO).format_eur());
+        doc.text_centered(center, y, 10.0, Font::Bold, TEXT_SECONDARY, &rest);
+    }
+
+    // Personal message
+    if let Some(message) = voucher.message.as_deref() {
+        y += 22.0;
+        for line in message.lines().flat_map(|l| native_pdf::wrap_text(l, 12.0, Font::Regular, CONTENT_WIDTH - 120.0)) {
+            y += 17.0;
+            doc.text_centered(center, y, 12.0, Font::Regular, Color::BLACK, &line);
+        }
+    }
+
+    // Code with QR code, validity
+    y += 40.0;
+    let qr_size = 96.0;
+    doc.line(MARGIN, y, MARGIN + CONTENT_WIDTH, y, 0.5, BORDER);
+    doc.qr_code(&voucher.code, MARGIN + CONTENT_WIDTH - qr_size, y + 16.0, qr_size)?;
+    doc.text(MARGIN, y + 30.0, 8.5, Font::Bold, TEXT_TERTIARY, "GUTSCHEINCODE");
+    doc.text(MARGIN, y + 54.0, 22.0, Font::Bold, Color::BLACK, &voucher.code);
+    doc.text(MARGIN, y + 78.0, 10.0, Font::Regular, TEXT_SECONDARY, &format!("Ausgestellt am {}", german_date(&voucher.issued_at)));
+    doc.text(MARGIN, y + 94.0, 10.0, Font::Bold, Color::BLACK, &format!("Gültig bis {}", german_date(&voucher.expires_at)));
+    y += qr_size + 32.0;
+
+    // Conditions and contact
+    doc.line(MARGIN, y, MARGIN + CONTENT_WIDTH, y, 0.5, BORDER);
+    y += 6.0;
+    for line in native_pdf::wrap_text(CONDITIONS, 8.5, Font::Regular, CONTENT_WIDTH) {
+        y += 12.0;
+        doc.text(MARGIN, y, 8.5, Font::Regular, TEXT_SECONDARY, &line);
+    }
+    let company = data.company;
+    let contact = [opt(&company.phone), opt(&company.email)]
+        .into_iter()
+        .filter(|value| !value.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(" | ");
+    if !contact.is_empty() {
+        y += 18.0;
+        doc.text(MARGIN, y, 8.5, Font::Bold, Color::BLACK, &format!("Buchung und Einlösung: {}", contact));
+    }
+
+    let bottom = A4_HEIGHT - 40.0;
+    for (i, line) in native_pdf::wrap_text(invoice_pdf::FOOTER_TEXT, 6.5, Font::Regular, CONTENT_WIDTH).iter().enumerate() {
+        doc.text_centered(center, bottom + i as f32 * 8.0, 6.5, Font::Regular, TEXT_TERTIARY, line);
+    }
+
+    Ok(doc.finish())
+}
@@ -0,0 +1,289 @@
+// Vouchers (Gutscheine) and expiry of guest credit
+// Pure functions only: no State, no DB access. VoucherRepository and GuestCreditRepository load
+// the vouchers, redemptions and credit transactions and ask this module what is allowed.
+//
+// Rules:
+// - Codes look like GS-XXXX-XXXX: seven random characters and a check character, from an
+//   alphabet without characters that are easily confused (0/O, 1/I, 8/B). Typos are detected
+//   before the database is asked
+// - A voucher is valid until 31.12. of the third year after the sale (regular limitation
+//   period, §§ 195, 199 BGB), a shorter date can be set when it is sold
+// - A voucher can be redeemed in parts, never for more than its rest value and never for
+//   more than the booking still has outstanding
+// - Credit that is used is taken from the credit that expires first. What is left of a credit
+//   after its expiry date expires; credit without a date never expires
+
+use chrono::{Datelike, NaiveDate};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+
+pub const CODE_PREFIX: &str = "GS";
+
+/// 31 characters (a prime) - the weighted check character detects every single wrong
+/// character and every swap of two neighbouring characters
+const CODE_ALPHABET: &[u8; 31] = b"ABCDEFGHJKLMNPQRSTUVWXYZ2345679";
+
+/// Random characters of a code (the check character follows)
+const CODE_LENGTH: usize = 7;
+
+/// Years after the year of sale until a voucher expires
+const DEFAULT_VALIDITY_YEARS: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VoucherStatus {
+    /// Rest value left, not expired
+    Aktiv,
+    /// Fully redeemed
+    Eingeloest,
+    /// Expiry date passed with a rest value
+    Abgelaufen,
+    /// Cancelled (e.g. purchase reversed)
+    Storniert,
+}
+
+impl VoucherStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VoucherStatus::Aktiv => "aktiv",
+            VoucherStatus::Eingeloest => "eingeloest",
+            VoucherStatus::Abgelaufen => "abgelaufen",
+            VoucherStatus::Storniert => "storniert",
+        }
+    }
+}
+
+/// Credit transaction that can expire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CreditLot {
+    pub amount: Money,
+    /// Valid until (incl.), None = does not expire
+    pub expires_at: Option<NaiveDate>,
+}
+
+fn check_character(chars: &[u8]) -> u8 {
+    let sum: usize = chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let value = CODE_ALPHABET.iter().position(|a| a == c).unwrap_or(0);
+            (i + 1) * value
+        })
+        .sum();
+    CODE_ALPHABET[sum % CODE_ALPHABET.len()]
+}
+
+fn format_code(chars: &[u8]) -> String {
+    let chars = String::from_utf8_lossy(chars);
+    format!("{}-{}-{}", CODE_PREFIX, &chars[..4], &chars[4..])
+}
+
+/// New random voucher code (uniqueness is checked by the database)
+pub fn generate_code<R: Rng>(rng: &mut R) -> String {
+    let mut chars: Vec<u8> = (0..CODE_LENGTH)
+        .map(|_| CODE_ALPHABET[rng.gen_range(0..CODE_ALPHABET.len())])
+        .collect();
+    chars.push(check_character(&chars));
+    format_code(&chars)
+}
+
+/// Code as typed in (lower case, blanks, without dashes or prefix) in its canonical form
+pub fn normalize_code(input: &str) -> Result<String, String> {
+    let cleaned: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_uppercase();
+    // Only a complete code has the prefix - the random part may start with "GS" as well
+    let chars = match cleaned.strip_prefix(CODE_PREFIX) {
+        Some(rest) if rest.len() == CODE_LENGTH + 1 => rest,
+        _ => &cleaned,
+    }
+    .as_bytes();
+
+    if chars.len() != CODE_LENGTH + 1 || !chars.iter().all(|c| CODE_ALPHABET.contains(c)) {
+        return Err(format!("'{}' ist kein gültiger Gutscheincode (Format GS-XXXX-XXXX)", input.trim()));
+    }
+    if check_character(&chars[..CODE_LENGTH]) != chars[CODE_LENGTH] {
+        return Err(format!("Gutscheincode '{}' ist fehlerhaft - bitte Eingabe prüfen", input.trim()));
+    }
+
+    Ok(format_code(chars))
+}
+
+/// Default expiry of a voucher sold on `issued_at`
+pub fn default_expiry(issued_at: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(issued_at.year() + DEFAULT_VALIDITY_YEARS, 12, 31).unwrap_or(issued_at)
+}
+
+pub fn status(remaining: Money, expires_at: NaiveDate, cancelled: bool, today: NaiveDate) -> VoucherStatus {
+    if cancelled {
+        VoucherStatus::Storniert
+    } else if !remaining.is_positive() {
+        VoucherStatus::Eingeloest
+    } else if today > expires_at {
+        VoucherStatus::Abgelaufen
+    } else {
+        VoucherStatus::Aktiv
+    }
+}
+
+/// Amount to redeem against a booking. Without `requested` as much as possible.
+pub fn redemption_amount(
+    status: VoucherStatus,
+    remaining: Money,
+    outstanding: Money,
+    requested: Option<Money>,
+) -> Result<Money, String> {
+    match status {
+        VoucherStatus::Aktiv => {}
+        VoucherStatus::Eingeloest => return Err("Der Gutschein ist bereits vollständig eingelöst".to_string()),
+        VoucherStatus::Abgelaufen => return Err("Der Gutschein ist abgelaufen".to_string()),
+        VoucherStatus::Storniert => return Err("Der Gutschein wurde storniert".to_string()),
+    }
+    if !outstanding.is_positive() {
+        return Err("Für diese Buchung ist kein Betrag offen".to_string());
+    }
+
+    let amount = requested.unwrap_or_else(|| remaining.min(outstanding));
+    if !amount.is_positive() {
+        return Err("Der Betrag muss größer als 0 sein".to_string());
+    }
+    if amount > remaining {
+        return Err(format!("Der Gutschein hat nur noch einen Wert von {}", remaining.format_eur()));
+    }
+    if amount > outstanding {
+        return Err(format!("Für die Buchung sind nur noch {} offen", outstanding.format_eur()));
+    }
+
+    Ok(amount)
+}
+
+/// Credit that has to be booked as expired on `today`: what is left of the expired lots after
+/// `used` (debits minus refunds) was taken from the lots in order of their expiry dates,
+/// minus what has been booked as expired before
+pub fn expired_credit(lots: &[CreditLot], used: Money, already_expired: Money, today: NaiveDate) -> Money {
+    let mut lots: Vec<&CreditLot> = lots.iter().collect();
+    // Lots without a date last
+    lots.sort_by_key(|lot| (lot.expires_at.is_none(), lot.expires_at));
+
+    let mut unused = used.max(Money::ZERO);
+    let mut expired = Money::ZERO;
+    for lot in lots {
+        let taken = unused.min(lot.amount);
+        unused -= taken;
+        if lot.expires_at.is_some_and(|date| today > date) {
+            expired += lot.amount - taken;
+        }
+    }
+
+    (expired - already_expired).max(Money::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn eur(euros: f64) -> Money {
+        Money::from_euros(euros)
+    }
+
+    fn lot(euros: f64, expires_at: Option<NaiveDate>) -> CreditLot {
+        CreditLot { amount: eur(euros), expires_at }
+    }
+
+    #[test]
+    fn generated_codes_are_valid() {
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let code = generate_code(&mut rng);
+            assert_eq!(code.len(), 12);
+            assert!(code.starts_with("GS-"));
+            assert_eq!(normalize_code(&code), Ok(code.clone()));
+        }
+    }
+
+    #[test]
+    fn typed_codes_are_normalized() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let code = generate_code(&mut rng);
+        let typed = format!(" {} ", code[3..].replace('-', " ").to_lowercase());
+        assert_eq!(normalize_code(&typed), Ok(code));
+        assert!(normalize_code("GS-1234").is_err());
+        assert!(normalize_code("GS-ABCD-EFG0").is_err());
+    }
+
+    #[test]
+    fn typos_are_detected() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let code = generate_code(&mut rng);
+        let chars: Vec<char> = code.chars().collect();
+
+        // Every single wrong character
+        for position in [3, 4, 5, 6, 8, 9, 10, 11] {
+            for replacement in CODE_ALPHABET.iter().map(|&c| c as char).filter(|&c| c != chars[position]) {
+                let mut typo = chars.clone();
+                typo[position] = replacement;
+                assert!(normalize_code(&typo.iter().collect::<String>()).is_err());
+            }
+        }
+
+        // Swapped neighbours
+        for position in [3, 4, 5, 8, 9, 10] {
+            if chars[position] != chars[position + 1] {
+                let mut typo = chars.clone();
+                typo.swap(position, position + 1);
+                assert!(normalize_code(&typo.iter().collect::<String>()).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn default_expiry_is_end_of_third_year() {
+        assert_eq!(default_expiry(date(2026, 12, 10)), date(2029, 12, 31));
+        assert_eq!(default_expiry(date(2027, 1, 2)), date(2030, 12, 31));
+    }
+
+    #[test]
+    fn voucher_status() {
+        let expiry = date(2029, 12, 31);
+        assert_eq!(status(eur(50.0), expiry, false, date(2029, 12, 31)), VoucherStatus::Aktiv);
+        assert_eq!(status(eur(50.0), expiry, false, date(2030, 1, 1)), VoucherStatus::Abgelaufen);
+        assert_eq!(status(Money::ZERO, expiry, false, date(2030, 1, 1)), VoucherStatus::Eingeloest);
+        assert_eq!(status(eur(50.0), expiry, true, date(2027, 1, 1)), VoucherStatus::Storniert);
+    }
+
+    #[test]
+    fn redemption_is_limited_by_rest_value_and_outstanding() {
+        let active = VoucherStatus::Aktiv;
+        assert_eq!(redemption_amount(active, eur(100.0), eur(240.0), None), Ok(eur(100.0)));
+        assert_eq!(redemption_amount(active, eur(100.0), eur(60.0), None), Ok(eur(60.0)));
+        assert_eq!(redemption_amount(active, eur(100.0), eur(240.0), Some(eur(30.0))), Ok(eur(30.0)));
+        assert!(redemption_amount(active, eur(100.0), eur(240.0), Some(eur(120.0))).is_err());
+        assert!(redemption_amount(active, eur(100.0), eur(60.0), Some(eur(80.0))).is_err());
+        assert!(redemption_amount(active, eur(100.0), Money::ZERO, None).is_err());
+        assert!(redemption_amount(VoucherStatus::Abgelaufen, eur(100.0), eur(60.0), None).is_err());
+    }
+
+    #[test]
+    fn unused_credit_expires_first_expiring_used_first() {
+        let lots = [lot(50.0, Some(date(2026, 12, 31))), lot(50.0, Some(date(2027, 12, 31))), lot(20.0, None)];
+
+        assert_eq!(expired_credit(&lots, Money::ZERO, Money::ZERO, date(2026, 12, 31)), Money::ZERO);
+        assert_eq!(expired_credit(&lots, eur(30.0), Money::ZERO, date(2027, 1, 1)), eur(20.0));
+        // Already booked as expired
+        assert_eq!(expired_credit(&lots, eur(30.0), eur(20.0), date(2027, 1, 1)), Money::ZERO);
+        // Used after the first lot expired: taken from the second one
+        assert_eq!(expired_credit(&lots, eur(40.0), eur(20.0), date(2028, 1, 1)), eur(40.0));
+        // Credit without a date never expires
+        assert_eq!(expired_credit(&lots, eur(100.0), Money::ZERO, date(2040, 1, 1)), Money::ZERO);
+    }
+}
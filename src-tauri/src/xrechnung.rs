@@ -0,0 +1,757 @@
+// XRechnung Export - e-invoice (UN/CEFACT CII, XRechnung 3.0) of a booking invoice
+// Pure functions only: the XML is generated from the same `invoice::Invoice` as the
+// HTML/PDF invoice, parties come from company/payment settings, guest and the
+// optional external payment recipient.
+//
+// Amounts: invoice positions are gross, XRechnung works with net amounts.
+// Per tax category the net amount before discounts is split over its lines pro rata
+// (Money::allocate), the discount share becomes a document level allowance, so that
+// line nets - allowances = taxable amount of `invoice.tax_lines` to the cent.
+// Kurtaxe is a pass-through levy and is reported as VAT exempt (category "E").
+//
+// Mandatory XRechnung fields that are missing are returned as a list of German
+// messages instead of an incomplete XML.
+
+use chrono::NaiveDate;
+
+use crate::database_pg::{CompanySettings, Guest, PaymentRecipient, PaymentSettings};
+use crate::invoice::Invoice;
+use crate::money::Money;
+use crate::pricing;
+
+const GUIDELINE_ID: &str = "urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0";
+const BUSINESS_PROCESS_ID: &str = "urn:fdc:peppol.eu:2017:poacc:billing:01:1.0";
+const EXEMPTION_REASON_KURTAXE: &str = "Kurtaxe - durchlaufender Posten, nicht steuerbar";
+
+/// Seller or buyer of the invoice
+#[derive(Debug, Clone, Default)]
+pub struct TradeParty {
+    pub name: String,
+    pub contact_name: Option<String>,
+    pub street: Option<String>,
+    pub postcode: Option<String>,
+    pub city: Option<String>,
+    /// Country name ("Deutschland") or ISO 3166 code ("DE"), empty = Germany
+    pub country: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    /// USt-IdNr. ("DE…") or Steuernummer
+    pub tax_id: Option<String>,
+}
+
+/// Everything besides the invoice itself that goes into the XRechnung
+#[derive(Debug, Clone, Default)]
+pub struct XRechnungParties {
+    pub seller: TradeParty,
+    pub buyer: TradeParty,
+    /// Leitweg-ID of the public-sector payer (BT-10 buyer reference, BT-49 electronic address)
+    pub leitweg_id: Option<String>,
+    /// Debitorenkonto (BT-46 buyer identifier)
+    pub buyer_id: Option<String>,
+    /// Kostenstelle (BT-19 buyer accounting reference)
+    pub cost_center: Option<String>,
+    pub iban: Option<String>,
+    pub bic: Option<String>,
+    pub account_holder: Option<String>,
+}
+
+impl XRechnungParties {
+    /// Seller from the company settings, buyer is the external payment recipient if set, else the guest
+    pub fn new(
+        company: &CompanySettings,
+        payment: &PaymentSettings,
+        guest: &Guest,
+        payment_recipient: Option<&PaymentRecipient>,
+    ) -> Self {
+        let seller = TradeParty {
+            name: company.company_name.clone(),
+            contact_name: company.ceo_name.clone().or_else(|| Some(company.company_name.clone())),
+            street: company.street_address.clone(),
+            postcode: company.plz.clone(),
+            city: company.city.clone(),
+            country: company.country.clone(),
+            phone: company.phone.clone(),
+            email: company.email.clone(),
+            tax_id: company.tax_id.clone(),
+        };
+
+        let buyer = match payment_recipient {
+            Some(recipient) => TradeParty {
+                name: recipient.company.clone().unwrap_or_else(|| recipient.name.clone()),
+                contact_name: recipient.contact_person.clone().or_else(|| Some(recipient.name.clone())),
+                street: recipient.street.clone(),
+                postcode: recipient.plz.clone(),
+                city: recipient.city.clone(),
+                country: recipient.country.clone(),
+                ..Default::default()
+            },
+            None => TradeParty {
+                name: format!("{} {}", guest.vorname, guest.nachname),
+                street: guest.strasse.clone(),
+                postcode: guest.plz.clone(),
+                city: guest.ort.clone(),
+                country: guest.land.clone(),
+                email: guest.rechnungs_email.clone().or_else(|| Some(guest.email.clone())),
+                ..Default::default()
+            },
+        };
+
+        XRechnungParties {
+            seller,
+            buyer,
+            leitweg_id: guest.leitweg_id.clone(),
+            buyer_id: guest.debitorenkonto.clone(),
+            cost_center: guest.kostenstelle.clone(),
+            iban: payment.iban.clone(),
+            bic: payment.bic.clone(),
+            account_holder: payment.account_holder.clone(),
+        }
+    }
+}
+
+/// Check the XRechnung mandatory fields. Empty list = complete.
+pub fn validate(invoice: &Invoice, parties: &XRechnungParties) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    match text(&parties.leitweg_id) {
+        None => errors.push("Leitweg-ID (BT-10) fehlt - bitte beim Gast hinterlegen".to_string()),
+        Some(id) if !is_valid_leitweg_id(id) => errors.push(format!(
+            "Leitweg-ID '{}' hat kein gültiges Format (z.B. 991-12345-67)",
+            id
+        )),
+        Some(_) => {}
+    }
+
+    let seller = &parties.seller;
+    if seller.name.trim().is_empty() {
+        errors.push("Firmenname (BT-27) fehlt in den Firmeneinstellungen".to_string());
+    }
+    if text(&seller.postcode).is_none() || text(&seller.city).is_none() {
+        errors.push("PLZ und Ort der Firma (BT-38, BT-37) fehlen in den Firmeneinstellungen".to_string());
+    }
+    if text(&seller.phone).is_none() {
+        errors.push("Telefonnummer der Firma (BT-42) fehlt in den Firmeneinstellungen".to_string());
+    }
+    if text(&seller.email).is_none() {
+        errors.push("E-Mail der Firma (BT-34, BT-43) fehlt in den Firmeneinstellungen".to_string());
+    }
+    if text(&seller.tax_id).is_none() {
+        errors.push("Steuernummer oder USt-IdNr. (BT-31/BT-32) fehlt in den Firmeneinstellungen".to_string());
+    }
+    if country_code(&seller.country).is_none() {
+        errors.push(format!("Unbekanntes Land der Firma: {}", seller.country.as_deref().unwrap_or("")));
+    }
+
+    let buyer = &parties.buyer;
+    if buyer.name.trim().is_empty() {
+        errors.push("Name des Rechnungsempfängers (BT-44) fehlt".to_string());
+    }
+    if text(&buyer.postcode).is_none() || text(&buyer.city).is_none() {
+        errors.push("PLZ und Ort des Rechnungsempfängers (BT-53, BT-52) fehlen".to_string());
+    }
+    if country_code(&buyer.country).is_none() {
+        errors.push(format!(
+            "Unbekanntes Land des Rechnungsempfängers: {}",
+            buyer.country.as_deref().unwrap_or("")
+        ));
+    }
+
+    if text(&parties.iban).is_none() {
+        errors.push("IBAN (BT-84) fehlt in den Zahlungseinstellungen".to_string());
+    }
+    if invoice.number.is_empty() {
+        errors.push("Die Rechnung ist noch nicht ausgestellt (keine Rechnungsnummer)".to_string());
+    }
+    if invoice.lines.is_empty() {
+        errors.push("Die Rechnung enthält keine Positionen".to_string());
+    }
+
+    errors
+}
+
+/// Generate the XRechnung XML (CII syntax). Missing mandatory fields are returned as errors.
+pub fn generate_xrechnung(
+    invoice: &Invoice,
+    parties: &XRechnungParties,
+    reservierungsnummer: &str,
+) -> Result<String, Vec<String>> {
+    let errors = validate(invoice, parties);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let amounts = net_amounts(invoice);
+    let leitweg_id = text(&parties.leitweg_id).unwrap_or_default();
+    let payment_reference = format!("{} / {}", invoice.number, reservierungsnummer);
+
+    let mut xml = XmlWriter::default();
+    xml.out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.open_attrs(
+        "rsm:CrossIndustryInvoice",
+        &[
+            ("xmlns:rsm", "urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100"),
+            ("xmlns:ram", "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:100"),
+            ("xmlns:qdt", "urn:un:unece:uncefact:data:standard:QualifiedDataType:100"),
+            ("xmlns:udt", "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:100"),
+        ],
+    );
+
+    xml.open("rsm:ExchangedDocumentContext");
+    xml.open("ram:BusinessProcessSpecifiedDocumentContextParameter");
+    xml.leaf("ram:ID", BUSINESS_PROCESS_ID);
+    xml.close("ram:BusinessProcessSpecifiedDocumentContextParameter");
+    xml.open("ram:GuidelineSpecifiedDocumentContextParameter");
+    xml.leaf("ram:ID", GUIDELINE_ID);
+    xml.close("ram:GuidelineSpecifiedDocumentContextParameter");
+    xml.close("rsm:ExchangedDocumentContext");
+
+    xml.open("rsm:ExchangedDocument");
+    xml.leaf("ram:ID", &invoice.number);
+    xml.leaf("ram:TypeCode", "380");
+    xml.date("ram:IssueDateTime", invoice.issue_date);
+    xml.open("ram:IncludedNote");
+    xml.leaf(
+        "ram:Content",
+        &format!(
+            "Buchung {}, Aufenthalt {} - {}",
+            reservierungsnummer,
+            german_date(&invoice.checkin_date),
+            german_date(&invoice.checkout_date)
+        ),
+    );
+    xml.close("ram:IncludedNote");
+    xml.close("rsm:ExchangedDocument");
+
+    xml.open("rsm:SupplyChainTradeTransaction");
+
+    // Positions
+    for (line, net) in invoice.lines.iter().zip(&amounts.line_nets) {
+        let quantity = line.quantity.max(1);
+        xml.open("ram:IncludedSupplyChainTradeLineItem");
+        xml.open("ram:AssociatedDocumentLineDocument");
+        xml.leaf("ram:LineID", &line.position.to_string());
+        xml.close("ram:AssociatedDocumentLineDocument");
+        xml.open("ram:SpecifiedTradeProduct");
+        xml.leaf("ram:Name", &line.description);
+        if !line.details.is_empty() {
+            xml.leaf("ram:Description", &line.details);
+        }
+        xml.close("ram:SpecifiedTradeProduct");
+        xml.open("ram:SpecifiedLineTradeAgreement");
+        xml.open("ram:NetPriceProductTradePrice");
+        // Net price per unit if it is a whole cent amount, else price per billed quantity
+        if net.cents() % quantity == 0 {
+            xml.leaf("ram:ChargeAmount", &Money::from_cents(net.cents() / quantity).to_string());
+        } else {
+            xml.leaf("ram:ChargeAmount", &net.to_string());
+            xml.leaf_attrs("ram:BasisQuantity", &[("unitCode", &line.unit_code)], &quantity.to_string());
+        }
+        xml.close("ram:NetPriceProductTradePrice");
+        xml.close("ram:SpecifiedLineTradeAgreement");
+        xml.open("ram:SpecifiedLineTradeDelivery");
+        xml.leaf_attrs("ram:BilledQuantity", &[("unitCode", &line.unit_code)], &quantity.to_string());
+        xml.close("ram:SpecifiedLineTradeDelivery");
+        xml.open("ram:SpecifiedLineTradeSettlement");
+        xml.open("ram:ApplicableTradeTax");
+        xml.leaf("ram:TypeCode", "VAT");
+        xml.leaf("ram:CategoryCode", tax_category_code(&line.tax_category, line.tax_rate));
+        xml.leaf("ram:RateApplicablePercent", &format!("{:.2}", line.tax_rate));
+        xml.close("ram:ApplicableTradeTax");
+        xml.open("ram:SpecifiedTradeSettlementLineMonetarySummation");
+        xml.leaf("ram:LineTotalAmount", &net.to_string());
+        xml.close("ram:SpecifiedTradeSettlementLineMonetarySummation");
+        xml.close("ram:SpecifiedLineTradeSettlement");
+        xml.close("ram:IncludedSupplyChainTradeLineItem");
+    }
+
+    // Parties
+    xml.open("ram:ApplicableHeaderTradeAgreement");
+    xml.leaf("ram:BuyerReference", leitweg_id);
+    write_party(&mut xml, "ram:SellerTradeParty", &parties.seller, None, |xml| {
+        if let Some(email) = text(&parties.seller.email) {
+            xml.open("ram:URIUniversalCommunication");
+            xml.leaf_attrs("ram:URIID", &[("schemeID", "EM")], email);
+            xml.close("ram:URIUniversalCommunication");
+        }
+        if let Some(tax_id) = text(&parties.seller.tax_id) {
+            // USt-IdNr. starts with the country code, everything else is a Steuernummer
+            let scheme = if tax_id.chars().take(2).all(|c| c.is_ascii_alphabetic()) { "VA" } else { "FC" };
+            xml.open("ram:SpecifiedTaxRegistration");
+            xml.leaf_attrs("ram:ID", &[("schemeID", scheme)], tax_id);
+            xml.close("ram:SpecifiedTaxRegistration");
+        }
+    });
+    write_party(&mut xml, "ram:BuyerTradeParty", &parties.buyer, text(&parties.buyer_id), |xml| {
+        xml.open("ram:URIUniversalCommunication");
+        xml.leaf_attrs("ram:URIID", &[("schemeID", "0204")], leitweg_id);
+        xml.close("ram:URIUniversalCommunication");
+    });
+    xml.close("ram:ApplicableHeaderTradeAgreement");
+
+    xml.out.push_str(&"  ".repeat(xml.depth));
+    xml.out.push_str("<ram:ApplicableHeaderTradeDelivery/>\n");
+
+    // Settlement
+    xml.open("ram:ApplicableHeaderTradeSettlement");
+    xml.leaf("ram:PaymentReference", &payment_reference);
+    xml.leaf("ram:InvoiceCurrencyCode", "EUR");
+    xml.open("ram:SpecifiedTradeSettlementPaymentMeans");
+    xml.leaf("ram:TypeCode", "58");
+    xml.open("ram:PayeePartyCreditorFinancialAccount");
+    xml.leaf("ram:IBANID", &text(&parties.iban).unwrap_or_default().replace(' ', ""));
+    if let Some(holder) = text(&parties.account_holder) {
+        xml.leaf("ram:AccountName", holder);
+    }
+    xml.close("ram:PayeePartyCreditorFinancialAccount");
+    if let Some(bic) = text(&parties.bic) {
+        xml.open("ram:PayeeSpecifiedCreditorFinancialInstitution");
+        xml.leaf("ram:BICID", bic);
+        xml.close("ram:PayeeSpecifiedCreditorFinancialInstitution");
+    }
+    xml.close("ram:SpecifiedTradeSettlementPaymentMeans");
+
+    for group in &amounts.breakdown {
+        xml.open("ram:ApplicableTradeTax");
+        xml.leaf("ram:CalculatedAmount", &group.tax.to_string());
+        xml.leaf("ram:TypeCode", "VAT");
+        if group.code == "E" {
+            xml.leaf("ram:ExemptionReason", EXEMPTION_REASON_KURTAXE);
+        }
+        xml.leaf("ram:BasisAmount", &group.basis.to_string());
+        xml.leaf("ram:CategoryCode", group.code);
+        xml.leaf("ram:RateApplicablePercent", &format!("{:.2}", group.rate));
+        xml.close("ram:ApplicableTradeTax");
+    }
+
+    if let (Ok(start), Ok(end)) = (parse_date(&invoice.checkin_date), parse_date(&invoice.checkout_date)) {
+        xml.open("ram:BillingSpecifiedPeriod");
+        xml.date("ram:StartDateTime", start);
+        xml.date("ram:EndDateTime", end);
+        xml.close("ram:BillingSpecifiedPeriod");
+    }
+
+    let discount_reason = if invoice.discounts.is_empty() {
+        "Rabatt".to_string()
+    } else {
+        invoice.discounts.iter().map(|d| d.name.as_str()).collect::<Vec<_>>().join(", ")
+    };
+    for allowance in &amounts.allowances {
+        xml.open("ram:SpecifiedTradeAllowanceCharge");
+        xml.open("ram:ChargeIndicator");
+        xml.leaf("udt:Indicator", if allowance.amount.is_negative() { "true" } else { "false" });
+        xml.close("ram:ChargeIndicator");
+        xml.leaf("ram:ActualAmount", &allowance.amount.abs().to_string());
+        xml.leaf("ram:Reason", &discount_reason);
+        xml.open("ram:CategoryTradeTax");
+        xml.leaf("ram:TypeCode", "VAT");
+        xml.leaf("ram:CategoryCode", allowance.code);
+        xml.leaf("ram:RateApplicablePercent", &format!("{:.2}", allowance.rate));
+        xml.close("ram:CategoryTradeTax");
+        xml.close("ram:SpecifiedTradeAllowanceCharge");
+    }
+
+    xml.open("ram:SpecifiedTradePaymentTerms");
+    xml.leaf(
+        "ram:Description",
+        &format!("Zahlbar bis {} ohne Abzug", invoice.due_date.format("%d.%m.%Y")),
+    );
+    xml.date("ram:DueDateDateTime", invoice.due_date);
+    xml.close("ram:SpecifiedTradePaymentTerms");
+
+    let line_total: Money = amounts.line_nets.iter().copied().sum();
+    let allowance_total: Money = amounts.allowances.iter().map(|a| a.amount.max(Money::ZERO)).sum();
+    let charge_total: Money = amounts.allowances.iter().map(|a| (-a.amount).max(Money::ZERO)).sum();
+    let basis_total = line_total - allowance_total + charge_total;
+    let tax_total: Money = amounts.breakdown.iter().map(|g| g.tax).sum();
+    let grand_total = basis_total + tax_total;
+
+    xml.open("ram:SpecifiedTradeSettlementHeaderMonetarySummation");
+    xml.leaf("ram:LineTotalAmount", &line_total.to_string());
+    xml.leaf("ram:ChargeTotalAmount", &charge_total.to_string());
+    xml.leaf("ram:AllowanceTotalAmount", &allowance_total.to_string());
+    xml.leaf("ram:TaxBasisTotalAmount", &basis_total.to_string());
+    xml.leaf_attrs("ram:TaxTotalAmount", &[("currencyID", "EUR")], &tax_total.to_string());
+    xml.leaf("ram:GrandTotalAmount", &grand_total.to_string());
+    // Guest credit and deposits received are both prepaid amounts (BT-113)
+    let prepaid = invoice.credit_used + invoice.paid;
+    xml.leaf("ram:TotalPrepaidAmount", &prepaid.to_string());
+    xml.leaf("ram:DuePayableAmount", &(grand_total - prepaid).to_string());
+    xml.close("ram:SpecifiedTradeSettlementHeaderMonetarySummation");
+
+    if let Some(cost_center) = text(&parties.cost_center) {
+        xml.open("ram:ReceivableSpecifiedTradeAccountingAccount");
+        xml.leaf("ram:ID", cost_center);
+        xml.close("ram:ReceivableSpecifiedTradeAccountingAccount");
+    }
+
+    xml.close("ram:ApplicableHeaderTradeSettlement");
+    xml.close("rsm:SupplyChainTradeTransaction");
+    xml.close("rsm:CrossIndustryInvoice");
+
+    Ok(xml.out)
+}
+
+// ============================================================================
+// NET AMOUNTS
+// ============================================================================
+
+struct Allowance {
+    code: &'static str,
+    rate: f64,
+    /// Net discount share, negative = charge (rounding only)
+    amount: Money,
+}
+
+struct TaxGroup {
+    code: &'static str,
+    rate: f64,
+    basis: Money,
+    tax: Money,
+}
+
+struct NetAmounts {
+    line_nets: Vec<Money>,
+    allowances: Vec<Allowance>,
+    breakdown: Vec<TaxGroup>,
+}
+
+fn net_amounts(invoice: &Invoice) -> NetAmounts {
+    let mut line_nets = vec![Money::ZERO; invoice.lines.len()];
+    let mut allowances = Vec::new();
+    let mut breakdown: Vec<TaxGroup> = Vec::new();
+
+    let mut categories: Vec<&str> = Vec::new();
+    for line in &invoice.lines {
+        if !categories.contains(&line.tax_category.as_str()) {
+            categories.push(&line.tax_category);
+        }
+    }
+
+    for category in categories {
+        let indices: Vec<usize> = (0..invoice.lines.len())
+            .filter(|&i| invoice.lines[i].tax_category == category)
+            .collect();
+        let weights: Vec<Money> = indices.iter().map(|&i| invoice.lines[i].total).collect();
+        let gross: Money = weights.iter().copied().sum();
+        let rate = invoice.lines[indices[0]].tax_rate;
+        let code = tax_category_code(category, rate);
+
+        // Taxable amount and VAT after discounts, as printed on the invoice
+        let (net_after, tax) = invoice
+            .tax_lines
+            .iter()
+            .find(|t| t.category == category)
+            .map(|t| (t.net, t.tax))
+            .unwrap_or((Money::ZERO, Money::ZERO));
+
+        let net_before = gross - gross.included_tax(rate);
+        for (i, net) in indices.iter().zip(net_before.allocate(&weights)) {
+            line_nets[*i] = net;
+        }
+
+        let discount = net_before - net_after;
+        if !discount.is_zero() {
+            allowances.push(Allowance { code, rate, amount: discount });
+        }
+
+        match breakdown.iter_mut().find(|g| g.code == code && g.rate == rate) {
+            Some(group) => {
+                group.basis += net_after;
+                group.tax += tax;
+            }
+            None => breakdown.push(TaxGroup { code, rate, basis: net_after, tax }),
+        }
+    }
+
+    NetAmounts { line_nets, allowances, breakdown }
+}
+
+/// UNTDID 5305 VAT category code
+fn tax_category_code(category: &str, rate: f64) -> &'static str {
+    if category == pricing::TAX_EXEMPT {
+        "E"
+    } else if rate == 0.0 {
+        "Z"
+    } else {
+        "S"
+    }
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+fn write_party(
+    xml: &mut XmlWriter,
+    tag: &str,
+    party: &TradeParty,
+    id: Option<&str>,
+    communication: impl FnOnce(&mut XmlWriter),
+) {
+    xml.open(tag);
+    if let Some(id) = id {
+        xml.leaf("ram:ID", id);
+    }
+    xml.leaf("ram:Name", party.name.trim());
+    if text(&party.contact_name).is_some() || text(&party.phone).is_some() || text(&party.email).is_some() {
+        xml.open("ram:DefinedTradeContact");
+        if let Some(name) = text(&party.contact_name) {
+            xml.leaf("ram:PersonName", name);
+        }
+        if let Some(phone) = text(&party.phone) {
+            xml.open("ram:TelephoneUniversalCommunication");
+            xml.leaf("ram:CompleteNumber", phone);
+            xml.close("ram:TelephoneUniversalCommunication");
+        }
+        if let Some(email) = text(&party.email) {
+            xml.open("ram:EmailURIUniversalCommunication");
+            xml.leaf("ram:URIID", email);
+            xml.close("ram:EmailURIUniversalCommunication");
+        }
+        xml.close("ram:DefinedTradeContact");
+    }
+    xml.open("ram:PostalTradeAddress");
+    xml.leaf("ram:PostcodeCode", text(&party.postcode).unwrap_or_default());
+    if let Some(street) = text(&party.street) {
+        xml.leaf("ram:LineOne", street);
+    }
+    xml.leaf("ram:CityName", text(&party.city).unwrap_or_default());
+    xml.leaf("ram:CountryID", country_code(&party.country).unwrap_or("DE"));
+    xml.close("ram:PostalTradeAddress");
+    communication(xml);
+    xml.close(tag);
+}
+
+/// Trimmed, non-empty text of an optional field
+fn text(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// ISO 3166-1 alpha-2 code of a country name. Empty = Germany (default of the app).
+fn country_code(country: &Option<String>) -> Option<&'static str> {
+    let Some(country) = text(country) else {
+        return Some("DE");
+    };
+    match country.to_lowercase().as_str() {
+        "de" | "deutschland" | "germany" => Some("DE"),
+        "at" | "österreich" | "austria" => Some("AT"),
+        "ch" | "schweiz" | "switzerland" => Some("CH"),
+        "nl" | "niederlande" | "netherlands" => Some("NL"),
+        "be" | "belgien" | "belgium" => Some("BE"),
+        "lu" | "luxemburg" | "luxembourg" => Some("LU"),
+        "fr" | "frankreich" | "france" => Some("FR"),
+        "it" | "italien" | "italy" => Some("IT"),
+        "dk" | "dänemark" | "denmark" => Some("DK"),
+        "pl" | "polen" | "poland" => Some("PL"),
+        "cz" | "tschechien" | "czech republic" => Some("CZ"),
+        _ => None,
+    }
+}
+
+/// Leitweg-ID: "<Grobadressierung>-<Feinadressierung>-<Prüfziffer>", the middle part is optional
+fn is_valid_leitweg_id(id: &str) -> bool {
+    let parts: Vec<&str> = id.split('-').collect();
+    let coarse = parts[0];
+    let check = parts[parts.len() - 1];
+    let coarse_ok = (2..=12).contains(&coarse.len()) && coarse.chars().all(|c| c.is_ascii_digit());
+    let check_ok = check.len() == 2 && check.chars().all(|c| c.is_ascii_digit());
+    let fine_ok = match parts.len() {
+        2 => true,
+        3 => (1..=30).contains(&parts[1].len()) && parts[1].chars().all(|c| c.is_ascii_alphanumeric()),
+        _ => false,
+    };
+    coarse_ok && check_ok && fine_ok
+}
+
+fn parse_date(date: &str) -> chrono::ParseResult<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+}
+
+fn german_date(date: &str) -> String {
+    parse_date(date)
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Minimal indenting XML writer, values and attributes are escaped (also used by sepa.rs)
+#[derive(Default)]
+pub(crate) struct XmlWriter {
+    pub(crate) out: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    pub(crate) fn open(&mut self, tag: &str) {
+        self.open_attrs(tag, &[]);
+    }
+
+    pub(crate) fn open_attrs(&mut self, tag: &str, attrs: &[(&str, &str)]) {
+        self.out.push_str(&format!("{}<{}{}>\n", "  ".repeat(self.depth), tag, attributes(attrs)));
+        self.depth += 1;
+    }
+
+    pub(crate) fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.out.push_str(&format!("{}</{}>\n", "  ".repeat(self.depth), tag));
+    }
+
+    pub(crate) fn leaf(&mut self, tag: &str, value: &str) {
+        self.leaf_attrs(tag, &[], value);
+    }
+
+    pub(crate) fn leaf_attrs(&mut self, tag: &str, attrs: &[(&str, &str)], value: &str) {
+        self.out.push_str(&format!(
+            "{}<{}{}>{}</{}>\n",
+            "  ".repeat(self.depth),
+            tag,
+            attributes(attrs),
+            escape_xml(value),
+            tag
+        ));
+    }
+
+    /// Date element in format 102 (YYYYMMDD)
+    fn date(&mut self, tag: &str, date: NaiveDate) {
+        self.open(tag);
+        self.leaf_attrs("udt:DateTimeString", &[("format", "102")], &date.format("%Y%m%d").to_string());
+        self.close(tag);
+    }
+}
+
+fn attributes(attrs: &[(&str, &str)]) -> String {
+    attrs
+        .iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, escape_xml(value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invoice::{InvoiceDiscount, InvoiceLine, UNIT_LUMP_SUM, UNIT_NIGHT};
+    use crate::pricing::{default_pricing_settings, TaxPosition};
+
+    fn line(position: i32, description: &str, quantity: i64, unit_code: &str, total: f64, category: &str) -> InvoiceLine {
+        let settings = default_pricing_settings();
+        let total = Money::from_euros(total);
+        InvoiceLine {
+            position,
+            description: description.to_string(),
+            details: String::new(),
+            quantity,
+            unit_code: unit_code.to_string(),
+            quantity_label: String::new(),
+            unit_price: total.divide(quantity),
+            total,
+            tax_category: category.to_string(),
+            tax_rate: pricing::tax_rate(&settings, category),
+        }
+    }
+
+    fn invoice() -> Invoice {
+        let settings = default_pricing_settings();
+        let lines = vec![
+            line(1, "Übernachtung Haus", 3, UNIT_NIGHT, 300.0, pricing::TAX_ACCOMMODATION),
+            line(2, "Parkplatz", 1, UNIT_LUMP_SUM, 20.0, pricing::TAX_STANDARD),
+            line(3, "Kurtaxe Fall", 6, UNIT_NIGHT, 6.0, pricing::TAX_EXEMPT),
+        ];
+        let positions: Vec<TaxPosition> = lines
+            .iter()
+            .map(|l| TaxPosition {
+                category: l.tax_category.clone(),
+                gross: l.total,
+                discountable: l.position == 1,
+            })
+            .collect();
+        let discounts_total = Money::from_euros(30.0);
+        let date = NaiveDate::from_ymd_opt(2026, 3, 4).unwrap();
+
+        Invoice {
+            number: "RE-2026-0042".to_string(),
+            issue_date: date,
+            due_date: date + chrono::Duration::days(14),
+            checkin_date: "2026-03-01".to_string(),
+            checkout_date: "2026-03-04".to_string(),
+            nights: 3,
+            tax_lines: pricing::tax_summary(&settings, &positions, discounts_total),
+            lines,
+            subtotal: Money::from_euros(326.0),
+            discounts: vec![InvoiceDiscount { name: "Stammgast".to_string(), amount: discounts_total }],
+            discounts_total,
+            kurtaxe: Money::from_euros(6.0),
+            total: Money::from_euros(296.0),
+            credit_used: Money::from_euros(10.0),
+            paid: Money::ZERO,
+            amount_due: Money::from_euros(286.0),
+        }
+    }
+
+    fn parties() -> XRechnungParties {
+        XRechnungParties {
+            seller: TradeParty {
+                name: "Gästehaus Müller & Söhne".to_string(),
+                postcode: Some("12345".to_string()),
+                city: Some("Fall".to_string()),
+                phone: Some("0123 456".to_string()),
+                email: Some("info@example.org".to_string()),
+                tax_id: Some("DE123456789".to_string()),
+                ..Default::default()
+            },
+            buyer: TradeParty {
+                name: "Polizeipräsidium".to_string(),
+                postcode: Some("80331".to_string()),
+                city: Some("München".to_string()),
+                ..Default::default()
+            },
+            leitweg_id: Some("991-12345-67".to_string()),
+            iban: Some("DE02 1203 0000 0000 2020 51".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn element<'a>(xml: &'a str, tag: &str) -> &'a str {
+        let open = format!("<{}>", tag);
+        let start = xml.rfind(&open).unwrap_or_else(|| panic!("{} missing", tag)) + open.len();
+        let end = start + xml[start..].find('<').unwrap();
+        &xml[start..end]
+    }
+
+    #[test]
+    fn test_totals_match_invoice() {
+        let xml = generate_xrechnung(&invoice(), &parties(), "R-2026-001").unwrap();
+
+        // 300 € (7%) - 30 € discount, 20 € (19%), 6 € Kurtaxe (exempt)
+        assert_eq!(element(&xml, "ram:LineTotalAmount"), "303.18");
+        assert_eq!(element(&xml, "ram:AllowanceTotalAmount"), "28.03");
+        assert_eq!(element(&xml, "ram:TaxBasisTotalAmount"), "275.15");
+        assert_eq!(element(&xml, "ram:GrandTotalAmount"), "296.00");
+        assert_eq!(element(&xml, "ram:DuePayableAmount"), "286.00");
+        assert_eq!(element(&xml, "ram:BuyerReference"), "991-12345-67");
+        assert!(xml.contains("Gästehaus Müller &amp; Söhne"));
+        assert!(xml.contains("<ram:CategoryCode>E</ram:CategoryCode>"));
+    }
+
+    #[test]
+    fn test_missing_mandatory_fields() {
+        let mut parties = parties();
+        parties.leitweg_id = None;
+        parties.seller.phone = Some(" ".to_string());
+
+        let errors = generate_xrechnung(&invoice(), &parties, "R-2026-001").unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("Leitweg-ID"));
+        assert!(errors[1].contains("Telefonnummer"));
+
+        parties.leitweg_id = Some("12345".to_string());
+        assert!(validate(&invoice(), &parties)[0].contains("kein gültiges Format"));
+    }
+}
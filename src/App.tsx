@@ -28,8 +28,10 @@ import CleaningSync from './components/CleaningSync';
 import ErrorBoundary from './components/ErrorBoundary';
 import ReminderDropdown from './components/Reminders/ReminderDropdown';
 import RemindersView from './components/Reminders/RemindersView';
+import PaymentsView from './components/Payments/PaymentsView';
+import PlanningView from './components/Planning/PlanningView';
 import DevTools from './components/DevTools/ComprehensiveDevTools';
-import { Calendar, Hotel, UserPlus, LayoutDashboard, CalendarCheck, Users, Settings, Mail, Briefcase, TrendingUp, Cloud, Bell } from 'lucide-react';
+import { Calendar, Hotel, UserPlus, LayoutDashboard, CalendarCheck, Users, Settings, Mail, Briefcase, TrendingUp, Cloud, Bell, Landmark, Layers } from 'lucide-react';
 import loadingAnimation from './loading-animation.json';
 import appIcon from './assets/app-icon.png';
 import { formatDateShort } from './utils/dateFormatting';
@@ -68,7 +70,7 @@ interface BookingWithDetails {
   guest: Guest;
 }
 
-type Tab = 'dashboard' | 'bookings' | 'guests' | 'rooms' | 'emails' | 'templates' | 'statistics' | 'cleaning' | 'reminders';
+type Tab = 'dashboard' | 'bookings' | 'guests' | 'rooms' | 'emails' | 'templates' | 'statistics' | 'cleaning' | 'reminders' | 'payments' | 'planning';
 
 interface DbChangeEvent {
   table: string;
@@ -408,6 +410,8 @@ function AppContent() {
     { id: 'guests' as Tab, label: 'Gäste', icon: Users },
     { id: 'rooms' as Tab, label: 'Zimmer', icon: Hotel },
     { id: 'reminders' as Tab, label: 'Erinnerungen', icon: Bell },
+    { id: 'payments' as Tab, label: 'Zahlungen', icon: Landmark },
+    { id: 'planning' as Tab, label: 'Planung', icon: Layers },
     { id: 'templates' as Tab, label: 'Services & Rabatte', icon: Briefcase },
     { id: 'emails' as Tab, label: 'Email-Verlauf', icon: Mail },
     { id: 'statistics' as Tab, label: 'Statistiken', icon: TrendingUp },
@@ -649,6 +653,26 @@ function AppContent() {
             }}
           />
         )}
+        {activeTab === 'payments' && (
+          <PaymentsView
+            onNavigateToBooking={(bookingId) => {
+              setSidebarBookingId(bookingId);
+              setSidebarMode('view');
+              setSidebarPrefillData(undefined);
+              setShowBookingSidebar(true);
+            }}
+          />
+        )}
+        {activeTab === 'planning' && (
+          <PlanningView
+            onNavigateToBooking={(bookingId) => {
+              setSidebarBookingId(bookingId);
+              setSidebarMode('view');
+              setSidebarPrefillData(undefined);
+              setShowBookingSidebar(true);
+            }}
+          />
+        )}
         {activeTab === 'templates' && <TemplatesManagement />}
         {activeTab === 'emails' && <EmailHistoryView />}
         {activeTab === 'cleaning' && <CleaningSync />}
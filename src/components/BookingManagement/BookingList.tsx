@@ -385,6 +385,8 @@ export default function BookingList() {
               className={SELECT_STYLES}
             >
               <option value="all">Alle Status</option>
+              <option value="anfrage">Anfrage</option>
+              <option value="optioniert">Optioniert</option>
               <option value="reserviert">Reserviert</option>
               <option value="bestaetigt">Bestätigt</option>
               <option value="eingecheckt">Eingecheckt</option>
@@ -6,7 +6,7 @@ import {
   UserCheck, ShoppingBag, Percent, Edit2, XCircle, FileText,
   Clock, Home, Send, CheckCircle, AlertCircle, Euro, Download,
   FolderOpen, Eye, AlertTriangle, MessageSquare, Search, History,
-  ChevronDown, ChevronUp, Info, Plus, Trash2, Bookmark, Loader2, Briefcase, Wallet
+  ChevronDown, ChevronUp, Info, Plus, Trash2, Bookmark, Loader2, Briefcase, Wallet, Pin, ArrowRightLeft
 } from 'lucide-react';
 import { formatServicePrice, formatCalculatedServicePrice } from '../../utils/priceFormatting';
 import { usePriceCalculation, ServiceInput as PriceServiceInput, DiscountInput as PriceDiscountInput } from '../../hooks/usePriceCalculation';
@@ -25,6 +25,11 @@ import CompanionSelector from './CompanionSelector';
 import CancellationConfirmDialog from './CancellationConfirmDialog';
 import GuestDialog from '../GuestManagement/GuestDialog';
 import BookingDatePicker from './BookingDatePicker';
+import RoomFinder from './RoomFinder';
+import StaySplitDialog from './StaySplitDialog';
+import PaymentLedger from './PaymentLedger';
+import DunningHistory from './DunningHistory';
+import IssuedInvoices from './IssuedInvoices';
 
 interface BookingSidebarProps {
   bookingId: number | null;
@@ -85,12 +90,18 @@ interface Booking {
   rechnung_versendet_an?: string | null;
   mahnung_gesendet_am?: string | null;
   ist_stiftungsfall?: boolean;
+  geschaeftsreise?: boolean | null;
   payment_recipient_id?: number | null;
   putzplan_checkout_date?: string | null;
   created_at?: string;
   updated_at?: string;
   created_by?: string;
   updated_by?: string;
+  option_expires_at?: string | null;
+  zimmer_fixiert?: boolean | null;
+  split_parent_id?: number | null;
+  booking_group_id?: number | null;
+  allotment_id?: number | null;
   room?: Room;
   guest?: Guest;
 }
@@ -171,6 +182,7 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
   const [invoicePdfs, setInvoicePdfs] = useState<InvoicePdfInfo[]>([]);
   const [paymentRecipients, setPaymentRecipients] = useState<PaymentRecipient[]>([]);
   const [currentPaymentRecipient, setCurrentPaymentRecipient] = useState<PaymentRecipient | null>(null);
+  const [optionExpiry, setOptionExpiry] = useState('');
 
   // Form State (for Edit/Create mode)
   const [formData, setFormData] = useState<Booking>({
@@ -326,6 +338,8 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
             discountType: d.discount_type as 'fixed' | 'percent',
             value: d.discount_value,
           })),
+          anzahlGaeste: formData.anzahl_gaeste,
+          companionBirthdates: accompanyingGuests.map(g => g.geburtsdatum || null),
         };
       }
     }
@@ -339,6 +353,7 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
 
   // Dialog State
   const [showCancelDialog, setShowCancelDialog] = useState(false);
+  const [showSplitDialog, setShowSplitDialog] = useState(false);
   const [showSuccessDialog, setShowSuccessDialog] = useState<{ show: boolean; message: string }>({ show: false, message: '' });
   const [showErrorDialog, setShowErrorDialog] = useState<{ show: boolean; message: string }>({ show: false, message: '' });
   const [showEmailDialog, setShowEmailDialog] = useState(false);
@@ -828,6 +843,14 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
 
         await updateBooking(booking.id, updatePayload);
 
+        // Geschäftsreise (Kurtaxe-Befreiung) wird separat gespeichert und aktualisiert die Kurtaxe
+        if (!!formData.geschaeftsreise !== !!booking.geschaeftsreise) {
+          await invoke('set_booking_geschaeftsreise_pg', {
+            bookingId: booking.id,
+            geschaeftsreise: !!formData.geschaeftsreise,
+          });
+        }
+
         // Sync accompanying guests - parallel execution for performance
         try {
           const existingGuests = await invoke<AccompanyingGuest[]>('get_accompanying_guests_by_booking_pg', { bookingId: booking.id });
@@ -958,10 +981,7 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
         setMode('view');
         await loadBookingDetails();
       } else {
-        // Create new booking
-        // Temporäre Reservierungsnummer - wird nach Erstellung mit JAHR-ID aktualisiert
-        const year = new Date().getFullYear();
-        const tempReservierungsnummer = `${year}-TEMP`;
+        // Create new booking (Reservierungsnummer vergibt das Backend)
         const nights = priceBreakdown?.nights || 1;
         const basePrice = priceBreakdown?.basePrice || 0;
         const servicesTotal = priceBreakdown?.servicesTotal || 0;
@@ -971,7 +991,6 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
         const bookingData = {
           roomId: formData.room_id,
           guestId: formData.guest_id,
-          reservierungsnummer: tempReservierungsnummer,
           checkinDate: formData.checkin_date,
           checkoutDate: formData.checkout_date,
           anzahlGaeste: formData.anzahl_gaeste,
@@ -995,29 +1014,6 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
 
         const result = await createBooking(bookingData) as any;
 
-        // Update reservierungsnummer with JAHR-BUCHUNGSID format
-        if (result.id) {
-          const finalReservierungsnummer = `${year}-${result.id}`;
-          await invoke('update_booking_pg', {
-            id: result.id,
-            roomId: formData.room_id,
-            guestId: formData.guest_id,
-            reservierungsnummer: finalReservierungsnummer,
-            checkinDate: formData.checkin_date,
-            checkoutDate: formData.checkout_date,
-            anzahlGaeste: formData.anzahl_gaeste,
-            status: formData.ist_stiftungsfall ? 'stiftungsfall' : formData.status,
-            gesamtpreis: totalPrice,
-            bemerkungen: formData.bemerkungen || null,
-            istStiftungsfall: formData.ist_stiftungsfall || false,
-            grundpreis: basePrice,
-            servicesPreis: servicesTotal,
-            rabattPreis: discountsTotal,
-            anzahlNaechte: nights,
-          });
-          console.log(`✅ Reservierungsnummer aktualisiert: ${finalReservierungsnummer}`);
-        }
-
         // Save accompanying guests - parallel for performance
         if (accompanyingGuests.length > 0 && result.id) {
           await Promise.all(
@@ -1057,6 +1053,17 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
 
         // Save discounts
         if (discounts.length > 0 && result.id) {
+          // Der Mitgliederrabatt wurde beim Anlegen automatisch gebucht - ein eigener Mitgliederrabatt ersetzt ihn
+          const isMemberDiscount = (name: string) =>
+            name.toLowerCase().includes('dpolg') || name.toLowerCase().includes('mitglieder');
+          if (discounts.some((d: Discount) => isMemberDiscount(d.discount_name))) {
+            const autoDiscounts = await invoke<Discount[]>('get_discounts_by_booking_pg', { bookingId: result.id });
+            await Promise.all(
+              autoDiscounts
+                .filter(d => isMemberDiscount(d.discount_name))
+                .map(d => invoke('delete_discount_pg', { id: d.id }))
+            );
+          }
           for (const discount of discounts) {
             if (discount.template_id) {
               await invoke('link_discount_template_to_booking_command', {
@@ -1078,24 +1085,17 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
           }
         }
 
-        // Save DPolG-Auto-Rabatt aus priceBreakdown (nicht im discounts-State enthalten)
-        if (priceBreakdown?.discounts && result.id) {
-          for (const autoDiscount of priceBreakdown.discounts) {
-            const isDpolg = autoDiscount.name.toLowerCase().includes('dpolg') ||
-                            autoDiscount.name.toLowerCase().includes('mitglieder');
-            const alreadyInDiscounts = discounts.some((d: Discount) =>
-              d.discount_name.toLowerCase().includes('dpolg') ||
-              d.discount_name.toLowerCase().includes('mitglieder')
-            );
-            if (isDpolg && !alreadyInDiscounts) {
-              await invoke('create_discount_pg', {
-                bookingId: result.id,
-                discountName: autoDiscount.name,
-                discountType: autoDiscount.discountType,
-                discountValue: autoDiscount.originalValue,
-                calculatedAmount: autoDiscount.calculatedAmount,
-              });
-            }
+        if (formData.geschaeftsreise && result.id) {
+          await invoke('set_booking_geschaeftsreise_pg', { bookingId: result.id, geschaeftsreise: true });
+        }
+
+        // Preise mit Begleitpersonen, Services und Rabatten neu berechnen (Belegungszuschlag,
+        // Kurtaxe, Mitgliederrabatt - der Mitgliederrabatt wird im Backend gebucht)
+        if (result.id) {
+          try {
+            await invoke('recalculate_booking_prices_pg', { bookingId: result.id });
+          } catch (err) {
+            console.error('❌ Preisberechnung nach dem Anlegen fehlgeschlagen:', err);
           }
         }
 
@@ -1159,6 +1159,7 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
 
   const getStatusBadge = (status: string) => {
     const styles = {
+      optioniert: 'bg-orange-100 text-orange-700 border-orange-200',
       reserviert: 'bg-blue-100 text-blue-700 border-blue-200',
       bestaetigt: 'bg-emerald-100 text-emerald-700 border-emerald-200',
       eingecheckt: 'bg-purple-100 text-purple-700 border-purple-200',
@@ -1235,6 +1236,130 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
                 </div>
               </div>
 
+              {/* Option: Haltefrist */}
+              {booking.status === 'optioniert' && (
+                <div className="border-2 border-dashed border-orange-300 rounded-lg p-4 bg-orange-50">
+                  <div className="flex items-start gap-3">
+                    <Clock className="w-5 h-5 text-orange-600 flex-shrink-0 mt-0.5" />
+                    <div className="flex-1">
+                      <p className="text-sm font-semibold text-orange-900">
+                        Option bis {booking.option_expires_at
+                          ? format(new Date(booking.option_expires_at.replace(' ', 'T')), 'dd.MM.yyyy HH:mm', { locale: de })
+                          : '-'}
+                      </p>
+                      <p className="text-xs text-orange-700 mt-1">
+                        Ohne Bestätigung wird das Zimmer danach automatisch freigegeben und der Gast informiert.
+                      </p>
+                      <div className="flex items-center gap-2 mt-3">
+                        <input
+                          type="datetime-local"
+                          value={optionExpiry}
+                          onChange={(e) => setOptionExpiry(e.target.value)}
+                          className="px-2 py-1 border border-orange-300 rounded text-sm"
+                        />
+                        <button
+                          disabled={!optionExpiry}
+                          onClick={async () => {
+                            try {
+                              const updated = await invoke<Booking>('update_option_expiry_pg', {
+                                id: booking.id,
+                                expiresAt: optionExpiry,
+                              });
+                              setBooking({ ...booking, option_expires_at: updated.option_expires_at });
+                              setOptionExpiry('');
+                              await reloadBooking(booking.id!);
+                              toast.success('Option verlängert');
+                            } catch (error) {
+                              toast.error(`Fehler beim Verlängern der Option: ${error}`);
+                            }
+                          }}
+                          className="px-3 py-1 bg-orange-600 hover:bg-orange-700 disabled:opacity-50 text-white rounded text-sm font-semibold"
+                        >
+                          Verlängern
+                        </button>
+                      </div>
+                    </div>
+                  </div>
+                </div>
+              )}
+
+              {/* Zimmer fixiert (Zimmer-Optimierer verschiebt die Buchung nicht) */}
+              {booking.status !== 'storniert' && booking.status !== 'ausgecheckt' && (
+                <label className="flex items-center gap-3 px-4 py-3 border border-slate-200 rounded-lg bg-slate-50 cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={booking.zimmer_fixiert ?? false}
+                    onChange={async (e) => {
+                      const fixed = e.target.checked;
+                      try {
+                        await invoke<Booking>('set_booking_room_fixed_pg', { id: booking.id, fixed });
+                        setBooking({ ...booking, zimmer_fixiert: fixed });
+                        await reloadBooking(booking.id!);
+                      } catch (error) {
+                        toast.error(`Fehler beim Speichern: ${error}`);
+                      }
+                    }}
+                    className="w-4 h-4 rounded"
+                  />
+                  <Pin className="w-4 h-4 text-slate-600" />
+                  <span className="text-sm text-slate-700">
+                    Zimmer fixiert <span className="text-slate-500">(dem Gast zugesagt, wird nicht automatisch umgebucht)</span>
+                  </span>
+                </label>
+              )}
+
+              {/* Zimmerwechsel: Abschnitte des Aufenthalts */}
+              {(() => {
+                const masterId = booking.split_parent_id ?? booking.id;
+                const segments = allBookings
+                  .filter((b) => b.id === masterId || b.split_parent_id === masterId)
+                  .sort((a, b) => a.checkin_date.localeCompare(b.checkin_date));
+                const canSplit = booking.status !== 'storniert' && booking.status !== 'ausgecheckt' && !booking.booking_group_id && !booking.allotment_id;
+                if (segments.length < 2 && !canSplit) return null;
+                return (
+                  <div className="px-4 py-3 border border-slate-200 rounded-lg bg-slate-50 space-y-2">
+                    <div className="flex items-center justify-between">
+                      <span className="flex items-center gap-2 text-sm font-semibold text-slate-700">
+                        <ArrowRightLeft className="w-4 h-4 text-slate-600" />
+                        Zimmerwechsel
+                      </span>
+                      {canSplit && (
+                        <button
+                          onClick={() => setShowSplitDialog(true)}
+                          className="px-3 py-1 bg-blue-600 hover:bg-blue-700 text-white rounded text-sm font-semibold"
+                        >
+                          Aufenthalt teilen
+                        </button>
+                      )}
+                    </div>
+                    {segments.length > 1 && (
+                      <div className="space-y-1">
+                        {segments.map((segment) => (
+                          <div
+                            key={segment.id}
+                            className={`flex justify-between text-sm ${segment.id === booking.id ? 'font-semibold text-slate-800' : 'text-slate-600'}`}
+                          >
+                            <span>Zimmer {rooms.find((r) => r.id === segment.room_id)?.name ?? segment.room_id}</span>
+                            <span>
+                              {formatDate(segment.checkin_date)} - {formatDate(segment.checkout_date)}
+                            </span>
+                          </div>
+                        ))}
+                        <p className="text-xs text-slate-500">Eine gemeinsame Rechnung für alle Abschnitte</p>
+                      </div>
+                    )}
+                  </div>
+                );
+              })()}
+
+              {/* Geschäftsreise Info */}
+              {booking.geschaeftsreise && (
+                <div className="flex items-center gap-2 border border-slate-200 rounded-lg px-4 py-2 bg-slate-50 text-sm text-slate-700">
+                  <Briefcase className="w-4 h-4 text-slate-500" />
+                  Geschäftsreise - von der Kurtaxe befreit
+                </div>
+              )}
+
               {/* Stiftungsfall Warning */}
               {booking.ist_stiftungsfall && (
                 <div className="border-2 border-amber-300 rounded-lg p-4 bg-gradient-to-br from-amber-50 to-orange-50">
@@ -1661,6 +1786,10 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
                       )}
                     </div>
                   )}
+                  {booking.id && (
+                    <PaymentLedger bookingId={booking.id} onChange={loadBookingDetails} refreshKey={booking.updated_at} />
+                  )}
+                  {booking.id && <DunningHistory bookingId={booking.id} refreshKey={booking.updated_at} />}
                 </div>
               </div>
 
@@ -1694,6 +1823,24 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
                       <Download className="w-4 h-4" />
                       {generatingPdf ? 'Erstellt...' : 'PDF erstellen'}
                     </button>
+                    <button
+                      onClick={async () => {
+                        if (!booking.id) return;
+                        const toastId = toast.loading('XRechnung wird erstellt...');
+                        try {
+                          await invoke<string>('generate_xrechnung_command', { bookingId: booking.id });
+                          toast.success('XRechnung erfolgreich erstellt!', { id: toastId, duration: 4000 });
+                        } catch (error) {
+                          toast.dismiss(toastId);
+                          setShowErrorDialog({ show: true, message: `${error}` });
+                        }
+                      }}
+                      className="flex items-center gap-2 px-3 py-1.5 bg-slate-600 hover:bg-slate-700 text-white rounded-lg font-semibold transition-colors text-sm"
+                      title="E-Rechnung (XRechnung, CII) für öffentliche Auftraggeber"
+                    >
+                      <FileText className="w-4 h-4" />
+                      XRechnung
+                    </button>
                     <button
                       onClick={async () => {
                         try {
@@ -1753,6 +1900,17 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
                     ))}
                   </div>
                 )}
+
+                {booking.id && (
+                  <IssuedInvoices
+                    bookingId={booking.id}
+                    refreshKey={`${booking.updated_at}-${invoicePdfs.length}`}
+                    onChange={async () => {
+                      if (!booking.id) return;
+                      setInvoicePdfs(await invoke<InvoicePdfInfo[]>('get_invoice_pdfs_for_booking_command', { bookingId: booking.id }));
+                    }}
+                  />
+                )}
               </div>
 
               {/* Remarks */}
@@ -2140,6 +2298,18 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
                 />
               </div>
 
+              {/* Zimmersuche: freie Zimmer passend zu Zeitraum, Personen und Wünschen */}
+              <RoomFinder
+                rooms={rooms}
+                checkinDate={formData.checkin_date}
+                checkoutDate={formData.checkout_date}
+                anzahlGaeste={formData.anzahl_gaeste}
+                isMember={guests.find(g => g.id === formData.guest_id)?.dpolg_mitglied || false}
+                excludeBookingId={bookingId || undefined}
+                selectedRoomId={formData.room_id}
+                onSelectRoom={(roomId) => setFormData({ ...formData, room_id: roomId })}
+              />
+
               {/* Alternative Cleaning Checkout Date */}
               <div className="border-2 border-blue-300 rounded-lg p-4 bg-gradient-to-br from-blue-50 to-cyan-50">
                 <label className="flex items-start gap-3 cursor-pointer mb-3">
@@ -2488,6 +2658,27 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
                 </label>
               </div>
 
+              {/* Geschäftsreise Checkbox (Kurtaxe-Befreiung) */}
+              <div className="border border-slate-200 rounded-lg p-4 bg-slate-50">
+                <label className="flex items-start gap-3 cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={formData.geschaeftsreise || false}
+                    onChange={(e) => setFormData({ ...formData, geschaeftsreise: e.target.checked })}
+                    className="mt-0.5 w-5 h-5 accent-blue-600 bg-white border-slate-300 rounded focus:ring-2 focus:ring-blue-500"
+                  />
+                  <div className="flex-1">
+                    <div className="flex items-center gap-2 text-slate-900 font-semibold mb-1">
+                      <Briefcase className="w-4 h-4" />
+                      Geschäftsreise
+                    </div>
+                    <p className="text-sm text-slate-600">
+                      Dienstlicher Aufenthalt - von der Kurtaxe befreit, sofern die Gemeinde Geschäftsreisen befreit.
+                    </p>
+                  </div>
+                </label>
+              </div>
+
               {/* Companion Selector */}
               {formData.guest_id && (() => {
                 const selectedGuest = guests.find(g => g.id === formData.guest_id);
@@ -3015,6 +3206,25 @@ export default function BookingSidebar({ bookingId, isOpen, onClose, mode: initi
         />
       )}
 
+      {/* Zimmerwechsel Dialog */}
+      {booking?.id && (
+        <StaySplitDialog
+          isOpen={showSplitDialog}
+          onClose={() => setShowSplitDialog(false)}
+          booking={{
+            id: booking.id,
+            room_id: booking.room_id,
+            checkin_date: booking.checkin_date,
+            checkout_date: booking.checkout_date,
+            reservierungsnummer: booking.reservierungsnummer,
+          }}
+          onSplit={async () => {
+            await refreshBookings();
+            await loadBookingDetails();
+          }}
+        />
+      )}
+
       {/* Cancel Booking Confirmation Dialog */}
       <CancellationConfirmDialog
         isOpen={showCancelDialog && booking !== null}
@@ -0,0 +1,61 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { MailWarning, UserCheck } from 'lucide-react';
+import { format, parseISO } from 'date-fns';
+import type { DunningEntry } from '../../types/booking';
+
+interface DunningHistoryProps {
+  bookingId: number;
+  refreshKey?: unknown;
+}
+
+const EMAIL_STATUS_LABELS: Record<NonNullable<DunningEntry['emailStatus']>, string> = {
+  pending: 'geplant',
+  sent: 'versendet',
+  failed: 'fehlgeschlagen',
+  cancelled: 'storniert',
+};
+
+const formatDay = (date: string) => format(parseISO(date), 'dd.MM.yyyy');
+const formatEur = (amount: number) => `${amount.toFixed(2)} €`;
+
+export default function DunningHistory({ bookingId, refreshKey }: DunningHistoryProps) {
+  const [entries, setEntries] = useState<DunningEntry[]>([]);
+
+  useEffect(() => {
+    invoke<DunningEntry[]>('get_booking_dunning_history_pg', { bookingId })
+      .then(setEntries)
+      .catch((err) => console.error('Mahnverlauf konnte nicht geladen werden:', err));
+  }, [bookingId, refreshKey]);
+
+  if (entries.length === 0) return null;
+
+  return (
+    <div className="mt-4 text-sm">
+      <h4 className="font-semibold text-slate-700 mb-2 flex items-center gap-1">
+        <MailWarning className="w-4 h-4" />
+        Mahnverlauf
+      </h4>
+      {entries.map((entry) => (
+        <div key={entry.id} className="flex items-center justify-between py-1 border-b border-slate-100">
+          {entry.event === 'uebergabe' ? (
+            <span className="flex items-center gap-1 text-red-700">
+              <UserCheck className="w-4 h-4" />
+              {entry.createdAt ? formatDay(entry.createdAt.slice(0, 10)) : '–'} · Zur manuellen Nachverfolgung übergeben
+            </span>
+          ) : (
+            <span>
+              {entry.createdAt ? formatDay(entry.createdAt.slice(0, 10)) : '–'} · {entry.stageName}
+              {entry.zahlungsfrist ? ` · Frist ${formatDay(entry.zahlungsfrist)}` : ''}
+              {entry.emailStatus ? ` · ${EMAIL_STATUS_LABELS[entry.emailStatus]}` : ''}
+            </span>
+          )}
+          <span className="text-right">
+            <span className="font-semibold">{formatEur(entry.outstanding)}</span>
+            {entry.gebuehr > 0 && <span className="block text-xs text-slate-500">inkl. Gebühr {formatEur(entry.gebuehr)}</span>}
+          </span>
+        </div>
+      ))}
+    </div>
+  );
+}
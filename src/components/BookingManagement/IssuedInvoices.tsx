@@ -0,0 +1,233 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Ban, FilePen, FileMinus, Loader2 } from 'lucide-react';
+import { format, parseISO } from 'date-fns';
+import toast from 'react-hot-toast';
+import type { IssuedInvoice } from '../../types/booking';
+
+interface IssuedInvoicesProps {
+  bookingId: number;
+  // Credit notes add a PDF - reload the PDF list afterwards
+  onChange: () => void;
+  // Reload trigger, e.g. after a new invoice PDF was created
+  refreshKey?: unknown;
+}
+
+interface CorrectionForm {
+  invoiceId: number;
+  line: number;
+  quantity: string;
+  unitPrice: string;
+  reason: string;
+}
+
+const formatDay = (date: string) => format(parseISO(date), 'dd.MM.yyyy');
+const formatEur = (amount: number) => `${amount.toFixed(2)} €`;
+
+const documentLabel = (invoice: IssuedInvoice) => {
+  if (invoice.documentType === 'rechnung') return 'Rechnung';
+  return invoice.isCancellation ? 'Stornorechnung' : 'Rechnungskorrektur';
+};
+
+export default function IssuedInvoices({ bookingId, onChange, refreshKey }: IssuedInvoicesProps) {
+  const [invoices, setInvoices] = useState<IssuedInvoice[]>([]);
+  const [busyId, setBusyId] = useState<number | null>(null);
+  const [correction, setCorrection] = useState<CorrectionForm | null>(null);
+
+  const loadInvoices = () =>
+    invoke<IssuedInvoice[]>('get_invoices_for_booking_command', { bookingId })
+      .then(setInvoices)
+      .catch((err) => console.error('Rechnungen konnten nicht geladen werden:', err));
+
+  useEffect(() => {
+    loadInvoices();
+  }, [bookingId, refreshKey]);
+
+  // Render and open the PDF of a credit note (Stornorechnung / Korrektur)
+  const openCreditNote = async (creditNoteId: number) => {
+    setBusyId(creditNoteId);
+    try {
+      const path = await invoke<string>('generate_credit_note_pdf_command', { creditNoteId });
+      await invoke('open_pdf_file_command', { path });
+      onChange();
+    } catch (error) {
+      toast.error(`PDF konnte nicht erstellt werden: ${error}`);
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const issued = async (creditNote: IssuedInvoice) => {
+    toast.success(`${documentLabel(creditNote)} ${creditNote.invoiceNumber} ausgestellt`);
+    setCorrection(null);
+    await loadInvoices();
+    await openCreditNote(creditNote.id);
+  };
+
+  const cancelInvoice = async (invoice: IssuedInvoice) => {
+    const reason = window.prompt(`Rechnung ${invoice.invoiceNumber} stornieren - Grund:`);
+    if (!reason?.trim()) return;
+    setBusyId(invoice.id);
+    try {
+      await issued(await invoke<IssuedInvoice>('cancel_invoice_command', { invoiceId: invoice.id, reason }));
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const startCorrection = (invoice: IssuedInvoice) => {
+    const line = invoice.document.lines[0];
+    setCorrection({
+      invoiceId: invoice.id,
+      line: 0,
+      quantity: '1',
+      unitPrice: line ? line.unitPrice.toFixed(2) : '',
+      reason: '',
+    });
+  };
+
+  const correctInvoice = async (e: React.FormEvent, invoice: IssuedInvoice) => {
+    e.preventDefault();
+    if (!correction) return;
+    const line = invoice.document.lines[correction.line];
+    setBusyId(invoice.id);
+    try {
+      const creditNote = await invoke<IssuedInvoice>('correct_invoice_command', {
+        invoiceId: invoice.id,
+        corrections: [
+          {
+            description: line.description,
+            quantity: Number(correction.quantity),
+            unitPrice: Number(correction.unitPrice.replace(',', '.')),
+            taxCategory: line.taxCategory,
+          },
+        ],
+        reason: correction.reason,
+      });
+      await issued(creditNote);
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  if (invoices.length === 0) return null;
+
+  const cancelled = new Set(invoices.filter((i) => i.isCancellation).map((i) => i.correctsInvoiceId));
+  const inputClass = 'px-2 py-1 border border-slate-200 rounded text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+
+  return (
+    <div className="mt-4 text-sm">
+      <h4 className="font-semibold text-slate-700 mb-2">Ausgestellte Rechnungen und Gutschriften</h4>
+      {invoices.map((invoice) => (
+        <div key={invoice.id} className="py-1 border-b border-slate-100">
+          <div className="flex items-center justify-between gap-2">
+            <span>
+              <span className="font-semibold">{invoice.invoiceNumber}</span> · {documentLabel(invoice)} ·{' '}
+              {formatDay(invoice.issueDate)}
+              {cancelled.has(invoice.id) && <span className="ml-2 text-red-700 font-semibold">storniert</span>}
+              {invoice.reason && <span className="block text-xs text-slate-500">Grund: {invoice.reason}</span>}
+            </span>
+            <span className="flex items-center gap-2">
+              <span className={`font-semibold ${invoice.total < 0 ? 'text-red-700' : 'text-slate-900'}`}>
+                {formatEur(invoice.total)}
+              </span>
+              {busyId === invoice.id && <Loader2 className="w-4 h-4 animate-spin" />}
+              {invoice.documentType === 'gutschrift' && (
+                <button
+                  type="button"
+                  disabled={busyId !== null}
+                  onClick={() => openCreditNote(invoice.id)}
+                  className="flex items-center gap-1 px-2 py-1 bg-slate-100 hover:bg-slate-200 text-slate-700 rounded font-semibold"
+                  title="Gutschrift als PDF erstellen und öffnen"
+                >
+                  <FileMinus className="w-3.5 h-3.5" />
+                  Gutschrift
+                </button>
+              )}
+              {invoice.documentType === 'rechnung' && !cancelled.has(invoice.id) && (
+                <>
+                  <button
+                    type="button"
+                    disabled={busyId !== null}
+                    onClick={() => (correction?.invoiceId === invoice.id ? setCorrection(null) : startCorrection(invoice))}
+                    className="flex items-center gap-1 px-2 py-1 bg-slate-100 hover:bg-slate-200 text-slate-700 rounded font-semibold"
+                    title="Einzelne Positionen gutschreiben"
+                  >
+                    <FilePen className="w-3.5 h-3.5" />
+                    Korrigieren
+                  </button>
+                  <button
+                    type="button"
+                    disabled={busyId !== null}
+                    onClick={() => cancelInvoice(invoice)}
+                    className="flex items-center gap-1 px-2 py-1 bg-red-50 hover:bg-red-100 text-red-700 rounded font-semibold"
+                    title="Ganze Rechnung stornieren (Stornorechnung)"
+                  >
+                    <Ban className="w-3.5 h-3.5" />
+                    Stornieren
+                  </button>
+                </>
+              )}
+            </span>
+          </div>
+
+          {correction?.invoiceId === invoice.id && (
+            <form onSubmit={(e) => correctInvoice(e, invoice)} className="flex flex-wrap items-center gap-2 mt-2">
+              <select
+                value={correction.line}
+                onChange={(e) => {
+                  const line = Number(e.target.value);
+                  setCorrection({ ...correction, line, unitPrice: invoice.document.lines[line].unitPrice.toFixed(2) });
+                }}
+                className={inputClass}
+              >
+                {invoice.document.lines.map((line, index) => (
+                  <option key={line.position} value={index}>
+                    {line.description} ({line.quantityLabel} × {formatEur(line.unitPrice)})
+                  </option>
+                ))}
+              </select>
+              <input
+                type="number"
+                min={1}
+                required
+                value={correction.quantity}
+                onChange={(e) => setCorrection({ ...correction, quantity: e.target.value })}
+                className={`${inputClass} w-16`}
+                title="Menge"
+              />
+              <input
+                type="text"
+                inputMode="decimal"
+                required
+                value={correction.unitPrice}
+                onChange={(e) => setCorrection({ ...correction, unitPrice: e.target.value })}
+                className={`${inputClass} w-24`}
+                title="Einzelpreis €"
+              />
+              <input
+                type="text"
+                required
+                placeholder="Grund (z.B. eine Nacht weniger)"
+                value={correction.reason}
+                onChange={(e) => setCorrection({ ...correction, reason: e.target.value })}
+                className={`${inputClass} flex-1`}
+              />
+              <button
+                type="submit"
+                disabled={busyId !== null}
+                className="px-3 py-1 bg-emerald-600 hover:bg-emerald-700 disabled:bg-slate-400 text-white rounded font-semibold"
+              >
+                Gutschrift ausstellen
+              </button>
+            </form>
+          )}
+        </div>
+      ))}
+    </div>
+  );
+}
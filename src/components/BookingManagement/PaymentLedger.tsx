@@ -0,0 +1,307 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Plus, Trash2, Loader2, CalendarClock, Gift } from 'lucide-react';
+import { format, parseISO } from 'date-fns';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import type { BookingPayments, DepositRequest } from '../../types/booking';
+
+interface PaymentLedgerProps {
+  bookingId: number;
+  // Changes bezahlt / bezahlt_am of the booking - reload it afterwards
+  onChange: () => void;
+  // Reload trigger, e.g. after the payment dropdown changed the ledger
+  refreshKey?: unknown;
+}
+
+const PAYMENT_STATUS_LABELS: Record<BookingPayments['paymentStatus'], string> = {
+  ausstehend: 'Ausstehend',
+  teilweise_bezahlt: 'Teilweise bezahlt',
+  bezahlt: 'Bezahlt',
+};
+
+const DEPOSIT_STATE_STYLES: Record<DepositRequest['state'], string> = {
+  offen: 'bg-amber-100 text-amber-800',
+  ueberfaellig: 'bg-red-100 text-red-800',
+  bezahlt: 'bg-emerald-100 text-emerald-800',
+};
+
+const DEPOSIT_STATE_LABELS: Record<DepositRequest['state'], string> = {
+  offen: 'Offen',
+  ueberfaellig: 'Überfällig',
+  bezahlt: 'Bezahlt',
+};
+
+const today = () => format(new Date(), 'yyyy-MM-dd');
+const formatDay = (date: string) => format(parseISO(date), 'dd.MM.yyyy');
+const formatEur = (amount: number) => `${amount.toFixed(2)} €`;
+
+export default function PaymentLedger({ bookingId, onChange, refreshKey }: PaymentLedgerProps) {
+  const { userName } = useUser();
+  const [ledger, setLedger] = useState<BookingPayments | null>(null);
+  const [saving, setSaving] = useState(false);
+  const [payment, setPayment] = useState({ amount: '', paymentDate: today(), method: 'Überweisung', reference: '' });
+  const [deposit, setDeposit] = useState({ percentage: '30', amount: '', dueDate: '' });
+  const [voucher, setVoucher] = useState({ code: '', amount: '' });
+
+  useEffect(() => {
+    invoke<BookingPayments>('get_booking_payments_pg', { bookingId })
+      .then(setLedger)
+      .catch((err) => console.error('Zahlungen konnten nicht geladen werden:', err));
+  }, [bookingId, refreshKey]);
+
+  const run = async (action: () => Promise<BookingPayments>, success: string) => {
+    setSaving(true);
+    try {
+      setLedger(await action());
+      toast.success(success);
+      onChange();
+      return true;
+    } catch (err) {
+      toast.error(String(err));
+      return false;
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const addPayment = async (e: React.FormEvent) => {
+    e.preventDefault();
+    const saved = await run(
+      () =>
+        invoke<BookingPayments>('create_payment_pg', {
+          bookingId,
+          amount: Number(payment.amount.replace(',', '.')),
+          paymentDate: payment.paymentDate,
+          method: payment.method || null,
+          reference: payment.reference || null,
+          notes: null,
+          currentUser: userName,
+        }),
+      'Zahlung erfasst'
+    );
+    if (saved) setPayment({ ...payment, amount: '', reference: '' });
+  };
+
+  const addDeposit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    const saved = await run(
+      () =>
+        invoke<BookingPayments>('create_deposit_request_pg', {
+          bookingId,
+          description: null,
+          amount: deposit.amount ? Number(deposit.amount.replace(',', '.')) : null,
+          percentage: deposit.amount ? null : Number(deposit.percentage),
+          dueDate: deposit.dueDate,
+          currentUser: userName,
+        }),
+      'Anzahlung angefordert'
+    );
+    if (saved) setDeposit({ ...deposit, amount: '', dueDate: '' });
+  };
+
+  const redeemVoucher = async (e: React.FormEvent) => {
+    e.preventDefault();
+    const saved = await run(
+      () =>
+        invoke<BookingPayments>('redeem_voucher_pg', {
+          code: voucher.code,
+          bookingId,
+          amount: voucher.amount ? Number(voucher.amount.replace(',', '.')) : null,
+          currentUser: userName,
+        }),
+      'Gutschein eingelöst'
+    );
+    if (saved) setVoucher({ code: '', amount: '' });
+  };
+
+  if (!ledger) return null;
+
+  const inputClass = 'px-3 py-1.5 border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+
+  return (
+    <div className="space-y-4 mt-4 text-sm">
+      {/* Balance */}
+      <div className="grid grid-cols-3 gap-3">
+        <div>
+          <span className="text-slate-600">Fällig</span>
+          <p className="font-semibold text-slate-900">{formatEur(ledger.totalDue)}</p>
+        </div>
+        <div>
+          <span className="text-slate-600">Bezahlt</span>
+          <p className="font-semibold text-emerald-700">{formatEur(ledger.paid)}</p>
+        </div>
+        <div>
+          <span className="text-slate-600">Offen ({PAYMENT_STATUS_LABELS[ledger.paymentStatus]})</span>
+          <p className="font-semibold text-slate-900">{formatEur(ledger.outstanding)}</p>
+        </div>
+      </div>
+
+      {/* Deposit requests */}
+      <div>
+        <h4 className="font-semibold text-slate-700 mb-2 flex items-center gap-1">
+          <CalendarClock className="w-4 h-4" />
+          Anzahlungen
+        </h4>
+        {ledger.deposits.map((d) => (
+          <div key={d.id} className="flex items-center justify-between py-1 border-b border-slate-100">
+            <span>
+              {d.description}
+              {d.percentage ? ` (${d.percentage} %)` : ''} · fällig {formatDay(d.dueDate)}
+            </span>
+            <span className="flex items-center gap-2">
+              <span className="font-semibold">{formatEur(d.amount)}</span>
+              <span className={`px-2 py-0.5 rounded text-xs font-semibold ${DEPOSIT_STATE_STYLES[d.state]}`}>
+                {DEPOSIT_STATE_LABELS[d.state]}
+              </span>
+              <button
+                type="button"
+                disabled={saving}
+                onClick={() =>
+                  run(() => invoke<BookingPayments>('delete_deposit_request_pg', { id: d.id, bookingId }), 'Anzahlung entfernt')
+                }
+                className="p-1 text-red-600 hover:bg-red-50 rounded"
+                title="Anzahlung entfernen"
+              >
+                <Trash2 className="w-3.5 h-3.5" />
+              </button>
+            </span>
+          </div>
+        ))}
+        <form onSubmit={addDeposit} className="flex flex-wrap gap-2 mt-2">
+          <input
+            type="number"
+            min="1"
+            max="100"
+            value={deposit.percentage}
+            disabled={!!deposit.amount}
+            onChange={(e) => setDeposit({ ...deposit, percentage: e.target.value })}
+            className={`${inputClass} w-20`}
+            title="Prozent des fälligen Betrags"
+          />
+          <input
+            type="text"
+            inputMode="decimal"
+            placeholder="oder Betrag €"
+            value={deposit.amount}
+            onChange={(e) => setDeposit({ ...deposit, amount: e.target.value })}
+            className={`${inputClass} w-28`}
+          />
+          <input
+            type="date"
+            required
+            value={deposit.dueDate}
+            onChange={(e) => setDeposit({ ...deposit, dueDate: e.target.value })}
+            className={inputClass}
+          />
+          <button
+            type="submit"
+            disabled={saving}
+            className="flex items-center gap-1 px-3 py-1.5 bg-slate-100 hover:bg-slate-200 text-slate-700 rounded-lg font-semibold"
+          >
+            <Plus className="w-4 h-4" />
+            Anfordern
+          </button>
+        </form>
+      </div>
+
+      {/* Payments */}
+      <div>
+        <h4 className="font-semibold text-slate-700 mb-2">Zahlungseingänge</h4>
+        {ledger.payments.map((p) => (
+          <div key={p.id} className="flex items-center justify-between py-1 border-b border-slate-100">
+            <span>
+              {formatDay(p.paymentDate)} · {p.method ?? '–'}
+              {p.reference ? ` · ${p.reference}` : ''}
+              {p.reversalOf ? ' · Storno' : ''}
+            </span>
+            <span className="flex items-center gap-2">
+              <span className={`font-semibold ${p.amount < 0 ? 'text-red-700' : 'text-emerald-700'}`}>{formatEur(p.amount)}</span>
+              <button
+                type="button"
+                disabled={saving}
+                onClick={() => run(() => invoke<BookingPayments>('delete_payment_pg', { id: p.id }), 'Zahlung gelöscht')}
+                className="p-1 text-red-600 hover:bg-red-50 rounded"
+                title="Zahlung löschen"
+              >
+                <Trash2 className="w-3.5 h-3.5" />
+              </button>
+            </span>
+          </div>
+        ))}
+        <form onSubmit={addPayment} className="flex flex-wrap gap-2 mt-2">
+          <input
+            type="text"
+            inputMode="decimal"
+            required
+            placeholder="Betrag €"
+            value={payment.amount}
+            onChange={(e) => setPayment({ ...payment, amount: e.target.value })}
+            className={`${inputClass} w-28`}
+            title="Negativer Betrag = Erstattung"
+          />
+          <input
+            type="date"
+            required
+            value={payment.paymentDate}
+            onChange={(e) => setPayment({ ...payment, paymentDate: e.target.value })}
+            className={inputClass}
+          />
+          <select
+            value={payment.method}
+            onChange={(e) => setPayment({ ...payment, method: e.target.value })}
+            className={inputClass}
+          >
+            <option>Überweisung</option>
+            <option>Barzahlung</option>
+            <option>EC-Karte</option>
+            <option>Kreditkarte</option>
+            <option>PayPal</option>
+          </select>
+          <input
+            type="text"
+            placeholder="Verwendungszweck / Beleg"
+            value={payment.reference}
+            onChange={(e) => setPayment({ ...payment, reference: e.target.value })}
+            className={`${inputClass} flex-1 min-w-[8rem]`}
+          />
+          <button
+            type="submit"
+            disabled={saving}
+            className="flex items-center gap-1 px-3 py-1.5 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold"
+          >
+            {saving ? <Loader2 className="w-4 h-4 animate-spin" /> : <Plus className="w-4 h-4" />}
+            Zahlung erfassen
+          </button>
+        </form>
+        <form onSubmit={redeemVoucher} className="flex flex-wrap gap-2 mt-2">
+          <input
+            type="text"
+            required
+            placeholder="Gutscheincode GS-XXXX-XXXX"
+            value={voucher.code}
+            onChange={(e) => setVoucher({ ...voucher, code: e.target.value })}
+            className={`${inputClass} flex-1 min-w-[10rem] font-mono`}
+          />
+          <input
+            type="text"
+            inputMode="decimal"
+            placeholder="Betrag € (optional)"
+            value={voucher.amount}
+            onChange={(e) => setVoucher({ ...voucher, amount: e.target.value })}
+            className={`${inputClass} w-36`}
+            title="Leer = so viel wie möglich (Restwert bzw. offener Betrag)"
+          />
+          <button
+            type="submit"
+            disabled={saving || ledger.outstanding <= 0}
+            className="flex items-center gap-1 px-3 py-1.5 bg-slate-100 hover:bg-slate-200 disabled:opacity-50 text-slate-700 rounded-lg font-semibold"
+          >
+            <Gift className="w-4 h-4" />
+            Gutschein einlösen
+          </button>
+        </form>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,144 @@
+import { useState, useMemo } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Sparkles, Dog, Home, CheckCircle, Loader2 } from 'lucide-react';
+import type { Room, RoomSuggestion } from '../../types/booking';
+
+interface RoomFinderProps {
+  rooms: Room[];
+  checkinDate: string;
+  checkoutDate: string;
+  anzahlGaeste: number;
+  isMember?: boolean;
+  excludeBookingId?: number;
+  selectedRoomId: number;
+  onSelectRoom: (roomId: number) => void;
+}
+
+export default function RoomFinder({
+  rooms,
+  checkinDate,
+  checkoutDate,
+  anzahlGaeste,
+  isMember,
+  excludeBookingId,
+  selectedRoomId,
+  onSelectRoom,
+}: RoomFinderProps) {
+  const [ort, setOrt] = useState('');
+  const [gebaeudeTyp, setGebaeudeTyp] = useState('');
+  const [hund, setHund] = useState(false);
+  const [erdgeschoss, setErdgeschoss] = useState(false);
+  const [suggestions, setSuggestions] = useState<RoomSuggestion[] | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const orte = useMemo(() => Array.from(new Set(rooms.map((r) => r.ort))).sort(), [rooms]);
+  const typen = useMemo(() => Array.from(new Set(rooms.map((r) => r.gebaeude_typ))).sort(), [rooms]);
+
+  const canSearch = !!checkinDate && !!checkoutDate && checkoutDate > checkinDate;
+
+  const handleSearch = async () => {
+    setLoading(true);
+    setError(null);
+    try {
+      const result = await invoke<RoomSuggestion[]>('find_free_rooms_pg', {
+        criteria: {
+          checkin: checkinDate,
+          checkout: checkoutDate,
+          anzahlGaeste: Math.max(anzahlGaeste, 1),
+          ort: ort || null,
+          gebaeudeTyp: gebaeudeTyp || null,
+          hund,
+          erdgeschoss,
+        },
+        isMember: isMember ?? false,
+        excludeBookingId: excludeBookingId ?? null,
+      });
+      setSuggestions(result);
+    } catch (err) {
+      setError(String(err));
+      setSuggestions(null);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+    <div className="p-4 bg-slate-50 border border-slate-200 rounded-lg space-y-3">
+      <div className="flex flex-wrap items-center gap-3">
+        <select value={ort} onChange={(e) => setOrt(e.target.value)} className="px-3 py-2 bg-white border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500">
+          <option value="">Alle Orte</option>
+          {orte.map((o) => (
+            <option key={o} value={o}>{o}</option>
+          ))}
+        </select>
+        <select value={gebaeudeTyp} onChange={(e) => setGebaeudeTyp(e.target.value)} className="px-3 py-2 bg-white border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500">
+          <option value="">Alle Zimmertypen</option>
+          {typen.map((t) => (
+            <option key={t} value={t}>{t}</option>
+          ))}
+        </select>
+        <label className="flex items-center gap-1.5 text-sm text-slate-700 cursor-pointer">
+          <input type="checkbox" checked={hund} onChange={(e) => setHund(e.target.checked)} className="w-4 h-4 rounded" />
+          <Dog className="w-4 h-4" />
+          Hund
+        </label>
+        <label className="flex items-center gap-1.5 text-sm text-slate-700 cursor-pointer">
+          <input type="checkbox" checked={erdgeschoss} onChange={(e) => setErdgeschoss(e.target.checked)} className="w-4 h-4 rounded" />
+          <Home className="w-4 h-4" />
+          Erdgeschoss
+        </label>
+        <button
+          type="button"
+          onClick={handleSearch}
+          disabled={!canSearch || loading}
+          className="ml-auto flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:bg-slate-400 disabled:cursor-not-allowed text-white rounded-lg text-sm font-semibold transition-colors"
+          title={canSearch ? '' : 'Bitte zuerst An- und Abreise wählen'}
+        >
+          {loading ? <Loader2 className="w-4 h-4 animate-spin" /> : <Sparkles className="w-4 h-4" />}
+          Zimmer vorschlagen
+        </button>
+      </div>
+
+      {error && <p className="text-sm text-red-700">{error}</p>}
+
+      {suggestions && suggestions.length === 0 && (
+        <p className="text-sm text-slate-500">Kein passendes Zimmer frei.</p>
+      )}
+
+      {suggestions && suggestions.length > 0 && (
+        <div className="space-y-2 max-h-64 overflow-y-auto">
+          {suggestions.map((s) => (
+            <button
+              type="button"
+              key={s.roomId}
+              onClick={() => onSelectRoom(s.roomId)}
+              className={`w-full text-left p-3 rounded-lg border transition-colors ${
+                s.roomId === selectedRoomId
+                  ? 'bg-blue-50 border-blue-400'
+                  : 'bg-white border-slate-200 hover:border-blue-300'
+              }`}
+            >
+              <div className="flex items-center justify-between">
+                <div className="flex items-center gap-2">
+                  {s.roomId === selectedRoomId && <CheckCircle className="w-4 h-4 text-blue-600" />}
+                  <span className="font-semibold text-slate-800">{s.room.name}</span>
+                  <span className="text-xs text-slate-500">
+                    {s.room.gebaeude_typ} • {s.room.ort} • {s.room.capacity} Pers.
+                  </span>
+                </div>
+                <div className="text-right">
+                  <div className="text-sm font-bold text-slate-800">{s.totalPrice.toFixed(2)} €</div>
+                  <div className="text-xs text-slate-500">{s.pricePerNight.toFixed(2)} € / Nacht</div>
+                </div>
+              </div>
+              {s.hinweise.length > 0 && (
+                <div className="mt-1 text-xs text-slate-500">{s.hinweise.join(' • ')}</div>
+              )}
+            </button>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,5 +1,5 @@
 import { useState, useRef, useEffect } from 'react';
-import { Circle, CheckCircle, Clock, X, ChevronDown } from 'lucide-react';
+import { Circle, CheckCircle, Clock, X, ChevronDown, HelpCircle, Hourglass } from 'lucide-react';
 import PortalDropdown from '../PortalDropdown';
 
 interface StatusDropdownProps {
@@ -13,6 +13,8 @@ export default function StatusDropdown({ currentStatus, bookingId, onStatusChang
   const buttonRef = useRef<HTMLButtonElement>(null);
 
   const statusOptions = [
+    { value: 'anfrage', label: 'Anfrage', icon: HelpCircle, color: 'amber' },
+    { value: 'optioniert', label: 'Optioniert', icon: Hourglass, color: 'orange' },
     { value: 'reserviert', label: 'Reserviert', icon: Circle, color: 'blue' },
     { value: 'bestaetigt', label: 'Bestätigt', icon: CheckCircle, color: 'emerald' },
     { value: 'eingecheckt', label: 'Eingecheckt', icon: Clock, color: 'purple' },
@@ -32,6 +34,8 @@ export default function StatusDropdown({ currentStatus, bookingId, onStatusChang
 
   const getColorClasses = (color: string) => {
     const colors = {
+      amber: 'bg-amber-100 text-amber-700 border-amber-200 hover:bg-amber-200',
+      orange: 'bg-orange-100 text-orange-700 border-orange-200 hover:bg-orange-200',
       blue: 'bg-blue-100 text-blue-700 border-blue-200 hover:bg-blue-200',
       emerald: 'bg-emerald-100 text-emerald-700 border-emerald-200 hover:bg-emerald-200',
       purple: 'bg-purple-100 text-purple-700 border-purple-200 hover:bg-purple-200',
@@ -0,0 +1,188 @@
+import { useState, useEffect, useMemo } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { X, ArrowRightLeft, Plus, Trash2, Loader2 } from 'lucide-react';
+import { format, parseISO } from 'date-fns';
+import toast from 'react-hot-toast';
+import { useData } from '../../context/DataContext';
+import { useUser } from '../../context/UserContext';
+import type { Booking, StaySegment } from '../../types/booking';
+
+interface StaySplitDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+  booking: { id: number; room_id: number; checkin_date: string; checkout_date: string; reservierungsnummer: string };
+  onSplit: () => void;
+}
+
+interface RoomChange {
+  date: string;
+  roomId: number;
+}
+
+export default function StaySplitDialog({ isOpen, onClose, booking, onSplit }: StaySplitDialogProps) {
+  const { rooms } = useData();
+  const { userName } = useUser();
+  const [changes, setChanges] = useState<RoomChange[]>([{ date: '', roomId: 0 }]);
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (isOpen) {
+      setChanges([{ date: '', roomId: 0 }]);
+      setError(null);
+    }
+  }, [isOpen]);
+
+  // Segments: current room until the first change, then one segment per change
+  const segments = useMemo<StaySegment[]>(() => {
+    const dates = [booking.checkin_date, ...changes.map((c) => c.date), booking.checkout_date];
+    const roomIds = [booking.room_id, ...changes.map((c) => c.roomId)];
+    return roomIds.map((roomId, i) => ({ roomId, checkinDate: dates[i], checkoutDate: dates[i + 1] }));
+  }, [booking, changes]);
+
+  const isComplete = segments.every(
+    (s, i) => s.roomId > 0 && s.checkinDate && s.checkoutDate > s.checkinDate && (i === 0 || s.roomId !== segments[i - 1].roomId)
+  );
+
+  const updateChange = (index: number, change: Partial<RoomChange>) => {
+    setChanges(changes.map((c, i) => (i === index ? { ...c, ...change } : c)));
+  };
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setSaving(true);
+    setError(null);
+    try {
+      await invoke<Booking[]>('split_booking_pg', { id: booking.id, segments, currentUser: userName });
+      toast.success(`Zimmerwechsel für ${booking.reservierungsnummer} gespeichert`);
+      onSplit();
+      onClose();
+    } catch (err) {
+      setError(String(err).replace(/^(DOUBLE_BOOKING|CONFLICT):/, ''));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  if (!isOpen) return null;
+
+  const formatDay = (date: string) => (date ? format(parseISO(date), 'dd.MM.yyyy') : '…');
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-[60] p-4">
+      <div className="bg-white rounded-2xl shadow-2xl w-full max-w-xl max-h-[90vh] overflow-hidden">
+        {/* Header */}
+        <div className="bg-gradient-to-r from-blue-600 to-blue-700 px-6 py-4 flex items-center justify-between">
+          <div className="flex items-center gap-3">
+            <div className="bg-white/20 p-2 rounded-lg">
+              <ArrowRightLeft className="w-6 h-6 text-white" />
+            </div>
+            <h2 className="text-xl font-bold text-white">Zimmerwechsel {booking.reservierungsnummer}</h2>
+          </div>
+          <button
+            onClick={onClose}
+            className="text-white/80 hover:text-white transition-colors p-1 rounded-lg hover:bg-white/10"
+          >
+            <X className="w-6 h-6" />
+          </button>
+        </div>
+
+        <form onSubmit={handleSubmit} className="p-6 overflow-y-auto max-h-[calc(90vh-80px)] space-y-4">
+          {error && (
+            <div className="p-4 bg-red-50 border border-red-200 rounded-lg">
+              <p className="text-sm text-red-700">{error}</p>
+            </div>
+          )}
+
+          <p className="text-sm text-slate-600">
+            Der Aufenthalt wird in Abschnitte mit eigenem Zimmer und eigenem Preis geteilt. Alle Abschnitte behalten die
+            Reservierungsnummer und werden gemeinsam abgerechnet.
+          </p>
+
+          {changes.map((change, index) => (
+            <div key={index} className="grid grid-cols-[1fr_1fr_auto] gap-3 items-end">
+              <div>
+                <label className="block text-sm font-semibold text-slate-700 mb-2">Umzug am *</label>
+                <input
+                  type="date"
+                  required
+                  value={change.date}
+                  min={index === 0 ? booking.checkin_date : changes[index - 1].date}
+                  max={booking.checkout_date}
+                  onChange={(e) => updateChange(index, { date: e.target.value })}
+                  className="w-full px-4 py-2 border border-slate-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
+                />
+              </div>
+              <div>
+                <label className="block text-sm font-semibold text-slate-700 mb-2">Neues Zimmer *</label>
+                <select
+                  required
+                  value={change.roomId}
+                  onChange={(e) => updateChange(index, { roomId: Number(e.target.value) })}
+                  className="w-full px-4 py-2 border border-slate-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
+                >
+                  <option value={0}>Zimmer wählen</option>
+                  {rooms.map((room) => (
+                    <option key={room.id} value={room.id}>
+                      {room.name} ({room.gebaeude_typ}, {room.ort})
+                    </option>
+                  ))}
+                </select>
+              </div>
+              <button
+                type="button"
+                disabled={changes.length === 1}
+                onClick={() => setChanges(changes.filter((_, i) => i !== index))}
+                className="p-2 text-red-600 hover:bg-red-50 disabled:opacity-30 rounded-lg transition-colors"
+                title="Zimmerwechsel entfernen"
+              >
+                <Trash2 className="w-4 h-4" />
+              </button>
+            </div>
+          ))}
+
+          <button
+            type="button"
+            onClick={() => setChanges([...changes, { date: '', roomId: 0 }])}
+            className="flex items-center gap-2 text-sm font-semibold text-blue-600 hover:text-blue-700"
+          >
+            <Plus className="w-4 h-4" />
+            Weiteren Zimmerwechsel hinzufügen
+          </button>
+
+          {/* Preview of the segments */}
+          <div className="space-y-1 p-3 bg-slate-50 border border-slate-200 rounded-lg">
+            {segments.map((segment, index) => (
+              <div key={index} className="flex justify-between text-sm text-slate-700">
+                <span className="font-semibold">
+                  {rooms.find((r) => r.id === segment.roomId)?.name ?? 'Zimmer wählen'}
+                </span>
+                <span>
+                  {formatDay(segment.checkinDate)} - {formatDay(segment.checkoutDate)}
+                </span>
+              </div>
+            ))}
+          </div>
+
+          <div className="flex justify-end gap-3 pt-2">
+            <button
+              type="button"
+              onClick={onClose}
+              className="px-4 py-2 bg-slate-100 hover:bg-slate-200 text-slate-700 rounded-lg font-semibold transition-colors"
+            >
+              Abbrechen
+            </button>
+            <button
+              type="submit"
+              disabled={!isComplete || saving}
+              className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold transition-colors"
+            >
+              {saving ? <Loader2 className="w-4 h-4 animate-spin" /> : <ArrowRightLeft className="w-4 h-4" />}
+              Aufenthalt teilen
+            </button>
+          </div>
+        </form>
+      </div>
+    </div>
+  );
+}
@@ -167,6 +167,8 @@ export default function EmailHistoryView() {
       reminder: 'Erinnerung',
       invoice: 'Rechnung',
       payment_reminder: 'Zahlungserinnerung',
+      mahnung_1: '1. Mahnung',
+      mahnung_2: '2. Mahnung',
       cancellation: 'Stornierungsbestätigung',
     };
     return names[name] || name;
@@ -178,6 +180,8 @@ export default function EmailHistoryView() {
       reminder: 'bg-amber-100 text-amber-700 border-amber-200',
       invoice: 'bg-emerald-100 text-emerald-700 border-emerald-200',
       payment_reminder: 'bg-orange-100 text-orange-700 border-orange-200',
+      mahnung_1: 'bg-orange-100 text-orange-700 border-orange-200',
+      mahnung_2: 'bg-red-100 text-red-700 border-red-200',
       cancellation: 'bg-red-100 text-red-700 border-red-200',
     };
     return colors[name] || 'bg-slate-100 text-slate-700 border-slate-200';
@@ -10,6 +10,7 @@ import { de } from 'date-fns/locale';
 import { formatDate } from '../../utils/dateFormatting';
 import { useBatchPriceCalculation, getBookingPrice } from '../../hooks/useBatchPriceCalculation';
 import { useData } from '../../context/DataContext';
+import SepaMandateSection from './SepaMandateSection';
 
 interface GuestDetailsProps {
   guestId: number;
@@ -59,6 +60,15 @@ interface Guest {
   marketing_einwilligung?: boolean;
   automail?: boolean;
   automail_sprache?: string;
+  // SEPA-Lastschriftmandat
+  sepa_mandatsreferenz?: string;
+  sepa_mandat_datum?: string;
+  sepa_iban?: string;
+  sepa_bic?: string;
+  sepa_kontoinhaber?: string;
+  sepa_mandat_typ?: 'CORE' | 'B2B';
+  sepa_einmalig?: boolean;
+  sepa_letzte_lastschrift?: string;
 }
 
 interface Room {
@@ -598,6 +608,9 @@ export default function GuestDetails({ guestId, isOpen, onClose, onEdit }: Guest
                 </div>
               )}
 
+              {/* SEPA-Lastschriftmandat */}
+              <SepaMandateSection guest={guest} onChange={(updated) => setGuest({ ...guest, ...updated })} />
+
               {/* Mitreisende-Übersicht */}
               {uniqueAccompanyingGuests.length > 0 && (
                 <div className="border border-slate-200 rounded-lg p-5">
@@ -2,7 +2,7 @@ import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { X, User, Mail, Phone, MapPin, Hash, FileText, Check, Loader2, Briefcase, MapPinned, Building2, Wallet, Plus, Clock, History, Globe, Calendar, CreditCard, Tag, Settings, Home, Smartphone } from 'lucide-react';
 import { useData } from '../../context/DataContext';
-import { formatDateTime } from '../../utils/dateFormatting';
+import { formatDateTime, formatDate as formatDay } from '../../utils/dateFormatting';
 import FilterDatePicker from '../BookingManagement/FilterDatePicker';
 
 // HMR Force Reload - Tab-based UI with 21 new fields
@@ -51,9 +51,10 @@ interface GuestCreditTransaction {
   id: number;
   guestId: number;
   amount: number;
-  transactionType: 'added' | 'used' | 'expired' | 'refund';
+  transactionType: 'added' | 'used' | 'expired' | 'refund' | 'credit' | 'debit' | 'expiry';
   bookingId?: number | null;
   notes?: string | null;
+  expiresAt?: string | null;
   createdBy: string;
   createdAt: string;
 }
@@ -177,6 +178,7 @@ export default function GuestDialog({ isOpen, onClose, guest, onSuccess }: Guest
   const [creditLoading, setCreditLoading] = useState(false);
   const [creditAmount, setCreditAmount] = useState('');
   const [creditNotes, setCreditNotes] = useState('');
+  const [creditExpiresAt, setCreditExpiresAt] = useState('');
   const [creditType, setCreditType] = useState<'added' | 'expired' | 'refund'>('added');
 
   // Initialize form with guest data when editing
@@ -318,6 +320,7 @@ export default function GuestDialog({ isOpen, onClose, guest, onSuccess }: Guest
         guestId: guest.id,
         amount,
         description: creditNotes.trim(),
+        expiresAt: creditType === 'added' && creditExpiresAt ? creditExpiresAt : null,
       });
 
       // Reload credit data
@@ -326,6 +329,7 @@ export default function GuestDialog({ isOpen, onClose, guest, onSuccess }: Guest
       // Reset form
       setCreditAmount('');
       setCreditNotes('');
+      setCreditExpiresAt('');
       setCreditType('added');
 
       showToast('success', 'Guthaben erfolgreich hinzugefügt');
@@ -358,8 +362,11 @@ export default function GuestDialog({ isOpen, onClose, guest, onSuccess }: Guest
   const getTransactionTypeLabel = (type: string): string => {
     const labels: Record<string, string> = {
       added: 'Gutgeschrieben',
+      credit: 'Gutgeschrieben',
       used: 'Verrechnet',
+      debit: 'Verrechnet',
       expired: 'Verfallen',
+      expiry: 'Verfallen',
       refund: 'Rückerstattet',
     };
     return labels[type] || type;
@@ -369,8 +376,11 @@ export default function GuestDialog({ isOpen, onClose, guest, onSuccess }: Guest
   const getTransactionTypeColor = (type: string): string => {
     const colors: Record<string, string> = {
       added: 'text-emerald-400',
+      credit: 'text-emerald-400',
       used: 'text-red-400',
+      debit: 'text-red-400',
       expired: 'text-amber-400',
+      expiry: 'text-amber-400',
       refund: 'text-blue-400',
     };
     return colors[type] || 'text-slate-400';
@@ -1689,6 +1699,25 @@ export default function GuestDialog({ isOpen, onClose, guest, onSuccess }: Guest
                     </div>
                   </div>
 
+                  {/* Expiry (credit only) */}
+                  {creditType === 'added' && (
+                    <div className="mt-4 space-y-2">
+                      <label className="block text-sm font-medium text-slate-200">
+                        Gültig bis (optional)
+                      </label>
+                      <input
+                        type="date"
+                        value={creditExpiresAt}
+                        onChange={(e) => setCreditExpiresAt(e.target.value)}
+                        className="w-full md:w-1/3 px-4 py-3 bg-slate-700 border border-slate-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-emerald-500 focus:border-transparent"
+                        disabled={creditLoading}
+                      />
+                      <p className="text-xs text-slate-400">
+                        Nicht genutztes Guthaben verfällt nach diesem Datum automatisch.
+                      </p>
+                    </div>
+                  )}
+
                   {/* Notes */}
                   <div className="mt-4 space-y-2">
                     <label className="block text-sm font-medium text-slate-200">
@@ -1733,6 +1762,11 @@ export default function GuestDialog({ isOpen, onClose, guest, onSuccess }: Guest
                                 <span className="text-xs text-slate-500">
                                   {formatDate(transaction.createdAt)}
                                 </span>
+                                {transaction.expiresAt && (
+                                  <span className="text-xs text-amber-400">
+                                    gültig bis {formatDay(transaction.expiresAt)}
+                                  </span>
+                                )}
                               </div>
                               {transaction.notes && (
                                 <p className="text-sm text-slate-300 mb-2">{transaction.notes}</p>
@@ -0,0 +1,268 @@
+import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Landmark, Edit2, Trash2, Save, X } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import { formatDate } from '../../utils/dateFormatting';
+import type { Guest } from '../../types/booking';
+
+type MandateGuest = Pick<
+  Guest,
+  | 'id'
+  | 'vorname'
+  | 'nachname'
+  | 'mitgliedsnummer'
+  | 'sepa_mandatsreferenz'
+  | 'sepa_mandat_datum'
+  | 'sepa_iban'
+  | 'sepa_bic'
+  | 'sepa_kontoinhaber'
+  | 'sepa_mandat_typ'
+  | 'sepa_einmalig'
+  | 'sepa_letzte_lastschrift'
+>;
+
+interface SepaMandateSectionProps {
+  guest: MandateGuest;
+  onChange: (guest: Guest) => void;
+}
+
+// IBAN in Vierergruppen für die Anzeige
+const formatIban = (iban: string) => iban.replace(/(.{4})/g, '$1 ').trim();
+
+export default function SepaMandateSection({ guest, onChange }: SepaMandateSectionProps) {
+  const { userName } = useUser();
+  const [editing, setEditing] = useState(false);
+  const [saving, setSaving] = useState(false);
+  const [form, setForm] = useState({
+    mandatsreferenz: '',
+    mandatDatum: '',
+    iban: '',
+    bic: '',
+    kontoinhaber: '',
+    mandatTyp: 'CORE' as 'CORE' | 'B2B',
+    einmalig: false,
+  });
+
+  const startEditing = () => {
+    setForm({
+      // Vorschlag für neue Mandate: Mitgliedsnummer
+      mandatsreferenz: guest.sepa_mandatsreferenz ?? (guest.mitgliedsnummer ? `MITGL-${guest.mitgliedsnummer}` : ''),
+      mandatDatum: guest.sepa_mandat_datum ?? '',
+      iban: guest.sepa_iban ?? '',
+      bic: guest.sepa_bic ?? '',
+      kontoinhaber: guest.sepa_kontoinhaber ?? '',
+      mandatTyp: guest.sepa_mandat_typ ?? 'CORE',
+      einmalig: guest.sepa_einmalig ?? false,
+    });
+    setEditing(true);
+  };
+
+  const save = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setSaving(true);
+    try {
+      const updated = await invoke<Guest>('set_guest_sepa_mandate_pg', {
+        guestId: guest.id,
+        mandatsreferenz: form.mandatsreferenz,
+        mandatDatum: form.mandatDatum,
+        iban: form.iban,
+        bic: form.bic || null,
+        kontoinhaber: form.kontoinhaber || null,
+        mandatTyp: form.mandatTyp,
+        einmalig: form.einmalig,
+        currentUser: userName,
+      });
+      onChange(updated);
+      setEditing(false);
+      toast.success('Lastschriftmandat gespeichert');
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const remove = async () => {
+    if (!confirm('Lastschriftmandat wirklich entfernen? Offene Buchungen werden dann nicht mehr eingezogen.')) return;
+    try {
+      onChange(await invoke<Guest>('remove_guest_sepa_mandate_pg', { guestId: guest.id, currentUser: userName }));
+      toast.success('Lastschriftmandat entfernt');
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const inputClass =
+    'w-full px-3 py-2 border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+
+  return (
+    <div className="border border-slate-200 rounded-lg p-5">
+      <div className="flex items-center justify-between mb-4">
+        <h3 className="text-lg font-bold text-slate-800 flex items-center gap-2">
+          <Landmark className="w-5 h-5" />
+          SEPA-Lastschriftmandat
+        </h3>
+        {!editing && (
+          <div className="flex gap-2">
+            <button
+              onClick={startEditing}
+              className="flex items-center gap-1 px-3 py-1.5 bg-slate-100 hover:bg-slate-200 text-slate-700 rounded-lg text-sm font-semibold"
+            >
+              <Edit2 className="w-4 h-4" />
+              {guest.sepa_mandatsreferenz ? 'Bearbeiten' : 'Mandat erfassen'}
+            </button>
+            {guest.sepa_mandatsreferenz && (
+              <button
+                onClick={remove}
+                className="p-1.5 text-red-600 hover:bg-red-50 rounded-lg"
+                title="Mandat entfernen"
+              >
+                <Trash2 className="w-4 h-4" />
+              </button>
+            )}
+          </div>
+        )}
+      </div>
+
+      {!editing && !guest.sepa_mandatsreferenz && (
+        <p className="text-sm text-slate-500">Kein Mandat – Zahlungen erfolgen per Überweisung.</p>
+      )}
+
+      {!editing && guest.sepa_mandatsreferenz && (
+        <div className="grid grid-cols-2 gap-4">
+          <div>
+            <p className="text-sm text-slate-600 mb-1">Mandatsreferenz</p>
+            <p className="font-medium text-slate-900">{guest.sepa_mandatsreferenz}</p>
+          </div>
+          <div>
+            <p className="text-sm text-slate-600 mb-1">Unterschrieben am</p>
+            <p className="font-medium text-slate-900">
+              {guest.sepa_mandat_datum ? formatDate(guest.sepa_mandat_datum) : '–'}
+            </p>
+          </div>
+          <div>
+            <p className="text-sm text-slate-600 mb-1">IBAN</p>
+            <p className="font-medium text-slate-900">{guest.sepa_iban ? formatIban(guest.sepa_iban) : '–'}</p>
+          </div>
+          <div>
+            <p className="text-sm text-slate-600 mb-1">BIC</p>
+            <p className="font-medium text-slate-900">{guest.sepa_bic || '–'}</p>
+          </div>
+          <div>
+            <p className="text-sm text-slate-600 mb-1">Kontoinhaber</p>
+            <p className="font-medium text-slate-900">
+              {guest.sepa_kontoinhaber || `${guest.vorname} ${guest.nachname}`}
+            </p>
+          </div>
+          <div>
+            <p className="text-sm text-slate-600 mb-1">Art</p>
+            <p className="font-medium text-slate-900">
+              {guest.sepa_mandat_typ === 'B2B' ? 'Firmenlastschrift' : 'Basislastschrift'}
+              {guest.sepa_einmalig ? ', einmalig' : ', wiederkehrend'}
+            </p>
+          </div>
+          <div className="col-span-2">
+            <p className="text-sm text-slate-600 mb-1">Letzte Lastschrift</p>
+            <p className="font-medium text-slate-900">
+              {guest.sepa_letzte_lastschrift
+                ? formatDate(guest.sepa_letzte_lastschrift)
+                : 'Noch keine – nächste Lastschrift ist eine Erstlastschrift'}
+            </p>
+          </div>
+        </div>
+      )}
+
+      {editing && (
+        <form onSubmit={save} className="grid grid-cols-2 gap-3 text-sm">
+          <label className="space-y-1">
+            <span className="text-slate-600">Mandatsreferenz *</span>
+            <input
+              required
+              maxLength={35}
+              value={form.mandatsreferenz}
+              onChange={(e) => setForm({ ...form, mandatsreferenz: e.target.value })}
+              className={inputClass}
+            />
+          </label>
+          <label className="space-y-1">
+            <span className="text-slate-600">Unterschrieben am *</span>
+            <input
+              type="date"
+              required
+              value={form.mandatDatum}
+              onChange={(e) => setForm({ ...form, mandatDatum: e.target.value })}
+              className={inputClass}
+            />
+          </label>
+          <label className="space-y-1">
+            <span className="text-slate-600">IBAN *</span>
+            <input
+              required
+              value={form.iban}
+              onChange={(e) => setForm({ ...form, iban: e.target.value })}
+              placeholder="DE89 3704 0044 0532 0130 00"
+              className={inputClass}
+            />
+          </label>
+          <label className="space-y-1">
+            <span className="text-slate-600">BIC</span>
+            <input
+              value={form.bic}
+              onChange={(e) => setForm({ ...form, bic: e.target.value })}
+              placeholder="optional"
+              className={inputClass}
+            />
+          </label>
+          <label className="space-y-1">
+            <span className="text-slate-600">Kontoinhaber</span>
+            <input
+              maxLength={70}
+              value={form.kontoinhaber}
+              onChange={(e) => setForm({ ...form, kontoinhaber: e.target.value })}
+              placeholder={`${guest.vorname} ${guest.nachname}`}
+              className={inputClass}
+            />
+          </label>
+          <label className="space-y-1">
+            <span className="text-slate-600">Art</span>
+            <select
+              value={form.mandatTyp}
+              onChange={(e) => setForm({ ...form, mandatTyp: e.target.value as 'CORE' | 'B2B' })}
+              className={inputClass}
+            >
+              <option value="CORE">Basislastschrift (CORE)</option>
+              <option value="B2B">Firmenlastschrift (B2B)</option>
+            </select>
+          </label>
+          <label className="col-span-2 flex items-center gap-2">
+            <input
+              type="checkbox"
+              checked={form.einmalig}
+              onChange={(e) => setForm({ ...form, einmalig: e.target.checked })}
+            />
+            <span className="text-slate-700">Einmaliges Mandat (nur für eine Lastschrift)</span>
+          </label>
+          <div className="col-span-2 flex justify-end gap-2">
+            <button
+              type="button"
+              onClick={() => setEditing(false)}
+              className="flex items-center gap-1 px-3 py-1.5 bg-slate-100 hover:bg-slate-200 text-slate-700 rounded-lg font-semibold"
+            >
+              <X className="w-4 h-4" />
+              Abbrechen
+            </button>
+            <button
+              type="submit"
+              disabled={saving}
+              className="flex items-center gap-1 px-3 py-1.5 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold"
+            >
+              <Save className="w-4 h-4" />
+              Speichern
+            </button>
+          </div>
+        </form>
+      )}
+    </div>
+  );
+}
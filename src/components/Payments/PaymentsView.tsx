@@ -0,0 +1,313 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
+import { Landmark, Upload, Check, EyeOff, Undo2, Loader2, ExternalLink } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import { formatDate } from '../../utils/dateFormatting';
+import type { BankImportResult, ImportedBankTransaction } from '../../types/booking';
+import SepaDirectDebits from './SepaDirectDebits';
+import Vouchers from './Vouchers';
+
+interface PaymentsViewProps {
+  onNavigateToBooking?: (bookingId: number) => void;
+}
+
+type Filter = 'offen' | 'zugeordnet' | 'ignoriert';
+type View = 'eingaenge' | 'lastschriften' | 'gutscheine';
+
+// Ab diesem Wert kann der Vorschlag mit "Alle sicheren bestätigen" übernommen werden
+const SAFE_CONFIDENCE = 85;
+
+const confidenceStyle = (confidence: number) =>
+  confidence >= SAFE_CONFIDENCE
+    ? 'bg-emerald-100 text-emerald-800'
+    : confidence >= 60
+      ? 'bg-amber-100 text-amber-800'
+      : 'bg-slate-100 text-slate-700';
+
+export default function PaymentsView({ onNavigateToBooking }: PaymentsViewProps) {
+  const { userName } = useUser();
+  const [view, setView] = useState<View>('eingaenge');
+  const [filter, setFilter] = useState<Filter>('offen');
+  const [transactions, setTransactions] = useState<ImportedBankTransaction[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [busyId, setBusyId] = useState<number | null>(null);
+
+  useEffect(() => {
+    loadTransactions();
+  }, [filter]);
+
+  const loadTransactions = async () => {
+    try {
+      setLoading(true);
+      setTransactions(await invoke<ImportedBankTransaction[]>('get_bank_transactions_pg', { status: filter }));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Umsätze: ${error}`);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const handleImport = async () => {
+    const file = await open({
+      filters: [{ name: 'Kontoauszug (CAMT.053, MT940)', extensions: ['xml', 'sta', 'mt940', 'txt'] }],
+      multiple: false,
+      directory: false,
+    });
+    if (!file) return;
+
+    const toastId = toast.loading('Kontoauszug wird importiert...');
+    try {
+      const result = await invoke<BankImportResult>('import_bank_statement_pg', { filePath: file, currentUser: userName });
+      toast.success(
+        `${result.imported} neue Zahlungseingänge importiert` +
+          (result.duplicates > 0 ? `, ${result.duplicates} bereits vorhanden` : ''),
+        { id: toastId, duration: 5000 }
+      );
+      setFilter('offen');
+      setTransactions(result.transactions);
+    } catch (error) {
+      toast.error(`Import fehlgeschlagen: ${error}`, { id: toastId, duration: 6000 });
+    }
+  };
+
+  const confirm = async (transaction: ImportedBankTransaction, bookingId: number, confidence: number | null) => {
+    setBusyId(transaction.id);
+    try {
+      const remaining = await invoke<ImportedBankTransaction[]>('confirm_bank_transaction_pg', {
+        id: transaction.id,
+        bookingId,
+        confidence,
+        currentUser: userName,
+      });
+      setTransactions(remaining);
+      toast.success('Zahlung gebucht');
+    } catch (error) {
+      toast.error(`Fehler beim Zuordnen: ${error}`);
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const confirmAllSafe = async () => {
+    const safe = transactions.filter(
+      (t) => t.proposals[0]?.confidence >= SAFE_CONFIDENCE && (t.proposals[1]?.confidence ?? 0) < SAFE_CONFIDENCE
+    );
+    let confirmed = 0;
+    for (const transaction of safe) {
+      try {
+        await invoke('confirm_bank_transaction_pg', {
+          id: transaction.id,
+          bookingId: transaction.proposals[0].bookingId,
+          confidence: transaction.proposals[0].confidence,
+          currentUser: userName,
+        });
+        confirmed++;
+      } catch (error) {
+        console.error('Zuordnung fehlgeschlagen:', error);
+      }
+    }
+    toast.success(`${confirmed} Zahlungen gebucht`);
+    loadTransactions();
+  };
+
+  const ignore = async (transaction: ImportedBankTransaction) => {
+    setBusyId(transaction.id);
+    try {
+      setTransactions(
+        await invoke<ImportedBankTransaction[]>('ignore_bank_transaction_pg', { id: transaction.id, currentUser: userName })
+      );
+    } catch (error) {
+      toast.error(`Fehler: ${error}`);
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const undo = async (transaction: ImportedBankTransaction) => {
+    setBusyId(transaction.id);
+    try {
+      await invoke('undo_bank_transaction_pg', { id: transaction.id });
+      toast.success(transaction.paymentId ? 'Zahlung storniert, Umsatz ist wieder offen' : 'Umsatz ist wieder offen');
+      loadTransactions();
+    } catch (error) {
+      toast.error(`Fehler: ${error}`);
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const safeCount = transactions.filter((t) => t.status === 'offen' && t.proposals[0]?.confidence >= SAFE_CONFIDENCE).length;
+
+  const viewSwitch = (
+    <div className="flex gap-1 p-1 bg-slate-100 rounded-lg w-fit">
+      {(['eingaenge', 'lastschriften', 'gutscheine'] as View[]).map((v) => (
+        <button
+          key={v}
+          onClick={() => setView(v)}
+          className={`px-4 py-1.5 rounded-md text-sm font-semibold transition-colors ${
+            view === v ? 'bg-white text-slate-900 shadow-sm' : 'text-slate-600 hover:text-slate-900'
+          }`}
+        >
+          {v === 'eingaenge' ? 'Zahlungseingänge' : v === 'lastschriften' ? 'SEPA-Lastschriften' : 'Gutscheine'}
+        </button>
+      ))}
+    </div>
+  );
+
+  if (view === 'lastschriften') {
+    return (
+      <div className="h-full overflow-y-auto p-6">
+        <div className="max-w-6xl mx-auto space-y-4">
+          {viewSwitch}
+          <SepaDirectDebits onNavigateToBooking={onNavigateToBooking} />
+        </div>
+      </div>
+    );
+  }
+
+  if (view === 'gutscheine') {
+    return (
+      <div className="h-full overflow-y-auto p-6">
+        <div className="max-w-6xl mx-auto space-y-4">
+          {viewSwitch}
+          <Vouchers onNavigateToBooking={onNavigateToBooking} />
+        </div>
+      </div>
+    );
+  }
+
+  return (
+    <div className="h-full overflow-y-auto p-6">
+      <div className="max-w-6xl mx-auto space-y-4">
+        {viewSwitch}
+        <div className="flex items-center justify-between">
+          <h2 className="flex items-center gap-2 text-2xl font-bold text-slate-800">
+            <Landmark className="w-6 h-6 text-blue-600" />
+            Zahlungseingänge
+          </h2>
+          <div className="flex gap-2">
+            {filter === 'offen' && safeCount > 0 && (
+              <button
+                onClick={confirmAllSafe}
+                className="flex items-center gap-2 px-4 py-2 bg-emerald-600 hover:bg-emerald-700 text-white rounded-lg font-semibold transition-colors"
+              >
+                <Check className="w-4 h-4" />
+                Alle sicheren bestätigen ({safeCount})
+              </button>
+            )}
+            <button
+              onClick={handleImport}
+              className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 text-white rounded-lg font-semibold transition-colors"
+            >
+              <Upload className="w-4 h-4" />
+              Kontoauszug importieren
+            </button>
+          </div>
+        </div>
+
+        <div className="flex gap-2">
+          {(['offen', 'zugeordnet', 'ignoriert'] as Filter[]).map((f) => (
+            <button
+              key={f}
+              onClick={() => setFilter(f)}
+              className={`px-3 py-1.5 rounded-lg text-sm font-semibold transition-colors ${
+                filter === f ? 'bg-slate-800 text-white' : 'bg-white text-slate-600 hover:bg-slate-100 border border-slate-200'
+              }`}
+            >
+              {f === 'offen' ? 'Offen' : f === 'zugeordnet' ? 'Zugeordnet' : 'Ignoriert'}
+            </button>
+          ))}
+        </div>
+
+        {loading && <Loader2 className="w-6 h-6 animate-spin text-slate-400" />}
+        {!loading && transactions.length === 0 && (
+          <p className="text-slate-500">Keine Umsätze in dieser Ansicht.</p>
+        )}
+
+        {transactions.map((transaction) => (
+          <div key={transaction.id} className="bg-white border border-slate-200 rounded-lg p-4 shadow-sm">
+            <div className="flex items-start justify-between gap-4">
+              <div className="min-w-0">
+                <p className="font-semibold text-slate-900">
+                  {transaction.payerName ?? 'Unbekannter Zahler'}
+                  <span className="ml-2 text-sm font-normal text-slate-500">{formatDate(transaction.bookingDate)}</span>
+                </p>
+                <p className="text-sm text-slate-600 break-words">{transaction.remittance || '–'}</p>
+                {transaction.payerIban && <p className="text-xs text-slate-400">{transaction.payerIban}</p>}
+              </div>
+              <div className="text-right shrink-0">
+                <p className="text-lg font-bold text-emerald-700">{transaction.amount.toFixed(2)} €</p>
+                {transaction.status !== 'offen' && (
+                  <button
+                    disabled={busyId === transaction.id}
+                    onClick={() => undo(transaction)}
+                    className="mt-1 flex items-center gap-1 text-sm text-slate-600 hover:text-slate-900"
+                  >
+                    <Undo2 className="w-4 h-4" />
+                    Rückgängig
+                  </button>
+                )}
+              </div>
+            </div>
+
+            {transaction.status === 'zugeordnet' && transaction.bookingId && (
+              <button
+                onClick={() => onNavigateToBooking?.(transaction.bookingId!)}
+                className="mt-2 flex items-center gap-1 text-sm font-semibold text-blue-600 hover:text-blue-700"
+              >
+                <ExternalLink className="w-4 h-4" />
+                Buchung öffnen
+                {transaction.confidence != null && ` (Treffer ${transaction.confidence} %)`}
+              </button>
+            )}
+
+            {transaction.status === 'offen' && (
+              <div className="mt-3 space-y-2">
+                {transaction.proposals.length === 0 && (
+                  <p className="text-sm text-slate-500">Keine passende Buchung gefunden.</p>
+                )}
+                {transaction.proposals.map((proposal) => (
+                  <div
+                    key={proposal.bookingId}
+                    className="flex items-center justify-between gap-3 p-2 bg-slate-50 border border-slate-200 rounded-lg"
+                  >
+                    <div className="flex items-center gap-3 min-w-0">
+                      <span className={`px-2 py-0.5 rounded text-xs font-bold ${confidenceStyle(proposal.confidence)}`}>
+                        {proposal.confidence} %
+                      </span>
+                      <button
+                        onClick={() => onNavigateToBooking?.(proposal.bookingId)}
+                        className="font-semibold text-blue-700 hover:underline"
+                      >
+                        {proposal.reservierungsnummer}
+                      </button>
+                      <span className="text-sm text-slate-600 truncate">{proposal.reasons.join(', ')}</span>
+                    </div>
+                    <button
+                      disabled={busyId === transaction.id}
+                      onClick={() => confirm(transaction, proposal.bookingId, proposal.confidence)}
+                      className="flex items-center gap-1 px-3 py-1.5 bg-emerald-600 hover:bg-emerald-700 disabled:opacity-50 text-white rounded-lg text-sm font-semibold"
+                    >
+                      <Check className="w-4 h-4" />
+                      Zuordnen
+                    </button>
+                  </div>
+                ))}
+                <button
+                  disabled={busyId === transaction.id}
+                  onClick={() => ignore(transaction)}
+                  className="flex items-center gap-1 text-sm text-slate-500 hover:text-slate-700"
+                >
+                  <EyeOff className="w-4 h-4" />
+                  Keine Buchungszahlung (ignorieren)
+                </button>
+              </div>
+            )}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,301 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { FileDown, Loader2, AlertTriangle, Undo2, ExternalLink } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import { formatDate } from '../../utils/dateFormatting';
+import type { SepaBatch, SepaCollection, SepaDuePreview } from '../../types/booking';
+
+interface SepaDirectDebitsProps {
+  onNavigateToBooking?: (bookingId: number) => void;
+}
+
+type CollectionFilter = SepaCollection['status'];
+
+// Häufige Rückgabegründe (ISO 20022), Texte wie im Backend (sepa.rs)
+const RETURN_REASONS: [string, string][] = [
+  ['AM04', 'Deckung unzureichend'],
+  ['MD06', 'Rückgabe auf Verlangen des Zahlers'],
+  ['MS02', 'Widerspruch des Zahlers'],
+  ['MS03', 'Grund nicht angegeben'],
+  ['AC04', 'Konto aufgelöst'],
+  ['AC06', 'Konto gesperrt'],
+  ['AC01', 'IBAN fehlerhaft'],
+  ['MD01', 'Kein gültiges Mandat'],
+  ['MD02', 'Mandatsdaten fehlen oder sind fehlerhaft'],
+  ['MD07', 'Zahler verstorben'],
+  ['AG01', 'Lastschrift auf diesem Konto nicht zulässig'],
+  ['AM05', 'Doppelte Einreichung'],
+  ['BE05', 'Gläubiger-ID unbekannt'],
+  ['FF01', 'Ungültiges Dateiformat'],
+  ['SL01', 'Lastschriftsperre der Bank des Zahlers'],
+];
+
+const FILTER_LABELS: Record<CollectionFilter, string> = {
+  ausstehend: 'Ausstehend',
+  eingezogen: 'Eingezogen',
+  zurueckgegeben: 'Zurückgegeben',
+};
+
+const today = () => new Date().toISOString().slice(0, 10);
+
+export default function SepaDirectDebits({ onNavigateToBooking }: SepaDirectDebitsProps) {
+  const { userName } = useUser();
+  const [preview, setPreview] = useState<SepaDuePreview | null>(null);
+  const [collectionDate, setCollectionDate] = useState<string | null>(null);
+  const [filter, setFilter] = useState<CollectionFilter>('ausstehend');
+  const [collections, setCollections] = useState<SepaCollection[]>([]);
+  const [batches, setBatches] = useState<SepaBatch[]>([]);
+  const [exporting, setExporting] = useState(false);
+  const [returning, setReturning] = useState<{ id: number; reasonCode: string; returnedAt: string } | null>(null);
+
+  useEffect(() => {
+    invoke<SepaDuePreview>('get_sepa_due_debits_pg', { collectionDate })
+      .then(setPreview)
+      .catch((error) => toast.error(`Fehler beim Laden der fälligen Lastschriften: ${error}`));
+  }, [collectionDate]);
+
+  useEffect(() => {
+    loadCollections();
+  }, [filter]);
+
+  useEffect(() => {
+    loadBatches();
+  }, []);
+
+  const loadCollections = async () => {
+    try {
+      setCollections(await invoke<SepaCollection[]>('get_sepa_collections_pg', { status: filter }));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Lastschriften: ${error}`);
+    }
+  };
+
+  const loadBatches = async () => {
+    try {
+      setBatches(await invoke<SepaBatch[]>('get_sepa_batches_pg'));
+    } catch (error) {
+      console.error('Sammler konnten nicht geladen werden:', error);
+    }
+  };
+
+  const handleExport = async () => {
+    if (!preview) return;
+    setExporting(true);
+    try {
+      const batch = await invoke<SepaBatch>('export_sepa_direct_debits_pg', {
+        collectionDate: preview.collectionDate,
+        currentUser: userName,
+      });
+      toast.success(
+        `${batch.transactionCount} Lastschriften (${batch.total.toFixed(2)} €) exportiert:\n${batch.filePath ?? ''}`,
+        { duration: 8000 }
+      );
+      setPreview(await invoke<SepaDuePreview>('get_sepa_due_debits_pg', { collectionDate: preview.collectionDate }));
+      setFilter('ausstehend');
+      loadCollections();
+      loadBatches();
+    } catch (error) {
+      toast.error(`Export fehlgeschlagen: ${error}`, { duration: 8000 });
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  const recordReturn = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!returning) return;
+    try {
+      await invoke<SepaCollection>('record_sepa_return_pg', {
+        id: returning.id,
+        reasonCode: returning.reasonCode,
+        returnedAt: returning.returnedAt,
+        currentUser: userName,
+      });
+      toast.success('Rücklastschrift erfasst');
+      setReturning(null);
+      loadCollections();
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const exportable = preview?.debits.filter((d) => !d.problem) ?? [];
+  const exportTotal = exportable.reduce((sum, d) => sum + d.amount, 0);
+
+  return (
+    <div className="space-y-6">
+      {/* Fällige Lastschriften */}
+      <div className="bg-white border border-slate-200 rounded-lg p-4 shadow-sm space-y-3">
+        <div className="flex flex-wrap items-end justify-between gap-3">
+          <div>
+            <h3 className="text-lg font-bold text-slate-800">Fällige Lastschriften</h3>
+            <p className="text-sm text-slate-500">
+              Buchungen von Gästen mit Mandat: nach der Abreise der offene Betrag, vorher fällige Anzahlungen.
+            </p>
+          </div>
+          <div className="flex items-end gap-2">
+            <label className="text-sm">
+              <span className="block text-slate-600 mb-1">Einzugsdatum</span>
+              <input
+                type="date"
+                min={preview?.earliestCollectionDate}
+                value={preview?.collectionDate ?? ''}
+                onChange={(e) => setCollectionDate(e.target.value || null)}
+                className="px-3 py-1.5 border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+              />
+            </label>
+            <button
+              onClick={handleExport}
+              disabled={exporting || exportable.length === 0}
+              className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold transition-colors"
+            >
+              {exporting ? <Loader2 className="w-4 h-4 animate-spin" /> : <FileDown className="w-4 h-4" />}
+              pain.008 exportieren ({exportable.length}, {exportTotal.toFixed(2)} €)
+            </button>
+          </div>
+        </div>
+
+        {preview && preview.debits.length === 0 && (
+          <p className="text-sm text-slate-500">Zum {formatDate(preview.collectionDate)} ist nichts fällig.</p>
+        )}
+        {preview?.debits.map((debit) => (
+          <div
+            key={debit.bookingId}
+            className="flex items-center justify-between gap-3 py-1.5 border-b border-slate-100 text-sm"
+          >
+            <div className="flex items-center gap-3 min-w-0">
+              <button
+                onClick={() => onNavigateToBooking?.(debit.bookingId)}
+                className="font-semibold text-blue-700 hover:underline"
+              >
+                {debit.reservierungsnummer}
+              </button>
+              <span className="text-slate-700 truncate">{debit.guestName}</span>
+              <span className="text-slate-400">Abreise {formatDate(debit.checkoutDate)}</span>
+              {debit.problem ? (
+                <span className="flex items-center gap-1 text-amber-700">
+                  <AlertTriangle className="w-4 h-4" />
+                  {debit.problem}
+                </span>
+              ) : (
+                <span className="px-2 py-0.5 rounded bg-slate-100 text-slate-700 text-xs font-bold">{debit.sequenceType}</span>
+              )}
+            </div>
+            <span className={`font-semibold ${debit.problem ? 'text-slate-400 line-through' : 'text-slate-900'}`}>
+              {debit.amount.toFixed(2)} €
+            </span>
+          </div>
+        ))}
+      </div>
+
+      {/* Lastschriften nach Status */}
+      <div className="space-y-3">
+        <div className="flex gap-2">
+          {(Object.keys(FILTER_LABELS) as CollectionFilter[]).map((f) => (
+            <button
+              key={f}
+              onClick={() => setFilter(f)}
+              className={`px-3 py-1.5 rounded-lg text-sm font-semibold transition-colors ${
+                filter === f ? 'bg-slate-800 text-white' : 'bg-white text-slate-600 hover:bg-slate-100 border border-slate-200'
+              }`}
+            >
+              {FILTER_LABELS[f]}
+            </button>
+          ))}
+        </div>
+
+        {collections.length === 0 && <p className="text-slate-500">Keine Lastschriften in dieser Ansicht.</p>}
+        {collections.map((collection) => (
+          <div key={collection.id} className="bg-white border border-slate-200 rounded-lg p-3 shadow-sm text-sm">
+            <div className="flex items-center justify-between gap-3">
+              <div className="flex items-center gap-3 min-w-0">
+                <button
+                  onClick={() => onNavigateToBooking?.(collection.bookingId)}
+                  className="flex items-center gap-1 font-semibold text-blue-700 hover:underline"
+                >
+                  <ExternalLink className="w-4 h-4" />
+                  {collection.reservierungsnummer}
+                </button>
+                <span className="text-slate-700 truncate">{collection.guestName}</span>
+                <span className="text-slate-400">
+                  {formatDate(collection.collectionDate)} · {collection.sequenceType} · {collection.mandateReference}
+                </span>
+              </div>
+              <div className="flex items-center gap-3">
+                <span className="font-semibold text-slate-900">{collection.amount.toFixed(2)} €</span>
+                {collection.status !== 'zurueckgegeben' && returning?.id !== collection.id && (
+                  <button
+                    onClick={() => setReturning({ id: collection.id, reasonCode: 'AM04', returnedAt: today() })}
+                    className="flex items-center gap-1 text-slate-600 hover:text-red-700"
+                  >
+                    <Undo2 className="w-4 h-4" />
+                    Rücklastschrift
+                  </button>
+                )}
+              </div>
+            </div>
+            {collection.status === 'zurueckgegeben' && (
+              <p className="mt-1 text-red-700">
+                Zurückgegeben am {collection.returnedAt ? formatDate(collection.returnedAt) : '–'}: {collection.returnReason}
+                {collection.returnReasonText ? ` – ${collection.returnReasonText}` : ''}
+              </p>
+            )}
+            {returning?.id === collection.id && (
+              <form onSubmit={recordReturn} className="mt-2 flex flex-wrap items-center gap-2">
+                <select
+                  value={returning.reasonCode}
+                  onChange={(e) => setReturning({ ...returning, reasonCode: e.target.value })}
+                  className="px-3 py-1.5 border border-slate-200 rounded-lg text-sm"
+                >
+                  {RETURN_REASONS.map(([code, text]) => (
+                    <option key={code} value={code}>
+                      {code} – {text}
+                    </option>
+                  ))}
+                </select>
+                <input
+                  type="date"
+                  required
+                  value={returning.returnedAt}
+                  onChange={(e) => setReturning({ ...returning, returnedAt: e.target.value })}
+                  className="px-3 py-1.5 border border-slate-200 rounded-lg text-sm"
+                />
+                <button type="submit" className="px-3 py-1.5 bg-red-600 hover:bg-red-700 text-white rounded-lg font-semibold">
+                  Erfassen
+                </button>
+                <button
+                  type="button"
+                  onClick={() => setReturning(null)}
+                  className="px-3 py-1.5 bg-slate-100 hover:bg-slate-200 text-slate-700 rounded-lg font-semibold"
+                >
+                  Abbrechen
+                </button>
+              </form>
+            )}
+          </div>
+        ))}
+      </div>
+
+      {/* Exportierte Sammler */}
+      {batches.length > 0 && (
+        <div className="bg-white border border-slate-200 rounded-lg p-4 shadow-sm">
+          <h3 className="text-lg font-bold text-slate-800 mb-2">Exportierte Dateien</h3>
+          {batches.map((batch) => (
+            <div key={batch.id} className="flex items-center justify-between gap-3 py-1 border-b border-slate-100 text-sm">
+              <span className="text-slate-700">
+                {batch.messageId} · Einzug {formatDate(batch.collectionDate)} · {batch.transactionCount} Lastschriften
+              </span>
+              <span className="flex items-center gap-3">
+                <span className="text-xs text-slate-400 truncate max-w-xs" title={batch.filePath ?? ''}>
+                  {batch.filePath}
+                </span>
+                <span className="font-semibold">{batch.total.toFixed(2)} €</span>
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,301 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Gift, Plus, Printer, Ban, Loader2, ChevronDown, ChevronRight, ExternalLink } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import { useData } from '../../context/DataContext';
+import { formatDate } from '../../utils/dateFormatting';
+import SearchableGuestPicker from '../BookingManagement/SearchableGuestPicker';
+import type { Voucher, VoucherRedemption } from '../../types/booking';
+
+interface VouchersProps {
+  onNavigateToBooking?: (bookingId: number) => void;
+}
+
+type StatusFilter = Voucher['status'] | 'alle';
+
+const STATUS_LABELS: Record<Voucher['status'], string> = {
+  aktiv: 'Aktiv',
+  eingeloest: 'Eingelöst',
+  abgelaufen: 'Abgelaufen',
+  storniert: 'Storniert',
+};
+
+const STATUS_STYLES: Record<Voucher['status'], string> = {
+  aktiv: 'bg-emerald-100 text-emerald-800',
+  eingeloest: 'bg-slate-100 text-slate-700',
+  abgelaufen: 'bg-amber-100 text-amber-800',
+  storniert: 'bg-red-100 text-red-800',
+};
+
+const emptyForm = {
+  value: '',
+  purchaserGuestId: 0,
+  purchaserName: '',
+  recipientName: '',
+  message: '',
+  expiresAt: '',
+  notes: '',
+};
+
+export default function Vouchers({ onNavigateToBooking }: VouchersProps) {
+  const { userName } = useUser();
+  const { guests } = useData();
+  const [filter, setFilter] = useState<StatusFilter>('aktiv');
+  const [vouchers, setVouchers] = useState<Voucher[]>([]);
+  const [form, setForm] = useState(emptyForm);
+  const [saving, setSaving] = useState(false);
+  const [expanded, setExpanded] = useState<number | null>(null);
+  const [redemptions, setRedemptions] = useState<VoucherRedemption[]>([]);
+  const [printingId, setPrintingId] = useState<number | null>(null);
+
+  useEffect(() => {
+    loadVouchers();
+  }, [filter]);
+
+  const loadVouchers = async () => {
+    try {
+      setVouchers(await invoke<Voucher[]>('get_vouchers_pg', { status: filter === 'alle' ? null : filter }));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Gutscheine: ${error}`);
+    }
+  };
+
+  const toggleRedemptions = async (voucher: Voucher) => {
+    if (expanded === voucher.id) {
+      setExpanded(null);
+      return;
+    }
+    try {
+      setRedemptions(await invoke<VoucherRedemption[]>('get_voucher_redemptions_pg', { voucherId: voucher.id }));
+      setExpanded(voucher.id);
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const printVoucher = async (voucher: Voucher) => {
+    setPrintingId(voucher.id);
+    try {
+      const path = await invoke<string>('generate_voucher_pdf_pg', { id: voucher.id });
+      await invoke('open_pdf_file_command', { path });
+    } catch (error) {
+      toast.error(`PDF konnte nicht erstellt werden: ${error}`);
+    } finally {
+      setPrintingId(null);
+    }
+  };
+
+  const createVoucher = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setSaving(true);
+    try {
+      const voucher = await invoke<Voucher>('create_voucher_pg', {
+        value: Number(form.value.replace(',', '.')),
+        issuedAt: null,
+        expiresAt: form.expiresAt || null,
+        purchaserGuestId: form.purchaserGuestId || null,
+        purchaserName: form.purchaserName || null,
+        recipientName: form.recipientName || null,
+        message: form.message || null,
+        notes: form.notes || null,
+        currentUser: userName,
+      });
+      toast.success(`Gutschein ${voucher.code} angelegt`);
+      setForm(emptyForm);
+      setFilter('aktiv');
+      loadVouchers();
+      printVoucher(voucher);
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const cancelVoucher = async (voucher: Voucher) => {
+    if (!window.confirm(`Gutschein ${voucher.code} stornieren?`)) return;
+    try {
+      await invoke<Voucher>('cancel_voucher_pg', { id: voucher.id });
+      toast.success('Gutschein storniert');
+      loadVouchers();
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const inputClass = 'px-3 py-1.5 border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+  const openValue = vouchers.filter((v) => v.status === 'aktiv').reduce((sum, v) => sum + v.remaining, 0);
+
+  return (
+    <div className="space-y-6">
+      {/* Verkauf */}
+      <form onSubmit={createVoucher} className="bg-white border border-slate-200 rounded-lg p-4 shadow-sm space-y-3">
+        <div>
+          <h3 className="flex items-center gap-2 text-lg font-bold text-slate-800">
+            <Gift className="w-5 h-5 text-blue-600" />
+            Gutschein verkaufen
+          </h3>
+          <p className="text-sm text-slate-500">
+            Ohne Ablaufdatum gilt der Gutschein bis zum 31.12. des dritten Jahres nach dem Kauf.
+          </p>
+        </div>
+        <div className="grid grid-cols-1 md:grid-cols-3 gap-3">
+          <input
+            type="text"
+            inputMode="decimal"
+            required
+            placeholder="Wert €"
+            value={form.value}
+            onChange={(e) => setForm({ ...form, value: e.target.value })}
+            className={inputClass}
+          />
+          <input
+            type="text"
+            placeholder="Für (Beschenkte/r)"
+            value={form.recipientName}
+            onChange={(e) => setForm({ ...form, recipientName: e.target.value })}
+            className={inputClass}
+          />
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Gültig bis
+            <input
+              type="date"
+              value={form.expiresAt}
+              onChange={(e) => setForm({ ...form, expiresAt: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <SearchableGuestPicker
+            guests={guests}
+            selectedGuestId={form.purchaserGuestId}
+            onSelectGuest={(guestId) => setForm({ ...form, purchaserGuestId: guestId })}
+          />
+          <input
+            type="text"
+            placeholder="oder Käufer (kein Gast)"
+            value={form.purchaserName}
+            onChange={(e) => setForm({ ...form, purchaserName: e.target.value })}
+            className={inputClass}
+          />
+          <input
+            type="text"
+            placeholder="Interne Notiz (z.B. bar bezahlt)"
+            value={form.notes}
+            onChange={(e) => setForm({ ...form, notes: e.target.value })}
+            className={inputClass}
+          />
+        </div>
+        <textarea
+          rows={2}
+          placeholder="Persönlicher Text auf dem Gutschein (z.B. Frohe Weihnachten!)"
+          value={form.message}
+          onChange={(e) => setForm({ ...form, message: e.target.value })}
+          className={`${inputClass} w-full resize-none`}
+        />
+        <button
+          type="submit"
+          disabled={saving}
+          className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold transition-colors"
+        >
+          {saving ? <Loader2 className="w-4 h-4 animate-spin" /> : <Plus className="w-4 h-4" />}
+          Anlegen und drucken
+        </button>
+      </form>
+
+      {/* Gutscheine nach Status */}
+      <div className="space-y-3">
+        <div className="flex items-center justify-between">
+          <div className="flex gap-2">
+            {(['aktiv', 'eingeloest', 'abgelaufen', 'storniert', 'alle'] as StatusFilter[]).map((f) => (
+              <button
+                key={f}
+                onClick={() => setFilter(f)}
+                className={`px-3 py-1.5 rounded-lg text-sm font-semibold transition-colors ${
+                  filter === f ? 'bg-slate-800 text-white' : 'bg-white text-slate-600 hover:bg-slate-100 border border-slate-200'
+                }`}
+              >
+                {f === 'alle' ? 'Alle' : STATUS_LABELS[f]}
+              </button>
+            ))}
+          </div>
+          {openValue > 0 && <span className="text-sm text-slate-600">Offener Gutscheinwert: {openValue.toFixed(2)} €</span>}
+        </div>
+
+        {vouchers.length === 0 && <p className="text-slate-500">Keine Gutscheine in dieser Ansicht.</p>}
+        {vouchers.map((voucher) => (
+          <div key={voucher.id} className="bg-white border border-slate-200 rounded-lg p-3 shadow-sm text-sm">
+            <div className="flex items-center justify-between gap-3">
+              <div className="flex items-center gap-3 min-w-0">
+                <button onClick={() => toggleRedemptions(voucher)} className="text-slate-500 hover:text-slate-800">
+                  {expanded === voucher.id ? <ChevronDown className="w-4 h-4" /> : <ChevronRight className="w-4 h-4" />}
+                </button>
+                <span className="font-mono font-semibold text-slate-900">{voucher.code}</span>
+                <span className={`px-2 py-0.5 rounded text-xs font-semibold ${STATUS_STYLES[voucher.status]}`}>
+                  {STATUS_LABELS[voucher.status]}
+                </span>
+                <span className="text-slate-700 truncate">
+                  {voucher.purchaserName ?? '–'}
+                  {voucher.recipientName ? ` → ${voucher.recipientName}` : ''}
+                </span>
+                <span className="text-slate-400">
+                  {formatDate(voucher.issuedAt)} · gültig bis {formatDate(voucher.expiresAt)}
+                </span>
+              </div>
+              <div className="flex items-center gap-3">
+                <span className="font-semibold text-slate-900">
+                  {voucher.remaining < voucher.value
+                    ? `${voucher.remaining.toFixed(2)} € von ${voucher.value.toFixed(2)} €`
+                    : `${voucher.value.toFixed(2)} €`}
+                </span>
+                {voucher.status !== 'storniert' && (
+                  <button
+                    onClick={() => printVoucher(voucher)}
+                    disabled={printingId === voucher.id}
+                    className="flex items-center gap-1 text-slate-600 hover:text-blue-700"
+                    title="Gutschein drucken"
+                  >
+                    {printingId === voucher.id ? <Loader2 className="w-4 h-4 animate-spin" /> : <Printer className="w-4 h-4" />}
+                  </button>
+                )}
+                {voucher.status === 'aktiv' && voucher.remaining === voucher.value && (
+                  <button
+                    onClick={() => cancelVoucher(voucher)}
+                    className="flex items-center gap-1 text-slate-600 hover:text-red-700"
+                    title="Gutschein stornieren"
+                  >
+                    <Ban className="w-4 h-4" />
+                  </button>
+                )}
+              </div>
+            </div>
+            {voucher.notes && <p className="mt-1 ml-7 text-slate-500">{voucher.notes}</p>}
+            {expanded === voucher.id && (
+              <div className="mt-2 ml-7 space-y-1">
+                {redemptions.length === 0 && <p className="text-slate-500">Noch nicht eingelöst.</p>}
+                {redemptions.map((redemption) => (
+                  <div key={redemption.id} className="flex items-center justify-between border-b border-slate-100 py-1">
+                    <span className="flex items-center gap-3">
+                      <button
+                        onClick={() => onNavigateToBooking?.(redemption.bookingId)}
+                        className="flex items-center gap-1 font-semibold text-blue-700 hover:underline"
+                      >
+                        <ExternalLink className="w-4 h-4" />
+                        {redemption.reservierungsnummer}
+                      </button>
+                      <span className="text-slate-700">{redemption.guestName}</span>
+                      <span className="text-slate-400">
+                        {redemption.createdAt ? formatDate(redemption.createdAt.slice(0, 10)) : '–'}
+                      </span>
+                    </span>
+                    <span className="font-semibold">{redemption.amount.toFixed(2)} €</span>
+                  </div>
+                ))}
+              </div>
+            )}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,464 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Package, Plus, Save, Unlock, Trash2, CalendarPlus, Loader2 } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import { useData } from '../../context/DataContext';
+import { formatDate } from '../../utils/dateFormatting';
+import SearchableGuestPicker from '../BookingManagement/SearchableGuestPicker';
+import type { Allotment, AllotmentNight, Booking } from '../../types/booking';
+
+interface AllotmentsProps {
+  onNavigateToBooking?: (bookingId: number) => void;
+}
+
+interface AllotmentForm {
+  id: number | null;
+  name: string;
+  guestId: number;
+  gebaeudeTyp: string;
+  roomIds: number[];
+  startDate: string;
+  endDate: string;
+  quantity: string;
+  releaseDate: string;
+  bemerkungen: string;
+}
+
+interface DrawForm {
+  allotmentId: number;
+  roomId: number;
+  guestId: number;
+  checkinDate: string;
+  checkoutDate: string;
+  anzahlGaeste: string;
+}
+
+const emptyForm: AllotmentForm = {
+  id: null,
+  name: '',
+  guestId: 0,
+  gebaeudeTyp: '',
+  roomIds: [],
+  startDate: '',
+  endDate: '',
+  quantity: '1',
+  releaseDate: '',
+  bemerkungen: '',
+};
+
+const toForm = (allotment: Allotment): AllotmentForm => ({
+  id: allotment.id,
+  name: allotment.name,
+  guestId: allotment.guestId ?? 0,
+  gebaeudeTyp: allotment.gebaeudeTyp ?? '',
+  roomIds: allotment.roomIds,
+  startDate: allotment.startDate,
+  endDate: allotment.endDate,
+  quantity: String(allotment.quantity),
+  releaseDate: allotment.releaseDate,
+  bemerkungen: allotment.bemerkungen ?? '',
+});
+
+// Kontingente: für Partner freigehaltene Zimmer, abrufbar bis zur Freigabefrist
+export default function Allotments({ onNavigateToBooking }: AllotmentsProps) {
+  const { userName } = useUser();
+  const { guests, rooms, guestMap, refreshBookings } = useData();
+  const [allotments, setAllotments] = useState<Allotment[]>([]);
+  const [nights, setNights] = useState<AllotmentNight[]>([]);
+  const [form, setForm] = useState<AllotmentForm>(emptyForm);
+  const [draw, setDraw] = useState<DrawForm | null>(null);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    loadAllotments();
+  }, []);
+
+  const loadAllotments = async () => {
+    try {
+      const data = await invoke<Allotment[]>('get_allotments_pg');
+      setAllotments(data);
+      if (data.length > 0) {
+        const from = data.reduce((min, a) => (a.startDate < min ? a.startDate : min), data[0].startDate);
+        const to = data.reduce((max, a) => (a.endDate > max ? a.endDate : max), data[0].endDate);
+        setNights(await invoke<AllotmentNight[]>('get_allotment_nights_pg', { from, to }));
+      }
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Kontingente: ${error}`);
+    }
+  };
+
+  const saveAllotment = async (e: React.FormEvent) => {
+    e.preventDefault();
+    setSaving(true);
+    try {
+      const args = {
+        name: form.name.trim(),
+        guestId: form.guestId || null,
+        gebaeudeTyp: form.gebaeudeTyp || null,
+        roomIds: form.gebaeudeTyp ? [] : form.roomIds,
+        startDate: form.startDate,
+        endDate: form.endDate,
+        quantity: parseInt(form.quantity) || 1,
+        releaseDate: form.releaseDate,
+        bemerkungen: form.bemerkungen || null,
+      };
+      const saved =
+        form.id === null
+          ? await invoke<Allotment>('create_allotment_pg', { ...args, currentUser: userName })
+          : await invoke<Allotment>('update_allotment_pg', { ...args, id: form.id });
+      toast.success(`Kontingent ${saved.name} gespeichert`);
+      setForm(emptyForm);
+      loadAllotments();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const releaseAllotment = async (allotment: Allotment) => {
+    if (!window.confirm(`Nicht abgerufene Zimmer von ${allotment.name} jetzt freigeben?`)) return;
+    try {
+      await invoke<Allotment>('release_allotment_pg', { id: allotment.id });
+      toast.success('Kontingent freigegeben');
+      loadAllotments();
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const deleteAllotment = async (allotment: Allotment) => {
+    if (!window.confirm(`Kontingent ${allotment.name} löschen? Bereits gebuchte Zimmer bleiben bestehen.`)) return;
+    try {
+      await invoke('delete_allotment_pg', { id: allotment.id });
+      loadAllotments();
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  // Zimmer, aus denen das Kontingent abgerufen werden kann
+  const allotmentRooms = (allotment: Allotment) =>
+    rooms.filter((room) =>
+      allotment.gebaeudeTyp ? room.gebaeude_typ === allotment.gebaeudeTyp : allotment.roomIds.includes(room.id)
+    );
+
+  const startDraw = (allotment: Allotment) => {
+    setDraw({
+      allotmentId: allotment.id,
+      roomId: allotmentRooms(allotment)[0]?.id ?? 0,
+      guestId: allotment.guestId ?? 0,
+      checkinDate: allotment.startDate,
+      checkoutDate: allotment.endDate,
+      anzahlGaeste: '1',
+    });
+  };
+
+  const bookFromAllotment = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!draw) return;
+    if (!draw.guestId) {
+      toast.error('Bitte einen Gast wählen');
+      return;
+    }
+    setSaving(true);
+    try {
+      const booking = await invoke<Booking>('book_from_allotment_pg', {
+        allotmentId: draw.allotmentId,
+        roomId: draw.roomId,
+        guestId: draw.guestId,
+        checkinDate: draw.checkinDate,
+        checkoutDate: draw.checkoutDate,
+        anzahlGaeste: parseInt(draw.anzahlGaeste) || 1,
+        status: 'reserviert',
+        bemerkungen: null,
+        currentUser: userName,
+      });
+      toast.success(`Buchung ${booking.reservierungsnummer} aus dem Kontingent angelegt`);
+      setDraw(null);
+      await refreshBookings();
+      loadAllotments();
+      onNavigateToBooking?.(booking.id);
+    } catch (error) {
+      const message = String(error);
+      toast.error(message.startsWith('DOUBLE_BOOKING:') ? message.replace('DOUBLE_BOOKING:', '') : message, {
+        duration: 6000,
+      });
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const inputClass = 'px-3 py-1.5 border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+  const gebaeudeTypen = [...new Set(rooms.map((room) => room.gebaeude_typ).filter(Boolean))];
+  const today = new Date().toISOString().slice(0, 10);
+
+  return (
+    <div className="space-y-6">
+      {/* Kontingent anlegen / bearbeiten */}
+      <form onSubmit={saveAllotment} className="bg-white border border-slate-200 rounded-lg p-4 shadow-sm space-y-3">
+        <div>
+          <h3 className="flex items-center gap-2 text-lg font-bold text-slate-800">
+            <Package className="w-5 h-5 text-blue-600" />
+            {form.id === null ? 'Kontingent anlegen' : `Kontingent ${form.name} bearbeiten`}
+          </h3>
+          <p className="text-sm text-slate-500">
+            Die Zimmer sind pro Nacht für den Partner reserviert. Nach der Freigabefrist werden nicht abgerufene Zimmer
+            automatisch wieder frei.
+          </p>
+        </div>
+        <div className="grid grid-cols-1 md:grid-cols-3 gap-3">
+          <input
+            type="text"
+            required
+            placeholder="Name (z.B. Reisebüro Sonne)"
+            value={form.name}
+            onChange={(e) => setForm({ ...form, name: e.target.value })}
+            className={inputClass}
+          />
+          <SearchableGuestPicker
+            guests={guests}
+            selectedGuestId={form.guestId}
+            onSelectGuest={(guestId) => setForm({ ...form, guestId })}
+          />
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Zimmer pro Nacht
+            <input
+              type="number"
+              min={1}
+              required
+              value={form.quantity}
+              onChange={(e) => setForm({ ...form, quantity: e.target.value })}
+              className={`${inputClass} w-20`}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Anreise
+            <input
+              type="date"
+              required
+              value={form.startDate}
+              onChange={(e) => setForm({ ...form, startDate: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Abreise
+            <input
+              type="date"
+              required
+              value={form.endDate}
+              onChange={(e) => setForm({ ...form, endDate: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Freigabefrist
+            <input
+              type="date"
+              required
+              value={form.releaseDate}
+              onChange={(e) => setForm({ ...form, releaseDate: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <select
+            value={form.gebaeudeTyp}
+            onChange={(e) => setForm({ ...form, gebaeudeTyp: e.target.value })}
+            className={inputClass}
+          >
+            <option value="">Feste Zimmerauswahl</option>
+            {gebaeudeTypen.map((typ) => (
+              <option key={typ} value={typ}>
+                Zimmertyp: {typ}
+              </option>
+            ))}
+          </select>
+          <input
+            type="text"
+            placeholder="Bemerkungen"
+            value={form.bemerkungen}
+            onChange={(e) => setForm({ ...form, bemerkungen: e.target.value })}
+            className={`${inputClass} md:col-span-2`}
+          />
+        </div>
+        {!form.gebaeudeTyp && (
+          <div className="flex flex-wrap gap-2">
+            {rooms.map((room) => (
+              <label
+                key={room.id}
+                className={`flex items-center gap-1 px-2 py-1 rounded border text-sm ${
+                  form.roomIds.includes(room.id) ? 'border-blue-300 bg-blue-50' : 'border-slate-200'
+                }`}
+              >
+                <input
+                  type="checkbox"
+                  checked={form.roomIds.includes(room.id)}
+                  onChange={(e) =>
+                    setForm({
+                      ...form,
+                      roomIds: e.target.checked ? [...form.roomIds, room.id] : form.roomIds.filter((id) => id !== room.id),
+                    })
+                  }
+                />
+                {room.name}
+              </label>
+            ))}
+          </div>
+        )}
+        <div className="flex items-center gap-3">
+          <button
+            type="submit"
+            disabled={saving || (!form.gebaeudeTyp && form.roomIds.length === 0)}
+            className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold transition-colors"
+          >
+            {saving ? (
+              <Loader2 className="w-4 h-4 animate-spin" />
+            ) : form.id === null ? (
+              <Plus className="w-4 h-4" />
+            ) : (
+              <Save className="w-4 h-4" />
+            )}
+            {form.id === null ? 'Anlegen' : 'Speichern'}
+          </button>
+          {form.id !== null && (
+            <button type="button" onClick={() => setForm(emptyForm)} className="text-sm text-slate-600 hover:text-slate-900">
+              Abbrechen
+            </button>
+          )}
+        </div>
+      </form>
+
+      {/* Kontingente mit Auslastung */}
+      <div className="space-y-3">
+        {allotments.length === 0 && <p className="text-slate-500">Keine Kontingente angelegt.</p>}
+        {allotments.map((allotment) => {
+          const own = nights.filter((night) => night.allotmentId === allotment.id);
+          const held = own.reduce((sum, night) => sum + night.quantity, 0);
+          const drawn = own.reduce((sum, night) => sum + night.drawn, 0);
+          const partner = allotment.guestId ? guestMap.get(allotment.guestId) : undefined;
+          const active = allotment.status === 'aktiv';
+          return (
+            <div key={allotment.id} className="bg-white border border-slate-200 rounded-lg p-3 shadow-sm text-sm">
+              <div className="flex items-center justify-between gap-3">
+                <div className="flex items-center gap-3 min-w-0">
+                  <span className="font-semibold text-slate-900 truncate">{allotment.name}</span>
+                  <span
+                    className={`px-2 py-0.5 rounded text-xs font-semibold ${
+                      active ? 'bg-emerald-100 text-emerald-800' : 'bg-slate-100 text-slate-700'
+                    }`}
+                  >
+                    {active ? 'Aktiv' : 'Freigegeben'}
+                  </span>
+                  {partner && (
+                    <span className="text-slate-700 truncate">
+                      {partner.vorname} {partner.nachname}
+                    </span>
+                  )}
+                  <span className="text-slate-400">
+                    {formatDate(allotment.startDate)} – {formatDate(allotment.endDate)} · {allotment.quantity} Zimmer/Nacht
+                    · {allotment.gebaeudeTyp ?? allotmentRooms(allotment).map((room) => room.name).join(', ')}
+                  </span>
+                </div>
+                <div className="flex items-center gap-3">
+                  <span className="text-slate-600" title="Abgerufene / gehaltene Zimmernächte">
+                    {drawn} / {held} abgerufen
+                  </span>
+                  <span className={allotment.releaseDate < today && active ? 'text-red-700 font-semibold' : 'text-slate-500'}>
+                    Frist {formatDate(allotment.releaseDate)}
+                  </span>
+                  {active && (
+                    <>
+                      <button
+                        onClick={() => (draw?.allotmentId === allotment.id ? setDraw(null) : startDraw(allotment))}
+                        className="flex items-center gap-1 text-slate-600 hover:text-blue-700"
+                        title="Zimmer aus dem Kontingent buchen"
+                      >
+                        <CalendarPlus className="w-4 h-4" />
+                      </button>
+                      <button
+                        onClick={() => setForm(toForm(allotment))}
+                        className="text-xs text-blue-700 hover:underline"
+                      >
+                        Bearbeiten
+                      </button>
+                      <button
+                        onClick={() => releaseAllotment(allotment)}
+                        className="flex items-center gap-1 text-slate-600 hover:text-amber-700"
+                        title="Jetzt freigeben"
+                      >
+                        <Unlock className="w-4 h-4" />
+                      </button>
+                    </>
+                  )}
+                  <button
+                    onClick={() => deleteAllotment(allotment)}
+                    className="flex items-center gap-1 text-slate-600 hover:text-red-700"
+                    title="Kontingent löschen"
+                  >
+                    <Trash2 className="w-4 h-4" />
+                  </button>
+                </div>
+              </div>
+              {allotment.bemerkungen && <p className="mt-1 text-slate-500">{allotment.bemerkungen}</p>}
+
+              {draw?.allotmentId === allotment.id && (
+                <form onSubmit={bookFromAllotment} className="flex flex-wrap items-center gap-2 mt-3">
+                  <select
+                    value={draw.roomId}
+                    onChange={(e) => setDraw({ ...draw, roomId: Number(e.target.value) })}
+                    className={inputClass}
+                  >
+                    {allotmentRooms(allotment).map((room) => (
+                      <option key={room.id} value={room.id}>
+                        {room.name}
+                      </option>
+                    ))}
+                  </select>
+                  <div className="w-64">
+                    <SearchableGuestPicker
+                      guests={guests}
+                      selectedGuestId={draw.guestId}
+                      onSelectGuest={(guestId) => setDraw({ ...draw, guestId })}
+                    />
+                  </div>
+                  <input
+                    type="date"
+                    required
+                    min={allotment.startDate}
+                    value={draw.checkinDate}
+                    onChange={(e) => setDraw({ ...draw, checkinDate: e.target.value })}
+                    className={inputClass}
+                  />
+                  <input
+                    type="date"
+                    required
+                    value={draw.checkoutDate}
+                    onChange={(e) => setDraw({ ...draw, checkoutDate: e.target.value })}
+                    className={inputClass}
+                  />
+                  <input
+                    type="number"
+                    min={1}
+                    value={draw.anzahlGaeste}
+                    onChange={(e) => setDraw({ ...draw, anzahlGaeste: e.target.value })}
+                    className={`${inputClass} w-16`}
+                    title="Anzahl Gäste"
+                  />
+                  <button
+                    type="submit"
+                    disabled={saving || !draw.roomId}
+                    className="px-3 py-1.5 bg-emerald-600 hover:bg-emerald-700 disabled:bg-slate-400 text-white rounded-lg font-semibold"
+                  >
+                    Buchen
+                  </button>
+                </form>
+              )}
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,419 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Users, Plus, Ban, FileText, Loader2, ChevronDown, ChevronRight, ExternalLink, Save } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import { useData } from '../../context/DataContext';
+import { formatDate } from '../../utils/dateFormatting';
+import SearchableGuestPicker from '../BookingManagement/SearchableGuestPicker';
+import type { Booking, BookingGroup, BookingGroupWithRooms } from '../../types/booking';
+
+interface BookingGroupsProps {
+  onNavigateToBooking?: (bookingId: number) => void;
+}
+
+type NewStatus = 'anfrage' | 'optioniert' | 'reserviert' | 'bestaetigt';
+
+const STATUS_LABELS: Record<Booking['status'], string> = {
+  anfrage: 'Anfrage',
+  optioniert: 'Optioniert',
+  reserviert: 'Reserviert',
+  bestaetigt: 'Bestätigt',
+  eingecheckt: 'Eingecheckt',
+  ausgecheckt: 'Ausgecheckt',
+  storniert: 'Storniert',
+};
+
+interface RoomSelection {
+  anzahlGaeste: string;
+  guestNames: string;
+}
+
+const emptyForm = {
+  name: '',
+  organiserGuestId: 0,
+  status: 'reserviert' as NewStatus,
+  checkinDate: '',
+  checkoutDate: '',
+  bemerkungen: '',
+};
+
+// Namen der Personen im Zimmer: eine Zeile bzw. ein Komma pro Person
+const parseNames = (value: string) =>
+  value
+    .split(/[\n,]/)
+    .map((name) => name.trim())
+    .filter(Boolean);
+
+export default function BookingGroups({ onNavigateToBooking }: BookingGroupsProps) {
+  const { userName } = useUser();
+  const { guests, rooms, refreshBookings } = useData();
+  const [groups, setGroups] = useState<BookingGroup[]>([]);
+  const [form, setForm] = useState(emptyForm);
+  const [selectedRooms, setSelectedRooms] = useState<Record<number, RoomSelection>>({});
+  const [saving, setSaving] = useState(false);
+  const [expanded, setExpanded] = useState<BookingGroupWithRooms | null>(null);
+  const [guestNames, setGuestNames] = useState<Record<number, string>>({});
+  const [busyId, setBusyId] = useState<number | null>(null);
+
+  useEffect(() => {
+    loadGroups();
+  }, []);
+
+  const loadGroups = async () => {
+    try {
+      setGroups(await invoke<BookingGroup[]>('get_booking_groups_pg'));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Gruppen: ${error}`);
+    }
+  };
+
+  const loadGroup = async (id: number) => {
+    const group = await invoke<BookingGroupWithRooms>('get_booking_group_pg', { id });
+    setExpanded(group);
+    setGuestNames(Object.fromEntries(group.rooms.map((room) => [room.booking.id, room.guestNames.join(', ')])));
+  };
+
+  const toggleGroup = async (group: BookingGroup) => {
+    if (expanded?.id === group.id) {
+      setExpanded(null);
+      return;
+    }
+    try {
+      await loadGroup(group.id);
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const toggleRoom = (roomId: number) => {
+    const { [roomId]: selected, ...rest } = selectedRooms;
+    setSelectedRooms(selected ? rest : { ...selectedRooms, [roomId]: { anzahlGaeste: '1', guestNames: '' } });
+  };
+
+  const createGroup = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!form.organiserGuestId) {
+      toast.error('Bitte einen Organisator wählen');
+      return;
+    }
+    setSaving(true);
+    try {
+      const group = await invoke<BookingGroupWithRooms>('create_booking_group_pg', {
+        name: form.name.trim(),
+        organiserGuestId: form.organiserGuestId,
+        paymentRecipientId: null,
+        status: form.status,
+        bemerkungen: form.bemerkungen || null,
+        rooms: Object.entries(selectedRooms).map(([roomId, selection]) => ({
+          roomId: Number(roomId),
+          checkinDate: form.checkinDate,
+          checkoutDate: form.checkoutDate,
+          anzahlGaeste: parseInt(selection.anzahlGaeste) || 1,
+          guestNames: parseNames(selection.guestNames),
+        })),
+        currentUser: userName,
+      });
+      toast.success(`Gruppe ${group.reservierungsnummer} mit ${group.rooms.length} Zimmern angelegt`);
+      setForm(emptyForm);
+      setSelectedRooms({});
+      await refreshBookings();
+      await loadGroups();
+      await loadGroup(group.id);
+    } catch (error) {
+      const message = String(error);
+      toast.error(
+        message.startsWith('DOUBLE_BOOKING:')
+          ? `Nicht alle Zimmer sind frei: ${message.replace('DOUBLE_BOOKING:', '')}`
+          : message,
+        { duration: 6000 }
+      );
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const saveGroup = async () => {
+    if (!expanded) return;
+    try {
+      await invoke<BookingGroup>('update_booking_group_pg', {
+        id: expanded.id,
+        name: expanded.name.trim(),
+        paymentRecipientId: expanded.paymentRecipientId ?? null,
+        bemerkungen: expanded.bemerkungen || null,
+      });
+      toast.success('Gruppe gespeichert');
+      loadGroups();
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const saveGuestNames = async (bookingId: number) => {
+    try {
+      await invoke('set_booking_guest_names_pg', { bookingId, guestNames: parseNames(guestNames[bookingId] ?? '') });
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const printInvoice = async (group: BookingGroup) => {
+    setBusyId(group.id);
+    try {
+      const path = await invoke<string>('generate_group_invoice_pdf_command', { groupId: group.id });
+      await invoke('open_pdf_file_command', { path });
+    } catch (error) {
+      toast.error(`Gruppenrechnung konnte nicht erstellt werden: ${error}`);
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const cancelGroup = async (group: BookingGroup) => {
+    if (!window.confirm(`Alle Zimmer der Gruppe ${group.name} (${group.reservierungsnummer}) stornieren?`)) return;
+    setBusyId(group.id);
+    try {
+      await invoke('cancel_booking_group_command', { groupId: group.id });
+      toast.success('Gruppe storniert');
+      await refreshBookings();
+      if (expanded?.id === group.id) await loadGroup(group.id);
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setBusyId(null);
+    }
+  };
+
+  const inputClass = 'px-3 py-1.5 border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+  const roomCount = Object.keys(selectedRooms).length;
+
+  return (
+    <div className="space-y-6">
+      {/* Neue Gruppe */}
+      <form onSubmit={createGroup} className="bg-white border border-slate-200 rounded-lg p-4 shadow-sm space-y-3">
+        <div>
+          <h3 className="flex items-center gap-2 text-lg font-bold text-slate-800">
+            <Users className="w-5 h-5 text-blue-600" />
+            Gruppenbuchung anlegen
+          </h3>
+          <p className="text-sm text-slate-500">
+            Alle Zimmer werden gemeinsam gebucht - ist eines belegt, wird keines gebucht. Die Gruppe erhält eine gemeinsame
+            Rechnung.
+          </p>
+        </div>
+        <div className="grid grid-cols-1 md:grid-cols-3 gap-3">
+          <input
+            type="text"
+            required
+            placeholder="Gruppenname (z.B. Chor Musterstadt)"
+            value={form.name}
+            onChange={(e) => setForm({ ...form, name: e.target.value })}
+            className={inputClass}
+          />
+          <SearchableGuestPicker
+            guests={guests}
+            selectedGuestId={form.organiserGuestId}
+            onSelectGuest={(guestId) => setForm({ ...form, organiserGuestId: guestId })}
+          />
+          <select
+            value={form.status}
+            onChange={(e) => setForm({ ...form, status: e.target.value as NewStatus })}
+            className={inputClass}
+          >
+            {(['anfrage', 'optioniert', 'reserviert', 'bestaetigt'] as NewStatus[]).map((status) => (
+              <option key={status} value={status}>
+                {STATUS_LABELS[status]}
+              </option>
+            ))}
+          </select>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Anreise
+            <input
+              type="date"
+              required
+              value={form.checkinDate}
+              onChange={(e) => setForm({ ...form, checkinDate: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Abreise
+            <input
+              type="date"
+              required
+              value={form.checkoutDate}
+              onChange={(e) => setForm({ ...form, checkoutDate: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <input
+            type="text"
+            placeholder="Bemerkungen"
+            value={form.bemerkungen}
+            onChange={(e) => setForm({ ...form, bemerkungen: e.target.value })}
+            className={inputClass}
+          />
+        </div>
+
+        {/* Zimmer der Gruppe */}
+        <div className="grid grid-cols-1 md:grid-cols-2 gap-2">
+          {rooms.map((room) => {
+            const selection = selectedRooms[room.id];
+            return (
+              <div
+                key={room.id}
+                className={`flex items-center gap-2 px-3 py-2 rounded-lg border text-sm ${
+                  selection ? 'border-blue-300 bg-blue-50' : 'border-slate-200'
+                }`}
+              >
+                <label className="flex items-center gap-2 w-40 truncate">
+                  <input type="checkbox" checked={!!selection} onChange={() => toggleRoom(room.id)} />
+                  {room.name}
+                </label>
+                {selection && (
+                  <>
+                    <input
+                      type="number"
+                      min={1}
+                      max={room.capacity}
+                      value={selection.anzahlGaeste}
+                      onChange={(e) =>
+                        setSelectedRooms({ ...selectedRooms, [room.id]: { ...selection, anzahlGaeste: e.target.value } })
+                      }
+                      className={`${inputClass} w-16`}
+                      title="Anzahl Gäste"
+                    />
+                    <input
+                      type="text"
+                      placeholder="Namen (mit Komma getrennt)"
+                      value={selection.guestNames}
+                      onChange={(e) =>
+                        setSelectedRooms({ ...selectedRooms, [room.id]: { ...selection, guestNames: e.target.value } })
+                      }
+                      className={`${inputClass} flex-1`}
+                    />
+                  </>
+                )}
+              </div>
+            );
+          })}
+        </div>
+
+        <button
+          type="submit"
+          disabled={saving || roomCount === 0}
+          className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold transition-colors"
+        >
+          {saving ? <Loader2 className="w-4 h-4 animate-spin" /> : <Plus className="w-4 h-4" />}
+          {roomCount > 0 ? `${roomCount} Zimmer buchen` : 'Zimmer wählen'}
+        </button>
+      </form>
+
+      {/* Bestehende Gruppen */}
+      <div className="space-y-3">
+        {groups.length === 0 && <p className="text-slate-500">Noch keine Gruppenbuchungen.</p>}
+        {groups.map((group) => {
+          const open = expanded?.id === group.id ? expanded : null;
+          const activeRooms = open?.rooms.filter((room) => room.booking.status !== 'storniert') ?? [];
+          return (
+            <div key={group.id} className="bg-white border border-slate-200 rounded-lg p-3 shadow-sm text-sm">
+              <div className="flex items-center justify-between gap-3">
+                <div className="flex items-center gap-3 min-w-0">
+                  <button onClick={() => toggleGroup(group)} className="text-slate-500 hover:text-slate-800">
+                    {open ? <ChevronDown className="w-4 h-4" /> : <ChevronRight className="w-4 h-4" />}
+                  </button>
+                  <span className="font-mono font-semibold text-slate-900">{group.reservierungsnummer}</span>
+                  <span className="font-semibold text-slate-800 truncate">{group.name}</span>
+                  {group.bemerkungen && <span className="text-slate-400 truncate">{group.bemerkungen}</span>}
+                </div>
+                <div className="flex items-center gap-3">
+                  {busyId === group.id && <Loader2 className="w-4 h-4 animate-spin" />}
+                  <button
+                    onClick={() => printInvoice(group)}
+                    disabled={busyId !== null}
+                    className="flex items-center gap-1 text-slate-600 hover:text-blue-700"
+                    title="Gruppenrechnung erstellen und öffnen"
+                  >
+                    <FileText className="w-4 h-4" />
+                  </button>
+                  <button
+                    onClick={() => cancelGroup(group)}
+                    disabled={busyId !== null}
+                    className="flex items-center gap-1 text-slate-600 hover:text-red-700"
+                    title="Gruppe stornieren"
+                  >
+                    <Ban className="w-4 h-4" />
+                  </button>
+                </div>
+              </div>
+
+              {open && (
+                <div className="mt-3 ml-7 space-y-2">
+                  <div className="flex flex-wrap items-center gap-2">
+                    <input
+                      type="text"
+                      value={open.name}
+                      onChange={(e) => setExpanded({ ...open, name: e.target.value })}
+                      className={`${inputClass} w-56`}
+                    />
+                    <input
+                      type="text"
+                      placeholder="Bemerkungen"
+                      value={open.bemerkungen ?? ''}
+                      onChange={(e) => setExpanded({ ...open, bemerkungen: e.target.value })}
+                      className={`${inputClass} flex-1`}
+                    />
+                    <button
+                      onClick={saveGroup}
+                      disabled={!open.name.trim()}
+                      className="flex items-center gap-1 px-3 py-1.5 bg-slate-100 hover:bg-slate-200 text-slate-700 rounded-lg font-semibold"
+                    >
+                      <Save className="w-4 h-4" />
+                      Speichern
+                    </button>
+                  </div>
+
+                  {open.rooms.map((room) => (
+                    <div key={room.booking.id} className="flex items-center gap-3 border-b border-slate-100 py-1">
+                      <button
+                        onClick={() => onNavigateToBooking?.(room.booking.id)}
+                        className="flex items-center gap-1 font-semibold text-blue-700 hover:underline w-28"
+                      >
+                        <ExternalLink className="w-4 h-4" />
+                        {room.booking.reservierungsnummer}
+                      </button>
+                      <span className="w-32 truncate text-slate-800">{room.roomName}</span>
+                      <span className="text-slate-500">
+                        {formatDate(room.booking.checkin_date)} – {formatDate(room.booking.checkout_date)}
+                      </span>
+                      <span className="text-slate-500">{room.booking.anzahl_gaeste} Pers.</span>
+                      <span
+                        className={`px-2 py-0.5 rounded text-xs font-semibold ${
+                          room.booking.status === 'storniert' ? 'bg-red-100 text-red-800' : 'bg-slate-100 text-slate-700'
+                        }`}
+                      >
+                        {STATUS_LABELS[room.booking.status]}
+                      </span>
+                      <input
+                        type="text"
+                        placeholder="Namen im Zimmer"
+                        value={guestNames[room.booking.id] ?? ''}
+                        onChange={(e) => setGuestNames({ ...guestNames, [room.booking.id]: e.target.value })}
+                        onBlur={() => saveGuestNames(room.booking.id)}
+                        className={`${inputClass} flex-1`}
+                      />
+                      <span className="font-semibold w-24 text-right">{room.booking.gesamtpreis.toFixed(2)} €</span>
+                    </div>
+                  ))}
+                  <p className="text-right font-semibold text-slate-800">
+                    Summe: {activeRooms.reduce((sum, room) => sum + room.booking.gesamtpreis, 0).toFixed(2)} €
+                  </p>
+                </div>
+              )}
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
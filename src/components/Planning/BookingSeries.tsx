@@ -0,0 +1,537 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Repeat, Plus, Save, Ban, ArrowRightLeft, Loader2, ChevronDown, ChevronRight, ExternalLink } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import { useData } from '../../context/DataContext';
+import { formatDate } from '../../utils/dateFormatting';
+import SearchableGuestPicker from '../BookingManagement/SearchableGuestPicker';
+import type {
+  Booking,
+  BookingSeries as Series,
+  BookingSeriesWithBookings,
+  RecurrenceRule,
+  SeriesConflict,
+} from '../../types/booking';
+
+interface BookingSeriesProps {
+  onNavigateToBooking?: (bookingId: number) => void;
+}
+
+type NewStatus = 'anfrage' | 'optioniert' | 'reserviert' | 'bestaetigt';
+
+const STATUS_LABELS: Record<Booking['status'], string> = {
+  anfrage: 'Anfrage',
+  optioniert: 'Optioniert',
+  reserviert: 'Reserviert',
+  bestaetigt: 'Bestätigt',
+  eingecheckt: 'Eingecheckt',
+  ausgecheckt: 'Ausgecheckt',
+  storniert: 'Storniert',
+};
+
+const emptyForm = {
+  name: '',
+  guestId: 0,
+  status: 'reserviert' as NewStatus,
+  roomIds: [] as number[],
+  anzahlGaeste: '1',
+  firstCheckin: '',
+  nights: '1',
+  frequency: 'monatlich' as RecurrenceRule['frequency'],
+  interval: '1',
+  count: '6',
+  until: '',
+  bemerkungen: '',
+};
+
+interface BulkForm {
+  name: string;
+  anzahlGaeste: string;
+  bemerkungen: string;
+  fromDate: string;
+  fromRoomId: number;
+  toRoomId: number;
+}
+
+const today = () => new Date().toISOString().slice(0, 10);
+
+// z.B. "alle 2 Wochen, 3 Nächte, 6 Termine"
+const ruleLabel = (series: Series) => {
+  const weekly = series.frequency === 'woechentlich';
+  const every =
+    series.intervalCount > 1
+      ? `alle ${series.intervalCount} ${weekly ? 'Wochen' : 'Monate'}`
+      : weekly
+        ? 'jede Woche'
+        : 'jeden Monat';
+  const nights = `${series.nights} ${series.nights === 1 ? 'Nacht' : 'Nächte'}`;
+  if (series.occurrences) return `${every}, ${nights}, ${series.occurrences} Termine`;
+  if (series.untilDate) return `${every}, ${nights}, bis ${formatDate(series.untilDate)}`;
+  return `${every}, ${nights}`;
+};
+
+// Serienbuchungen: wiederkehrende Termine (z.B. monatliches Seminar), je Termin und Zimmer eine Buchung
+export default function BookingSeries({ onNavigateToBooking }: BookingSeriesProps) {
+  const { userName } = useUser();
+  const { guests, rooms, roomMap, refreshBookings } = useData();
+  const [seriesList, setSeriesList] = useState<Series[]>([]);
+  const [form, setForm] = useState(emptyForm);
+  const [saving, setSaving] = useState(false);
+  const [expanded, setExpanded] = useState<BookingSeriesWithBookings | null>(null);
+  const [bulk, setBulk] = useState<BulkForm | null>(null);
+  const [conflicts, setConflicts] = useState<SeriesConflict[]>([]);
+
+  useEffect(() => {
+    loadSeries();
+  }, []);
+
+  const loadSeries = async () => {
+    try {
+      setSeriesList(await invoke<Series[]>('get_booking_series_list_pg'));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Serien: ${error}`);
+    }
+  };
+
+  const showSeries = (series: BookingSeriesWithBookings) => {
+    setExpanded(series);
+    setConflicts(series.conflicts);
+    setBulk({
+      name: series.name,
+      anzahlGaeste: String(series.anzahlGaeste),
+      bemerkungen: series.bemerkungen ?? '',
+      fromDate: today(),
+      fromRoomId: series.roomIds[0] ?? 0,
+      toRoomId: 0,
+    });
+  };
+
+  const toggleSeries = async (series: Series) => {
+    if (expanded?.id === series.id) {
+      setExpanded(null);
+      return;
+    }
+    try {
+      showSeries(await invoke<BookingSeriesWithBookings>('get_booking_series_pg', { id: series.id }));
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const createSeries = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!form.guestId) {
+      toast.error('Bitte einen Gast wählen');
+      return;
+    }
+    setSaving(true);
+    try {
+      const useCount = form.count.trim() !== '';
+      const created = await invoke<BookingSeriesWithBookings>('create_booking_series_pg', {
+        name: form.name.trim(),
+        guestId: form.guestId,
+        paymentRecipientId: null,
+        status: form.status,
+        roomIds: form.roomIds,
+        anzahlGaeste: parseInt(form.anzahlGaeste) || 1,
+        firstCheckin: form.firstCheckin,
+        nights: parseInt(form.nights) || 1,
+        rule: {
+          frequency: form.frequency,
+          interval: parseInt(form.interval) || 1,
+          count: useCount ? parseInt(form.count) : null,
+          until: useCount ? null : form.until || null,
+        },
+        bemerkungen: form.bemerkungen || null,
+        currentUser: userName,
+      });
+      if (created.conflicts.length > 0) {
+        toast(`${created.bookings.length} Buchungen angelegt, ${created.conflicts.length} Termine nicht frei`, {
+          icon: '⚠️',
+          duration: 6000,
+        });
+      } else {
+        toast.success(`Serie ${created.reservierungsnummer} mit ${created.bookings.length} Buchungen angelegt`);
+      }
+      setForm(emptyForm);
+      await refreshBookings();
+      await loadSeries();
+      showSeries(created);
+    } catch (error) {
+      const message = String(error);
+      toast.error(message.startsWith('DOUBLE_BOOKING:') ? message.replace('DOUBLE_BOOKING:', '') : message, {
+        duration: 6000,
+      });
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const updateSeries = async () => {
+    if (!expanded || !bulk) return;
+    setSaving(true);
+    try {
+      const updated = await invoke<BookingSeriesWithBookings>('update_booking_series_pg', {
+        id: expanded.id,
+        name: bulk.name.trim(),
+        paymentRecipientId: expanded.paymentRecipientId ?? null,
+        anzahlGaeste: parseInt(bulk.anzahlGaeste) || 1,
+        bemerkungen: bulk.bemerkungen || null,
+        fromDate: bulk.fromDate || null,
+        currentUser: userName,
+      });
+      toast.success(`Serie ab ${formatDate(bulk.fromDate || today())} geändert`);
+      showSeries(updated);
+      await refreshBookings();
+      loadSeries();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const moveRoom = async () => {
+    if (!expanded || !bulk || !bulk.toRoomId) return;
+    setSaving(true);
+    try {
+      const moved = await invoke<BookingSeriesWithBookings>('move_booking_series_room_pg', {
+        id: expanded.id,
+        fromRoomId: bulk.fromRoomId,
+        toRoomId: bulk.toRoomId,
+        fromDate: bulk.fromDate || null,
+      });
+      if (moved.conflicts.length > 0) {
+        toast(`${moved.conflicts.length} Termine bleiben im bisherigen Zimmer (nicht frei)`, { icon: '⚠️', duration: 6000 });
+      } else {
+        toast.success(`Zimmer ab ${formatDate(bulk.fromDate || today())} gewechselt`);
+      }
+      showSeries(moved);
+      await refreshBookings();
+      loadSeries();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const cancelSeries = async () => {
+    if (!expanded || !bulk) return;
+    const from = bulk.fromDate || today();
+    if (!window.confirm(`Alle offenen Termine von ${expanded.name} ab ${formatDate(from)} stornieren?`)) return;
+    setSaving(true);
+    try {
+      const cancelled = await invoke<number>('cancel_booking_series_command', { seriesId: expanded.id, fromDate: from });
+      toast.success(`${cancelled} Buchungen storniert`);
+      showSeries(await invoke<BookingSeriesWithBookings>('get_booking_series_pg', { id: expanded.id }));
+      await refreshBookings();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const inputClass = 'px-3 py-1.5 border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+
+  return (
+    <div className="space-y-6">
+      {/* Neue Serie */}
+      <form onSubmit={createSeries} className="bg-white border border-slate-200 rounded-lg p-4 shadow-sm space-y-3">
+        <div>
+          <h3 className="flex items-center gap-2 text-lg font-bold text-slate-800">
+            <Repeat className="w-5 h-5 text-blue-600" />
+            Serienbuchung anlegen
+          </h3>
+          <p className="text-sm text-slate-500">
+            Jeder Termin wird wie eine Einzelbuchung auf Verfügbarkeit geprüft. Belegte Termine werden nicht gebucht und
+            als Konflikt angezeigt.
+          </p>
+        </div>
+        <div className="grid grid-cols-1 md:grid-cols-4 gap-3">
+          <input
+            type="text"
+            required
+            placeholder="Name (z.B. Yoga-Wochenende)"
+            value={form.name}
+            onChange={(e) => setForm({ ...form, name: e.target.value })}
+            className={inputClass}
+          />
+          <SearchableGuestPicker
+            guests={guests}
+            selectedGuestId={form.guestId}
+            onSelectGuest={(guestId) => setForm({ ...form, guestId })}
+          />
+          <select
+            value={form.status}
+            onChange={(e) => setForm({ ...form, status: e.target.value as NewStatus })}
+            className={inputClass}
+          >
+            {(['anfrage', 'optioniert', 'reserviert', 'bestaetigt'] as NewStatus[]).map((status) => (
+              <option key={status} value={status}>
+                {STATUS_LABELS[status]}
+              </option>
+            ))}
+          </select>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Gäste pro Zimmer
+            <input
+              type="number"
+              min={1}
+              value={form.anzahlGaeste}
+              onChange={(e) => setForm({ ...form, anzahlGaeste: e.target.value })}
+              className={`${inputClass} w-16`}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Erste Anreise
+            <input
+              type="date"
+              required
+              value={form.firstCheckin}
+              onChange={(e) => setForm({ ...form, firstCheckin: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Nächte
+            <input
+              type="number"
+              min={1}
+              value={form.nights}
+              onChange={(e) => setForm({ ...form, nights: e.target.value })}
+              className={`${inputClass} w-16`}
+            />
+          </label>
+          <div className="flex items-center gap-2 text-sm text-slate-600">
+            alle
+            <input
+              type="number"
+              min={1}
+              value={form.interval}
+              onChange={(e) => setForm({ ...form, interval: e.target.value })}
+              className={`${inputClass} w-14`}
+            />
+            <select
+              value={form.frequency}
+              onChange={(e) => setForm({ ...form, frequency: e.target.value as RecurrenceRule['frequency'] })}
+              className={inputClass}
+            >
+              <option value="woechentlich">Wochen</option>
+              <option value="monatlich">Monate</option>
+            </select>
+          </div>
+          <div className="flex items-center gap-2 text-sm text-slate-600">
+            <input
+              type="number"
+              min={1}
+              placeholder="Anzahl"
+              value={form.count}
+              onChange={(e) => setForm({ ...form, count: e.target.value })}
+              className={`${inputClass} w-16`}
+              title="Anzahl der Termine"
+            />
+            Termine oder bis
+            <input
+              type="date"
+              value={form.until}
+              disabled={form.count.trim() !== ''}
+              onChange={(e) => setForm({ ...form, until: e.target.value })}
+              className={`${inputClass} flex-1 disabled:opacity-50`}
+              title="Letzte mögliche Anreise (Anzahl leer lassen)"
+            />
+          </div>
+        </div>
+        <div className="flex flex-wrap gap-2">
+          {rooms.map((room) => (
+            <label
+              key={room.id}
+              className={`flex items-center gap-1 px-2 py-1 rounded border text-sm ${
+                form.roomIds.includes(room.id) ? 'border-blue-300 bg-blue-50' : 'border-slate-200'
+              }`}
+            >
+              <input
+                type="checkbox"
+                checked={form.roomIds.includes(room.id)}
+                onChange={(e) =>
+                  setForm({
+                    ...form,
+                    roomIds: e.target.checked ? [...form.roomIds, room.id] : form.roomIds.filter((id) => id !== room.id),
+                  })
+                }
+              />
+              {room.name}
+            </label>
+          ))}
+        </div>
+        <input
+          type="text"
+          placeholder="Bemerkungen"
+          value={form.bemerkungen}
+          onChange={(e) => setForm({ ...form, bemerkungen: e.target.value })}
+          className={`${inputClass} w-full`}
+        />
+        <button
+          type="submit"
+          disabled={saving || form.roomIds.length === 0}
+          className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold transition-colors"
+        >
+          {saving ? <Loader2 className="w-4 h-4 animate-spin" /> : <Plus className="w-4 h-4" />}
+          Serie buchen
+        </button>
+      </form>
+
+      {/* Bestehende Serien */}
+      <div className="space-y-3">
+        {seriesList.length === 0 && <p className="text-slate-500">Noch keine Serienbuchungen.</p>}
+        {seriesList.map((series) => {
+          const open = expanded?.id === series.id ? expanded : null;
+          return (
+            <div key={series.id} className="bg-white border border-slate-200 rounded-lg p-3 shadow-sm text-sm">
+              <div className="flex items-center gap-3 min-w-0">
+                <button onClick={() => toggleSeries(series)} className="text-slate-500 hover:text-slate-800">
+                  {open ? <ChevronDown className="w-4 h-4" /> : <ChevronRight className="w-4 h-4" />}
+                </button>
+                <span className="font-mono font-semibold text-slate-900">{series.reservierungsnummer}</span>
+                <span className="font-semibold text-slate-800 truncate">{series.name}</span>
+                <span className="text-slate-500">ab {formatDate(series.firstCheckin)}</span>
+                <span className="text-slate-400 truncate">
+                  {ruleLabel(series)} · {series.roomIds.map((id) => roomMap.get(id)?.name ?? `#${id}`).join(', ')}
+                </span>
+              </div>
+
+              {open && bulk && (
+                <div className="mt-3 ml-7 space-y-3">
+                  {/* Sammeländerung ab Stichtag */}
+                  <div className="flex flex-wrap items-center gap-2 p-2 bg-slate-50 rounded-lg">
+                    <label className="flex items-center gap-2 text-slate-600">
+                      Ab
+                      <input
+                        type="date"
+                        value={bulk.fromDate}
+                        onChange={(e) => setBulk({ ...bulk, fromDate: e.target.value })}
+                        className={inputClass}
+                      />
+                    </label>
+                    <input
+                      type="text"
+                      value={bulk.name}
+                      onChange={(e) => setBulk({ ...bulk, name: e.target.value })}
+                      className={`${inputClass} w-48`}
+                    />
+                    <input
+                      type="number"
+                      min={1}
+                      value={bulk.anzahlGaeste}
+                      onChange={(e) => setBulk({ ...bulk, anzahlGaeste: e.target.value })}
+                      className={`${inputClass} w-16`}
+                      title="Gäste pro Zimmer"
+                    />
+                    <input
+                      type="text"
+                      placeholder="Bemerkungen"
+                      value={bulk.bemerkungen}
+                      onChange={(e) => setBulk({ ...bulk, bemerkungen: e.target.value })}
+                      className={`${inputClass} flex-1`}
+                    />
+                    <button
+                      onClick={updateSeries}
+                      disabled={saving || !bulk.name.trim()}
+                      className="flex items-center gap-1 px-3 py-1.5 bg-slate-200 hover:bg-slate-300 text-slate-700 rounded-lg font-semibold"
+                    >
+                      <Save className="w-4 h-4" />
+                      Übernehmen
+                    </button>
+                  </div>
+                  <div className="flex flex-wrap items-center gap-2 p-2 bg-slate-50 rounded-lg">
+                    <span className="text-slate-600">Zimmer wechseln</span>
+                    <select
+                      value={bulk.fromRoomId}
+                      onChange={(e) => setBulk({ ...bulk, fromRoomId: Number(e.target.value) })}
+                      className={inputClass}
+                    >
+                      {open.roomIds.map((id) => (
+                        <option key={id} value={id}>
+                          {roomMap.get(id)?.name ?? `#${id}`}
+                        </option>
+                      ))}
+                    </select>
+                    →
+                    <select
+                      value={bulk.toRoomId}
+                      onChange={(e) => setBulk({ ...bulk, toRoomId: Number(e.target.value) })}
+                      className={inputClass}
+                    >
+                      <option value={0}>Zimmer wählen</option>
+                      {rooms
+                        .filter((room) => !open.roomIds.includes(room.id))
+                        .map((room) => (
+                          <option key={room.id} value={room.id}>
+                            {room.name}
+                          </option>
+                        ))}
+                    </select>
+                    <button
+                      onClick={moveRoom}
+                      disabled={saving || !bulk.toRoomId}
+                      className="flex items-center gap-1 px-3 py-1.5 bg-slate-200 hover:bg-slate-300 disabled:opacity-50 text-slate-700 rounded-lg font-semibold"
+                    >
+                      <ArrowRightLeft className="w-4 h-4" />
+                      Wechseln
+                    </button>
+                    <button
+                      onClick={cancelSeries}
+                      disabled={saving}
+                      className="ml-auto flex items-center gap-1 px-3 py-1.5 bg-red-50 hover:bg-red-100 text-red-700 rounded-lg font-semibold"
+                    >
+                      <Ban className="w-4 h-4" />
+                      Ab Stichtag stornieren
+                    </button>
+                  </div>
+
+                  {conflicts.length > 0 && (
+                    <div className="p-2 bg-amber-50 border border-amber-200 rounded-lg">
+                      <p className="font-semibold text-amber-800">Nicht gebuchte Termine</p>
+                      {conflicts.map((conflict) => (
+                        <p key={`${conflict.occurrence}-${conflict.roomId}`} className="text-amber-800">
+                          {formatDate(conflict.checkinDate)} – {formatDate(conflict.checkoutDate)} ·{' '}
+                          {roomMap.get(conflict.roomId)?.name ?? `#${conflict.roomId}`}: {conflict.reason}
+                        </p>
+                      ))}
+                    </div>
+                  )}
+
+                  {open.bookings.map((booking) => (
+                    <div key={booking.id} className="flex items-center gap-3 border-b border-slate-100 py-1">
+                      <button
+                        onClick={() => onNavigateToBooking?.(booking.id)}
+                        className="flex items-center gap-1 font-semibold text-blue-700 hover:underline w-28"
+                      >
+                        <ExternalLink className="w-4 h-4" />
+                        {booking.reservierungsnummer}
+                      </button>
+                      <span className="text-slate-700">
+                        {formatDate(booking.checkin_date)} – {formatDate(booking.checkout_date)}
+                      </span>
+                      <span className="w-32 truncate text-slate-800">{roomMap.get(booking.room_id)?.name}</span>
+                      <span
+                        className={`px-2 py-0.5 rounded text-xs font-semibold ${
+                          booking.status === 'storniert' ? 'bg-red-100 text-red-800' : 'bg-slate-100 text-slate-700'
+                        }`}
+                      >
+                        {STATUS_LABELS[booking.status]}
+                      </span>
+                      <span className="ml-auto font-semibold">{booking.gesamtpreis.toFixed(2)} €</span>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,48 @@
+import { useState } from 'react';
+import BookingGroups from './BookingGroups';
+import Allotments from './Allotments';
+import Waitlist from './Waitlist';
+import BookingSeries from './BookingSeries';
+
+interface PlanningViewProps {
+  onNavigateToBooking?: (bookingId: number) => void;
+}
+
+type View = 'gruppen' | 'serien' | 'kontingente' | 'warteliste';
+
+const VIEW_LABELS: Record<View, string> = {
+  gruppen: 'Gruppen',
+  serien: 'Serien',
+  kontingente: 'Kontingente',
+  warteliste: 'Warteliste',
+};
+
+// Planung über Einzelbuchungen hinaus: Gruppen (mehrere Zimmer, eine Rechnung),
+// Serienbuchungen, Kontingente für Partner und Warteliste
+export default function PlanningView({ onNavigateToBooking }: PlanningViewProps) {
+  const [view, setView] = useState<View>('gruppen');
+
+  return (
+    <div className="h-full overflow-y-auto p-6">
+      <div className="max-w-6xl mx-auto space-y-4">
+        <div className="flex gap-1 p-1 bg-slate-100 rounded-lg w-fit">
+          {(Object.keys(VIEW_LABELS) as View[]).map((v) => (
+            <button
+              key={v}
+              onClick={() => setView(v)}
+              className={`px-4 py-1.5 rounded-md text-sm font-semibold transition-colors ${
+                view === v ? 'bg-white text-slate-900 shadow-sm' : 'text-slate-600 hover:text-slate-900'
+              }`}
+            >
+              {VIEW_LABELS[v]}
+            </button>
+          ))}
+        </div>
+        {view === 'gruppen' && <BookingGroups onNavigateToBooking={onNavigateToBooking} />}
+        {view === 'serien' && <BookingSeries onNavigateToBooking={onNavigateToBooking} />}
+        {view === 'kontingente' && <Allotments onNavigateToBooking={onNavigateToBooking} />}
+        {view === 'warteliste' && <Waitlist />}
+      </div>
+    </div>
+  );
+}
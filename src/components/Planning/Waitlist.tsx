@@ -0,0 +1,347 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { ListOrdered, Plus, Save, Check, Ban, Undo2, Trash2, Loader2 } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useUser } from '../../context/UserContext';
+import { useData } from '../../context/DataContext';
+import { formatDate } from '../../utils/dateFormatting';
+import SearchableGuestPicker from '../BookingManagement/SearchableGuestPicker';
+import type { WaitlistEntry } from '../../types/booking';
+
+type Status = WaitlistEntry['status'];
+type StatusFilter = Status | 'alle';
+
+const STATUS_LABELS: Record<Status, string> = {
+  wartend: 'Wartend',
+  angeboten: 'Angeboten',
+  gebucht: 'Gebucht',
+  storniert: 'Storniert',
+};
+
+const STATUS_STYLES: Record<Status, string> = {
+  wartend: 'bg-amber-100 text-amber-800',
+  angeboten: 'bg-blue-100 text-blue-800',
+  gebucht: 'bg-emerald-100 text-emerald-800',
+  storniert: 'bg-red-100 text-red-800',
+};
+
+interface EntryForm {
+  id: number | null;
+  guestId: number;
+  checkinDate: string;
+  checkoutDate: string;
+  anzahlGaeste: string;
+  roomIds: number[];
+  orte: string[];
+  bemerkungen: string;
+}
+
+const emptyForm: EntryForm = {
+  id: null,
+  guestId: 0,
+  checkinDate: '',
+  checkoutDate: '',
+  anzahlGaeste: '1',
+  roomIds: [],
+  orte: [],
+  bemerkungen: '',
+};
+
+const toForm = (entry: WaitlistEntry): EntryForm => ({
+  id: entry.id,
+  guestId: entry.guestId,
+  checkinDate: entry.checkinDate,
+  checkoutDate: entry.checkoutDate,
+  anzahlGaeste: String(entry.anzahlGaeste),
+  roomIds: entry.roomIds,
+  orte: entry.orte,
+  bemerkungen: entry.bemerkungen ?? '',
+});
+
+const toggle = <T,>(list: T[], value: T) => (list.includes(value) ? list.filter((v) => v !== value) : [...list, value]);
+
+// Warteliste: wird ein passendes Zimmer frei, bekommt der erste wartende Gast automatisch ein Angebot per E-Mail
+export default function Waitlist() {
+  const { userName } = useUser();
+  const { guests, rooms, roomMap } = useData();
+  const [entries, setEntries] = useState<WaitlistEntry[]>([]);
+  const [filter, setFilter] = useState<StatusFilter>('wartend');
+  const [form, setForm] = useState<EntryForm>(emptyForm);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    loadEntries();
+  }, []);
+
+  const loadEntries = async () => {
+    try {
+      setEntries(await invoke<WaitlistEntry[]>('get_waitlist_pg'));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Warteliste: ${error}`);
+    }
+  };
+
+  const saveEntry = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!form.guestId) {
+      toast.error('Bitte einen Gast wählen');
+      return;
+    }
+    setSaving(true);
+    try {
+      const args = {
+        checkinDate: form.checkinDate,
+        checkoutDate: form.checkoutDate,
+        anzahlGaeste: parseInt(form.anzahlGaeste) || 1,
+        roomIds: form.roomIds,
+        orte: form.orte,
+        bemerkungen: form.bemerkungen || null,
+      };
+      if (form.id === null) {
+        await invoke<WaitlistEntry>('create_waitlist_entry_pg', { ...args, guestId: form.guestId, currentUser: userName });
+        toast.success('Gast auf die Warteliste gesetzt');
+      } else {
+        await invoke<WaitlistEntry>('update_waitlist_entry_pg', { ...args, id: form.id });
+        toast.success('Eintrag gespeichert');
+      }
+      setForm(emptyForm);
+      loadEntries();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const setStatus = async (entry: WaitlistEntry, status: Status) => {
+    try {
+      await invoke<WaitlistEntry>('set_waitlist_status_pg', { id: entry.id, status });
+      loadEntries();
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const deleteEntry = async (entry: WaitlistEntry) => {
+    if (!window.confirm(`Eintrag von ${entry.guestName} löschen?`)) return;
+    try {
+      await invoke('delete_waitlist_entry_pg', { id: entry.id });
+      loadEntries();
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const inputClass = 'px-3 py-1.5 border border-slate-200 rounded-lg text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+  const orte = [...new Set(rooms.map((room) => room.ort).filter(Boolean))];
+  const visible = filter === 'alle' ? entries : entries.filter((entry) => entry.status === filter);
+
+  return (
+    <div className="space-y-6">
+      {/* Eintrag anlegen / bearbeiten */}
+      <form onSubmit={saveEntry} className="bg-white border border-slate-200 rounded-lg p-4 shadow-sm space-y-3">
+        <div>
+          <h3 className="flex items-center gap-2 text-lg font-bold text-slate-800">
+            <ListOrdered className="w-5 h-5 text-blue-600" />
+            {form.id === null ? 'Auf die Warteliste setzen' : 'Wartelisten-Eintrag bearbeiten'}
+          </h3>
+          <p className="text-sm text-slate-500">
+            Wird durch eine Stornierung ein passendes Zimmer frei, erhält der am längsten wartende Gast automatisch ein
+            Angebot per E-Mail. Ohne Auswahl von Zimmern oder Orten passt jedes Zimmer.
+          </p>
+        </div>
+        <div className="grid grid-cols-1 md:grid-cols-4 gap-3">
+          {form.id === null ? (
+            <SearchableGuestPicker
+              guests={guests}
+              selectedGuestId={form.guestId}
+              onSelectGuest={(guestId) => setForm({ ...form, guestId })}
+            />
+          ) : (
+            <span className="px-3 py-1.5 text-sm font-semibold text-slate-800">
+              {entries.find((entry) => entry.id === form.id)?.guestName}
+            </span>
+          )}
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Anreise
+            <input
+              type="date"
+              required
+              value={form.checkinDate}
+              onChange={(e) => setForm({ ...form, checkinDate: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Abreise
+            <input
+              type="date"
+              required
+              value={form.checkoutDate}
+              onChange={(e) => setForm({ ...form, checkoutDate: e.target.value })}
+              className={`${inputClass} flex-1`}
+            />
+          </label>
+          <label className="flex items-center gap-2 text-sm text-slate-600">
+            Gäste
+            <input
+              type="number"
+              min={1}
+              value={form.anzahlGaeste}
+              onChange={(e) => setForm({ ...form, anzahlGaeste: e.target.value })}
+              className={`${inputClass} w-20`}
+            />
+          </label>
+        </div>
+        <div className="flex flex-wrap gap-2">
+          {orte.map((ort) => (
+            <label
+              key={ort}
+              className={`flex items-center gap-1 px-2 py-1 rounded border text-sm ${
+                form.orte.includes(ort) ? 'border-blue-300 bg-blue-50' : 'border-slate-200'
+              }`}
+            >
+              <input
+                type="checkbox"
+                checked={form.orte.includes(ort)}
+                onChange={() => setForm({ ...form, orte: toggle(form.orte, ort) })}
+              />
+              {ort}
+            </label>
+          ))}
+          {rooms.map((room) => (
+            <label
+              key={room.id}
+              className={`flex items-center gap-1 px-2 py-1 rounded border text-sm ${
+                form.roomIds.includes(room.id) ? 'border-blue-300 bg-blue-50' : 'border-slate-200'
+              }`}
+            >
+              <input
+                type="checkbox"
+                checked={form.roomIds.includes(room.id)}
+                onChange={() => setForm({ ...form, roomIds: toggle(form.roomIds, room.id) })}
+              />
+              {room.name}
+            </label>
+          ))}
+        </div>
+        <input
+          type="text"
+          placeholder="Bemerkungen"
+          value={form.bemerkungen}
+          onChange={(e) => setForm({ ...form, bemerkungen: e.target.value })}
+          className={`${inputClass} w-full`}
+        />
+        <div className="flex items-center gap-3">
+          <button
+            type="submit"
+            disabled={saving}
+            className="flex items-center gap-2 px-4 py-2 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg font-semibold transition-colors"
+          >
+            {saving ? (
+              <Loader2 className="w-4 h-4 animate-spin" />
+            ) : form.id === null ? (
+              <Plus className="w-4 h-4" />
+            ) : (
+              <Save className="w-4 h-4" />
+            )}
+            {form.id === null ? 'Eintragen' : 'Speichern'}
+          </button>
+          {form.id !== null && (
+            <button type="button" onClick={() => setForm(emptyForm)} className="text-sm text-slate-600 hover:text-slate-900">
+              Abbrechen
+            </button>
+          )}
+        </div>
+      </form>
+
+      {/* Einträge nach Status */}
+      <div className="space-y-3">
+        <div className="flex gap-2">
+          {(['wartend', 'angeboten', 'gebucht', 'storniert', 'alle'] as StatusFilter[]).map((f) => (
+            <button
+              key={f}
+              onClick={() => setFilter(f)}
+              className={`px-3 py-1.5 rounded-lg text-sm font-semibold transition-colors ${
+                filter === f ? 'bg-slate-800 text-white' : 'bg-white text-slate-600 hover:bg-slate-100 border border-slate-200'
+              }`}
+            >
+              {f === 'alle' ? 'Alle' : STATUS_LABELS[f]}
+              {f !== 'alle' && ` (${entries.filter((entry) => entry.status === f).length})`}
+            </button>
+          ))}
+        </div>
+
+        {visible.length === 0 && <p className="text-slate-500">Keine Einträge in dieser Ansicht.</p>}
+        {visible.map((entry) => {
+          const wishes = [...entry.orte, ...entry.roomIds.map((id) => roomMap.get(id)?.name ?? `#${id}`)];
+          const offeredRoom = entry.offeredRoomId ? roomMap.get(entry.offeredRoomId) : undefined;
+          return (
+            <div key={entry.id} className="bg-white border border-slate-200 rounded-lg p-3 shadow-sm text-sm">
+              <div className="flex items-center justify-between gap-3">
+                <div className="flex items-center gap-3 min-w-0">
+                  <span className={`px-2 py-0.5 rounded text-xs font-semibold ${STATUS_STYLES[entry.status]}`}>
+                    {STATUS_LABELS[entry.status]}
+                  </span>
+                  <span className="font-semibold text-slate-900 truncate">{entry.guestName}</span>
+                  <span className="text-slate-700">
+                    {formatDate(entry.checkinDate)} – {formatDate(entry.checkoutDate)} · {entry.anzahlGaeste} Pers.
+                  </span>
+                  <span className="text-slate-400 truncate">{wishes.length > 0 ? wishes.join(', ') : 'beliebiges Zimmer'}</span>
+                </div>
+                <div className="flex items-center gap-3">
+                  {entry.status === 'angeboten' && (
+                    <span className="text-blue-700">
+                      {offeredRoom?.name ?? 'Zimmer'} angeboten
+                      {entry.offeredAt ? ` am ${formatDate(entry.offeredAt.slice(0, 10))}` : ''}
+                    </span>
+                  )}
+                  {(entry.status === 'wartend' || entry.status === 'angeboten') && (
+                    <>
+                      <button
+                        onClick={() => setStatus(entry, 'gebucht')}
+                        className="flex items-center gap-1 text-slate-600 hover:text-emerald-700"
+                        title="Gast hat gebucht"
+                      >
+                        <Check className="w-4 h-4" />
+                      </button>
+                      <button
+                        onClick={() => setStatus(entry, 'storniert')}
+                        className="flex items-center gap-1 text-slate-600 hover:text-red-700"
+                        title="Gast hat abgesagt"
+                      >
+                        <Ban className="w-4 h-4" />
+                      </button>
+                    </>
+                  )}
+                  {entry.status !== 'wartend' && (
+                    <button
+                      onClick={() => setStatus(entry, 'wartend')}
+                      className="flex items-center gap-1 text-slate-600 hover:text-slate-900"
+                      title="Wieder auf die Warteliste setzen"
+                    >
+                      <Undo2 className="w-4 h-4" />
+                    </button>
+                  )}
+                  {entry.status === 'wartend' && (
+                    <button onClick={() => setForm(toForm(entry))} className="text-xs text-blue-700 hover:underline">
+                      Bearbeiten
+                    </button>
+                  )}
+                  <button
+                    onClick={() => deleteEntry(entry)}
+                    className="flex items-center gap-1 text-slate-600 hover:text-red-700"
+                    title="Eintrag löschen"
+                  >
+                    <Trash2 className="w-4 h-4" />
+                  </button>
+                </div>
+              </div>
+              {entry.bemerkungen && <p className="mt-1 text-slate-500">{entry.bemerkungen}</p>}
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
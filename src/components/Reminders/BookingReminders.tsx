@@ -222,6 +222,7 @@ export default function BookingReminders({ bookingId }: BookingRemindersProps) {
       case 'auto_payment': return 'Zahlung ausstehend';
       case 'auto_checkin': return 'Check-in Vorbereitung';
       case 'auto_invoice': return 'Rechnung versenden';
+      case 'auto_dunning': return 'Mahnverfahren';
       default: return type;
     }
   };
@@ -0,0 +1,191 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { X, Wrench, Trash2, Plus } from 'lucide-react';
+import { format } from 'date-fns';
+import { useUser } from '../../context/UserContext';
+import type { RoomBlock } from '../../types/booking';
+
+interface RoomBlocksDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+  room?: { id: number; name: string };
+}
+
+export default function RoomBlocksDialog({ isOpen, onClose, room }: RoomBlocksDialogProps) {
+  const { userName } = useUser();
+  const [blocks, setBlocks] = useState<RoomBlock[]>([]);
+  const [startDate, setStartDate] = useState('');
+  const [endDate, setEndDate] = useState('');
+  const [reason, setReason] = useState('');
+  const [bemerkungen, setBemerkungen] = useState('');
+  const [saving, setSaving] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const loadBlocks = useCallback(async () => {
+    if (!room) return;
+    try {
+      const all = await invoke<RoomBlock[]>('get_room_blocks_pg', {
+        from: format(new Date(), 'yyyy-MM-dd'),
+        to: null,
+      });
+      setBlocks(all.filter((block) => block.roomId === room.id));
+    } catch (err) {
+      setError(String(err));
+    }
+  }, [room]);
+
+  useEffect(() => {
+    if (isOpen) {
+      setError(null);
+      loadBlocks();
+    }
+  }, [isOpen, loadBlocks]);
+
+  const handleCreate = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!room) return;
+
+    setSaving(true);
+    setError(null);
+    try {
+      await invoke('create_room_block_pg', {
+        roomId: room.id,
+        startDate,
+        endDate,
+        reason,
+        bemerkungen: bemerkungen || null,
+        currentUser: userName,
+      });
+      setStartDate('');
+      setEndDate('');
+      setReason('');
+      setBemerkungen('');
+      await loadBlocks();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await invoke('delete_room_block_pg', { id });
+      await loadBlocks();
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  if (!isOpen || !room) return null;
+
+  return (
+    <div className="fixed inset-0 bg-black/50 flex items-center justify-center z-50 p-4">
+      <div className="bg-white rounded-2xl shadow-2xl w-full max-w-2xl max-h-[90vh] overflow-hidden">
+        {/* Header */}
+        <div className="bg-gradient-to-r from-slate-600 to-slate-700 px-6 py-4 flex items-center justify-between">
+          <div className="flex items-center gap-3">
+            <div className="bg-white/20 p-2 rounded-lg">
+              <Wrench className="w-6 h-6 text-white" />
+            </div>
+            <h2 className="text-xl font-bold text-white">Sperrzeiten {room.name}</h2>
+          </div>
+          <button
+            onClick={onClose}
+            className="text-white/80 hover:text-white transition-colors p-1 rounded-lg hover:bg-white/10"
+          >
+            <X className="w-6 h-6" />
+          </button>
+        </div>
+
+        <div className="p-6 overflow-y-auto max-h-[calc(90vh-80px)] space-y-6">
+          {error && (
+            <div className="p-4 bg-red-50 border border-red-200 rounded-lg">
+              <p className="text-sm text-red-700">{error}</p>
+            </div>
+          )}
+
+          {/* Current and upcoming blocks */}
+          <div className="space-y-2">
+            {blocks.length === 0 ? (
+              <p className="text-sm text-slate-500">Keine aktuellen oder geplanten Sperrungen.</p>
+            ) : (
+              blocks.map((block) => (
+                <div key={block.id} className="flex items-center justify-between p-3 bg-slate-50 border border-slate-200 rounded-lg">
+                  <div>
+                    <div className="text-sm font-semibold text-slate-800">{block.reason}</div>
+                    <div className="text-xs text-slate-500">
+                      {format(new Date(block.startDate), 'dd.MM.yyyy')} - {format(new Date(block.endDate), 'dd.MM.yyyy')}
+                      {block.bemerkungen && ` • ${block.bemerkungen}`}
+                    </div>
+                  </div>
+                  <button
+                    onClick={() => handleDelete(block.id)}
+                    className="p-2 text-red-600 hover:bg-red-50 rounded-lg transition-colors"
+                    title="Sperrung aufheben"
+                  >
+                    <Trash2 className="w-4 h-4" />
+                  </button>
+                </div>
+              ))
+            )}
+          </div>
+
+          {/* New block */}
+          <form onSubmit={handleCreate} className="space-y-4 pt-4 border-t border-slate-200">
+            <div className="grid grid-cols-2 gap-4">
+              <div>
+                <label className="block text-sm font-semibold text-slate-700 mb-2">Von *</label>
+                <input
+                  type="date"
+                  required
+                  value={startDate}
+                  onChange={(e) => setStartDate(e.target.value)}
+                  className="w-full px-4 py-2 border border-slate-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
+                />
+              </div>
+              <div>
+                <label className="block text-sm font-semibold text-slate-700 mb-2">Bis (wieder frei ab) *</label>
+                <input
+                  type="date"
+                  required
+                  value={endDate}
+                  onChange={(e) => setEndDate(e.target.value)}
+                  className="w-full px-4 py-2 border border-slate-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
+                />
+              </div>
+            </div>
+            <div>
+              <label className="block text-sm font-semibold text-slate-700 mb-2">Grund *</label>
+              <input
+                type="text"
+                required
+                value={reason}
+                onChange={(e) => setReason(e.target.value)}
+                className="w-full px-4 py-2 border border-slate-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
+                placeholder="z.B. Renovierung Bad"
+              />
+            </div>
+            <div>
+              <label className="block text-sm font-semibold text-slate-700 mb-2">Bemerkungen (optional)</label>
+              <input
+                type="text"
+                value={bemerkungen}
+                onChange={(e) => setBemerkungen(e.target.value)}
+                className="w-full px-4 py-2 border border-slate-200 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500"
+              />
+            </div>
+            <button
+              type="submit"
+              disabled={saving}
+              className="flex items-center gap-2 px-4 py-2 bg-slate-700 hover:bg-slate-800 disabled:opacity-50 text-white rounded-lg text-sm font-semibold transition-colors"
+            >
+              <Plus className="w-4 h-4" />
+              Zimmer sperren
+            </button>
+          </form>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -1,5 +1,5 @@
 import { useState, useEffect } from 'react';
-import { X, Hotel, MapPin, Users, Euro, Key } from 'lucide-react';
+import { X, Hotel, MapPin, Users, Euro, Key, Dog, Home } from 'lucide-react';
 import { useData } from '../../context/DataContext';
 
 interface Room {
@@ -16,6 +16,8 @@ interface Room {
   postalCode?: string;
   city?: string;
   notizen?: string;
+  hunde_erlaubt?: boolean | null;
+  erdgeschoss?: boolean | null;
 }
 
 interface RoomDialogProps {
@@ -40,6 +42,8 @@ export default function RoomDialog({ isOpen, onClose, onSuccess, room }: RoomDia
     postalCode: '',
     city: '',
     notizen: '',
+    hunde_erlaubt: false,
+    erdgeschoss: false,
   });
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
@@ -61,6 +65,8 @@ export default function RoomDialog({ isOpen, onClose, onSuccess, room }: RoomDia
         postalCode: '',
         city: '',
         notizen: '',
+        hunde_erlaubt: false,
+        erdgeschoss: false,
       });
     }
     setError(null);
@@ -85,6 +91,8 @@ export default function RoomDialog({ isOpen, onClose, onSuccess, room }: RoomDia
         postalCode: formData.postalCode || null,
         city: formData.city || null,
         notizen: formData.notizen || null,
+        hundeErlaubt: formData.hunde_erlaubt ?? false,
+        erdgeschoss: formData.erdgeschoss ?? false,
       };
 
       if (room?.id) {
@@ -346,6 +354,30 @@ export default function RoomDialog({ isOpen, onClose, onSuccess, room }: RoomDia
               />
             </div>
 
+            {/* Ausstattung (für die Zimmersuche) */}
+            <div className="flex flex-wrap gap-6">
+              <label className="flex items-center gap-2 text-sm text-slate-700 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={formData.hunde_erlaubt ?? false}
+                  onChange={(e) => setFormData({ ...formData, hunde_erlaubt: e.target.checked })}
+                  className="w-4 h-4 rounded border-slate-300 text-blue-600 focus:ring-blue-500"
+                />
+                <Dog className="w-4 h-4" />
+                Hunde erlaubt
+              </label>
+              <label className="flex items-center gap-2 text-sm text-slate-700 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={formData.erdgeschoss ?? false}
+                  onChange={(e) => setFormData({ ...formData, erdgeschoss: e.target.checked })}
+                  className="w-4 h-4 rounded border-slate-300 text-blue-600 focus:ring-blue-500"
+                />
+                <Home className="w-4 h-4" />
+                Erdgeschoss
+              </label>
+            </div>
+
             {/* Notizen */}
             <div>
               <label className="block text-sm font-semibold text-slate-700 mb-2">
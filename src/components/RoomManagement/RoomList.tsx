@@ -1,7 +1,8 @@
 import { useState, useEffect } from 'react';
-import { Hotel, Search, Plus, Edit2, MapPin, Users, Euro, Trash2, FileText } from 'lucide-react';
+import { Hotel, Search, Plus, Edit2, MapPin, Users, Euro, Trash2, FileText, Wrench } from 'lucide-react';
 import { useData } from '../../context/DataContext';
 import RoomDialog from './RoomDialog';
+import RoomBlocksDialog from './RoomBlocksDialog';
 import ConfirmDialog from '../ConfirmDialog';
 import { SELECT_STYLES } from '../../lib/selectStyles';
 
@@ -26,6 +27,7 @@ export default function RoomList() {
   const [locationFilter, setLocationFilter] = useState<string>('all');
   const [showDialog, setShowDialog] = useState(false);
   const [selectedRoom, setSelectedRoom] = useState<Room | undefined>(undefined);
+  const [blocksRoom, setBlocksRoom] = useState<{ id: number; name: string } | undefined>(undefined);
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
   const [roomToDelete, setRoomToDelete] = useState<{ id: number; name: string } | null>(null);
   const [showErrorDialog, setShowErrorDialog] = useState(false);
@@ -253,6 +255,13 @@ export default function RoomList() {
                     <Edit2 className="w-3.5 h-3.5" />
                     Bearbeiten
                   </button>
+                  <button
+                    onClick={() => setBlocksRoom({ id: room.id, name: room.name })}
+                    className="flex-1 flex items-center justify-center gap-2 px-4 py-2 text-sm text-slate-600 hover:bg-slate-100 rounded-lg transition-colors"
+                  >
+                    <Wrench className="w-3.5 h-3.5" />
+                    Sperrzeiten
+                  </button>
                   <button
                     onClick={() => handleDeleteRoom(room.id, room.name)}
                     className="flex-1 flex items-center justify-center gap-2 px-4 py-2 text-sm text-red-600 hover:bg-red-50 rounded-lg transition-colors"
@@ -278,6 +287,13 @@ export default function RoomList() {
         room={selectedRoom}
       />
 
+      {/* Room Blocks Dialog */}
+      <RoomBlocksDialog
+        isOpen={blocksRoom !== undefined}
+        onClose={() => setBlocksRoom(undefined)}
+        room={blocksRoom}
+      />
+
       {/* Delete Confirmation Dialog */}
       <ConfirmDialog
         isOpen={showDeleteConfirm}
@@ -0,0 +1,170 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Plus, Trash2, Save, ArrowUp } from 'lucide-react';
+import toast from 'react-hot-toast';
+import type { DunningStage } from '../../types/booking';
+
+interface EmailTemplate {
+  id: number;
+  template_name: string;
+}
+
+type StageForm = Pick<DunningStage, 'name' | 'templateName' | 'fristTage'> & { gebuehr: string };
+
+const MAX_STAGES = 5;
+
+// Liegt im Formular der Benachrichtigungen - daher ohne eigenes <form>
+export default function DunningStagesSection() {
+  const [stages, setStages] = useState<StageForm[]>([]);
+  const [templates, setTemplates] = useState<string[]>([]);
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    invoke<DunningStage[]>('get_dunning_stages_pg')
+      .then((data) => setStages(data.map(toForm)))
+      .catch((error) => toast.error(`Fehler beim Laden der Mahnstufen: ${error}`));
+    invoke<EmailTemplate[]>('get_all_email_templates_pg')
+      .then((data) => setTemplates(data.map((t) => t.template_name)))
+      .catch((error) => console.error('Vorlagen konnten nicht geladen werden:', error));
+  }, []);
+
+  const toForm = (stage: DunningStage): StageForm => ({
+    name: stage.name,
+    templateName: stage.templateName,
+    fristTage: stage.fristTage,
+    gebuehr: stage.gebuehr ? stage.gebuehr.toFixed(2).replace('.', ',') : '',
+  });
+
+  const update = (index: number, changes: Partial<StageForm>) =>
+    setStages(stages.map((stage, i) => (i === index ? { ...stage, ...changes } : stage)));
+
+  const moveUp = (index: number) => {
+    const reordered = [...stages];
+    [reordered[index - 1], reordered[index]] = [reordered[index], reordered[index - 1]];
+    setStages(reordered);
+  };
+
+  const save = async () => {
+    setSaving(true);
+    try {
+      const saved = await invoke<DunningStage[]>('save_dunning_stages_pg', {
+        stages: stages.map((stage) => ({
+          name: stage.name,
+          templateName: stage.templateName,
+          fristTage: stage.fristTage,
+          gebuehr: Number(stage.gebuehr.replace(',', '.')) || 0,
+        })),
+      });
+      setStages(saved.map(toForm));
+      toast.success('Mahnstufen gespeichert');
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const inputClass =
+    'px-2 py-1 bg-slate-600 border border-slate-500 rounded text-white text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+
+  return (
+    <div className="mt-4 space-y-2">
+      <p className="text-xs font-medium text-slate-300">Mahnstufen</p>
+      <p className="text-xs text-slate-400">
+        Jede Stufe wird erst nach Ablauf der Zahlungsfrist der vorherigen versendet. Nach der Frist der letzten Stufe
+        wird eine Erinnerung zur manuellen Nachverfolgung angelegt. Gebühren werden als Zusatzleistung berechnet.
+      </p>
+      {stages.map((stage, index) => (
+        <div key={index} className="flex flex-wrap items-center gap-2">
+          <span className="w-5 text-xs text-slate-400">{index + 1}.</span>
+          <input
+            value={stage.name}
+            onChange={(e) => update(index, { name: e.target.value })}
+            placeholder="Bezeichnung"
+            className={`${inputClass} w-40`}
+          />
+          <select
+            value={stage.templateName}
+            onChange={(e) => update(index, { templateName: e.target.value })}
+            className={`${inputClass} w-44`}
+            title="E-Mail-Vorlage"
+          >
+            <option value="">Vorlage wählen…</option>
+            {templates.map((name) => (
+              <option key={name} value={name}>
+                {name}
+              </option>
+            ))}
+          </select>
+          <label className="flex items-center gap-1 text-xs text-slate-300">
+            Frist
+            <input
+              type="number"
+              min="1"
+              max="365"
+              value={stage.fristTage}
+              onChange={(e) => update(index, { fristTage: parseInt(e.target.value) || 14 })}
+              className={`${inputClass} w-16`}
+            />
+            Tage
+          </label>
+          <label className="flex items-center gap-1 text-xs text-slate-300">
+            Gebühr
+            <input
+              inputMode="decimal"
+              value={stage.gebuehr}
+              onChange={(e) => update(index, { gebuehr: e.target.value })}
+              placeholder="0,00"
+              className={`${inputClass} w-20`}
+            />
+            €
+          </label>
+          {index > 0 && (
+            <button
+              type="button"
+              onClick={() => moveUp(index)}
+              className="p-1 text-slate-300 hover:text-white"
+              title="Nach oben"
+            >
+              <ArrowUp className="w-4 h-4" />
+            </button>
+          )}
+          <button
+            type="button"
+            onClick={() => setStages(stages.filter((_, i) => i !== index))}
+            className="p-1 text-red-400 hover:text-red-300"
+            title="Stufe entfernen"
+          >
+            <Trash2 className="w-4 h-4" />
+          </button>
+        </div>
+      ))}
+      <div className="flex gap-2 pt-1">
+        {stages.length < MAX_STAGES && (
+          <button
+            type="button"
+            onClick={() =>
+              setStages([...stages, { name: `${stages.length}. Mahnung`, templateName: '', fristTage: 14, gebuehr: '' }])
+            }
+            className="flex items-center gap-1 px-3 py-1.5 bg-slate-600 hover:bg-slate-500 text-white rounded-lg text-xs font-semibold"
+          >
+            <Plus className="w-4 h-4" />
+            Stufe hinzufügen
+          </button>
+        )}
+        <button
+          type="button"
+          onClick={save}
+          disabled={saving}
+          className="flex items-center gap-1 px-3 py-1.5 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg text-xs font-semibold"
+        >
+          <Save className="w-4 h-4" />
+          Mahnstufen speichern
+        </button>
+      </div>
+      <p className="text-xs text-slate-400">
+        Platzhalter in den Vorlagen: {'{{mahnstufe}}'}, {'{{offener_betrag}}'}, {'{{mahngebuehr}}'}, {'{{zahlungsfrist}}'}
+      </p>
+    </div>
+  );
+}
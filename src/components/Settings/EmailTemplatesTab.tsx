@@ -91,6 +91,8 @@ export default function EmailTemplatesTab() {
       reminder: 'Erinnerung',
       invoice: 'Rechnung',
       payment_reminder: 'Zahlungserinnerung',
+      mahnung_1: '1. Mahnung',
+      mahnung_2: '2. Mahnung',
       cancellation: 'Stornierungsbestätigung',
     };
     return names[name] || name;
@@ -25,6 +25,7 @@ interface CompanySettings {
   logoPath: string | null;
   logoData: string | null;
   logoMimeType: string | null;
+  reservierungsnummerFormat: string | null;
   updatedAt: string | null;
 }
 
@@ -52,6 +53,7 @@ export default function GeneralSettingsTab() {
   const [logoPath, setLogoPath] = useState('');
   const [logoData, setLogoData] = useState<string | null>(null);
   const [logoMimeType, setLogoMimeType] = useState<string | null>(null);
+  const [reservierungsnummerFormat, setReservierungsnummerFormat] = useState('{YYYY}-{seq}');
   const [loading, setLoading] = useState(false);
   const [loadingData, setLoadingData] = useState(true);
   const [successMessage, setSuccessMessage] = useState<string | null>(null);
@@ -90,6 +92,7 @@ export default function GeneralSettingsTab() {
       setLogoPath(data.logoPath || '');
       setLogoData(data.logoData || null);
       setLogoMimeType(data.logoMimeType || null);
+      setReservierungsnummerFormat(data.reservierungsnummerFormat || '{YYYY}-{seq}');
     } catch (error) {
       console.error('Fehler beim Laden der Einstellungen:', error);
       alert(`Fehler beim Laden: ${error}`);
@@ -277,6 +280,7 @@ export default function GeneralSettingsTab() {
         logoPath: logoPath || null,
         logoData: logoData || null,
         logoMimeType: logoMimeType || null,
+        reservierungsnummerFormat: reservierungsnummerFormat || null,
         updatedAt: null,
       };
 
@@ -632,6 +636,29 @@ export default function GeneralSettingsTab() {
         </div>
       </div>
 
+      {/* Reservierungsnummern */}
+      <div className="border-t border-slate-700 pt-6">
+        <h3 className="text-lg font-bold text-white mb-4">Reservierungsnummern</h3>
+        <div>
+          <label className="block text-sm font-medium text-slate-300 mb-2">
+            Format *
+          </label>
+          <input
+            type="text"
+            required
+            value={reservierungsnummerFormat}
+            onChange={(e) => setReservierungsnummerFormat(e.target.value)}
+            placeholder="{YYYY}-{seq}"
+            className="w-full px-4 py-3 bg-slate-700 border border-slate-600 rounded-lg text-white placeholder-slate-400 focus:outline-none focus:ring-2 focus:ring-blue-500 font-mono"
+          />
+          <p className="text-xs text-slate-400 mt-2">
+            Neue Buchungen erhalten fortlaufende Nummern pro Jahr. Platzhalter: {'{YYYY}'} Jahr, {'{YY}'} Jahr zweistellig,
+            {' {seq}'} laufende Nummer, {'{seq:4}'} laufende Nummer vierstellig (0001). Erlaubt sind außerdem Buchstaben,
+            Ziffern, "-" und ".".
+          </p>
+        </div>
+      </div>
+
 
       {/* Logo Upload */}
       <div className="border-t border-slate-700 pt-6">
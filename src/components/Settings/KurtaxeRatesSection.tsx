@@ -0,0 +1,182 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Landmark, Plus, Save, Trash2 } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useData } from '../../context/DataContext';
+
+interface KurtaxeRate {
+  id: number;
+  ort: string;
+  betragProNacht: number;
+  befreitUnterAlter: number;
+  geschaeftsreiseBefreit: boolean;
+  isActive: boolean;
+}
+
+type RateForm = Omit<KurtaxeRate, 'id' | 'betragProNacht'> & { id: number | null; betrag: string };
+
+const toForm = (rate: KurtaxeRate): RateForm => ({
+  id: rate.id,
+  ort: rate.ort,
+  betrag: rate.betragProNacht.toFixed(2).replace('.', ','),
+  befreitUnterAlter: rate.befreitUnterAlter,
+  geschaeftsreiseBefreit: rate.geschaeftsreiseBefreit,
+  isActive: rate.isActive,
+});
+
+// Kurtaxe-Sätze pro Gemeinde (Zimmer-Ort), jede Zeile wird einzeln gespeichert
+export default function KurtaxeRatesSection() {
+  const { rooms } = useData();
+  const [rates, setRates] = useState<RateForm[]>([]);
+  const [savingIndex, setSavingIndex] = useState<number | null>(null);
+
+  useEffect(() => {
+    loadRates();
+  }, []);
+
+  const loadRates = async () => {
+    try {
+      setRates((await invoke<KurtaxeRate[]>('get_kurtaxe_rates_pg')).map(toForm));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Kurtaxe-Sätze: ${error}`);
+    }
+  };
+
+  const update = (index: number, changes: Partial<RateForm>) =>
+    setRates(rates.map((rate, i) => (i === index ? { ...rate, ...changes } : rate)));
+
+  const save = async (index: number) => {
+    const rate = rates[index];
+    setSavingIndex(index);
+    try {
+      const saved = await invoke<KurtaxeRate>('save_kurtaxe_rate_pg', {
+        ort: rate.ort.trim(),
+        betragProNacht: Number(rate.betrag.replace(',', '.')) || 0,
+        befreitUnterAlter: rate.befreitUnterAlter,
+        geschaeftsreiseBefreit: rate.geschaeftsreiseBefreit,
+        isActive: rate.isActive,
+      });
+      setRates(rates.map((r, i) => (i === index ? toForm(saved) : r)));
+      toast.success(`Kurtaxe für ${saved.ort} gespeichert`);
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSavingIndex(null);
+    }
+  };
+
+  const remove = async (index: number) => {
+    const rate = rates[index];
+    if (rate.id !== null) {
+      if (!window.confirm(`Kurtaxe-Satz für ${rate.ort} löschen?`)) return;
+      try {
+        await invoke('delete_kurtaxe_rate_pg', { id: rate.id });
+      } catch (error) {
+        toast.error(String(error));
+        return;
+      }
+    }
+    setRates(rates.filter((_, i) => i !== index));
+  };
+
+  const orte = [...new Set(rooms.map((room) => room.ort).filter(Boolean))];
+  const inputClass =
+    'px-2 py-1 bg-slate-600 border border-slate-500 rounded text-white text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+
+  return (
+    <div className="bg-slate-700/50 rounded-lg p-6 space-y-4">
+      <div className="flex items-center gap-3">
+        <Landmark className="w-5 h-5 text-amber-400" />
+        <h4 className="text-base font-semibold text-white">Kurtaxe</h4>
+      </div>
+      <p className="text-xs text-slate-400">
+        Betrag pro Person und Nacht, gilt für alle Zimmer mit diesem Ort. Gäste unter dem Befreiungsalter sind befreit,
+        Geschäftsreisen optional (Häkchen „Geschäftsreise“ in der Buchung).
+      </p>
+
+      {rates.map((rate, index) => (
+        <div key={rate.id ?? `neu-${index}`} className="flex flex-wrap items-center gap-2">
+          <input
+            value={rate.ort}
+            onChange={(e) => update(index, { ort: e.target.value })}
+            disabled={rate.id !== null}
+            list="kurtaxe-orte"
+            placeholder="Ort"
+            className={`${inputClass} w-40 disabled:opacity-70`}
+          />
+          <label className="flex items-center gap-1 text-xs text-slate-300">
+            <input
+              inputMode="decimal"
+              value={rate.betrag}
+              onChange={(e) => update(index, { betrag: e.target.value })}
+              placeholder="0,00"
+              className={`${inputClass} w-20`}
+            />
+            € / Nacht
+          </label>
+          <label className="flex items-center gap-1 text-xs text-slate-300">
+            befreit unter
+            <input
+              type="number"
+              min="0"
+              max="99"
+              value={rate.befreitUnterAlter}
+              onChange={(e) => update(index, { befreitUnterAlter: parseInt(e.target.value) || 0 })}
+              className={`${inputClass} w-14`}
+            />
+            Jahren
+          </label>
+          <label className="flex items-center gap-1 text-xs text-slate-300">
+            <input
+              type="checkbox"
+              checked={rate.geschaeftsreiseBefreit}
+              onChange={(e) => update(index, { geschaeftsreiseBefreit: e.target.checked })}
+            />
+            Geschäftsreise befreit
+          </label>
+          <label className="flex items-center gap-1 text-xs text-slate-300">
+            <input
+              type="checkbox"
+              checked={rate.isActive}
+              onChange={(e) => update(index, { isActive: e.target.checked })}
+            />
+            aktiv
+          </label>
+          <button
+            type="button"
+            onClick={() => save(index)}
+            disabled={savingIndex !== null || !rate.ort.trim()}
+            className="p-1 text-blue-400 hover:text-blue-300 disabled:opacity-50"
+            title="Speichern"
+          >
+            <Save className="w-4 h-4" />
+          </button>
+          <button
+            type="button"
+            onClick={() => remove(index)}
+            className="p-1 text-red-400 hover:text-red-300"
+            title="Löschen"
+          >
+            <Trash2 className="w-4 h-4" />
+          </button>
+        </div>
+      ))}
+      <datalist id="kurtaxe-orte">
+        {orte.map((ort) => (
+          <option key={ort} value={ort} />
+        ))}
+      </datalist>
+
+      <button
+        type="button"
+        onClick={() =>
+          setRates([...rates, { id: null, ort: '', betrag: '', befreitUnterAlter: 0, geschaeftsreiseBefreit: true, isActive: true }])
+        }
+        className="flex items-center gap-1 px-3 py-1.5 bg-slate-600 hover:bg-slate-500 text-white rounded-lg text-xs font-semibold"
+      >
+        <Plus className="w-4 h-4" />
+        Ort hinzufügen
+      </button>
+    </div>
+  );
+}
@@ -2,6 +2,7 @@ import { useState, useEffect } from 'react';
 import { Bell, Save, RefreshCw, AlertCircle, PlayCircle, Mail, Bug } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
+import DunningStagesSection from './DunningStagesSection';
 
 interface NotificationSettings {
   id: number;
@@ -19,6 +20,9 @@ interface NotificationSettings {
   autoReminderCheckin: boolean | null;
   autoReminderInvoice: boolean | null;
 
+  // Optionen (vorgemerkte Zimmer)
+  optionHoldDays: number | null;
+
   updatedAt: string | null;
 }
 
@@ -46,6 +50,7 @@ export default function NotificationsTab() {
     paymentReminderBeforeDays: 7,
     autoReminderCheckin: true,
     autoReminderInvoice: true,
+    optionHoldDays: 3,
     updatedAt: null,
   });
   const [loading, setLoading] = useState(true);
@@ -217,42 +222,25 @@ export default function NotificationsTab() {
                 Zahlungserinnerungen
               </label>
               <p className="text-xs text-slate-400 mt-1 mb-3">
-                Sendet automatisch Zahlungserinnerungen an Gäste mit unbezahlten Buchungen.
+                Mahnt unbezahlte Buchungen automatisch in Stufen: Zahlungserinnerung, danach Mahnungen.
               </p>
               {settings.paymentRemindersEnabled && (
-                <div className="grid grid-cols-2 gap-4 mt-3">
-                  <div>
-                    <label className="block text-xs font-medium text-slate-300 mb-2">
-                      Erste Erinnerung nach (Tage)
-                    </label>
-                    <input
-                      type="number"
-                      value={settings.paymentReminderAfterDays}
-                      onChange={(e) => setSettings({ ...settings, paymentReminderAfterDays: parseInt(e.target.value) || 14 })}
-                      min="1"
-                      max="90"
-                      className="w-full px-3 py-2 bg-slate-700 border border-slate-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-blue-500"
-                    />
-                    <p className="text-xs text-slate-400 mt-1">
-                      Nach wie vielen Tagen seit Buchungserstellung
-                    </p>
-                  </div>
-                  <div>
-                    <label className="block text-xs font-medium text-slate-300 mb-2">
-                      Wiederholung nach (Tage)
-                    </label>
-                    <input
-                      type="number"
-                      value={settings.paymentReminderRepeatDays}
-                      onChange={(e) => setSettings({ ...settings, paymentReminderRepeatDays: parseInt(e.target.value) || 14 })}
-                      min="1"
-                      max="90"
-                      className="w-full px-3 py-2 bg-slate-700 border border-slate-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-blue-500"
-                    />
-                    <p className="text-xs text-slate-400 mt-1">
-                      Nach wie vielen Tagen erneut mahnen
-                    </p>
-                  </div>
+                <div className="mt-3">
+                  <label className="block text-xs font-medium text-slate-300 mb-2">
+                    Erste Stufe nach (Tage)
+                  </label>
+                  <input
+                    type="number"
+                    value={settings.paymentReminderAfterDays}
+                    onChange={(e) => setSettings({ ...settings, paymentReminderAfterDays: parseInt(e.target.value) || 14 })}
+                    min="1"
+                    max="90"
+                    className="w-32 px-3 py-2 bg-slate-700 border border-slate-600 rounded-lg text-white focus:outline-none focus:ring-2 focus:ring-blue-500"
+                  />
+                  <p className="text-xs text-slate-400 mt-1">
+                    Nach wie vielen Tagen seit Fälligkeit (Abreise bzw. Termin der Anzahlung)
+                  </p>
+                  <DunningStagesSection />
                 </div>
               )}
             </div>
@@ -394,6 +382,32 @@ export default function NotificationsTab() {
               </p>
             </div>
           </div>
+
+          {/* Option bookings: default hold period */}
+          <div className="p-4 bg-slate-700/50 rounded-lg border border-slate-600">
+            <label htmlFor="option_hold_days" className="block text-sm font-semibold text-white">
+              Optionen
+            </label>
+            <p className="text-xs text-slate-400 mt-1 mb-3">
+              Vorgemerkte Zimmer (Status „Optioniert") werden nach Ablauf automatisch freigegeben und der Gast
+              per Email informiert. Einen Tag vorher erscheint eine Erinnerung.
+            </p>
+            <div className="flex items-center gap-3 pl-1">
+              <label htmlFor="option_hold_days" className="text-xs text-slate-300">
+                Standard-Haltefrist:
+              </label>
+              <input
+                type="number"
+                id="option_hold_days"
+                value={settings.optionHoldDays ?? 3}
+                onChange={(e) => setSettings({ ...settings, optionHoldDays: parseInt(e.target.value) || 3 })}
+                min="1"
+                max="60"
+                className="w-20 px-2 py-1 bg-slate-600 border border-slate-500 rounded text-white text-sm focus:outline-none focus:ring-2 focus:ring-purple-500"
+              />
+              <span className="text-xs text-slate-400">Tage</span>
+            </div>
+          </div>
         </div>
 
         <div className="bg-purple-500/10 border border-purple-500/30 rounded-lg p-4 mt-6">
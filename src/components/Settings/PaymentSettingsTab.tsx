@@ -14,6 +14,8 @@ interface PaymentSettings {
   paymentText: string | null;
   updatedAt: string | null;
   dpolgRabatt: number | null;
+  glaeubigerId: string | null;
+  sepaVorlaufTage: number | null;
 }
 
 export default function PaymentSettingsTab() {
@@ -26,6 +28,8 @@ export default function PaymentSettingsTab() {
   const [paymentDueDays, setPaymentDueDays] = useState(14);
   const [reminderAfterDays, setReminderAfterDays] = useState(14);
   const [paymentText, setPaymentText] = useState('');
+  const [glaeubigerId, setGlaeubigerId] = useState('');
+  const [sepaVorlaufTage, setSepaVorlaufTage] = useState(2);
   const [loading, setLoading] = useState(false);
   const [loadingData, setLoadingData] = useState(true);
   const [successMessage, setSuccessMessage] = useState<string | null>(null);
@@ -49,6 +53,8 @@ export default function PaymentSettingsTab() {
       setPaymentDueDays(data.paymentDueDays || 14);
       setReminderAfterDays(data.reminderAfterDays || 14);
       setPaymentText(data.paymentText || '');
+      setGlaeubigerId(data.glaeubigerId || '');
+      setSepaVorlaufTage(data.sepaVorlaufTage || 2);
     } catch (error) {
       console.error('Fehler beim Laden der Zahlungseinstellungen:', error);
       alert(`Fehler beim Laden: ${error}`);
@@ -75,6 +81,9 @@ export default function PaymentSettingsTab() {
         paymentText: paymentText || null,
         updatedAt: null,
         dpolgRabatt: settings?.dpolgRabatt || null,
+        // Leerer String entfernt die Gläubiger-ID
+        glaeubigerId: glaeubigerId.trim(),
+        sepaVorlaufTage: sepaVorlaufTage,
       };
 
       const result = await invoke<PaymentSettings>('update_payment_settings_pg', {
@@ -221,6 +230,45 @@ export default function PaymentSettingsTab() {
         </div>
       </div>
 
+      <div className="border-t border-slate-700 pt-6">
+        <h3 className="text-lg font-bold text-white mb-4">SEPA-Lastschrift</h3>
+        <div className="space-y-4">
+          {/* Creditor ID */}
+          <div>
+            <label className="block text-sm font-medium text-slate-300 mb-2">
+              Gläubiger-Identifikationsnummer
+            </label>
+            <input
+              type="text"
+              value={glaeubigerId}
+              onChange={(e) => setGlaeubigerId(e.target.value)}
+              placeholder="DE98ZZZ09999999999"
+              className="w-full px-4 py-3 bg-slate-700 border border-slate-600 rounded-lg text-white placeholder-slate-400 focus:outline-none focus:ring-2 focus:ring-blue-500"
+            />
+            <p className="text-xs text-slate-400 mt-1">
+              Von der Bundesbank vergeben, Voraussetzung für den Lastschrift-Export
+            </p>
+          </div>
+
+          {/* Lead Days */}
+          <div>
+            <label className="block text-sm font-medium text-slate-300 mb-2">
+              Vorlauf (Bankarbeitstage)
+            </label>
+            <input
+              type="number"
+              min="1"
+              value={sepaVorlaufTage}
+              onChange={(e) => setSepaVorlaufTage(parseInt(e.target.value))}
+              className="w-full px-4 py-3 bg-slate-700 border border-slate-600 rounded-lg text-white placeholder-slate-400 focus:outline-none focus:ring-2 focus:ring-blue-500"
+            />
+            <p className="text-xs text-slate-400 mt-1">
+              Frühestes Einzugsdatum nach dem Export der Datei
+            </p>
+          </div>
+        </div>
+      </div>
+
       {/* Success Message */}
       {successMessage && (
         <div className="bg-emerald-500/10 border border-emerald-500/30 rounded-lg p-4">
@@ -1,6 +1,7 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { DollarSign, Calendar, Percent, Target } from 'lucide-react';
+import KurtaxeRatesSection from './KurtaxeRatesSection';
 
 interface PricingSettings {
   id: number;
@@ -307,6 +308,9 @@ export default function PricingSettingsTab() {
           )}
         </button>
       </div>
+
+      {/* Kurtaxe (eigene Speichern-Buttons pro Ort) */}
+      <KurtaxeRatesSection />
     </div>
   );
 }
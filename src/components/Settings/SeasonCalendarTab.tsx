@@ -0,0 +1,343 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { CalendarRange, ChevronLeft, ChevronRight, Copy, Plus, Save, Trash2 } from 'lucide-react';
+import toast from 'react-hot-toast';
+import { useData } from '../../context/DataContext';
+import { formatDate } from '../../utils/dateFormatting';
+
+interface Season {
+  id: number;
+  name: string;
+  year: number;
+  startDate: string; // erste Nacht
+  endDate: string; // letzte Nacht (inklusive)
+  priority: number;
+  color?: string | null;
+  isActive: boolean;
+}
+
+interface RoomSeasonPrice {
+  id: number;
+  roomId: number;
+  seasonId: number;
+  pricePerNight: number;
+}
+
+type SeasonForm = Omit<Season, 'id' | 'year'> & { id: number | null };
+
+const emptySeason = (year: number): SeasonForm => ({
+  id: null,
+  name: '',
+  startDate: `${year}-01-01`,
+  endDate: `${year}-01-31`,
+  priority: 0,
+  color: '#3b82f6',
+  isActive: true,
+});
+
+// Saisonkalender: Saisons pro Jahr mit Zimmerpreisen pro Nacht (vor Haupt-/Nebensaison des Zimmers)
+export default function SeasonCalendarTab() {
+  const { rooms } = useData();
+  const [year, setYear] = useState(new Date().getFullYear());
+  const [seasons, setSeasons] = useState<Season[]>([]);
+  const [form, setForm] = useState<SeasonForm | null>(null);
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+  const [prices, setPrices] = useState<Record<number, string>>({});
+  const [increase, setIncrease] = useState('0');
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    loadSeasons();
+    setForm(null);
+    setSelectedId(null);
+  }, [year]);
+
+  useEffect(() => {
+    if (selectedId !== null) loadPrices(selectedId);
+  }, [selectedId]);
+
+  const loadSeasons = async () => {
+    try {
+      setSeasons(await invoke<Season[]>('get_seasons_pg', { year }));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Saisons: ${error}`);
+    }
+  };
+
+  const loadPrices = async (seasonId: number) => {
+    try {
+      const data = await invoke<RoomSeasonPrice[]>('get_room_season_prices_pg', { seasonId });
+      setPrices(Object.fromEntries(data.map((p) => [p.roomId, p.pricePerNight.toFixed(2).replace('.', ',')])));
+    } catch (error) {
+      toast.error(`Fehler beim Laden der Zimmerpreise: ${error}`);
+    }
+  };
+
+  const saveSeason = async () => {
+    if (!form) return;
+    setSaving(true);
+    try {
+      const args = {
+        name: form.name.trim(),
+        year,
+        startDate: form.startDate,
+        endDate: form.endDate,
+        priority: form.priority,
+        color: form.color || null,
+      };
+      const saved =
+        form.id === null
+          ? await invoke<Season>('create_season_pg', args)
+          : await invoke<Season>('update_season_pg', { ...args, id: form.id, isActive: form.isActive });
+      toast.success(`Saison ${saved.name} gespeichert`);
+      setForm(null);
+      setSelectedId(saved.id);
+      loadSeasons();
+    } catch (error) {
+      toast.error(String(error));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const deleteSeason = async (season: Season) => {
+    if (!window.confirm(`Saison ${season.name} mit allen Zimmerpreisen löschen?`)) return;
+    try {
+      await invoke('delete_season_pg', { id: season.id });
+      if (selectedId === season.id) setSelectedId(null);
+      loadSeasons();
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const saveRoomPrice = async (roomId: number) => {
+    if (selectedId === null) return;
+    const value = prices[roomId]?.trim();
+    try {
+      if (value) {
+        await invoke('set_room_season_price_pg', {
+          roomId,
+          seasonId: selectedId,
+          pricePerNight: Number(value.replace(',', '.')),
+        });
+      } else {
+        await invoke('delete_room_season_price_pg', { roomId, seasonId: selectedId });
+      }
+      toast.success('Zimmerpreis gespeichert');
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const copyYear = async () => {
+    if (!window.confirm(`Saisons und Zimmerpreise von ${year} nach ${year + 1} kopieren?`)) return;
+    try {
+      const message = await invoke<string>('copy_price_list_to_year_command', {
+        fromYear: year,
+        toYear: year + 1,
+        priceIncreasePercent: Number(increase.replace(',', '.')) || 0,
+      });
+      toast.success(message, { duration: 6000 });
+      setYear(year + 1);
+    } catch (error) {
+      toast.error(String(error));
+    }
+  };
+
+  const selected = seasons.find((s) => s.id === selectedId);
+  const inputClass =
+    'px-2 py-1 bg-slate-600 border border-slate-500 rounded text-white text-sm focus:outline-none focus:ring-2 focus:ring-blue-500';
+
+  return (
+    <div className="space-y-6">
+      {/* Header */}
+      <div className="flex items-center justify-between">
+        <div className="flex items-center gap-3">
+          <div className="p-2 bg-blue-500/20 rounded-lg">
+            <CalendarRange className="w-5 h-5 text-blue-400" />
+          </div>
+          <div>
+            <h3 className="text-lg font-semibold text-white">Saisonkalender</h3>
+            <p className="text-sm text-slate-400">
+              Saisonpreise gelten vor den Haupt-/Nebensaison-Preisen der Zimmer; bei Überschneidung gewinnt die höhere
+              Priorität
+            </p>
+          </div>
+        </div>
+        <div className="flex items-center gap-2">
+          <button type="button" onClick={() => setYear(year - 1)} className="p-1 text-slate-300 hover:text-white">
+            <ChevronLeft className="w-5 h-5" />
+          </button>
+          <span className="text-lg font-bold text-white w-14 text-center">{year}</span>
+          <button type="button" onClick={() => setYear(year + 1)} className="p-1 text-slate-300 hover:text-white">
+            <ChevronRight className="w-5 h-5" />
+          </button>
+        </div>
+      </div>
+
+      {/* Saisons */}
+      <div className="bg-slate-700/50 rounded-lg p-6 space-y-2">
+        {seasons.length === 0 && <p className="text-sm text-slate-400">Keine Saisons für {year} angelegt.</p>}
+        {seasons.map((season) => (
+          <div
+            key={season.id}
+            onClick={() => setSelectedId(season.id)}
+            className={`flex items-center justify-between gap-3 px-3 py-2 rounded-lg cursor-pointer text-sm ${
+              selectedId === season.id ? 'bg-slate-600' : 'hover:bg-slate-600/50'
+            }`}
+          >
+            <span className="flex items-center gap-2 text-white">
+              <span className="w-3 h-3 rounded-full" style={{ backgroundColor: season.color || '#64748b' }} />
+              <span className={`font-semibold ${season.isActive ? '' : 'line-through text-slate-400'}`}>{season.name}</span>
+              <span className="text-slate-300">
+                {formatDate(season.startDate)} – {formatDate(season.endDate)}
+              </span>
+              {season.priority !== 0 && <span className="text-xs text-slate-400">Priorität {season.priority}</span>}
+            </span>
+            <span className="flex items-center gap-2">
+              <button
+                type="button"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  setForm({ ...season, id: season.id });
+                }}
+                className="text-xs text-blue-300 hover:text-blue-200"
+              >
+                Bearbeiten
+              </button>
+              <button
+                type="button"
+                onClick={(e) => {
+                  e.stopPropagation();
+                  deleteSeason(season);
+                }}
+                className="p-1 text-red-400 hover:text-red-300"
+                title="Saison löschen"
+              >
+                <Trash2 className="w-4 h-4" />
+              </button>
+            </span>
+          </div>
+        ))}
+
+        {form ? (
+          <div className="flex flex-wrap items-center gap-2 pt-2">
+            <input
+              value={form.name}
+              onChange={(e) => setForm({ ...form, name: e.target.value })}
+              placeholder="Name (z.B. Ostern)"
+              className={`${inputClass} w-40`}
+            />
+            <input
+              type="date"
+              value={form.startDate}
+              onChange={(e) => setForm({ ...form, startDate: e.target.value })}
+              className={inputClass}
+              title="Erste Nacht"
+            />
+            <input
+              type="date"
+              value={form.endDate}
+              onChange={(e) => setForm({ ...form, endDate: e.target.value })}
+              className={inputClass}
+              title="Letzte Nacht"
+            />
+            <label className="flex items-center gap-1 text-xs text-slate-300">
+              Priorität
+              <input
+                type="number"
+                value={form.priority}
+                onChange={(e) => setForm({ ...form, priority: parseInt(e.target.value) || 0 })}
+                className={`${inputClass} w-16`}
+              />
+            </label>
+            <input
+              type="color"
+              value={form.color || '#3b82f6'}
+              onChange={(e) => setForm({ ...form, color: e.target.value })}
+              className="w-8 h-8 bg-transparent"
+            />
+            {form.id !== null && (
+              <label className="flex items-center gap-1 text-xs text-slate-300">
+                <input
+                  type="checkbox"
+                  checked={form.isActive}
+                  onChange={(e) => setForm({ ...form, isActive: e.target.checked })}
+                />
+                aktiv
+              </label>
+            )}
+            <button
+              type="button"
+              onClick={saveSeason}
+              disabled={saving || !form.name.trim()}
+              className="flex items-center gap-1 px-3 py-1.5 bg-blue-600 hover:bg-blue-700 disabled:opacity-50 text-white rounded-lg text-xs font-semibold"
+            >
+              <Save className="w-4 h-4" />
+              Speichern
+            </button>
+            <button type="button" onClick={() => setForm(null)} className="text-xs text-slate-300 hover:text-white">
+              Abbrechen
+            </button>
+          </div>
+        ) : (
+          <button
+            type="button"
+            onClick={() => setForm(emptySeason(year))}
+            className="flex items-center gap-1 px-3 py-1.5 bg-slate-600 hover:bg-slate-500 text-white rounded-lg text-xs font-semibold"
+          >
+            <Plus className="w-4 h-4" />
+            Saison hinzufügen
+          </button>
+        )}
+      </div>
+
+      {/* Zimmerpreise der gewählten Saison */}
+      {selected && (
+        <div className="bg-slate-700/50 rounded-lg p-6 space-y-2">
+          <h4 className="text-base font-semibold text-white">Zimmerpreise pro Nacht – {selected.name}</h4>
+          <p className="text-xs text-slate-400">Leeres Feld = Zimmer nutzt in dieser Saison seinen normalen Preis.</p>
+          <div className="grid grid-cols-2 gap-2">
+            {rooms.map((room) => (
+              <div key={room.id} className="flex items-center gap-2 text-sm text-slate-200">
+                <span className="flex-1 truncate">{room.name}</span>
+                <input
+                  inputMode="decimal"
+                  value={prices[room.id] ?? ''}
+                  onChange={(e) => setPrices({ ...prices, [room.id]: e.target.value })}
+                  onBlur={() => saveRoomPrice(room.id)}
+                  placeholder={room.hauptsaison_preis.toFixed(2).replace('.', ',')}
+                  className={`${inputClass} w-24 text-right`}
+                />
+                €
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
+      {/* Preisliste ins Folgejahr kopieren */}
+      {seasons.length > 0 && (
+        <div className="bg-slate-700/50 rounded-lg p-6 flex flex-wrap items-center gap-3">
+          <Copy className="w-5 h-5 text-emerald-400" />
+          <span className="text-sm text-white">Preisliste nach {year + 1} kopieren, Preise anpassen um</span>
+          <input
+            inputMode="decimal"
+            value={increase}
+            onChange={(e) => setIncrease(e.target.value)}
+            className={`${inputClass} w-16 text-right`}
+          />
+          <span className="text-sm text-slate-300">%</span>
+          <button
+            type="button"
+            onClick={copyYear}
+            className="px-3 py-1.5 bg-emerald-600 hover:bg-emerald-700 text-white rounded-lg text-xs font-semibold"
+          >
+            Kopieren
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { X, Mail, FileText, CreditCard, Building2, Bell, HardDrive, DollarSign, Users } from 'lucide-react';
+import { X, Mail, FileText, CreditCard, Building2, Bell, HardDrive, DollarSign, Users, CalendarRange } from 'lucide-react';
 import EmailConfigTab from './EmailConfigTab';
 import EmailTemplatesTab from './EmailTemplatesTab';
 import PaymentSettingsTab from './PaymentSettingsTab';
@@ -7,6 +7,7 @@ import GeneralSettingsTab from './GeneralSettingsTab';
 import NotificationsTab from './NotificationsTab';
 import BackupTab from './BackupTab';
 import PricingSettingsTab from './PricingSettingsTab';
+import SeasonCalendarTab from './SeasonCalendarTab';
 import PaymentRecipientsTab from './PaymentRecipientsTab';
 
 interface SettingsDialogProps {
@@ -14,7 +15,7 @@ interface SettingsDialogProps {
   onClose: () => void;
 }
 
-type SettingsTab = 'email' | 'templates' | 'payment' | 'payment_recipients' | 'pricing' | 'general' | 'notifications' | 'backup';
+type SettingsTab = 'email' | 'templates' | 'payment' | 'payment_recipients' | 'pricing' | 'seasons' | 'general' | 'notifications' | 'backup';
 
 export default function SettingsDialog({ isOpen, onClose }: SettingsDialogProps) {
   const [activeTab, setActiveTab] = useState<SettingsTab>('general');
@@ -25,6 +26,7 @@ export default function SettingsDialog({ isOpen, onClose }: SettingsDialogProps)
     // 🏢 GRUNDEINSTELLUNGEN (meist verwendet)
     { id: 'general' as SettingsTab, label: 'Allgemein', icon: Building2 },
     { id: 'pricing' as SettingsTab, label: 'Preise', icon: DollarSign },
+    { id: 'seasons' as SettingsTab, label: 'Saisonkalender', icon: CalendarRange },
 
     // 💳 BUSINESS LOGIC (täglich verwendet)
     { id: 'payment' as SettingsTab, label: 'Zahlungseinstellungen', icon: CreditCard },
@@ -100,6 +102,7 @@ export default function SettingsDialog({ isOpen, onClose }: SettingsDialogProps)
           {activeTab === 'payment' && <PaymentSettingsTab />}
           {activeTab === 'payment_recipients' && <PaymentRecipientsTab />}
           {activeTab === 'pricing' && <PricingSettingsTab />}
+          {activeTab === 'seasons' && <SeasonCalendarTab />}
           {activeTab === 'general' && <GeneralSettingsTab />}
           {activeTab === 'notifications' && <NotificationsTab />}
           {activeTab === 'backup' && <BackupTab />}
@@ -0,0 +1,106 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Landmark } from 'lucide-react';
+import { format, subMonths } from 'date-fns';
+
+// Zeile der Monatsmeldung pro Gemeinde (Backend liefert snake_case)
+interface KurtaxeReportRow {
+  ort: string;
+  total_bookings: number;
+  liable_nights: number;
+  exempt_age_nights: number;
+  exempt_business_nights: number;
+  total_kurtaxe: number;
+}
+
+// Monatliche Kurtaxe-Meldung an die Gemeinden - standardmäßig für den Vormonat
+export default function KurtaxeReport() {
+  const [month, setMonth] = useState(format(subMonths(new Date(), 1), 'yyyy-MM'));
+  const [rows, setRows] = useState<KurtaxeReportRow[]>([]);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const [year, monthNumber] = month.split('-').map(Number);
+    if (!year || !monthNumber) return;
+    invoke<KurtaxeReportRow[]>('get_kurtaxe_report_command', { year, month: monthNumber })
+      .then((data) => {
+        setRows(data);
+        setError(null);
+      })
+      .catch((err) => setError(String(err)));
+  }, [month]);
+
+  const total = rows.reduce(
+    (sum, row) => ({
+      bookings: sum.bookings + row.total_bookings,
+      liable: sum.liable + row.liable_nights,
+      age: sum.age + row.exempt_age_nights,
+      business: sum.business + row.exempt_business_nights,
+      kurtaxe: sum.kurtaxe + row.total_kurtaxe,
+    }),
+    { bookings: 0, liable: 0, age: 0, business: 0, kurtaxe: 0 }
+  );
+
+  return (
+    <div className="mb-8">
+      <div className="flex items-center justify-between mb-6">
+        <h2 className="text-2xl font-bold text-white flex items-center gap-2">
+          <Landmark className="w-6 h-6 text-blue-400" />
+          Kurtaxe-Meldung
+        </h2>
+        <input
+          type="month"
+          value={month}
+          onChange={(e) => setMonth(e.target.value)}
+          className="px-3 py-2 bg-slate-700 border border-slate-600 rounded-lg text-white text-sm focus:outline-none focus:ring-2 focus:ring-blue-500"
+        />
+      </div>
+
+      <div className="bg-gradient-to-br from-slate-800 to-slate-900 rounded-xl p-6 border border-slate-700">
+        {error && <p className="text-red-400 text-sm mb-4">{error}</p>}
+        {rows.length === 0 ? (
+          <p className="text-slate-400">Keine Übernachtungen mit Kurtaxe in diesem Monat.</p>
+        ) : (
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="text-slate-400 border-b border-slate-700">
+                <th className="py-2 text-left font-medium">Gemeinde</th>
+                <th className="py-2 text-right font-medium">Buchungen</th>
+                <th className="py-2 text-right font-medium">Pflichtige Nächte</th>
+                <th className="py-2 text-right font-medium">Befreit (Alter)</th>
+                <th className="py-2 text-right font-medium">Befreit (Geschäftsreise)</th>
+                <th className="py-2 text-right font-medium">Kurtaxe</th>
+              </tr>
+            </thead>
+            <tbody className="text-white">
+              {rows.map((row) => (
+                <tr key={row.ort} className="border-b border-slate-700/50">
+                  <td className="py-2 font-semibold">{row.ort}</td>
+                  <td className="py-2 text-right">{row.total_bookings}</td>
+                  <td className="py-2 text-right">{row.liable_nights}</td>
+                  <td className="py-2 text-right">{row.exempt_age_nights}</td>
+                  <td className="py-2 text-right">{row.exempt_business_nights}</td>
+                  <td className="py-2 text-right font-semibold">{row.total_kurtaxe.toFixed(2)} €</td>
+                </tr>
+              ))}
+              {rows.length > 1 && (
+                <tr className="font-bold">
+                  <td className="py-2">Summe</td>
+                  <td className="py-2 text-right">{total.bookings}</td>
+                  <td className="py-2 text-right">{total.liable}</td>
+                  <td className="py-2 text-right">{total.age}</td>
+                  <td className="py-2 text-right">{total.business}</td>
+                  <td className="py-2 text-right">{total.kurtaxe.toFixed(2)} €</td>
+                </tr>
+              )}
+            </tbody>
+          </table>
+        )}
+        <p className="text-xs text-slate-500 mt-4">
+          Personen-Nächte im gewählten Monat laut Kurtaxe-Stand der Buchungen; Aufenthalte über den Monatswechsel werden
+          anteilig gezählt.
+        </p>
+      </div>
+    </div>
+  );
+}
@@ -3,6 +3,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { TrendingUp, Home, Users, Calendar, DollarSign, Clock, Award, UserCheck, Repeat, XCircle, CalendarRange, Heart, Briefcase, Moon } from 'lucide-react';
 import { useBatchPriceCalculation, getBookingPrice } from '../../hooks/useBatchPriceCalculation';
 import FilterDatePicker from '../BookingManagement/FilterDatePicker';
+import KurtaxeReport from './KurtaxeReport';
 
 interface Room {
   id: number;
@@ -685,6 +686,9 @@ export default function StatisticsView() {
           </div>
         </div>
       </div>
+
+      {/* Kurtaxe Section */}
+      <KurtaxeReport />
     </div>
   );
 }
@@ -2,6 +2,7 @@ import React, { useState, useRef, useEffect, useLayoutEffect, useCallback, useMe
 import { format, addDays, differenceInDays, startOfMonth, endOfMonth, eachDayOfInterval, addMonths, subMonths, startOfDay, isSameDay } from 'date-fns';
 import { de } from 'date-fns/locale';
 import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import toast from 'react-hot-toast';
 import { cn } from '../lib/utils';
 import { useData } from '../context/DataContext';
@@ -23,10 +24,12 @@ import {
 } from '@dnd-kit/core';
 import { CSS } from '@dnd-kit/utilities';
 import ContextMenu, { ContextMenuItem } from './ContextMenu';
-import { Edit2, Mail, XCircle, X, Users } from 'lucide-react';
+import { Edit2, Mail, XCircle, X, Users, Wrench, Shuffle } from 'lucide-react';
 import ChangeConfirmationDialog from './TapeChart/ChangeConfirmationDialog';
 import TapeChartFilters from './TapeChart/TapeChartFilters';
+import RoomAssignmentDialog from './TapeChart/RoomAssignmentDialog';
 import { filterBookings, getUniqueRoomTypes } from './TapeChart/TapeChartHelpers';
+import type { RoomBlock } from '../types/booking';
 
 interface Room {
   id: number;
@@ -85,6 +88,13 @@ interface TapeChartProps {
 }
 
 const STATUS_COLORS: Record<string, { bg: string; border: string; text: string; shadow: string }> = {
+  // Option: Zimmer nur vorgemerkt (gestrichelt, läuft ohne Bestätigung ab)
+  optioniert: {
+    bg: 'bg-gradient-to-r from-orange-300 to-orange-400',
+    border: 'border-dashed border-orange-600',
+    text: 'text-white',
+    shadow: 'shadow-lg shadow-orange-400/50'
+  },
   bestaetigt: {
     bg: 'bg-gradient-to-r from-emerald-500 to-emerald-600',
     border: 'border-emerald-700',
@@ -667,6 +677,36 @@ export default function TapeChart({ startDate, endDate, onBookingClick, onCreate
 
   const days = eachDayOfInterval({ start: defaultStart, end: defaultEnd });
 
+  // Zimmersperrungen (Wartung, Renovierung) im sichtbaren Zeitraum - eigene Balken, nicht verschiebbar
+  const [roomBlocks, setRoomBlocks] = useState<RoomBlock[]>([]);
+  const [showRoomAssignment, setShowRoomAssignment] = useState(false);
+  const blocksFrom = format(defaultStart, 'yyyy-MM-dd');
+  const blocksTo = format(addDays(defaultEnd, 1), 'yyyy-MM-dd');
+
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+    let cancelled = false;
+
+    const loadBlocks = () => {
+      invoke<RoomBlock[]>('get_room_blocks_pg', { from: blocksFrom, to: blocksTo })
+        .then((blocks) => { if (!cancelled) setRoomBlocks(blocks); })
+        .catch((error) => console.error('Fehler beim Laden der Zimmersperrungen:', error));
+    };
+
+    loadBlocks();
+    listen<{ table: string }>('db-change', (event) => {
+      if (event.payload.table === 'room_blocks') loadBlocks();
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+
+    return () => {
+      cancelled = true;
+      if (unlisten) unlisten();
+    };
+  }, [blocksFrom, blocksTo]);
+
   // Filter bookings
   // NORMALIZED STATE: Pass guestMap and roomMap for O(1) lookups
   const filteredBookings = filterBookings(localBookings, searchQuery, statusFilter, roomTypeFilter, guestMap, roomMap)
@@ -750,7 +790,7 @@ export default function TapeChart({ startDate, endDate, onBookingClick, onCreate
     'Juli', 'August', 'September', 'Oktober', 'November', 'Dezember'
   ];
 
-  const getBookingPosition = (booking: Booking) => {
+  const getBookingPosition = (booking: Pick<Booking, 'checkin_date' | 'checkout_date'>) => {
     const checkin = startOfDay(new Date(booking.checkin_date));
     const checkout = startOfDay(new Date(booking.checkout_date));
 
@@ -1758,6 +1798,15 @@ export default function TapeChart({ startDate, endDate, onBookingClick, onCreate
               </button>
             </div>
 
+            <button
+              onClick={() => setShowRoomAssignment(true)}
+              className="bg-slate-600/50 hover:bg-slate-600 backdrop-blur-sm text-white py-2 rounded-lg font-bold text-sm transition-all shadow-lg flex items-center gap-2 border border-slate-500/30 px-3"
+              title="Zukünftige Buchungen umverteilen, um kurze Lücken zu schließen"
+            >
+              <Shuffle className="w-4 h-4" />
+              <span>Optimieren</span>
+            </button>
+
             <button
               onClick={goToToday}
               className="bg-gradient-to-r from-emerald-500 to-emerald-600 hover:from-emerald-600 hover:to-emerald-700 text-white py-2 rounded-lg font-bold text-sm transition-all shadow-lg flex items-center gap-2 border border-emerald-400/40 hover:border-emerald-300/60 px-3"
@@ -1944,6 +1993,32 @@ export default function TapeChart({ startDate, endDate, onBookingClick, onCreate
                         />
                       );
                     })}
+
+                  {/* Room Blocks (Wartung / außer Betrieb) */}
+                  {roomBlocks
+                    .filter((block) => block.roomId === room.id)
+                    .map((block) => {
+                      const pos = getBookingPosition({ checkin_date: block.startDate, checkout_date: block.endDate });
+                      if (!pos.isVisible) return null;
+
+                      return (
+                        <div
+                          key={`block-${block.id}`}
+                          className="absolute z-10 flex items-center gap-1.5 px-2 rounded-lg border-2 border-slate-500 text-slate-700 text-xs font-semibold overflow-hidden select-none"
+                          style={{
+                            left: `${pos.left}px`,
+                            width: `${pos.width}px`,
+                            top: '8px',
+                            bottom: '8px',
+                            backgroundImage: 'repeating-linear-gradient(45deg, #cbd5e1 0, #cbd5e1 8px, #e2e8f0 8px, #e2e8f0 16px)',
+                          }}
+                          title={`Gesperrt: ${block.reason} (${format(new Date(block.startDate), 'dd.MM.yyyy')} - ${format(new Date(block.endDate), 'dd.MM.yyyy')})${block.bemerkungen ? `\n${block.bemerkungen}` : ''}`}
+                        >
+                          <Wrench className="w-3 h-3 flex-shrink-0" />
+                          <span className="truncate">{block.reason}</span>
+                        </div>
+                      );
+                    })}
                 </div>
               </div>
             );
@@ -2047,6 +2122,14 @@ export default function TapeChart({ startDate, endDate, onBookingClick, onCreate
           />
         )}
 
+        {/* Room Assignment (optimiser) Dialog */}
+        <RoomAssignmentDialog
+          isOpen={showRoomAssignment}
+          onClose={() => setShowRoomAssignment(false)}
+          from={blocksFrom}
+          to={blocksTo}
+        />
+
         {/* Change Confirmation Dialog */}
         {showChangeConfirmation && pendingChange && (
           <ChangeConfirmationDialog
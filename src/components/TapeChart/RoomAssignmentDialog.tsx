@@ -0,0 +1,157 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { X, Shuffle, ArrowRight, Loader2 } from 'lucide-react';
+import { format, parseISO } from 'date-fns';
+import { de } from 'date-fns/locale';
+import toast from 'react-hot-toast';
+import { useData } from '../../context/DataContext';
+import { useUser } from '../../context/UserContext';
+import type { AssignmentPlan } from '../../types/booking';
+
+interface RoomAssignmentDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+  from: string;
+  to: string;
+}
+
+export default function RoomAssignmentDialog({ isOpen, onClose, from, to }: RoomAssignmentDialogProps) {
+  const { bookings, guestMap, roomMap, refreshBookings } = useData();
+  const { userName } = useUser();
+  const [plan, setPlan] = useState<AssignmentPlan | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [applying, setApplying] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!isOpen) return;
+    let cancelled = false;
+    setPlan(null);
+    setError(null);
+    setLoading(true);
+    invoke<AssignmentPlan>('suggest_room_assignment_pg', { from, to })
+      .then((result) => {
+        if (!cancelled) setPlan(result);
+      })
+      .catch((err) => {
+        if (!cancelled) setError(String(err));
+      })
+      .finally(() => {
+        if (!cancelled) setLoading(false);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [isOpen, from, to]);
+
+  const handleApply = async () => {
+    if (!plan || plan.moves.length === 0) return;
+    setApplying(true);
+    setError(null);
+    try {
+      await invoke('apply_room_assignment_pg', { moves: plan.moves, currentUser: userName });
+      await refreshBookings();
+      toast.success(`${plan.moves.length} Buchung(en) umgebucht`);
+      onClose();
+    } catch (err) {
+      setError(String(err).replace(/^(DOUBLE_BOOKING|CONFLICT):/, ''));
+    } finally {
+      setApplying(false);
+    }
+  };
+
+  if (!isOpen) return null;
+
+  const formatDate = (dateStr: string) => format(parseISO(dateStr), 'dd.MM.', { locale: de });
+
+  return (
+    <div className="fixed inset-0 bg-black/50 backdrop-blur-sm flex items-center justify-center z-50">
+      <div className="bg-gradient-to-br from-slate-800 to-slate-900 rounded-2xl shadow-2xl p-8 max-w-2xl w-full mx-4">
+        {/* Header */}
+        <div className="flex items-center justify-between mb-6">
+          <div>
+            <h2 className="text-2xl font-bold text-white">Zimmer optimieren</h2>
+            <p className="text-slate-400 text-sm mt-1">
+              {format(parseISO(from), 'dd.MM.yyyy')} - {format(parseISO(to), 'dd.MM.yyyy')} · kurze Lücken (1-2 Nächte) schließen
+            </p>
+          </div>
+          <button onClick={onClose} className="p-2 hover:bg-slate-700 rounded-lg transition-colors">
+            <X className="w-5 h-5 text-slate-300" />
+          </button>
+        </div>
+
+        {loading && (
+          <div className="flex items-center gap-2 text-slate-300">
+            <Loader2 className="w-4 h-4 animate-spin" />
+            Vorschlag wird berechnet...
+          </div>
+        )}
+
+        {error && (
+          <div className="mb-4 p-3 bg-red-500/10 border border-red-500/40 rounded-lg text-sm text-red-300">{error}</div>
+        )}
+
+        {plan && plan.moves.length === 0 && (
+          <p className="text-slate-300">
+            Keine Verbesserung möglich ({plan.shortGapNightsBefore} Nächte in kurzen Lücken). Fixierte, laufende und
+            Gruppen-/Kontingentbuchungen werden nicht verschoben.
+          </p>
+        )}
+
+        {plan && plan.moves.length > 0 && (
+          <>
+            <p className="text-slate-300 mb-4">
+              Nächte in kurzen Lücken: {plan.shortGapNightsBefore} → {plan.shortGapNightsAfter}
+            </p>
+            <div className="space-y-2 max-h-80 overflow-y-auto mb-6">
+              {plan.moves.map((move) => {
+                const booking = bookings.find((b) => b.id === move.bookingId);
+                const guest = booking ? guestMap.get(booking.guest_id) : undefined;
+                return (
+                  <div key={move.bookingId} className="flex items-center justify-between p-3 bg-slate-700/50 rounded-lg">
+                    <div>
+                      <div className="text-white font-semibold text-sm">
+                        {booking?.reservierungsnummer ?? `#${move.bookingId}`}
+                        {guest && <span className="text-slate-400 font-normal"> · {guest.vorname} {guest.nachname}</span>}
+                      </div>
+                      {booking && (
+                        <div className="text-xs text-slate-400">
+                          {formatDate(booking.checkin_date)} - {formatDate(booking.checkout_date)}
+                        </div>
+                      )}
+                    </div>
+                    <div className="flex items-center gap-2 text-sm">
+                      <span className="text-slate-300">{roomMap.get(move.fromRoomId)?.name ?? move.fromRoomId}</span>
+                      <ArrowRight className="w-4 h-4 text-slate-500" />
+                      <span className="text-emerald-400 font-semibold">{roomMap.get(move.toRoomId)?.name ?? move.toRoomId}</span>
+                    </div>
+                  </div>
+                );
+              })}
+            </div>
+          </>
+        )}
+
+        {/* Footer */}
+        <div className="flex justify-end gap-3">
+          <button
+            onClick={onClose}
+            className="px-4 py-2 bg-slate-700 hover:bg-slate-600 text-white rounded-lg font-semibold transition-colors"
+          >
+            Schließen
+          </button>
+          {plan && plan.moves.length > 0 && (
+            <button
+              onClick={handleApply}
+              disabled={applying}
+              className="flex items-center gap-2 px-4 py-2 bg-emerald-600 hover:bg-emerald-700 disabled:opacity-50 text-white rounded-lg font-semibold transition-colors"
+            >
+              {applying ? <Loader2 className="w-4 h-4 animate-spin" /> : <Shuffle className="w-4 h-4" />}
+              Umbuchen
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+}
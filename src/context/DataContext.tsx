@@ -364,6 +364,8 @@ export function DataProvider({ children }: { children: ReactNode }) {
         postalCode: data.postalCode || null,
         city: data.city || null,
         notizen: data.notizen || null,
+        hundeErlaubt: data.hundeErlaubt ?? null,
+        erdgeschoss: data.erdgeschoss ?? null,
         currentUser: userName,
       });
 
@@ -403,6 +405,8 @@ export function DataProvider({ children }: { children: ReactNode }) {
         postalCode: data.postalCode || null,
         city: data.city || null,
         notizen: data.notizen || null,
+        hundeErlaubt: data.hundeErlaubt ?? null,
+        erdgeschoss: data.erdgeschoss ?? null,
         currentUser: userName,
       });
 
@@ -85,6 +85,9 @@ export interface PriceCalculationInput {
   isMember: boolean;
   services: ServiceInput[];
   discounts: DiscountInput[];
+  // Belegung (Zimmer mit Belegungspreisen): Gäste inkl. Hauptgast, Geburtsdaten der Begleitpersonen
+  anzahlGaeste?: number;
+  companionBirthdates?: (string | null)[];
 }
 
 // ============================================================================
@@ -173,6 +176,8 @@ export function usePriceCalculation(input: PriceCalculationInput | null) {
           isMember: input.isMember,
           services: input.services.length > 0 ? input.services : undefined,
           discounts: input.discounts.length > 0 ? input.discounts : undefined,
+          anzahlGaeste: input.anzahlGaeste,
+          companionBirthdates: input.companionBirthdates,
         }
       );
 
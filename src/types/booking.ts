@@ -11,6 +11,8 @@ export interface Room {
   streetAddress?: string;
   postalCode?: string;
   city?: string;
+  hunde_erlaubt?: boolean | null;
+  erdgeschoss?: boolean | null;
   created_at?: string;
   updated_at?: string;
   // Audit Trail
@@ -58,6 +60,15 @@ export interface Guest {
   leitweg_id?: string; // E-Rechnungs-Leitweg-ID
   kostenstelle?: string;
   tags?: string; // Komma-separierte Tags
+  // SEPA-Lastschriftmandat
+  sepa_mandatsreferenz?: string;
+  sepa_mandat_datum?: string; // Unterschriftsdatum (YYYY-MM-DD)
+  sepa_iban?: string;
+  sepa_bic?: string;
+  sepa_kontoinhaber?: string; // leer = Name des Gastes
+  sepa_mandat_typ?: 'CORE' | 'B2B';
+  sepa_einmalig?: boolean;
+  sepa_letzte_lastschrift?: string; // leer = nächste Lastschrift ist Erstlastschrift (FRST)
 }
 
 export interface Booking {
@@ -69,7 +80,7 @@ export interface Booking {
   checkout_date: string;
   anzahl_gaeste: number;
   anzahl_begleitpersonen: number;
-  status: 'reserviert' | 'bestaetigt' | 'eingecheckt' | 'ausgecheckt' | 'storniert';
+  status: 'anfrage' | 'optioniert' | 'reserviert' | 'bestaetigt' | 'eingecheckt' | 'ausgecheckt' | 'storniert';
   grundpreis: number;
   services_preis: number;
   rabatt_preis: number;
@@ -83,6 +94,9 @@ export interface Booking {
   rechnung_versendet_am?: string | null;
   rechnung_versendet_an?: string | null;
   ist_stiftungsfall: boolean;
+  // Kurtaxe: Geschäftsreise (befreit) und gespeicherter Kurtaxe-Betrag
+  geschaeftsreise?: boolean | null;
+  kurtaxe?: number | null;
   payment_recipient_id?: number | null;
   // Putzplan: Alternatives Checkout-Datum (optional, falls abweichend)
   putzplan_checkout_date?: string | null;
@@ -95,11 +109,411 @@ export interface Booking {
   ist_dpolg_mitglied?: boolean;
   // Gast-Guthaben (für Rechnungen)
   credit_used?: number | null;
+  // Gruppenbuchung (mehrere Zimmer eines Organisators)
+  booking_group_id?: number | null;
+  // Aus einem Kontingent abgerufen (allotments)
+  allotment_id?: number | null;
+  // Option: Zimmer vorgemerkt bis zu diesem Zeitpunkt (Status 'optioniert')
+  option_expires_at?: string | null;
+  zimmer_fixiert?: boolean | null; // Zimmer zugesagt - wird vom Optimierer nicht verschoben
+  split_parent_id?: number | null; // Zimmerwechsel: späterer Abschnitt, zeigt auf den ersten Abschnitt
+  series_id?: number | null; // Termin einer Serienbuchung
+  // Zahlungen (aus dem Zahlungsjournal abgeleitet)
+  bezahlter_betrag?: number | null;
+  offener_betrag?: number | null;
+  payment_status?: 'ausstehend' | 'teilweise_bezahlt' | 'bezahlt' | null;
   // Services und Discounts für TapeChart Emoji-Anzeige
   services?: AdditionalService[];
   discounts?: DiscountTemplate[];
 }
 
+// Gruppenbuchung: mehrere Zimmer eines Organisators, eine gemeinsame Rechnung
+export interface BookingGroup {
+  id: number;
+  name: string;
+  reservierungsnummer: string;
+  organiserGuestId: number;
+  paymentRecipientId?: number | null;
+  bemerkungen?: string | null;
+  createdAt?: string | null;
+  updatedAt?: string | null;
+  createdBy?: string | null;
+}
+
+export interface BookingGroupRoom {
+  booking: Booking;
+  roomName: string;
+  guestNames: string[];
+}
+
+export interface BookingGroupWithRooms extends BookingGroup {
+  rooms: BookingGroupRoom[];
+}
+
+// Kontingent: pro Nacht für eine Partnerorganisation freigehaltene Zimmer
+export interface Allotment {
+  id: number;
+  name: string;
+  guestId?: number | null;
+  gebaeudeTyp?: string | null; // Zimmertyp, oder null bei fester Zimmerauswahl
+  roomIds: number[];
+  startDate: string;
+  endDate: string;
+  quantity: number;
+  releaseDate: string; // Freigabefrist
+  status: 'aktiv' | 'freigegeben';
+  releasedAt?: string | null;
+  bemerkungen?: string | null;
+  createdAt?: string;
+  updatedAt?: string;
+  createdBy?: string | null;
+}
+
+// Kontingent-Auslastung einer Nacht (TapeChart)
+export interface AllotmentNight {
+  allotmentId: number;
+  name: string;
+  date: string;
+  quantity: number;
+  drawn: number;
+  open: number;
+}
+
+// Wartelisten-Eintrag: Gast wartet auf ein frei werdendes Zimmer
+export interface WaitlistEntry {
+  id: number;
+  guestId: number;
+  guestName: string;
+  checkinDate: string;
+  checkoutDate: string;
+  anzahlGaeste: number;
+  roomIds: number[]; // akzeptierte Zimmer (leer = beliebig)
+  orte: string[]; // akzeptierte Orte (leer = beliebig)
+  status: 'wartend' | 'angeboten' | 'gebucht' | 'storniert';
+  offeredRoomId?: number | null;
+  offeredAt?: string | null;
+  offerEmailId?: number | null;
+  bemerkungen?: string | null;
+  createdAt?: string;
+  updatedAt?: string;
+  createdBy?: string | null;
+}
+
+// Zimmersperrung (Wartung, Renovierung, außer Betrieb) - endDate exklusiv wie checkout_date
+export interface RoomBlock {
+  id: number;
+  roomId: number;
+  roomName: string;
+  startDate: string;
+  endDate: string;
+  reason: string;
+  bemerkungen?: string | null;
+  createdAt?: string;
+  updatedAt?: string;
+  createdBy?: string | null;
+}
+
+// Zimmersuche: Suchkriterien und bewertete freie Zimmer mit Preis
+export interface RoomSearchCriteria {
+  checkin: string;
+  checkout: string;
+  anzahlGaeste: number;
+  ort?: string | null;
+  gebaeudeTyp?: string | null;
+  hund?: boolean;
+  erdgeschoss?: boolean;
+}
+
+export interface RoomSuggestion {
+  room: Room;
+  roomId: number;
+  score: number;
+  gapBefore?: number | null;
+  gapAfter?: number | null;
+  hinweise: string[];
+  totalPrice: number;
+  pricePerNight: number;
+}
+
+// Zimmer-Optimierer: Umbuchungsvorschlag zum Schließen kurzer Lücken
+export interface RoomMove {
+  bookingId: number;
+  fromRoomId: number;
+  toRoomId: number;
+}
+
+export interface AssignmentPlan {
+  moves: RoomMove[];
+  shortGapNightsBefore: number;
+  shortGapNightsAfter: number;
+}
+
+// Zimmerwechsel: Abschnitt eines geteilten Aufenthalts (Abreise = Anreise des nächsten Abschnitts)
+export interface StaySegment {
+  roomId: number;
+  checkinDate: string;
+  checkoutDate: string;
+}
+
+// Serienbuchung: wiederkehrender Aufenthalt (z. B. monatliches Seminar), eine Buchung pro Termin und Zimmer
+export interface RecurrenceRule {
+  frequency: 'woechentlich' | 'monatlich';
+  interval?: number; // jede n-te Woche / jeden n-ten Monat
+  count?: number | null; // Anzahl der Termine
+  until?: string | null; // letzte mögliche Anreise
+}
+
+export interface BookingSeries {
+  id: number;
+  name: string;
+  reservierungsnummer: string;
+  guestId: number;
+  paymentRecipientId?: number | null;
+  frequency: 'woechentlich' | 'monatlich';
+  intervalCount: number;
+  firstCheckin: string;
+  nights: number;
+  occurrences?: number | null;
+  untilDate?: string | null;
+  anzahlGaeste: number;
+  roomIds: number[];
+  bemerkungen?: string | null;
+  createdAt?: string | null;
+  updatedAt?: string | null;
+  createdBy?: string | null;
+}
+
+// Termin, der nicht gebucht werden konnte (Konfliktbericht)
+export interface SeriesConflict {
+  occurrence: number;
+  roomId: number;
+  checkinDate: string;
+  checkoutDate: string;
+  reason: string;
+}
+
+export interface BookingSeriesWithBookings extends BookingSeries {
+  bookings: Booking[];
+  conflicts: SeriesConflict[];
+}
+
+// Rechnungsposition (Brutto) aus dem unveränderlichen Rechnungs-Snapshot
+export interface InvoiceLine {
+  position: number;
+  description: string;
+  details: string;
+  quantity: number;
+  quantityLabel: string;
+  unitPrice: number;
+  total: number;
+  taxCategory: string;
+  taxRate: number;
+}
+
+// Ausgestellte Rechnung oder Gutschrift (Stornorechnung / Rechnungskorrektur, negative Beträge)
+export interface IssuedInvoice {
+  id: number;
+  documentType: 'rechnung' | 'gutschrift';
+  invoiceNumber: string;
+  bookingId: number;
+  bookingGroupId?: number | null;
+  issueDate: string;
+  total: number;
+  amountDue: number;
+  document: { lines: InvoiceLine[] };
+  correctsInvoiceId?: number | null; // Gutschrift: korrigierte Rechnung
+  isCancellation: boolean; // Gutschrift storniert die ganze Rechnung
+  reason?: string | null;
+  pdfPath?: string | null;
+  createdAt?: string | null;
+  createdBy?: string | null;
+}
+
+// Zahlung (oder Erstattung, negativer Betrag) einer Buchung
+export interface Payment {
+  id: number;
+  bookingId: number;
+  amount: number;
+  paymentDate: string;
+  method?: string | null;
+  reference?: string | null;
+  notes?: string | null;
+  recordedBy?: string | null;
+  createdAt?: string | null;
+  reversalOf?: number | null; // Storno: ID der stornierten Zahlung
+}
+
+// Angeforderte Anzahlung mit dem bereits durch Zahlungen gedeckten Teil
+export interface DepositRequest {
+  id: number;
+  bookingId: number;
+  description: string;
+  amount: number;
+  percentage?: number | null;
+  dueDate: string;
+  createdAt?: string | null;
+  createdBy?: string | null;
+  covered: number;
+  state: 'offen' | 'ueberfaellig' | 'bezahlt';
+}
+
+export interface BookingBalance {
+  bookingId: number;
+  totalDue: number; // Gesamtpreis + Kurtaxe - verrechnetes Guthaben
+  paid: number;
+  outstanding: number;
+  paymentStatus: 'ausstehend' | 'teilweise_bezahlt' | 'bezahlt';
+}
+
+export interface BookingPayments extends BookingBalance {
+  payments: Payment[];
+  deposits: DepositRequest[];
+}
+
+// Kontoauszug-Import (CAMT.053 / MT940): Zahlungseingang mit Zuordnungsvorschlägen
+export interface MatchProposal {
+  bookingId: number;
+  reservierungsnummer: string;
+  confidence: number; // 0-100
+  reasons: string[];
+}
+
+export interface ImportedBankTransaction {
+  id: number;
+  bookingDate: string;
+  amount: number;
+  payerName?: string | null;
+  payerIban?: string | null;
+  remittance: string;
+  bankReference?: string | null;
+  fileName?: string | null;
+  status: 'offen' | 'zugeordnet' | 'ignoriert';
+  bookingId?: number | null;
+  paymentId?: number | null;
+  confidence?: number | null;
+  importedAt?: string | null;
+  matchedAt?: string | null;
+  matchedBy?: string | null;
+  proposals: MatchProposal[];
+}
+
+export interface BankImportResult {
+  imported: number;
+  duplicates: number;
+  skippedDebits: number;
+  transactions: ImportedBankTransaction[];
+}
+
+// SEPA-Lastschrift (pain.008)
+export interface SepaDueDebit {
+  bookingId: number;
+  reservierungsnummer: string;
+  guestId: number;
+  guestName: string;
+  checkoutDate: string;
+  amount: number;
+  mandateReference: string;
+  sequenceType: 'FRST' | 'RCUR' | 'OOFF' | null;
+  problem: string | null; // Grund, warum die Buchung nicht exportiert wird
+}
+
+export interface SepaDuePreview {
+  collectionDate: string;
+  earliestCollectionDate: string;
+  debits: SepaDueDebit[];
+}
+
+export interface SepaBatch {
+  id: number;
+  messageId: string;
+  collectionDate: string;
+  transactionCount: number;
+  total: number;
+  filePath: string | null;
+  createdAt: string | null;
+  createdBy: string | null;
+}
+
+export interface SepaCollection {
+  id: number;
+  batchId: number;
+  bookingId: number;
+  reservierungsnummer: string;
+  guestId: number;
+  guestName: string;
+  amount: number;
+  mandateReference: string;
+  sequenceType: 'FRST' | 'RCUR' | 'OOFF';
+  endToEndId: string;
+  collectionDate: string;
+  status: 'ausstehend' | 'eingezogen' | 'zurueckgegeben';
+  paymentId: number | null;
+  returnReason: string | null;
+  returnReasonText: string | null;
+  returnedAt: string | null;
+}
+
+// Mahnstufe (Zahlungserinnerung, 1. Mahnung, ...) in Reihenfolge der Eskalation
+export interface DunningStage {
+  id: number;
+  stage: number;
+  name: string;
+  templateName: string;
+  fristTage: number;
+  gebuehr: number;
+  updatedAt: string | null;
+}
+
+// Schritt des Mahnverfahrens einer Buchung
+export interface DunningEntry {
+  id: number;
+  bookingId: number;
+  event: 'mahnung' | 'uebergabe';
+  stage: number | null;
+  stageName: string | null;
+  templateName: string | null;
+  outstanding: number;
+  gebuehr: number;
+  zahlungsfrist: string | null;
+  serviceId: number | null;
+  reminderId: number | null;
+  emailStatus: 'pending' | 'sent' | 'failed' | 'cancelled' | null;
+  createdAt: string | null;
+}
+
+// Gutschein (übertragbar, einlösbar gegen jede Buchung)
+export interface Voucher {
+  id: number;
+  code: string;
+  value: number;
+  remaining: number;
+  issuedAt: string;
+  expiresAt: string;
+  purchaserGuestId: number | null;
+  purchaserName: string | null;
+  recipientName: string | null;
+  message: string | null;
+  notes: string | null;
+  status: 'aktiv' | 'eingeloest' | 'abgelaufen' | 'storniert';
+  cancelledAt: string | null;
+  createdAt: string | null;
+  createdBy: string | null;
+}
+
+// Einlösung eines Gutscheins (Zahlung 'Gutschein' in der Buchung)
+export interface VoucherRedemption {
+  id: number;
+  voucherId: number;
+  code: string;
+  bookingId: number;
+  reservierungsnummer: string;
+  guestName: string;
+  amount: number;
+  paymentId: number;
+  createdAt: string | null;
+  createdBy: string | null;
+}
+
 // Additional Service (gebuchter Service mit berechneten Werten)
 export interface AdditionalService {
   id: number;